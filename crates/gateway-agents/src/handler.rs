@@ -330,6 +330,7 @@ mod tests {
                 max_context_length: None,
                 max_output_tokens: None,
                 parallel_tool_calls: false,
+                audio_output: false,
             };
             &CAPS
         }
@@ -1,43 +1,183 @@
 //! Concurrency handling benchmark adapter.
 //!
-//! Measures the performance of concurrent request handling
-//! including connection pooling and request multiplexing.
+//! Holds a fixed number of requests in flight against a mock provider for a
+//! set duration, mixing streaming and non-streaming requests, and reports
+//! throughput, latency percentiles and error rate under that sustained load.
+//!
+//! The load shape is read from the environment, so CI can track regressions
+//! at a fixed shape:
+//!
+//! | Variable | Default | Meaning |
+//! |----------|---------|---------|
+//! | `GATEWAY_BENCH_CONCURRENCY` | `100` | Requests in flight at once |
+//! | `GATEWAY_BENCH_STREAMING_RATIO` | `0.25` | Fraction of requests that stream, 0 to 1 |
+//! | `GATEWAY_BENCH_DURATION_MS` | `1000` | How long to sustain the load |
+//! | `GATEWAY_BENCH_PROVIDER_LATENCY_MS` | `1` | Mock provider latency per request |
+//! | `GATEWAY_BENCH_ERROR_RATE` | `0` | Fraction of mock provider requests that fail, 0 to 1 |
 
 use super::BenchTarget;
 use crate::BenchmarkResult;
-use anyhow::Result;
+use anyhow::{bail, Context as _, Result};
 use async_trait::async_trait;
-use std::sync::atomic::{AtomicU64, Ordering};
+use futures::stream::{BoxStream, StreamExt};
+use gateway_core::{
+    ChatChunk, ChatMessage, Choice, ChunkChoice, FinishReason, GatewayError, GatewayRequest,
+    GatewayResponse, HealthStatus, LLMProvider, ModelInfo, ProviderCapabilities, ProviderType,
+    Usage,
+};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
+
+/// Model requested from the mock provider
+const MOCK_MODEL: &str = "mock-model";
+
+/// Load shape for [`ConcurrencyBenchmark`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConcurrencyConfig {
+    /// Requests kept in flight at once.
+    pub concurrency: u32,
+    /// Fraction of requests that stream, from 0.0 to 1.0.
+    pub streaming_ratio: f64,
+    /// How long the load is sustained.
+    pub duration: Duration,
+    /// Mock provider latency per request, before the first chunk when streaming.
+    pub provider_latency: Duration,
+    /// Chunks in each streamed mock response.
+    pub chunks_per_stream: u32,
+    /// Fraction of mock provider requests that fail, from 0.0 to 1.0.
+    pub error_rate: f64,
+}
+
+impl Default for ConcurrencyConfig {
+    fn default() -> Self {
+        Self {
+            concurrency: 100,
+            streaming_ratio: 0.25,
+            duration: Duration::from_millis(1000),
+            provider_latency: Duration::from_millis(1),
+            chunks_per_stream: 8,
+            error_rate: 0.0,
+        }
+    }
+}
+
+impl ConcurrencyConfig {
+    /// Read the load shape from `GATEWAY_BENCH_*` variables, defaulting
+    /// any that are unset.
+    ///
+    /// # Errors
+    /// Returns an error naming the variable if a value is not valid.
+    pub fn from_env() -> Result<Self> {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        fn parse<T: FromStr>(
+            lookup: &impl Fn(&str) -> Option<String>,
+            name: &str,
+            default: T,
+        ) -> Result<T>
+        where
+            T::Err: std::error::Error + Send + Sync + 'static,
+        {
+            match lookup(name) {
+                Some(value) => value
+                    .trim()
+                    .parse()
+                    .with_context(|| format!("invalid {name} '{value}'")),
+                None => Ok(default),
+            }
+        }
+
+        let defaults = Self::default();
+        let config = Self {
+            concurrency: parse(&lookup, "GATEWAY_BENCH_CONCURRENCY", defaults.concurrency)?,
+            streaming_ratio: parse(
+                &lookup,
+                "GATEWAY_BENCH_STREAMING_RATIO",
+                defaults.streaming_ratio,
+            )?,
+            duration: Duration::from_millis(parse(
+                &lookup,
+                "GATEWAY_BENCH_DURATION_MS",
+                defaults.duration.as_millis() as u64,
+            )?),
+            provider_latency: Duration::from_millis(parse(
+                &lookup,
+                "GATEWAY_BENCH_PROVIDER_LATENCY_MS",
+                defaults.provider_latency.as_millis() as u64,
+            )?),
+            chunks_per_stream: defaults.chunks_per_stream,
+            error_rate: parse(&lookup, "GATEWAY_BENCH_ERROR_RATE", defaults.error_rate)?,
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Check that the shape can be run.
+    ///
+    /// # Errors
+    /// Returns an error describing the first invalid setting.
+    pub fn validate(&self) -> Result<()> {
+        if self.concurrency == 0 {
+            bail!("concurrency must be at least 1");
+        }
+        if !(0.0..=1.0).contains(&self.streaming_ratio) {
+            bail!("streaming ratio must be between 0 and 1, got {}", self.streaming_ratio);
+        }
+        if !(0.0..=1.0).contains(&self.error_rate) {
+            bail!("error rate must be between 0 and 1, got {}", self.error_rate);
+        }
+        if self.duration.is_zero() {
+            bail!("duration must be greater than zero");
+        }
+        Ok(())
+    }
+}
 
 /// Benchmark for concurrent request handling.
 ///
-/// This benchmark measures:
-/// - Concurrent request processing throughput
-/// - Lock contention overhead
-/// - Connection pool efficiency
+/// This benchmark measures, under sustained load:
+/// - Throughput of completed requests
+/// - Latency percentiles of successful requests, to the last chunk when streaming
+/// - Error rate
+///
+/// Each of `concurrency` workers sends its next request as soon as the last
+/// one completes, so exactly that many requests are in flight until the
+/// duration elapses.
 pub struct ConcurrencyBenchmark {
-    iterations: u32,
-    concurrency: u32,
+    config: Result<ConcurrencyConfig, String>,
+    provider: Option<Arc<dyn LLMProvider>>,
 }
 
 impl ConcurrencyBenchmark {
-    /// Create a new concurrency benchmark.
+    /// Create a benchmark with the load shape from the environment.
+    ///
+    /// An invalid shape is reported when the benchmark runs.
     pub fn new() -> Self {
         Self {
-            iterations: 1000,
-            concurrency: 100,
+            config: ConcurrencyConfig::from_env().map_err(|e| format!("{e:#}")),
+            provider: None,
         }
     }
 
-    /// Create with custom parameters.
-    pub fn with_params(iterations: u32, concurrency: u32) -> Self {
+    /// Create a benchmark with the given load shape.
+    pub fn with_config(config: ConcurrencyConfig) -> Self {
         Self {
-            iterations,
-            concurrency,
+            config: Ok(config),
+            provider: None,
         }
     }
+
+    /// Run the load against `provider` instead of the built-in mock.
+    ///
+    /// The provider must serve `mock-model`.
+    pub fn with_provider(mut self, provider: Arc<dyn LLMProvider>) -> Self {
+        self.provider = Some(provider);
+        self
+    }
 }
 
 impl Default for ConcurrencyBenchmark {
@@ -46,6 +186,12 @@ impl Default for ConcurrencyBenchmark {
     }
 }
 
+/// Outcome of one request.
+struct Sample {
+    streaming: bool,
+    latency_ms: Option<f64>,
+}
+
 #[async_trait]
 impl BenchTarget for ConcurrencyBenchmark {
     fn id(&self) -> &str {
@@ -53,138 +199,351 @@ impl BenchTarget for ConcurrencyBenchmark {
     }
 
     fn description(&self) -> &str {
-        "Measures concurrent request handling performance and lock contention"
-    }
-
-    fn iterations(&self) -> u32 {
-        self.iterations
+        "Measures throughput, latency and error rate under sustained concurrent load"
     }
 
     async fn run(&self) -> Result<BenchmarkResult> {
-        // Shared state for measuring contention
-        let counter = Arc::new(AtomicU64::new(0));
-        let completed = Arc::new(AtomicU64::new(0));
+        let config = match &self.config {
+            Ok(config) => config.clone(),
+            Err(e) => bail!("{e}"),
+        };
+        config.validate()?;
 
-        // Warmup with reduced concurrency
-        let warmup_tasks: Vec<_> = (0..self.warmup_iterations())
-            .map(|_| {
-                let counter = Arc::clone(&counter);
-                tokio::spawn(async move {
-                    simulate_concurrent_request(&counter).await;
-                })
-            })
-            .collect();
+        let mock = Arc::new(MockLoadProvider::new(&config));
+        let provider = self
+            .provider
+            .clone()
+            .unwrap_or_else(|| Arc::clone(&mock) as Arc<dyn LLMProvider>);
+        let request = GatewayRequest::builder()
+            .model(MOCK_MODEL)
+            .message(ChatMessage::user("Reply with the single word: pong"))
+            .build()?;
+        let streaming_request = GatewayRequest {
+            stream: true,
+            ..request.clone()
+        };
 
-        for task in warmup_tasks {
-            let _ = task.await;
+        // Warmup (not counted)
+        for _ in 0..self.warmup_iterations() {
+            let _ = provider.chat_completion(&request).await;
         }
 
-        // Reset counters
-        counter.store(0, Ordering::SeqCst);
-        completed.store(0, Ordering::SeqCst);
-
-        // Benchmark phase with full concurrency
+        let sequence = Arc::new(AtomicU64::new(0));
         let start = Instant::now();
-        let mut latencies = Vec::with_capacity(self.iterations as usize);
-
-        // Process in batches to control concurrency level
-        let batches = (self.iterations + self.concurrency - 1) / self.concurrency;
-
-        for batch in 0..batches {
-            let batch_size = std::cmp::min(
-                self.concurrency,
-                self.iterations - batch * self.concurrency,
-            );
-
-            let batch_start = Instant::now();
-
-            let tasks: Vec<_> = (0..batch_size)
-                .map(|_| {
-                    let counter = Arc::clone(&counter);
-                    let completed = Arc::clone(&completed);
-                    tokio::spawn(async move {
-                        let task_start = Instant::now();
-                        simulate_concurrent_request(&counter).await;
-                        completed.fetch_add(1, Ordering::Relaxed);
-                        task_start.elapsed().as_nanos() as f64 / 1_000_000.0
-                    })
-                })
-                .collect();
+        let deadline = start + config.duration;
 
-            for task in tasks {
-                if let Ok(latency) = task.await {
-                    latencies.push(latency);
-                }
-            }
+        let workers: Vec<_> = (0..config.concurrency)
+            .map(|_| {
+                let provider = Arc::clone(&provider);
+                let request = request.clone();
+                let streaming_request = streaming_request.clone();
+                let sequence = Arc::clone(&sequence);
+                let streaming_ratio = config.streaming_ratio;
+                tokio::spawn(async move {
+                    let mut samples = Vec::new();
+                    while Instant::now() < deadline {
+                        let n = sequence.fetch_add(1, Ordering::Relaxed);
+                        let streaming = spread(n, streaming_ratio);
+                        let request_start = Instant::now();
+                        let ok = if streaming {
+                            drain_stream(provider.as_ref(), &streaming_request).await
+                        } else {
+                            provider.chat_completion(&request).await.is_ok()
+                        };
+                        let latency_ms = request_start.elapsed().as_nanos() as f64 / 1_000_000.0;
+                        samples.push(Sample {
+                            streaming,
+                            latency_ms: ok.then_some(latency_ms),
+                        });
+                    }
+                    samples
+                })
+            })
+            .collect();
 
-            let _batch_duration = batch_start.elapsed();
+        let mut samples = Vec::new();
+        for worker in workers {
+            samples.extend(worker.await?);
         }
+        let elapsed = start.elapsed();
 
-        let total_duration = start.elapsed();
-        let total_completed = completed.load(Ordering::SeqCst);
-
-        // Statistics
+        let total = samples.len();
+        let streaming = samples.iter().filter(|s| s.streaming).count();
+        let mut latencies: Vec<f64> = samples.iter().filter_map(|s| s.latency_ms).collect();
+        let failed = total - latencies.len();
         latencies.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
 
-        let sum: f64 = latencies.iter().sum();
-        let avg_ms = if !latencies.is_empty() {
-            sum / latencies.len() as f64
+        let avg_ms = if latencies.is_empty() {
+            0.0
         } else {
+            latencies.iter().sum::<f64>() / latencies.len() as f64
+        };
+        let percentile = |p: f64| {
+            let idx = (latencies.len() as f64 * p) as usize;
+            latencies
+                .get(idx.min(latencies.len().saturating_sub(1)))
+                .copied()
+                .unwrap_or(0.0)
+        };
+        let error_rate = if total == 0 {
             0.0
+        } else {
+            failed as f64 / total as f64
+        };
+        let peak_in_flight = if self.provider.is_some() {
+            None
+        } else {
+            Some(mock.peak_in_flight.load(Ordering::SeqCst))
         };
-        let min_ms = latencies.first().copied().unwrap_or(0.0);
-        let max_ms = latencies.last().copied().unwrap_or(0.0);
-
-        let p50_idx = latencies.len() / 2;
-        let p90_idx = (latencies.len() as f64 * 0.90) as usize;
-        let p95_idx = (latencies.len() as f64 * 0.95) as usize;
-        let p99_idx = (latencies.len() as f64 * 0.99) as usize;
-
-        let throughput_rps = total_completed as f64 / total_duration.as_secs_f64();
 
         Ok(BenchmarkResult::new(
             self.id(),
             serde_json::json!({
-                "iterations": self.iterations,
-                "concurrency": self.concurrency,
+                "concurrency": config.concurrency,
+                "streaming_ratio": config.streaming_ratio,
+                "duration_ms": config.duration.as_millis(),
+                "total_requests": total,
+                "streaming_requests": streaming,
+                "non_streaming_requests": total - streaming,
+                "failed_requests": failed,
+                "error_rate": error_rate,
+                "throughput_rps": total as f64 / elapsed.as_secs_f64(),
                 "latency_ms": avg_ms,
-                "min_ms": min_ms,
-                "max_ms": max_ms,
-                "p50_ms": latencies.get(p50_idx).copied().unwrap_or(0.0),
-                "p90_ms": latencies.get(p90_idx).copied().unwrap_or(0.0),
-                "p95_ms": latencies.get(p95_idx).copied().unwrap_or(0.0),
-                "p99_ms": latencies.get(p99_idx).copied().unwrap_or(0.0),
-                "throughput_rps": throughput_rps,
-                "total_completed": total_completed,
-                "total_duration_ms": total_duration.as_millis(),
+                "min_ms": latencies.first().copied().unwrap_or(0.0),
+                "max_ms": latencies.last().copied().unwrap_or(0.0),
+                "p50_ms": percentile(0.50),
+                "p90_ms": percentile(0.90),
+                "p95_ms": percentile(0.95),
+                "p99_ms": percentile(0.99),
+                "peak_in_flight": peak_in_flight,
                 "description": self.description()
             }),
         ))
     }
 }
 
-/// Simulate a concurrent request with shared state access.
-async fn simulate_concurrent_request(counter: &AtomicU64) {
-    // Simulate request processing with atomic counter access
-    counter.fetch_add(1, Ordering::Relaxed);
+/// Whether the `n`th request falls in a `ratio` share spread evenly over
+/// the sequence, e.g. every fourth request for 0.25.
+fn spread(n: u64, ratio: f64) -> bool {
+    ((n + 1) as f64 * ratio).floor() > (n as f64 * ratio).floor()
+}
 
-    // Simulate some async work
-    tokio::task::yield_now().await;
+/// Read a stream to its end, returning whether it completed without error.
+async fn drain_stream(provider: &dyn LLMProvider, request: &GatewayRequest) -> bool {
+    let Ok(mut stream) = provider.chat_completion_stream(request).await else {
+        return false;
+    };
+    while let Some(chunk) = stream.next().await {
+        if chunk.is_err() {
+            return false;
+        }
+    }
+    true
+}
 
-    // Simulate response handling
-    let _count = counter.load(Ordering::Relaxed);
+/// Provider answering after a fixed latency, failing a set share of
+/// requests, and recording how many requests it served at once.
+struct MockLoadProvider {
+    latency: Duration,
+    chunks_per_stream: u32,
+    error_rate: f64,
+    models: Vec<ModelInfo>,
+    requests: AtomicU64,
+    in_flight: AtomicU32,
+    peak_in_flight: AtomicU32,
+}
+
+impl MockLoadProvider {
+    fn new(config: &ConcurrencyConfig) -> Self {
+        Self {
+            latency: config.provider_latency,
+            chunks_per_stream: config.chunks_per_stream,
+            error_rate: config.error_rate,
+            models: vec![ModelInfo::new(MOCK_MODEL)],
+            requests: AtomicU64::new(0),
+            in_flight: AtomicU32::new(0),
+            peak_in_flight: AtomicU32::new(0),
+        }
+    }
+
+    /// Wait out the latency, then decide whether this request fails.
+    async fn respond(&self) -> Result<(), GatewayError> {
+        let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        self.peak_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+        tokio::time::sleep(self.latency).await;
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+        let n = self.requests.fetch_add(1, Ordering::Relaxed);
+        if spread(n, self.error_rate) {
+            Err(GatewayError::provider("mock", "injected failure", Some(503), true))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[async_trait]
+impl LLMProvider for MockLoadProvider {
+    fn id(&self) -> &str {
+        "mock"
+    }
+
+    fn provider_type(&self) -> ProviderType {
+        ProviderType::Custom
+    }
+
+    async fn chat_completion(
+        &self,
+        request: &GatewayRequest,
+    ) -> Result<GatewayResponse, GatewayError> {
+        self.respond().await?;
+        Ok(GatewayResponse::builder()
+            .model(request.model.clone())
+            .choice(Choice::new(0, "pong", FinishReason::Stop))
+            .usage(Usage::new(10, 1))
+            .build())
+    }
+
+    async fn chat_completion_stream(
+        &self,
+        request: &GatewayRequest,
+    ) -> Result<BoxStream<'static, Result<ChatChunk, GatewayError>>, GatewayError> {
+        self.respond().await?;
+        let model = request.model.clone();
+        let chunks = self.chunks_per_stream;
+        let stream = futures::stream::iter(0..=chunks).then(move |i| {
+            let model = model.clone();
+            async move {
+                tokio::task::yield_now().await;
+                let choice = if i == chunks {
+                    ChunkChoice::with_finish(0, FinishReason::Stop)
+                } else {
+                    ChunkChoice::with_content(0, "po")
+                };
+                Ok(ChatChunk::builder().model(model).choice(choice).build())
+            }
+        });
+        Ok(stream.boxed())
+    }
+
+    async fn health_check(&self) -> HealthStatus {
+        HealthStatus::Healthy
+    }
+
+    fn capabilities(&self) -> &ProviderCapabilities {
+        static CAPS: ProviderCapabilities = ProviderCapabilities {
+            chat: true,
+            streaming: true,
+            function_calling: false,
+            vision: false,
+            embeddings: false,
+            json_mode: false,
+            seed: false,
+            logprobs: false,
+            max_context_length: None,
+            max_output_tokens: None,
+            parallel_tool_calls: false,
+            audio_output: false,
+        };
+        &CAPS
+    }
+
+    fn models(&self) -> &[ModelInfo] {
+        &self.models
+    }
+
+    fn base_url(&self) -> &str {
+        "http://localhost"
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashMap;
+
+    fn quick_config(concurrency: u32) -> ConcurrencyConfig {
+        ConcurrencyConfig {
+            concurrency,
+            streaming_ratio: 0.5,
+            duration: Duration::from_millis(200),
+            provider_latency: Duration::from_millis(5),
+            chunks_per_stream: 4,
+            error_rate: 0.1,
+        }
+    }
 
     #[tokio::test]
-    async fn test_concurrency_benchmark() {
-        let benchmark = ConcurrencyBenchmark::with_params(100, 10);
+    async fn test_concurrency_benchmark_honors_concurrency() {
+        let benchmark = ConcurrencyBenchmark::with_config(quick_config(8));
         let result = benchmark.run().await.expect("Benchmark should succeed");
+        let metrics = &result.metrics;
 
         assert_eq!(result.target_id, "concurrency_handling");
-        assert!(result.throughput_rps().is_some());
+        for key in [
+            "concurrency",
+            "streaming_ratio",
+            "duration_ms",
+            "total_requests",
+            "streaming_requests",
+            "non_streaming_requests",
+            "failed_requests",
+            "error_rate",
+            "throughput_rps",
+            "latency_ms",
+            "p50_ms",
+            "p90_ms",
+            "p95_ms",
+            "p99_ms",
+            "peak_in_flight",
+        ] {
+            assert!(metrics[key].is_number(), "missing metric {key}");
+        }
+
+        assert_eq!(metrics["concurrency"], 8);
+        assert_eq!(metrics["peak_in_flight"], 8);
+        assert!(result.throughput_rps().expect("throughput") > 0.0);
+
+        let total = metrics["total_requests"].as_u64().expect("total");
+        let streaming = metrics["streaming_requests"].as_u64().expect("streaming");
+        assert!(total >= 8);
+        assert!(streaming.abs_diff(total / 2) <= 1);
+        let error_rate = metrics["error_rate"].as_f64().expect("error rate");
+        assert!((error_rate - 0.1).abs() < 0.05, "error rate {error_rate}");
+    }
+
+    #[test]
+    fn test_config_from_environment() {
+        let vars: HashMap<&str, &str> = [
+            ("GATEWAY_BENCH_CONCURRENCY", "16"),
+            ("GATEWAY_BENCH_STREAMING_RATIO", "1"),
+            ("GATEWAY_BENCH_DURATION_MS", "250"),
+        ]
+        .into();
+        let config = ConcurrencyConfig::from_lookup(|name| vars.get(name).map(|v| v.to_string()))
+            .expect("valid config");
+        assert_eq!(config.concurrency, 16);
+        assert_eq!(config.streaming_ratio, 1.0);
+        assert_eq!(config.duration, Duration::from_millis(250));
+        assert_eq!(config.error_rate, 0.0);
+
+        let err = ConcurrencyConfig::from_lookup(|name| {
+            (name == "GATEWAY_BENCH_CONCURRENCY").then(|| "many".to_string())
+        })
+        .unwrap_err();
+        assert!(err.to_string().contains("GATEWAY_BENCH_CONCURRENCY"));
+        let err = ConcurrencyConfig::from_lookup(|name| {
+            (name == "GATEWAY_BENCH_STREAMING_RATIO").then(|| "1.5".to_string())
+        })
+        .unwrap_err();
+        assert!(err.to_string().contains("streaming ratio"));
+    }
+
+    #[test]
+    fn test_spread_selects_share_evenly() {
+        let picked: Vec<u64> = (0..8).filter(|&n| spread(n, 0.25)).collect();
+        assert_eq!(picked, [3, 7]);
+        assert!((0..100).all(|n| !spread(n, 0.0)));
+        assert!((0..100).all(|n| spread(n, 1.0)));
     }
 }
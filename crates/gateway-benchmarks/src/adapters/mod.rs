@@ -7,6 +7,7 @@ mod backend_routing;
 mod circuit_breaker;
 mod concurrency;
 mod health_check;
+mod provider_comparison;
 mod rate_limiting;
 mod request_transform;
 mod streaming_throughput;
@@ -18,8 +19,9 @@ use async_trait::async_trait;
 
 pub use backend_routing::BackendRoutingBenchmark;
 pub use circuit_breaker::CircuitBreakerBenchmark;
-pub use concurrency::ConcurrencyBenchmark;
+pub use concurrency::{ConcurrencyBenchmark, ConcurrencyConfig};
 pub use health_check::HealthCheckBenchmark;
+pub use provider_comparison::ProviderComparisonBenchmark;
 pub use rate_limiting::RateLimitingBenchmark;
 pub use request_transform::RequestTransformBenchmark;
 pub use streaming_throughput::StreamingThroughputBenchmark;
@@ -103,6 +105,7 @@ pub fn all_targets() -> Vec<Box<dyn BenchTarget>> {
         Box::new(HealthCheckBenchmark::new()),
         Box::new(CircuitBreakerBenchmark::new()),
         Box::new(RateLimitingBenchmark::new()),
+        Box::new(ProviderComparisonBenchmark::new()),
     ]
 }
 
@@ -122,6 +125,7 @@ pub fn list_target_ids() -> Vec<&'static str> {
         "health_check_latency",
         "circuit_breaker",
         "rate_limiting",
+        "provider_comparison",
     ]
 }
 
@@ -0,0 +1,393 @@
+//! Provider comparison benchmark adapter.
+//!
+//! Runs an identical prompt set against each configured provider and
+//! reports per-provider latency, token usage, and estimated cost.
+
+use super::BenchTarget;
+use crate::BenchmarkResult;
+use anyhow::Result;
+use async_trait::async_trait;
+use gateway_core::{ChatMessage, GatewayRequest, LLMProvider};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Default prompt set shared by every provider.
+const DEFAULT_PROMPTS: &[&str] = &[
+    "Reply with the single word: pong",
+    "Summarize in one sentence why the sky appears blue.",
+    "List three prime numbers greater than 100.",
+];
+
+/// A provider under comparison and the model it is asked to serve.
+struct ComparedProvider {
+    provider: Arc<dyn LLMProvider>,
+    model: String,
+}
+
+/// Benchmark comparing providers on identical prompts.
+///
+/// This benchmark measures, per provider:
+/// - Latency percentiles over the prompt set
+/// - Prompt and completion token usage
+/// - Estimated cost from the model's published pricing
+///
+/// Providers without credentials are reported as skipped, and warmup
+/// requests are excluded from all metrics.
+pub struct ProviderComparisonBenchmark {
+    providers: Vec<ComparedProvider>,
+    skipped: Vec<String>,
+    prompts: Vec<String>,
+    iterations: u32,
+    warmup_iterations: u32,
+}
+
+impl ProviderComparisonBenchmark {
+    /// Create a benchmark over the providers with credentials in the environment.
+    pub fn new() -> Self {
+        let mut benchmark = Self::empty();
+
+        match std::env::var("OPENAI_API_KEY") {
+            Ok(key) => {
+                let config = gateway_providers::openai::OpenAIConfig::new("openai", key);
+                match gateway_providers::OpenAIProvider::new(config) {
+                    Ok(provider) => {
+                        benchmark = benchmark.with_provider(Arc::new(provider), "gpt-4o-mini");
+                    }
+                    Err(_) => benchmark.skipped.push("openai".to_string()),
+                }
+            }
+            Err(_) => benchmark.skipped.push("openai".to_string()),
+        }
+
+        match std::env::var("ANTHROPIC_API_KEY") {
+            Ok(key) => {
+                let config = gateway_providers::anthropic::AnthropicConfig::new(key);
+                match gateway_providers::AnthropicProvider::new(config) {
+                    Ok(provider) => {
+                        benchmark = benchmark
+                            .with_provider(Arc::new(provider), "claude-3-5-haiku-20241022");
+                    }
+                    Err(_) => benchmark.skipped.push("anthropic".to_string()),
+                }
+            }
+            Err(_) => benchmark.skipped.push("anthropic".to_string()),
+        }
+
+        benchmark
+    }
+
+    /// Create a benchmark with no providers registered.
+    pub fn empty() -> Self {
+        Self {
+            providers: Vec::new(),
+            skipped: Vec::new(),
+            prompts: DEFAULT_PROMPTS.iter().map(|p| (*p).to_string()).collect(),
+            iterations: 1,
+            warmup_iterations: 1,
+        }
+    }
+
+    /// Add a provider to compare, served with the given model.
+    pub fn with_provider(mut self, provider: Arc<dyn LLMProvider>, model: impl Into<String>) -> Self {
+        self.providers.push(ComparedProvider {
+            provider,
+            model: model.into(),
+        });
+        self
+    }
+
+    /// Replace the prompt set.
+    pub fn with_prompts(mut self, prompts: Vec<String>) -> Self {
+        self.prompts = prompts;
+        self
+    }
+
+    /// Set how many passes over the prompt set each provider runs.
+    pub fn with_iterations(mut self, iterations: u32) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Set how many untimed warmup requests each provider receives.
+    pub fn with_warmup_iterations(mut self, warmup_iterations: u32) -> Self {
+        self.warmup_iterations = warmup_iterations;
+        self
+    }
+
+    async fn run_provider(&self, compared: &ComparedProvider) -> serde_json::Value {
+        let requests: Vec<GatewayRequest> = self
+            .prompts
+            .iter()
+            .filter_map(|prompt| {
+                GatewayRequest::builder()
+                    .model(compared.model.clone())
+                    .message(ChatMessage::user(prompt.clone()))
+                    .build()
+                    .ok()
+            })
+            .collect();
+
+        // Warmup (not counted)
+        if let Some(request) = requests.first() {
+            for _ in 0..self.warmup_iterations {
+                let _ = compared.provider.chat_completion(request).await;
+            }
+        }
+
+        let mut latencies = Vec::with_capacity(requests.len() * self.iterations as usize);
+        let mut errors = 0u64;
+        let mut prompt_tokens = 0u64;
+        let mut completion_tokens = 0u64;
+
+        for _ in 0..self.iterations {
+            for request in &requests {
+                let start = Instant::now();
+                match compared.provider.chat_completion(request).await {
+                    Ok(response) => {
+                        latencies.push(start.elapsed().as_nanos() as f64 / 1_000_000.0);
+                        prompt_tokens += u64::from(response.usage.prompt_tokens);
+                        completion_tokens += u64::from(response.usage.completion_tokens);
+                    }
+                    Err(_) => errors += 1,
+                }
+            }
+        }
+
+        latencies.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+        let avg_ms = if latencies.is_empty() {
+            0.0
+        } else {
+            latencies.iter().sum::<f64>() / latencies.len() as f64
+        };
+        let percentile = |p: f64| {
+            let idx = (latencies.len() as f64 * p) as usize;
+            latencies
+                .get(idx.min(latencies.len().saturating_sub(1)))
+                .copied()
+                .unwrap_or(0.0)
+        };
+
+        let pricing = compared
+            .provider
+            .models()
+            .iter()
+            .find(|m| m.matches(&compared.model))
+            .and_then(|m| Some((m.input_cost_per_1k?, m.output_cost_per_1k?)));
+        let estimated_cost_usd = pricing.map(|(input, output)| {
+            (prompt_tokens as f64 / 1000.0) * input + (completion_tokens as f64 / 1000.0) * output
+        });
+
+        serde_json::json!({
+            "model": compared.model,
+            "requests": latencies.len(),
+            "errors": errors,
+            "latency_ms": avg_ms,
+            "min_ms": latencies.first().copied().unwrap_or(0.0),
+            "max_ms": latencies.last().copied().unwrap_or(0.0),
+            "p50_ms": percentile(0.50),
+            "p90_ms": percentile(0.90),
+            "p95_ms": percentile(0.95),
+            "p99_ms": percentile(0.99),
+            "prompt_tokens": prompt_tokens,
+            "completion_tokens": completion_tokens,
+            "total_tokens": prompt_tokens + completion_tokens,
+            "estimated_cost_usd": estimated_cost_usd,
+        })
+    }
+}
+
+impl Default for ProviderComparisonBenchmark {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl BenchTarget for ProviderComparisonBenchmark {
+    fn id(&self) -> &str {
+        "provider_comparison"
+    }
+
+    fn description(&self) -> &str {
+        "Compares latency, tokens, and estimated cost across providers on identical prompts"
+    }
+
+    fn iterations(&self) -> u32 {
+        self.iterations
+    }
+
+    fn warmup_iterations(&self) -> u32 {
+        self.warmup_iterations
+    }
+
+    async fn run(&self) -> Result<BenchmarkResult> {
+        let mut providers = serde_json::Map::new();
+        let mut avg_latencies = Vec::with_capacity(self.providers.len());
+
+        for compared in &self.providers {
+            let metrics = self.run_provider(compared).await;
+            if let Some(latency) = metrics.get("latency_ms").and_then(|v| v.as_f64()) {
+                avg_latencies.push(latency);
+            }
+            providers.insert(compared.provider.id().to_string(), metrics);
+        }
+
+        let latency_ms = if avg_latencies.is_empty() {
+            0.0
+        } else {
+            avg_latencies.iter().sum::<f64>() / avg_latencies.len() as f64
+        };
+
+        Ok(BenchmarkResult::new(
+            self.id(),
+            serde_json::json!({
+                "iterations": self.iterations,
+                "warmup_iterations": self.warmup_iterations,
+                "prompts": self.prompts.len(),
+                "latency_ms": latency_ms,
+                "providers": providers,
+                "skipped_providers": self.skipped,
+                "description": self.description()
+            }),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::stream::BoxStream;
+    use gateway_core::{
+        ChatChunk, Choice, FinishReason, GatewayError, GatewayResponse, HealthStatus, ModelInfo,
+        ProviderCapabilities, ProviderType, Usage,
+    };
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    struct MockProvider {
+        id: String,
+        models: Vec<ModelInfo>,
+        calls: AtomicU32,
+    }
+
+    impl MockProvider {
+        fn new(id: &str, model: ModelInfo) -> Self {
+            Self {
+                id: id.to_string(),
+                models: vec![model],
+                calls: AtomicU32::new(0),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl LLMProvider for MockProvider {
+        fn id(&self) -> &str {
+            &self.id
+        }
+
+        fn provider_type(&self) -> ProviderType {
+            ProviderType::Custom
+        }
+
+        async fn chat_completion(
+            &self,
+            request: &GatewayRequest,
+        ) -> Result<GatewayResponse, GatewayError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(GatewayResponse::builder()
+                .id("mock")
+                .model(request.model.clone())
+                .choice(Choice::new(0, "pong", FinishReason::Stop))
+                .usage(Usage::new(10, 5))
+                .build())
+        }
+
+        async fn chat_completion_stream(
+            &self,
+            _: &GatewayRequest,
+        ) -> Result<BoxStream<'static, Result<ChatChunk, GatewayError>>, GatewayError> {
+            unimplemented!()
+        }
+
+        async fn health_check(&self) -> HealthStatus {
+            HealthStatus::Healthy
+        }
+
+        fn capabilities(&self) -> &ProviderCapabilities {
+            static CAPS: ProviderCapabilities = ProviderCapabilities {
+                chat: true,
+                streaming: false,
+                function_calling: false,
+                vision: false,
+                embeddings: false,
+                json_mode: false,
+                seed: false,
+                logprobs: false,
+                max_context_length: None,
+                max_output_tokens: None,
+                parallel_tool_calls: false,
+                audio_output: false,
+            };
+            &CAPS
+        }
+
+        fn models(&self) -> &[ModelInfo] {
+            &self.models
+        }
+
+        fn base_url(&self) -> &str {
+            "http://localhost"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_provider_comparison_breakdown() {
+        let fast = Arc::new(MockProvider::new(
+            "mock-fast",
+            ModelInfo::new("fast-model").with_pricing(0.001, 0.002),
+        ));
+        let cheap = Arc::new(MockProvider::new("mock-cheap", ModelInfo::new("cheap-model")));
+
+        let benchmark = ProviderComparisonBenchmark::empty()
+            .with_provider(fast.clone(), "fast-model")
+            .with_provider(cheap.clone(), "cheap-model")
+            .with_prompts(vec!["a".to_string(), "b".to_string()])
+            .with_iterations(3)
+            .with_warmup_iterations(2);
+
+        let result = benchmark.run().await.expect("Benchmark should succeed");
+        assert_eq!(result.target_id, "provider_comparison");
+
+        let providers = &result.metrics["providers"];
+        for id in ["mock-fast", "mock-cheap"] {
+            let breakdown = &providers[id];
+            // Warmup requests are excluded from the measured count
+            assert_eq!(breakdown["requests"], 6);
+            assert_eq!(breakdown["prompt_tokens"], 60);
+            assert_eq!(breakdown["completion_tokens"], 30);
+            assert!(breakdown["p50_ms"].is_number());
+            assert!(breakdown["p99_ms"].is_number());
+        }
+        assert_eq!(fast.calls.load(Ordering::SeqCst), 8);
+        assert_eq!(cheap.calls.load(Ordering::SeqCst), 8);
+
+        let cost = providers["mock-fast"]["estimated_cost_usd"]
+            .as_f64()
+            .expect("priced model has a cost");
+        assert!((cost - (0.06 * 0.001 + 0.03 * 0.002)).abs() < 1e-12);
+        assert!(providers["mock-cheap"]["estimated_cost_usd"].is_null());
+    }
+
+    #[tokio::test]
+    async fn test_providers_without_credentials_are_skipped() {
+        let benchmark = ProviderComparisonBenchmark {
+            skipped: vec!["openai".to_string()],
+            ..ProviderComparisonBenchmark::empty()
+        };
+
+        let result = benchmark.run().await.expect("Benchmark should succeed");
+        assert_eq!(result.metrics["skipped_providers"][0], "openai");
+        assert!(result.metrics["providers"].as_object().expect("object").is_empty());
+    }
+}
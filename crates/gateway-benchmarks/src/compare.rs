@@ -0,0 +1,318 @@
+//! Structured comparison between two benchmark runs.
+//!
+//! Metrics are matched by target and name. Whether a change counts as a
+//! regression depends on the metric: latencies (`*_ms`) should go down,
+//! throughputs (`*_rps`, `*_per_second`) should go up. Other numeric
+//! metrics are reported but never flagged.
+
+use crate::BenchmarkResult;
+use serde::{Deserialize, Serialize};
+
+/// Default relative change (in percent) beyond which a metric is flagged.
+pub const DEFAULT_REGRESSION_THRESHOLD_PCT: f64 = 10.0;
+
+/// Which direction of change is an improvement for a metric.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetricDirection {
+    /// Smaller values are better (latencies, error counts).
+    LowerIsBetter,
+    /// Larger values are better (throughput).
+    HigherIsBetter,
+}
+
+impl MetricDirection {
+    /// Infer the direction from a metric name, if it has one.
+    pub fn for_metric(name: &str) -> Option<Self> {
+        if name.ends_with("_ms")
+            || name.ends_with("_us")
+            || name.contains("error")
+            || name.contains("failure")
+        {
+            Some(Self::LowerIsBetter)
+        } else if name.ends_with("_rps")
+            || name.ends_with("_per_second")
+            || name.starts_with("throughput")
+        {
+            Some(Self::HigherIsBetter)
+        } else {
+            None
+        }
+    }
+}
+
+/// Outcome of comparing a single metric.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeltaStatus {
+    /// Moved in the wrong direction by more than the threshold.
+    Regressed,
+    /// Moved in the right direction by more than the threshold.
+    Improved,
+    /// Changed by no more than the threshold.
+    Unchanged,
+    /// Metric has no known direction, or the baseline is zero.
+    Informational,
+}
+
+/// Change in one metric between the baseline and current runs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricDelta {
+    /// Metric name (e.g. `p99_ms`).
+    pub name: String,
+    /// Value in the baseline run.
+    pub baseline: f64,
+    /// Value in the current run.
+    pub current: f64,
+    /// Absolute change (`current - baseline`).
+    pub delta: f64,
+    /// Relative change in percent, `None` when the baseline is zero.
+    pub delta_pct: Option<f64>,
+    /// Classification against the regression threshold.
+    pub status: DeltaStatus,
+}
+
+impl MetricDelta {
+    /// Compare a metric and classify it against `threshold_pct`.
+    pub fn new(name: impl Into<String>, baseline: f64, current: f64, threshold_pct: f64) -> Self {
+        let name = name.into();
+        let delta = current - baseline;
+        let delta_pct = if baseline == 0.0 {
+            None
+        } else {
+            Some(delta / baseline.abs() * 100.0)
+        };
+
+        let status = match (MetricDirection::for_metric(&name), delta_pct) {
+            (Some(direction), Some(pct)) => {
+                let worse = match direction {
+                    MetricDirection::LowerIsBetter => pct,
+                    MetricDirection::HigherIsBetter => -pct,
+                };
+                if worse > threshold_pct {
+                    DeltaStatus::Regressed
+                } else if worse < -threshold_pct {
+                    DeltaStatus::Improved
+                } else {
+                    DeltaStatus::Unchanged
+                }
+            }
+            _ => DeltaStatus::Informational,
+        };
+
+        Self {
+            name,
+            baseline,
+            current,
+            delta,
+            delta_pct,
+            status,
+        }
+    }
+}
+
+/// Comparison of a target present in both runs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TargetComparison {
+    /// Benchmark target identifier.
+    pub target_id: String,
+    /// Whether the target failed in the baseline run.
+    pub baseline_failed: bool,
+    /// Whether the target failed in the current run.
+    pub current_failed: bool,
+    /// Numeric metrics present in both runs, sorted by name.
+    pub metrics: Vec<MetricDelta>,
+}
+
+impl TargetComparison {
+    /// Whether the target started failing or any metric regressed.
+    pub fn is_regressed(&self) -> bool {
+        (self.current_failed && !self.baseline_failed)
+            || self.metrics.iter().any(|m| m.status == DeltaStatus::Regressed)
+    }
+}
+
+/// Structured diff between a baseline and a current benchmark run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkComparison {
+    /// Relative change (in percent) beyond which a metric is flagged.
+    pub threshold_pct: f64,
+    /// Targets present in both runs, in baseline order.
+    pub targets: Vec<TargetComparison>,
+    /// Targets only present in the current run.
+    pub added: Vec<String>,
+    /// Targets missing from the current run.
+    pub removed: Vec<String>,
+}
+
+impl BenchmarkComparison {
+    /// Compare two runs using [`DEFAULT_REGRESSION_THRESHOLD_PCT`].
+    pub fn new(baseline: &[BenchmarkResult], current: &[BenchmarkResult]) -> Self {
+        Self::with_threshold(baseline, current, DEFAULT_REGRESSION_THRESHOLD_PCT)
+    }
+
+    /// Compare two runs, flagging changes beyond `threshold_pct` percent.
+    pub fn with_threshold(
+        baseline: &[BenchmarkResult],
+        current: &[BenchmarkResult],
+        threshold_pct: f64,
+    ) -> Self {
+        let mut targets = Vec::new();
+        let mut removed = Vec::new();
+
+        for base in baseline {
+            match current.iter().find(|c| c.target_id == base.target_id) {
+                Some(cur) => targets.push(compare_target(base, cur, threshold_pct)),
+                None => removed.push(base.target_id.clone()),
+            }
+        }
+
+        let added = current
+            .iter()
+            .filter(|c| !baseline.iter().any(|b| b.target_id == c.target_id))
+            .map(|c| c.target_id.clone())
+            .collect();
+
+        Self {
+            threshold_pct,
+            targets,
+            added,
+            removed,
+        }
+    }
+
+    /// Whether any target regressed.
+    pub fn has_regressions(&self) -> bool {
+        self.targets.iter().any(TargetComparison::is_regressed)
+    }
+
+    /// All regressed metrics, paired with their target.
+    pub fn regressions(&self) -> impl Iterator<Item = (&str, &MetricDelta)> {
+        self.metrics_with_status(DeltaStatus::Regressed)
+    }
+
+    /// All improved metrics, paired with their target.
+    pub fn improvements(&self) -> impl Iterator<Item = (&str, &MetricDelta)> {
+        self.metrics_with_status(DeltaStatus::Improved)
+    }
+
+    fn metrics_with_status(
+        &self,
+        status: DeltaStatus,
+    ) -> impl Iterator<Item = (&str, &MetricDelta)> {
+        self.targets.iter().flat_map(move |t| {
+            t.metrics
+                .iter()
+                .filter(move |m| m.status == status)
+                .map(move |m| (t.target_id.as_str(), m))
+        })
+    }
+}
+
+fn compare_target(
+    baseline: &BenchmarkResult,
+    current: &BenchmarkResult,
+    threshold_pct: f64,
+) -> TargetComparison {
+    let mut metrics = Vec::new();
+
+    if let (Some(base), Some(cur)) = (baseline.metrics.as_object(), current.metrics.as_object()) {
+        for (name, base_value) in base {
+            let values = base_value
+                .as_f64()
+                .zip(cur.get(name).and_then(|v| v.as_f64()));
+            if let Some((base_value, cur_value)) = values {
+                metrics.push(MetricDelta::new(name.clone(), base_value, cur_value, threshold_pct));
+            }
+        }
+    }
+
+    metrics.sort_by(|a, b| a.name.cmp(&b.name));
+
+    TargetComparison {
+        target_id: baseline.target_id.clone(),
+        baseline_failed: baseline.is_error(),
+        current_failed: current.is_error(),
+        metrics,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metric_direction() {
+        assert_eq!(
+            MetricDirection::for_metric("p99_ms"),
+            Some(MetricDirection::LowerIsBetter)
+        );
+        assert_eq!(
+            MetricDirection::for_metric("throughput_rps"),
+            Some(MetricDirection::HigherIsBetter)
+        );
+        assert_eq!(
+            MetricDirection::for_metric("tokens_per_second"),
+            Some(MetricDirection::HigherIsBetter)
+        );
+        assert_eq!(MetricDirection::for_metric("iterations"), None);
+    }
+
+    #[test]
+    fn test_metric_delta_math() {
+        let regressed = MetricDelta::new("p99_ms", 20.0, 25.0, 10.0);
+        assert_eq!(regressed.delta, 5.0);
+        assert_eq!(regressed.delta_pct, Some(25.0));
+        assert_eq!(regressed.status, DeltaStatus::Regressed);
+
+        let improved = MetricDelta::new("throughput_rps", 1000.0, 1500.0, 10.0);
+        assert_eq!(improved.delta, 500.0);
+        assert_eq!(improved.delta_pct, Some(50.0));
+        assert_eq!(improved.status, DeltaStatus::Improved);
+
+        let within = MetricDelta::new("p50_ms", 10.0, 10.5, 10.0);
+        assert_eq!(within.status, DeltaStatus::Unchanged);
+
+        let zero = MetricDelta::new("p50_ms", 0.0, 1.0, 10.0);
+        assert_eq!(zero.delta_pct, None);
+        assert_eq!(zero.status, DeltaStatus::Informational);
+    }
+
+    #[test]
+    fn test_throughput_drop_is_regression() {
+        let delta = MetricDelta::new("throughput_rps", 1000.0, 800.0, 10.0);
+        assert_eq!(delta.delta_pct, Some(-20.0));
+        assert_eq!(delta.status, DeltaStatus::Regressed);
+    }
+
+    #[test]
+    fn test_comparison_added_and_removed_targets() {
+        let baseline = vec![
+            BenchmarkResult::new("kept", serde_json::json!({"p99_ms": 10.0})),
+            BenchmarkResult::new("dropped", serde_json::json!({"p99_ms": 10.0})),
+        ];
+        let current = vec![
+            BenchmarkResult::new("kept", serde_json::json!({"p99_ms": 10.0})),
+            BenchmarkResult::new("new", serde_json::json!({"p99_ms": 10.0})),
+        ];
+
+        let comparison = BenchmarkComparison::new(&baseline, &current);
+        assert_eq!(comparison.targets.len(), 1);
+        assert_eq!(comparison.added, vec!["new".to_string()]);
+        assert_eq!(comparison.removed, vec!["dropped".to_string()]);
+        assert!(!comparison.has_regressions());
+    }
+
+    #[test]
+    fn test_newly_failing_target_is_regression() {
+        let baseline = vec![BenchmarkResult::new("t", serde_json::json!({"p99_ms": 10.0}))];
+        let current = vec![BenchmarkResult::new(
+            "t",
+            serde_json::json!({"error": "boom", "status": "failed"}),
+        )];
+
+        let comparison = BenchmarkComparison::new(&baseline, &current);
+        assert!(comparison.targets[0].metrics.is_empty());
+        assert!(comparison.has_regressions());
+    }
+}
@@ -114,6 +114,28 @@ pub fn read_all_results(output_dir: &Path) -> Result<Vec<BenchmarkResult>> {
     Ok(results)
 }
 
+/// Read benchmark results from a results file or an output directory.
+///
+/// A file must contain a JSON array of results (e.g. `all_results.json`);
+/// a directory is read with [`read_all_results`].
+///
+/// # Arguments
+///
+/// * `path` - Results file or base output directory
+pub fn read_results_from(path: &Path) -> Result<Vec<BenchmarkResult>> {
+    if path.is_dir() {
+        return read_all_results(path);
+    }
+
+    let content =
+        fs::read_to_string(path).context(format!("Failed to read {}", path.display()))?;
+
+    let results: Vec<BenchmarkResult> = serde_json::from_str(&content)
+        .context(format!("Failed to parse {}", path.display()))?;
+
+    Ok(results)
+}
+
 /// Sanitize a string for use as a filename.
 fn sanitize_filename(name: &str) -> String {
     name.chars()
@@ -206,4 +228,21 @@ mod tests {
         assert!(output_dir.exists());
         assert!(output_dir.join("raw").exists());
     }
+
+    #[test]
+    fn test_read_results_from_file_and_dir() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let output_dir = temp_dir.path();
+
+        let results = vec![BenchmarkResult::new("test_a", serde_json::json!({"value": 1}))];
+        write_raw_results(&results, output_dir).expect("Failed to write results");
+
+        let from_file = read_results_from(&output_dir.join("all_results.json"))
+            .expect("Failed to read results file");
+        let from_dir = read_results_from(output_dir).expect("Failed to read results dir");
+
+        assert_eq!(from_file.len(), 1);
+        assert_eq!(from_file[0].target_id, "test_a");
+        assert_eq!(from_dir.len(), 1);
+    }
 }
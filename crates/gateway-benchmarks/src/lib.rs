@@ -18,11 +18,13 @@
 #![warn(missing_docs)]
 
 pub mod adapters;
+pub mod compare;
 pub mod io;
 pub mod markdown;
 pub mod result;
 
 pub use adapters::{all_targets, BenchTarget};
+pub use compare::BenchmarkComparison;
 pub use result::BenchmarkResult;
 
 use anyhow::Result;
@@ -1,5 +1,6 @@
 //! Markdown report generation for benchmark results.
 
+use crate::compare::{BenchmarkComparison, DeltaStatus, MetricDelta};
 use crate::BenchmarkResult;
 use chrono::Utc;
 
@@ -165,6 +166,156 @@ pub fn generate_detailed_report(result: &BenchmarkResult) -> String {
     md
 }
 
+/// Generate a comparison report between a baseline and a current run.
+///
+/// Uses the default regression threshold; see
+/// [`generate_comparison_report`] to render a comparison built with a
+/// custom one.
+pub fn generate_comparison(baseline: &[BenchmarkResult], current: &[BenchmarkResult]) -> String {
+    generate_comparison_report(&BenchmarkComparison::new(baseline, current))
+}
+
+/// Generate a markdown report from a structured benchmark comparison.
+pub fn generate_comparison_report(comparison: &BenchmarkComparison) -> String {
+    let mut md = String::new();
+
+    md.push_str("# Benchmark Comparison\n\n");
+    md.push_str(&format!(
+        "**Generated:** {}\n\n",
+        Utc::now().format("%Y-%m-%d %H:%M:%S UTC")
+    ));
+    md.push_str(&format!(
+        "**Regression threshold:** {:.1}%\n\n",
+        comparison.threshold_pct
+    ));
+
+    let regressed_targets = comparison.targets.iter().filter(|t| t.is_regressed()).count();
+
+    md.push_str("## Overview\n\n");
+    md.push_str("| Metric | Value |\n");
+    md.push_str("|--------|-------|\n");
+    md.push_str(&format!("| Targets Compared | {} |\n", comparison.targets.len()));
+    md.push_str(&format!("| Regressed Targets | {} |\n", regressed_targets));
+    md.push_str(&format!("| Regressed Metrics | {} |\n", comparison.regressions().count()));
+    md.push_str(&format!("| Improved Metrics | {} |\n", comparison.improvements().count()));
+    md.push_str(&format!("| New Targets | {} |\n", comparison.added.len()));
+    md.push_str(&format!("| Missing Targets | {} |\n\n", comparison.removed.len()));
+
+    if comparison.has_regressions() {
+        md.push_str("## Regressions\n\n");
+        for target in comparison.targets.iter().filter(|t| t.current_failed && !t.baseline_failed) {
+            md.push_str(&format!("- ❌ **{}**: now failing\n", target.target_id));
+        }
+        for (target_id, metric) in comparison.regressions() {
+            md.push_str(&format!(
+                "- ❌ **{}** `{}`: {}\n",
+                target_id,
+                metric.name,
+                describe_change(metric)
+            ));
+        }
+        md.push('\n');
+    }
+
+    if comparison.improvements().next().is_some() {
+        md.push_str("## Improvements\n\n");
+        for (target_id, metric) in comparison.improvements() {
+            md.push_str(&format!(
+                "- ✅ **{}** `{}`: {}\n",
+                target_id,
+                metric.name,
+                describe_change(metric)
+            ));
+        }
+        md.push('\n');
+    }
+
+    md.push_str("## Results\n\n");
+    for target in &comparison.targets {
+        md.push_str(&format!("### {}\n\n", target.target_id));
+
+        if target.baseline_failed || target.current_failed {
+            md.push_str(&format!(
+                "**Status:** {} → {}\n\n",
+                status_label(target.baseline_failed),
+                status_label(target.current_failed)
+            ));
+        }
+
+        if target.metrics.is_empty() {
+            md.push_str("*No comparable metrics.*\n\n");
+            continue;
+        }
+
+        md.push_str("| Metric | Baseline | Current | Delta | Change | Status |\n");
+        md.push_str("|--------|----------|---------|-------|--------|--------|\n");
+        for metric in &target.metrics {
+            md.push_str(&format!(
+                "| {} | {:.2} | {:.2} | {:+.2} | {} | {} |\n",
+                metric.name,
+                metric.baseline,
+                metric.current,
+                metric.delta,
+                format_pct(metric.delta_pct),
+                delta_status_label(metric.status)
+            ));
+        }
+        md.push('\n');
+    }
+
+    if !comparison.added.is_empty() {
+        md.push_str("## New Targets\n\n");
+        for target_id in &comparison.added {
+            md.push_str(&format!("- {}\n", target_id));
+        }
+        md.push('\n');
+    }
+
+    if !comparison.removed.is_empty() {
+        md.push_str("## Missing Targets\n\n");
+        for target_id in &comparison.removed {
+            md.push_str(&format!("- {}\n", target_id));
+        }
+        md.push('\n');
+    }
+
+    md.push_str("---\n\n");
+    md.push_str("*Generated by gateway-benchmarks canonical benchmark system*\n");
+
+    md
+}
+
+fn describe_change(metric: &MetricDelta) -> String {
+    format!(
+        "{:.2} → {:.2} ({})",
+        metric.baseline,
+        metric.current,
+        format_pct(metric.delta_pct)
+    )
+}
+
+fn format_pct(pct: Option<f64>) -> String {
+    pct.map(|v| format!("{:+.1}%", v))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn status_label(failed: bool) -> &'static str {
+    if failed {
+        "❌ Failed"
+    } else {
+        "✅ Passed"
+    }
+}
+
+fn delta_status_label(status: DeltaStatus) -> &'static str {
+    match status {
+        DeltaStatus::Regressed => "❌ Regressed",
+        DeltaStatus::Improved => "✅ Improved",
+        DeltaStatus::Unchanged => "Unchanged",
+        DeltaStatus::Informational => "-",
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -221,4 +372,55 @@ mod tests {
         assert!(report.contains("✅ **PASSED**"));
         assert!(report.contains("Latency:** 15.50 ms"));
     }
+
+    #[test]
+    fn test_generate_comparison_flags_regression_and_improvement() {
+        let baseline = vec![
+            BenchmarkResult::new(
+                "routing",
+                serde_json::json!({
+                    "p99_ms": 20.0,
+                    "throughput_rps": 1000.0,
+                    "iterations": 1000
+                }),
+            ),
+            BenchmarkResult::new("dropped", serde_json::json!({"p99_ms": 1.0})),
+        ];
+        let current = vec![
+            BenchmarkResult::new(
+                "routing",
+                serde_json::json!({
+                    "p99_ms": 23.0,
+                    "throughput_rps": 1250.0,
+                    "iterations": 1000
+                }),
+            ),
+            BenchmarkResult::new("added", serde_json::json!({"p99_ms": 1.0})),
+        ];
+
+        let report = generate_comparison(&baseline, &current);
+        assert!(report.contains("# Benchmark Comparison"));
+        assert!(report.contains("Regressed Metrics | 1"));
+        assert!(report.contains("Improved Metrics | 1"));
+        assert!(report.contains("**routing** `p99_ms`: 20.00 → 23.00 (+15.0%)"));
+        assert!(report.contains("| p99_ms | 20.00 | 23.00 | +3.00 | +15.0% | ❌ Regressed |"));
+        assert!(report.contains(
+            "| throughput_rps | 1000.00 | 1250.00 | +250.00 | +25.0% | ✅ Improved |"
+        ));
+        assert!(report.contains("| iterations | 1000.00 | 1000.00 | +0.00 | +0.0% | - |"));
+        assert!(report.contains("## New Targets\n\n- added"));
+        assert!(report.contains("## Missing Targets\n\n- dropped"));
+    }
+
+    #[test]
+    fn test_generate_comparison_report_custom_threshold() {
+        let baseline = vec![BenchmarkResult::new("t", serde_json::json!({"p99_ms": 20.0}))];
+        let current = vec![BenchmarkResult::new("t", serde_json::json!({"p99_ms": 23.0}))];
+
+        let comparison = BenchmarkComparison::with_threshold(&baseline, &current, 20.0);
+        let report = generate_comparison_report(&comparison);
+        assert!(report.contains("**Regression threshold:** 20.0%"));
+        assert!(report.contains("Regressed Metrics | 0"));
+        assert!(!report.contains("## Regressions"));
+    }
 }
@@ -55,12 +55,21 @@ pub enum Commands {
     /// Validate configuration file
     Validate(commands::validate::ValidateArgs),
 
+    /// Self-test config, database, Redis, providers and a chat end to end
+    Diagnose(commands::diagnose::DiagnoseArgs),
+
+    /// Inspect and replay integration events that permanently failed
+    DeadLetter(commands::dead_letter::DeadLetterArgs),
+
     /// Generate shell completions
     Completions(commands::completions::CompletionsArgs),
 
     /// Database migration management
     Migrate(commands::migrate::MigrateArgs),
 
+    /// Export and purge persisted audit events
+    Audit(commands::audit::AuditArgs),
+
     /// View latency metrics and statistics
     Latency(commands::latency::LatencyArgs),
 
@@ -101,8 +110,11 @@ impl Cli {
             Commands::Config(args) => commands::config::execute(args, self.json).await,
             Commands::Info(args) => commands::info::execute(args, &self.url, self.json).await,
             Commands::Validate(args) => commands::validate::execute(args, self.json).await,
+            Commands::Diagnose(args) => commands::diagnose::execute(args, self.json).await,
+            Commands::DeadLetter(args) => commands::dead_letter::execute(args, self.json).await,
             Commands::Completions(args) => commands::completions::execute(args),
             Commands::Migrate(args) => commands::migrate::execute(args, self.json).await,
+            Commands::Audit(args) => commands::audit::execute(args, self.json).await,
             Commands::Latency(args) => commands::latency::execute(args, &self.url, self.api_key.as_deref(), self.json).await,
             Commands::Cost(args) => commands::cost::execute(args, &self.url, self.api_key.as_deref(), self.json).await,
             Commands::TokenUsage(args) => commands::token_usage::execute(args, &self.url, self.api_key.as_deref(), self.json).await,
@@ -0,0 +1,172 @@
+//! Audit command - export and purge persisted audit events.
+
+use anyhow::Result;
+use chrono::{DateTime, NaiveDate, Utc};
+use clap::{Args, Subcommand};
+use gateway_config::PersistenceConfig;
+use gateway_server::audit_store::{AuditExportFormat, AuditStore};
+
+use crate::output::{self, CommandResult, OutputFormat};
+
+/// Arguments for the audit command.
+#[derive(Args, Debug)]
+pub struct AuditArgs {
+    #[command(subcommand)]
+    pub command: AuditCommand,
+
+    /// Database URL
+    #[arg(long, env = "DATABASE_URL")]
+    pub database_url: Option<String>,
+}
+
+/// Audit subcommands.
+#[derive(Subcommand, Debug)]
+pub enum AuditCommand {
+    /// Export a date range of audit events, with chain verification
+    Export(ExportArgs),
+
+    /// Purge audit events older than the retention period
+    Purge(PurgeArgs),
+}
+
+/// Arguments for audit export.
+#[derive(Args, Debug)]
+pub struct ExportArgs {
+    /// Start of the range, inclusive (RFC 3339 or YYYY-MM-DD)
+    #[arg(long, value_parser = parse_time)]
+    pub from: DateTime<Utc>,
+
+    /// End of the range, exclusive (RFC 3339 or YYYY-MM-DD)
+    #[arg(long, value_parser = parse_time)]
+    pub to: DateTime<Utc>,
+
+    /// Export format (jsonl or csv)
+    #[arg(long, default_value = "jsonl")]
+    pub format: AuditExportFormat,
+
+    /// File to write the export to
+    #[arg(short, long)]
+    pub output: std::path::PathBuf,
+}
+
+/// Arguments for audit purge.
+#[derive(Args, Debug)]
+pub struct PurgeArgs {
+    /// Keep events newer than this (e.g. 90d)
+    #[arg(long, default_value = "365d", value_parser = humantime::parse_duration)]
+    pub retention: std::time::Duration,
+
+    /// Archive purged events as JSONL into this directory first
+    #[arg(long)]
+    pub archive_dir: Option<String>,
+}
+
+/// Parse an RFC 3339 timestamp or a date (midnight UTC).
+fn parse_time(value: &str) -> std::result::Result<DateTime<Utc>, String> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|time| time.and_utc())
+        .ok_or_else(|| format!("invalid time '{value}': expected RFC 3339 or YYYY-MM-DD"))
+}
+
+/// Execute the audit command.
+pub async fn execute(args: AuditArgs, json: bool) -> Result<()> {
+    let format = OutputFormat::from_json_flag(json);
+
+    let database_url = args.database_url.or_else(|| std::env::var("DATABASE_URL").ok());
+    let Some(database_url) = database_url else {
+        let result: CommandResult<()> =
+            CommandResult::failure("DATABASE_URL environment variable or --database-url required");
+        result.print(format)?;
+        return Ok(());
+    };
+
+    let mut config = PersistenceConfig {
+        enabled: true,
+        database_url: Some(database_url),
+        ..PersistenceConfig::default()
+    };
+    if let AuditCommand::Purge(purge_args) = &args.command {
+        config.audit_retention = purge_args.retention;
+        config.audit_archive_dir = purge_args.archive_dir.clone();
+    }
+
+    let store = AuditStore::connect(&config)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to connect: {}", e))?
+        .ok_or_else(|| anyhow::anyhow!("Persistence is disabled"))?;
+
+    match args.command {
+        AuditCommand::Export(export_args) => execute_export(&store, export_args, format).await,
+        AuditCommand::Purge(_) => execute_purge(&store, format).await,
+    }
+}
+
+/// Execute audit export.
+async fn execute_export(store: &AuditStore, args: ExportArgs, format: OutputFormat) -> Result<()> {
+    let file = std::fs::File::create(&args.output)?;
+    let mut writer = std::io::BufWriter::new(file);
+    let export = store
+        .export(args.from, args.to, args.format, &mut writer)
+        .await
+        .map_err(|e| anyhow::anyhow!("Export failed: {}", e))?;
+
+    match format {
+        OutputFormat::Json => CommandResult::success(&export).print(format)?,
+        OutputFormat::Text => {
+            output::success(&format!(
+                "Exported {} audit event(s) to {}",
+                export.event_count,
+                args.output.display()
+            ));
+            output::status("Chain verified", export.chain.verified);
+            if let Some(seq) = export.chain.broken_at {
+                output::warning(&format!("Chain broken at event {seq}"));
+            }
+            if let Some(hash) = &export.chain.head_hash {
+                output::key_value("Head hash", hash);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Execute audit purge.
+async fn execute_purge(store: &AuditStore, format: OutputFormat) -> Result<()> {
+    let removed = store
+        .purge_expired()
+        .await
+        .map_err(|e| anyhow::anyhow!("Purge failed: {}", e))?;
+
+    match format {
+        OutputFormat::Json => {
+            CommandResult::success(serde_json::json!({ "purged": removed })).print(format)?;
+        }
+        OutputFormat::Text => {
+            output::success(&format!("Purged {removed} audit event(s)"));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_time() {
+        let date = parse_time("2024-03-01").unwrap();
+        assert_eq!(date.to_rfc3339(), "2024-03-01T00:00:00+00:00");
+
+        let time = parse_time("2024-03-01T12:30:00+02:00").unwrap();
+        assert_eq!(time.to_rfc3339(), "2024-03-01T10:30:00+00:00");
+
+        assert!(parse_time("yesterday").is_err());
+    }
+}
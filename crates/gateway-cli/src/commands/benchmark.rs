@@ -4,7 +4,8 @@ use anyhow::Result;
 use clap::Args;
 use colored::Colorize;
 use gateway_benchmarks::{
-    all_targets, io, markdown, run_all_benchmarks, BenchmarkResult,
+    all_targets, compare::DEFAULT_REGRESSION_THRESHOLD_PCT, io, markdown, run_all_benchmarks,
+    BenchmarkComparison, BenchmarkResult,
 };
 use std::path::PathBuf;
 use tabled::{Table, Tabled};
@@ -26,6 +27,8 @@ pub enum BenchmarkCommand {
     List(ListArgs),
     /// Show results from previous benchmark run
     Results(ResultsArgs),
+    /// Compare two benchmark runs and flag regressions
+    Compare(CompareArgs),
 }
 
 /// Arguments for running benchmarks.
@@ -68,6 +71,32 @@ pub struct ResultsArgs {
     pub json: bool,
 }
 
+/// Arguments for comparing two benchmark runs.
+#[derive(Args, Debug)]
+pub struct CompareArgs {
+    /// Baseline results file (`all_results.json`) or output directory
+    pub baseline: PathBuf,
+
+    /// Current results file (`all_results.json`) or output directory
+    pub current: PathBuf,
+
+    /// Relative change (in percent) beyond which a metric is flagged
+    #[arg(long, default_value_t = DEFAULT_REGRESSION_THRESHOLD_PCT)]
+    pub threshold: f64,
+
+    /// Write the markdown comparison report to this file
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+
+    /// Exit with an error if any regression is found
+    #[arg(long)]
+    pub fail_on_regression: bool,
+
+    /// Output in JSON format
+    #[arg(long)]
+    pub json: bool,
+}
+
 /// Table row for benchmark results.
 #[derive(Tabled)]
 struct BenchmarkRow {
@@ -100,6 +129,7 @@ pub async fn execute(args: BenchmarkArgs, json: bool) -> Result<()> {
         BenchmarkCommand::Run(run_args) => execute_run(run_args, json).await,
         BenchmarkCommand::List(list_args) => execute_list(list_args, json),
         BenchmarkCommand::Results(results_args) => execute_results(results_args, json),
+        BenchmarkCommand::Compare(compare_args) => execute_compare(compare_args, json),
     }
 }
 
@@ -247,6 +277,51 @@ fn execute_results(args: ResultsArgs, global_json: bool) -> Result<()> {
     Ok(())
 }
 
+/// Execute the compare subcommand.
+fn execute_compare(args: CompareArgs, global_json: bool) -> Result<()> {
+    let json = args.json || global_json;
+    let baseline = io::read_results_from(&args.baseline)?;
+    let current = io::read_results_from(&args.current)?;
+
+    let comparison = BenchmarkComparison::with_threshold(&baseline, &current, args.threshold);
+    let report = markdown::generate_comparison_report(&comparison);
+
+    if let Some(output) = &args.output {
+        std::fs::write(output, &report)?;
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&comparison)?);
+    } else {
+        println!("{}", report);
+
+        let regressions = comparison.regressions().count();
+        if comparison.has_regressions() {
+            println!(
+                "{}",
+                format!(
+                    "Regressions found: {} metric(s) beyond {:.1}%",
+                    regressions, comparison.threshold_pct
+                )
+                .red()
+                .bold()
+            );
+        } else {
+            println!("{}", "No regressions found.".green());
+        }
+
+        if let Some(output) = &args.output {
+            println!("Report saved to: {}", output.display());
+        }
+    }
+
+    if args.fail_on_regression && comparison.has_regressions() {
+        anyhow::bail!("Benchmark regressions detected");
+    }
+
+    Ok(())
+}
+
 /// Print results as a formatted table.
 fn print_results_table(results: &[BenchmarkResult]) {
     let rows: Vec<BenchmarkRow> = results
@@ -1,8 +1,11 @@
 //! Cache status command.
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::{Args, Subcommand};
+use gateway_resilience::{DistributedCache, DistributedCacheConfig, RedisCacheBackend};
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
 use tabled::Tabled;
 
 use crate::output::{self, CommandResult, OutputFormat};
@@ -32,6 +35,9 @@ pub enum CacheCommand {
 
     /// Show cache configuration
     Config,
+
+    /// Show cache backend health, connectivity and usage
+    Health(HealthArgs),
 }
 
 /// Arguments for stats command.
@@ -46,6 +52,18 @@ pub struct StatsArgs {
     pub detailed: bool,
 }
 
+/// Arguments for health command.
+#[derive(Args, Debug)]
+pub struct HealthArgs {
+    /// Redis URL of the distributed cache (in-memory cache if unset)
+    #[arg(long, env = "REDIS_URL")]
+    pub redis_url: Option<String>,
+
+    /// Key prefix used by the gateway
+    #[arg(long, default_value = "llm-gateway")]
+    pub key_prefix: String,
+}
+
 /// Arguments for list command.
 #[derive(Args, Debug)]
 pub struct ListArgs {
@@ -160,6 +178,39 @@ pub struct CacheConfigOutput {
     pub cache_control_header: bool,
 }
 
+/// Cache backend health output.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CacheHealthOutput {
+    /// Active backend (`memory`, `redis`)
+    pub backend: String,
+    pub distributed: bool,
+    /// `healthy`, `degraded` or `disabled`
+    pub status: String,
+    pub connected: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub hits: u64,
+    pub misses: u64,
+    pub skipped: u64,
+    pub hit_rate: f64,
+    pub backend_errors: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entries: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_bytes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub redis: Option<RedisInfoOutput>,
+}
+
+/// Highlights from Redis `INFO`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RedisInfoOutput {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub used_memory: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub evicted_keys: Option<u64>,
+}
+
 /// Cache clear result.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CacheClearResult {
@@ -213,6 +264,9 @@ pub async fn execute(
             execute_clear(clear_args, base_url, api_key, args.timeout, json, format).await
         }
         CacheCommand::Config => execute_config(base_url, api_key, args.timeout, format).await,
+        CacheCommand::Health(health_args) => {
+            execute_health(health_args, args.timeout, format).await
+        }
     }
 }
 
@@ -445,6 +499,142 @@ async fn execute_config(
     Ok(())
 }
 
+async fn execute_health(args: HealthArgs, timeout: u64, format: OutputFormat) -> Result<()> {
+    let config = DistributedCacheConfig {
+        redis_url: args.redis_url.clone(),
+        redis_operation_timeout: Duration::from_secs(timeout),
+        key_prefix: args.key_prefix.clone(),
+        ..Default::default()
+    };
+    let mut cache = DistributedCache::new(config);
+
+    // A Redis that can't be reached is reported as degraded, not an error
+    let mut connect_error = None;
+    if let Some(url) = args.redis_url {
+        let backend = RedisCacheBackend::new(url, &args.key_prefix, Duration::from_secs(timeout))
+            .await
+            .context("Failed to create Redis cache backend")?;
+        if let Err(e) = backend.connect().await {
+            connect_error = Some(e.to_string());
+        }
+        cache.set_l2_backend(Arc::new(backend));
+    }
+
+    let mut health = collect_health(&cache).await;
+    if connect_error.is_some() {
+        health.error = connect_error;
+    }
+
+    match format {
+        OutputFormat::Json => {
+            let result = CommandResult::success(health);
+            result.print(format)?;
+        }
+        OutputFormat::Text => print_health(&health),
+    }
+
+    Ok(())
+}
+
+/// Probe the cache backend and gather its stats.
+async fn collect_health(cache: &DistributedCache) -> CacheHealthOutput {
+    let backend = cache.backend_name();
+    let distributed = cache.is_distributed();
+    let stats = cache.stats().await;
+
+    let probe = match cache.health_check().await {
+        Ok(()) => cache.backend_info().await,
+        Err(e) => Err(e),
+    };
+    let (connected, error, info) = match probe {
+        Ok(info) => (true, None, Some(info)),
+        Err(e) => (false, Some(e.to_string()), None),
+    };
+
+    let status = if !cache.is_enabled() {
+        "disabled"
+    } else if connected {
+        "healthy"
+    } else {
+        "degraded"
+    };
+
+    // A lookup only misses when every layer missed
+    let misses = if distributed {
+        stats.l2_misses
+    } else {
+        stats.l1_misses
+    };
+
+    let redis = info
+        .as_ref()
+        .filter(|_| backend == "redis")
+        .map(|info| RedisInfoOutput {
+            used_memory: info.memory_bytes,
+            evicted_keys: info.evicted_keys,
+        });
+
+    CacheHealthOutput {
+        backend: backend.to_string(),
+        distributed,
+        status: status.to_string(),
+        connected,
+        error,
+        hits: stats.l1_hits + stats.l2_hits,
+        misses,
+        skipped: stats.skipped,
+        hit_rate: stats.hit_rate(),
+        backend_errors: stats.backend_errors,
+        entries: info.as_ref().and_then(|info| info.entries),
+        memory_bytes: info.as_ref().and_then(|info| info.memory_bytes),
+        redis,
+    }
+}
+
+fn print_health(health: &CacheHealthOutput) {
+    output::section("Cache Health");
+    output::key_value("Backend", &health.backend);
+    output::key_value("Distributed", &health.distributed.to_string());
+    output::status(&format!("Status: {}", health.status), health.status == "healthy");
+    if let Some(ref error) = health.error {
+        output::key_value("Error", error);
+    }
+
+    println!();
+    output::section("Performance");
+    output::key_value("Cache Hits", &health.hits.to_string());
+    output::key_value("Cache Misses", &health.misses.to_string());
+    output::key_value("Skipped", &health.skipped.to_string());
+    output::key_value("Hit Rate", &format!("{:.1}%", health.hit_rate));
+    output::key_value("Backend Errors", &health.backend_errors.to_string());
+
+    println!();
+    output::section("Usage");
+    output::key_value(
+        "Entries",
+        &health
+            .entries
+            .map_or_else(|| "unknown".to_string(), |entries| entries.to_string()),
+    );
+    output::key_value(
+        "Memory",
+        &health
+            .memory_bytes
+            .map_or_else(|| "unknown".to_string(), format_size_inline),
+    );
+
+    if let Some(ref redis) = health.redis {
+        println!();
+        output::section("Redis");
+        if let Some(used_memory) = redis.used_memory {
+            output::key_value("Used Memory", &format_size_inline(used_memory));
+        }
+        if let Some(evicted_keys) = redis.evicted_keys {
+            output::key_value("Evicted Keys", &evicted_keys.to_string());
+        }
+    }
+}
+
 fn format_size_inline(bytes: u64) -> String {
     if bytes >= 1_073_741_824 {
         format!("{:.2} GB", bytes as f64 / 1_073_741_824.0)
@@ -679,6 +869,65 @@ mod tests {
         assert!(entries.len() <= 5);
     }
 
+    fn make_request(content: &str, temperature: f32) -> gateway_core::GatewayRequest {
+        gateway_core::GatewayRequest::builder()
+            .model("gpt-4o")
+            .message(gateway_core::ChatMessage::user(content))
+            .temperature(temperature)
+            .build()
+            .expect("valid request")
+    }
+
+    #[tokio::test]
+    async fn test_memory_backend_health() {
+        let cache = DistributedCache::with_defaults();
+        let response = gateway_core::GatewayResponse::builder()
+            .id("resp-1")
+            .model("gpt-4o")
+            .build();
+
+        cache.put(&make_request("Hello", 0.7), response).await;
+        assert!(cache.get(&make_request("Hello", 0.7)).await.is_some());
+        assert!(cache.get(&make_request("Goodbye", 0.7)).await.is_none());
+        assert!(cache.get(&make_request("Hello", 1.9)).await.is_none());
+
+        let health = collect_health(&cache).await;
+        assert_eq!(health.backend, "memory");
+        assert!(!health.distributed);
+        assert_eq!(health.status, "healthy");
+        assert!(health.connected);
+        assert_eq!(health.hits, 1);
+        assert_eq!(health.misses, 1);
+        assert_eq!(health.skipped, 1);
+        assert!((health.hit_rate - 50.0).abs() < f64::EPSILON);
+        assert_eq!(health.entries, Some(1));
+        assert!(health.memory_bytes.unwrap() > 0);
+        assert!(health.redis.is_none());
+
+        let json = serde_json::to_value(CommandResult::success(health)).unwrap();
+        assert_eq!(json["data"]["status"], "healthy");
+        assert_eq!(json["data"]["entries"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_redis_reports_degraded() {
+        let mut cache = DistributedCache::with_defaults();
+        // Never connected, as when Redis can't be reached
+        let backend = RedisCacheBackend::new("redis://127.0.0.1:1", "llm-gateway", Duration::from_secs(1))
+            .await
+            .unwrap();
+        cache.set_l2_backend(Arc::new(backend));
+
+        let health = collect_health(&cache).await;
+        assert_eq!(health.backend, "redis");
+        assert!(health.distributed);
+        assert_eq!(health.status, "degraded");
+        assert!(!health.connected);
+        assert!(health.error.unwrap().contains("not connected"));
+        assert!(health.entries.is_none());
+        assert!(health.redis.is_none());
+    }
+
     #[test]
     fn test_format_size() {
         assert_eq!(format_size(&500), "500 B");
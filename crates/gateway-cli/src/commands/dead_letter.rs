@@ -0,0 +1,189 @@
+//! Dead-letter command - inspect and replay permanently failed integration events.
+
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand};
+use gateway_integrations::{DeadLetter, IntegrationManager, IntegrationsConfig};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tabled::Tabled;
+
+use crate::output::{self, CommandResult, OutputFormat};
+
+/// Arguments for the dead-letter command.
+#[derive(Args, Debug)]
+pub struct DeadLetterArgs {
+    #[command(subcommand)]
+    pub command: DeadLetterCommand,
+
+    /// Integrations configuration file (the `dead_letter`, `ruvector` and
+    /// `webhooks` sections are used)
+    #[arg(
+        short,
+        long,
+        env = "GATEWAY_INTEGRATIONS_CONFIG",
+        default_value = "integrations.yaml",
+        global = true
+    )]
+    pub config: PathBuf,
+}
+
+/// Dead-letter subcommands.
+#[derive(Subcommand, Debug)]
+pub enum DeadLetterCommand {
+    /// List dead-lettered events, oldest first
+    List(ListArgs),
+
+    /// Re-submit all dead-lettered events
+    Replay,
+}
+
+/// Arguments for dead-letter list.
+#[derive(Args, Debug)]
+pub struct ListArgs {
+    /// Number of events to show
+    #[arg(short = 'n', long, default_value = "20")]
+    pub limit: usize,
+}
+
+/// A dead-lettered event in list output.
+#[derive(Debug, Serialize, Tabled)]
+pub struct DeadLetterRow {
+    pub id: String,
+    pub integration: String,
+    pub attempts: u32,
+    pub failed_at: String,
+    pub error: String,
+}
+
+impl From<&DeadLetter> for DeadLetterRow {
+    fn from(letter: &DeadLetter) -> Self {
+        Self {
+            id: letter.id.clone(),
+            integration: letter.payload.integration().to_string(),
+            attempts: letter.attempts,
+            failed_at: letter.failed_at.format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+            error: letter.error.clone(),
+        }
+    }
+}
+
+/// Execute the dead-letter command.
+pub async fn execute(args: DeadLetterArgs, json: bool) -> Result<()> {
+    let format = OutputFormat::from_json_flag(json);
+    let config = load_config(&args.config).await?;
+    if !config.dead_letter.enabled {
+        anyhow::bail!(
+            "Dead-lettering is not enabled in {} (set dead_letter.enabled)",
+            args.config.display()
+        );
+    }
+
+    let manager = IntegrationManager::new(config);
+    let sink = manager
+        .dead_letters()
+        .context("Dead-letter sink unavailable")?;
+
+    match args.command {
+        DeadLetterCommand::List(list) => {
+            let letters = sink.entries().await?;
+            let rows: Vec<DeadLetterRow> =
+                letters.iter().take(list.limit).map(Into::into).collect();
+            match format {
+                OutputFormat::Json => CommandResult::success(&rows).print(format),
+                OutputFormat::Text => {
+                    output::section(&format!(
+                        "Dead-lettered events ({} of {}, {})",
+                        rows.len(),
+                        letters.len(),
+                        sink.path().display()
+                    ));
+                    output::table(&rows);
+                    Ok(())
+                }
+            }
+        }
+        DeadLetterCommand::Replay => {
+            let summary = manager.replay_dead_letters().await?;
+            match format {
+                OutputFormat::Json => CommandResult::success(summary).print(format)?,
+                OutputFormat::Text => {
+                    output::success(&format!("Replayed {} event(s)", summary.replayed));
+                    if summary.failed > 0 {
+                        output::warning(&format!(
+                            "{} event(s) failed again and remain dead-lettered",
+                            summary.failed
+                        ));
+                    }
+                }
+            }
+            if summary.failed > 0 {
+                anyhow::bail!("{} event(s) could not be replayed", summary.failed);
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Load the integrations configuration from a YAML file.
+async fn load_config(path: &Path) -> Result<IntegrationsConfig> {
+    let contents = tokio::fs::read_to_string(path)
+        .await
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_yaml::from_str(&contents)
+        .with_context(|| format!("Invalid configuration in {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gateway_integrations::{
+        DeadLetterPayload, IntegrationError, WebhookEvent, WebhookEventType,
+    };
+
+    #[tokio::test]
+    async fn test_config_and_rows() {
+        let dir = tempfile::tempdir().unwrap();
+        let sink_path = dir.path().join("dead.jsonl");
+        let config_path = dir.path().join("integrations.yaml");
+        std::fs::write(
+            &config_path,
+            format!(
+                "dead_letter:\n  enabled: true\n  path: {}\n  max_entries: 5\n",
+                sink_path.display()
+            ),
+        )
+        .unwrap();
+
+        let config = load_config(&config_path).await.unwrap();
+        assert!(config.dead_letter.enabled);
+        assert_eq!(config.dead_letter.path, sink_path);
+        assert_eq!(config.dead_letter.max_entries, 5);
+
+        let letter = DeadLetter::new(
+            DeadLetterPayload::Webhook {
+                url: "https://hooks.example.com".to_string(),
+                event: WebhookEvent::new(WebhookEventType::KeyExpiring, serde_json::json!({})),
+            },
+            &IntegrationError::webhook("Webhook receiver returned 503"),
+            4,
+        );
+        let row = DeadLetterRow::from(&letter);
+        assert_eq!(row.integration, "webhooks");
+        assert_eq!(row.attempts, 4);
+        assert_eq!(row.error, "Webhook error: Webhook receiver returned 503");
+    }
+
+    #[tokio::test]
+    async fn test_disabled_sink_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("integrations.yaml");
+        std::fs::write(&config_path, "enabled: true\n").unwrap();
+
+        let args = DeadLetterArgs {
+            command: DeadLetterCommand::Replay,
+            config: config_path,
+        };
+        let err = execute(args, true).await.unwrap_err();
+        assert!(err.to_string().contains("not enabled"));
+    }
+}
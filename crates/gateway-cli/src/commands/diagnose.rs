@@ -0,0 +1,731 @@
+//! Diagnose command - self-test a deployment end to end.
+//!
+//! Runs each stage a request depends on in order: loading and validating
+//! the configuration, the persistence database and its migrations, Redis,
+//! every configured provider, and finally a one-token chat completion sent
+//! through the gateway's own request pipeline. Stages that depend on a
+//! failed one are skipped rather than run against a broken setup.
+
+use anyhow::Result;
+use axum::body::Body;
+use axum::http::{header, Method, Request, StatusCode};
+use clap::Args;
+use gateway_config::{ConfigError, GatewayConfig};
+use gateway_core::{HealthStatus, LLMProvider};
+use gateway_providers::{ProviderDefinition, ProviderRegistry, RegistryBuilder};
+use gateway_resilience::{CacheBackend, RedisCacheBackend};
+use gateway_routing::{Router, RouterConfig};
+use http_body_util::BodyExt;
+use serde::Serialize;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tower::ServiceExt;
+
+use crate::output::{self, CommandResult, OutputFormat};
+
+/// Arguments for the diagnose command.
+#[derive(Args, Debug)]
+pub struct DiagnoseArgs {
+    /// Configuration file to diagnose
+    #[arg(short, long, env = "GATEWAY_CONFIG", default_value = "gateway.yaml")]
+    pub config: PathBuf,
+
+    /// Redis URL to check (skipped if not set)
+    #[arg(long, env = "REDIS_URL")]
+    pub redis_url: Option<String>,
+
+    /// Model for the end-to-end chat (defaults to the first model of the
+    /// first healthy provider)
+    #[arg(long)]
+    pub model: Option<String>,
+
+    /// Skip the end-to-end chat, which is billed by the provider
+    #[arg(long)]
+    pub skip_chat: bool,
+
+    /// Timeout for each stage in seconds
+    #[arg(long, default_value = "10")]
+    pub timeout: u64,
+}
+
+/// Outcome of a stage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StageStatus {
+    Pass,
+    /// Working, but not as expected (e.g. a degraded provider)
+    Warn,
+    Fail,
+    /// Not applicable, or a stage it depends on failed
+    Skip,
+}
+
+/// Result of one diagnostic stage.
+#[derive(Debug, Serialize)]
+pub struct StageResult {
+    pub stage: String,
+    pub status: StageStatus,
+    /// Whether a failure of this stage fails the diagnosis
+    pub critical: bool,
+    pub message: String,
+    pub duration_ms: u64,
+}
+
+impl StageResult {
+    fn new(stage: impl Into<String>, status: StageStatus, message: impl Into<String>) -> Self {
+        Self {
+            stage: stage.into(),
+            status,
+            critical: true,
+            message: message.into(),
+            duration_ms: 0,
+        }
+    }
+
+    fn pass(stage: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(stage, StageStatus::Pass, message)
+    }
+
+    fn fail(stage: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(stage, StageStatus::Fail, message)
+    }
+
+    fn skip(stage: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(stage, StageStatus::Skip, message)
+    }
+
+    /// Report failures of this stage without failing the diagnosis
+    fn optional(mut self) -> Self {
+        self.critical = false;
+        self
+    }
+
+    fn timed(mut self, start: Instant) -> Self {
+        self.duration_ms = u64::try_from(start.elapsed().as_millis()).unwrap_or(u64::MAX);
+        self
+    }
+
+    fn passed(&self) -> bool {
+        matches!(self.status, StageStatus::Pass | StageStatus::Warn)
+    }
+}
+
+/// Per-stage results of a diagnosis.
+#[derive(Debug, Default, Serialize)]
+pub struct DiagnosticReport {
+    pub healthy: bool,
+    pub stages: Vec<StageResult>,
+}
+
+impl DiagnosticReport {
+    fn push(&mut self, stage: StageResult) {
+        self.stages.push(stage);
+        self.healthy = self.critical_failures() == 0;
+    }
+
+    /// Number of critical stages that failed.
+    pub fn critical_failures(&self) -> usize {
+        self.stages
+            .iter()
+            .filter(|s| s.critical && s.status == StageStatus::Fail)
+            .count()
+    }
+}
+
+/// Execute the diagnose command.
+///
+/// Exits non-zero if any critical stage failed.
+pub async fn execute(args: DiagnoseArgs, json: bool) -> Result<()> {
+    let format = OutputFormat::from_json_flag(json);
+    let timeout = Duration::from_secs(args.timeout);
+
+    let mut report = DiagnosticReport::default();
+
+    if let Some(config) = load_config(&args.config, &mut report).await {
+        check_database(&config, timeout, &mut report).await;
+
+        let redis = match &args.redis_url {
+            Some(url) => match connect_redis(url, timeout).await {
+                Ok(backend) => check_redis(Some(&backend), timeout).await,
+                Err(e) => StageResult::fail("redis", e).optional(),
+            },
+            None => check_redis(None, timeout).await,
+        };
+        report.push(redis);
+
+        let providers = build_providers(&config, &mut report);
+        let options = ChatOptions {
+            model: args.model.clone(),
+            skip: args.skip_chat,
+            timeout,
+        };
+        diagnose_components(&config, providers, &options, &mut report).await;
+    }
+
+    print_report(&report, format)?;
+
+    if !report.healthy {
+        anyhow::bail!(
+            "Diagnosis failed: {} critical stage(s) failed",
+            report.critical_failures()
+        );
+    }
+    Ok(())
+}
+
+/// Options for the end-to-end chat stage.
+pub struct ChatOptions {
+    pub model: Option<String>,
+    pub skip: bool,
+    pub timeout: Duration,
+}
+
+/// Load the configuration, reporting parsing and validation separately.
+async fn load_config(path: &Path, report: &mut DiagnosticReport) -> Option<GatewayConfig> {
+    let start = Instant::now();
+    let loaded = gateway_config::ConfigLoader::new()
+        .with_file(path.display().to_string())
+        .load()
+        .await;
+
+    match loaded {
+        Ok(config) => {
+            let loaded = format!("Loaded {}", path.display());
+            report.push(StageResult::pass("config", loaded).timed(start));
+            report.push(StageResult::pass("validation", "Configuration is valid"));
+            Some(config)
+        }
+        Err(ConfigError::Validation(message)) => {
+            let loaded = format!("Loaded {}", path.display());
+            report.push(StageResult::pass("config", loaded).timed(start));
+            report.push(StageResult::fail("validation", message));
+            None
+        }
+        Err(e) => {
+            report.push(StageResult::fail("config", e.to_string()).timed(start));
+            report.push(StageResult::skip(
+                "validation",
+                "Configuration could not be loaded",
+            ));
+            None
+        }
+    }
+}
+
+/// Connect to the persistence database and check migrations are current.
+async fn check_database(config: &GatewayConfig, timeout: Duration, report: &mut DiagnosticReport) {
+    use gateway_migrations::{schema, MigrationConfig, Migrator};
+
+    let persistence = &config.persistence;
+    let Some(database_url) = persistence
+        .database_url
+        .as_ref()
+        .filter(|_| persistence.enabled)
+    else {
+        report.push(StageResult::skip("database", "Persistence is disabled"));
+        return;
+    };
+
+    let start = Instant::now();
+    let checked = within(timeout, async {
+        let config = MigrationConfig::builder()
+            .database_url(database_url)
+            .connect_timeout(timeout)
+            .build()
+            .map_err(|e| format!("Configuration error: {e}"))?;
+        let mut migrator = Migrator::new(config)
+            .await
+            .map_err(|e| format!("Failed to connect: {e}"))?;
+        migrator.add_migrations(schema::all_migrations());
+        let pending = migrator
+            .get_pending()
+            .await
+            .map_err(|e| format!("Failed to read migration status: {e}"))?;
+        Ok(pending.len())
+    })
+    .await;
+
+    let result = match checked {
+        Ok(0) => StageResult::pass("database", "Connected; migrations are current"),
+        Ok(pending) => StageResult::fail(
+            "database",
+            format!("{pending} migration(s) pending; run `llm-gateway migrate run`"),
+        ),
+        Err(e) => StageResult::fail("database", e),
+    };
+    report.push(result.timed(start));
+}
+
+/// Connect to Redis at `url`.
+async fn connect_redis(
+    url: &str,
+    timeout: Duration,
+) -> std::result::Result<RedisCacheBackend, String> {
+    let backend = RedisCacheBackend::new(url.to_string(), "diagnose", timeout)
+        .await
+        .map_err(|e| format!("Invalid Redis URL: {e}"))?;
+    backend
+        .connect()
+        .await
+        .map_err(|e| format!("Failed to connect: {e}"))?;
+    Ok(backend)
+}
+
+/// Probe the Redis backend, if one is configured.
+///
+/// The gateway falls back to its in-memory cache without Redis, so a
+/// failure here does not fail the diagnosis.
+pub async fn check_redis(backend: Option<&dyn CacheBackend>, timeout: Duration) -> StageResult {
+    let Some(backend) = backend else {
+        return StageResult::skip("redis", "No Redis URL configured");
+    };
+
+    let start = Instant::now();
+    let checked = within(timeout, async {
+        backend.health_check().await.map_err(|e| e.to_string())
+    })
+    .await;
+    match checked {
+        Ok(()) => StageResult::pass("redis", format!("{} backend reachable", backend.name())),
+        Err(e) => StageResult::fail("redis", e),
+    }
+    .optional()
+    .timed(start)
+}
+
+/// Build the enabled providers from the configuration.
+///
+/// Providers that cannot be built are reported as failed stages.
+fn build_providers(
+    config: &GatewayConfig,
+    report: &mut DiagnosticReport,
+) -> Vec<Arc<dyn LLMProvider>> {
+    let registry = ProviderRegistry::new();
+    for provider_config in config.providers.iter().filter(|p| p.enabled) {
+        let built = ProviderDefinition::from_gateway_config(provider_config)
+            .and_then(|definition| RegistryBuilder::new().provider(definition).build_into(&registry));
+        if let Err(e) = built {
+            report.push(StageResult::fail(
+                format!("provider:{}", provider_config.id),
+                e.to_string(),
+            ));
+        }
+    }
+    registry
+        .provider_ids()
+        .iter()
+        .filter_map(|id| registry.get(id))
+        .collect()
+}
+
+/// Run the provider and end-to-end chat stages against built components.
+pub async fn diagnose_components(
+    config: &GatewayConfig,
+    providers: Vec<Arc<dyn LLMProvider>>,
+    options: &ChatOptions,
+    report: &mut DiagnosticReport,
+) {
+    let configured = report
+        .stages
+        .iter()
+        .any(|s| s.stage.starts_with("provider:"));
+    if providers.is_empty() && !configured {
+        report.push(StageResult::fail("providers", "No providers are enabled"));
+    }
+
+    let mut healthy = Vec::new();
+    for provider in providers {
+        let stage = check_provider(provider.as_ref(), options.timeout).await;
+        if stage.passed() {
+            healthy.push(provider);
+        }
+        report.push(stage);
+    }
+
+    report.push(check_chat(config, healthy, options).await);
+}
+
+/// Check a provider with its health check, which does not bill tokens.
+pub async fn check_provider(provider: &dyn LLMProvider, timeout: Duration) -> StageResult {
+    let stage = format!("provider:{}", provider.id());
+    let start = Instant::now();
+    let health = tokio::time::timeout(timeout, provider.health_check()).await;
+
+    match health {
+        Ok(HealthStatus::Healthy) => StageResult::pass(stage, "Healthy"),
+        Ok(HealthStatus::Degraded) => StageResult::new(stage, StageStatus::Warn, "Degraded"),
+        Ok(status) => StageResult::fail(stage, format!("Health check reported {status}")),
+        Err(_) => StageResult::fail(stage, format!("Health check timed out after {timeout:?}")),
+    }
+    .timed(start)
+}
+
+/// Send a one-token chat completion through the gateway's request pipeline.
+pub async fn check_chat(
+    config: &GatewayConfig,
+    providers: Vec<Arc<dyn LLMProvider>>,
+    options: &ChatOptions,
+) -> StageResult {
+    if options.skip {
+        return StageResult::skip("chat", "Skipped with --skip-chat");
+    }
+    let model = options.model.clone().or_else(|| {
+        providers
+            .iter()
+            .find_map(|p| p.models().first().map(|m| m.id.clone()))
+    });
+    let Some(model) = model.filter(|_| !providers.is_empty()) else {
+        return StageResult::skip("chat", "No healthy provider to send a chat to");
+    };
+
+    let registry = ProviderRegistry::new();
+    let router = Router::new(RouterConfig::new());
+    for provider in providers {
+        let id = provider.id().to_string();
+        router.register_provider(Arc::clone(&provider), 100, 100);
+        router.update_health(&id, HealthStatus::Healthy);
+        let _ = registry.register(provider, 100, 100);
+    }
+    let state = gateway_server::AppState::builder()
+        .config(config.clone())
+        .providers(registry)
+        .router(router)
+        .build();
+
+    let body = serde_json::json!({
+        "model": model,
+        "messages": [{"role": "user", "content": "Reply with OK."}],
+        "max_tokens": 1,
+    });
+    let request = match Request::builder()
+        .method(Method::POST)
+        .uri("/v1/chat/completions")
+        .header(header::CONTENT_TYPE, "application/json")
+        .header("x-parent-span-id", uuid::Uuid::new_v4().to_string())
+        .body(Body::from(body.to_string()))
+    {
+        Ok(request) => request,
+        Err(e) => return StageResult::fail("chat", format!("Invalid chat request: {e}")),
+    };
+
+    let start = Instant::now();
+    let sent = within(options.timeout, async {
+        let response = gateway_server::routes::create_router(state)
+            .oneshot(request)
+            .await
+            .map_err(|e| e.to_string())?;
+        let status = response.status();
+        let bytes = response
+            .into_body()
+            .collect()
+            .await
+            .map_err(|e| e.to_string())?
+            .to_bytes();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap_or_default();
+        Ok((status, body))
+    })
+    .await;
+
+    match sent {
+        Ok((StatusCode::OK, body)) if body["success"] == true => {
+            let provider = body["result"]["provider"].as_str().unwrap_or("provider");
+            StageResult::pass("chat", format!("Completed with {model} via {provider}"))
+        }
+        Ok((status, body)) => {
+            let reason = body["error"]["message"]
+                .as_str()
+                .map_or_else(|| "execution failed".to_string(), str::to_string);
+            StageResult::fail("chat", format!("HTTP {}: {reason}", status.as_u16()))
+        }
+        Err(e) => StageResult::fail("chat", e),
+    }
+    .timed(start)
+}
+
+/// Await a fallible stage, failing it if it outlasts `timeout`.
+async fn within<T>(
+    timeout: Duration,
+    stage: impl Future<Output = std::result::Result<T, String>>,
+) -> std::result::Result<T, String> {
+    tokio::time::timeout(timeout, stage)
+        .await
+        .unwrap_or_else(|_| Err(format!("Timed out after {timeout:?}")))
+}
+
+/// Print the diagnostic report.
+fn print_report(report: &DiagnosticReport, format: OutputFormat) -> Result<()> {
+    match format {
+        OutputFormat::Json => {
+            let result = CommandResult {
+                success: report.healthy,
+                data: Some(report),
+                error: (!report.healthy).then(|| "Diagnosis failed".to_string()),
+                message: None,
+            };
+            result.print(format)?;
+        }
+        OutputFormat::Text => {
+            output::section("Diagnosis");
+            for stage in &report.stages {
+                let line = format!(
+                    "{}: {} ({}ms)",
+                    stage.stage, stage.message, stage.duration_ms
+                );
+                match stage.status {
+                    StageStatus::Pass => output::success(&line),
+                    StageStatus::Warn => output::warning(&line),
+                    StageStatus::Fail => output::error(&line),
+                    StageStatus::Skip => {
+                        output::info(&format!("{} skipped: {}", stage.stage, stage.message))
+                    }
+                }
+            }
+            println!();
+            if report.healthy {
+                output::success("All critical stages passed");
+            } else {
+                output::error(&format!(
+                    "{} critical stage(s) failed",
+                    report.critical_failures()
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::stream::BoxStream;
+    use gateway_core::{
+        ChatChunk, Choice, FinishReason, GatewayError, GatewayRequest, GatewayResponse, ModelInfo,
+        ProviderCapabilities, ProviderType,
+    };
+    use gateway_resilience::MemoryCacheBackend;
+
+    struct MockProvider {
+        id: &'static str,
+        health: HealthStatus,
+        models: Vec<ModelInfo>,
+        capabilities: ProviderCapabilities,
+    }
+
+    impl MockProvider {
+        fn arc(id: &'static str, health: HealthStatus) -> Arc<dyn LLMProvider> {
+            Arc::new(Self {
+                id,
+                health,
+                models: vec![ModelInfo::new("mock-model")],
+                capabilities: ProviderCapabilities {
+                    chat: true,
+                    ..ProviderCapabilities::default()
+                },
+            })
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl LLMProvider for MockProvider {
+        fn id(&self) -> &str {
+            self.id
+        }
+
+        fn provider_type(&self) -> ProviderType {
+            ProviderType::Custom
+        }
+
+        async fn chat_completion(
+            &self,
+            _: &GatewayRequest,
+        ) -> std::result::Result<GatewayResponse, GatewayError> {
+            Ok(GatewayResponse::builder()
+                .id("diag-1")
+                .model("mock-model")
+                .provider(self.id)
+                .choice(Choice::new(0, "OK", FinishReason::Stop))
+                .build())
+        }
+
+        async fn chat_completion_stream(
+            &self,
+            _: &GatewayRequest,
+        ) -> std::result::Result<
+            BoxStream<'static, std::result::Result<ChatChunk, GatewayError>>,
+            GatewayError,
+        > {
+            Err(GatewayError::internal("not streaming"))
+        }
+
+        async fn health_check(&self) -> HealthStatus {
+            self.health
+        }
+
+        fn capabilities(&self) -> &ProviderCapabilities {
+            &self.capabilities
+        }
+
+        fn models(&self) -> &[ModelInfo] {
+            &self.models
+        }
+
+        fn base_url(&self) -> &str {
+            "http://localhost"
+        }
+    }
+
+    fn options() -> ChatOptions {
+        ChatOptions {
+            model: None,
+            skip: false,
+            timeout: Duration::from_secs(5),
+        }
+    }
+
+    fn stage<'a>(report: &'a DiagnosticReport, name: &str) -> &'a StageResult {
+        report.stages.iter().find(|s| s.stage == name).unwrap()
+    }
+
+    fn statuses(report: &DiagnosticReport) -> Vec<(&str, StageStatus)> {
+        report
+            .stages
+            .iter()
+            .map(|s| (s.stage.as_str(), s.status))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_healthy_components_pass_every_stage() {
+        let mut report = DiagnosticReport::default();
+        let redis = MemoryCacheBackend::new(16, Duration::from_secs(60));
+        report.push(check_redis(Some(&redis), Duration::from_secs(1)).await);
+
+        let providers = vec![MockProvider::arc("mock", HealthStatus::Healthy)];
+        diagnose_components(
+            &GatewayConfig::default(),
+            providers,
+            &options(),
+            &mut report,
+        )
+        .await;
+
+        assert_eq!(
+            statuses(&report),
+            [
+                ("redis", StageStatus::Pass),
+                ("provider:mock", StageStatus::Pass),
+                ("chat", StageStatus::Pass),
+            ]
+        );
+        assert!(stage(&report, "chat").message.contains("mock-model"));
+        assert!(report.healthy);
+        assert_eq!(report.critical_failures(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_unhealthy_provider_fails_diagnosis() {
+        let mut report = DiagnosticReport::default();
+        let providers = vec![
+            MockProvider::arc("up", HealthStatus::Healthy),
+            MockProvider::arc("down", HealthStatus::Unhealthy),
+        ];
+        diagnose_components(
+            &GatewayConfig::default(),
+            providers,
+            &options(),
+            &mut report,
+        )
+        .await;
+
+        assert_eq!(stage(&report, "provider:up").status, StageStatus::Pass);
+        assert_eq!(stage(&report, "provider:down").status, StageStatus::Fail);
+        // The chat still goes out through the healthy provider
+        assert_eq!(stage(&report, "chat").status, StageStatus::Pass);
+        assert!(!report.healthy);
+        assert_eq!(report.critical_failures(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_chat_skipped_without_healthy_provider() {
+        let mut report = DiagnosticReport::default();
+        diagnose_components(&GatewayConfig::default(), vec![], &options(), &mut report).await;
+
+        assert_eq!(
+            statuses(&report),
+            [
+                ("providers", StageStatus::Fail),
+                ("chat", StageStatus::Skip)
+            ]
+        );
+        assert!(!report.healthy);
+
+        let skipped = ChatOptions {
+            skip: true,
+            ..options()
+        };
+        let providers = vec![MockProvider::arc("mock", HealthStatus::Healthy)];
+        let chat = check_chat(&GatewayConfig::default(), providers, &skipped).await;
+        assert_eq!(chat.status, StageStatus::Skip);
+    }
+
+    #[tokio::test]
+    async fn test_config_errors_are_reported_per_stage() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let mut report = DiagnosticReport::default();
+        assert!(load_config(&dir.path().join("missing.yaml"), &mut report)
+            .await
+            .is_none());
+        assert_eq!(
+            statuses(&report),
+            [
+                ("config", StageStatus::Fail),
+                ("validation", StageStatus::Skip)
+            ]
+        );
+
+        let path = dir.path().join("gateway.yaml");
+        std::fs::write(&path, "routing:\n  mirroring:\n    enabled: true\n").unwrap();
+        let mut report = DiagnosticReport::default();
+        assert!(load_config(&path, &mut report).await.is_none());
+        assert_eq!(
+            statuses(&report),
+            [
+                ("config", StageStatus::Pass),
+                ("validation", StageStatus::Fail)
+            ]
+        );
+        assert!(!report.healthy);
+    }
+
+    #[tokio::test]
+    async fn test_critical_failure_exits_non_zero() {
+        let dir = tempfile::tempdir().unwrap();
+        let args = DiagnoseArgs {
+            config: dir.path().join("missing.yaml"),
+            redis_url: None,
+            model: None,
+            skip_chat: true,
+            timeout: 1,
+        };
+
+        let err = execute(args, true).await.unwrap_err();
+        assert!(err.to_string().contains("1 critical stage(s) failed"));
+    }
+
+    #[tokio::test]
+    async fn test_optional_stage_failure_keeps_diagnosis_healthy() {
+        let mut report = DiagnosticReport::default();
+        report.push(StageResult::pass("config", "Loaded"));
+        let Err(e) = connect_redis("http://localhost:6379", Duration::from_secs(1)).await else {
+            panic!("an http:// URL is not a Redis URL");
+        };
+        report.push(StageResult::fail("redis", e).optional());
+
+        assert!(report.healthy);
+        assert_eq!(report.critical_failures(), 0);
+    }
+}
@@ -1,8 +1,12 @@
 //! Info command - show gateway version and information.
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::Args;
+use gateway_config::schema::AuthMethod;
+use gateway_config::GatewayConfig;
 use serde::Serialize;
+use std::path::PathBuf;
+use std::time::Duration;
 
 use crate::output::{self, CommandResult, OutputFormat};
 
@@ -16,6 +20,18 @@ pub struct InfoArgs {
     /// Show runtime information
     #[arg(long)]
     pub runtime: bool,
+
+    /// Configuration file to summarize (defaults are used otherwise)
+    #[arg(short, long)]
+    pub config: Option<PathBuf>,
+
+    /// Don't probe configured provider endpoints for reachability
+    #[arg(long)]
+    pub skip_probe: bool,
+
+    /// Timeout in seconds for server and provider probes
+    #[arg(short, long, default_value = "3")]
+    pub timeout: u64,
 }
 
 /// Info output.
@@ -23,6 +39,12 @@ pub struct InfoArgs {
 pub struct InfoOutput {
     pub version: String,
     pub name: String,
+    /// Commit the binary was built from (`GIT_SHA` at build time)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub git_sha: Option<String>,
+    /// Provider features compiled into this build
+    pub provider_features: Vec<String>,
+    pub config: ConfigSummary,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub build: Option<BuildInfo>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -31,6 +53,33 @@ pub struct InfoOutput {
     pub server: Option<ServerInfo>,
 }
 
+/// Summary of the effective configuration.
+#[derive(Debug, Serialize)]
+pub struct ConfigSummary {
+    /// Configuration file, or `None` for defaults
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    pub providers: Vec<ProviderSummary>,
+    pub auth_mode: String,
+    /// Cache backend reported by the running server
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_backend: Option<String>,
+    pub integrations: Vec<String>,
+}
+
+/// Configured provider and whether its endpoint answered.
+#[derive(Debug, Serialize)]
+pub struct ProviderSummary {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub provider_type: String,
+    pub endpoint: String,
+    pub enabled: bool,
+    /// `None` when probing was skipped or the provider is disabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reachable: Option<bool>,
+}
+
 /// Build information.
 #[derive(Debug, Serialize)]
 pub struct BuildInfo {
@@ -61,89 +110,224 @@ pub struct ServerInfo {
 pub async fn execute(args: InfoArgs, base_url: &str, json: bool) -> Result<()> {
     let format = OutputFormat::from_json_flag(json);
 
-    let mut info = InfoOutput {
+    let info = collect_info(&args, base_url).await?;
+
+    match format {
+        OutputFormat::Json => {
+            let result = CommandResult::success(info);
+            result.print(format)?;
+        }
+        OutputFormat::Text => print_text(&info),
+    }
+
+    Ok(())
+}
+
+/// Gather everything the info command reports.
+async fn collect_info(args: &InfoArgs, base_url: &str) -> Result<InfoOutput> {
+    let config = if let Some(ref path) = args.config {
+        gateway_config::ConfigLoader::new()
+            .with_file(path.display().to_string())
+            .load()
+            .await
+            .context("Failed to load configuration file")?
+    } else {
+        GatewayConfig::default()
+    };
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(args.timeout))
+        .build()?;
+
+    let (server, cache_backend, providers) = futures::join!(
+        get_server_info(&client, base_url),
+        get_cache_backend(&client, base_url),
+        summarize_providers(&client, &config, !args.skip_probe),
+    );
+
+    let build = args.build.then(|| BuildInfo {
+        rust_version: env!("CARGO_PKG_RUST_VERSION").to_string(),
+        target: std::env::consts::ARCH.to_string(),
+        profile: if cfg!(debug_assertions) {
+            "debug".to_string()
+        } else {
+            "release".to_string()
+        },
+    });
+
+    let runtime = args.runtime.then(|| RuntimeInfo {
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        cpus: num_cpus(),
+    });
+
+    Ok(InfoOutput {
         version: env!("CARGO_PKG_VERSION").to_string(),
         name: "LLM Inference Gateway".to_string(),
-        build: None,
-        runtime: None,
-        server: None,
-    };
+        git_sha: option_env!("GIT_SHA").map(String::from),
+        provider_features: gateway_providers::compiled_provider_features()
+            .into_iter()
+            .map(String::from)
+            .collect(),
+        config: ConfigSummary {
+            file: args.config.as_ref().map(|p| p.display().to_string()),
+            providers,
+            auth_mode: auth_mode(&config),
+            cache_backend,
+            integrations: enabled_integrations(&config),
+        },
+        build,
+        runtime,
+        server,
+    })
+}
+
+fn print_text(info: &InfoOutput) {
+    output::section("LLM Inference Gateway");
+    output::key_value("Version", &info.version);
+    output::key_value("Git SHA", info.git_sha.as_deref().unwrap_or("unknown"));
+    output::key_value("Provider Features", &join_or_none(&info.provider_features));
 
-    if args.build {
-        info.build = Some(BuildInfo {
-            rust_version: env!("CARGO_PKG_RUST_VERSION").to_string(),
-            target: std::env::consts::ARCH.to_string(),
-            profile: if cfg!(debug_assertions) {
-                "debug".to_string()
-            } else {
-                "release".to_string()
-            },
-        });
+    if let Some(ref build) = info.build {
+        output::section("Build Information");
+        output::key_value("Rust Version", &build.rust_version);
+        output::key_value("Target", &build.target);
+        output::key_value("Profile", &build.profile);
     }
 
-    if args.runtime {
-        info.runtime = Some(RuntimeInfo {
-            os: std::env::consts::OS.to_string(),
-            arch: std::env::consts::ARCH.to_string(),
-            cpus: num_cpus(),
-        });
+    if let Some(ref runtime) = info.runtime {
+        output::section("Runtime Information");
+        output::key_value("OS", &runtime.os);
+        output::key_value("Architecture", &runtime.arch);
+        output::key_value("CPUs", &runtime.cpus.to_string());
     }
 
-    // Try to get server info
-    let server_info = get_server_info(base_url).await;
-    info.server = server_info;
+    output::section("Configuration");
+    output::key_value("Source", info.config.file.as_deref().unwrap_or("defaults"));
+    output::key_value("Auth Mode", &info.config.auth_mode);
+    output::key_value(
+        "Cache Backend",
+        info.config.cache_backend.as_deref().unwrap_or("unknown"),
+    );
+    output::key_value("Integrations", &join_or_none(&info.config.integrations));
 
-    match format {
-        OutputFormat::Json => {
-            let result = CommandResult::success(info);
-            result.print(format)?;
+    output::section("Providers");
+    if info.config.providers.is_empty() {
+        output::info("No providers configured");
+    }
+    for provider in &info.config.providers {
+        let label = format!(
+            "{} ({}) {}",
+            provider.id, provider.provider_type, provider.endpoint
+        );
+        match (provider.enabled, provider.reachable) {
+            (false, _) => output::key_value(&label, "disabled"),
+            (true, None) => output::key_value(&label, "not probed"),
+            (true, Some(reachable)) => output::status(&label, reachable),
+        }
+    }
+
+    output::section("Server Status");
+    if let Some(ref server) = info.server {
+        output::status(&format!("Status: {}", server.status), server.status == "healthy");
+
+        if let Some(ref version) = server.version {
+            output::key_value("Server Version", version);
         }
-        OutputFormat::Text => {
-            output::section("LLM Inference Gateway");
-            output::key_value("Version", &info.version);
-
-            if let Some(ref build) = info.build {
-                output::section("Build Information");
-                output::key_value("Rust Version", &build.rust_version);
-                output::key_value("Target", &build.target);
-                output::key_value("Profile", &build.profile);
-            }
-
-            if let Some(ref runtime) = info.runtime {
-                output::section("Runtime Information");
-                output::key_value("OS", &runtime.os);
-                output::key_value("Architecture", &runtime.arch);
-                output::key_value("CPUs", &runtime.cpus.to_string());
-            }
-
-            if let Some(ref server) = info.server {
-                output::section("Server Status");
-                output::status(&format!("Status: {}", server.status), server.status == "healthy");
-
-                if let Some(ref version) = server.version {
-                    output::key_value("Server Version", version);
-                }
-
-                if let Some(ref uptime) = server.uptime {
-                    output::key_value("Uptime", uptime);
-                }
-            } else {
-                output::section("Server Status");
-                output::status("Server not reachable", false);
-            }
+
+        if let Some(ref uptime) = server.uptime {
+            output::key_value("Uptime", uptime);
         }
+    } else {
+        output::status("Server not reachable", false);
     }
+}
 
-    Ok(())
+fn join_or_none(items: &[String]) -> String {
+    if items.is_empty() {
+        "none".to_string()
+    } else {
+        items.join(", ")
+    }
 }
 
-/// Get server information from the gateway.
-async fn get_server_info(base_url: &str) -> Option<ServerInfo> {
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(5))
-        .build()
-        .ok()?;
+/// Describe how requests are authenticated.
+fn auth_mode(config: &GatewayConfig) -> String {
+    let auth = &config.security.auth;
+    if !auth.enabled {
+        return "disabled".to_string();
+    }
+
+    let methods: Vec<&str> = auth
+        .methods
+        .iter()
+        .map(|method| match method {
+            AuthMethod::ApiKey => "api_key",
+            AuthMethod::Jwt => "jwt",
+            AuthMethod::None => "none",
+        })
+        .collect();
+
+    if methods.is_empty() {
+        "none".to_string()
+    } else {
+        methods.join(",")
+    }
+}
+
+/// External systems the configuration sends data to.
+fn enabled_integrations(config: &GatewayConfig) -> Vec<String> {
+    let observability = &config.observability;
+    [
+        ("prometheus", observability.metrics.enabled),
+        ("otlp", observability.tracing.enabled),
+        ("persistence", config.persistence.enabled),
+    ]
+    .into_iter()
+    .filter_map(|(name, enabled)| enabled.then(|| name.to_string()))
+    .collect()
+}
+
+/// Summarize configured providers, probing enabled ones if requested.
+async fn summarize_providers(
+    client: &reqwest::Client,
+    config: &GatewayConfig,
+    probe: bool,
+) -> Vec<ProviderSummary> {
+    let probes = config.providers.iter().map(|provider| async move {
+        let reachable = if probe && provider.enabled {
+            Some(client.get(&provider.endpoint).send().await.is_ok())
+        } else {
+            None
+        };
+
+        ProviderSummary {
+            id: provider.id.clone(),
+            provider_type: provider.provider_type.to_string(),
+            endpoint: provider.endpoint.clone(),
+            enabled: provider.enabled,
+            reachable,
+        }
+    });
+
+    futures::future::join_all(probes).await
+}
+
+/// Ask the running server which cache backend it uses.
+async fn get_cache_backend(client: &reqwest::Client, base_url: &str) -> Option<String> {
+    let url = format!("{}/api/v1/cache/config", base_url.trim_end_matches('/'));
+
+    let response = client.get(&url).send().await.ok()?;
+    if !response.status().is_success() {
+        return None;
+    }
 
+    let body: serde_json::Value = response.json().await.ok()?;
+    body.get("backend").and_then(|v| v.as_str()).map(String::from)
+}
+
+/// Get server information from the gateway.
+async fn get_server_info(client: &reqwest::Client, base_url: &str) -> Option<ServerInfo> {
     let url = format!("{}/health", base_url.trim_end_matches('/'));
 
     let response = client.get(&url).send().await.ok()?;
@@ -175,3 +359,57 @@ fn num_cpus() -> usize {
         .map(|p| p.get())
         .unwrap_or(1)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn offline_args() -> InfoArgs {
+        InfoArgs {
+            build: false,
+            runtime: false,
+            config: None,
+            skip_probe: true,
+            timeout: 1,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_json_includes_version_and_provider_features() {
+        // Nothing listens on the discard port, so the server is unreachable
+        let info = collect_info(&offline_args(), "http://127.0.0.1:9").await.unwrap();
+        let json = serde_json::to_value(CommandResult::success(info)).unwrap();
+
+        assert_eq!(json["data"]["version"], env!("CARGO_PKG_VERSION"));
+
+        let features: Vec<&str> = json["data"]["provider_features"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v.as_str().unwrap())
+            .collect();
+        assert_eq!(features, gateway_providers::compiled_provider_features());
+        assert!(features.contains(&"openai"));
+
+        assert_eq!(json["data"]["config"]["auth_mode"], "disabled");
+        assert!(json["data"].get("server").is_none());
+    }
+
+    #[test]
+    fn test_auth_mode_and_integrations() {
+        let mut config = GatewayConfig::default();
+        assert_eq!(auth_mode(&config), "disabled");
+        assert_eq!(enabled_integrations(&config), vec!["prometheus".to_string()]);
+
+        config.security.auth.enabled = true;
+        config.security.auth.methods = vec![AuthMethod::ApiKey, AuthMethod::Jwt];
+        config.observability.tracing.enabled = true;
+        config.persistence.enabled = true;
+
+        assert_eq!(auth_mode(&config), "api_key,jwt");
+        assert_eq!(
+            enabled_integrations(&config),
+            vec!["prometheus", "otlp", "persistence"]
+        );
+    }
+}
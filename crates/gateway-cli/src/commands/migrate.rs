@@ -152,35 +152,35 @@ async fn execute_run(database_url: String, args: RunArgs, format: OutputFormat)
     migrator.add_migrations(schema::all_migrations());
 
     if args.dry_run {
-        let pending = migrator
-            .get_pending()
+        let plan = migrator
+            .dry_run()
             .await
-            .map_err(|e| anyhow::anyhow!("Failed to get pending: {}", e))?;
+            .map_err(|e| anyhow::anyhow!("Dry run failed: {}", e))?;
 
         match format {
             OutputFormat::Json => {
-                let migrations: Vec<_> = pending
-                    .iter()
-                    .map(|m| MigrationInfo {
-                        version: m.version,
-                        name: m.name.clone(),
-                        status: "pending".to_string(),
-                        applied_at: None,
-                        execution_time_ms: None,
-                    })
-                    .collect();
-
                 let result = CommandResult::success(serde_json::json!({
                     "dry_run": true,
-                    "pending_count": pending.len(),
-                    "migrations": migrations,
+                    "pending_count": plan.pending().count(),
+                    "conflicted_count": plan.conflicted().count(),
+                    "migrations": plan.migrations,
                 }));
                 result.print(format)?;
             }
             OutputFormat::Text => {
-                output::info(&format!("Dry run - {} migrations would be applied:", pending.len()));
-                for m in &pending {
-                    output::key_value(&format!("V{}", m.version), &m.name);
+                output::info(&format!(
+                    "Dry run - {} migrations would be applied:",
+                    plan.pending().count()
+                ));
+                for m in plan.pending() {
+                    let checksum = m.checksum.get(..12).unwrap_or(&m.checksum);
+                    output::key_value(&format!("V{}", m.version), &format!("{} ({checksum})", m.name));
+                }
+                for m in plan.conflicted() {
+                    output::warning(&format!(
+                        "V{} {} has changed since it was applied (checksum mismatch)",
+                        m.version, m.name
+                    ));
                 }
             }
         }
@@ -1,6 +1,7 @@
 //! CLI commands module.
 
 pub mod agent;
+pub mod audit;
 pub mod backend_health;
 pub mod benchmark;
 pub mod cache_status;
@@ -8,6 +9,8 @@ pub mod chat;
 pub mod completions;
 pub mod config;
 pub mod cost;
+pub mod dead_letter;
+pub mod diagnose;
 pub mod health;
 pub mod info;
 pub mod latency;
@@ -1,7 +1,8 @@
 //! # Gateway Configuration
 //!
 //! Configuration management for the LLM Inference Gateway, including:
-//! - Configuration schema and validation
+//! - Configuration schema and validation, including cross-field checks
+//!   and rejection of unknown keys
 //! - Loading from YAML/TOML files
 //! - Hot reload support via file watching
 //! - Environment variable substitution
@@ -12,13 +13,17 @@
 pub mod loader;
 pub mod schema;
 pub mod hot_reload;
+mod validation;
 
 // Re-export main types
 pub use loader::{load_config, ConfigError, ConfigLoader, ConfigSource};
 pub use schema::{
     GatewayConfig, ServerConfig, ProviderConfig, RoutingConfig,
     ResilienceConfig, ObservabilityConfig, SecurityConfig,
-    CircuitBreakerConfig, RetryConfig, RateLimitConfig, RateLimitKeyBy,
-    AuthConfig, TlsConfig,
+    CircuitBreakerConfig, RetryConfig, ProactiveBackoffConfig, RateLimitConfig, RateLimitKeyBy,
+    AuthConfig, TlsConfig, ErrorDetailConfig, ErrorDetailLevel, PersistenceConfig, MirroringConfig,
+    SloConfig, BurnWindowConfig, DeterministicConfig, ModelDefaults, PostProcessingConfig,
+    RequestTraceConfig, ImageLimitsConfig, ReadinessConfig, ProviderOverrideConfig, ResponseCacheConfig, EgressConfig, ProviderCooldownConfig, ContextRoutingSettings, UnauthorizedOverride,
 };
 pub use hot_reload::ConfigWatcher;
+pub use validation::ENV_PROVIDERS;
@@ -4,6 +4,7 @@
 //! with support for environment variable substitution.
 
 use crate::schema::GatewayConfig;
+use crate::validation::unknown_field_error;
 use std::path::Path;
 use thiserror::Error;
 use tokio::fs;
@@ -39,6 +40,10 @@ pub enum ConfigError {
     #[error("Configuration validation error: {0}")]
     Validation(String),
 
+    /// A key the schema does not define, usually a typo
+    #[error("Unknown configuration field: {0}")]
+    UnknownField(String),
+
     /// Unsupported format
     #[error("Unsupported configuration format: {extension}")]
     UnsupportedFormat {
@@ -124,9 +129,7 @@ impl ConfigLoader {
         }
 
         // Validate final configuration
-        config
-            .validate_config()
-            .map_err(|e| ConfigError::Validation(format!("{e:?}")))?;
+        config.validate()?;
 
         info!("Configuration loaded successfully");
         Ok(config)
@@ -176,17 +179,20 @@ impl ConfigLoader {
 
     /// Parse YAML content
     fn parse_yaml(content: &str) -> Result<GatewayConfig, ConfigError> {
-        Ok(serde_yaml::from_str(content)?)
+        serde_yaml::from_str(content)
+            .map_err(|e| unknown_field_error(&e.to_string()).unwrap_or_else(|| e.into()))
     }
 
     /// Parse TOML content
     fn parse_toml(content: &str) -> Result<GatewayConfig, ConfigError> {
-        Ok(toml::from_str(content)?)
+        toml::from_str(content)
+            .map_err(|e| unknown_field_error(&e.to_string()).unwrap_or_else(|| e.into()))
     }
 
     /// Parse JSON content
     fn parse_json(content: &str) -> Result<GatewayConfig, ConfigError> {
-        Ok(serde_json::from_str(content)?)
+        serde_json::from_str(content)
+            .map_err(|e| unknown_field_error(&e.to_string()).unwrap_or_else(|| e.into()))
     }
 
     /// Substitute environment variables in content
@@ -249,6 +255,9 @@ impl ConfigLoader {
             resilience: overlay.resilience,
             observability: overlay.observability,
             security: overlay.security,
+            persistence: overlay.persistence,
+            cache: overlay.cache,
+            egress: overlay.egress,
         }
     }
 
@@ -2,7 +2,7 @@
 //!
 //! This module defines all configuration types with validation and defaults.
 
-use gateway_core::ProviderType;
+use gateway_core::{ProviderErrorKind, ProviderType};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -11,7 +11,7 @@ use validator::Validate;
 
 /// Main gateway configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 #[derive(Default)]
 pub struct GatewayConfig {
     /// Server configuration
@@ -37,6 +37,17 @@ pub struct GatewayConfig {
     /// Security configuration
     #[validate(nested)]
     pub security: SecurityConfig,
+
+    /// Request/response persistence configuration
+    #[validate(nested)]
+    pub persistence: PersistenceConfig,
+
+    /// Response cache configuration
+    #[validate(nested)]
+    pub cache: ResponseCacheConfig,
+
+    /// Outbound host policy for provider clients
+    pub egress: EgressConfig,
 }
 
 
@@ -46,7 +57,7 @@ impl GatewayConfig {
     /// # Errors
     /// Returns validation errors if configuration is invalid
     pub fn validate_config(&self) -> Result<(), validator::ValidationErrors> {
-        self.validate()
+        Validate::validate(self)
     }
 
     /// Get a provider config by ID
@@ -64,7 +75,7 @@ impl GatewayConfig {
 
 /// Server configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct ServerConfig {
     /// Bind host
     #[validate(length(min = 1))]
@@ -81,6 +92,21 @@ pub struct ServerConfig {
     #[serde(with = "humantime_serde")]
     pub request_timeout: Duration,
 
+    /// Maximum total duration of a streaming response
+    ///
+    /// Applies from stream start regardless of chunk activity; independent
+    /// of `request_timeout`.
+    #[serde(with = "humantime_serde")]
+    pub max_stream_duration: Duration,
+
+    /// Overall deadline for producing a response, enforced at the server edge
+    ///
+    /// Covers every phase before the response head is sent (auth, validation,
+    /// queueing and dispatch). A streaming response is only bound by it until
+    /// its headers are sent; after that `max_stream_duration` applies.
+    #[serde(with = "humantime_serde")]
+    pub edge_timeout: Duration,
+
     /// Graceful shutdown timeout
     #[serde(with = "humantime_serde")]
     pub graceful_shutdown_timeout: Duration,
@@ -95,6 +121,73 @@ pub struct ServerConfig {
     /// Enable HTTP/2
     pub http2: bool,
 
+    /// Map deprecated request fields from legacy clients to the current
+    /// request shape
+    pub legacy_request_compat: bool,
+
+    /// Maximum concurrent streaming responses per tenant (unlimited if unset)
+    ///
+    /// Individual API keys can override this with a `max_concurrent_streams`
+    /// metadata entry.
+    pub max_concurrent_streams_per_tenant: Option<u32>,
+
+    /// Upper bound on structured output repair reprompts per request
+    ///
+    /// Requests opt in with `response_format.max_repair_attempts`; larger
+    /// values are clamped to this cap.
+    pub max_json_repair_attempts: u32,
+
+    /// Model to send structured output repair reprompts to
+    ///
+    /// Unset sends them to the model that produced the invalid output.
+    /// Repairs are routed like any request for this model and billed to it.
+    pub json_repair_model: Option<String>,
+
+    /// Estimate the final streaming usage chunk when the provider sends none
+    ///
+    /// Only applies to requests with `stream_options.include_usage`; the
+    /// estimate uses the gateway's ~4 characters per token approximation.
+    pub estimate_stream_usage: bool,
+
+    /// Fresh provider requests allowed when a stream is reset before its
+    /// first chunk
+    ///
+    /// Resets after content has been delivered are always surfaced to the
+    /// client as `connection_reset` errors.
+    pub stream_reset_restarts: u32,
+
+    /// Retry streaming requests per the retry policy until the first chunk
+    ///
+    /// Covers connection setup and any retryable error the stream yields
+    /// before delivering content; errors after the first chunk are always
+    /// surfaced. Supersedes `stream_reset_restarts` when enabled.
+    pub stream_retry_before_first_chunk: bool,
+
+    /// Sub-requests in flight at once when an embeddings batch exceeds the
+    /// provider's per-request input limit and is split
+    pub embedding_batch_concurrency: usize,
+
+    /// Requests in flight at once for a batch the gateway emulates because
+    /// its provider has no batch endpoint
+    pub batch_emulation_concurrency: usize,
+
+    /// Report a SHA-256 of each response's content, in the
+    /// `X-Gateway-Response-Hash` header or the streaming `execution_output`
+    /// event
+    pub response_hash: bool,
+
+    /// Assistant content post-processing for non-streaming responses
+    pub post_processing: PostProcessingConfig,
+
+    /// Request lifecycle traces served by `/admin/requests/{id}/trace`
+    pub request_trace: RequestTraceConfig,
+
+    /// Limits on inline images in chat requests
+    pub images: ImageLimitsConfig,
+
+    /// Provider health required before `/ready` reports ready
+    pub readiness: ReadinessConfig,
+
     /// TLS configuration (optional)
     #[validate(nested)]
     pub tls: Option<TlsConfig>,
@@ -107,10 +200,26 @@ impl Default for ServerConfig {
             port: 8080,
             workers: 0,
             request_timeout: Duration::from_secs(120),
+            max_stream_duration: Duration::from_secs(600),
+            edge_timeout: Duration::from_secs(150),
             graceful_shutdown_timeout: Duration::from_secs(30),
             keep_alive_timeout: Duration::from_secs(60),
             max_request_body_size: 10 * 1024 * 1024, // 10MB
             http2: true,
+            legacy_request_compat: true,
+            max_concurrent_streams_per_tenant: None,
+            max_json_repair_attempts: 3,
+            json_repair_model: None,
+            estimate_stream_usage: false,
+            stream_reset_restarts: 1,
+            stream_retry_before_first_chunk: false,
+            embedding_batch_concurrency: 4,
+            batch_emulation_concurrency: 4,
+            response_hash: false,
+            post_processing: PostProcessingConfig::default(),
+            request_trace: RequestTraceConfig::default(),
+            images: ImageLimitsConfig::default(),
+            readiness: ReadinessConfig::default(),
             tls: None,
         }
     }
@@ -124,8 +233,124 @@ impl ServerConfig {
     }
 }
 
+/// Response post-processing selection
+///
+/// Processors run in order over the assistant's text content, e.g.
+/// `strip_code_fences` to unwrap JSON from a markdown fence. Tool calls are
+/// never rewritten.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PostProcessingConfig {
+    /// Processors applied to every request from a tenant
+    pub tenants: HashMap<String, Vec<String>>,
+
+    /// Whether callers may choose processors per request with the
+    /// `X-Post-Process` header, replacing their tenant's list
+    pub allow_header: bool,
+}
+
+impl Default for PostProcessingConfig {
+    fn default() -> Self {
+        Self {
+            tenants: HashMap::new(),
+            allow_header: true,
+        }
+    }
+}
+
+/// Request lifecycle trace retention and access
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RequestTraceConfig {
+    /// How long a request's trace stays available after it starts
+    #[serde(with = "humantime_serde")]
+    pub retention: Duration,
+
+    /// Maximum traces kept; the oldest are evicted first
+    pub max_traces: usize,
+
+    /// Scope an authenticated caller needs to read traces
+    pub scope: String,
+}
+
+impl Default for RequestTraceConfig {
+    fn default() -> Self {
+        Self {
+            retention: Duration::from_secs(15 * 60),
+            max_traces: 10_000,
+            scope: "gateway:admin".to_string(),
+        }
+    }
+}
+
+/// Readiness gate on provider health
+///
+/// Only enabled providers that support every capability in
+/// `required_capabilities` count towards `min_healthy_providers`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ReadinessConfig {
+    /// Healthy providers needed before the gateway is ready; 0 disables the
+    /// gate
+    pub min_healthy_providers: usize,
+
+    /// Capabilities a provider must support to count, e.g. `chat` or
+    /// `embeddings`
+    pub required_capabilities: Vec<String>,
+
+    /// How long a provider health result is served before it is refreshed
+    #[serde(with = "humantime_serde")]
+    pub provider_cache_ttl: Duration,
+}
+
+impl Default for ReadinessConfig {
+    fn default() -> Self {
+        Self {
+            min_healthy_providers: 1,
+            required_capabilities: Vec::new(),
+            provider_cache_ttl: Duration::from_secs(10),
+        }
+    }
+}
+
+/// Limits on images in chat requests
+///
+/// Inline (`data:` URL) images are decoded and checked before dispatch;
+/// remote image URLs only count towards `max_count`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ImageLimitsConfig {
+    /// Whether images are validated
+    pub enabled: bool,
+
+    /// Maximum decoded size of one inline image, in bytes
+    pub max_bytes: usize,
+
+    /// Maximum images in one request
+    pub max_count: usize,
+
+    /// Maximum width of an inline image, in pixels
+    pub max_width: Option<u32>,
+
+    /// Maximum height of an inline image, in pixels
+    pub max_height: Option<u32>,
+}
+
+impl Default for ImageLimitsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_bytes: 20 * 1024 * 1024, // 20MB
+            max_count: 20,
+            max_width: None,
+            max_height: None,
+        }
+    }
+}
+
 /// TLS configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[serde(deny_unknown_fields)]
 pub struct TlsConfig {
     /// Path to certificate file
     pub cert_path: PathBuf,
@@ -144,6 +369,7 @@ fn default_min_tls_version() -> String {
 
 /// Provider configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[serde(deny_unknown_fields)]
 pub struct ProviderConfig {
     /// Unique provider instance ID
     #[validate(length(min = 1, max = 64))]
@@ -194,6 +420,13 @@ pub struct ProviderConfig {
     #[serde(default)]
     pub headers: HashMap<String, String>,
 
+    /// Forward this provider's SSE stream to clients verbatim instead of
+    /// parsing and re-serializing it
+    ///
+    /// Only honoured for providers with an OpenAI-compatible stream.
+    #[serde(default)]
+    pub stream_passthrough: bool,
+
     /// Provider-specific options
     #[serde(default)]
     pub options: HashMap<String, serde_json::Value>,
@@ -236,7 +469,7 @@ impl ProviderConfig {
 
 /// Provider-specific rate limit configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 #[derive(Default)]
 pub struct ProviderRateLimitConfig {
     /// Requests per minute
@@ -252,7 +485,7 @@ pub struct ProviderRateLimitConfig {
 
 /// Routing configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct RoutingConfig {
     /// Default load balancing strategy
     #[serde(default = "default_strategy")]
@@ -269,6 +502,36 @@ pub struct RoutingConfig {
     /// Enable health-aware routing
     #[serde(default = "default_true")]
     pub health_aware: bool,
+
+    /// Shadow traffic mirroring
+    #[validate(nested)]
+    pub mirroring: MirroringConfig,
+
+    /// Provider IDs each tenant may be routed to
+    ///
+    /// Tenants not listed are unrestricted. An `allowed_providers` claim on
+    /// the caller's JWT or API key takes precedence.
+    #[serde(default)]
+    pub tenant_provider_allowlists: HashMap<String, Vec<String>>,
+
+    /// Deterministic sampling for evaluation runs
+    pub deterministic: DeterministicConfig,
+
+    /// Default sampling parameters by model glob
+    ///
+    /// Applied only to fields the client omitted. Where several patterns
+    /// match, the longest pattern takes precedence field by field.
+    #[serde(default)]
+    pub model_defaults: HashMap<String, ModelDefaults>,
+
+    /// Explicit provider selection by the caller
+    pub provider_override: ProviderOverrideConfig,
+
+    /// Exponential cooldown for providers that keep failing
+    pub cooldown: ProviderCooldownConfig,
+
+    /// Routing by estimated prompt size against provider context windows
+    pub context_routing: ContextRoutingSettings,
 }
 
 fn default_strategy() -> LoadBalancingStrategy {
@@ -282,14 +545,205 @@ impl Default for RoutingConfig {
             rules: Vec::new(),
             model_mappings: HashMap::new(),
             health_aware: true,
+            mirroring: MirroringConfig::default(),
+            tenant_provider_allowlists: HashMap::new(),
+            deterministic: DeterministicConfig::default(),
+            model_defaults: HashMap::new(),
+            provider_override: ProviderOverrideConfig::default(),
+            cooldown: ProviderCooldownConfig::default(),
+            context_routing: ContextRoutingSettings::default(),
         }
     }
 }
 
-/// Load balancing strategy
+/// Exponential cooldown for providers that keep failing
+///
+/// Each consecutive provider fault doubles the provider's cooldown, from
+/// `base` up to `max`, and each success steps it back down. Client errors
+/// (4xx) never count. A cooling-down provider is only routed to when every
+/// other candidate is cooling down too.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProviderCooldownConfig {
+    /// Whether failures put providers into cooldown
+    pub enabled: bool,
+
+    /// Cooldown after the first failure
+    #[serde(with = "humantime_serde")]
+    pub base: Duration,
+
+    /// Longest cooldown, however many failures
+    #[serde(with = "humantime_serde")]
+    pub max: Duration,
+}
+
+impl Default for ProviderCooldownConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            base: Duration::from_secs(1),
+            max: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Routing by estimated prompt size against provider context windows
+///
+/// Providers whose `max_context_length` would overflow are excluded.
+/// Providers that don't report one are never excluded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ContextRoutingSettings {
+    /// Whether requests are routed by estimated size
+    pub enabled: bool,
+
+    /// Required margin over the estimated prompt plus `max_tokens`
+    /// (1.2 = 20% headroom)
+    pub headroom: f64,
+
+    /// Prompts of at least this many estimated tokens go only to the
+    /// largest-context providers available
+    pub large_prompt_threshold: Option<u32>,
+}
+
+impl Default for ContextRoutingSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            headroom: 1.2,
+            large_prompt_threshold: None,
+        }
+    }
+}
+
+/// Per-request provider override
+///
+/// Callers holding `scope` may pin a request to a named provider with the
+/// `X-Gateway-Provider` header or `metadata.preferred_provider`. The pinned
+/// provider must still be healthy, support the model and be in the
+/// caller's provider allowlist.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProviderOverrideConfig {
+    /// Whether overrides are honored; when disabled they are ignored
+    pub enabled: bool,
+
+    /// Scope an authenticated caller needs to override routing
+    pub scope: String,
+
+    /// What to do with an override from a caller without `scope`
+    pub unauthorized: UnauthorizedOverride,
+}
+
+impl Default for ProviderOverrideConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            scope: "gateway:provider_override".to_string(),
+            unauthorized: UnauthorizedOverride::Reject,
+        }
+    }
+}
+
+/// Handling of provider overrides from callers not allowed to make them
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 #[derive(Default)]
+pub enum UnauthorizedOverride {
+    /// Fail the request with `403`
+    #[default]
+    Reject,
+    /// Route the request normally
+    Ignore,
+}
+
+/// Default sampling parameters for a model
+///
+/// Unset fields leave the request as the client sent it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ModelDefaults {
+    /// Sampling temperature
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+
+    /// Nucleus sampling probability
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+
+    /// Top-k sampling
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_k: Option<u32>,
+
+    /// Generation limit, applied when neither `max_tokens` nor
+    /// `max_completion_tokens` is sent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+
+    /// Frequency penalty
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frequency_penalty: Option<f32>,
+
+    /// Presence penalty
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub presence_penalty: Option<f32>,
+}
+
+/// Deterministic sampling for evaluation runs
+///
+/// Requests in scope have `temperature` forced to 0, `top_p` to 1, and a
+/// seed derived from `seed` and their tenant or session, so repeated runs
+/// dispatch identical sampling parameters.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DeterministicConfig {
+    /// Whether the transform is enabled
+    pub enabled: bool,
+
+    /// Tenants whose requests are always made deterministic
+    pub tenants: Vec<String>,
+
+    /// Whether callers may opt a session in with the
+    /// `X-Deterministic-Session` header
+    pub allow_session_header: bool,
+
+    /// Base seed the per-tenant or per-session seed is derived from
+    pub seed: u64,
+}
+
+/// Shadow traffic mirroring
+///
+/// A sample of successful non-streaming requests is replayed against
+/// `shadow_provider` after the client has its response. The shadow response
+/// is discarded; only its diff against the primary is recorded.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[serde(default, deny_unknown_fields)]
+pub struct MirroringConfig {
+    /// Whether mirroring is enabled
+    pub enabled: bool,
+
+    /// Provider ID receiving the mirrored requests
+    pub shadow_provider: Option<String>,
+
+    /// Fraction of requests to mirror (0.0 - 1.0)
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub sample_rate: f64,
+}
+
+impl Default for MirroringConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            shadow_provider: None,
+            sample_rate: 0.1,
+        }
+    }
+}
+
+/// Load balancing strategy
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[derive(Default)]
 pub enum LoadBalancingStrategy {
     /// Round-robin distribution
     #[default]
@@ -300,15 +754,31 @@ pub enum LoadBalancingStrategy {
     CostOptimized,
     /// Weighted random distribution
     Weighted,
+    /// Fewest in-flight requests per unit of provider weight
+    WeightedLeastConnections,
     /// Random selection
     Random,
     /// Always use primary, failover on error
     PrimaryBackup,
+    /// Lowest weighted sum of normalized projected cost, latency and error
+    /// rate (weights are normalized to sum to 1; all zero means round-robin)
+    Composite {
+        /// Weight of projected request cost
+        #[serde(default)]
+        cost_weight: f64,
+        /// Weight of recent latency
+        #[serde(default)]
+        latency_weight: f64,
+        /// Weight of recent error rate
+        #[serde(default)]
+        error_weight: f64,
+    },
 }
 
 
 /// Routing rule
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct RoutingRule {
     /// Rule name
     pub name: String,
@@ -334,6 +804,7 @@ pub struct RoutingRule {
 
 /// Match conditions for routing rules
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct MatchConditions {
     /// Match model pattern (glob)
     #[serde(default)]
@@ -358,7 +829,7 @@ pub struct MatchConditions {
 
 /// Resilience configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 #[derive(Default)]
 pub struct ResilienceConfig {
     /// Circuit breaker configuration
@@ -376,12 +847,16 @@ pub struct ResilienceConfig {
     /// Timeout configuration
     #[validate(nested)]
     pub timeout: TimeoutConfig,
+
+    /// Backoff driven by provider-reported rate limits
+    #[validate(nested)]
+    pub proactive_backoff: ProactiveBackoffConfig,
 }
 
 
 /// Circuit breaker configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct CircuitBreakerConfig {
     /// Whether circuit breaker is enabled
     #[serde(default = "default_true")]
@@ -391,10 +866,13 @@ pub struct CircuitBreakerConfig {
     #[validate(range(min = 1, max = 100))]
     pub failure_threshold: u32,
 
-    /// Number of successes to close the circuit
+    /// Number of consecutive successes to close the circuit
     #[validate(range(min = 1, max = 100))]
     pub success_threshold: u32,
 
+    /// Trial requests allowed in flight while half-open; 0 means unlimited
+    pub half_open_max_probes: u32,
+
     /// Time to wait before testing the circuit
     #[serde(with = "humantime_serde")]
     pub timeout: Duration,
@@ -406,6 +884,9 @@ pub struct CircuitBreakerConfig {
     /// Minimum requests before failure rate is calculated
     #[validate(range(min = 1, max = 100))]
     pub min_requests: u32,
+
+    /// Error kinds counted toward `failure_threshold`
+    pub failure_kinds: Vec<ProviderErrorKind>,
 }
 
 impl Default for CircuitBreakerConfig {
@@ -414,16 +895,18 @@ impl Default for CircuitBreakerConfig {
             enabled: true,
             failure_threshold: 5,
             success_threshold: 3,
+            half_open_max_probes: 3,
             timeout: Duration::from_secs(30),
             window_size: 100,
             min_requests: 10,
+            failure_kinds: ProviderErrorKind::PROVIDER_FAULTS.to_vec(),
         }
     }
 }
 
 /// Retry configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct RetryConfig {
     /// Whether retry is enabled
     #[serde(default = "default_true")]
@@ -471,9 +954,39 @@ impl Default for RetryConfig {
     }
 }
 
+/// Proactive backoff configuration
+///
+/// When enabled, requests to a provider/model whose reported remaining
+/// quota is at or below `threshold` are delayed until the quota resets
+/// (at most `max_delay`), instead of running into upstream 429s.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProactiveBackoffConfig {
+    /// Whether proactive backoff is enabled
+    pub enabled: bool,
+
+    /// Remaining quota fraction (0.0 - 1.0) that triggers backoff
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub threshold: f64,
+
+    /// Maximum delay applied to a single request
+    #[serde(with = "humantime_serde")]
+    pub max_delay: Duration,
+}
+
+impl Default for ProactiveBackoffConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            threshold: 0.05,
+            max_delay: Duration::from_secs(5),
+        }
+    }
+}
+
 /// Bulkhead configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct BulkheadConfig {
     /// Whether bulkhead is enabled
     #[serde(default = "default_true")]
@@ -505,7 +1018,7 @@ impl Default for BulkheadConfig {
 
 /// Timeout configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct TimeoutConfig {
     /// Connection timeout
     #[serde(with = "humantime_serde")]
@@ -537,7 +1050,7 @@ impl Default for TimeoutConfig {
 
 /// Observability configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 #[derive(Default)]
 pub struct ObservabilityConfig {
     /// Metrics configuration
@@ -551,12 +1064,82 @@ pub struct ObservabilityConfig {
     /// Logging configuration
     #[validate(nested)]
     pub logging: LoggingConfig,
+
+    /// SLO burn-rate alert configuration
+    #[validate(nested)]
+    pub slo: SloConfig,
+}
+
+/// SLO burn-rate alert configuration
+///
+/// Availability and latency burn rates are tracked over a long and a short
+/// window per alert; an alert fires while both exceed its threshold.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[serde(default, deny_unknown_fields)]
+pub struct SloConfig {
+    /// Whether request outcomes are tracked against the SLOs
+    pub enabled: bool,
+
+    /// Target fraction of requests that do not fail server-side
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub availability_target: f64,
+
+    /// Requests slower than this count against the latency SLO
+    #[serde(with = "humantime_serde")]
+    pub latency_threshold: Duration,
+
+    /// Target fraction of successful requests under `latency_threshold`
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub latency_target: f64,
+
+    /// Fast-burn alert windows
+    pub fast_burn: BurnWindowConfig,
+
+    /// Slow-burn alert windows
+    pub slow_burn: BurnWindowConfig,
+}
+
+impl Default for SloConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            availability_target: 0.999,
+            latency_threshold: Duration::from_secs(10),
+            latency_target: 0.99,
+            fast_burn: BurnWindowConfig {
+                long_window: Duration::from_secs(60 * 60),
+                short_window: Duration::from_secs(5 * 60),
+                threshold: 14.4,
+            },
+            slow_burn: BurnWindowConfig {
+                long_window: Duration::from_secs(6 * 60 * 60),
+                short_window: Duration::from_secs(30 * 60),
+                threshold: 6.0,
+            },
+        }
+    }
+}
+
+/// Windows and threshold for one burn-rate alert
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BurnWindowConfig {
+    /// Long window, which must show sustained burn
+    #[serde(with = "humantime_serde")]
+    pub long_window: Duration,
+
+    /// Short window, which must still be burning
+    #[serde(with = "humantime_serde")]
+    pub short_window: Duration,
+
+    /// Burn rate both windows must exceed
+    pub threshold: f64,
 }
 
 
 /// Metrics configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct MetricsConfig {
     /// Whether metrics are enabled
     pub enabled: bool,
@@ -570,12 +1153,23 @@ pub struct MetricsConfig {
     /// Histogram buckets for latency
     #[serde(default = "default_latency_buckets")]
     pub latency_buckets: Vec<f64>,
+
+    /// Histogram buckets for prompt and completion token counts
+    #[serde(default = "default_token_buckets")]
+    pub token_buckets: Vec<f64>,
 }
 
 fn default_latency_buckets() -> Vec<f64> {
     vec![0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0]
 }
 
+fn default_token_buckets() -> Vec<f64> {
+    vec![
+        16.0, 32.0, 64.0, 128.0, 256.0, 512.0, 1_024.0, 2_048.0, 4_096.0, 8_192.0, 16_384.0,
+        32_768.0, 65_536.0, 131_072.0,
+    ]
+}
+
 impl Default for MetricsConfig {
     fn default() -> Self {
         Self {
@@ -583,13 +1177,14 @@ impl Default for MetricsConfig {
             endpoint: "/metrics".to_string(),
             histograms: true,
             latency_buckets: default_latency_buckets(),
+            token_buckets: default_token_buckets(),
         }
     }
 }
 
 /// Tracing configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct TracingConfig {
     /// Whether tracing is enabled
     pub enabled: bool,
@@ -618,7 +1213,7 @@ impl Default for TracingConfig {
 
 /// Logging configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct LoggingConfig {
     /// Log level
     pub level: String,
@@ -631,6 +1226,10 @@ pub struct LoggingConfig {
 
     /// Redact sensitive fields in logs
     pub redact_sensitive: bool,
+
+    /// Share of requests whose routing decision is logged (0.0 - 1.0)
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub routing_decision_sample_rate: f64,
 }
 
 impl Default for LoggingConfig {
@@ -640,6 +1239,7 @@ impl Default for LoggingConfig {
             format: LogFormat::Json,
             log_bodies: false,
             redact_sensitive: true,
+            routing_decision_sample_rate: 0.0,
         }
     }
 }
@@ -661,7 +1261,7 @@ pub enum LogFormat {
 
 /// Security configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 #[derive(Default)]
 pub struct SecurityConfig {
     /// Authentication configuration
@@ -674,12 +1274,15 @@ pub struct SecurityConfig {
 
     /// CORS configuration
     pub cors: CorsConfig,
+
+    /// Error detail rendering configuration
+    pub error_detail: ErrorDetailConfig,
 }
 
 
 /// Authentication configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct AuthConfig {
     /// Whether authentication is enabled
     pub enabled: bool,
@@ -721,6 +1324,7 @@ pub enum AuthMethod {
 
 /// API key configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct ApiKeyConfig {
     /// Key identifier
     pub id: String,
@@ -747,6 +1351,7 @@ pub struct ApiKeyConfig {
 
 /// JWT configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct JwtConfig {
     /// JWT secret or public key
     pub secret: String,
@@ -770,7 +1375,7 @@ fn default_jwt_algorithm() -> String {
 
 /// Rate limiting configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct RateLimitConfig {
     /// Whether rate limiting is enabled
     pub enabled: bool,
@@ -818,9 +1423,168 @@ pub enum RateLimitKeyBy {
 }
 
 
+/// How much internal context error responses include
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[derive(Default)]
+pub enum ErrorDetailLevel {
+    /// Generic messages for server errors, client errors unchanged
+    Minimal,
+    /// Error messages as produced by the gateway
+    #[default]
+    Standard,
+    /// Messages plus the internal error chain
+    Debug,
+}
+
+/// Error detail configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ErrorDetailConfig {
+    /// Detail level for regular and anonymous callers
+    pub level: ErrorDetailLevel,
+
+    /// Scope that unlocks `admin_level` for authenticated callers
+    #[serde(default)]
+    pub admin_scope: Option<String>,
+
+    /// Detail level for callers holding `admin_scope`
+    pub admin_level: ErrorDetailLevel,
+}
+
+impl Default for ErrorDetailConfig {
+    fn default() -> Self {
+        Self {
+            level: ErrorDetailLevel::Standard,
+            admin_scope: None,
+            admin_level: ErrorDetailLevel::Debug,
+        }
+    }
+}
+
+/// Request/response persistence configuration
+///
+/// Persistence is strictly opt-in: nothing is written unless `enabled` is set
+/// and a `database_url` is provided.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[serde(default, deny_unknown_fields)]
+pub struct PersistenceConfig {
+    /// Whether completed requests are persisted
+    pub enabled: bool,
+
+    /// Database URL (e.g. `postgres://...` or `sqlite://gateway.db`)
+    #[serde(default)]
+    pub database_url: Option<String>,
+
+    /// Maximum database connections
+    #[validate(range(min = 1))]
+    pub max_connections: u32,
+
+    /// Whether PII is redacted from prompts and responses before storage
+    pub redact_pii: bool,
+
+    /// How long records are kept before the sweeper deletes them
+    #[serde(with = "humantime_serde")]
+    pub retention: Duration,
+
+    /// Interval between retention sweeps
+    #[serde(with = "humantime_serde")]
+    pub sweep_interval: Duration,
+
+    /// How long persisted audit events are kept before they are purged
+    #[serde(with = "humantime_serde")]
+    pub audit_retention: Duration,
+
+    /// Directory expired audit events are archived to (as JSONL) before
+    /// being purged; when unset they are deleted outright
+    #[serde(default)]
+    pub audit_archive_dir: Option<String>,
+
+    /// Longest content, in bytes, kept in persisted exchanges, audit events
+    /// and recorded request errors; longer strings are truncated with a
+    /// marker giving their original length. Responses sent to clients are
+    /// never truncated.
+    #[serde(default)]
+    pub max_stored_content_length: Option<usize>,
+}
+
+impl Default for PersistenceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            database_url: None,
+            max_connections: 5,
+            redact_pii: true,
+            retention: Duration::from_secs(7 * 24 * 60 * 60),
+            sweep_interval: Duration::from_secs(60 * 60),
+            audit_retention: Duration::from_secs(365 * 24 * 60 * 60),
+            audit_archive_dir: None,
+            max_stored_content_length: None,
+        }
+    }
+}
+
+/// Response cache configuration
+///
+/// The cache is opt-in: identical requests are only answered from it when
+/// `enabled` is set.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[serde(default, deny_unknown_fields)]
+pub struct ResponseCacheConfig {
+    /// Whether completed responses are cached
+    pub enabled: bool,
+
+    /// How long a cached response is served
+    #[serde(with = "humantime_serde")]
+    pub default_ttl: Duration,
+
+    /// Maximum number of cached responses
+    #[validate(range(min = 1))]
+    pub max_entries: usize,
+
+    /// Whether expired responses may be served when every provider fails
+    pub serve_stale_on_error: bool,
+
+    /// How long past expiry a response may still be served as stale
+    #[serde(with = "humantime_serde")]
+    pub max_staleness: Duration,
+}
+
+impl Default for ResponseCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            default_ttl: Duration::from_secs(60 * 60),
+            max_entries: 10_000,
+            serve_stale_on_error: false,
+            max_staleness: Duration::from_secs(5 * 60),
+        }
+    }
+}
+
+/// Outbound host policy for provider clients
+///
+/// When enabled, providers only connect to `allowed_hosts`, and never to
+/// loopback, private, link-local or other internal addresses unless
+/// `allow_private_networks` is set. Redirect targets are checked too.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EgressConfig {
+    /// Whether outbound connections are restricted
+    pub enabled: bool,
+
+    /// Hosts that may be contacted; `*.example.com` matches any subdomain
+    ///
+    /// An empty list allows any host, subject to the internal address check.
+    pub allowed_hosts: Vec<String>,
+
+    /// Allow loopback, private, link-local and other internal addresses
+    pub allow_private_networks: bool,
+}
+
 /// CORS configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct CorsConfig {
     /// Whether CORS is enabled
     pub enabled: bool,
@@ -891,6 +1655,7 @@ mod tests {
             priority: 100,
             weight: 100,
             headers: HashMap::new(),
+            stream_passthrough: false,
             options: HashMap::new(),
         };
 
@@ -906,6 +1671,8 @@ mod tests {
         assert_eq!(config.failure_threshold, 5);
         assert_eq!(config.success_threshold, 3);
         assert_eq!(config.timeout, Duration::from_secs(30));
+        assert!(config.failure_kinds.contains(&ProviderErrorKind::Overloaded));
+        assert!(!config.failure_kinds.contains(&ProviderErrorKind::InvalidRequest));
     }
 
     #[test]
@@ -925,6 +1692,43 @@ mod tests {
         assert!(yaml.contains("port: 8080"));
     }
 
+    #[test]
+    fn test_egress_section() {
+        let config: GatewayConfig = serde_yaml::from_str(
+            "egress:\n  enabled: true\n  allowed_hosts: [api.openai.com, \"*.openai.azure.com\"]",
+        )
+        .expect("deserialize");
+        assert!(config.egress.enabled);
+        assert_eq!(config.egress.allowed_hosts.len(), 2);
+        assert!(!config.egress.allow_private_networks);
+        assert!(!GatewayConfig::default().egress.enabled);
+    }
+
+    #[test]
+    fn test_context_routing_is_opt_in() {
+        assert!(!GatewayConfig::default().routing.context_routing.enabled);
+
+        let config: GatewayConfig = serde_yaml::from_str(
+            "routing:\n  context_routing:\n    enabled: true\n    large_prompt_threshold: 32000",
+        )
+        .expect("deserialize");
+        let context = &config.routing.context_routing;
+        assert!(context.enabled);
+        assert!((context.headroom - 1.2).abs() < f64::EPSILON);
+        assert_eq!(context.large_prompt_threshold, Some(32_000));
+    }
+
+    #[test]
+    fn test_error_detail_level() {
+        let config = ErrorDetailConfig::default();
+        assert_eq!(config.level, ErrorDetailLevel::Standard);
+        assert_eq!(config.admin_level, ErrorDetailLevel::Debug);
+
+        let level: ErrorDetailLevel = serde_yaml::from_str("minimal").expect("deserialize");
+        assert_eq!(level, ErrorDetailLevel::Minimal);
+        assert!(ErrorDetailLevel::Minimal < ErrorDetailLevel::Debug);
+    }
+
     #[test]
     fn test_load_balancing_strategy() {
         let strategy: LoadBalancingStrategy =
@@ -934,5 +1738,22 @@ mod tests {
         let strategy: LoadBalancingStrategy =
             serde_yaml::from_str("least_latency").expect("deserialize");
         assert_eq!(strategy, LoadBalancingStrategy::LeastLatency);
+
+        let strategy: LoadBalancingStrategy =
+            serde_yaml::from_str("weighted_least_connections").expect("deserialize");
+        assert_eq!(strategy, LoadBalancingStrategy::WeightedLeastConnections);
+
+        let strategy: LoadBalancingStrategy = serde_yaml::from_str(
+            "!composite\ncost_weight: 0.7\nlatency_weight: 0.3",
+        )
+        .expect("deserialize");
+        assert_eq!(
+            strategy,
+            LoadBalancingStrategy::Composite {
+                cost_weight: 0.7,
+                latency_weight: 0.3,
+                error_weight: 0.0,
+            }
+        );
     }
 }
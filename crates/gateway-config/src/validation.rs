@@ -0,0 +1,237 @@
+//! Cross-field configuration validation.
+//!
+//! Field-level rules (ranges, URLs, lengths) are declared on the schema with
+//! `validator`; this module checks invariants that span sections, such as
+//! routing entries naming providers that exist, and turns serde's unknown
+//! field errors into messages that suggest the intended key.
+
+use crate::loader::ConfigError;
+use crate::schema::GatewayConfig;
+use std::collections::HashSet;
+use validator::Validate;
+
+/// Providers the gateway binary registers from environment variables, by id
+/// and the variable holding the API key
+///
+/// Configuration may refer to these ids without listing them in
+/// `providers`.
+pub const ENV_PROVIDERS: &[(&str, &str)] = &[
+    ("openai", "OPENAI_API_KEY"),
+    ("anthropic", "ANTHROPIC_API_KEY"),
+];
+
+impl GatewayConfig {
+    /// Validate field constraints and cross-field invariants
+    ///
+    /// Every problem found is reported, each prefixed with the path of the
+    /// offending key.
+    ///
+    /// # Errors
+    /// Returns [`ConfigError::Validation`] listing all violations
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        Validate::validate(self).map_err(|e| ConfigError::Validation(e.to_string()))?;
+
+        let issues = self.cross_field_issues();
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError::Validation(issues.join("; ")))
+        }
+    }
+
+    fn cross_field_issues(&self) -> Vec<String> {
+        let mut issues = Vec::new();
+
+        let mut ids = HashSet::new();
+        for (i, provider) in self.providers.iter().enumerate() {
+            if !ids.insert(provider.id.as_str()) {
+                issues.push(format!(
+                    "providers[{i}].id: duplicate provider `{}`",
+                    provider.id
+                ));
+            }
+        }
+
+        ids.extend(ENV_PROVIDERS.iter().map(|(id, _)| *id));
+        let mut check_provider = |path: String, id: &str| {
+            if !ids.contains(id) {
+                issues.push(match suggest(id, ids.iter().copied()) {
+                    Some(suggestion) => {
+                        format!("{path}: unknown provider `{id}` (did you mean `{suggestion}`?)")
+                    }
+                    None => format!("{path}: unknown provider `{id}`"),
+                });
+            }
+        };
+
+        let routing = &self.routing;
+        for (i, rule) in routing.rules.iter().enumerate() {
+            for id in &rule.route_to {
+                check_provider(format!("routing.rules[{i}].route_to"), id);
+            }
+        }
+        for (model, providers) in sorted(&routing.model_mappings) {
+            for id in providers {
+                check_provider(format!("routing.model_mappings.{model}"), id);
+            }
+        }
+        for (tenant, providers) in sorted(&routing.tenant_provider_allowlists) {
+            for id in providers {
+                check_provider(format!("routing.tenant_provider_allowlists.{tenant}"), id);
+            }
+        }
+        if let Some(id) = &routing.mirroring.shadow_provider {
+            check_provider("routing.mirroring.shadow_provider".to_string(), id);
+        }
+
+        if routing.mirroring.enabled && routing.mirroring.shadow_provider.is_none() {
+            issues.push(
+                "routing.mirroring.shadow_provider: required when mirroring is enabled".to_string(),
+            );
+        }
+        if self.persistence.enabled && self.persistence.database_url.is_none() {
+            issues
+                .push("persistence.database_url: required when persistence is enabled".to_string());
+        }
+
+        issues
+    }
+}
+
+/// Map entries in key order, so issues are reported deterministically
+fn sorted<V>(map: &std::collections::HashMap<String, V>) -> Vec<(&String, &V)> {
+    let mut entries: Vec<_> = map.iter().collect();
+    entries.sort_by_key(|(key, _)| *key);
+    entries
+}
+
+/// Rewrite a serde unknown field error with a suggestion, if it is one
+///
+/// Serde reports these as ``unknown field `prot`, expected one of `host`,
+/// `port` ``; the closest expected name is appended as a "did you mean".
+pub(crate) fn unknown_field_error(message: &str) -> Option<ConfigError> {
+    let rest = &message[message.find("unknown field `")? + "unknown field `".len()..];
+    let field = &rest[..rest.find('`')?];
+    let expected = rest[field.len() + 1..].split('`').skip(1).step_by(2);
+
+    Some(ConfigError::UnknownField(match suggest(field, expected) {
+        Some(suggestion) => format!("{message} (did you mean `{suggestion}`?)"),
+        None => message.to_string(),
+    }))
+}
+
+/// The candidate closest to `input`, if it is close enough to be a typo
+fn suggest<'a>(input: &str, candidates: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    let max_distance = (input.chars().count() / 3).max(1);
+    candidates
+        .map(|candidate| (edit_distance(input, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Edit distance between two strings, counting adjacent transpositions
+/// as a single edit
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::loader::{ConfigLoader, ConfigSource};
+
+    async fn load(yaml: &str) -> Result<GatewayConfig, ConfigError> {
+        ConfigLoader::new()
+            .with_source(ConfigSource::Yaml(yaml.to_string()))
+            .load()
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_unknown_field_is_reported_with_suggestion() {
+        let err = load("server:\n  prot: 9090\n").await.unwrap_err();
+
+        let ConfigError::UnknownField(message) = &err else {
+            panic!("expected an unknown field error, got {err}");
+        };
+        assert!(message.contains("unknown field `prot`"));
+        assert!(message.contains("did you mean `port`?"));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_top_level_section_is_rejected() {
+        let err = load("caching:\n  enabled: true\n").await.unwrap_err();
+        assert!(matches!(err, ConfigError::UnknownField(_)));
+        assert!(err.to_string().contains("unknown field `caching`"));
+    }
+
+    #[tokio::test]
+    async fn test_dangling_provider_reference_is_reported() {
+        let yaml = r"
+providers:
+  - id: openai
+    type: openai
+    endpoint: https://api.openai.com/v1
+routing:
+  rules:
+    - name: premium
+      match_conditions:
+        model: gpt-4*
+      route_to: [openai, opneai]
+  model_mappings:
+    claude-3: [anthropic]
+    titan: [bedrock]
+";
+        let err = load(yaml).await.unwrap_err();
+
+        let message = err.to_string();
+        assert!(message.contains(
+            "routing.rules[0].route_to: unknown provider `opneai` (did you mean `openai`?)"
+        ));
+        assert!(message.contains("routing.model_mappings.titan: unknown provider `bedrock`"));
+        assert!(!message.contains("unknown provider `openai`"));
+        // Registered from ANTHROPIC_API_KEY, so it need not be listed
+        assert!(!message.contains("unknown provider `anthropic`"));
+    }
+
+    #[test]
+    fn test_cross_field_invariants() {
+        let mut config = GatewayConfig::default();
+        assert!(config.validate().is_ok());
+
+        config.routing.mirroring.enabled = true;
+        config.persistence.enabled = true;
+        let message = config.validate().unwrap_err().to_string();
+        assert!(message.contains("routing.mirroring.shadow_provider: required"));
+        assert!(message.contains("persistence.database_url: required"));
+    }
+
+    #[test]
+    fn test_suggest() {
+        let candidates = ["host", "port", "workers"];
+        assert_eq!(suggest("prot", candidates.into_iter()), Some("port"));
+        assert_eq!(suggest("wrokers", candidates.into_iter()), Some("workers"));
+        assert_eq!(suggest("tls_cert", candidates.into_iter()), None);
+    }
+}
@@ -0,0 +1,367 @@
+//! Batch types and provider capability.
+//!
+//! A batch is a set of chat completion requests processed offline, in the
+//! style of the OpenAI Batch API. Providers with a native batch endpoint opt
+//! in by implementing [`BatchProvider`] and returning themselves from
+//! [`LLMProvider::as_batch_provider`]; the gateway emulates batches for the
+//! rest by dispatching the requests itself in the background.
+//!
+//! [`LLMProvider::as_batch_provider`]: crate::LLMProvider::as_batch_provider
+
+use crate::error::GatewayError;
+use crate::request::GatewayRequest;
+use crate::response::{GatewayResponse, Usage};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Maximum number of requests in one batch
+pub const MAX_BATCH_REQUESTS: usize = 50_000;
+
+/// Native batch capability
+#[async_trait]
+pub trait BatchProvider: Send + Sync {
+    /// Submit the requests as one batch
+    ///
+    /// Returns the provider's id for the batch.
+    ///
+    /// # Errors
+    /// Returns `GatewayError` if the provider rejects the batch
+    async fn submit_batch(&self, requests: &[BatchItem]) -> Result<String, GatewayError>;
+
+    /// Current status of a submitted batch
+    ///
+    /// # Errors
+    /// Returns `GatewayError` on provider errors
+    async fn batch_status(&self, batch_id: &str) -> Result<BatchProgress, GatewayError>;
+
+    /// Results of a completed batch, one per request that finished
+    ///
+    /// # Errors
+    /// Returns `GatewayError` on provider errors
+    async fn batch_results(&self, batch_id: &str) -> Result<Vec<BatchItemResult>, GatewayError>;
+}
+
+/// Batch submission request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchRequest {
+    /// Chat completion requests to process
+    pub requests: Vec<BatchItem>,
+}
+
+/// One request of a batch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchItem {
+    /// Caller-chosen id, unique within the batch, matching results to requests
+    pub custom_id: String,
+
+    /// Chat completion request
+    pub body: GatewayRequest,
+}
+
+impl BatchRequest {
+    /// Create a batch of the given requests
+    #[must_use]
+    pub fn new(requests: Vec<BatchItem>) -> Self {
+        Self { requests }
+    }
+
+    /// Model every request of the batch targets
+    ///
+    /// Empty for an empty batch.
+    #[must_use]
+    pub fn model(&self) -> &str {
+        self.requests
+            .first()
+            .map_or("", |item| item.body.model.as_str())
+    }
+
+    /// Validate the request
+    ///
+    /// # Errors
+    /// Returns `GatewayError::Validation` if the batch is empty or too large,
+    /// a `custom_id` is empty or repeated, the requests target different
+    /// models, or any of them asks for streaming
+    pub fn validate(&self) -> Result<(), GatewayError> {
+        if self.requests.is_empty() {
+            return Err(GatewayError::validation(
+                "Batch must contain at least one request",
+                Some("requests".to_string()),
+                "invalid_batch",
+            ));
+        }
+
+        if self.requests.len() > MAX_BATCH_REQUESTS {
+            return Err(GatewayError::validation(
+                format!("Batch must contain at most {MAX_BATCH_REQUESTS} requests"),
+                Some("requests".to_string()),
+                "invalid_batch",
+            ));
+        }
+
+        let model = self.model();
+        let mut custom_ids = HashSet::with_capacity(self.requests.len());
+        for item in &self.requests {
+            if item.custom_id.is_empty() {
+                return Err(GatewayError::validation(
+                    "custom_id must not be empty",
+                    Some("custom_id".to_string()),
+                    "invalid_custom_id",
+                ));
+            }
+            if !custom_ids.insert(item.custom_id.as_str()) {
+                return Err(GatewayError::validation(
+                    format!("Duplicate custom_id '{}'", item.custom_id),
+                    Some("custom_id".to_string()),
+                    "duplicate_custom_id",
+                ));
+            }
+            if item.body.model != model {
+                return Err(GatewayError::validation(
+                    format!(
+                        "All requests of a batch must use the same model (found '{model}' and '{}')",
+                        item.body.model
+                    ),
+                    Some("model".to_string()),
+                    "mixed_batch_models",
+                ));
+            }
+            if item.body.stream {
+                return Err(GatewayError::validation(
+                    format!("Request '{}' asks for streaming, which batches do not support", item.custom_id),
+                    Some("stream".to_string()),
+                    "invalid_batch",
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Lifecycle state of a batch (OpenAI compatible)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchStatus {
+    /// Being validated before processing
+    Validating,
+    /// Requests are being processed
+    InProgress,
+    /// All requests processed, results being prepared
+    Finalizing,
+    /// Results are available
+    Completed,
+    /// The batch could not be processed
+    Failed,
+    /// The batch did not finish within the provider's window
+    Expired,
+    /// Being cancelled
+    Cancelling,
+    /// Cancelled before finishing
+    Cancelled,
+}
+
+impl BatchStatus {
+    /// Status name as serialized
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Validating => "validating",
+            Self::InProgress => "in_progress",
+            Self::Finalizing => "finalizing",
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+            Self::Expired => "expired",
+            Self::Cancelling => "cancelling",
+            Self::Cancelled => "cancelled",
+        }
+    }
+
+    /// Whether the batch will not change state again
+    #[must_use]
+    pub fn is_terminal(self) -> bool {
+        matches!(
+            self,
+            Self::Completed | Self::Failed | Self::Expired | Self::Cancelled
+        )
+    }
+}
+
+/// Request counts of a batch
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchRequestCounts {
+    /// Requests in the batch
+    pub total: u32,
+    /// Requests that succeeded
+    pub completed: u32,
+    /// Requests that failed
+    pub failed: u32,
+}
+
+/// Progress of a batch as reported by the provider
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchProgress {
+    /// Lifecycle state
+    pub status: BatchStatus,
+    /// Request counts
+    pub request_counts: BatchRequestCounts,
+}
+
+/// Outcome of one request of a batch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchItemResult {
+    /// `custom_id` of the request
+    pub custom_id: String,
+
+    /// Response, if the request succeeded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response: Option<GatewayResponse>,
+
+    /// Error, if the request failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<BatchItemError>,
+}
+
+impl BatchItemResult {
+    /// Result of a request that succeeded
+    #[must_use]
+    pub fn success(custom_id: impl Into<String>, response: GatewayResponse) -> Self {
+        Self {
+            custom_id: custom_id.into(),
+            response: Some(response),
+            error: None,
+        }
+    }
+
+    /// Result of a request that failed
+    #[must_use]
+    pub fn failure(custom_id: impl Into<String>, code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            custom_id: custom_id.into(),
+            response: None,
+            error: Some(BatchItemError {
+                code: code.into(),
+                message: message.into(),
+            }),
+        }
+    }
+
+    /// Token usage of the request, zero if it failed
+    #[must_use]
+    pub fn usage(&self) -> Usage {
+        self.response
+            .as_ref()
+            .map_or_else(Usage::default, |response| response.usage.clone())
+    }
+}
+
+/// Why one request of a batch failed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchItemError {
+    /// Error code, as in error responses
+    pub code: String,
+    /// Error message
+    pub message: String,
+}
+
+/// Batch object returned by the batch endpoints (OpenAI compatible)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Batch {
+    /// Gateway batch id
+    pub id: String,
+
+    /// Object type, always `batch`
+    pub object: String,
+
+    /// Model the requests target
+    pub model: String,
+
+    /// Provider processing the batch
+    pub provider: String,
+
+    /// Whether the provider processes the batch natively (`native`) or the
+    /// gateway dispatches its requests (`emulated`)
+    pub mode: BatchMode,
+
+    /// Lifecycle state
+    pub status: BatchStatus,
+
+    /// Unix timestamp of submission
+    pub created_at: i64,
+
+    /// Unix timestamp the batch reached a terminal state
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<i64>,
+
+    /// Request counts
+    pub request_counts: BatchRequestCounts,
+
+    /// Token usage of the requests processed so far
+    pub usage: Usage,
+}
+
+/// How a batch is processed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchMode {
+    /// Forwarded to the provider's batch endpoint
+    Native,
+    /// Requests dispatched one by one by the gateway
+    Emulated,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::request::ChatMessage;
+
+    fn item(custom_id: &str, model: &str) -> BatchItem {
+        BatchItem {
+            custom_id: custom_id.to_string(),
+            body: GatewayRequest::builder()
+                .model(model)
+                .message(ChatMessage::user("hi"))
+                .build()
+                .unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_valid_batch() {
+        let batch = BatchRequest::new(vec![item("a", "gpt-4o"), item("b", "gpt-4o")]);
+        assert!(batch.validate().is_ok());
+        assert_eq!(batch.model(), "gpt-4o");
+    }
+
+    #[test]
+    fn test_invalid_batches_rejected() {
+        let err = BatchRequest::new(Vec::new()).validate().unwrap_err();
+        assert_eq!(err.error_code(), "invalid_batch");
+
+        let err = BatchRequest::new(vec![item("a", "gpt-4o"), item("a", "gpt-4o")])
+            .validate()
+            .unwrap_err();
+        assert_eq!(err.error_code(), "duplicate_custom_id");
+
+        let err = BatchRequest::new(vec![item("a", "gpt-4o"), item("b", "gpt-4o-mini")])
+            .validate()
+            .unwrap_err();
+        assert_eq!(err.error_code(), "mixed_batch_models");
+
+        let mut streaming = item("a", "gpt-4o");
+        streaming.body.stream = true;
+        let err = BatchRequest::new(vec![streaming]).validate().unwrap_err();
+        assert_eq!(err.error_code(), "invalid_batch");
+    }
+
+    #[test]
+    fn test_terminal_statuses() {
+        assert!(BatchStatus::Completed.is_terminal());
+        assert!(BatchStatus::Expired.is_terminal());
+        assert!(!BatchStatus::Finalizing.is_terminal());
+        assert_eq!(
+            serde_json::to_value(BatchStatus::InProgress).unwrap(),
+            BatchStatus::InProgress.as_str()
+        );
+    }
+}
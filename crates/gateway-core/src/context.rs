@@ -0,0 +1,222 @@
+//! Request-scoped deadline and cancellation.
+//!
+//! A [`RequestContext`] is created once per incoming request and passed to
+//! every sub-operation (cache lookups, provider dispatch, retries) so they
+//! all share a single deadline instead of each applying its own timeout.
+
+use crate::error::GatewayError;
+use std::future::Future;
+use std::time::Duration;
+use tokio::time::Instant;
+use tokio_util::sync::CancellationToken;
+
+/// Deadline and cancellation token shared by all work done for a request
+///
+/// Cloning is cheap and clones share the same cancellation token.
+#[derive(Debug, Clone, Default)]
+pub struct RequestContext {
+    /// Point in time after which the request should be abandoned
+    deadline: Option<Instant>,
+    /// Total time budget, reported in timeout errors
+    budget: Option<Duration>,
+    /// Cancelled when the request is abandoned
+    token: CancellationToken,
+}
+
+impl RequestContext {
+    /// Create a context with no deadline
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a context that expires `timeout` from now
+    #[must_use]
+    pub fn with_timeout(timeout: Duration) -> Self {
+        Self {
+            deadline: Some(Instant::now() + timeout),
+            budget: Some(timeout),
+            token: CancellationToken::new(),
+        }
+    }
+
+    /// Create a context that expires at `deadline`
+    #[must_use]
+    pub fn with_deadline(deadline: Instant) -> Self {
+        Self {
+            deadline: Some(deadline),
+            budget: Some(deadline.saturating_duration_since(Instant::now())),
+            token: CancellationToken::new(),
+        }
+    }
+
+    /// Derive a context with the same deadline
+    ///
+    /// Cancelling the parent cancels the child, but not the other way round.
+    #[must_use]
+    pub fn child(&self) -> Self {
+        Self {
+            deadline: self.deadline,
+            budget: self.budget,
+            token: self.token.child_token(),
+        }
+    }
+
+    /// The deadline, if any
+    #[must_use]
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    /// Time left until the deadline, `None` if there is no deadline
+    #[must_use]
+    pub fn remaining(&self) -> Option<Duration> {
+        self.deadline
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()))
+    }
+
+    /// Whether the deadline has passed
+    #[must_use]
+    pub fn is_expired(&self) -> bool {
+        self.deadline.is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
+    /// Cancel all work bound to this context
+    pub fn cancel(&self) {
+        self.token.cancel();
+    }
+
+    /// Whether the context was cancelled
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.token.is_cancelled()
+    }
+
+    /// Whether work bound to this context should stop
+    #[must_use]
+    pub fn is_done(&self) -> bool {
+        self.is_cancelled() || self.is_expired()
+    }
+
+    /// Fail fast if the context is already done
+    ///
+    /// # Errors
+    /// Returns `GatewayError::Timeout` once the deadline has passed, or
+    /// `GatewayError::Internal` if the context was cancelled
+    pub fn check(&self) -> Result<(), GatewayError> {
+        if self.is_done() {
+            Err(self.done_error())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Wait until the deadline passes or the context is cancelled
+    pub async fn done(&self) {
+        match self.deadline {
+            Some(deadline) => {
+                tokio::select! {
+                    () = tokio::time::sleep_until(deadline) => {}
+                    () = self.token.cancelled() => {}
+                }
+            }
+            None => self.token.cancelled().await,
+        }
+    }
+
+    /// Wait until the context is cancelled, ignoring the deadline
+    pub async fn cancelled(&self) {
+        self.token.cancelled().await;
+    }
+
+    /// Run `future` until it completes or the context is done
+    ///
+    /// The future is dropped as soon as the deadline passes or the context
+    /// is cancelled, aborting any in-flight I/O it owns.
+    ///
+    /// # Errors
+    /// Returns the future's error, or the error from [`Self::check`] if the
+    /// context finished first
+    pub async fn run<F, T>(&self, future: F) -> Result<T, GatewayError>
+    where
+        F: Future<Output = Result<T, GatewayError>>,
+    {
+        self.check()?;
+        tokio::select! {
+            biased;
+            result = future => result,
+            () = self.done() => Err(self.done_error()),
+        }
+    }
+
+    fn done_error(&self) -> GatewayError {
+        if self.is_cancelled() {
+            GatewayError::internal("Request cancelled")
+        } else {
+            GatewayError::timeout(self.budget.unwrap_or_default())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_remaining_counts_down() {
+        let ctx = RequestContext::with_timeout(Duration::from_secs(2));
+        assert_eq!(ctx.remaining(), Some(Duration::from_secs(2)));
+
+        tokio::time::advance(Duration::from_millis(1500)).await;
+        assert_eq!(ctx.remaining(), Some(Duration::from_millis(500)));
+        assert!(!ctx.is_expired());
+
+        tokio::time::advance(Duration::from_secs(1)).await;
+        assert_eq!(ctx.remaining(), Some(Duration::ZERO));
+        assert!(ctx.is_expired());
+        assert!(matches!(ctx.check(), Err(GatewayError::Timeout { .. })));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_run_times_out_at_deadline() {
+        let ctx = RequestContext::with_timeout(Duration::from_millis(100));
+        let start = Instant::now();
+
+        let result = ctx
+            .run(async {
+                tokio::time::sleep(Duration::from_secs(10)).await;
+                Ok::<_, GatewayError>(())
+            })
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(GatewayError::Timeout { duration }) if duration == Duration::from_millis(100)
+        ));
+        assert_eq!(start.elapsed(), Duration::from_millis(100));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_cancel_propagates_to_child() {
+        let ctx = RequestContext::new();
+        let child = ctx.child();
+
+        let handle = tokio::spawn(async move {
+            child
+                .run(std::future::pending::<Result<(), GatewayError>>())
+                .await
+        });
+        tokio::task::yield_now().await;
+
+        ctx.cancel();
+        let result = handle.await.unwrap();
+        assert!(matches!(result, Err(GatewayError::Internal { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_no_deadline_runs_to_completion() {
+        let ctx = RequestContext::new();
+        assert_eq!(ctx.remaining(), None);
+        assert_eq!(ctx.run(async { Ok::<_, GatewayError>(7) }).await.unwrap(), 7);
+    }
+}
@@ -0,0 +1,605 @@
+//! Embedding types, provider capability, and batch chunking.
+//!
+//! Request and response types follow the OpenAI `/v1/embeddings` format.
+//! Providers opt in by implementing [`EmbeddingProvider`] and returning
+//! themselves from [`LLMProvider::as_embedding_provider`].
+//!
+//! Providers cap how many inputs one request may carry. [`embed_batched`]
+//! splits larger batches into sub-requests within that cap, dispatches them
+//! with bounded concurrency and reassembles the results in input order. A
+//! failed sub-request does not fail the batch; its inputs are reported in
+//! [`EmbeddingResponse::errors`]. [`embed_batched_stream`] dispatches the
+//! same way but yields each sub-request's results as it finishes, followed by
+//! a terminal event with the total usage.
+//!
+//! [`LLMProvider::as_embedding_provider`]: crate::LLMProvider::as_embedding_provider
+
+use crate::error::GatewayError;
+use async_trait::async_trait;
+use futures::stream::{self, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+/// Inputs per request for providers that do not declare their own limit
+pub const DEFAULT_MAX_INPUTS_PER_REQUEST: usize = 2048;
+
+/// Embedding capability
+#[async_trait]
+pub trait EmbeddingProvider: Send + Sync {
+    /// Embed every input of the request
+    ///
+    /// # Errors
+    /// Returns `GatewayError` on provider errors, timeouts, or validation failures
+    async fn embed(&self, request: &EmbeddingRequest) -> Result<EmbeddingResponse, GatewayError>;
+
+    /// Maximum number of inputs the provider accepts in one request
+    fn max_inputs_per_request(&self) -> usize {
+        DEFAULT_MAX_INPUTS_PER_REQUEST
+    }
+}
+
+/// Embedding request (OpenAI compatible)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbeddingRequest {
+    /// Model to use
+    pub model: String,
+
+    /// Text or texts to embed
+    pub input: EmbeddingInput,
+
+    /// Number of dimensions of the returned embeddings, for models that
+    /// support shortening
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dimensions: Option<u32>,
+
+    /// End-user identifier
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+
+    /// Stream progress as sub-batches finish; never sent to providers
+    #[serde(default, skip_serializing)]
+    pub stream: bool,
+}
+
+/// One text or a batch of texts
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum EmbeddingInput {
+    /// A single text
+    Single(String),
+    /// A batch of texts
+    Batch(Vec<String>),
+}
+
+impl EmbeddingInput {
+    /// The texts to embed, in order
+    #[must_use]
+    pub fn as_slice(&self) -> &[String] {
+        match self {
+            Self::Single(text) => std::slice::from_ref(text),
+            Self::Batch(texts) => texts,
+        }
+    }
+}
+
+impl EmbeddingRequest {
+    /// Create a request embedding `input` with `model`
+    #[must_use]
+    pub fn new(model: impl Into<String>, input: Vec<String>) -> Self {
+        Self {
+            model: model.into(),
+            input: EmbeddingInput::Batch(input),
+            dimensions: None,
+            user: None,
+            stream: false,
+        }
+    }
+
+    /// Set the number of dimensions
+    #[must_use]
+    pub fn with_dimensions(mut self, dimensions: u32) -> Self {
+        self.dimensions = Some(dimensions);
+        self
+    }
+
+    /// Validate the request
+    ///
+    /// # Errors
+    /// Returns `GatewayError::Validation` if there are no inputs or
+    /// `dimensions` is zero
+    pub fn validate(&self) -> Result<(), GatewayError> {
+        if self.input.as_slice().is_empty() {
+            return Err(GatewayError::validation(
+                "Input must not be empty",
+                Some("input".to_string()),
+                "invalid_input",
+            ));
+        }
+
+        if self.dimensions == Some(0) {
+            return Err(GatewayError::validation(
+                "dimensions must be at least 1",
+                Some("dimensions".to_string()),
+                "invalid_dimensions",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Copy of this request for a subset of its inputs
+    fn with_input(&self, input: Vec<String>) -> Self {
+        Self {
+            input: EmbeddingInput::Batch(input),
+            ..self.clone()
+        }
+    }
+}
+
+/// Embedding response (OpenAI compatible)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbeddingResponse {
+    /// Object type, always `list`
+    pub object: String,
+
+    /// Embeddings for the inputs that succeeded, ordered by index
+    pub data: Vec<Embedding>,
+
+    /// Model that produced the embeddings
+    pub model: String,
+
+    /// Token usage across all sub-requests
+    pub usage: EmbeddingUsage,
+
+    /// Inputs whose sub-request failed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<EmbeddingFailure>,
+}
+
+/// Embedding of one input
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Embedding {
+    /// Object type, always `embedding`
+    pub object: String,
+
+    /// Position of the input in the request
+    pub index: usize,
+
+    /// Embedding vector
+    pub embedding: Vec<f32>,
+}
+
+impl Embedding {
+    /// Create an embedding for the input at `index`
+    #[must_use]
+    pub fn new(index: usize, embedding: Vec<f32>) -> Self {
+        Self {
+            object: "embedding".to_string(),
+            index,
+            embedding,
+        }
+    }
+}
+
+/// Token usage of an embedding request
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmbeddingUsage {
+    /// Tokens in the inputs
+    pub prompt_tokens: u32,
+    /// Total tokens billed
+    pub total_tokens: u32,
+}
+
+impl EmbeddingUsage {
+    /// Add another request's usage to this one
+    pub fn add(&mut self, other: &Self) {
+        self.prompt_tokens = self.prompt_tokens.saturating_add(other.prompt_tokens);
+        self.total_tokens = self.total_tokens.saturating_add(other.total_tokens);
+    }
+}
+
+/// An input that could not be embedded
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmbeddingFailure {
+    /// Position of the input in the request
+    pub index: usize,
+    /// Error code, as in error responses
+    pub code: String,
+    /// Error message
+    pub message: String,
+}
+
+/// Progress of a streamed batch embedding
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum EmbeddingProgress {
+    /// A sub-request finished
+    Chunk {
+        /// Positions of the sub-request's inputs in the request
+        indices: Range<usize>,
+        /// Embeddings of the inputs that succeeded
+        data: Vec<Embedding>,
+        /// Inputs that failed
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        errors: Vec<EmbeddingFailure>,
+        /// Inputs finished so far, embedded or failed
+        completed: usize,
+        /// Inputs in the request
+        total: usize,
+        /// Token usage of the sub-requests finished so far
+        usage: EmbeddingUsage,
+    },
+    /// Every sub-request finished
+    Done {
+        /// Model that produced the embeddings
+        model: String,
+        /// Token usage across all sub-requests
+        usage: EmbeddingUsage,
+        /// Inputs embedded
+        embedded: usize,
+        /// Inputs that failed
+        failed: usize,
+    },
+}
+
+/// Outcome of one sub-request, indexed within the whole batch
+struct SubBatch {
+    indices: Range<usize>,
+    result: Result<EmbeddingResponse, GatewayError>,
+}
+
+impl SubBatch {
+    /// One failure per input of a failed sub-request
+    fn failures(indices: Range<usize>, error: &GatewayError) -> Vec<EmbeddingFailure> {
+        indices
+            .map(|index| EmbeddingFailure {
+                index,
+                code: error.error_code().to_string(),
+                message: error.to_string(),
+            })
+            .collect()
+    }
+}
+
+/// Split a batch within the provider's input limit and dispatch the
+/// sub-requests, yielding their outcomes in input order
+fn dispatch<'a>(
+    provider: &'a dyn EmbeddingProvider,
+    request: &'a EmbeddingRequest,
+    max_concurrency: usize,
+) -> impl Stream<Item = SubBatch> + 'a {
+    let chunk_size = provider.max_inputs_per_request().max(1);
+    // (offset of the first input, sub-request)
+    let sub_requests: Vec<(usize, EmbeddingRequest)> = request
+        .input
+        .as_slice()
+        .chunks(chunk_size)
+        .enumerate()
+        .map(|(chunk, texts)| (chunk * chunk_size, request.with_input(texts.to_vec())))
+        .collect();
+
+    stream::iter(sub_requests)
+        .map(move |(offset, sub_request)| async move {
+            let indices = offset..offset + sub_request.input.as_slice().len();
+            let mut result = provider.embed(&sub_request).await;
+            if let Ok(response) = &mut result {
+                for embedding in &mut response.data {
+                    embedding.index += offset;
+                }
+            }
+            SubBatch { indices, result }
+        })
+        .buffered(max_concurrency.max(1))
+}
+
+/// Embed a batch of any size, splitting it within the provider's input limit
+///
+/// At most `max_concurrency` sub-requests are in flight at once. Results are
+/// reassembled in input order with usage summed over the sub-requests.
+///
+/// # Errors
+/// Returns the provider's error if the batch fits in one request or every
+/// sub-request fails. Otherwise failed inputs are listed in
+/// [`EmbeddingResponse::errors`].
+pub async fn embed_batched(
+    provider: &dyn EmbeddingProvider,
+    request: &EmbeddingRequest,
+    max_concurrency: usize,
+) -> Result<EmbeddingResponse, GatewayError> {
+    let inputs = request.input.as_slice();
+    if inputs.len() <= provider.max_inputs_per_request().max(1) {
+        return provider.embed(request).await;
+    }
+
+    let results: Vec<SubBatch> = dispatch(provider, request, max_concurrency).collect().await;
+
+    let mut merged = EmbeddingResponse {
+        object: "list".to_string(),
+        data: Vec::with_capacity(inputs.len()),
+        model: request.model.clone(),
+        usage: EmbeddingUsage::default(),
+        errors: Vec::new(),
+    };
+    let mut first_error = None;
+
+    for SubBatch { indices, result } in results {
+        match result {
+            Ok(response) => {
+                merged.model = response.model;
+                merged.usage.add(&response.usage);
+                merged.data.extend(response.data);
+            }
+            Err(e) => {
+                merged.errors.extend(SubBatch::failures(indices, &e));
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+
+    match first_error {
+        Some(e) if merged.data.is_empty() => Err(e),
+        _ => {
+            merged.data.sort_by_key(|embedding| embedding.index);
+            Ok(merged)
+        }
+    }
+}
+
+/// Embed a batch of any size, yielding each sub-request's results as it
+/// finishes
+///
+/// Sub-requests are dispatched as in [`embed_batched`], and a
+/// [`EmbeddingProgress::Chunk`] is yielded for each in input order, with the
+/// running usage. A failed sub-request is reported in its chunk's `errors`.
+/// The stream ends with one [`EmbeddingProgress::Done`] carrying the total
+/// usage.
+pub fn embed_batched_stream<'a>(
+    provider: &'a dyn EmbeddingProvider,
+    request: &'a EmbeddingRequest,
+    max_concurrency: usize,
+) -> impl Stream<Item = EmbeddingProgress> + 'a {
+    let total = request.input.as_slice().len();
+    let sub_batches = Box::pin(dispatch(provider, request, max_concurrency));
+    let done = EmbeddingProgress::Done {
+        model: request.model.clone(),
+        usage: EmbeddingUsage::default(),
+        embedded: 0,
+        failed: 0,
+    };
+
+    // The running totals are kept as the terminal event, which is yielded
+    // once every sub-request has finished
+    stream::unfold((sub_batches, Some(done)), move |(mut sub_batches, done)| async move {
+        let EmbeddingProgress::Done {
+            mut model,
+            mut usage,
+            mut embedded,
+            mut failed,
+        } = done?
+        else {
+            return None;
+        };
+
+        let Some(SubBatch { indices, result }) = sub_batches.next().await else {
+            let done = EmbeddingProgress::Done {
+                model,
+                usage,
+                embedded,
+                failed,
+            };
+            return Some((done, (sub_batches, None)));
+        };
+
+        let (data, errors) = match result {
+            Ok(response) => {
+                model = response.model;
+                usage.add(&response.usage);
+                (response.data, Vec::new())
+            }
+            Err(e) => (Vec::new(), SubBatch::failures(indices.clone(), &e)),
+        };
+        embedded += data.len();
+        failed += errors.len();
+        let chunk = EmbeddingProgress::Chunk {
+            indices,
+            data,
+            errors,
+            completed: embedded + failed,
+            total,
+            usage,
+        };
+        let done = EmbeddingProgress::Done {
+            model,
+            usage,
+            embedded,
+            failed,
+        };
+        Some((chunk, (sub_batches, Some(done))))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    /// Embeds each text as `[len]`, accepting at most `limit` inputs
+    struct LengthEmbedder {
+        limit: usize,
+        failing: Option<&'static str>,
+        batches: Mutex<Vec<Vec<String>>>,
+        in_flight: AtomicUsize,
+        max_in_flight: AtomicUsize,
+    }
+
+    impl LengthEmbedder {
+        fn new(limit: usize, failing: Option<&'static str>) -> Self {
+            Self {
+                limit,
+                failing,
+                batches: Mutex::new(Vec::new()),
+                in_flight: AtomicUsize::new(0),
+                max_in_flight: AtomicUsize::new(0),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl EmbeddingProvider for LengthEmbedder {
+        async fn embed(&self, request: &EmbeddingRequest) -> Result<EmbeddingResponse, GatewayError> {
+            let texts = request.input.as_slice().to_vec();
+            assert!(texts.len() <= self.limit, "batch over the provider limit");
+            self.batches.lock().unwrap().push(texts.clone());
+
+            let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+            tokio::task::yield_now().await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+            if self.failing.is_some_and(|bad| texts.iter().any(|t| t == bad)) {
+                return Err(GatewayError::provider("embedder", "bad input", Some(400), false));
+            }
+
+            let tokens = u32::try_from(texts.len()).unwrap_or(u32::MAX);
+            Ok(EmbeddingResponse {
+                object: "list".to_string(),
+                data: texts
+                    .iter()
+                    .enumerate()
+                    .map(|(i, text)| Embedding::new(i, vec![text.len() as f32]))
+                    .collect(),
+                model: request.model.clone(),
+                usage: EmbeddingUsage {
+                    prompt_tokens: tokens,
+                    total_tokens: tokens,
+                },
+                errors: Vec::new(),
+            })
+        }
+
+        fn max_inputs_per_request(&self) -> usize {
+            self.limit
+        }
+    }
+
+    fn texts(count: usize) -> Vec<String> {
+        (1..=count).map(|len| "x".repeat(len)).collect()
+    }
+
+    #[tokio::test]
+    async fn test_oversized_batch_is_split_and_reassembled_in_order() {
+        let provider = LengthEmbedder::new(2, None);
+        let request = EmbeddingRequest::new("text-embedding-3-small", texts(5));
+
+        let response = embed_batched(&provider, &request, 2).await.unwrap();
+
+        assert_eq!(provider.batches.lock().unwrap().len(), 3);
+        assert!(provider.max_in_flight.load(Ordering::SeqCst) <= 2);
+        assert!(response.errors.is_empty());
+        let indices: Vec<usize> = response.data.iter().map(|e| e.index).collect();
+        assert_eq!(indices, [0, 1, 2, 3, 4]);
+        for embedding in &response.data {
+            assert_eq!(embedding.embedding, [(embedding.index + 1) as f32]);
+        }
+        assert_eq!(response.usage, EmbeddingUsage { prompt_tokens: 5, total_tokens: 5 });
+    }
+
+    #[tokio::test]
+    async fn test_sub_batch_error_is_attributed_to_its_indices() {
+        // "xxx" is input 2, in the second sub-batch of inputs 2 and 3
+        let provider = LengthEmbedder::new(2, Some("xxx"));
+        let request = EmbeddingRequest::new("text-embedding-3-small", texts(5));
+
+        let response = embed_batched(&provider, &request, 4).await.unwrap();
+
+        let indices: Vec<usize> = response.data.iter().map(|e| e.index).collect();
+        assert_eq!(indices, [0, 1, 4]);
+        let failed: Vec<usize> = response.errors.iter().map(|e| e.index).collect();
+        assert_eq!(failed, [2, 3]);
+        assert_eq!(response.errors[0].code, "provider_error");
+        assert_eq!(response.usage.prompt_tokens, 3);
+    }
+
+    #[tokio::test]
+    async fn test_small_batch_and_total_failure_return_provider_error() {
+        let provider = LengthEmbedder::new(2, Some("x"));
+        let request = EmbeddingRequest::new("text-embedding-3-small", texts(1));
+        assert!(embed_batched(&provider, &request, 2).await.is_err());
+
+        let provider = LengthEmbedder::new(1, Some("x"));
+        let request = EmbeddingRequest::new("text-embedding-3-small", vec!["x".to_string(); 3]);
+        assert!(embed_batched(&provider, &request, 2).await.is_err());
+        assert_eq!(provider.batches.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_stream_reports_sub_batches_in_order_then_total_usage() {
+        // "xxx" is input 2, in the second sub-batch of inputs 2 and 3
+        let provider = LengthEmbedder::new(2, Some("xxx"));
+        let request = EmbeddingRequest::new("text-embedding-3-small", texts(5));
+
+        let events: Vec<EmbeddingProgress> =
+            embed_batched_stream(&provider, &request, 3).collect().await;
+
+        assert_eq!(events.len(), 4);
+        let chunks: Vec<(Range<usize>, Vec<usize>, usize, u32)> = events[..3]
+            .iter()
+            .filter_map(|event| match event {
+                EmbeddingProgress::Chunk {
+                    indices,
+                    data,
+                    completed,
+                    total,
+                    usage,
+                    ..
+                } => {
+                    assert_eq!(*total, 5);
+                    Some((
+                        indices.clone(),
+                        data.iter().map(|e| e.index).collect(),
+                        *completed,
+                        usage.prompt_tokens,
+                    ))
+                }
+                EmbeddingProgress::Done { .. } => None,
+            })
+            .collect();
+        assert_eq!(
+            chunks,
+            [
+                (0..2, vec![0, 1], 2, 2),
+                (2..4, vec![], 4, 2),
+                (4..5, vec![4], 5, 3),
+            ]
+        );
+        assert!(matches!(
+            &events[1],
+            EmbeddingProgress::Chunk { errors, .. } if errors.iter().map(|e| e.index).eq([2, 3])
+        ));
+
+        assert_eq!(
+            events[3],
+            EmbeddingProgress::Done {
+                model: "text-embedding-3-small".to_string(),
+                usage: EmbeddingUsage { prompt_tokens: 3, total_tokens: 3 },
+                embedded: 3,
+                failed: 2,
+            }
+        );
+        let json = serde_json::to_value(&events[3]).unwrap();
+        assert_eq!(json["type"], "done");
+    }
+
+    #[test]
+    fn test_input_forms_and_validation() {
+        let single: EmbeddingRequest =
+            serde_json::from_str(r#"{"model":"m","input":"hello"}"#).unwrap();
+        assert_eq!(single.input.as_slice(), ["hello"]);
+        assert!(single.validate().is_ok());
+
+        let empty: EmbeddingRequest = serde_json::from_str(r#"{"model":"m","input":[]}"#).unwrap();
+        assert!(empty.validate().is_err());
+        assert!(EmbeddingRequest::new("m", texts(1)).with_dimensions(0).validate().is_err());
+    }
+}
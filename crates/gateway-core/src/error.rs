@@ -98,6 +98,39 @@ pub enum GatewayError {
         provider: String,
     },
 
+    /// Provider does not support the requested capability
+    #[error("Provider {provider} does not support {capability}")]
+    UnsupportedCapability {
+        /// Provider that was selected
+        provider: String,
+        /// Capability that was requested
+        capability: String,
+    },
+
+    /// Provider safety filters blocked the prompt or the response
+    #[error("Content filtered by {provider}: {message}")]
+    ContentFilter {
+        /// Provider that applied the filter
+        provider: String,
+        /// Error message
+        message: String,
+        /// Harm categories that triggered the filter
+        categories: Vec<String>,
+    },
+
+    /// Prompt does not fit in the model's context window
+    #[error("Context length exceeded for {model}: request of about {requested} tokens is too long")]
+    ContextLengthExceeded {
+        /// Provider that rejected the request
+        provider: String,
+        /// Model that was requested
+        model: String,
+        /// Model's context window in tokens, if known
+        max_context: Option<u32>,
+        /// Request size in tokens, as reported by the provider or estimated
+        requested: u32,
+    },
+
     /// Request payload too large
     #[error("Request payload too large: {size} bytes exceeds limit of {limit} bytes")]
     PayloadTooLarge {
@@ -112,6 +145,8 @@ pub enum GatewayError {
     Streaming {
         /// Error message
         message: String,
+        /// What ended the stream
+        kind: StreamError,
     },
 
     /// Configuration error
@@ -147,6 +182,9 @@ impl GatewayError {
             Self::Timeout { .. } => StatusCode::GATEWAY_TIMEOUT,
             Self::ModelNotFound { .. } | Self::ProviderNotFound { .. } => StatusCode::NOT_FOUND,
             Self::PayloadTooLarge { .. } => StatusCode::PAYLOAD_TOO_LARGE,
+            Self::UnsupportedCapability { .. }
+            | Self::ContentFilter { .. }
+            | Self::ContextLengthExceeded { .. } => StatusCode::BAD_REQUEST,
             Self::Streaming { .. } | Self::Configuration { .. } | Self::Internal { .. } => {
                 StatusCode::INTERNAL_SERVER_ERROR
             }
@@ -171,7 +209,11 @@ impl GatewayError {
     #[must_use]
     pub fn error_type(&self) -> &'static str {
         match self {
-            Self::Validation { .. } | Self::PayloadTooLarge { .. } => "invalid_request_error",
+            Self::Validation { .. }
+            | Self::PayloadTooLarge { .. }
+            | Self::UnsupportedCapability { .. }
+            | Self::ContentFilter { .. }
+            | Self::ContextLengthExceeded { .. } => "invalid_request_error",
             Self::Authentication { .. } => "authentication_error",
             Self::Authorization { .. } => "authorization_error",
             Self::RateLimit { .. } => "rate_limit_error",
@@ -201,6 +243,13 @@ impl GatewayError {
             Self::ModelNotFound { .. } => "model_not_found",
             Self::ProviderNotFound { .. } => "provider_not_found",
             Self::PayloadTooLarge { .. } => "payload_too_large",
+            Self::UnsupportedCapability { .. } => "unsupported_capability",
+            Self::ContentFilter { .. } => "content_filter",
+            Self::ContextLengthExceeded { .. } => "context_length_exceeded",
+            Self::Streaming {
+                kind: StreamError::ConnectionReset,
+                ..
+            } => "connection_reset",
             Self::Streaming { .. } => "streaming_error",
             Self::Configuration { .. } => "configuration_error",
             Self::Internal { .. } => "internal_error",
@@ -293,13 +342,173 @@ impl GatewayError {
         Self::RateLimit { retry_after, limit }
     }
 
+    /// Create an unsupported capability error
+    #[must_use]
+    pub fn unsupported_capability(provider: impl Into<String>, capability: impl Into<String>) -> Self {
+        Self::UnsupportedCapability {
+            provider: provider.into(),
+            capability: capability.into(),
+        }
+    }
+
+    /// Create a content filter error
+    #[must_use]
+    pub fn content_filter(
+        provider: impl Into<String>,
+        message: impl Into<String>,
+        categories: Vec<String>,
+    ) -> Self {
+        Self::ContentFilter {
+            provider: provider.into(),
+            message: message.into(),
+            categories,
+        }
+    }
+
+    /// Create a context length exceeded error
+    ///
+    /// Not retryable: the same prompt will be rejected again until it is
+    /// shortened.
+    #[must_use]
+    pub fn context_length_exceeded(
+        provider: impl Into<String>,
+        model: impl Into<String>,
+        max_context: Option<u32>,
+        requested: u32,
+    ) -> Self {
+        Self::ContextLengthExceeded {
+            provider: provider.into(),
+            model: model.into(),
+            max_context,
+            requested,
+        }
+    }
+
+    /// Check if the prompt did not fit in the model's context window
+    #[must_use]
+    pub fn is_context_length_exceeded(&self) -> bool {
+        matches!(self, Self::ContextLengthExceeded { .. })
+    }
+
     /// Create a streaming error
     #[must_use]
     pub fn streaming(message: impl Into<String>) -> Self {
         Self::Streaming {
             message: message.into(),
+            kind: StreamError::Other,
         }
     }
+
+    /// Create a connection reset error
+    ///
+    /// Used when the provider connection drops before the response body is
+    /// complete. Retryable, as the failure is in transport, not the request.
+    #[must_use]
+    pub fn connection_reset(message: impl Into<String>) -> Self {
+        Self::Streaming {
+            message: message.into(),
+            kind: StreamError::ConnectionReset,
+        }
+    }
+
+    /// Check if the provider connection was reset mid-response
+    #[must_use]
+    pub fn is_connection_reset(&self) -> bool {
+        matches!(
+            self,
+            Self::Streaming {
+                kind: StreamError::ConnectionReset,
+                ..
+            }
+        )
+    }
+
+    /// Classify this error by what it says about the provider's health
+    #[must_use]
+    pub fn provider_error_kind(&self) -> ProviderErrorKind {
+        match self {
+            Self::Timeout { .. } => ProviderErrorKind::Timeout,
+            Self::Streaming {
+                kind: StreamError::ConnectionReset,
+                ..
+            } => ProviderErrorKind::Connection,
+            Self::Provider {
+                status_code: Some(status),
+                ..
+            } => match status {
+                429 => ProviderErrorKind::RateLimited,
+                503 | 529 => ProviderErrorKind::Overloaded,
+                500..=599 => ProviderErrorKind::ServerError,
+                401 | 403 => ProviderErrorKind::Authentication,
+                400..=499 => ProviderErrorKind::InvalidRequest,
+                _ => ProviderErrorKind::Other,
+            },
+            // Transport failures carry no status and are marked retryable
+            Self::Provider {
+                status_code: None,
+                retryable: true,
+                ..
+            } => ProviderErrorKind::Connection,
+            Self::RateLimit { .. } => ProviderErrorKind::RateLimited,
+            Self::Authentication { .. } | Self::Authorization { .. } => {
+                ProviderErrorKind::Authentication
+            }
+            Self::ContentFilter { .. } => ProviderErrorKind::ContentFilter,
+            Self::Validation { .. }
+            | Self::UnsupportedCapability { .. }
+            | Self::ContextLengthExceeded { .. }
+            | Self::PayloadTooLarge { .. } => ProviderErrorKind::InvalidRequest,
+            _ => ProviderErrorKind::Other,
+        }
+    }
+}
+
+/// Classification of errors that end a response stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StreamError {
+    /// The connection was reset or the body ended before it was complete
+    ConnectionReset,
+    /// Any other streaming failure
+    #[default]
+    Other,
+}
+
+/// Classification of errors by what they say about the provider's health
+///
+/// Timeouts, connection failures, 5xx responses and overloads point at the
+/// provider; rejected requests and rate limits point at the caller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProviderErrorKind {
+    /// The request timed out
+    Timeout,
+    /// The connection failed or was reset
+    Connection,
+    /// The provider failed with a 5xx other than an overload
+    ServerError,
+    /// The provider is overloaded (503, or 529 from Anthropic)
+    Overloaded,
+    /// The caller was rate limited (429)
+    RateLimited,
+    /// The request was rejected as invalid (other 4xx)
+    InvalidRequest,
+    /// Credentials were rejected (401, 403)
+    Authentication,
+    /// Safety filters blocked the prompt or the response
+    ContentFilter,
+    /// Anything else
+    Other,
+}
+
+impl ProviderErrorKind {
+    /// Kinds that count against a provider's health by default
+    pub const PROVIDER_FAULTS: [Self; 4] = [
+        Self::Timeout,
+        Self::Connection,
+        Self::ServerError,
+        Self::Overloaded,
+    ];
 }
 
 impl From<ValidationError> for GatewayError {
@@ -402,6 +611,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_connection_reset_classification() {
+        let err = GatewayError::connection_reset("connection closed before message completed");
+        assert!(err.is_connection_reset());
+        assert!(err.is_retryable());
+        assert_eq!(err.error_code(), "connection_reset");
+        assert_eq!(err.error_type(), "streaming_error");
+
+        let err = GatewayError::streaming("bad chunk");
+        assert!(!err.is_connection_reset());
+        assert_eq!(err.error_code(), "streaming_error");
+    }
+
+    #[test]
+    fn test_provider_error_kinds() {
+        let kind = |status| GatewayError::provider("openai", "error", Some(status), false).provider_error_kind();
+        assert_eq!(kind(400), ProviderErrorKind::InvalidRequest);
+        assert_eq!(kind(422), ProviderErrorKind::InvalidRequest);
+        assert_eq!(kind(401), ProviderErrorKind::Authentication);
+        assert_eq!(kind(429), ProviderErrorKind::RateLimited);
+        assert_eq!(kind(500), ProviderErrorKind::ServerError);
+        assert_eq!(kind(503), ProviderErrorKind::Overloaded);
+        assert_eq!(kind(529), ProviderErrorKind::Overloaded);
+
+        let connect = GatewayError::provider("openai", "Request failed: connect", None, true);
+        assert_eq!(connect.provider_error_kind(), ProviderErrorKind::Connection);
+        assert_eq!(
+            GatewayError::connection_reset("reset").provider_error_kind(),
+            ProviderErrorKind::Connection
+        );
+        assert_eq!(
+            GatewayError::timeout(Duration::from_secs(30)).provider_error_kind(),
+            ProviderErrorKind::Timeout
+        );
+    }
+
     #[test]
     fn test_error_retryability() {
         assert!(!GatewayError::validation("test", None, "test").is_retryable());
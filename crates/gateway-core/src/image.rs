@@ -0,0 +1,223 @@
+//! Image generation types and provider capability.
+//!
+//! Request and response types follow the OpenAI `/v1/images/generations`
+//! format. Providers opt in by implementing [`ImageProvider`] and returning
+//! themselves from [`LLMProvider::as_image_provider`].
+//!
+//! [`LLMProvider::as_image_provider`]: crate::LLMProvider::as_image_provider
+
+use crate::error::GatewayError;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of images per request
+pub const MAX_IMAGES_PER_REQUEST: u32 = 10;
+
+/// Image generation capability
+#[async_trait]
+pub trait ImageProvider: Send + Sync {
+    /// Generate images from a prompt
+    ///
+    /// # Errors
+    /// Returns `GatewayError` on provider errors, timeouts, or validation failures
+    async fn generate_images(&self, request: &ImageRequest) -> Result<ImageResponse, GatewayError>;
+}
+
+/// Image generation request (OpenAI compatible)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImageRequest {
+    /// Text description of the desired image(s)
+    pub prompt: String,
+
+    /// Model to use
+    #[serde(default = "default_image_model")]
+    pub model: String,
+
+    /// Number of images to generate
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub n: Option<u32>,
+
+    /// Image size (e.g. "1024x1024")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<String>,
+
+    /// Image quality (e.g. "standard", "hd", "high")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quality: Option<String>,
+
+    /// Format of the returned images
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<ImageResponseFormat>,
+
+    /// Image style (e.g. "vivid", "natural")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub style: Option<String>,
+
+    /// End-user identifier
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+}
+
+fn default_image_model() -> String {
+    "dall-e-2".to_string()
+}
+
+impl ImageRequest {
+    /// Create a request for a prompt with the default model
+    #[must_use]
+    pub fn new(prompt: impl Into<String>) -> Self {
+        Self {
+            prompt: prompt.into(),
+            model: default_image_model(),
+            n: None,
+            size: None,
+            quality: None,
+            response_format: None,
+            style: None,
+            user: None,
+        }
+    }
+
+    /// Set the model
+    #[must_use]
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = model.into();
+        self
+    }
+
+    /// Set the number of images
+    #[must_use]
+    pub fn with_n(mut self, n: u32) -> Self {
+        self.n = Some(n);
+        self
+    }
+
+    /// Set the image size
+    #[must_use]
+    pub fn with_size(mut self, size: impl Into<String>) -> Self {
+        self.size = Some(size.into());
+        self
+    }
+
+    /// Set the image quality
+    #[must_use]
+    pub fn with_quality(mut self, quality: impl Into<String>) -> Self {
+        self.quality = Some(quality.into());
+        self
+    }
+
+    /// Set the response format
+    #[must_use]
+    pub fn with_response_format(mut self, format: ImageResponseFormat) -> Self {
+        self.response_format = Some(format);
+        self
+    }
+
+    /// Validate the request
+    ///
+    /// # Errors
+    /// Returns `GatewayError::Validation` if the prompt is empty, `n` is out
+    /// of range, or `size` is not `WIDTHxHEIGHT`
+    pub fn validate(&self) -> Result<(), GatewayError> {
+        if self.prompt.trim().is_empty() {
+            return Err(GatewayError::validation(
+                "Prompt must not be empty",
+                Some("prompt".to_string()),
+                "invalid_prompt",
+            ));
+        }
+
+        if let Some(n) = self.n {
+            if n == 0 || n > MAX_IMAGES_PER_REQUEST {
+                return Err(GatewayError::validation(
+                    format!("n must be between 1 and {MAX_IMAGES_PER_REQUEST}"),
+                    Some("n".to_string()),
+                    "invalid_n",
+                ));
+            }
+        }
+
+        if let Some(size) = &self.size {
+            let valid = size == "auto"
+                || size
+                    .split_once('x')
+                    .is_some_and(|(w, h)| w.parse::<u32>().is_ok() && h.parse::<u32>().is_ok());
+            if !valid {
+                return Err(GatewayError::validation(
+                    format!("Invalid size: {size}"),
+                    Some("size".to_string()),
+                    "invalid_size",
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Format of generated images
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImageResponseFormat {
+    /// Hosted URL
+    Url,
+    /// Base64-encoded image data
+    B64Json,
+}
+
+/// Image generation response (OpenAI compatible)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImageResponse {
+    /// Unix timestamp of creation
+    pub created: i64,
+
+    /// Generated images
+    pub data: Vec<ImageData>,
+}
+
+/// A single generated image
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImageData {
+    /// Hosted URL (when `response_format` is `url`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+
+    /// Base64-encoded image (when `response_format` is `b64_json`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub b64_json: Option<String>,
+
+    /// Prompt the provider actually used, if it rewrote it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revised_prompt: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_defaults() {
+        let request: ImageRequest = serde_json::from_str(r#"{"prompt":"a cat"}"#).unwrap();
+        assert_eq!(request.model, "dall-e-2");
+        assert!(request.n.is_none());
+        assert!(request.validate().is_ok());
+    }
+
+    #[test]
+    fn test_response_format_serialization() {
+        let request = ImageRequest::new("a cat").with_response_format(ImageResponseFormat::B64Json);
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["response_format"], "b64_json");
+        assert!(json.get("size").is_none());
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!(ImageRequest::new(" ").validate().is_err());
+        assert!(ImageRequest::new("a cat").with_n(0).validate().is_err());
+        assert!(ImageRequest::new("a cat").with_n(11).validate().is_err());
+        assert!(ImageRequest::new("a cat").with_size("big").validate().is_err());
+        assert!(ImageRequest::new("a cat").with_size("1024x1792").validate().is_ok());
+        assert!(ImageRequest::new("a cat").with_size("auto").validate().is_ok());
+    }
+}
@@ -0,0 +1,216 @@
+//! Structured output repair.
+//!
+//! Models asked for JSON via `response_format` occasionally return output
+//! that is almost JSON: trailing prose, a missing brace, a code fence.
+//! [`repair_json`] sends such output back to the model together with the
+//! parse error until it produces valid JSON or the attempt cap is reached.
+
+use crate::error::GatewayError;
+use crate::request::{ChatMessage, GatewayRequest};
+use crate::response::GatewayResponse;
+use std::future::Future;
+
+/// Result of a repair loop
+#[derive(Debug, Clone)]
+pub struct JsonRepairOutcome {
+    /// First response that parsed, or the last one received
+    pub response: GatewayResponse,
+    /// Number of repair reprompts sent
+    pub attempts: u32,
+    /// Why the final output is still not valid JSON, `None` once repaired
+    pub error: Option<String>,
+}
+
+impl JsonRepairOutcome {
+    /// Whether the final output parsed as JSON
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Parse error for a response's content, `None` if it is valid JSON
+///
+/// Responses without text content (e.g. tool calls) have nothing to parse
+/// and are treated as valid.
+#[must_use]
+pub fn json_parse_error(response: &GatewayResponse) -> Option<String> {
+    let content = response.content()?;
+    serde_json::from_str::<serde_json::Value>(content)
+        .err()
+        .map(|e| e.to_string())
+}
+
+/// Build the reprompt for output that failed to parse
+///
+/// The original conversation is followed by the model's output and a user
+/// message carrying the parse error.
+#[must_use]
+pub fn repair_request(request: &GatewayRequest, output: &str, error: &str) -> GatewayRequest {
+    let mut repaired = request.clone();
+    repaired.messages.push(ChatMessage::assistant(output));
+    repaired.messages.push(ChatMessage::user(format!(
+        "Your previous response was not valid JSON ({error}). \
+         Reply with only the corrected JSON and no other text."
+    )));
+    repaired
+}
+
+/// Reprompt until `response` holds valid JSON or `max_attempts` is reached
+///
+/// `call` sends a repair request to the provider. Token usage of all
+/// attempts is added to the returned response. A failed repair call ends the
+/// loop and is reported in [`JsonRepairOutcome::error`] alongside the last
+/// output received.
+pub async fn repair_json<F, Fut>(
+    request: &GatewayRequest,
+    response: GatewayResponse,
+    max_attempts: u32,
+    mut call: F,
+) -> JsonRepairOutcome
+where
+    F: FnMut(GatewayRequest) -> Fut,
+    Fut: Future<Output = Result<GatewayResponse, GatewayError>>,
+{
+    let mut response = response;
+    let mut attempts = 0;
+
+    loop {
+        let Some(error) = json_parse_error(&response) else {
+            return JsonRepairOutcome {
+                response,
+                attempts,
+                error: None,
+            };
+        };
+
+        if attempts >= max_attempts {
+            return JsonRepairOutcome {
+                response,
+                attempts,
+                error: Some(error),
+            };
+        }
+        attempts += 1;
+
+        let output = response.content().unwrap_or_default();
+        match call(repair_request(request, output, &error)).await {
+            Ok(mut repaired) => {
+                repaired.usage.add(&response.usage);
+                response = repaired;
+            }
+            Err(e) => {
+                return JsonRepairOutcome {
+                    response,
+                    attempts,
+                    error: Some(format!("repair request failed: {e}")),
+                };
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::request::MessageContent;
+    use crate::response::{Choice, FinishReason, Usage};
+    use std::sync::Mutex;
+
+    fn make_response(content: &str) -> GatewayResponse {
+        GatewayResponse::builder()
+            .id("resp")
+            .model("gpt-4o")
+            .choice(Choice::new(0, content, FinishReason::Stop))
+            .usage(Usage::new(10, 5))
+            .build()
+    }
+
+    fn make_request() -> GatewayRequest {
+        GatewayRequest::builder()
+            .model("gpt-4o")
+            .message(ChatMessage::user("List three colors as JSON"))
+            .build()
+            .expect("valid request")
+    }
+
+    #[tokio::test]
+    async fn test_invalid_then_valid_is_repaired() {
+        let request = make_request();
+        let replies = Mutex::new(vec![
+            make_response(r#"{"colors": ["red", "green", "blue"]}"#),
+            make_response(r#"{"colors": ["red", "green""#),
+        ]);
+        let sent = Mutex::new(Vec::new());
+
+        let outcome = repair_json(&request, make_response("Sure! {\"colors\":"), 3, |req| {
+            sent.lock().unwrap().push(req);
+            let reply = replies.lock().unwrap().pop().unwrap();
+            async move { Ok(reply) }
+        })
+        .await;
+
+        assert!(outcome.is_valid());
+        assert_eq!(outcome.attempts, 2);
+        assert_eq!(outcome.response.content(), Some(r#"{"colors": ["red", "green", "blue"]}"#));
+        assert_eq!(outcome.response.usage.total_tokens, 45);
+
+        // Each reprompt carries the previous output and the parse error
+        let sent = sent.into_inner().unwrap();
+        let last = &sent[1].messages;
+        assert_eq!(last.len(), 3);
+        assert!(matches!(
+            &last[1].content,
+            MessageContent::Text(text) if text == r#"{"colors": ["red", "green""#
+        ));
+        assert!(matches!(
+            &last[2].content,
+            MessageContent::Text(text) if text.contains("not valid JSON")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_exhausting_attempts_surfaces_error() {
+        let request = make_request();
+        let mut calls = 0;
+
+        let outcome = repair_json(&request, make_response("not json"), 2, |_| {
+            calls += 1;
+            async { Ok(make_response("still not json")) }
+        })
+        .await;
+
+        assert_eq!(calls, 2);
+        assert_eq!(outcome.attempts, 2);
+        assert!(!outcome.is_valid());
+        assert!(outcome.error.is_some());
+        assert_eq!(outcome.response.content(), Some("still not json"));
+    }
+
+    #[tokio::test]
+    async fn test_valid_output_is_not_reprompted() {
+        let request = make_request();
+
+        let outcome = repair_json(&request, make_response("[1, 2, 3]"), 3, |_| async {
+            Err(GatewayError::internal("should not be called"))
+        })
+        .await;
+
+        assert!(outcome.is_valid());
+        assert_eq!(outcome.attempts, 0);
+    }
+
+    #[tokio::test]
+    async fn test_failed_repair_call_keeps_last_output() {
+        let request = make_request();
+
+        let outcome = repair_json(&request, make_response("{oops"), 3, |_| async {
+            Err(GatewayError::internal("provider down"))
+        })
+        .await;
+
+        assert_eq!(outcome.attempts, 1);
+        assert_eq!(outcome.response.content(), Some("{oops"));
+        assert!(outcome.error.unwrap().contains("provider down"));
+    }
+}
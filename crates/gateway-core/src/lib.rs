@@ -7,28 +7,56 @@
 //! - Provider traits and abstractions
 //! - Error types and handling
 //! - Validated domain types (newtypes)
+//! - Token estimation before dispatch
 
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
 
+pub mod batch;
+pub mod context;
+pub mod embedding;
 pub mod error;
+pub mod image;
+pub mod json_repair;
+pub mod postprocess;
 pub mod provider;
+pub mod rate_limit;
 pub mod request;
 pub mod response;
+pub mod shadow;
+pub mod sse;
 pub mod streaming;
+pub mod tokenizer;
 pub mod types;
 
 // Re-export commonly used types
-pub use error::{GatewayError, GatewayResult};
+pub use batch::{
+    Batch, BatchItem, BatchItemError, BatchItemResult, BatchMode, BatchProgress, BatchProvider,
+    BatchRequest, BatchRequestCounts, BatchStatus,
+};
+pub use context::RequestContext;
+pub use embedding::{
+    Embedding, EmbeddingFailure, EmbeddingInput, EmbeddingProgress, EmbeddingProvider,
+    EmbeddingRequest, EmbeddingResponse, EmbeddingUsage,
+};
+pub use error::{GatewayError, GatewayResult, ProviderErrorKind, StreamError};
+pub use image::{ImageData, ImageProvider, ImageRequest, ImageResponse, ImageResponseFormat};
+pub use json_repair::JsonRepairOutcome;
+pub use postprocess::{PostProcessor, PostProcessors};
 pub use provider::{
-    HealthStatus, LLMProvider, ModelInfo, ProviderCapabilities, ProviderType,
+    ConnectionPoolStats, HealthStatus, LLMProvider, ModelInfo, ProviderCapabilities, ProviderType,
 };
+pub use rate_limit::{ProviderRateLimits, RateLimitWindow};
 pub use request::{
-    ChatMessage, ContentPart, FunctionCall, GatewayRequest, MessageContent, MessageRole,
-    RequestMetadata, ToolCall, ToolChoice,
+    is_reasoning_model, AudioContent, AudioOutputOptions, ChatMessage, ContentPart, FunctionCall, GatewayRequest, MessageContent,
+    MessageRole, RequestMetadata, ResponseFormat, SafetySetting, ToolCall, ToolChoice,
+    SERVICE_TIERS,
 };
 pub use response::{Choice, FinishReason, GatewayResponse, ModelObject, ModelsResponse, Usage};
+pub use shadow::{ResponseDiff, SimilarityHook};
+pub use sse::{SseDecoder, SseEvent};
 pub use streaming::{ChatChunk, ChunkChoice, ChunkDelta};
+pub use tokenizer::{CharHeuristicEstimator, SharedTokenEstimator, TokenEstimator};
 pub use types::{
     ApiKey, MaxTokens, ModelId, ProviderId, RequestId, Temperature, TenantId, TopK, TopP,
 };
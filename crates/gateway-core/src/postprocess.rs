@@ -0,0 +1,205 @@
+//! Response post-processing.
+//!
+//! Downstream tools often need output exactly as the model meant it, but
+//! models decorate it, e.g. by wrapping JSON in a markdown code fence. A
+//! [`PostProcessors`] registry holds named [`PostProcessor`]s that rewrite
+//! the assistant's text content once the provider has responded. Tool calls
+//! and function calls are never passed to a processor.
+
+use crate::error::GatewayError;
+use crate::response::GatewayResponse;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// Rewrites assistant text content in place
+pub type PostProcessor = Arc<dyn Fn(&mut String) + Send + Sync>;
+
+/// Name of the built-in processor removing a surrounding markdown code fence
+pub const STRIP_CODE_FENCES: &str = "strip_code_fences";
+
+/// Named post-processors, including the built-ins
+#[derive(Clone)]
+pub struct PostProcessors {
+    processors: BTreeMap<String, PostProcessor>,
+}
+
+impl PostProcessors {
+    /// Create a registry holding the built-in processors
+    #[must_use]
+    pub fn new() -> Self {
+        let mut processors = Self {
+            processors: BTreeMap::new(),
+        };
+        processors.register(STRIP_CODE_FENCES, strip_code_fences);
+        processors
+    }
+
+    /// Register a processor, replacing any with the same name
+    pub fn register<F>(&mut self, name: impl Into<String>, processor: F)
+    where
+        F: Fn(&mut String) + Send + Sync + 'static,
+    {
+        self.processors.insert(name.into(), Arc::new(processor));
+    }
+
+    /// Names of the registered processors
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.processors.keys().map(String::as_str)
+    }
+
+    /// Check that every name refers to a registered processor
+    ///
+    /// # Errors
+    /// Returns a validation error naming the first unknown processor
+    pub fn validate(&self, names: &[String]) -> Result<(), GatewayError> {
+        match names.iter().find(|name| !self.processors.contains_key(*name)) {
+            Some(name) => Err(GatewayError::validation(
+                format!(
+                    "Unknown post-processor '{name}'; available: {}",
+                    self.names().collect::<Vec<_>>().join(", ")
+                ),
+                Some("post_process".to_string()),
+                "unknown_post_processor",
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Run the named processors, in order, over every choice's text content
+    ///
+    /// Unknown names are skipped; use [`Self::validate`] to reject them
+    /// before dispatch.
+    pub fn apply(&self, names: &[String], response: &mut GatewayResponse) {
+        let processors: Vec<&PostProcessor> = names
+            .iter()
+            .filter_map(|name| self.processors.get(name))
+            .collect();
+        if processors.is_empty() {
+            return;
+        }
+
+        for choice in &mut response.choices {
+            if let Some(content) = choice.message.content.as_mut() {
+                for processor in &processors {
+                    processor(content);
+                }
+            }
+        }
+    }
+}
+
+impl Default for PostProcessors {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for PostProcessors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.names()).finish()
+    }
+}
+
+/// Remove a markdown code fence wrapping the whole content
+///
+/// Only content that is a single fenced block, optionally with an info
+/// string such as `json`, is rewritten. Fences inside prose are left alone.
+pub fn strip_code_fences(content: &mut String) {
+    let Some(inner) = content
+        .trim()
+        .strip_prefix("```")
+        .and_then(|rest| rest.strip_suffix("```"))
+    else {
+        return;
+    };
+    let Some((info, body)) = inner.split_once('\n') else {
+        return;
+    };
+    if info.trim().contains(char::is_whitespace) || body.contains("```") {
+        return;
+    }
+    *content = body.trim_end().to_string();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::request::{FunctionCall, ToolCall};
+    use crate::response::{Choice, FinishReason};
+
+    fn strip(content: &str) -> String {
+        let mut content = content.to_string();
+        strip_code_fences(&mut content);
+        content
+    }
+
+    #[test]
+    fn test_strip_code_fences() {
+        assert_eq!(strip("```json\n{\"a\": 1}\n```"), "{\"a\": 1}");
+        assert_eq!(strip("  ```\n[1, 2]\n```\n"), "[1, 2]");
+        assert_eq!(strip("```json\n{\n  \"a\": 1\n}\n```"), "{\n  \"a\": 1\n}");
+    }
+
+    #[test]
+    fn test_strip_code_fences_leaves_other_content() {
+        for content in [
+            "{\"a\": 1}",
+            "Here you go:\n```json\n{}\n```",
+            "```json\n{}\n```\nand\n```json\n{}\n```",
+            "```inline```",
+            "```this is prose\n{}\n```",
+        ] {
+            assert_eq!(strip(content), content);
+        }
+    }
+
+    #[test]
+    fn test_apply_skips_tool_calls() {
+        let call = ToolCall {
+            id: "call_1".to_string(),
+            tool_type: "function".to_string(),
+            function: FunctionCall {
+                name: "lookup".to_string(),
+                arguments: "```json\n{}\n```".to_string(),
+            },
+        };
+        let mut response = GatewayResponse::builder()
+            .id("resp")
+            .model("gpt-4o")
+            .choice(Choice::new(0, "```json\n{}\n```", FinishReason::Stop))
+            .choice(Choice::with_tool_calls(1, vec![call.clone()], FinishReason::ToolCalls))
+            .build();
+
+        let processors = PostProcessors::new();
+        processors.apply(&[STRIP_CODE_FENCES.to_string()], &mut response);
+
+        assert_eq!(response.choices[0].message.content.as_deref(), Some("{}"));
+        assert_eq!(response.choices[1].message.content, None);
+        assert_eq!(
+            response.choices[1].message.tool_calls.as_ref().map(|c| &c[0].function.arguments),
+            Some(&call.function.arguments)
+        );
+    }
+
+    #[test]
+    fn test_custom_processor_and_validation() {
+        let mut processors = PostProcessors::new();
+        processors.register("upper", |content: &mut String| {
+            *content = content.to_uppercase();
+        });
+
+        let mut response = GatewayResponse::builder()
+            .id("resp")
+            .model("gpt-4o")
+            .choice(Choice::new(0, "```\nok\n```", FinishReason::Stop))
+            .build();
+        let names = vec![STRIP_CODE_FENCES.to_string(), "upper".to_string()];
+        assert!(processors.validate(&names).is_ok());
+        processors.apply(&names, &mut response);
+        assert_eq!(response.choices[0].message.content.as_deref(), Some("OK"));
+
+        let err = processors.validate(&["missing".to_string()]).unwrap_err();
+        assert_eq!(err.error_code(), "unknown_post_processor");
+    }
+}
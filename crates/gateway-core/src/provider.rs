@@ -3,11 +3,17 @@
 //! This module defines the core trait that all LLM providers must implement,
 //! along with supporting types for capabilities and health status.
 
+use crate::batch::BatchProvider;
+use crate::context::RequestContext;
 use crate::error::GatewayError;
+use crate::embedding::EmbeddingProvider;
+use crate::image::ImageProvider;
+use crate::rate_limit::ProviderRateLimits;
 use crate::request::GatewayRequest;
 use crate::response::GatewayResponse;
 use crate::streaming::ChatChunk;
 use async_trait::async_trait;
+use bytes::Bytes;
 use futures::stream::BoxStream;
 use serde::{Deserialize, Serialize};
 use std::fmt;
@@ -34,6 +40,22 @@ pub trait LLMProvider: Send + Sync + 'static {
         request: &GatewayRequest,
     ) -> Result<GatewayResponse, GatewayError>;
 
+    /// Execute a chat completion bounded by the request's context
+    ///
+    /// The in-flight call is dropped as soon as the context's deadline
+    /// passes or it is cancelled.
+    ///
+    /// # Errors
+    /// Returns `GatewayError::Timeout` if the deadline is hit, otherwise as
+    /// for [`Self::chat_completion`]
+    async fn chat_completion_with_context(
+        &self,
+        request: &GatewayRequest,
+        ctx: &RequestContext,
+    ) -> Result<GatewayResponse, GatewayError> {
+        ctx.run(self.chat_completion(request)).await
+    }
+
     /// Execute a streaming chat completion
     ///
     /// # Errors
@@ -43,6 +65,30 @@ pub trait LLMProvider: Send + Sync + 'static {
         request: &GatewayRequest,
     ) -> Result<BoxStream<'static, Result<ChatChunk, GatewayError>>, GatewayError>;
 
+    /// Whether [`Self::chat_completion_stream_raw`] is supported
+    ///
+    /// Only providers whose upstream speaks OpenAI-compatible SSE can have
+    /// their stream forwarded verbatim.
+    fn supports_raw_stream(&self) -> bool {
+        false
+    }
+
+    /// Execute a streaming chat completion, returning the upstream SSE body
+    /// without parsing it
+    ///
+    /// # Errors
+    /// Returns `GatewayError` on provider errors, or if the provider does not
+    /// support raw streaming
+    async fn chat_completion_stream_raw(
+        &self,
+        _request: &GatewayRequest,
+    ) -> Result<BoxStream<'static, Result<Bytes, GatewayError>>, GatewayError> {
+        Err(GatewayError::streaming(format!(
+            "Provider {} does not support raw streaming",
+            self.id()
+        )))
+    }
+
     /// Perform a health check on this provider
     async fn health_check(&self) -> HealthStatus;
 
@@ -69,6 +115,56 @@ pub trait LLMProvider: Send + Sync + 'static {
     fn is_enabled(&self) -> bool {
         true
     }
+
+    /// Rate limit state reported on the most recent response for `model`
+    ///
+    /// Returns `None` for providers that don't report rate limits or
+    /// haven't served a request for the model yet.
+    fn rate_limits(&self, _model: &str) -> Option<ProviderRateLimits> {
+        None
+    }
+
+    /// Open connections until the configured number of idle connections is warm
+    ///
+    /// Returns `None` for providers that don't keep warm connections.
+    async fn warm_connections(&self) -> Option<ConnectionPoolStats> {
+        None
+    }
+
+    /// Warm connection state as of the last [`warm_connections`] call
+    ///
+    /// [`warm_connections`]: LLMProvider::warm_connections
+    fn connection_pool_stats(&self) -> Option<ConnectionPoolStats> {
+        None
+    }
+
+    /// How often [`warm_connections`] should run to keep connections alive
+    ///
+    /// [`warm_connections`]: LLMProvider::warm_connections
+    fn connection_keepalive_interval(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Image generation capability, if this provider supports it
+    ///
+    /// Returns `None` for providers without image generation.
+    fn as_image_provider(&self) -> Option<&dyn ImageProvider> {
+        None
+    }
+
+    /// Embedding capability, if this provider supports it
+    ///
+    /// Returns `None` for providers without embeddings.
+    fn as_embedding_provider(&self) -> Option<&dyn EmbeddingProvider> {
+        None
+    }
+
+    /// Native batch capability, if this provider has a batch endpoint
+    ///
+    /// Returns `None` for providers whose batches the gateway emulates.
+    fn as_batch_provider(&self) -> Option<&dyn BatchProvider> {
+        None
+    }
 }
 
 /// Provider type enumeration
@@ -218,6 +314,10 @@ pub struct ProviderCapabilities {
     /// Supports parallel function calls
     #[serde(default)]
     pub parallel_tool_calls: bool,
+
+    /// Supports generating audio output
+    #[serde(default)]
+    pub audio_output: bool,
 }
 
 fn default_true() -> bool {
@@ -250,6 +350,7 @@ impl ProviderCapabilities {
             max_context_length: Some(128_000),
             max_output_tokens: Some(4096),
             parallel_tool_calls: true,
+            audio_output: true,
         }
     }
 
@@ -266,6 +367,7 @@ impl ProviderCapabilities {
             "seed" => self.seed,
             "logprobs" => self.logprobs,
             "parallel_tool_calls" => self.parallel_tool_calls,
+            "audio_output" => self.audio_output,
             _ => false,
         }
     }
@@ -375,6 +477,15 @@ impl ModelInfo {
     }
 }
 
+/// Warm connections kept by a provider's HTTP client
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConnectionPoolStats {
+    /// Idle connections the provider tries to keep open
+    pub min_idle: usize,
+    /// Connections that answered the last keep-alive round
+    pub idle: usize,
+}
+
 /// Provider statistics for monitoring
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ProviderStats {
@@ -0,0 +1,271 @@
+//! Provider-reported rate limit state.
+//!
+//! Providers advertise their remaining quota in response headers:
+//! - OpenAI (and Azure OpenAI): `x-ratelimit-{limit,remaining,reset}-{requests,tokens}`,
+//!   with resets as durations such as `6m0s` or `20ms`
+//! - Anthropic: `anthropic-ratelimit-{requests,tokens}-{limit,remaining,reset}`,
+//!   with resets as RFC 3339 timestamps
+//!
+//! [`ProviderRateLimits::from_headers`] normalizes both into one shape so
+//! telemetry and backoff logic don't care which provider sent them.
+
+use chrono::{DateTime, Utc};
+use http::HeaderMap;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Quota state for one dimension (requests or tokens)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimitWindow {
+    /// Maximum allowed in the window
+    pub limit: Option<u64>,
+    /// Remaining in the current window
+    pub remaining: Option<u64>,
+    /// Time until the window resets
+    pub reset: Option<Duration>,
+}
+
+impl RateLimitWindow {
+    /// Fraction of the quota still available (`remaining / limit`)
+    #[must_use]
+    pub fn remaining_fraction(&self) -> Option<f64> {
+        match (self.remaining, self.limit) {
+            (Some(remaining), Some(limit)) if limit > 0 => {
+                Some((remaining as f64 / limit as f64).min(1.0))
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether no header for this window was present
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.limit.is_none() && self.remaining.is_none() && self.reset.is_none()
+    }
+
+    fn or(self, other: Self) -> Self {
+        Self {
+            limit: self.limit.or(other.limit),
+            remaining: self.remaining.or(other.remaining),
+            reset: self.reset.or(other.reset),
+        }
+    }
+}
+
+/// Rate limit state reported by a provider on its last response
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProviderRateLimits {
+    /// Request quota
+    pub requests: RateLimitWindow,
+    /// Token quota
+    pub tokens: RateLimitWindow,
+}
+
+impl ProviderRateLimits {
+    /// Parse rate limit headers from a provider response
+    ///
+    /// Returns `None` if the response carries no rate limit headers.
+    #[must_use]
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let limits = Self {
+            requests: openai_window(headers, "requests").or(anthropic_window(headers, "requests")),
+            tokens: openai_window(headers, "tokens").or(anthropic_window(headers, "tokens")),
+        };
+
+        if limits.requests.is_empty() && limits.tokens.is_empty() {
+            None
+        } else {
+            Some(limits)
+        }
+    }
+
+    /// Smallest remaining fraction across the request and token quotas
+    #[must_use]
+    pub fn min_remaining_fraction(&self) -> Option<f64> {
+        match (
+            self.requests.remaining_fraction(),
+            self.tokens.remaining_fraction(),
+        ) {
+            (Some(requests), Some(tokens)) => Some(requests.min(tokens)),
+            (requests, tokens) => requests.or(tokens),
+        }
+    }
+
+    /// Time until the most depleted quota resets
+    #[must_use]
+    pub fn reset_after(&self) -> Option<Duration> {
+        let requests = self.requests.remaining_fraction();
+        let tokens = self.tokens.remaining_fraction();
+        match (requests, tokens) {
+            (Some(r), Some(t)) if t < r => self.tokens.reset,
+            (None, Some(_)) => self.tokens.reset,
+            _ => self.requests.reset.or(self.tokens.reset),
+        }
+    }
+}
+
+fn openai_window(headers: &HeaderMap, kind: &str) -> RateLimitWindow {
+    RateLimitWindow {
+        limit: header_u64(headers, &format!("x-ratelimit-limit-{kind}")),
+        remaining: header_u64(headers, &format!("x-ratelimit-remaining-{kind}")),
+        reset: header_str(headers, &format!("x-ratelimit-reset-{kind}")).and_then(parse_reset),
+    }
+}
+
+fn anthropic_window(headers: &HeaderMap, kind: &str) -> RateLimitWindow {
+    RateLimitWindow {
+        limit: header_u64(headers, &format!("anthropic-ratelimit-{kind}-limit")),
+        remaining: header_u64(headers, &format!("anthropic-ratelimit-{kind}-remaining")),
+        reset: header_str(headers, &format!("anthropic-ratelimit-{kind}-reset"))
+            .and_then(parse_reset),
+    }
+}
+
+fn header_str<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers.get(name)?.to_str().ok().map(str::trim)
+}
+
+fn header_u64(headers: &HeaderMap, name: &str) -> Option<u64> {
+    header_str(headers, name)?.parse().ok()
+}
+
+/// Parse a reset value: plain seconds, an RFC 3339 timestamp, or a
+/// duration such as `1h2m3.5s` / `20ms`
+fn parse_reset(value: &str) -> Option<Duration> {
+    if let Ok(secs) = value.parse::<f64>() {
+        return Duration::try_from_secs_f64(secs).ok();
+    }
+
+    if let Ok(at) = DateTime::parse_from_rfc3339(value) {
+        return Some(
+            at.with_timezone(&Utc)
+                .signed_duration_since(Utc::now())
+                .to_std()
+                .unwrap_or(Duration::ZERO),
+        );
+    }
+
+    parse_duration(value)
+}
+
+fn parse_duration(value: &str) -> Option<Duration> {
+    let mut total = 0.0;
+    let mut rest = value;
+
+    while !rest.is_empty() {
+        let number_len = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        let number: f64 = rest[..number_len].parse().ok()?;
+        rest = &rest[number_len..];
+
+        let unit_len = rest
+            .find(|c: char| c.is_ascii_digit() || c == '.')
+            .unwrap_or(rest.len());
+        let seconds_per_unit = match &rest[..unit_len] {
+            "ms" => 0.001,
+            "s" => 1.0,
+            "m" => 60.0,
+            "h" => 3600.0,
+            _ => return None,
+        };
+        rest = &rest[unit_len..];
+
+        total += number * seconds_per_unit;
+    }
+
+    Duration::try_from_secs_f64(total).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::HeaderValue;
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.insert(*name, HeaderValue::from_str(value).unwrap());
+        }
+        map
+    }
+
+    #[test]
+    fn test_parse_openai_headers() {
+        let limits = ProviderRateLimits::from_headers(&headers(&[
+            ("x-ratelimit-limit-requests", "10000"),
+            ("x-ratelimit-remaining-requests", "9999"),
+            ("x-ratelimit-reset-requests", "6ms"),
+            ("x-ratelimit-limit-tokens", "2000000"),
+            ("x-ratelimit-remaining-tokens", "1999000"),
+            ("x-ratelimit-reset-tokens", "1m30s"),
+        ]))
+        .unwrap();
+
+        assert_eq!(limits.requests.limit, Some(10_000));
+        assert_eq!(limits.requests.remaining, Some(9_999));
+        assert_eq!(limits.requests.reset, Some(Duration::from_millis(6)));
+        assert_eq!(limits.tokens.limit, Some(2_000_000));
+        assert_eq!(limits.tokens.remaining, Some(1_999_000));
+        assert_eq!(limits.tokens.reset, Some(Duration::from_secs(90)));
+    }
+
+    #[test]
+    fn test_parse_anthropic_headers() {
+        let reset = (Utc::now() + chrono::Duration::seconds(30)).to_rfc3339();
+        let limits = ProviderRateLimits::from_headers(&headers(&[
+            ("anthropic-ratelimit-requests-limit", "50"),
+            ("anthropic-ratelimit-requests-remaining", "5"),
+            ("anthropic-ratelimit-requests-reset", &reset),
+            ("anthropic-ratelimit-tokens-limit", "40000"),
+            ("anthropic-ratelimit-tokens-remaining", "38000"),
+        ]))
+        .unwrap();
+
+        assert_eq!(limits.requests.limit, Some(50));
+        assert_eq!(limits.requests.remaining, Some(5));
+        let reset = limits.requests.reset.unwrap();
+        assert!(reset > Duration::from_secs(28) && reset <= Duration::from_secs(30));
+        assert_eq!(limits.tokens.remaining, Some(38_000));
+        assert_eq!(limits.tokens.reset, None);
+
+        assert_eq!(limits.min_remaining_fraction(), Some(0.1));
+        assert_eq!(limits.reset_after(), limits.requests.reset);
+    }
+
+    #[test]
+    fn test_no_rate_limit_headers() {
+        assert!(ProviderRateLimits::from_headers(&headers(&[("content-type", "application/json")]))
+            .is_none());
+    }
+
+    #[test]
+    fn test_parse_reset_formats() {
+        assert_eq!(parse_reset("20ms"), Some(Duration::from_millis(20)));
+        assert_eq!(parse_reset("1.5s"), Some(Duration::from_millis(1500)));
+        assert_eq!(parse_reset("6m0s"), Some(Duration::from_secs(360)));
+        assert_eq!(parse_reset("1h2m3s"), Some(Duration::from_secs(3723)));
+        assert_eq!(parse_reset("30"), Some(Duration::from_secs(30)));
+        assert_eq!(parse_reset("2000-01-01T00:00:00Z"), Some(Duration::ZERO));
+        assert_eq!(parse_reset("soon"), None);
+    }
+
+    #[test]
+    fn test_reset_after_follows_most_depleted_quota() {
+        let limits = ProviderRateLimits {
+            requests: RateLimitWindow {
+                limit: Some(100),
+                remaining: Some(90),
+                reset: Some(Duration::from_secs(1)),
+            },
+            tokens: RateLimitWindow {
+                limit: Some(1000),
+                remaining: Some(10),
+                reset: Some(Duration::from_secs(20)),
+            },
+        };
+
+        assert_eq!(limits.min_remaining_fraction(), Some(0.01));
+        assert_eq!(limits.reset_after(), Some(Duration::from_secs(20)));
+    }
+}
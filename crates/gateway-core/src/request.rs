@@ -2,10 +2,14 @@
 //!
 //! This module defines the unified request format that abstracts across all LLM providers.
 
+use crate::streaming::StreamOptions;
 use crate::types::{MaxTokens, ModelId, RequestId, Temperature, TopK, TopP};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+
+/// Values accepted for [`GatewayRequest::service_tier`]
+pub const SERVICE_TIERS: &[&str] = &["auto", "default", "flex", "priority"];
 
 /// Unified gateway request that abstracts all providers
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,6 +32,13 @@ pub struct GatewayRequest {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub max_tokens: Option<u32>,
 
+    /// Maximum completion tokens, including hidden reasoning tokens
+    ///
+    /// OpenAI's replacement for `max_tokens`, required by reasoning models.
+    /// If both are set they must agree; see [`GatewayRequest::token_limit`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_completion_tokens: Option<u32>,
+
     /// Top-p (nucleus sampling) parameter
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub top_p: Option<f32>,
@@ -52,6 +63,10 @@ pub struct GatewayRequest {
     #[serde(default)]
     pub stream: bool,
 
+    /// Streaming options, e.g. whether to send a final usage chunk
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stream_options: Option<StreamOptions>,
+
     /// Number of completions to generate
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub n: Option<u32>,
@@ -72,10 +87,32 @@ pub struct GatewayRequest {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub seed: Option<i64>,
 
+    /// Per-category safety thresholds (Gemini only; ignored by other providers)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub safety_settings: Option<Vec<SafetySetting>>,
+
     /// User identifier for abuse tracking
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub user: Option<String>,
 
+    /// Store the completion for the provider's dashboard and evals
+    /// (OpenAI and Azure only; ignored by other providers)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub store: Option<bool>,
+
+    /// Processing tier: `auto`, `default`, `flex` or `priority`
+    /// (OpenAI only; ignored by other providers)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service_tier: Option<String>,
+
+    /// Output modalities, e.g. `["text", "audio"]`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modalities: Option<Vec<String>>,
+
+    /// Audio output options, required when `modalities` includes `audio`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audio: Option<AudioOutputOptions>,
+
     /// Request metadata for routing/billing
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub metadata: Option<RequestMetadata>,
@@ -110,6 +147,41 @@ impl GatewayRequest {
             .map_err(Into::into)
     }
 
+    /// Generation limit from `max_completion_tokens` or `max_tokens`
+    ///
+    /// Validation rejects requests where both are set and differ, so either
+    /// may be used here.
+    #[must_use]
+    pub fn token_limit(&self) -> Option<u32> {
+        self.max_completion_tokens.or(self.max_tokens)
+    }
+
+    /// Get the validated generation limit
+    ///
+    /// # Errors
+    /// Returns error if the limit is out of range, or if `max_tokens` and
+    /// `max_completion_tokens` are both set to different values
+    pub fn validated_token_limit(&self) -> Result<Option<MaxTokens>, crate::error::GatewayError> {
+        if let (Some(max_tokens), Some(max_completion_tokens)) =
+            (self.max_tokens, self.max_completion_tokens)
+        {
+            if max_tokens != max_completion_tokens {
+                return Err(crate::error::GatewayError::validation(
+                    format!(
+                        "max_tokens ({max_tokens}) and max_completion_tokens ({max_completion_tokens}) conflict; set only one"
+                    ),
+                    Some("max_completion_tokens".to_string()),
+                    "conflicting_max_tokens",
+                ));
+            }
+        }
+
+        match self.max_completion_tokens {
+            Some(limit) => Ok(Some(MaxTokens::new(limit)?)),
+            None => self.validated_max_tokens(),
+        }
+    }
+
     /// Get validated top_p
     ///
     /// # Errors
@@ -134,6 +206,33 @@ impl GatewayRequest {
         ModelId::new(&self.model).map_err(Into::into)
     }
 
+    /// Whether the client asked for a final usage chunk when streaming
+    #[must_use]
+    pub fn includes_stream_usage(&self) -> bool {
+        self.stream_options.as_ref().is_some_and(|o| o.include_usage)
+    }
+
+    /// Estimate the prompt size in tokens
+    ///
+    /// Uses the default [`CharHeuristicEstimator`]; components that compare
+    /// estimates should share a [`TokenEstimator`] instead.
+    ///
+    /// [`CharHeuristicEstimator`]: crate::tokenizer::CharHeuristicEstimator
+    /// [`TokenEstimator`]: crate::tokenizer::TokenEstimator
+    #[must_use]
+    pub fn estimated_prompt_tokens(&self) -> u32 {
+        use crate::tokenizer::{CharHeuristicEstimator, TokenEstimator};
+        CharHeuristicEstimator::default().estimate_prompt(self)
+    }
+
+    /// Whether the request asks for audio output
+    #[must_use]
+    pub fn wants_audio_output(&self) -> bool {
+        self.modalities
+            .as_ref()
+            .is_some_and(|modalities| modalities.iter().any(|m| m == "audio"))
+    }
+
     /// Validate the entire request
     ///
     /// # Errors
@@ -154,8 +253,8 @@ impl GatewayRequest {
         // Validate temperature if present
         self.validated_temperature()?;
 
-        // Validate max_tokens if present
-        self.validated_max_tokens()?;
+        // Validate max_tokens and max_completion_tokens if present
+        self.validated_token_limit()?;
 
         // Validate top_p if present
         self.validated_top_p()?;
@@ -196,10 +295,46 @@ impl GatewayRequest {
             }
         }
 
+        // Validate service_tier if present
+        if let Some(tier) = &self.service_tier {
+            if !SERVICE_TIERS.contains(&tier.as_str()) {
+                return Err(crate::error::GatewayError::validation(
+                    format!(
+                        "service_tier must be one of {}, got '{tier}'",
+                        SERVICE_TIERS.join(", ")
+                    ),
+                    Some("service_tier".to_string()),
+                    "invalid_service_tier",
+                ));
+            }
+        }
+
+        // Audio output needs a voice and format to generate with
+        if self.wants_audio_output() && self.audio.is_none() {
+            return Err(crate::error::GatewayError::validation(
+                "audio is required when modalities includes \"audio\"",
+                Some("audio".to_string()),
+                "missing_audio_options",
+            ));
+        }
+
         Ok(())
     }
 }
 
+/// Whether `model` is an OpenAI reasoning model (o-series, GPT-5)
+///
+/// These reject `max_tokens` and take `max_completion_tokens` instead.
+#[must_use]
+pub fn is_reasoning_model(model: &str) -> bool {
+    let model = model.rsplit('/').next().unwrap_or(model);
+    let o_series = model
+        .strip_prefix('o')
+        .and_then(|rest| rest.chars().next())
+        .is_some_and(|c| c.is_ascii_digit());
+    o_series || model.starts_with("gpt-5")
+}
+
 /// Builder for `GatewayRequest`
 #[derive(Debug, Default)]
 pub struct GatewayRequestBuilder {
@@ -208,18 +343,25 @@ pub struct GatewayRequestBuilder {
     messages: Vec<ChatMessage>,
     temperature: Option<f32>,
     max_tokens: Option<u32>,
+    max_completion_tokens: Option<u32>,
     top_p: Option<f32>,
     top_k: Option<u32>,
     frequency_penalty: Option<f32>,
     presence_penalty: Option<f32>,
     stop: Option<Vec<String>>,
     stream: bool,
+    stream_options: Option<StreamOptions>,
     n: Option<u32>,
     tools: Option<Vec<ToolDefinition>>,
     tool_choice: Option<ToolChoice>,
     response_format: Option<ResponseFormat>,
     seed: Option<i64>,
+    safety_settings: Option<Vec<SafetySetting>>,
     user: Option<String>,
+    store: Option<bool>,
+    service_tier: Option<String>,
+    modalities: Option<Vec<String>>,
+    audio: Option<AudioOutputOptions>,
     metadata: Option<RequestMetadata>,
 }
 
@@ -252,6 +394,13 @@ impl GatewayRequestBuilder {
         self
     }
 
+    /// Add a system message
+    #[must_use]
+    pub fn system(mut self, content: impl Into<String>) -> Self {
+        self.messages.push(ChatMessage::system(content));
+        self
+    }
+
     /// Set the temperature
     #[must_use]
     pub fn temperature(mut self, temperature: f32) -> Self {
@@ -266,6 +415,13 @@ impl GatewayRequestBuilder {
         self
     }
 
+    /// Set max_completion_tokens
+    #[must_use]
+    pub fn max_completion_tokens(mut self, max_completion_tokens: u32) -> Self {
+        self.max_completion_tokens = Some(max_completion_tokens);
+        self
+    }
+
     /// Set top_p
     #[must_use]
     pub fn top_p(mut self, top_p: f32) -> Self {
@@ -308,6 +464,13 @@ impl GatewayRequestBuilder {
         self
     }
 
+    /// Set streaming options
+    #[must_use]
+    pub fn stream_options(mut self, options: StreamOptions) -> Self {
+        self.stream_options = Some(options);
+        self
+    }
+
     /// Set n (number of completions)
     #[must_use]
     pub fn n(mut self, n: u32) -> Self {
@@ -322,6 +485,13 @@ impl GatewayRequestBuilder {
         self
     }
 
+    /// Add a tool
+    #[must_use]
+    pub fn tool(mut self, tool: ToolDefinition) -> Self {
+        self.tools.get_or_insert_with(Vec::new).push(tool);
+        self
+    }
+
     /// Set tool_choice
     #[must_use]
     pub fn tool_choice(mut self, tool_choice: ToolChoice) -> Self {
@@ -343,6 +513,13 @@ impl GatewayRequestBuilder {
         self
     }
 
+    /// Add a safety setting
+    #[must_use]
+    pub fn safety_setting(mut self, setting: SafetySetting) -> Self {
+        self.safety_settings.get_or_insert_with(Vec::new).push(setting);
+        self
+    }
+
     /// Set user
     #[must_use]
     pub fn user(mut self, user: impl Into<String>) -> Self {
@@ -350,6 +527,30 @@ impl GatewayRequestBuilder {
         self
     }
 
+    /// Set whether the provider should store the completion
+    #[must_use]
+    pub fn store(mut self, store: bool) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Set the processing tier the provider should use
+    #[must_use]
+    pub fn service_tier(mut self, tier: impl Into<String>) -> Self {
+        self.service_tier = Some(tier.into());
+        self
+    }
+
+    /// Request audio output in `format` spoken by `voice`
+    ///
+    /// Sets `modalities` to text and audio.
+    #[must_use]
+    pub fn audio_output(mut self, voice: impl Into<String>, format: impl Into<String>) -> Self {
+        self.modalities = Some(vec!["text".to_string(), "audio".to_string()]);
+        self.audio = Some(AudioOutputOptions::new(voice, format));
+        self
+    }
+
     /// Set metadata
     #[must_use]
     pub fn metadata(mut self, metadata: RequestMetadata) -> Self {
@@ -359,8 +560,12 @@ impl GatewayRequestBuilder {
 
     /// Build the request
     ///
+    /// Sampling parameters are checked against their validated newtypes
+    /// ([`Temperature`], [`MaxTokens`], [`TopP`], [`TopK`]).
+    ///
     /// # Errors
-    /// Returns error if required fields are missing
+    /// Returns a validation error naming the offending field if required
+    /// fields are missing or a value is out of range
     pub fn build(self) -> Result<GatewayRequest, crate::error::GatewayError> {
         let model = self.model.ok_or_else(|| {
             crate::error::GatewayError::validation(
@@ -384,18 +589,25 @@ impl GatewayRequestBuilder {
             messages: self.messages,
             temperature: self.temperature,
             max_tokens: self.max_tokens,
+            max_completion_tokens: self.max_completion_tokens,
             top_p: self.top_p,
             top_k: self.top_k,
             frequency_penalty: self.frequency_penalty,
             presence_penalty: self.presence_penalty,
             stop: self.stop,
             stream: self.stream,
+            stream_options: self.stream_options,
             n: self.n,
             tools: self.tools,
             tool_choice: self.tool_choice,
             response_format: self.response_format,
             seed: self.seed,
+            safety_settings: self.safety_settings,
             user: self.user,
+            store: self.store,
+            service_tier: self.service_tier,
+            modalities: self.modalities,
+            audio: self.audio,
             metadata: self.metadata,
         };
 
@@ -556,6 +768,53 @@ pub enum ContentPart {
         /// Image URL details
         image_url: ImageUrl,
     },
+    /// Audio content part, as generated by audio-output models
+    Audio {
+        /// Encoded audio and its transcript
+        audio: AudioContent,
+    },
+}
+
+/// Generated audio
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AudioContent {
+    /// Provider ID of the audio, for referring to it in later turns
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Base64-encoded audio
+    pub data: String,
+    /// Audio format, e.g. `wav` or `mp3`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    /// Transcript of the audio
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transcript: Option<String>,
+    /// Unix time after which the provider no longer keeps the audio
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<i64>,
+}
+
+/// Audio output options
+///
+/// Mirrors OpenAI's `audio` request parameter, e.g. voice `alloy` with
+/// format `wav`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AudioOutputOptions {
+    /// Voice to speak with
+    pub voice: String,
+    /// Output audio format
+    pub format: String,
+}
+
+impl AudioOutputOptions {
+    /// Create audio output options
+    #[must_use]
+    pub fn new(voice: impl Into<String>, format: impl Into<String>) -> Self {
+        Self {
+            voice: voice.into(),
+            format: format.into(),
+        }
+    }
 }
 
 /// Image URL for vision models
@@ -590,6 +849,35 @@ pub struct ToolDefinition {
     pub function: FunctionDefinition,
 }
 
+impl ToolDefinition {
+    /// Create a function tool
+    #[must_use]
+    pub fn function(name: impl Into<String>) -> Self {
+        Self {
+            tool_type: "function".to_string(),
+            function: FunctionDefinition {
+                name: name.into(),
+                description: None,
+                parameters: None,
+            },
+        }
+    }
+
+    /// Set the function description
+    #[must_use]
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.function.description = Some(description.into());
+        self
+    }
+
+    /// Set the function parameters (JSON Schema)
+    #[must_use]
+    pub fn with_parameters(mut self, parameters: serde_json::Value) -> Self {
+        self.function.parameters = Some(parameters);
+        self
+    }
+}
+
 /// Function definition for tools
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FunctionDefinition {
@@ -653,6 +941,42 @@ pub struct ResponseFormat {
     /// Format type: "text" or "json_object"
     #[serde(rename = "type")]
     pub format_type: String,
+
+    /// Reprompt the model up to this many times when its output is not
+    /// valid JSON (gateway extension, off when unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_repair_attempts: Option<u32>,
+}
+
+impl ResponseFormat {
+    /// Whether the caller asked for JSON output
+    #[must_use]
+    pub fn expects_json(&self) -> bool {
+        matches!(self.format_type.as_str(), "json_object" | "json_schema")
+    }
+}
+
+/// Safety threshold for one harm category
+///
+/// Mirrors Gemini's `safetySettings` entries, e.g. category
+/// `HARM_CATEGORY_HATE_SPEECH` with threshold `BLOCK_ONLY_HIGH`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SafetySetting {
+    /// Harm category
+    pub category: String,
+    /// Blocking threshold for the category
+    pub threshold: String,
+}
+
+impl SafetySetting {
+    /// Create a safety setting
+    #[must_use]
+    pub fn new(category: impl Into<String>, threshold: impl Into<String>) -> Self {
+        Self {
+            category: category.into(),
+            threshold: threshold.into(),
+        }
+    }
 }
 
 /// Request metadata for routing and billing
@@ -691,6 +1015,52 @@ pub struct RequestMetadata {
     pub created_at: Option<DateTime<Utc>>,
 }
 
+/// Most metadata entries forwarded to a provider
+pub const MAX_PROVIDER_METADATA_ENTRIES: usize = 16;
+
+/// Longest metadata key forwarded to a provider, in characters
+pub const MAX_PROVIDER_METADATA_KEY_LEN: usize = 64;
+
+/// Longest metadata value forwarded to a provider, in characters
+pub const MAX_PROVIDER_METADATA_VALUE_LEN: usize = 512;
+
+impl RequestMetadata {
+    /// Tags sanitized for a provider's `metadata` request field
+    ///
+    /// Keys must be at most [`MAX_PROVIDER_METADATA_KEY_LEN`] characters of
+    /// ASCII letters, digits, `_`, `-` or `.`; other keys are dropped.
+    /// Values are truncated to [`MAX_PROVIDER_METADATA_VALUE_LEN`]
+    /// characters, and only the first [`MAX_PROVIDER_METADATA_ENTRIES`] keys
+    /// in sorted order are kept. Returns `None` if nothing is left.
+    #[must_use]
+    pub fn provider_metadata(&self) -> Option<BTreeMap<String, String>> {
+        let sanitized: BTreeMap<String, String> = self
+            .tags
+            .iter()
+            .filter(|(key, _)| is_valid_metadata_key(key))
+            .map(|(key, value)| {
+                let value = match value.char_indices().nth(MAX_PROVIDER_METADATA_VALUE_LEN) {
+                    Some((end, _)) => value[..end].to_string(),
+                    None => value.clone(),
+                };
+                (key.clone(), value)
+            })
+            .collect::<BTreeMap<_, _>>()
+            .into_iter()
+            .take(MAX_PROVIDER_METADATA_ENTRIES)
+            .collect();
+        (!sanitized.is_empty()).then_some(sanitized)
+    }
+}
+
+fn is_valid_metadata_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.len() <= MAX_PROVIDER_METADATA_KEY_LEN
+        && key
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'_' | b'-' | b'.'))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -739,6 +1109,154 @@ mod tests {
         assert!(request.is_err());
     }
 
+    #[test]
+    fn test_request_builder_matches_manual_request() {
+        let tool = ToolDefinition::function("get_weather")
+            .with_description("Get the weather")
+            .with_parameters(serde_json::json!({ "type": "object" }));
+        let id = RequestId::generate();
+
+        let built = GatewayRequest::builder()
+            .id(id.clone())
+            .model("gpt-4o")
+            .system("You are helpful")
+            .message(ChatMessage::user("Weather in Paris?"))
+            .temperature(0.2)
+            .top_p(0.9)
+            .max_tokens(256)
+            .tool(tool.clone())
+            .stream(true)
+            .build()
+            .expect("should build");
+
+        let manual = GatewayRequest {
+            id,
+            model: "gpt-4o".to_string(),
+            messages: vec![
+                ChatMessage::system("You are helpful"),
+                ChatMessage::user("Weather in Paris?"),
+            ],
+            temperature: Some(0.2),
+            max_tokens: Some(256),
+            max_completion_tokens: None,
+            top_p: Some(0.9),
+            top_k: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            stop: None,
+            stream: true,
+            stream_options: None,
+            n: None,
+            tools: Some(vec![tool]),
+            tool_choice: None,
+            response_format: None,
+            seed: None,
+            safety_settings: None,
+            user: None,
+            store: None,
+            service_tier: None,
+            modalities: None,
+            audio: None,
+            metadata: None,
+        };
+
+        assert_eq!(
+            serde_json::to_value(&built).expect("serialize"),
+            serde_json::to_value(&manual).expect("serialize")
+        );
+    }
+
+    #[test]
+    fn test_request_builder_rejects_out_of_range_values() {
+        let base = || {
+            GatewayRequest::builder()
+                .model("gpt-4o")
+                .message(ChatMessage::user("Hello"))
+        };
+
+        let err = base().temperature(2.5).build().expect_err("temperature");
+        assert!(matches!(
+            err,
+            crate::error::GatewayError::Validation { ref field, ref code, .. }
+                if field.as_deref() == Some("temperature") && code == "invalid_temperature"
+        ));
+
+        let err = base().top_p(0.0).build().expect_err("top_p");
+        assert!(matches!(
+            err,
+            crate::error::GatewayError::Validation { ref field, ref code, .. }
+                if field.as_deref() == Some("top_p") && code == "invalid_top_p"
+        ));
+
+        let err = base().top_p(1.5).build().expect_err("top_p");
+        assert!(matches!(err, crate::error::GatewayError::Validation { .. }));
+
+        let err = base().frequency_penalty(2.5).build().expect_err("frequency_penalty");
+        assert!(matches!(
+            err,
+            crate::error::GatewayError::Validation { ref field, ref code, .. }
+                if field.as_deref() == Some("frequency_penalty")
+                    && code == "invalid_frequency_penalty"
+        ));
+
+        let err = base().presence_penalty(-2.5).build().expect_err("presence_penalty");
+        assert!(matches!(
+            err,
+            crate::error::GatewayError::Validation { ref field, ref code, .. }
+                if field.as_deref() == Some("presence_penalty")
+                    && code == "invalid_presence_penalty"
+        ));
+
+        assert!(base().frequency_penalty(-2.0).presence_penalty(2.0).build().is_ok());
+
+        let err = base().service_tier("premium").build().expect_err("service_tier");
+        assert!(matches!(
+            err,
+            crate::error::GatewayError::Validation { ref field, ref code, .. }
+                if field.as_deref() == Some("service_tier") && code == "invalid_service_tier"
+        ));
+        assert!(base().service_tier("flex").build().is_ok());
+    }
+
+    #[test]
+    fn test_token_limit_reconciliation() {
+        let base = || {
+            GatewayRequest::builder()
+                .model("o3-mini")
+                .message(ChatMessage::user("Hello"))
+        };
+
+        let request = base().max_completion_tokens(500).build().expect("build");
+        assert_eq!(request.token_limit(), Some(500));
+
+        let request = base().max_tokens(500).max_completion_tokens(500).build().expect("build");
+        assert_eq!(request.token_limit(), Some(500));
+
+        let err = base()
+            .max_tokens(100)
+            .max_completion_tokens(500)
+            .build()
+            .expect_err("conflict");
+        assert!(matches!(
+            err,
+            crate::error::GatewayError::Validation { ref field, ref code, .. }
+                if field.as_deref() == Some("max_completion_tokens") && code == "conflicting_max_tokens"
+        ));
+        assert!(err.to_string().contains("max_tokens (100)"));
+
+        assert!(base().max_completion_tokens(0).build().is_err());
+    }
+
+    #[test]
+    fn test_reasoning_model_detection() {
+        for model in ["o1", "o1-mini", "o3-mini", "o4-mini", "gpt-5", "openai/o3"] {
+            assert!(is_reasoning_model(model), "{model}");
+        }
+        for model in ["gpt-4o", "gpt-4o-mini", "gpt-3.5-turbo", "omni-moderation", "claude-3"] {
+            assert!(!is_reasoning_model(model), "{model}");
+        }
+    }
+
     #[test]
     fn test_chat_message_constructors() {
         let system = ChatMessage::system("You are helpful");
@@ -767,4 +1285,46 @@ mod tests {
         let json = serde_json::to_string(&parts_content).expect("serialize");
         assert!(json.contains("text"));
     }
+
+    #[test]
+    fn test_provider_metadata_is_sanitized() {
+        let mut tags: HashMap<String, String> = (0..20)
+            .map(|i| (format!("key_{i:02}"), "v".to_string()))
+            .collect();
+        tags.insert("bad key!".to_string(), "dropped".to_string());
+        tags.insert("k".repeat(65), "dropped".to_string());
+        tags.insert("a_long".to_string(), "é".repeat(600));
+        let metadata = RequestMetadata {
+            tags,
+            ..RequestMetadata::default()
+        };
+
+        let sanitized = metadata.provider_metadata().expect("entries remain");
+
+        assert_eq!(sanitized.len(), MAX_PROVIDER_METADATA_ENTRIES);
+        assert_eq!(sanitized["a_long"].chars().count(), MAX_PROVIDER_METADATA_VALUE_LEN);
+        assert!(sanitized.contains_key("key_14"));
+        assert!(!sanitized.contains_key("key_15"));
+        assert!(!sanitized.contains_key("bad key!"));
+        assert!(RequestMetadata::default().provider_metadata().is_none());
+    }
+    #[test]
+    fn test_audio_output_request() {
+        let request = GatewayRequest::builder()
+            .model("gpt-4o-audio-preview")
+            .message(ChatMessage::user("Say hello"))
+            .audio_output("alloy", "wav")
+            .build()
+            .expect("valid request");
+        assert!(request.wants_audio_output());
+
+        let json = serde_json::to_value(&request).expect("serialize");
+        assert_eq!(json["modalities"], serde_json::json!(["text", "audio"]));
+        assert_eq!(json["audio"], serde_json::json!({"voice": "alloy", "format": "wav"}));
+
+        let mut missing_options = request;
+        missing_options.audio = None;
+        let err = missing_options.validate().unwrap_err();
+        assert!(err.to_string().contains("audio is required"));
+    }
 }
@@ -2,7 +2,7 @@
 //!
 //! This module defines the unified response format that is OpenAI-compatible.
 
-use crate::request::{FunctionCall, MessageRole, ToolCall};
+use crate::request::{AudioContent, ContentPart, FunctionCall, MessageRole, ToolCall};
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 
@@ -31,6 +31,10 @@ pub struct GatewayResponse {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub system_fingerprint: Option<String>,
 
+    /// Processing tier the provider reports having used
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service_tier: Option<String>,
+
     /// Provider that served this request (gateway extension)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub provider: Option<String>,
@@ -82,6 +86,7 @@ pub struct GatewayResponseBuilder {
     choices: Vec<Choice>,
     usage: Option<Usage>,
     system_fingerprint: Option<String>,
+    service_tier: Option<String>,
     provider: Option<String>,
 }
 
@@ -128,6 +133,13 @@ impl GatewayResponseBuilder {
         self
     }
 
+    /// Set the service tier
+    #[must_use]
+    pub fn service_tier(mut self, tier: impl Into<String>) -> Self {
+        self.service_tier = Some(tier.into());
+        self
+    }
+
     /// Set the provider
     #[must_use]
     pub fn provider(mut self, provider: impl Into<String>) -> Self {
@@ -148,6 +160,7 @@ impl GatewayResponseBuilder {
             choices: self.choices,
             usage: self.usage.unwrap_or_default(),
             system_fingerprint: self.system_fingerprint,
+            service_tier: self.service_tier,
             provider: self.provider,
         }
     }
@@ -182,6 +195,7 @@ impl Choice {
                 content: Some(content.into()),
                 tool_calls: None,
                 function_call: None,
+                audio: None,
             },
             finish_reason: Some(finish_reason),
             logprobs: None,
@@ -202,6 +216,7 @@ impl Choice {
                 content: None,
                 tool_calls: Some(tool_calls),
                 function_call: None,
+                audio: None,
             },
             finish_reason: Some(finish_reason),
             logprobs: None,
@@ -226,6 +241,26 @@ pub struct ResponseMessage {
     /// Deprecated: Function call (use tool_calls instead)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub function_call: Option<FunctionCall>,
+
+    /// Generated audio, for requests with the `audio` output modality
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audio: Option<AudioContent>,
+}
+
+impl ResponseMessage {
+    /// Content as parts: the text, if any, followed by the audio, if any
+    #[must_use]
+    pub fn parts(&self) -> Vec<ContentPart> {
+        let text = self
+            .content
+            .iter()
+            .map(|text| ContentPart::Text { text: text.clone() });
+        let audio = self
+            .audio
+            .iter()
+            .map(|audio| ContentPart::Audio { audio: audio.clone() });
+        text.chain(audio).collect()
+    }
 }
 
 /// Reason for finishing generation
@@ -302,6 +337,18 @@ pub struct Usage {
 
     /// Total number of tokens used
     pub total_tokens: u32,
+
+    /// Prompt tokens read from the provider's prompt cache
+    ///
+    /// These are included in `prompt_tokens` and billed at a discounted rate.
+    #[serde(default, alias = "cache_read_tokens", skip_serializing_if = "Option::is_none")]
+    pub prompt_tokens_cached: Option<u32>,
+
+    /// Completion tokens spent on hidden reasoning
+    ///
+    /// These are included in `completion_tokens`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completion_tokens_reasoning: Option<u32>,
 }
 
 impl Usage {
@@ -312,14 +359,49 @@ impl Usage {
             prompt_tokens,
             completion_tokens,
             total_tokens: prompt_tokens + completion_tokens,
+            prompt_tokens_cached: None,
+            completion_tokens_reasoning: None,
         }
     }
 
+    /// Set the number of prompt tokens read from the provider's cache
+    #[must_use]
+    pub fn with_prompt_tokens_cached(mut self, tokens: u32) -> Self {
+        self.prompt_tokens_cached = Some(tokens);
+        self
+    }
+
+    /// Set the number of completion tokens spent on reasoning
+    #[must_use]
+    pub fn with_completion_tokens_reasoning(mut self, tokens: u32) -> Self {
+        self.completion_tokens_reasoning = Some(tokens);
+        self
+    }
+
+    /// Prompt tokens billed at the full input price
+    #[must_use]
+    pub fn full_price_prompt_tokens(&self) -> u32 {
+        self.prompt_tokens
+            .saturating_sub(self.prompt_tokens_cached.unwrap_or(0))
+    }
+
     /// Add another usage record to this one
     pub fn add(&mut self, other: &Self) {
         self.prompt_tokens += other.prompt_tokens;
         self.completion_tokens += other.completion_tokens;
         self.total_tokens += other.total_tokens;
+        self.prompt_tokens_cached =
+            add_optional(self.prompt_tokens_cached, other.prompt_tokens_cached);
+        self.completion_tokens_reasoning =
+            add_optional(self.completion_tokens_reasoning, other.completion_tokens_reasoning);
+    }
+}
+
+/// Sum two optional counts, staying `None` only if neither is reported
+fn add_optional(a: Option<u32>, b: Option<u32>) -> Option<u32> {
+    match (a, b) {
+        (None, None) => None,
+        (a, b) => Some(a.unwrap_or(0) + b.unwrap_or(0)),
     }
 }
 
@@ -0,0 +1,152 @@
+//! Shadow response comparison.
+//!
+//! When requests are mirrored to a shadow provider, [`ResponseDiff`]
+//! summarizes how far the shadow's answer drifted from the primary's.
+//! Embedding-based similarity is optional and plugged in through a
+//! [`SimilarityHook`].
+
+use crate::response::GatewayResponse;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Computes semantic similarity between two texts, e.g. via embeddings
+#[async_trait]
+pub trait SimilarityHook: Send + Sync {
+    /// Similarity of `primary` and `shadow` in 0.0 - 1.0, `None` if unavailable
+    async fn similarity(&self, primary: &str, shadow: &str) -> Option<f64>;
+}
+
+/// Differences between a primary and a shadow response
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResponseDiff {
+    /// Jaccard overlap of the responses' lowercased words (0.0 - 1.0)
+    pub token_overlap: f64,
+    /// Shadow word count minus primary word count
+    pub length_delta: i64,
+    /// Whether both responses stopped for the same reason
+    pub finish_reason_match: bool,
+    /// Similarity reported by the [`SimilarityHook`], if one is configured
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub embedding_similarity: Option<f64>,
+}
+
+impl ResponseDiff {
+    /// Compare the first choice of `primary` and `shadow`
+    #[must_use]
+    pub fn between(primary: &GatewayResponse, shadow: &GatewayResponse) -> Self {
+        let primary_text = primary.content().unwrap_or_default();
+        let shadow_text = shadow.content().unwrap_or_default();
+
+        let primary_words = words(primary_text);
+        let shadow_words = words(shadow_text);
+        let primary_set: HashSet<&str> = primary_words.iter().map(String::as_str).collect();
+        let shadow_set: HashSet<&str> = shadow_words.iter().map(String::as_str).collect();
+
+        let union = primary_set.union(&shadow_set).count();
+        let token_overlap = if union == 0 {
+            1.0
+        } else {
+            primary_set.intersection(&shadow_set).count() as f64 / union as f64
+        };
+
+        Self {
+            token_overlap,
+            length_delta: shadow_words.len() as i64 - primary_words.len() as i64,
+            finish_reason_match: primary.finish_reason() == shadow.finish_reason(),
+            embedding_similarity: None,
+        }
+    }
+
+    /// Compare the responses, asking `hook` for their semantic similarity
+    pub async fn between_with_hook(
+        primary: &GatewayResponse,
+        shadow: &GatewayResponse,
+        hook: &dyn SimilarityHook,
+    ) -> Self {
+        let mut diff = Self::between(primary, shadow);
+        diff.embedding_similarity = hook
+            .similarity(
+                primary.content().unwrap_or_default(),
+                shadow.content().unwrap_or_default(),
+            )
+            .await;
+        diff
+    }
+}
+
+/// Lowercased words with surrounding punctuation stripped
+fn words(text: &str) -> Vec<String> {
+    text.split_whitespace()
+        .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase())
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::response::{Choice, FinishReason};
+
+    fn response(content: &str, finish_reason: FinishReason) -> GatewayResponse {
+        GatewayResponse::builder()
+            .id("resp")
+            .model("gpt-4o")
+            .choice(Choice::new(0, content, finish_reason))
+            .build()
+    }
+
+    struct FixedSimilarity(f64);
+
+    #[async_trait]
+    impl SimilarityHook for FixedSimilarity {
+        async fn similarity(&self, _primary: &str, _shadow: &str) -> Option<f64> {
+            Some(self.0)
+        }
+    }
+
+    #[test]
+    fn test_identical_responses() {
+        let primary = response("The capital of France is Paris.", FinishReason::Stop);
+        let diff = ResponseDiff::between(&primary, &primary.clone());
+
+        assert!((diff.token_overlap - 1.0).abs() < f64::EPSILON);
+        assert_eq!(diff.length_delta, 0);
+        assert!(diff.finish_reason_match);
+        assert!(diff.embedding_similarity.is_none());
+    }
+
+    #[test]
+    fn test_different_responses() {
+        let primary = response("The capital of France is Paris.", FinishReason::Stop);
+        let shadow = response(
+            "I cannot answer questions about geography right now, sorry",
+            FinishReason::Length,
+        );
+        let diff = ResponseDiff::between(&primary, &shadow);
+
+        assert!(diff.token_overlap < 0.1);
+        assert_eq!(diff.length_delta, 3);
+        assert!(!diff.finish_reason_match);
+    }
+
+    #[test]
+    fn test_overlap_ignores_case_and_punctuation() {
+        let primary = response("Paris, France.", FinishReason::Stop);
+        let shadow = response("paris france", FinishReason::Stop);
+
+        let diff = ResponseDiff::between(&primary, &shadow);
+        assert!((diff.token_overlap - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[tokio::test]
+    async fn test_similarity_hook_is_recorded() {
+        let primary = response("yes", FinishReason::Stop);
+        let shadow = response("indeed", FinishReason::Stop);
+
+        let diff =
+            ResponseDiff::between_with_hook(&primary, &shadow, &FixedSimilarity(0.92)).await;
+        assert!(diff.token_overlap < f64::EPSILON);
+        assert_eq!(diff.embedding_similarity, Some(0.92));
+    }
+}
@@ -0,0 +1,234 @@
+//! Server-sent event decoding for provider streams.
+//!
+//! [`SseDecoder`] turns the raw bytes of a `text/event-stream` body into
+//! events, following the framing rules of the HTML event stream format:
+//! events end at a blank line, lines end in `\n`, `\r\n` or `\r`, repeated
+//! `data:` fields are joined with newlines, and lines starting with `:` are
+//! comments. Bytes may arrive split at any point, including inside a line
+//! terminator or a multi-byte character.
+
+use futures::stream::{Stream, StreamExt};
+use std::collections::VecDeque;
+
+/// A decoded server-sent event
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SseEvent {
+    /// The `event:` field, if the event had one
+    pub event: Option<String>,
+    /// The `data:` fields, joined with newlines
+    pub data: String,
+    /// The `id:` field, if the event had one
+    pub id: Option<String>,
+}
+
+impl SseEvent {
+    /// Event type, defaulting to `message` as the format specifies
+    #[must_use]
+    pub fn event_type(&self) -> &str {
+        self.event.as_deref().unwrap_or("message")
+    }
+}
+
+/// Incremental decoder for a `text/event-stream` body
+#[derive(Debug, Default)]
+pub struct SseDecoder {
+    /// Bytes of a line that has not been terminated yet
+    line: Vec<u8>,
+    /// The last line ended in `\r`, so a leading `\n` belongs to it
+    skip_lf: bool,
+    /// Whether any bytes have been seen, for stripping a leading BOM
+    started: bool,
+    event: Option<String>,
+    data: Vec<String>,
+    id: Option<String>,
+}
+
+impl SseDecoder {
+    /// Create a decoder for a new stream
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed the next bytes of the body, returning the events they complete
+    pub fn decode(&mut self, mut bytes: &[u8]) -> Vec<SseEvent> {
+        let mut events = Vec::new();
+        if !self.started && !bytes.is_empty() {
+            self.started = true;
+            bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+        }
+
+        for &byte in bytes {
+            if std::mem::take(&mut self.skip_lf) && byte == b'\n' {
+                continue;
+            }
+            match byte {
+                b'\n' | b'\r' => {
+                    self.skip_lf = byte == b'\r';
+                    let line = std::mem::take(&mut self.line);
+                    events.extend(self.process_line(&line));
+                }
+                _ => self.line.push(byte),
+            }
+        }
+        events
+    }
+
+    /// Flush an event left unterminated when the body ends
+    ///
+    /// Strictly such an event is incomplete, but some servers close the
+    /// stream straight after the last `data:` line.
+    pub fn finish(&mut self) -> Option<SseEvent> {
+        if !self.line.is_empty() {
+            let line = std::mem::take(&mut self.line);
+            if let Some(event) = self.process_line(&line) {
+                return Some(event);
+            }
+        }
+        self.dispatch()
+    }
+
+    /// Apply one line, returning the event it completes, if any
+    fn process_line(&mut self, line: &[u8]) -> Option<SseEvent> {
+        if line.is_empty() {
+            return self.dispatch();
+        }
+        if line[0] == b':' {
+            return None;
+        }
+
+        let line = String::from_utf8_lossy(line);
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line.as_ref(), ""),
+        };
+        match field {
+            "event" => self.event = Some(value.to_string()),
+            "data" => self.data.push(value.to_string()),
+            "id" if !value.contains('\0') => self.id = Some(value.to_string()),
+            // `retry` only matters to clients that reconnect
+            _ => {}
+        }
+        None
+    }
+
+    /// End the current event; events without data are dropped
+    fn dispatch(&mut self) -> Option<SseEvent> {
+        let event = self.event.take();
+        let id = self.id.take();
+        if self.data.is_empty() {
+            return None;
+        }
+        Some(SseEvent {
+            event,
+            data: std::mem::take(&mut self.data).join("\n"),
+            id,
+        })
+    }
+}
+
+/// Decode a stream of body chunks into server-sent events
+///
+/// A chunk error is passed through and ends the stream.
+pub fn decode_stream<S, B, E>(body: S) -> impl Stream<Item = Result<SseEvent, E>>
+where
+    S: Stream<Item = Result<B, E>>,
+    B: AsRef<[u8]>,
+{
+    let state = (Box::pin(body), SseDecoder::new(), VecDeque::new(), false);
+    futures::stream::unfold(state, |(mut body, mut decoder, mut ready, mut done)| async move {
+        loop {
+            if let Some(event) = ready.pop_front() {
+                return Some((Ok(event), (body, decoder, ready, done)));
+            }
+            if done {
+                return None;
+            }
+            match body.next().await {
+                Some(Ok(chunk)) => ready.extend(decoder.decode(chunk.as_ref())),
+                Some(Err(err)) => return Some((Err(err), (body, decoder, ready, true))),
+                None => {
+                    done = true;
+                    ready.extend(decoder.finish());
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode_all(chunks: &[&[u8]]) -> Vec<SseEvent> {
+        let mut decoder = SseDecoder::new();
+        let mut events: Vec<SseEvent> = chunks.iter().flat_map(|c| decoder.decode(c)).collect();
+        events.extend(decoder.finish());
+        events
+    }
+
+    fn data(events: &[SseEvent]) -> Vec<&str> {
+        events.iter().map(|e| e.data.as_str()).collect()
+    }
+
+    #[test]
+    fn test_crlf_and_cr_line_endings() {
+        let events = decode_all(&[b"data: one\r\n\r\ndata: two\r\rdata: three\n\n"]);
+        assert_eq!(data(&events), ["one", "two", "three"]);
+    }
+
+    #[test]
+    fn test_multi_line_data_and_event_fields() {
+        let events = decode_all(&[
+            b"event: content_block_delta\nid: 7\ndata: {\"a\":\ndata:1}\n\ndata: plain\n\n",
+        ]);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].event_type(), "content_block_delta");
+        assert_eq!(events[0].id.as_deref(), Some("7"));
+        assert_eq!(events[0].data, "{\"a\":\n1}");
+        assert_eq!(events[1].event_type(), "message");
+        assert_eq!(events[1].id, None);
+    }
+
+    #[test]
+    fn test_events_split_across_reads() {
+        let body = "event: ping\r\ndata: {\"text\":\"héllo\"}\r\n\r\ndata: [DONE]\r\n\r\n".as_bytes();
+        let whole = decode_all(&[body]);
+        assert_eq!(whole.len(), 2);
+
+        // Every split point, including inside `\r\n` and inside `é`
+        for split in 1..body.len() {
+            let halves = [&body[..split], &body[split..]];
+            assert_eq!(decode_all(&halves), whole, "split at {split}");
+        }
+        let bytes: Vec<&[u8]> = body.chunks(1).collect();
+        assert_eq!(decode_all(&bytes), whole);
+    }
+
+    #[test]
+    fn test_comments_and_empty_events_are_skipped() {
+        let events = decode_all(&[
+            b": keep-alive\n\ndata: a\n: interleaved comment\ndata: b\n\nevent: empty\n\nretry: 10\ndata\n\n",
+        ]);
+        assert_eq!(data(&events), ["a\nb", ""]);
+        assert_eq!(events[1].event, None);
+    }
+
+    #[test]
+    fn test_leading_bom_and_unterminated_last_event() {
+        let events = decode_all(&[b"\xEF\xBB\xBFdata: first\n\ndata: last"]);
+        assert_eq!(data(&events), ["first", "last"]);
+        assert_eq!(data(&decode_all(&[b"data:x\n"])), ["x"]);
+        assert!(decode_all(&[b"event: only\n"]).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_decode_stream_passes_errors_through() {
+        let chunks: Vec<Result<&[u8], &str>> =
+            vec![Ok(b"data: a\n"), Ok(b"\ndata: b"), Err("reset"), Ok(b"\n\n")];
+        let items: Vec<_> = decode_stream(futures::stream::iter(chunks)).collect().await;
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].as_ref().map(|e| e.data.as_str()), Ok("a"));
+        assert_eq!(items[1], Err("reset"));
+    }
+}
@@ -2,9 +2,16 @@
 //!
 //! This module defines the types used for Server-Sent Events (SSE) streaming responses.
 
+use crate::context::RequestContext;
+use crate::error::GatewayError;
 use crate::request::MessageRole;
 use crate::response::{FinishReason, Usage};
+use futures::stream::{BoxStream, Stream};
 use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
 
 /// Streaming chat chunk (SSE data)
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -303,9 +310,311 @@ impl StreamMessage {
     }
 }
 
+/// Chunk stream bounded by an absolute maximum duration
+///
+/// Created by [`with_max_duration`].
+pub struct MaxDurationStream {
+    inner: Option<BoxStream<'static, Result<ChatChunk, GatewayError>>>,
+    deadline: Pin<Box<tokio::time::Sleep>>,
+    max_duration: Duration,
+}
+
+impl Stream for MaxDurationStream {
+    type Item = Result<ChatChunk, GatewayError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        let Some(inner) = this.inner.as_mut() else {
+            return Poll::Ready(None);
+        };
+
+        if this.deadline.as_mut().poll(cx).is_ready() {
+            // Dropping the upstream stream aborts the provider connection
+            this.inner = None;
+            return Poll::Ready(Some(Err(GatewayError::timeout(this.max_duration))));
+        }
+
+        match inner.as_mut().poll_next(cx) {
+            Poll::Ready(None) => {
+                this.inner = None;
+                Poll::Ready(None)
+            }
+            other => other,
+        }
+    }
+}
+
+/// Terminate a chunk stream once `max_duration` has elapsed
+///
+/// The deadline is absolute: it is not reset by chunk activity, so a stream
+/// that trickles data indefinitely is still cut off. On expiry the upstream
+/// stream is dropped and a single [`GatewayError::Timeout`] is yielded.
+#[must_use]
+pub fn with_max_duration(
+    stream: BoxStream<'static, Result<ChatChunk, GatewayError>>,
+    max_duration: Duration,
+) -> MaxDurationStream {
+    MaxDurationStream {
+        inner: Some(stream),
+        deadline: Box::pin(tokio::time::sleep(max_duration)),
+        max_duration,
+    }
+}
+
+/// Restart a chunk stream that is reset before delivering anything
+///
+/// If the upstream yields a connection reset error (see
+/// [`GatewayError::is_connection_reset`]) before its first chunk, `restart`
+/// is called for a fresh stream, at most `max_restarts` times. Once any
+/// chunk has been delivered a reset is passed through unchanged, since the
+/// client cannot un-receive partial content.
+pub fn with_reset_restart<F, Fut>(
+    stream: BoxStream<'static, Result<ChatChunk, GatewayError>>,
+    max_restarts: u32,
+    restart: F,
+) -> BoxStream<'static, Result<ChatChunk, GatewayError>>
+where
+    F: FnMut() -> Fut + Send + 'static,
+    Fut: Future<Output = Result<BoxStream<'static, Result<ChatChunk, GatewayError>>, GatewayError>>
+        + Send
+        + 'static,
+{
+    with_retry_before_first_chunk(
+        stream,
+        move |error, attempt| {
+            (error.is_connection_reset() && attempt < max_restarts).then_some(Duration::ZERO)
+        },
+        restart,
+    )
+}
+
+/// Retry a chunk stream that fails before delivering anything
+///
+/// When the upstream yields an error before its first chunk, `retry` is
+/// asked with the error and the number of retries made so far; `Some(delay)`
+/// waits that long and calls `restart` for a fresh stream, `None` passes the
+/// error through. Errors from `restart` itself are offered to `retry` the
+/// same way. Once any chunk has been delivered errors are always passed
+/// through, since the client cannot un-receive partial content.
+pub fn with_retry_before_first_chunk<R, F, Fut>(
+    stream: BoxStream<'static, Result<ChatChunk, GatewayError>>,
+    retry: R,
+    restart: F,
+) -> BoxStream<'static, Result<ChatChunk, GatewayError>>
+where
+    R: FnMut(&GatewayError, u32) -> Option<Duration> + Send + 'static,
+    F: FnMut() -> Fut + Send + 'static,
+    Fut: Future<Output = Result<BoxStream<'static, Result<ChatChunk, GatewayError>>, GatewayError>>
+        + Send
+        + 'static,
+{
+    struct State<R, F> {
+        inner: Option<BoxStream<'static, Result<ChatChunk, GatewayError>>>,
+        delivered: bool,
+        attempts: u32,
+        retry: R,
+        restart: F,
+    }
+
+    let state = State {
+        inner: Some(stream),
+        delivered: false,
+        attempts: 0,
+        retry,
+        restart,
+    };
+
+    Box::pin(futures::stream::unfold(state, |mut state| async move {
+        loop {
+            let inner = state.inner.as_mut()?;
+            let mut error = match futures::StreamExt::next(inner).await {
+                Some(Ok(chunk)) => {
+                    state.delivered = true;
+                    return Some((Ok(chunk), state));
+                }
+                Some(Err(e)) if !state.delivered => e,
+                Some(Err(e)) => return Some((Err(e), state)),
+                None => return None,
+            };
+
+            loop {
+                let Some(delay) = (state.retry)(&error, state.attempts) else {
+                    // Nothing left to read from if no stream is in place
+                    return Some((Err(error), state));
+                };
+                state.attempts += 1;
+                if !delay.is_zero() {
+                    tokio::time::sleep(delay).await;
+                }
+                match (state.restart)().await {
+                    Ok(stream) => {
+                        state.inner = Some(stream);
+                        break;
+                    }
+                    Err(e) => {
+                        state.inner = None;
+                        error = e;
+                    }
+                }
+            }
+        }
+    }))
+}
+
+/// Terminate a chunk stream once `ctx` is cancelled
+///
+/// Only cancellation ends the stream; the context's deadline does not, since
+/// streams are bounded separately by [`with_max_duration`]. On cancellation
+/// the upstream stream is dropped and a single error is yielded.
+pub fn with_cancellation(
+    stream: BoxStream<'static, Result<ChatChunk, GatewayError>>,
+    ctx: RequestContext,
+) -> BoxStream<'static, Result<ChatChunk, GatewayError>> {
+    Box::pin(futures::stream::unfold(
+        Some((stream, ctx)),
+        |state| async move {
+            let (mut inner, ctx) = state?;
+            tokio::select! {
+                biased;
+                () = ctx.cancelled() => {
+                    Some((Err(GatewayError::internal("Request cancelled")), None))
+                }
+                item = futures::StreamExt::next(&mut inner) => {
+                    item.map(|item| (item, Some((inner, ctx))))
+                }
+            }
+        },
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use futures::StreamExt;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+
+    type ChunkStream = BoxStream<'static, Result<ChatChunk, GatewayError>>;
+
+    fn content_chunk(text: &str) -> Result<ChatChunk, GatewayError> {
+        Ok(ChatChunk::builder()
+            .choice(ChunkChoice::with_content(0, text))
+            .build())
+    }
+
+    fn scripted(items: Vec<Result<ChatChunk, GatewayError>>) -> ChunkStream {
+        futures::stream::iter(items).boxed()
+    }
+
+    #[tokio::test]
+    async fn test_cancellation_ends_stream() {
+        let ctx = RequestContext::new();
+        let pending = futures::stream::pending().boxed();
+        let mut stream = with_cancellation(
+            scripted(vec![content_chunk("Hello")]).chain(pending).boxed(),
+            ctx.clone(),
+        );
+
+        assert!(stream.next().await.unwrap().is_ok());
+        ctx.cancel();
+        let err = stream.next().await.unwrap().unwrap_err();
+        assert!(err.to_string().contains("cancelled"));
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_reset_before_first_chunk_restarts() {
+        let calls = Arc::new(AtomicU32::new(0));
+        let counter = calls.clone();
+        let stream = with_reset_restart(
+            scripted(vec![Err(GatewayError::connection_reset("reset"))]),
+            1,
+            move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+                async { Ok(scripted(vec![content_chunk("Hello")])) }
+            },
+        );
+
+        let items: Vec<_> = stream.collect().await;
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].as_ref().unwrap().content(), Some("Hello"));
+    }
+
+    #[tokio::test]
+    async fn test_reset_after_partial_content_is_surfaced() {
+        let calls = Arc::new(AtomicU32::new(0));
+        let counter = calls.clone();
+        let stream = with_reset_restart(
+            scripted(vec![
+                content_chunk("Hel"),
+                Err(GatewayError::connection_reset("reset")),
+            ]),
+            1,
+            move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+                async { Ok(scripted(vec![content_chunk("Hello")])) }
+            },
+        );
+
+        let items: Vec<_> = stream.collect().await;
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+        assert_eq!(items.len(), 2);
+        assert!(items[1].as_ref().unwrap_err().is_connection_reset());
+    }
+
+    #[tokio::test]
+    async fn test_restarts_are_bounded() {
+        let stream = with_reset_restart(
+            scripted(vec![Err(GatewayError::connection_reset("reset"))]),
+            2,
+            || async { Ok(scripted(vec![Err(GatewayError::connection_reset("reset"))])) },
+        );
+
+        let items: Vec<_> = stream.collect().await;
+        assert_eq!(items.len(), 1);
+        assert!(items[0].as_ref().unwrap_err().is_connection_reset());
+    }
+
+    #[tokio::test]
+    async fn test_retry_before_first_chunk_uses_policy() {
+        let calls = Arc::new(AtomicU32::new(0));
+        let counter = calls.clone();
+        let overloaded = GatewayError::provider("test", "overloaded", Some(503), true);
+        let stream = with_retry_before_first_chunk(
+            scripted(vec![Err(overloaded)]),
+            |error, attempt| (error.is_retryable() && attempt < 2).then_some(Duration::ZERO),
+            move || {
+                let attempt = counter.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    if attempt == 0 {
+                        Err(GatewayError::timeout(Duration::from_secs(1)))
+                    } else {
+                        Ok(scripted(vec![content_chunk("Hello")]))
+                    }
+                }
+            },
+        );
+
+        let items: Vec<_> = stream.collect().await;
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].as_ref().unwrap().content(), Some("Hello"));
+    }
+
+    #[tokio::test]
+    async fn test_retry_skips_non_retryable_errors() {
+        let stream = with_retry_before_first_chunk(
+            scripted(vec![Err(GatewayError::validation("bad", None, "invalid"))]),
+            |error, _| error.is_retryable().then_some(Duration::ZERO),
+            || async { Ok(scripted(vec![content_chunk("Hello")])) },
+        );
+
+        let items: Vec<_> = stream.collect().await;
+        assert_eq!(items.len(), 1);
+        assert!(items[0].is_err());
+    }
 
     #[test]
     fn test_chunk_builder() {
@@ -384,4 +693,57 @@ mod tests {
         let choice = ChunkChoice::with_tool_call(0, vec![delta]);
         assert!(choice.delta.tool_calls.is_some());
     }
+
+    /// Stream that yields a tiny chunk every 10ms, forever
+    fn trickle() -> BoxStream<'static, Result<ChatChunk, GatewayError>> {
+        use futures::StreamExt;
+
+        futures::stream::unfold((), |()| async {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            let chunk = ChatChunk::builder()
+                .choice(ChunkChoice::with_content(0, "."))
+                .build();
+            Some((Ok(chunk), ()))
+        })
+        .boxed()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_max_duration_terminates_trickling_stream() {
+        use futures::StreamExt;
+
+        let start = tokio::time::Instant::now();
+        let mut stream = with_max_duration(trickle(), Duration::from_secs(1));
+
+        let mut chunks = 0;
+        let mut error = None;
+        while let Some(item) = stream.next().await {
+            match item {
+                Ok(_) => chunks += 1,
+                Err(e) => error = Some(e),
+            }
+        }
+
+        assert_eq!(start.elapsed(), Duration::from_secs(1));
+        assert!(chunks > 0);
+        assert!(matches!(
+            error,
+            Some(GatewayError::Timeout { duration }) if duration == Duration::from_secs(1)
+        ));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_max_duration_passes_through_finished_stream() {
+        use futures::StreamExt;
+
+        let chunks = vec![Ok(ChatChunk::builder().build()), Ok(ChatChunk::builder().build())];
+        let stream = with_max_duration(
+            futures::stream::iter(chunks).boxed(),
+            Duration::from_secs(1),
+        );
+
+        let items: Vec<_> = stream.collect().await;
+        assert_eq!(items.len(), 2);
+        assert!(items.iter().all(Result::is_ok));
+    }
 }
@@ -0,0 +1,350 @@
+//! Token estimation before provider dispatch.
+//!
+//! Rate limiting, cost tracking and context-window routing all need a
+//! prompt size before any provider has reported usage. A [`TokenEstimator`]
+//! counts the tokens in a piece of text; the provided
+//! [`TokenEstimator::estimate_prompt`] builds on that to size a whole
+//! request, adding the per-message framing chat models wrap around each
+//! message and a fixed placeholder for each image.
+//!
+//! - [`CharHeuristicEstimator`] assumes a fixed number of characters per
+//!   token and works for any model.
+//! - `TiktokenEstimator`, behind the `tiktoken` feature, runs OpenAI's BPE
+//!   encodings for OpenAI-family models and falls back to the heuristic for
+//!   everything else.
+//!
+//! Share one estimator between the components that compare estimates, so
+//! they all agree on the size of a request.
+
+use crate::request::{ContentPart, GatewayRequest, ImageDetail, MessageContent};
+use std::sync::Arc;
+
+/// Framing tokens chat models add around every message
+pub const MESSAGE_OVERHEAD_TOKENS: u32 = 3;
+
+/// Tokens that prime the assistant's reply
+pub const REPLY_PRIMING_TOKENS: u32 = 3;
+
+/// Placeholder for a low-detail image
+pub const LOW_DETAIL_IMAGE_TOKENS: u32 = 85;
+
+/// Placeholder for an image at `auto` or `high` detail
+///
+/// The real cost depends on the image's dimensions, which are not known
+/// before the provider decodes it; this is the cost of a 1024x1024 image.
+pub const HIGH_DETAIL_IMAGE_TOKENS: u32 = 765;
+
+/// Estimator shared between components
+pub type SharedTokenEstimator = Arc<dyn TokenEstimator>;
+
+/// Counts tokens before a provider reports usage
+pub trait TokenEstimator: Send + Sync {
+    /// Estimate the tokens in `text` as `model` would count them
+    fn count_text(&self, model: &str, text: &str) -> u32;
+
+    /// Estimate the prompt tokens of a request
+    ///
+    /// Counts each message's role, name, text and tool calls plus
+    /// [`MESSAGE_OVERHEAD_TOKENS`] of framing, a placeholder for each image,
+    /// audio transcripts, tool definitions and [`REPLY_PRIMING_TOKENS`].
+    fn estimate_prompt(&self, request: &GatewayRequest) -> u32 {
+        let model = request.model.as_str();
+        let mut tokens = REPLY_PRIMING_TOKENS;
+
+        for message in &request.messages {
+            tokens = tokens
+                .saturating_add(MESSAGE_OVERHEAD_TOKENS)
+                .saturating_add(self.count_text(model, &message.role.to_string()));
+            if let Some(name) = &message.name {
+                tokens = tokens.saturating_add(self.count_text(model, name));
+            }
+            match &message.content {
+                MessageContent::Text(text) => {
+                    tokens = tokens.saturating_add(self.count_text(model, text));
+                }
+                MessageContent::Parts(parts) => {
+                    for part in parts {
+                        let part_tokens = match part {
+                            ContentPart::Text { text } => self.count_text(model, text),
+                            ContentPart::ImageUrl { image_url } => match image_url.detail {
+                                Some(ImageDetail::Low) => LOW_DETAIL_IMAGE_TOKENS,
+                                _ => HIGH_DETAIL_IMAGE_TOKENS,
+                            },
+                            ContentPart::Audio { audio } => audio
+                                .transcript
+                                .as_deref()
+                                .map_or(0, |transcript| self.count_text(model, transcript)),
+                        };
+                        tokens = tokens.saturating_add(part_tokens);
+                    }
+                }
+            }
+            for call in message.tool_calls.iter().flatten() {
+                tokens = tokens
+                    .saturating_add(self.count_text(model, &call.function.name))
+                    .saturating_add(self.count_text(model, &call.function.arguments));
+            }
+        }
+
+        if let Some(tools) = &request.tools {
+            if let Ok(definitions) = serde_json::to_string(tools) {
+                tokens = tokens.saturating_add(self.count_text(model, &definitions));
+            }
+        }
+        tokens
+    }
+
+    /// Estimate the tokens a request may use in total
+    ///
+    /// The prompt plus the requested completion limit, which is what
+    /// tokens-per-minute limits charge up front.
+    fn estimate_request(&self, request: &GatewayRequest) -> u32 {
+        self.estimate_prompt(request)
+            .saturating_add(request.token_limit().unwrap_or(0))
+    }
+}
+
+/// Estimates tokens from a fixed number of characters per token
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CharHeuristicEstimator {
+    chars_per_token: f64,
+}
+
+impl CharHeuristicEstimator {
+    /// Characters per token for English text on current BPE tokenizers
+    pub const DEFAULT_CHARS_PER_TOKEN: f64 = 4.0;
+
+    /// Estimate one token per `chars_per_token` characters
+    ///
+    /// Values below one are treated as one.
+    #[must_use]
+    pub fn new(chars_per_token: f64) -> Self {
+        Self {
+            chars_per_token: chars_per_token.max(1.0),
+        }
+    }
+
+    /// Characters counted as one token
+    #[must_use]
+    pub fn chars_per_token(&self) -> f64 {
+        self.chars_per_token
+    }
+}
+
+impl Default for CharHeuristicEstimator {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CHARS_PER_TOKEN)
+    }
+}
+
+impl TokenEstimator for CharHeuristicEstimator {
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn count_text(&self, _model: &str, text: &str) -> u32 {
+        let chars = text.chars().count();
+        if chars == 0 {
+            return 0;
+        }
+        let tokens = (chars as f64 / self.chars_per_token).ceil();
+        if tokens >= f64::from(u32::MAX) {
+            u32::MAX
+        } else {
+            tokens as u32
+        }
+    }
+}
+
+#[cfg(feature = "tiktoken")]
+pub use self::tiktoken::TiktokenEstimator;
+
+#[cfg(feature = "tiktoken")]
+mod tiktoken {
+    use super::{CharHeuristicEstimator, TokenEstimator};
+    use std::sync::OnceLock;
+    use tiktoken_rs::tokenizer::{get_tokenizer, Tokenizer};
+    use tiktoken_rs::CoreBPE;
+
+    /// Counts tokens with OpenAI's BPE encodings
+    ///
+    /// Models tiktoken does not recognize, such as Claude or Gemini, are
+    /// estimated with the fallback heuristic. Encodings are loaded on first
+    /// use and kept for the life of the estimator.
+    #[derive(Default)]
+    pub struct TiktokenEstimator {
+        fallback: CharHeuristicEstimator,
+        cl100k: OnceLock<Option<CoreBPE>>,
+        o200k: OnceLock<Option<CoreBPE>>,
+    }
+
+    impl TiktokenEstimator {
+        /// Create an estimator with the default fallback heuristic
+        #[must_use]
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Estimate unrecognized models with `fallback`
+        #[must_use]
+        pub fn with_fallback(mut self, fallback: CharHeuristicEstimator) -> Self {
+            self.fallback = fallback;
+            self
+        }
+
+        /// The encoding for `model`, if it is an OpenAI-family model
+        fn encoding(&self, model: &str) -> Option<&CoreBPE> {
+            // Provider-prefixed names such as `openai/gpt-4o` still count
+            let model = model.rsplit('/').next().unwrap_or(model);
+            match get_tokenizer(model)? {
+                Tokenizer::O200kBase => self
+                    .o200k
+                    .get_or_init(|| tiktoken_rs::o200k_base().ok())
+                    .as_ref(),
+                Tokenizer::Cl100kBase => self
+                    .cl100k
+                    .get_or_init(|| tiktoken_rs::cl100k_base().ok())
+                    .as_ref(),
+                // Completion-era encodings are not used by chat models
+                _ => None,
+            }
+        }
+    }
+
+    impl TokenEstimator for TiktokenEstimator {
+        fn count_text(&self, model: &str, text: &str) -> u32 {
+            match self.encoding(model) {
+                Some(bpe) => {
+                    u32::try_from(bpe.encode_ordinary(text).len()).unwrap_or(u32::MAX)
+                }
+                None => self.fallback.count_text(model, text),
+            }
+        }
+    }
+}
+
+/// The best estimator available in this build
+///
+/// Tiktoken when the `tiktoken` feature is enabled, otherwise the character
+/// heuristic.
+#[must_use]
+pub fn default_estimator() -> SharedTokenEstimator {
+    #[cfg(feature = "tiktoken")]
+    {
+        Arc::new(TiktokenEstimator::new())
+    }
+    #[cfg(not(feature = "tiktoken"))]
+    {
+        Arc::new(CharHeuristicEstimator::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::request::{ChatMessage, ImageUrl};
+
+    fn request(model: &str, messages: Vec<ChatMessage>) -> GatewayRequest {
+        GatewayRequest::builder()
+            .model(model)
+            .messages(messages)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_heuristic_counts_characters() {
+        let estimator = CharHeuristicEstimator::default();
+        assert_eq!(estimator.count_text("any", ""), 0);
+        assert_eq!(estimator.count_text("any", "abc"), 1);
+        assert_eq!(estimator.count_text("any", "abcdefgh"), 2);
+        // Characters, not bytes
+        assert_eq!(estimator.count_text("any", "héllo wörld!"), 3);
+        assert_eq!(CharHeuristicEstimator::new(2.0).count_text("any", "abcdefgh"), 4);
+    }
+
+    #[test]
+    fn test_prompt_counts_roles_and_framing() {
+        let estimator = CharHeuristicEstimator::default();
+        let request = request(
+            "claude-3-5-sonnet",
+            vec![
+                ChatMessage::system("You are terse."),
+                ChatMessage::user("Hi"),
+            ],
+        );
+        // Reply priming, then per message framing + role + text
+        let expected = REPLY_PRIMING_TOKENS
+            + (MESSAGE_OVERHEAD_TOKENS + 2 + 4)
+            + (MESSAGE_OVERHEAD_TOKENS + 1 + 1);
+        assert_eq!(estimator.estimate_prompt(&request), expected);
+
+        let mut request = request;
+        request.max_tokens = Some(100);
+        assert_eq!(estimator.estimate_request(&request), expected + 100);
+    }
+
+    #[test]
+    fn test_images_count_as_placeholders() {
+        let estimator = CharHeuristicEstimator::default();
+        let image = |detail| ContentPart::ImageUrl {
+            image_url: ImageUrl {
+                url: "data:image/png;base64,iVBORw0KGgoAAAANSUhEUgAAAAEAAAAB".to_string(),
+                detail,
+            },
+        };
+        let mut message = ChatMessage::user("");
+        message.content = MessageContent::Parts(vec![
+            ContentPart::Text {
+                text: "What is this?".to_string(),
+            },
+            image(Some(ImageDetail::Low)),
+            image(None),
+        ]);
+        let request = request("gpt-4o", vec![message]);
+
+        // The URL itself is never counted
+        let expected = REPLY_PRIMING_TOKENS
+            + MESSAGE_OVERHEAD_TOKENS
+            + 1
+            + 4
+            + LOW_DETAIL_IMAGE_TOKENS
+            + HIGH_DETAIL_IMAGE_TOKENS;
+        assert_eq!(estimator.estimate_prompt(&request), expected);
+    }
+
+    #[cfg(feature = "tiktoken")]
+    #[test]
+    fn test_heuristic_is_close_to_tiktoken() {
+        let tiktoken = TiktokenEstimator::new();
+        let heuristic = CharHeuristicEstimator::default();
+        let prompts = [
+            "Summarize the following meeting notes in three bullet points.",
+            "The quick brown fox jumps over the lazy dog. Pack my box with five dozen liquor jugs.",
+            "Write a Rust function that parses an ISO 8601 timestamp and returns the Unix epoch \
+             seconds, handling time zone offsets and fractional seconds correctly.",
+            "You are a helpful assistant. Answer the user's questions about the refund policy \
+             clearly and politely, and ask for an order number when one is needed.",
+        ];
+
+        for model in ["gpt-4", "gpt-4o"] {
+            for prompt in prompts {
+                let request = request(model, vec![ChatMessage::user(prompt)]);
+                let exact = f64::from(tiktoken.estimate_prompt(&request));
+                let rough = f64::from(heuristic.estimate_prompt(&request));
+                let error = (rough - exact).abs() / exact;
+                assert!(error <= 0.3, "{model}: {rough} vs {exact} for {prompt:?}");
+            }
+        }
+    }
+
+    #[cfg(feature = "tiktoken")]
+    #[test]
+    fn test_tiktoken_falls_back_for_other_models() {
+        let tiktoken = TiktokenEstimator::new();
+        let text = "Hello there, how are you doing today?";
+        assert_eq!(tiktoken.count_text("gpt-4", "Hello world"), 2);
+        assert_eq!(tiktoken.count_text("openai/gpt-4o", "Hello world"), 2);
+        assert_eq!(
+            tiktoken.count_text("claude-3-5-sonnet", text),
+            CharHeuristicEstimator::default().count_text("claude-3-5-sonnet", text)
+        );
+    }
+}
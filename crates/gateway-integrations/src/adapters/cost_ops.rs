@@ -10,6 +10,7 @@ use crate::traits::{
 };
 use async_trait::async_trait;
 use dashmap::DashMap;
+use gateway_core::tokenizer::{CharHeuristicEstimator, SharedTokenEstimator};
 use gateway_core::GatewayRequest;
 use std::sync::Arc;
 use tracing::{debug, instrument, warn};
@@ -23,6 +24,8 @@ pub struct CostOpsAdapter {
     config: CostOpsConfig,
     /// Budget status cache
     budget_cache: DashMap<String, CachedBudget>,
+    /// Sizes requests for cost projections
+    estimator: SharedTokenEstimator,
 }
 
 /// Cached budget status
@@ -37,9 +40,17 @@ impl CostOpsAdapter {
         Self {
             config,
             budget_cache: DashMap::new(),
+            estimator: Arc::new(CharHeuristicEstimator::default()),
         }
     }
 
+    /// Size requests with `estimator`, shared with the router so cost
+    /// projections and routing agree.
+    pub fn with_estimator(mut self, estimator: SharedTokenEstimator) -> Self {
+        self.estimator = estimator;
+        self
+    }
+
     /// Check if the adapter is enabled.
     pub fn is_enabled(&self) -> bool {
         self.config.enabled
@@ -55,17 +66,9 @@ impl CostOpsAdapter {
         self.config.max_cost_per_request
     }
 
-    /// Estimate token count from request (simplified).
-    fn estimate_tokens(request: &GatewayRequest) -> u32 {
-        let content: String = request
-            .messages
-            .iter()
-            .filter_map(|msg| msg.text_content())
-            .collect::<Vec<_>>()
-            .join(" ");
-
-        // Rough estimate: ~4 characters per token
-        (content.len() / 4).max(1) as u32
+    /// Estimate the prompt tokens of a request.
+    fn estimate_tokens(&self, request: &GatewayRequest) -> u32 {
+        self.estimator.estimate_prompt(request)
     }
 }
 
@@ -82,8 +85,8 @@ impl CostConsumer for CostOpsAdapter {
 
         debug!(model = %request.model, "Getting cost projection from cost-ops");
 
-        let estimated_input_tokens = Self::estimate_tokens(request);
-        let estimated_output_tokens = request.max_tokens.unwrap_or(1000);
+        let estimated_input_tokens = self.estimate_tokens(request);
+        let estimated_output_tokens = request.token_limit().unwrap_or(1000);
 
         // Phase 2B: Cost projection interface ready.
         // Actual cost-ops client would calculate real costs here.
@@ -265,7 +268,8 @@ mod tests {
             .build()
             .unwrap();
 
-        let tokens = CostOpsAdapter::estimate_tokens(&request);
+        let adapter = CostOpsAdapter::new(CostOpsConfig::default());
+        let tokens = adapter.estimate_tokens(&request);
         assert!(tokens > 0);
     }
 
@@ -3,15 +3,20 @@
 //! This module provides a unified interface for managing and using
 //! all LLM-Dev-Ops ecosystem integrations.
 
+use crate::background::{BackgroundRunner, LaneStats};
 use crate::config::IntegrationsConfig;
-use crate::error::IntegrationResult;
+use crate::dead_letter::{DeadLetterPayload, DeadLetterSink, ReplaySummary};
+use crate::error::{IntegrationError, IntegrationResult};
 use crate::traits::*;
+use crate::webhooks::WebhookEmitter;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tracing::{debug, instrument, warn};
 
 use super::{
-    AutoOptimizerAdapter, ConnectorHubAdapter, CostOpsAdapter, ObservatoryAdapter,
-    PolicyEngineAdapter, RouterAdapter, RuVectorClient, SentinelAdapter, ShieldAdapter,
+    AutoOptimizerAdapter, BatchingPersistence, ConnectorHubAdapter, CostOpsAdapter, DecisionEvent,
+    ObservatoryAdapter, PolicyEngineAdapter, RouterAdapter, RuVectorClient, RuVectorPersistence,
+    SentinelAdapter, ShieldAdapter,
 };
 
 /// Manager for all integration adapters.
@@ -37,6 +42,14 @@ pub struct IntegrationManager {
     policy_engine: Arc<PolicyEngineAdapter>,
     /// RuVector client for persistence (DecisionEvents)
     ruvector: Option<Arc<RuVectorClient>>,
+    /// Batch queue for DecisionEvents, when `ruvector.batch_enabled`
+    decision_batch: Option<BatchingPersistence<Arc<RuVectorClient>>>,
+    /// Background runner for fire-and-forget integration work
+    background: Arc<BackgroundRunner>,
+    /// Lifecycle event webhooks, delivered on the background runner
+    webhooks: Option<Arc<WebhookEmitter>>,
+    /// Sink for events that failed all background retries
+    dead_letters: Option<Arc<DeadLetterSink>>,
     /// Overall enabled state
     enabled: bool,
 }
@@ -57,6 +70,39 @@ impl IntegrationManager {
             None
         };
 
+        let background = Arc::new(BackgroundRunner::new(config.background));
+
+        let dead_letters = config
+            .dead_letter
+            .enabled
+            .then(|| Arc::new(DeadLetterSink::new(&config.dead_letter)));
+
+        let decision_batch = ruvector
+            .as_ref()
+            .filter(|_| config.ruvector.batch_enabled)
+            .map(|client| {
+                let batch = Arc::clone(client).with_batching(config.ruvector.batch_config());
+                match &dead_letters {
+                    Some(sink) => batch.with_dead_letters(Arc::clone(sink)),
+                    None => batch,
+                }
+            });
+
+        let webhooks = if config.webhooks.enabled {
+            match WebhookEmitter::new(config.webhooks, Arc::clone(&background)) {
+                Ok(emitter) => Some(Arc::new(match &dead_letters {
+                    Some(sink) => emitter.with_dead_letters(Arc::clone(sink)),
+                    None => emitter,
+                })),
+                Err(e) => {
+                    warn!(error = %e, "Failed to create webhook emitter, webhooks disabled");
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
         Self {
             connector_hub: Arc::new(ConnectorHubAdapter::new(config.connector_hub)),
             shield: Arc::new(ShieldAdapter::new(config.shield)),
@@ -67,6 +113,10 @@ impl IntegrationManager {
             auto_optimizer: Arc::new(AutoOptimizerAdapter::new(config.auto_optimizer)),
             policy_engine: Arc::new(PolicyEngineAdapter::new(config.policy_engine)),
             ruvector,
+            decision_batch,
+            background,
+            webhooks,
+            dead_letters,
             enabled: config.enabled,
         }
     }
@@ -137,6 +187,30 @@ impl IntegrationManager {
         self.ruvector.is_some()
     }
 
+    /// Get the background task runner.
+    pub fn background(&self) -> &Arc<BackgroundRunner> {
+        &self.background
+    }
+
+    /// Get the webhook emitter.
+    ///
+    /// Returns None if webhooks are not enabled or failed to initialize.
+    pub fn webhooks(&self) -> Option<&Arc<WebhookEmitter>> {
+        self.webhooks.as_ref()
+    }
+
+    /// Get the dead-letter sink.
+    ///
+    /// Returns None if dead-lettering is not enabled.
+    pub fn dead_letters(&self) -> Option<&Arc<DeadLetterSink>> {
+        self.dead_letters.as_ref()
+    }
+
+    /// Get background queue counters per integration.
+    pub fn background_stats(&self) -> HashMap<String, LaneStats> {
+        self.background.all_stats()
+    }
+
     // =========================================================================
     // High-level integration workflows
     // =========================================================================
@@ -307,7 +381,7 @@ impl IntegrationManager {
             }
         }
 
-        // Report usage
+        // Report usage in the background
         if self.cost_ops.is_enabled() {
             let usage_report = UsageReport {
                 request_id: request.id.to_string(),
@@ -320,8 +394,14 @@ impl IntegrationManager {
                 timestamp: chrono::Utc::now(),
             };
 
-            if let Err(e) = self.cost_ops.report_usage(usage_report).await {
-                debug!(error = %e, "Usage reporting failed, continuing");
+            let cost_ops = self.cost_ops.clone();
+            let queued = self.background.try_submit("cost_ops", move || {
+                let cost_ops = cost_ops.clone();
+                let usage_report = usage_report.clone();
+                async move { cost_ops.report_usage(usage_report).await }
+            });
+            if !queued {
+                debug!("Usage report dropped, background queue full");
             }
         }
 
@@ -329,13 +409,122 @@ impl IntegrationManager {
     }
 
     /// Emit telemetry for a request.
+    ///
+    /// The profile is queued on the background runner and emitted off the
+    /// request path. If the queue is full the profile is dropped and counted.
     #[instrument(skip(self, profile))]
     pub async fn emit_telemetry(&self, profile: LatencyProfile) -> IntegrationResult<()> {
         if !self.enabled || !self.observatory.is_enabled() {
             return Ok(());
         }
 
-        self.observatory.emit_latency_profile(profile).await
+        let observatory = self.observatory.clone();
+        let queued = self.background.try_submit("observatory", move || {
+            let observatory = observatory.clone();
+            let profile = profile.clone();
+            async move { observatory.emit_latency_profile(profile).await }
+        });
+        if !queued {
+            debug!("Latency profile dropped, background queue full");
+        }
+
+        Ok(())
+    }
+
+    /// Persist a DecisionEvent to RuVector in the background.
+    ///
+    /// With `ruvector.batch_enabled` the event is queued for the next batch;
+    /// otherwise it is sent on its own on the background runner. Events that
+    /// fail all retries are recorded in the dead-letter sink, if enabled.
+    /// Returns `false` if RuVector is unavailable or the queue is full.
+    pub fn persist_decision_event_background(&self, event: DecisionEvent) -> bool {
+        if let Some(batch) = &self.decision_batch {
+            return match batch.try_enqueue(&event) {
+                Ok(_) => true,
+                Err(e) => {
+                    warn!(event_id = %event.id, error = %e, "DecisionEvent not queued");
+                    false
+                }
+            };
+        }
+        let Some(ruvector) = self.ruvector.clone() else {
+            return false;
+        };
+        let dead_letters = self.dead_letters.clone();
+        let payload = DeadLetterPayload::DecisionEvent {
+            event: event.clone(),
+        };
+
+        self.background.try_submit_or_else(
+            "ruvector",
+            move || {
+                let ruvector = ruvector.clone();
+                let event = event.clone();
+                async move { ruvector.persist_decision_event(&event).await.map(|_| ()) }
+            },
+            move |error, attempts| async move {
+                if let Some(sink) = dead_letters {
+                    sink.record_failure(payload, &error, attempts).await;
+                }
+            },
+        )
+    }
+
+    /// Re-submit every dead-lettered event.
+    ///
+    /// Each event is sent once, directly rather than through the background
+    /// queue. Once all are sent, delivered events are removed from the sink
+    /// and events that failed again are kept with the new error. Only one
+    /// replay of a sink runs at a time, across processes.
+    pub async fn replay_dead_letters(&self) -> IntegrationResult<ReplaySummary> {
+        let Some(sink) = &self.dead_letters else {
+            return Err(IntegrationError::NotEnabled("dead_letter".to_string()));
+        };
+        let _replay = sink.begin_replay().await?;
+
+        let mut summary = ReplaySummary::default();
+        let mut delivered = HashSet::new();
+        let mut failed = Vec::new();
+        for mut letter in sink.entries().await? {
+            match self.replay(&letter.payload).await {
+                Ok(()) => {
+                    summary.replayed += 1;
+                    delivered.insert(letter.id);
+                }
+                Err(e) => {
+                    warn!(
+                        id = %letter.id,
+                        integration = letter.payload.integration(),
+                        error = %e,
+                        "Dead-letter replay failed"
+                    );
+                    summary.failed += 1;
+                    letter.retry_failed(&e);
+                    failed.push(letter);
+                }
+            }
+        }
+        sink.settle(delivered, failed).await?;
+        Ok(summary)
+    }
+
+    async fn replay(&self, payload: &DeadLetterPayload) -> IntegrationResult<()> {
+        match payload {
+            DeadLetterPayload::DecisionEvent { event } => {
+                let ruvector = self
+                    .ruvector
+                    .as_ref()
+                    .ok_or_else(|| IntegrationError::NotEnabled("ruvector".to_string()))?;
+                ruvector.persist_decision_event(event).await.map(|_| ())
+            }
+            DeadLetterPayload::Webhook { url, event } => {
+                let webhooks = self
+                    .webhooks
+                    .as_ref()
+                    .ok_or_else(|| IntegrationError::NotEnabled("webhooks".to_string()))?;
+                webhooks.redeliver(url, event).await
+            }
+        }
     }
 
     /// Flush all pending telemetry.
@@ -347,6 +536,24 @@ impl IntegrationManager {
         self.observatory.flush().await
     }
 
+    /// Persist every queued DecisionEvent now.
+    ///
+    /// A no-op unless `ruvector.batch_enabled`.
+    pub async fn flush_decision_events(&self) {
+        if let Some(batch) = &self.decision_batch {
+            batch.flush().await;
+        }
+    }
+
+    /// Flush queued DecisionEvents and telemetry before exit.
+    ///
+    /// Call on graceful shutdown; events still queued when the process exits
+    /// are lost.
+    pub async fn shutdown(&self) -> IntegrationResult<()> {
+        self.flush_decision_events().await;
+        self.flush_telemetry().await
+    }
+
     /// Get integration status for health checks.
     pub fn status(&self) -> IntegrationStatus {
         IntegrationStatus {
@@ -377,6 +584,10 @@ impl std::fmt::Debug for IntegrationManager {
             .field("auto_optimizer", &self.auto_optimizer)
             .field("policy_engine", &self.policy_engine)
             .field("ruvector", &self.ruvector)
+            .field("decision_batch", &self.decision_batch)
+            .field("background", &self.background)
+            .field("webhooks", &self.webhooks)
+            .field("dead_letters", &self.dead_letters)
             .finish()
     }
 }
@@ -534,4 +745,177 @@ mod tests {
         assert!(!result.blocked);
         assert!(!result.has_integrations());
     }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_emit_telemetry_runs_in_background() {
+        let manager = IntegrationManager::new(IntegrationsConfig {
+            enabled: true,
+            observatory: crate::config::ObservatoryConfig {
+                enabled: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+
+        let profile = super::super::observatory::create_latency_profile(
+            "req-1", "openai", "gpt-4", 120, None, 1, 2, 110, 7,
+        );
+        manager.emit_telemetry(profile).await.unwrap();
+        assert_eq!(
+            manager.background().stats("observatory").unwrap().submitted,
+            1
+        );
+
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        let stats = manager.background_stats()["observatory"];
+        assert_eq!(stats.completed, 1);
+        assert_eq!(stats.dropped, 0);
+    }
+
+    #[tokio::test]
+    async fn test_failed_decision_event_is_dead_lettered_and_replayed() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/events"))
+            .respond_with(ResponseTemplate::new(503).set_body_string("unavailable"))
+            .mount(&server)
+            .await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let manager = IntegrationManager::new(IntegrationsConfig {
+            enabled: true,
+            ruvector: crate::config::RuVectorConfig {
+                enabled: true,
+                endpoint: Some(server.uri()),
+                retry_count: 0,
+                batch_enabled: false,
+                ..Default::default()
+            },
+            background: crate::config::BackgroundConfig {
+                tasks_per_second: 0,
+                max_retries: 2,
+                retry_backoff: std::time::Duration::from_millis(10),
+                ..Default::default()
+            },
+            dead_letter: crate::config::DeadLetterConfig {
+                enabled: true,
+                path: dir.path().join("dead_letters.jsonl"),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+
+        let event = DecisionEvent::builder()
+            .execution_ref("req-1")
+            .agent_id("router")
+            .decision_type("routing")
+            .build()
+            .unwrap();
+        assert!(manager.persist_decision_event_background(event.clone()));
+
+        let sink = manager.dead_letters().unwrap();
+        let mut letters = Vec::new();
+        for _ in 0..200 {
+            letters = sink.entries().await.unwrap();
+            if !letters.is_empty() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert_eq!(letters.len(), 1);
+        assert_eq!(letters[0].attempts, 3);
+        assert!(letters[0].error.contains("503"));
+        let DeadLetterPayload::DecisionEvent { event: recorded } = &letters[0].payload else {
+            panic!("expected a decision event");
+        };
+        assert_eq!(recorded.id, event.id);
+
+        // Still down: the event is recorded back with another attempt.
+        let summary = manager.replay_dead_letters().await.unwrap();
+        assert_eq!(summary, ReplaySummary { replayed: 0, failed: 1 });
+        assert_eq!(sink.entries().await.unwrap()[0].attempts, 4);
+
+        server.reset().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/events"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "id": event.id,
+                "version": 1,
+                "server_timestamp": "2026-01-01T00:00:00Z"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let summary = manager.replay_dead_letters().await.unwrap();
+        assert_eq!(summary, ReplaySummary { replayed: 1, failed: 0 });
+        assert!(sink.entries().await.unwrap().is_empty());
+
+        let requests = server.received_requests().await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(body["id"], event.id.as_str());
+    }
+
+    #[tokio::test]
+    async fn test_batched_decision_events_are_flushed_on_shutdown() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/events/batch"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!([])))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let manager = IntegrationManager::new(IntegrationsConfig {
+            enabled: true,
+            ruvector: crate::config::RuVectorConfig {
+                enabled: true,
+                endpoint: Some(server.uri()),
+                batch_size: 10,
+                flush_interval: std::time::Duration::from_secs(3600),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+
+        let events: Vec<_> = (0..3)
+            .map(|i| {
+                DecisionEvent::builder()
+                    .execution_ref(format!("req-{i}"))
+                    .agent_id("router")
+                    .decision_type("routing")
+                    .build()
+                    .unwrap()
+            })
+            .collect();
+        for event in &events {
+            assert!(manager.persist_decision_event_background(event.clone()));
+        }
+        assert!(server.received_requests().await.unwrap().is_empty());
+
+        manager.shutdown().await.unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 1);
+        let body: Vec<DecisionEvent> = serde_json::from_slice(&requests[0].body).unwrap();
+        let ids: Vec<_> = body.iter().map(|e| e.id.as_str()).collect();
+        let expected: Vec<_> = events.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, expected);
+    }
+
+    #[tokio::test]
+    async fn test_replay_requires_dead_letter_sink() {
+        let manager = IntegrationManager::disabled();
+        assert!(manager.dead_letters().is_none());
+        assert!(matches!(
+            manager.replay_dead_letters().await,
+            Err(IntegrationError::NotEnabled(_))
+        ));
+    }
 }
@@ -10,6 +10,7 @@ pub mod observatory;
 pub mod policy_engine;
 pub mod router;
 pub mod ruvector;
+pub mod ruvector_batch;
 pub mod sentinel;
 pub mod shield;
 
@@ -25,5 +26,6 @@ pub use observatory::ObservatoryAdapter;
 pub use policy_engine::PolicyEngineAdapter;
 pub use router::RouterAdapter;
 pub use ruvector::{DecisionEvent, EventQuery, RuVectorClient, RuVectorPersistence};
+pub use ruvector_batch::BatchingPersistence;
 pub use sentinel::SentinelAdapter;
 pub use shield::ShieldAdapter;
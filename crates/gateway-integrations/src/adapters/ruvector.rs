@@ -5,12 +5,14 @@
 //! NEVER connects directly to the database - all persistence happens via
 //! ruvector-service client calls.
 
-use crate::config::RuVectorConfig;
+use super::ruvector_batch::BatchingPersistence;
+use crate::config::{BatchConfig, RuVectorConfig};
 use crate::error::{IntegrationError, IntegrationResult};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
 use tracing::{debug, error, info, instrument, warn};
 
@@ -271,6 +273,16 @@ pub trait RuVectorPersistence: Send + Sync {
 
     /// Batch persist multiple DecisionEvents.
     async fn persist_batch(&self, events: &[DecisionEvent]) -> IntegrationResult<Vec<String>>;
+
+    /// Queue DecisionEvents and persist them in batches.
+    ///
+    /// See [`BatchingPersistence`].
+    fn with_batching(self, config: BatchConfig) -> BatchingPersistence<Self>
+    where
+        Self: Sized + 'static,
+    {
+        BatchingPersistence::new(self, config)
+    }
 }
 
 /// Client adapter for ruvector-service persistence.
@@ -702,6 +714,32 @@ impl RuVectorPersistence for RuVectorClient {
     }
 }
 
+#[async_trait]
+impl<P: RuVectorPersistence + ?Sized> RuVectorPersistence for Arc<P> {
+    async fn persist_decision_event(&self, event: &DecisionEvent) -> IntegrationResult<String> {
+        (**self).persist_decision_event(event).await
+    }
+
+    async fn get_events_by_execution(
+        &self,
+        execution_ref: &str,
+    ) -> IntegrationResult<Vec<DecisionEvent>> {
+        (**self).get_events_by_execution(execution_ref).await
+    }
+
+    async fn search_events(&self, query: &EventQuery) -> IntegrationResult<EventsResponse> {
+        (**self).search_events(query).await
+    }
+
+    async fn delete_event(&self, event_id: &str) -> IntegrationResult<bool> {
+        (**self).delete_event(event_id).await
+    }
+
+    async fn persist_batch(&self, events: &[DecisionEvent]) -> IntegrationResult<Vec<String>> {
+        (**self).persist_batch(events).await
+    }
+}
+
 impl std::fmt::Debug for RuVectorClient {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("RuVectorClient")
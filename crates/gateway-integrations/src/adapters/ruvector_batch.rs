@@ -0,0 +1,450 @@
+//! Batched DecisionEvent persistence.
+//!
+//! Persisting every decision with its own request to ruvector-service is
+//! expensive under load. [`BatchingPersistence`] wraps any
+//! [`RuVectorPersistence`] and queues events in a bounded channel instead. A
+//! worker persists them with `persist_batch` once `max_batch_size` events
+//! are waiting or `flush_interval` has passed, whichever comes first. A batch
+//! that fails is retried with backoff; once retries are exhausted it is
+//! handed to the dead-letter handler, or logged as lost if there is none.
+//!
+//! Each `persist_decision_event` call still queues exactly one event, so the
+//! emit site keeps its one DecisionEvent per invocation: events are never
+//! merged, and a retried batch resends the same events with the same IDs.
+//! Call [`BatchingPersistence::flush`] on shutdown so queued events are
+//! persisted before the process exits.
+
+use super::ruvector::{DecisionEvent, EventQuery, EventsResponse, RuVectorPersistence};
+use crate::config::BatchConfig;
+use crate::dead_letter::{DeadLetterPayload, DeadLetterSink};
+use crate::error::{IntegrationError, IntegrationResult};
+use async_trait::async_trait;
+use futures::future::BoxFuture;
+use std::future::Future;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use tokio::sync::mpsc::{self, error::TrySendError};
+use tokio::sync::oneshot;
+use tokio::time::MissedTickBehavior;
+use tracing::{debug, error, warn};
+
+/// Called with a batch, the last error and the attempt count once the
+/// batch's retries are exhausted.
+type DeadLetterHandler =
+    Arc<dyn Fn(Vec<DecisionEvent>, IntegrationError, u32) -> BoxFuture<'static, ()> + Send + Sync>;
+
+/// Work sent to the batch worker, in order.
+enum Command {
+    Event(Box<DecisionEvent>),
+    /// Persist everything queued so far, then acknowledge
+    Flush(oneshot::Sender<()>),
+}
+
+/// Persistence that queues DecisionEvents and writes them in batches.
+///
+/// Created with [`RuVectorPersistence::with_batching`]. Reads and deletes
+/// flush the queue first, so they see every event persisted before them.
+pub struct BatchingPersistence<P> {
+    inner: Arc<P>,
+    config: BatchConfig,
+    on_failed: Option<DeadLetterHandler>,
+    /// Queue to the worker, spawned on the first event
+    sender: OnceLock<mpsc::Sender<Command>>,
+}
+
+impl<P: RuVectorPersistence + 'static> BatchingPersistence<P> {
+    /// Batch events persisted through `inner`.
+    ///
+    /// The worker is spawned on the first event, so this can be called
+    /// outside a Tokio runtime.
+    pub fn new(inner: P, config: BatchConfig) -> Self {
+        Self {
+            inner: Arc::new(inner),
+            config,
+            on_failed: None,
+            sender: OnceLock::new(),
+        }
+    }
+
+    /// Call `handler` with batches that fail all retries.
+    ///
+    /// `handler` receives the events, the last error and the number of
+    /// attempts made.
+    #[must_use]
+    pub fn with_dead_letter_handler<F, Fut>(mut self, handler: F) -> Self
+    where
+        F: Fn(Vec<DecisionEvent>, IntegrationError, u32) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.on_failed = Some(Arc::new(move |events, error, attempts| {
+            Box::pin(handler(events, error, attempts))
+        }));
+        self
+    }
+
+    /// Record batches that fail all retries in a dead-letter sink.
+    #[must_use]
+    pub fn with_dead_letters(self, sink: Arc<DeadLetterSink>) -> Self {
+        self.with_dead_letter_handler(move |events, error, attempts| {
+            let sink = sink.clone();
+            async move {
+                for event in events {
+                    sink.record_failure(DeadLetterPayload::DecisionEvent { event }, &error, attempts)
+                        .await;
+                }
+            }
+        })
+    }
+
+    /// The wrapped persistence.
+    pub fn inner(&self) -> &P {
+        &self.inner
+    }
+
+    /// Get the batching configuration.
+    pub fn config(&self) -> &BatchConfig {
+        &self.config
+    }
+
+    /// Persist every queued event now.
+    ///
+    /// Returns once the events queued before the call have been persisted
+    /// or dead-lettered. Call it on graceful shutdown.
+    pub async fn flush(&self) {
+        let Some(sender) = self.sender.get() else {
+            return;
+        };
+        let (ack, done) = oneshot::channel();
+        if sender.send(Command::Flush(ack)).await.is_ok() {
+            let _ = done.await;
+        }
+    }
+
+    /// Queue an event without waiting.
+    ///
+    /// Same as `persist_decision_event`, for callers outside async code.
+    pub fn try_enqueue(&self, event: &DecisionEvent) -> IntegrationResult<String> {
+        match self.sender().try_send(Command::Event(Box::new(event.clone()))) {
+            Ok(()) => Ok(event.id.clone()),
+            Err(TrySendError::Full(_)) => Err(IntegrationError::ruvector_retryable(
+                "DecisionEvent batch queue is full",
+            )),
+            Err(TrySendError::Closed(_)) => Err(IntegrationError::ruvector(
+                "DecisionEvent batch worker has stopped",
+            )),
+        }
+    }
+
+    fn sender(&self) -> &mpsc::Sender<Command> {
+        self.sender.get_or_init(|| {
+            let (sender, receiver) = mpsc::channel(self.config.queue_capacity.max(1));
+            let worker = BatchWorker {
+                inner: self.inner.clone(),
+                max_batch_size: self.config.max_batch_size.max(1),
+                flush_interval: self.config.flush_interval.max(Duration::from_millis(1)),
+                max_retries: self.config.max_retries,
+                retry_backoff: self.config.retry_backoff,
+                on_failed: self.on_failed.clone(),
+            };
+            tokio::spawn(worker.run(receiver));
+            sender
+        })
+    }
+}
+
+#[async_trait]
+impl<P: RuVectorPersistence + 'static> RuVectorPersistence for BatchingPersistence<P> {
+    /// Queue the event, returning its ID without waiting for it to persist.
+    ///
+    /// Fails with a retryable error if the queue is full.
+    async fn persist_decision_event(&self, event: &DecisionEvent) -> IntegrationResult<String> {
+        self.try_enqueue(event)
+    }
+
+    async fn get_events_by_execution(
+        &self,
+        execution_ref: &str,
+    ) -> IntegrationResult<Vec<DecisionEvent>> {
+        self.flush().await;
+        self.inner.get_events_by_execution(execution_ref).await
+    }
+
+    async fn search_events(&self, query: &EventQuery) -> IntegrationResult<EventsResponse> {
+        self.flush().await;
+        self.inner.search_events(query).await
+    }
+
+    async fn delete_event(&self, event_id: &str) -> IntegrationResult<bool> {
+        self.flush().await;
+        self.inner.delete_event(event_id).await
+    }
+
+    async fn persist_batch(&self, events: &[DecisionEvent]) -> IntegrationResult<Vec<String>> {
+        self.inner.persist_batch(events).await
+    }
+}
+
+impl<P> std::fmt::Debug for BatchingPersistence<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BatchingPersistence")
+            .field("config", &self.config)
+            .field("dead_letters", &self.on_failed.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Worker that drains the queue into batches.
+struct BatchWorker<P> {
+    inner: Arc<P>,
+    max_batch_size: usize,
+    flush_interval: Duration,
+    max_retries: u32,
+    retry_backoff: Duration,
+    on_failed: Option<DeadLetterHandler>,
+}
+
+impl<P: RuVectorPersistence> BatchWorker<P> {
+    async fn run(self, mut receiver: mpsc::Receiver<Command>) {
+        let mut batch = Vec::with_capacity(self.max_batch_size);
+        let mut ticker = tokio::time::interval(self.flush_interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        // The first tick completes immediately
+        ticker.tick().await;
+
+        loop {
+            tokio::select! {
+                command = receiver.recv() => match command {
+                    Some(Command::Event(event)) => {
+                        batch.push(*event);
+                        if batch.len() >= self.max_batch_size {
+                            self.persist(std::mem::take(&mut batch)).await;
+                            ticker.reset();
+                        }
+                    }
+                    Some(Command::Flush(ack)) => {
+                        self.persist(std::mem::take(&mut batch)).await;
+                        let _ = ack.send(());
+                    }
+                    None => break,
+                },
+                _ = ticker.tick() => self.persist(std::mem::take(&mut batch)).await,
+            }
+        }
+
+        // Every handle was dropped: persist what is left
+        self.persist(batch).await;
+        debug!("DecisionEvent batch worker stopped");
+    }
+
+    /// Persist a batch with retries, dead-lettering it if they run out.
+    async fn persist(&self, batch: Vec<DecisionEvent>) {
+        if batch.is_empty() {
+            return;
+        }
+
+        let mut backoff = self.retry_backoff;
+        let mut attempt = 0;
+        loop {
+            match self.inner.persist_batch(&batch).await {
+                Ok(_) => {
+                    debug!(count = batch.len(), "Persisted DecisionEvent batch");
+                    return;
+                }
+                Err(e) if attempt < self.max_retries => {
+                    attempt += 1;
+                    debug!(
+                        count = batch.len(),
+                        attempt,
+                        error = %e,
+                        "DecisionEvent batch failed, retrying"
+                    );
+                    tokio::time::sleep(backoff).await;
+                    backoff = backoff.saturating_mul(2);
+                }
+                Err(e) => {
+                    warn!(
+                        count = batch.len(),
+                        error = %e,
+                        "DecisionEvent batch failed after retries"
+                    );
+                    if let Some(on_failed) = &self.on_failed {
+                        on_failed(batch, e, attempt + 1).await;
+                    } else {
+                        error!(count = batch.len(), "No dead-letter handler, DecisionEvents lost");
+                    }
+                    return;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Mutex;
+
+    /// Records persisted batches, failing the first `failures` attempts.
+    #[derive(Default)]
+    struct Recorder {
+        batches: Mutex<Vec<Vec<String>>>,
+        failures: AtomicU32,
+    }
+
+    impl Recorder {
+        fn failing(failures: u32) -> Self {
+            Self {
+                failures: AtomicU32::new(failures),
+                ..Default::default()
+            }
+        }
+
+        fn batch_sizes(&self) -> Vec<usize> {
+            self.batches.lock().unwrap().iter().map(Vec::len).collect()
+        }
+
+        fn persisted(&self) -> Vec<String> {
+            self.batches.lock().unwrap().concat()
+        }
+    }
+
+    #[async_trait]
+    impl RuVectorPersistence for Recorder {
+        async fn persist_decision_event(&self, event: &DecisionEvent) -> IntegrationResult<String> {
+            self.persist_batch(std::slice::from_ref(event))
+                .await
+                .map(|mut ids| ids.remove(0))
+        }
+
+        async fn get_events_by_execution(&self, _: &str) -> IntegrationResult<Vec<DecisionEvent>> {
+            Ok(Vec::new())
+        }
+
+        async fn search_events(&self, _: &EventQuery) -> IntegrationResult<EventsResponse> {
+            Ok(EventsResponse {
+                events: Vec::new(),
+                total_count: self.persisted().len() as u64,
+                has_more: false,
+            })
+        }
+
+        async fn delete_event(&self, _: &str) -> IntegrationResult<bool> {
+            Ok(false)
+        }
+
+        async fn persist_batch(&self, events: &[DecisionEvent]) -> IntegrationResult<Vec<String>> {
+            let failing = self
+                .failures
+                .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1))
+                .is_ok();
+            if failing {
+                return Err(IntegrationError::ruvector_retryable("unavailable"));
+            }
+            let ids: Vec<String> = events.iter().map(|e| e.id.clone()).collect();
+            self.batches.lock().unwrap().push(ids.clone());
+            Ok(ids)
+        }
+    }
+
+    fn config(max_batch_size: usize, flush_interval: Duration) -> BatchConfig {
+        BatchConfig {
+            max_batch_size,
+            flush_interval,
+            retry_backoff: Duration::from_millis(10),
+            ..Default::default()
+        }
+    }
+
+    fn event(n: usize) -> DecisionEvent {
+        DecisionEvent::builder()
+            .execution_ref(format!("exec-{n}"))
+            .agent_id("router-agent")
+            .decision_type("routing")
+            .success(true)
+            .build()
+            .unwrap()
+    }
+
+    async fn emit(persistence: &impl RuVectorPersistence, count: usize) -> Vec<String> {
+        let mut ids = Vec::new();
+        for n in 0..count {
+            ids.push(persistence.persist_decision_event(&event(n)).await.unwrap());
+        }
+        ids
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_full_batches_flush_and_every_event_persists_once() {
+        let persistence = Recorder::default().with_batching(config(3, Duration::from_secs(3600)));
+        let ids = emit(&persistence, 7).await;
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert_eq!(persistence.inner().batch_sizes(), vec![3, 3]);
+
+        persistence.flush().await;
+        assert_eq!(persistence.inner().batch_sizes(), vec![3, 3, 1]);
+        assert_eq!(persistence.inner().persisted(), ids);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_partial_batch_flushes_after_interval() {
+        let persistence = Recorder::default().with_batching(config(100, Duration::from_secs(1)));
+        emit(&persistence, 2).await;
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        assert!(persistence.inner().batch_sizes().is_empty());
+
+        tokio::time::sleep(Duration::from_millis(600)).await;
+        assert_eq!(persistence.inner().batch_sizes(), vec![2]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_failed_batch_is_retried() {
+        let persistence = Recorder::failing(2).with_batching(config(2, Duration::from_secs(3600)));
+        let ids = emit(&persistence, 2).await;
+        persistence.flush().await;
+        assert_eq!(persistence.inner().persisted(), ids);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_exhausted_batch_is_dead_lettered() {
+        let dead = Arc::new(Mutex::new(Vec::new()));
+        let recorded = dead.clone();
+        let persistence = Recorder::failing(u32::MAX)
+            .with_batching(config(100, Duration::from_secs(3600)))
+            .with_dead_letter_handler(move |events, _error, attempts| {
+                let recorded = recorded.clone();
+                async move {
+                    recorded
+                        .lock()
+                        .unwrap()
+                        .extend(events.into_iter().map(|e| (e.id, attempts)));
+                }
+            });
+        let ids = emit(&persistence, 3).await;
+        persistence.flush().await;
+
+        assert!(persistence.inner().persisted().is_empty());
+        let dead = dead.lock().unwrap().clone();
+        assert_eq!(dead, ids.into_iter().map(|id| (id, 4)).collect::<Vec<_>>());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_reads_see_queued_events() {
+        let persistence = Recorder::default().with_batching(config(100, Duration::from_secs(3600)));
+        emit(&persistence, 4).await;
+        let found = persistence.search_events(&EventQuery::default()).await.unwrap();
+        assert_eq!(found.total_count, 4);
+    }
+
+    #[tokio::test]
+    async fn test_full_queue_rejects_events() {
+        let persistence = Recorder::default().with_batching(BatchConfig {
+            queue_capacity: 1,
+            ..config(100, Duration::from_secs(3600))
+        });
+        // The worker has not run yet, so the second event finds the queue full
+        persistence.persist_decision_event(&event(0)).await.unwrap();
+        let err = persistence.persist_decision_event(&event(1)).await.unwrap_err();
+        assert!(matches!(err, IntegrationError::RuVector { retryable: true, .. }));
+    }
+}
@@ -0,0 +1,473 @@
+//! Bounded background task runner for integrations.
+//!
+//! Integration side effects such as telemetry emission, usage reporting and
+//! DecisionEvent persistence should never block the request path. The
+//! [`BackgroundRunner`] gives each integration its own bounded queue and
+//! worker. Workers pace tasks to the configured rate so bursts are smoothed,
+//! and retry failed tasks with exponential backoff. When a queue is full, new
+//! tasks are dropped and counted instead of waiting. Tasks submitted with
+//! [`BackgroundRunner::try_submit_or_else`] get a failure handler, called
+//! once retries are exhausted, so the work can be dead-lettered.
+
+use crate::config::BackgroundConfig;
+use crate::error::{IntegrationError, IntegrationResult};
+use dashmap::DashMap;
+use futures::future::BoxFuture;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tracing::{debug, warn};
+
+/// A retryable unit of background work.
+type Task = Box<dyn Fn() -> BoxFuture<'static, IntegrationResult<()>> + Send + Sync>;
+
+/// Called with the last error and attempt count once a task's retries are
+/// exhausted.
+type FailureHandler = Box<dyn FnOnce(IntegrationError, u32) -> BoxFuture<'static, ()> + Send>;
+
+/// A queued task and its optional failure handler.
+struct Job {
+    task: Task,
+    on_failed: Option<FailureHandler>,
+}
+
+/// Shared runner that executes integration work off the request path.
+pub struct BackgroundRunner {
+    config: BackgroundConfig,
+    lanes: DashMap<String, Lane>,
+}
+
+/// Queue and counters for a single integration.
+struct Lane {
+    sender: mpsc::Sender<Job>,
+    counters: Arc<LaneCounters>,
+}
+
+#[derive(Default)]
+struct LaneCounters {
+    queue_depth: AtomicUsize,
+    submitted: AtomicU64,
+    completed: AtomicU64,
+    failed: AtomicU64,
+    retried: AtomicU64,
+    dropped: AtomicU64,
+}
+
+impl LaneCounters {
+    fn snapshot(&self) -> LaneStats {
+        LaneStats {
+            queue_depth: self.queue_depth.load(Ordering::Relaxed),
+            submitted: self.submitted.load(Ordering::Relaxed),
+            completed: self.completed.load(Ordering::Relaxed),
+            failed: self.failed.load(Ordering::Relaxed),
+            retried: self.retried.load(Ordering::Relaxed),
+            dropped: self.dropped.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Point-in-time counters for one integration's queue.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct LaneStats {
+    /// Tasks waiting to run
+    pub queue_depth: usize,
+    /// Tasks accepted into the queue
+    pub submitted: u64,
+    /// Tasks that finished successfully
+    pub completed: u64,
+    /// Tasks that failed after all retries
+    pub failed: u64,
+    /// Retry attempts made
+    pub retried: u64,
+    /// Tasks rejected because the queue was full
+    pub dropped: u64,
+}
+
+impl BackgroundRunner {
+    /// Create a new runner.
+    ///
+    /// Workers are spawned lazily on the first submission for each
+    /// integration, so this can be called outside a Tokio runtime.
+    pub fn new(config: BackgroundConfig) -> Self {
+        Self {
+            config,
+            lanes: DashMap::new(),
+        }
+    }
+
+    /// Get the runner configuration.
+    pub fn config(&self) -> &BackgroundConfig {
+        &self.config
+    }
+
+    /// Queue a task for an integration without waiting.
+    ///
+    /// The task is called again for each retry, so it must be repeatable.
+    /// Returns `false` if the queue is full and the task was dropped.
+    /// Must be called from within a Tokio runtime.
+    pub fn try_submit<F, Fut>(&self, integration: &str, task: F) -> bool
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = IntegrationResult<()>> + Send + 'static,
+    {
+        self.enqueue(
+            integration,
+            Job {
+                task: Box::new(move || Box::pin(task())),
+                on_failed: None,
+            },
+        )
+    }
+
+    /// Queue a task, calling `on_failed` if it still fails after all retries.
+    ///
+    /// `on_failed` receives the last error and the number of attempts made.
+    /// It is not called if the task is dropped because the queue is full.
+    pub fn try_submit_or_else<F, Fut, G, GFut>(&self, integration: &str, task: F, on_failed: G) -> bool
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = IntegrationResult<()>> + Send + 'static,
+        G: FnOnce(IntegrationError, u32) -> GFut + Send + 'static,
+        GFut: Future<Output = ()> + Send + 'static,
+    {
+        self.enqueue(
+            integration,
+            Job {
+                task: Box::new(move || Box::pin(task())),
+                on_failed: Some(Box::new(move |error, attempts| {
+                    Box::pin(on_failed(error, attempts))
+                })),
+            },
+        )
+    }
+
+    fn enqueue(&self, integration: &str, job: Job) -> bool {
+        let (sender, counters) = self.lane(integration);
+
+        // Count before sending so the worker never sees a negative depth.
+        counters.queue_depth.fetch_add(1, Ordering::Relaxed);
+        if sender.try_send(job).is_ok() {
+            counters.submitted.fetch_add(1, Ordering::Relaxed);
+            true
+        } else {
+            counters.queue_depth.fetch_sub(1, Ordering::Relaxed);
+            counters.dropped.fetch_add(1, Ordering::Relaxed);
+            debug!(integration, "Background queue full, dropping task");
+            false
+        }
+    }
+
+    /// Get counters for one integration.
+    ///
+    /// Returns `None` if nothing has been submitted for it yet.
+    pub fn stats(&self, integration: &str) -> Option<LaneStats> {
+        self.lanes
+            .get(integration)
+            .map(|lane| lane.counters.snapshot())
+    }
+
+    /// Get counters for all integrations.
+    pub fn all_stats(&self) -> HashMap<String, LaneStats> {
+        self.lanes
+            .iter()
+            .map(|lane| (lane.key().clone(), lane.counters.snapshot()))
+            .collect()
+    }
+
+    /// Total tasks waiting across all integrations.
+    pub fn queue_depth(&self) -> usize {
+        self.lanes
+            .iter()
+            .map(|lane| lane.counters.queue_depth.load(Ordering::Relaxed))
+            .sum()
+    }
+
+    /// Total tasks dropped across all integrations.
+    pub fn dropped(&self) -> u64 {
+        self.lanes
+            .iter()
+            .map(|lane| lane.counters.dropped.load(Ordering::Relaxed))
+            .sum()
+    }
+
+    fn lane(&self, integration: &str) -> (mpsc::Sender<Job>, Arc<LaneCounters>) {
+        if let Some(lane) = self.lanes.get(integration) {
+            return (lane.sender.clone(), lane.counters.clone());
+        }
+
+        let lane = self
+            .lanes
+            .entry(integration.to_string())
+            .or_insert_with(|| self.spawn_lane(integration));
+        (lane.sender.clone(), lane.counters.clone())
+    }
+
+    fn spawn_lane(&self, integration: &str) -> Lane {
+        let (sender, receiver) = mpsc::channel(self.config.queue_capacity.max(1));
+        let counters = Arc::new(LaneCounters::default());
+        let worker = Worker {
+            integration: integration.to_string(),
+            interval: rate_interval(self.config.rate_limit_for(integration)),
+            max_retries: self.config.max_retries,
+            retry_backoff: self.config.retry_backoff,
+            counters: counters.clone(),
+        };
+        tokio::spawn(worker.run(receiver));
+        Lane { sender, counters }
+    }
+}
+
+impl std::fmt::Debug for BackgroundRunner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BackgroundRunner")
+            .field("config", &self.config)
+            .field("lanes", &self.lanes.len())
+            .finish()
+    }
+}
+
+impl Default for BackgroundRunner {
+    fn default() -> Self {
+        Self::new(BackgroundConfig::default())
+    }
+}
+
+fn rate_interval(tasks_per_second: u32) -> Option<Duration> {
+    (tasks_per_second > 0).then(|| Duration::from_secs(1) / tasks_per_second)
+}
+
+/// Worker that drains one integration's queue.
+struct Worker {
+    integration: String,
+    interval: Option<Duration>,
+    max_retries: u32,
+    retry_backoff: Duration,
+    counters: Arc<LaneCounters>,
+}
+
+impl Worker {
+    async fn run(self, mut receiver: mpsc::Receiver<Job>) {
+        let mut next_slot = Instant::now();
+
+        while let Some(job) = receiver.recv().await {
+            self.counters.queue_depth.fetch_sub(1, Ordering::Relaxed);
+
+            if let Some(interval) = self.interval {
+                tokio::time::sleep_until(next_slot).await;
+                next_slot = next_slot.max(Instant::now()) + interval;
+            }
+
+            if let Err((error, attempts)) = self.execute(&job.task).await {
+                if let Some(on_failed) = job.on_failed {
+                    on_failed(error, attempts).await;
+                }
+            }
+        }
+
+        debug!(integration = %self.integration, "Background worker stopped");
+    }
+
+    /// Run a task with retries, returning the last error and the attempt
+    /// count if it never succeeded.
+    async fn execute(&self, task: &Task) -> Result<(), (IntegrationError, u32)> {
+        let mut backoff = self.retry_backoff;
+        let mut attempt = 0;
+
+        loop {
+            match task().await {
+                Ok(()) => {
+                    self.counters.completed.fetch_add(1, Ordering::Relaxed);
+                    return Ok(());
+                }
+                Err(e) if attempt < self.max_retries => {
+                    attempt += 1;
+                    self.counters.retried.fetch_add(1, Ordering::Relaxed);
+                    debug!(
+                        integration = %self.integration,
+                        attempt,
+                        error = %e,
+                        "Background task failed, retrying"
+                    );
+                    tokio::time::sleep(backoff).await;
+                    backoff = backoff.saturating_mul(2);
+                }
+                Err(e) => {
+                    self.counters.failed.fetch_add(1, Ordering::Relaxed);
+                    warn!(
+                        integration = %self.integration,
+                        error = %e,
+                        "Background task failed after retries"
+                    );
+                    return Err((e, attempt + 1));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::IntegrationError;
+    use std::sync::Mutex;
+
+    fn config(tasks_per_second: u32, queue_capacity: usize) -> BackgroundConfig {
+        BackgroundConfig {
+            queue_capacity,
+            tasks_per_second,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_tasks_run_in_background_within_rate_limit() {
+        let runner = BackgroundRunner::new(config(10, 100));
+        let runs = Arc::new(Mutex::new(Vec::new()));
+        let start = Instant::now();
+
+        for _ in 0..5 {
+            let runs = runs.clone();
+            let accepted = runner.try_submit("observatory", move || {
+                let runs = runs.clone();
+                async move {
+                    runs.lock().unwrap().push(Instant::now());
+                    Ok(())
+                }
+            });
+            assert!(accepted);
+        }
+
+        // Submission returns immediately without running anything inline.
+        assert!(runs.lock().unwrap().is_empty());
+
+        tokio::time::sleep(Duration::from_secs(1)).await;
+
+        let runs = runs.lock().unwrap();
+        assert_eq!(runs.len(), 5);
+        for pair in runs.windows(2) {
+            assert!(pair[1] - pair[0] >= Duration::from_millis(100));
+        }
+        assert!(runs[4] - start >= Duration::from_millis(400));
+
+        let stats = runner.stats("observatory").unwrap();
+        assert_eq!(stats.submitted, 5);
+        assert_eq!(stats.completed, 5);
+        assert_eq!(stats.queue_depth, 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_overflow_drops_are_counted() {
+        let runner = BackgroundRunner::new(config(1, 2));
+
+        let accepted = (0..10)
+            .filter(|_| runner.try_submit("cost_ops", || async { Ok(()) }))
+            .count();
+
+        // The worker may pull one task before the rest arrive, so at most
+        // capacity + 1 tasks are accepted.
+        assert!(accepted <= 3);
+        let stats = runner.stats("cost_ops").unwrap();
+        assert_eq!(stats.dropped, (10 - accepted) as u64);
+        assert_eq!(runner.dropped(), stats.dropped);
+        assert!(runner.queue_depth() <= 2);
+
+        tokio::time::sleep(Duration::from_secs(5)).await;
+        let stats = runner.stats("cost_ops").unwrap();
+        assert_eq!(stats.completed, accepted as u64);
+        assert_eq!(stats.queue_depth, 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_failed_tasks_are_retried() {
+        let runner = BackgroundRunner::new(BackgroundConfig {
+            max_retries: 2,
+            retry_backoff: Duration::from_millis(10),
+            ..Default::default()
+        });
+        let attempts = Arc::new(AtomicUsize::new(0));
+
+        let counter = attempts.clone();
+        runner.try_submit("ruvector", move || {
+            let counter = counter.clone();
+            async move {
+                if counter.fetch_add(1, Ordering::SeqCst) < 1 {
+                    Err(IntegrationError::ruvector("transient"))
+                } else {
+                    Ok(())
+                }
+            }
+        });
+        runner.try_submit("ruvector", || async {
+            Err(IntegrationError::ruvector("permanent"))
+        });
+
+        tokio::time::sleep(Duration::from_secs(1)).await;
+
+        let stats = runner.stats("ruvector").unwrap();
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+        assert_eq!(stats.completed, 1);
+        assert_eq!(stats.failed, 1);
+        assert_eq!(stats.retried, 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_failure_handler_gets_last_error() {
+        let runner = BackgroundRunner::new(BackgroundConfig {
+            max_retries: 2,
+            retry_backoff: Duration::from_millis(10),
+            ..Default::default()
+        });
+        let failures = Arc::new(Mutex::new(Vec::new()));
+
+        let recorded = failures.clone();
+        runner.try_submit_or_else(
+            "webhooks",
+            || async { Err(IntegrationError::webhook("receiver down")) },
+            move |error, attempts| async move {
+                recorded.lock().unwrap().push((error.to_string(), attempts));
+            },
+        );
+        let recorded = failures.clone();
+        runner.try_submit_or_else(
+            "webhooks",
+            || async { Ok(()) },
+            move |error, attempts| async move {
+                recorded.lock().unwrap().push((error.to_string(), attempts));
+            },
+        );
+
+        tokio::time::sleep(Duration::from_secs(1)).await;
+
+        let failures = failures.lock().unwrap();
+        assert_eq!(
+            *failures,
+            [("Webhook error: receiver down".to_string(), 3)]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_per_integration_rate_limits() {
+        let mut config = config(0, 100);
+        config.rate_limits.insert("cost_ops".to_string(), 2);
+        assert_eq!(config.rate_limit_for("cost_ops"), 2);
+        assert_eq!(config.rate_limit_for("observatory"), 0);
+
+        let runner = BackgroundRunner::new(config);
+        for _ in 0..4 {
+            runner.try_submit("observatory", || async { Ok(()) });
+            runner.try_submit("cost_ops", || async { Ok(()) });
+        }
+
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert_eq!(runner.stats("observatory").unwrap().completed, 4);
+        assert!(runner.stats("cost_ops").unwrap().completed < 4);
+
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        assert_eq!(runner.stats("cost_ops").unwrap().completed, 4);
+        assert_eq!(runner.all_stats().len(), 2);
+    }
+}
@@ -1,6 +1,9 @@
 //! Configuration for integration adapters.
 
+use crate::webhooks::WebhookEventType;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
 use std::time::Duration;
 
 /// Configuration for all integrations
@@ -45,6 +48,18 @@ pub struct IntegrationsConfig {
     /// RuVector service configuration
     #[serde(default)]
     pub ruvector: RuVectorConfig,
+
+    /// Background task runner configuration
+    #[serde(default)]
+    pub background: BackgroundConfig,
+
+    /// Lifecycle event webhook configuration
+    #[serde(default)]
+    pub webhooks: WebhooksConfig,
+
+    /// Dead-letter sink for permanently failed events
+    #[serde(default)]
+    pub dead_letter: DeadLetterConfig,
 }
 
 impl Default for IntegrationsConfig {
@@ -60,6 +75,9 @@ impl Default for IntegrationsConfig {
             policy_engine: PolicyEngineConfig::default(),
             router: RouterConfig::default(),
             ruvector: RuVectorConfig::default(),
+            background: BackgroundConfig::default(),
+            webhooks: WebhooksConfig::default(),
+            dead_letter: DeadLetterConfig::default(),
         }
     }
 }
@@ -348,6 +366,13 @@ pub struct PolicyEngineConfig {
     #[serde(default = "default_true")]
     pub block_on_violation: bool,
 
+    /// Allow requests when the policy engine is unreachable
+    ///
+    /// Defaults to fail-closed: requests are rejected until the engine
+    /// answers again.
+    #[serde(default)]
+    pub fail_open: bool,
+
     /// Cache policy decisions
     #[serde(default = "default_true")]
     pub cache_decisions: bool,
@@ -369,6 +394,7 @@ impl Default for PolicyEngineConfig {
             pre_request_check: true,
             post_response_check: false,
             block_on_violation: true,
+            fail_open: false,
             cache_decisions: true,
             cache_ttl: default_cache_ttl(),
             timeout: default_timeout(),
@@ -407,6 +433,173 @@ impl Default for RouterConfig {
     }
 }
 
+/// Background task runner configuration
+///
+/// Controls the shared queue used for fire-and-forget integration work such
+/// as telemetry emission, usage reporting and DecisionEvent persistence.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackgroundConfig {
+    /// Maximum queued tasks per integration before new tasks are dropped
+    #[serde(default = "default_queue_capacity")]
+    pub queue_capacity: usize,
+
+    /// Default rate limit in tasks per second (0 disables rate limiting)
+    #[serde(default = "default_tasks_per_second")]
+    pub tasks_per_second: u32,
+
+    /// Per-integration rate limit overrides, keyed by integration name
+    #[serde(default)]
+    pub rate_limits: HashMap<String, u32>,
+
+    /// Number of retries for a failed task
+    #[serde(default = "default_retry_count")]
+    pub max_retries: u32,
+
+    /// Initial delay between retries, doubled on each attempt
+    #[serde(default = "default_retry_backoff", with = "humantime_serde")]
+    pub retry_backoff: Duration,
+}
+
+impl BackgroundConfig {
+    /// Rate limit for an integration in tasks per second.
+    pub fn rate_limit_for(&self, integration: &str) -> u32 {
+        self.rate_limits
+            .get(integration)
+            .copied()
+            .unwrap_or(self.tasks_per_second)
+    }
+}
+
+impl Default for BackgroundConfig {
+    fn default() -> Self {
+        Self {
+            queue_capacity: default_queue_capacity(),
+            tasks_per_second: default_tasks_per_second(),
+            rate_limits: HashMap::new(),
+            max_retries: default_retry_count(),
+            retry_backoff: default_retry_backoff(),
+        }
+    }
+}
+
+/// Lifecycle event webhook configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhooksConfig {
+    /// Enable webhook delivery
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Endpoints that receive events
+    #[serde(default)]
+    pub endpoints: Vec<WebhookEndpointConfig>,
+
+    /// Timeout for a single delivery attempt
+    #[serde(default = "default_webhook_timeout", with = "humantime_serde")]
+    pub timeout: Duration,
+}
+
+impl Default for WebhooksConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoints: Vec::new(),
+            timeout: default_webhook_timeout(),
+        }
+    }
+}
+
+/// A single webhook receiver
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookEndpointConfig {
+    /// URL events are POSTed to
+    pub url: String,
+
+    /// Shared secret used to sign deliveries
+    pub secret: String,
+
+    /// Event types to deliver (empty delivers all events)
+    #[serde(default)]
+    pub events: Vec<WebhookEventType>,
+}
+
+impl WebhookEndpointConfig {
+    /// Check whether this endpoint receives an event type.
+    pub fn subscribes_to(&self, event_type: WebhookEventType) -> bool {
+        self.events.is_empty() || self.events.contains(&event_type)
+    }
+}
+
+/// Dead-letter sink configuration
+///
+/// Events that still fail after all background retries, such as
+/// DecisionEvents RuVector would not accept or undeliverable webhooks, are
+/// appended to a JSON lines file so they can be inspected and replayed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetterConfig {
+    /// Record permanently failed events
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// JSON lines file the events are written to
+    #[serde(default = "default_dead_letter_path")]
+    pub path: PathBuf,
+
+    /// Maximum events kept; the oldest are discarded beyond this
+    #[serde(default = "default_dead_letter_max_entries")]
+    pub max_entries: usize,
+}
+
+impl Default for DeadLetterConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: default_dead_letter_path(),
+            max_entries: default_dead_letter_max_entries(),
+        }
+    }
+}
+
+/// Buffered DecisionEvent persistence configuration
+///
+/// Used by [`BatchingPersistence`](crate::adapters::BatchingPersistence),
+/// which queues events and persists them in batches instead of one request
+/// per event. A batch is flushed once it reaches `max_batch_size` events or
+/// `flush_interval` has passed, whichever comes first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchConfig {
+    /// Events per batch; reaching it flushes the batch
+    #[serde(default = "default_batch_size")]
+    pub max_batch_size: usize,
+
+    /// Longest an event waits before its batch is flushed
+    #[serde(default = "default_flush_interval", with = "humantime_serde")]
+    pub flush_interval: Duration,
+
+    /// Maximum queued events waiting for a batch
+    #[serde(default = "default_queue_capacity")]
+    pub queue_capacity: usize,
+
+    /// Number of retries for a batch that fails to persist
+    #[serde(default = "default_retry_count")]
+    pub max_retries: u32,
+
+    /// Initial delay between retries, doubled on each attempt
+    #[serde(default = "default_retry_backoff", with = "humantime_serde")]
+    pub retry_backoff: Duration,
+}
+
+impl Default for BatchConfig {
+    fn default() -> Self {
+        Self {
+            max_batch_size: default_batch_size(),
+            flush_interval: default_flush_interval(),
+            queue_capacity: default_queue_capacity(),
+            max_retries: default_retry_count(),
+            retry_backoff: default_retry_backoff(),
+        }
+    }
+}
+
 // Default value functions
 
 fn default_timeout() -> Duration {
@@ -453,6 +646,30 @@ fn default_retry_count() -> u32 {
     3
 }
 
+fn default_webhook_timeout() -> Duration {
+    Duration::from_secs(10)
+}
+
+fn default_queue_capacity() -> usize {
+    1024
+}
+
+fn default_tasks_per_second() -> u32 {
+    100
+}
+
+fn default_retry_backoff() -> Duration {
+    Duration::from_millis(100)
+}
+
+fn default_dead_letter_path() -> PathBuf {
+    PathBuf::from("dead_letters.jsonl")
+}
+
+fn default_dead_letter_max_entries() -> usize {
+    10_000
+}
+
 /// RuVector service configuration
 ///
 /// Configuration for the RuVector service client adapter.
@@ -519,3 +736,15 @@ impl Default for RuVectorConfig {
         }
     }
 }
+
+impl RuVectorConfig {
+    /// Batching settings with this client's `batch_size` and
+    /// `flush_interval`.
+    pub fn batch_config(&self) -> BatchConfig {
+        BatchConfig {
+            max_batch_size: self.batch_size,
+            flush_interval: self.flush_interval,
+            ..BatchConfig::default()
+        }
+    }
+}
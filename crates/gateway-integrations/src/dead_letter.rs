@@ -0,0 +1,437 @@
+//! Dead-letter sink for integration events that permanently failed.
+//!
+//! The [`BackgroundRunner`](crate::background::BackgroundRunner) retries
+//! failed tasks with backoff, but an event whose backend stays down past the
+//! last retry would otherwise be lost. The [`DeadLetterSink`] keeps such
+//! events, with the error that ended them, in a JSON lines file. The file is
+//! bounded by `max_entries`: once full, the oldest events are discarded.
+//!
+//! [`IntegrationManager::replay_dead_letters`](crate::IntegrationManager::replay_dead_letters)
+//! re-submits the recorded events once the backend has recovered. Events
+//! are removed from the file only after they are delivered, so a replay that
+//! is interrupted leaves them in place; events that fail again are kept with
+//! the new error.
+
+use crate::adapters::DecisionEvent;
+use crate::config::DeadLetterConfig;
+use crate::error::{IntegrationError, IntegrationResult};
+use crate::webhooks::{WebhookEvent, WEBHOOK_LANE};
+use chrono::{DateTime, Utc};
+use fs2::FileExt;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tracing::warn;
+
+/// The event that could not be delivered.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DeadLetterPayload {
+    /// A DecisionEvent RuVector did not persist
+    DecisionEvent {
+        /// The event
+        event: DecisionEvent,
+    },
+    /// A webhook event its endpoint did not accept
+    Webhook {
+        /// Endpoint the event was addressed to
+        url: String,
+        /// The event
+        event: WebhookEvent,
+    },
+}
+
+impl DeadLetterPayload {
+    /// Integration the event belongs to.
+    pub fn integration(&self) -> &'static str {
+        match self {
+            Self::DecisionEvent { .. } => "ruvector",
+            Self::Webhook { .. } => WEBHOOK_LANE,
+        }
+    }
+}
+
+/// A permanently failed event and why it failed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetter {
+    /// Unique ID of this record
+    pub id: String,
+    /// The undelivered event
+    #[serde(flatten)]
+    pub payload: DeadLetterPayload,
+    /// Error from the last attempt
+    pub error: String,
+    /// Delivery attempts made, including retries and replays
+    pub attempts: u32,
+    /// When the last attempt failed
+    pub failed_at: DateTime<Utc>,
+}
+
+impl DeadLetter {
+    /// Record a failure of `payload` after `attempts` attempts.
+    pub fn new(payload: DeadLetterPayload, error: &IntegrationError, attempts: u32) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            payload,
+            error: error.to_string(),
+            attempts,
+            failed_at: Utc::now(),
+        }
+    }
+
+    /// Record another failed attempt.
+    pub fn retry_failed(&mut self, error: &IntegrationError) {
+        self.error = error.to_string();
+        self.attempts += 1;
+        self.failed_at = Utc::now();
+    }
+}
+
+/// Outcome of replaying the dead-letter sink.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplaySummary {
+    /// Events delivered on replay and removed from the sink
+    pub replayed: usize,
+    /// Events that failed again and remain in the sink
+    pub failed: usize,
+}
+
+/// Bounded JSON lines file of permanently failed events.
+///
+/// Failures are appended to the file, which is compacted to `max_entries`
+/// once that many have been appended. Every access holds an OS lock on a
+/// `.lock` file next to it, so the gateway and the CLI can share the file.
+#[derive(Debug)]
+pub struct DeadLetterSink {
+    path: PathBuf,
+    max_entries: usize,
+    /// Events appended by this process since the file was last compacted
+    appended: AtomicUsize,
+}
+
+impl DeadLetterSink {
+    /// Create a sink writing to the configured file.
+    ///
+    /// The file is created on the first recorded event.
+    pub fn new(config: &DeadLetterConfig) -> Self {
+        Self {
+            path: config.path.clone(),
+            max_entries: config.max_entries.max(1),
+            appended: AtomicUsize::new(0),
+        }
+    }
+
+    /// Path of the dead-letter file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append an event, discarding the oldest ones beyond `max_entries`.
+    pub async fn record(self: &Arc<Self>, letter: DeadLetter) -> IntegrationResult<()> {
+        let line = serde_json::to_string(&letter)
+            .map_err(|e| IntegrationError::DeadLetter(e.to_string()))?;
+        let sink = Arc::clone(self);
+        blocking(move || sink.append(&line)).await
+    }
+
+    /// Record a failure, logging instead of failing if it cannot be written.
+    pub async fn record_failure(
+        self: &Arc<Self>,
+        payload: DeadLetterPayload,
+        error: &IntegrationError,
+        attempts: u32,
+    ) {
+        let integration = payload.integration();
+        if let Err(e) = self.record(DeadLetter::new(payload, error, attempts)).await {
+            warn!(integration, error = %e, "Failed to record dead letter, event lost");
+        }
+    }
+
+    /// All recorded events, oldest first.
+    pub async fn entries(self: &Arc<Self>) -> IntegrationResult<Vec<DeadLetter>> {
+        let sink = Arc::clone(self);
+        blocking(move || {
+            let _lock = sink.lock(LockMode::Shared)?;
+            let mut letters = sink.read()?;
+            let overflow = letters.len().saturating_sub(sink.max_entries);
+            letters.drain(..overflow);
+            Ok(letters)
+        })
+        .await
+    }
+
+    /// Settle replayed events in one rewrite: remove those in `delivered`
+    /// and replace those in `failed` with their updated record.
+    ///
+    /// Events recorded since they were read are kept.
+    pub async fn settle(
+        self: &Arc<Self>,
+        delivered: HashSet<String>,
+        failed: Vec<DeadLetter>,
+    ) -> IntegrationResult<()> {
+        if delivered.is_empty() && failed.is_empty() {
+            return Ok(());
+        }
+        let sink = Arc::clone(self);
+        blocking(move || {
+            let _lock = sink.lock(LockMode::Exclusive)?;
+            let mut failed: HashMap<String, DeadLetter> = failed
+                .into_iter()
+                .map(|letter| (letter.id.clone(), letter))
+                .collect();
+            let letters = sink
+                .read()?
+                .into_iter()
+                .filter(|letter| !delivered.contains(&letter.id))
+                .map(|letter| failed.remove(&letter.id).unwrap_or(letter))
+                .collect();
+            sink.rewrite(letters)
+        })
+        .await
+    }
+
+    /// Hold the replay lock, so only one process replays at a time.
+    ///
+    /// # Errors
+    /// Fails if another replay holds it.
+    pub async fn begin_replay(self: &Arc<Self>) -> IntegrationResult<ReplayLock> {
+        let sink = Arc::clone(self);
+        blocking(move || {
+            let file = sink.open_sidecar("replay")?;
+            file.try_lock_exclusive().map_err(|_| {
+                IntegrationError::DeadLetter(format!(
+                    "A replay of {} is already running",
+                    sink.path.display()
+                ))
+            })?;
+            Ok(ReplayLock { _file: file })
+        })
+        .await
+    }
+
+    fn append(&self, line: &str) -> IntegrationResult<()> {
+        let _lock = self.lock(LockMode::Exclusive)?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| self.io_error("open", &e))?;
+        writeln!(file, "{line}").map_err(|e| self.io_error("append to", &e))?;
+
+        if self.appended.fetch_add(1, Ordering::Relaxed) + 1 >= self.max_entries {
+            self.appended.store(0, Ordering::Relaxed);
+            let letters = self.read()?;
+            self.rewrite(letters)?;
+        }
+        Ok(())
+    }
+
+    fn read(&self) -> IntegrationResult<Vec<DeadLetter>> {
+        let contents = match std::fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(self.io_error("read", &e)),
+        };
+
+        Ok(contents
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| match serde_json::from_str(line) {
+                Ok(letter) => Some(letter),
+                Err(e) => {
+                    warn!(path = %self.path.display(), error = %e, "Skipping unreadable dead letter");
+                    None
+                }
+            })
+            .collect())
+    }
+
+    /// Replace the file contents with the newest `max_entries` events, via a
+    /// temporary file so a crash mid-write never truncates it.
+    fn rewrite(&self, mut letters: Vec<DeadLetter>) -> IntegrationResult<()> {
+        let overflow = letters.len().saturating_sub(self.max_entries);
+        if overflow > 0 {
+            warn!(
+                path = %self.path.display(),
+                discarded = overflow,
+                "Dead-letter sink full, discarding oldest events"
+            );
+            letters.drain(..overflow);
+        }
+
+        let mut contents = String::new();
+        for letter in &letters {
+            contents.push_str(
+                &serde_json::to_string(letter)
+                    .map_err(|e| IntegrationError::DeadLetter(e.to_string()))?,
+            );
+            contents.push('\n');
+        }
+
+        let tmp = self.path.with_extension("tmp");
+        std::fs::write(&tmp, contents).map_err(|e| self.io_error("write", &e))?;
+        std::fs::rename(&tmp, &self.path).map_err(|e| self.io_error("replace", &e))
+    }
+
+    /// Lock the file against other processes and tasks until the returned
+    /// handle is dropped.
+    fn lock(&self, mode: LockMode) -> IntegrationResult<File> {
+        let file = self.open_sidecar("lock")?;
+        match mode {
+            LockMode::Shared => FileExt::lock_shared(&file),
+            LockMode::Exclusive => FileExt::lock_exclusive(&file),
+        }
+        .map_err(|e| self.io_error("lock", &e))?;
+        Ok(file)
+    }
+
+    /// Open `<path>.<suffix>`, creating it and its directory if needed.
+    fn open_sidecar(&self, suffix: &str) -> IntegrationResult<File> {
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| self.io_error("create directory for", &e))?;
+        }
+        let mut path = self.path.clone().into_os_string();
+        path.push(".");
+        path.push(suffix);
+        OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(path)
+            .map_err(|e| self.io_error("open lock for", &e))
+    }
+
+    fn io_error(&self, action: &str, error: &std::io::Error) -> IntegrationError {
+        IntegrationError::DeadLetter(format!(
+            "Failed to {action} dead-letter file {}: {error}",
+            self.path.display()
+        ))
+    }
+}
+
+/// Held while a replay is running; released on drop.
+#[derive(Debug)]
+pub struct ReplayLock {
+    _file: File,
+}
+
+#[derive(Clone, Copy)]
+enum LockMode {
+    Shared,
+    Exclusive,
+}
+
+/// Run blocking file work off the async runtime.
+async fn blocking<T: Send + 'static>(
+    work: impl FnOnce() -> IntegrationResult<T> + Send + 'static,
+) -> IntegrationResult<T> {
+    tokio::task::spawn_blocking(work)
+        .await
+        .map_err(|e| IntegrationError::DeadLetter(format!("Dead-letter task failed: {e}")))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sink(dir: &tempfile::TempDir, max_entries: usize) -> Arc<DeadLetterSink> {
+        Arc::new(DeadLetterSink::new(&DeadLetterConfig {
+            enabled: true,
+            path: dir.path().join("dead_letters.jsonl"),
+            max_entries,
+        }))
+    }
+
+    fn webhook(n: usize) -> DeadLetterPayload {
+        DeadLetterPayload::Webhook {
+            url: format!("https://hooks.example.com/{n}"),
+            event: WebhookEvent::new(
+                crate::webhooks::WebhookEventType::CircuitOpened,
+                serde_json::json!({ "n": n }),
+            ),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sink_is_bounded() {
+        let dir = tempfile::tempdir().unwrap();
+        let sink = sink(&dir, 3);
+        let error = IntegrationError::webhook("Webhook receiver returned 503");
+
+        for n in 0..5 {
+            sink.record_failure(webhook(n), &error, 4).await;
+        }
+
+        let letters = sink.entries().await.unwrap();
+        assert_eq!(letters.len(), 3);
+        let urls: Vec<_> = letters
+            .iter()
+            .map(|letter| match &letter.payload {
+                DeadLetterPayload::Webhook { url, .. } => url.as_str(),
+                DeadLetterPayload::DecisionEvent { .. } => unreachable!(),
+            })
+            .collect();
+        assert_eq!(
+            urls,
+            [
+                "https://hooks.example.com/2",
+                "https://hooks.example.com/3",
+                "https://hooks.example.com/4"
+            ]
+        );
+        assert_eq!(letters[0].attempts, 4);
+        assert!(letters[0].error.contains("503"));
+
+        // Records survive reopening the file.
+        let reopened = self::sink(&dir, 3);
+        assert_eq!(reopened.entries().await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_settle_keeps_undelivered_and_new_events() {
+        let dir = tempfile::tempdir().unwrap();
+        let gateway = sink(&dir, 10);
+        let cli = sink(&dir, 10);
+        let error = IntegrationError::webhook("Webhook receiver returned 503");
+        for n in 0..3 {
+            gateway.record_failure(webhook(n), &error, 4).await;
+        }
+
+        let mut letters = cli.entries().await.unwrap();
+        let _replay = cli.begin_replay().await.unwrap();
+        assert!(gateway.begin_replay().await.is_err());
+
+        // Recorded by the other process while the replay runs
+        gateway.record_failure(webhook(3), &error, 4).await;
+
+        let delivered = HashSet::from([letters[0].id.clone()]);
+        letters[1].retry_failed(&IntegrationError::webhook("Webhook receiver returned 502"));
+        cli.settle(delivered, vec![letters[1].clone()]).await.unwrap();
+
+        let remaining = gateway.entries().await.unwrap();
+        let ids: Vec<_> = remaining.iter().map(|letter| letter.id.as_str()).collect();
+        assert_eq!(ids, [letters[1].id.as_str(), letters[2].id.as_str(), remaining[2].id.as_str()]);
+        assert_eq!(remaining[0].attempts, 5);
+        assert!(remaining[0].error.contains("502"));
+        assert!(matches!(
+            &remaining[2].payload,
+            DeadLetterPayload::Webhook { url, .. } if url == "https://hooks.example.com/3"
+        ));
+    }
+
+    #[test]
+    fn test_payload_wire_format() {
+        let letter = DeadLetter::new(webhook(1), &IntegrationError::webhook("down"), 2);
+        let json = serde_json::to_value(&letter).unwrap();
+        assert_eq!(json["kind"], "webhook");
+        assert_eq!(json["url"], "https://hooks.example.com/1");
+        assert_eq!(json["event"]["type"], "circuit.opened");
+        assert_eq!(json["attempts"], 2);
+        assert_eq!(letter.payload.integration(), WEBHOOK_LANE);
+    }
+}
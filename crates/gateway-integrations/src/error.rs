@@ -84,6 +84,17 @@ pub enum IntegrationError {
         retryable: bool,
     },
 
+    /// Error delivering a webhook
+    #[error("Webhook error: {message}")]
+    Webhook {
+        /// Error message
+        message: String,
+    },
+
+    /// Error reading or writing the dead-letter sink
+    #[error("Dead-letter error: {0}")]
+    DeadLetter(String),
+
     /// Configuration error
     #[error("Configuration error: {0}")]
     Configuration(String),
@@ -179,6 +190,13 @@ impl IntegrationError {
         }
     }
 
+    /// Create a new webhook error
+    pub fn webhook(message: impl Into<String>) -> Self {
+        Self::Webhook {
+            message: message.into(),
+        }
+    }
+
     /// Check if this error indicates content was blocked
     pub fn is_blocked(&self) -> bool {
         matches!(self, Self::Shield { blocked: true, .. })
@@ -22,16 +22,27 @@
 #![warn(missing_docs)]
 
 pub mod adapters;
+pub mod background;
 pub mod config;
+pub mod dead_letter;
 pub mod error;
 pub mod traits;
+pub mod webhooks;
 
 // Re-export commonly used types
 pub use adapters::IntegrationManager;
-pub use adapters::{DecisionEvent, EventQuery, RuVectorClient, RuVectorPersistence};
-pub use config::{IntegrationsConfig, RuVectorConfig};
+pub use adapters::{
+    BatchingPersistence, DecisionEvent, EventQuery, RuVectorClient, RuVectorPersistence,
+};
+pub use background::{BackgroundRunner, LaneStats};
+pub use config::{
+    BackgroundConfig, BatchConfig, DeadLetterConfig, IntegrationsConfig, RuVectorConfig, WebhookEndpointConfig,
+    WebhooksConfig,
+};
+pub use dead_letter::{DeadLetter, DeadLetterPayload, DeadLetterSink, ReplaySummary};
 pub use error::{IntegrationError, IntegrationResult};
 pub use traits::{
     CostConsumer, ObservabilityEmitter, OptimizationConsumer, PolicyConsumer, ProviderRouter,
     SafetyFilter, SentinelConsumer,
 };
+pub use webhooks::{WebhookEmitter, WebhookEvent, WebhookEventType};
@@ -0,0 +1,490 @@
+//! Signed webhook delivery for gateway lifecycle events.
+//!
+//! The [`WebhookEmitter`] POSTs JSON events such as a completed request or
+//! an opened circuit breaker to the configured endpoints. Each endpoint
+//! subscribes to a set of event types; events it has not subscribed to are
+//! never sent to it. Deliveries run on the shared [`BackgroundRunner`] under
+//! the `webhooks` lane, so they are queued, rate limited and retried with
+//! backoff off the request path. Deliveries that fail every retry are
+//! recorded in the [`DeadLetterSink`], if one is attached, and can be sent
+//! again with [`WebhookEmitter::redeliver`].
+//!
+//! Every delivery carries an `X-Gateway-Signature` header of the form
+//! `t=<unix seconds>,v1=<hex>`, where the hex value is the HMAC-SHA256 of
+//! `<unix seconds>.<body>` keyed with the endpoint secret. Receivers should
+//! recompute it with [`sign`] and reject stale timestamps.
+
+use crate::background::BackgroundRunner;
+use crate::config::{WebhookEndpointConfig, WebhooksConfig};
+use crate::dead_letter::{DeadLetterPayload, DeadLetterSink};
+use crate::error::{IntegrationError, IntegrationResult};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+/// Background lane used for webhook deliveries.
+pub const WEBHOOK_LANE: &str = "webhooks";
+
+/// Header carrying the delivery signature.
+pub const SIGNATURE_HEADER: &str = "x-gateway-signature";
+
+/// Header carrying the event type.
+pub const EVENT_HEADER: &str = "x-gateway-event";
+
+/// Gateway lifecycle events that can be delivered to a webhook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WebhookEventType {
+    /// A request completed successfully
+    #[serde(rename = "request.completed")]
+    RequestCompleted,
+    /// A tenant's spend crossed its monthly budget
+    #[serde(rename = "budget.exceeded")]
+    BudgetExceeded,
+    /// A provider's circuit breaker opened
+    #[serde(rename = "circuit.opened")]
+    CircuitOpened,
+    /// An API key is within its expiry warning window
+    #[serde(rename = "key.expiring")]
+    KeyExpiring,
+}
+
+impl WebhookEventType {
+    /// Wire name of the event type.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::RequestCompleted => "request.completed",
+            Self::BudgetExceeded => "budget.exceeded",
+            Self::CircuitOpened => "circuit.opened",
+            Self::KeyExpiring => "key.expiring",
+        }
+    }
+}
+
+impl std::fmt::Display for WebhookEventType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// JSON body of a webhook delivery.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookEvent {
+    /// Unique event ID, shared by all endpoints receiving the event
+    pub id: String,
+    /// Event type
+    #[serde(rename = "type")]
+    pub event_type: WebhookEventType,
+    /// When the event occurred
+    pub created_at: DateTime<Utc>,
+    /// Event-specific details
+    pub data: serde_json::Value,
+}
+
+impl WebhookEvent {
+    /// Create an event with a fresh ID.
+    pub fn new(event_type: WebhookEventType, data: serde_json::Value) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            event_type,
+            created_at: Utc::now(),
+            data,
+        }
+    }
+}
+
+/// Compute the hex HMAC-SHA256 signature of a delivery body.
+pub fn sign(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC can take key of any size");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Delivers lifecycle events to subscribed webhook endpoints.
+pub struct WebhookEmitter {
+    endpoints: Vec<WebhookEndpointConfig>,
+    runner: Arc<BackgroundRunner>,
+    http_client: reqwest::Client,
+    dead_letters: Option<Arc<DeadLetterSink>>,
+}
+
+impl WebhookEmitter {
+    /// Create an emitter that delivers on the given runner.
+    pub fn new(config: WebhooksConfig, runner: Arc<BackgroundRunner>) -> IntegrationResult<Self> {
+        let http_client = reqwest::Client::builder()
+            .timeout(config.timeout)
+            .build()
+            .map_err(|e| {
+                IntegrationError::Configuration(format!("Failed to create HTTP client: {e}"))
+            })?;
+
+        Ok(Self {
+            endpoints: config.endpoints,
+            runner,
+            http_client,
+            dead_letters: None,
+        })
+    }
+
+    /// Record deliveries that fail all retries in a dead-letter sink.
+    #[must_use]
+    pub fn with_dead_letters(mut self, sink: Arc<DeadLetterSink>) -> Self {
+        self.dead_letters = Some(sink);
+        self
+    }
+
+    /// Check whether any endpoint subscribes to an event type.
+    pub fn is_subscribed(&self, event_type: WebhookEventType) -> bool {
+        self.endpoints
+            .iter()
+            .any(|endpoint| endpoint.subscribes_to(event_type))
+    }
+
+    /// Queue an event for every endpoint subscribed to its type.
+    ///
+    /// Returns the number of deliveries queued. Deliveries dropped because
+    /// the queue is full are counted by the runner.
+    pub fn emit(&self, event: &WebhookEvent) -> usize {
+        let body = match serde_json::to_vec(event) {
+            Ok(body) => bytes::Bytes::from(body),
+            Err(e) => {
+                warn!(error = %e, event_type = %event.event_type, "Failed to serialize webhook event");
+                return 0;
+            }
+        };
+
+        let mut queued = 0;
+        for endpoint in self
+            .endpoints
+            .iter()
+            .filter(|endpoint| endpoint.subscribes_to(event.event_type))
+        {
+            let delivery =
+                self.delivery(endpoint, event, body.clone(), event.created_at.timestamp());
+            let dead_letters = self.dead_letters.clone();
+            let payload = DeadLetterPayload::Webhook {
+                url: endpoint.url.clone(),
+                event: event.clone(),
+            };
+
+            if self.runner.try_submit_or_else(
+                WEBHOOK_LANE,
+                move || delivery.clone().send(),
+                move |error, attempts| async move {
+                    if let Some(sink) = dead_letters {
+                        sink.record_failure(payload, &error, attempts).await;
+                    }
+                },
+            ) {
+                queued += 1;
+            } else {
+                debug!(url = %endpoint.url, "Webhook delivery dropped, background queue full");
+            }
+        }
+        queued
+    }
+
+    /// Send a previously failed event to one endpoint immediately.
+    ///
+    /// The event is signed afresh, with the current time, so receivers that
+    /// reject stale timestamps accept it. Fails if `url` is no longer a
+    /// configured endpoint or the delivery fails.
+    pub async fn redeliver(&self, url: &str, event: &WebhookEvent) -> IntegrationResult<()> {
+        let endpoint = self
+            .endpoints
+            .iter()
+            .find(|endpoint| endpoint.url == url)
+            .ok_or_else(|| {
+                IntegrationError::webhook(format!("No webhook endpoint configured for {url}"))
+            })?;
+        let body = serde_json::to_vec(event)
+            .map_err(|e| IntegrationError::webhook(format!("Failed to serialize event: {e}")))?;
+
+        self.delivery(endpoint, event, body.into(), Utc::now().timestamp())
+            .send()
+            .await
+    }
+
+    fn delivery(
+        &self,
+        endpoint: &WebhookEndpointConfig,
+        event: &WebhookEvent,
+        body: bytes::Bytes,
+        timestamp: i64,
+    ) -> Delivery {
+        let signature = format!(
+            "t={timestamp},v1={}",
+            sign(&endpoint.secret, timestamp, &body)
+        );
+        Delivery {
+            client: self.http_client.clone(),
+            url: endpoint.url.clone(),
+            event_type: event.event_type,
+            event_id: event.id.clone(),
+            signature,
+            body,
+        }
+    }
+}
+
+impl std::fmt::Debug for WebhookEmitter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebhookEmitter")
+            .field("endpoints", &self.endpoints.len())
+            .field("runner", &self.runner)
+            .field("dead_letters", &self.dead_letters)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A single signed POST, repeatable for retries.
+#[derive(Clone)]
+struct Delivery {
+    client: reqwest::Client,
+    url: String,
+    event_type: WebhookEventType,
+    event_id: String,
+    signature: String,
+    body: bytes::Bytes,
+}
+
+impl Delivery {
+    /// Send the event, failing on errors worth retrying.
+    ///
+    /// Server errors, rate limiting and connection failures are retried.
+    /// Other client errors mean the receiver rejected the event, so they are
+    /// logged and not retried.
+    async fn send(self) -> IntegrationResult<()> {
+        let response = self
+            .client
+            .post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(SIGNATURE_HEADER, &self.signature)
+            .header(EVENT_HEADER, self.event_type.as_str())
+            .body(self.body)
+            .send()
+            .await
+            .map_err(|e| IntegrationError::Connection(format!("Webhook delivery failed: {e}")))?;
+
+        let status = response.status();
+        if status.is_success() {
+            debug!(url = %self.url, event_id = %self.event_id, "Webhook delivered");
+            Ok(())
+        } else if status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            Err(IntegrationError::webhook(format!(
+                "Webhook receiver returned {status}"
+            )))
+        } else {
+            warn!(
+                url = %self.url,
+                event_id = %self.event_id,
+                status = %status,
+                "Webhook receiver rejected event"
+            );
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::BackgroundConfig;
+    use std::time::Duration;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const SECRET: &str = "whsec_test";
+
+    fn emitter(server: &MockServer, events: Vec<WebhookEventType>) -> WebhookEmitter {
+        let runner = Arc::new(BackgroundRunner::new(BackgroundConfig {
+            tasks_per_second: 0,
+            max_retries: 2,
+            retry_backoff: Duration::from_millis(10),
+            ..Default::default()
+        }));
+        let config = WebhooksConfig {
+            enabled: true,
+            endpoints: vec![WebhookEndpointConfig {
+                url: format!("{}/hooks", server.uri()),
+                secret: SECRET.to_string(),
+                events,
+            }],
+            ..Default::default()
+        };
+        WebhookEmitter::new(config, runner).unwrap()
+    }
+
+    async fn wait_until_settled(emitter: &WebhookEmitter) -> crate::background::LaneStats {
+        for _ in 0..200 {
+            if let Some(stats) = emitter.runner.stats(WEBHOOK_LANE) {
+                if stats.queue_depth == 0 && stats.completed + stats.failed == stats.submitted {
+                    return stats;
+                }
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("webhook deliveries did not settle");
+    }
+
+    #[tokio::test]
+    async fn test_subscribed_event_is_signed_and_retried() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/hooks"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/hooks"))
+            .and(header(EVENT_HEADER, "circuit.opened"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let emitter = emitter(&server, vec![WebhookEventType::CircuitOpened]);
+        let event = WebhookEvent::new(
+            WebhookEventType::CircuitOpened,
+            serde_json::json!({"provider": "openai"}),
+        );
+        assert_eq!(emitter.emit(&event), 1);
+
+        let stats = wait_until_settled(&emitter).await;
+        assert_eq!(stats.completed, 1);
+        assert_eq!(stats.retried, 1);
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 2);
+        let delivered = &requests[1];
+        let body: serde_json::Value = serde_json::from_slice(&delivered.body).unwrap();
+        assert_eq!(body["id"], event.id);
+        assert_eq!(body["type"], "circuit.opened");
+        assert_eq!(body["data"]["provider"], "openai");
+
+        let signature = delivered.headers[SIGNATURE_HEADER].to_str().unwrap();
+        let (timestamp, hex) = signature
+            .strip_prefix("t=")
+            .and_then(|rest| rest.split_once(",v1="))
+            .unwrap();
+        let timestamp: i64 = timestamp.parse().unwrap();
+        assert_eq!(timestamp, event.created_at.timestamp());
+        assert_eq!(hex, sign(SECRET, timestamp, &delivered.body));
+        assert_ne!(hex, sign("wrong", timestamp, &delivered.body));
+    }
+
+    #[tokio::test]
+    async fn test_failed_delivery_is_dead_lettered_and_redelivered() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let sink = Arc::new(DeadLetterSink::new(&crate::config::DeadLetterConfig {
+            enabled: true,
+            path: dir.path().join("dead_letters.jsonl"),
+            ..Default::default()
+        }));
+        let emitter = emitter(&server, Vec::new()).with_dead_letters(sink.clone());
+        let event = WebhookEvent::new(WebhookEventType::BudgetExceeded, serde_json::json!({}));
+        assert_eq!(emitter.emit(&event), 1);
+
+        let stats = wait_until_settled(&emitter).await;
+        assert_eq!(stats.failed, 1);
+        let mut letters = Vec::new();
+        for _ in 0..200 {
+            letters = sink.entries().await.unwrap();
+            if !letters.is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(letters.len(), 1);
+        assert_eq!(letters[0].attempts, 3);
+        assert!(letters[0].error.contains("503"));
+        let DeadLetterPayload::Webhook { url, event: recorded } = &letters[0].payload else {
+            panic!("expected a webhook event");
+        };
+        assert_eq!(recorded.id, event.id);
+
+        server.reset().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        emitter.redeliver(url, recorded).await.unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        let signature = requests[0].headers[SIGNATURE_HEADER].to_str().unwrap();
+        let (timestamp, hex) = signature
+            .strip_prefix("t=")
+            .and_then(|rest| rest.split_once(",v1="))
+            .unwrap();
+        let timestamp: i64 = timestamp.parse().unwrap();
+        assert!(timestamp >= event.created_at.timestamp());
+        assert_eq!(hex, sign(SECRET, timestamp, &requests[0].body));
+
+        assert!(emitter
+            .redeliver("https://unknown.example.com", recorded)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_unsubscribed_event_is_not_sent() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let emitter = emitter(&server, vec![WebhookEventType::BudgetExceeded]);
+        assert!(!emitter.is_subscribed(WebhookEventType::RequestCompleted));
+
+        let event = WebhookEvent::new(WebhookEventType::RequestCompleted, serde_json::json!({}));
+        assert_eq!(emitter.emit(&event), 0);
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(server.received_requests().await.unwrap().is_empty());
+        assert!(emitter.runner.stats(WEBHOOK_LANE).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_client_errors_are_not_retried() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(400))
+            .mount(&server)
+            .await;
+
+        let emitter = emitter(&server, Vec::new());
+        let event = WebhookEvent::new(WebhookEventType::KeyExpiring, serde_json::json!({}));
+        assert_eq!(emitter.emit(&event), 1);
+
+        let stats = wait_until_settled(&emitter).await;
+        assert_eq!(stats.retried, 0);
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_event_type_wire_names() {
+        for event_type in [
+            WebhookEventType::RequestCompleted,
+            WebhookEventType::BudgetExceeded,
+            WebhookEventType::CircuitOpened,
+            WebhookEventType::KeyExpiring,
+        ] {
+            let json = serde_json::to_value(event_type).unwrap();
+            assert_eq!(json, event_type.as_str());
+        }
+    }
+}
@@ -41,7 +41,7 @@ pub mod schema;
 pub use config::{DatabaseType, MigrationConfig, MigrationConfigBuilder};
 pub use error::{MigrationError, Result};
 pub use migration::{Migration, MigrationRecord, MigrationStatus};
-pub use migrator::Migrator;
+pub use migrator::{MigrationPlan, Migrator, PlanState, PlannedMigration};
 pub use pool::{DatabasePool, PoolConfig};
 
 /// Re-export sqlx types for convenience
@@ -5,6 +5,7 @@ use crate::error::{MigrationError, Result};
 use crate::migration::{Migration, MigrationRecord, MigrationStatus};
 use crate::pool::DatabasePool;
 use chrono::Utc;
+use serde::Serialize;
 use sqlx::{Executor, Row};
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -185,6 +186,59 @@ impl Migrator {
         Ok(pending)
     }
 
+    /// Plan the pending migrations without applying them.
+    ///
+    /// Only reads the tracking table, so a database that has never been
+    /// migrated plans every migration. Applied migrations whose SQL has
+    /// changed since they ran are flagged as [`PlanState::Conflicted`].
+    pub async fn plan(&self) -> Result<MigrationPlan> {
+        let applied = if self.tracking_table_exists().await? {
+            self.get_applied().await?
+        } else {
+            Vec::new()
+        };
+        Ok(MigrationPlan::new(&self.migrations, &applied))
+    }
+
+    /// Run the pending migrations in a transaction, then roll it back.
+    ///
+    /// Checks that every pending migration executes against the current
+    /// schema without keeping its changes or recording it as applied.
+    /// Conflicted migrations are reported in the plan but not executed.
+    pub async fn dry_run(&self) -> Result<MigrationPlan> {
+        let plan = self.plan().await?;
+
+        let mut tx = self
+            .pool
+            .inner()
+            .begin()
+            .await
+            .map_err(|e| MigrationError::Execution(e.to_string()))?;
+
+        for migration in plan.pending() {
+            debug!(version = migration.version, name = %migration.name, "Dry-running migration");
+            for statement in split_statements(&migration.up_sql) {
+                tx.execute(sqlx::query(statement))
+                    .await
+                    .map_err(|e| MigrationError::Failed {
+                        version: migration.version,
+                        reason: e.to_string(),
+                    })?;
+            }
+        }
+
+        tx.rollback()
+            .await
+            .map_err(|e| MigrationError::Execution(e.to_string()))?;
+
+        info!(
+            pending = plan.pending().count(),
+            conflicted = plan.conflicted().count(),
+            "Dry run rolled back"
+        );
+        Ok(plan)
+    }
+
     /// Run all pending migrations.
     pub async fn run_pending(&self) -> Result<Vec<MigrationRecord>> {
         self.init().await?;
@@ -471,14 +525,10 @@ impl Migrator {
             .await
             .map_err(|e| MigrationError::Execution(e.to_string()))?;
 
-        // Split by semicolons and execute each statement
-        for statement in sql.split(';') {
-            let statement = statement.trim();
-            if !statement.is_empty() {
-                tx.execute(sqlx::query(statement))
-                    .await
-                    .map_err(|e| MigrationError::Execution(e.to_string()))?;
-            }
+        for statement in split_statements(sql) {
+            tx.execute(sqlx::query(statement))
+                .await
+                .map_err(|e| MigrationError::Execution(e.to_string()))?;
         }
 
         tx.commit()
@@ -488,6 +538,26 @@ impl Migrator {
         Ok(())
     }
 
+    async fn tracking_table_exists(&self) -> Result<bool> {
+        let query = match self.config.database_type {
+            DatabaseType::PostgreSQL => sqlx::query(
+                "SELECT 1 FROM information_schema.tables WHERE table_schema = $1 AND table_name = $2",
+            )
+            .bind(self.config.schema.as_str())
+            .bind(self.config.table_name.as_str()),
+            DatabaseType::SQLite => {
+                sqlx::query("SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = $1")
+                    .bind(self.config.table_name.as_str())
+            }
+        };
+
+        let row = query
+            .fetch_optional(self.pool.inner())
+            .await
+            .map_err(|e| MigrationError::Execution(e.to_string()))?;
+        Ok(row.is_some())
+    }
+
     async fn save_record(&self, record: &MigrationRecord) -> Result<()> {
         let sql = match self.config.database_type {
             DatabaseType::PostgreSQL => format!(
@@ -533,6 +603,101 @@ impl Migrator {
     }
 }
 
+/// Split migration SQL into its statements on semicolons.
+fn split_statements(sql: &str) -> impl Iterator<Item = &str> {
+    sql.split(';').map(str::trim).filter(|s| !s.is_empty())
+}
+
+/// Migrations that would run, as reported by [`Migrator::plan`].
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MigrationPlan {
+    /// Planned migrations, in version order.
+    pub migrations: Vec<PlannedMigration>,
+}
+
+impl MigrationPlan {
+    fn new(migrations: &[Migration], applied: &[MigrationRecord]) -> Self {
+        let applied: HashMap<i64, &MigrationRecord> =
+            applied.iter().map(|r| (r.version, r)).collect();
+
+        let migrations = migrations
+            .iter()
+            .filter_map(|migration| {
+                let state = match applied.get(&migration.version) {
+                    None => PlanState::Pending,
+                    Some(record)
+                        if record.status == MigrationStatus::Applied
+                            && record.checksum != migration.checksum =>
+                    {
+                        PlanState::Conflicted {
+                            applied_checksum: record.checksum.clone(),
+                        }
+                    }
+                    Some(_) => return None,
+                };
+                Some(PlannedMigration {
+                    version: migration.version,
+                    name: migration.name.clone(),
+                    checksum: migration.checksum.clone(),
+                    up_sql: migration.up_sql.clone(),
+                    state,
+                })
+            })
+            .collect();
+
+        Self { migrations }
+    }
+
+    /// Migrations that would be applied.
+    pub fn pending(&self) -> impl Iterator<Item = &PlannedMigration> {
+        self.migrations
+            .iter()
+            .filter(|m| m.state == PlanState::Pending)
+    }
+
+    /// Applied migrations whose SQL has changed since they ran.
+    pub fn conflicted(&self) -> impl Iterator<Item = &PlannedMigration> {
+        self.migrations
+            .iter()
+            .filter(|m| matches!(m.state, PlanState::Conflicted { .. }))
+    }
+
+    /// Check if any applied migration has drifted.
+    #[must_use]
+    pub fn has_conflicts(&self) -> bool {
+        self.conflicted().next().is_some()
+    }
+}
+
+/// A migration in a [`MigrationPlan`].
+#[derive(Debug, Clone, Serialize)]
+pub struct PlannedMigration {
+    /// Migration version.
+    pub version: i64,
+    /// Migration name.
+    pub name: String,
+    /// Checksum of the migration SQL.
+    pub checksum: String,
+    /// SQL that would execute.
+    pub up_sql: String,
+    /// Whether the migration would run or conflicts with the database.
+    #[serde(flatten)]
+    pub state: PlanState,
+}
+
+/// State of a planned migration.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum PlanState {
+    /// Not yet applied; would run.
+    Pending,
+    /// Applied with different SQL; would not run.
+    Conflicted {
+        /// Checksum recorded when the migration was applied.
+        applied_checksum: String,
+    },
+}
+
 /// Validation issue.
 #[derive(Debug, Clone)]
 pub enum ValidationIssue {
@@ -590,4 +755,67 @@ mod tests {
         };
         assert!(issue.to_string().contains("mismatch"));
     }
+
+    #[test]
+    fn test_plan_flags_checksum_drift() {
+        let migrations = vec![
+            Migration::new(1, "create_users", "CREATE TABLE users (id INTEGER)"),
+            Migration::new(2, "create_keys", "CREATE TABLE keys (id INTEGER)"),
+            Migration::new(3, "create_logs", "CREATE TABLE logs (id INTEGER)"),
+        ];
+        let mut drifted = MigrationRecord::new(&migrations[1]).applied(5);
+        drifted.checksum = Migration::compute_checksum("CREATE TABLE keys (id TEXT)");
+        let applied = vec![MigrationRecord::new(&migrations[0]).applied(3), drifted];
+
+        let plan = MigrationPlan::new(&migrations, &applied);
+
+        assert_eq!(plan.migrations.len(), 2);
+        let pending: Vec<_> = plan.pending().map(|m| m.version).collect();
+        assert_eq!(pending, [3]);
+        assert_eq!(plan.migrations[1].up_sql, "CREATE TABLE logs (id INTEGER)");
+        assert!(plan.has_conflicts());
+        assert_eq!(
+            plan.migrations[0].state,
+            PlanState::Conflicted {
+                applied_checksum: applied[1].checksum.clone()
+            }
+        );
+
+        let json = serde_json::to_value(&plan.migrations[0]).unwrap();
+        assert_eq!(json["state"], "conflicted");
+        assert_eq!(json["applied_checksum"], applied[1].checksum.as_str());
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_rolls_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let url = format!("sqlite://{}?mode=rwc", dir.path().join("gateway.db").display());
+        let config = MigrationConfig::builder()
+            .database_url(&url)
+            .database_type(DatabaseType::SQLite)
+            .build()
+            .unwrap();
+        let mut migrator = Migrator::new(config).await.unwrap();
+        migrator.add_migrations([
+            Migration::new(1, "create_users", "CREATE TABLE users (id INTEGER)"),
+            Migration::new(2, "create_keys", "CREATE TABLE keys (id INTEGER); INSERT INTO keys VALUES (1)"),
+        ]);
+
+        // Planning an unmigrated database creates nothing
+        assert_eq!(migrator.plan().await.unwrap().pending().count(), 2);
+        assert!(!migrator.tracking_table_exists().await.unwrap());
+
+        let plan = migrator.dry_run().await.unwrap();
+        assert_eq!(plan.pending().count(), 2);
+        let tables: Vec<String> =
+            sqlx::query_scalar("SELECT name FROM sqlite_master WHERE type = 'table'")
+                .fetch_all(migrator.pool().inner())
+                .await
+                .unwrap();
+        assert!(tables.is_empty(), "{tables:?}");
+
+        migrator.add_migration(Migration::new(3, "broken", "CREATE TABLE"));
+        let err = migrator.dry_run().await.unwrap_err();
+        assert!(matches!(err, MigrationError::Failed { version: 3, .. }));
+    }
 }
@@ -114,6 +114,8 @@ pub struct DatabasePool {
 impl DatabasePool {
     /// Create a new database pool.
     pub async fn new(config: MigrationConfig) -> Result<Self> {
+        sqlx::any::install_default_drivers();
+
         let pool_options = AnyPoolOptions::new()
             .max_connections(config.max_connections)
             .min_connections(1)
@@ -136,6 +138,8 @@ impl DatabasePool {
         migration_config: MigrationConfig,
         pool_config: PoolConfig,
     ) -> Result<Self> {
+        sqlx::any::install_default_drivers();
+
         let pool_options = AnyPoolOptions::new()
             .max_connections(pool_config.max_connections)
             .min_connections(pool_config.min_connections)
@@ -3,19 +3,24 @@
 //! Supports Claude 3.5 Sonnet, Claude 3 Opus, Claude 3 Sonnet, Claude 3 Haiku models.
 
 use async_stream::try_stream;
+use dashmap::DashMap;
 use futures::stream::BoxStream;
 use futures_util::StreamExt;
 use gateway_core::{
-    ChatChunk, FinishReason, GatewayError, GatewayRequest, GatewayResponse,
+    ChatChunk, ConnectionPoolStats, FinishReason, GatewayError, GatewayRequest, GatewayResponse,
     HealthStatus, LLMProvider, MessageContent, MessageRole, ModelInfo, ProviderCapabilities,
-    ProviderType, Usage,
+    ProviderRateLimits, ProviderType, Usage,
 };
+use crate::{context_length, transport};
+use crate::egress::EgressPolicy;
+use crate::headers::DefaultHeaders;
+use crate::params::ParamFilter;
+use crate::pool::{ConnectionWarmer, PoolConfig};
 use reqwest::Client;
-use reqwest_eventsource::{Event, EventSource};
 use secrecy::{ExposeSecret, SecretString};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
-use tracing::{debug, error, instrument, warn};
+use tracing::{debug, instrument, warn};
 
 /// Anthropic API version header value
 const ANTHROPIC_VERSION: &str = "2023-06-01";
@@ -34,6 +39,14 @@ pub struct AnthropicConfig {
     pub timeout: Duration,
     /// Available models
     pub models: Vec<ModelInfo>,
+    /// Connection pool settings
+    pub pool: PoolConfig,
+    /// Headers sent on every request
+    pub default_headers: DefaultHeaders,
+    /// Hosts and networks the client may connect to
+    pub egress: EgressPolicy,
+    /// Request parameters the provider may receive
+    pub params: ParamFilter,
 }
 
 impl AnthropicConfig {
@@ -45,6 +58,10 @@ impl AnthropicConfig {
             base_url: DEFAULT_BASE_URL.to_string(),
             timeout: Duration::from_secs(120),
             models: default_anthropic_models(),
+            pool: PoolConfig::default(),
+            default_headers: DefaultHeaders::default(),
+            egress: EgressPolicy::default(),
+            params: ParamFilter::default(),
         }
     }
 
@@ -68,6 +85,34 @@ impl AnthropicConfig {
         self.models = models;
         self
     }
+
+    /// Set the connection pool settings
+    #[must_use]
+    pub fn with_pool(mut self, pool: PoolConfig) -> Self {
+        self.pool = pool;
+        self
+    }
+
+    /// Set headers sent on every request
+    #[must_use]
+    pub fn with_default_headers(mut self, headers: DefaultHeaders) -> Self {
+        self.default_headers = headers;
+        self
+    }
+
+    /// Set the hosts and networks the client may connect to
+    #[must_use]
+    pub fn with_egress(mut self, egress: EgressPolicy) -> Self {
+        self.egress = egress;
+        self
+    }
+
+    /// Set the request parameters the provider may receive
+    #[must_use]
+    pub fn with_params(mut self, params: ParamFilter) -> Self {
+        self.params = params;
+        self
+    }
 }
 
 /// Get default Anthropic models
@@ -113,6 +158,10 @@ pub struct AnthropicProvider {
     config: AnthropicConfig,
     /// Provider capabilities
     capabilities: ProviderCapabilities,
+    /// Last reported rate limits per model
+    rate_limits: DashMap<String, ProviderRateLimits>,
+    /// Keeps idle connections warm when enabled
+    warmer: Option<ConnectionWarmer>,
 }
 
 impl AnthropicProvider {
@@ -129,12 +178,17 @@ impl AnthropicProvider {
     /// # Errors
     /// Returns error if HTTP client cannot be built
     pub fn with_id(id: impl Into<String>, config: AnthropicConfig) -> Result<Self, GatewayError> {
-        let client = Client::builder()
-            .timeout(config.timeout)
+        let builder = config.pool.configure(Client::builder().timeout(config.timeout));
+        config.egress.check_url(&config.base_url)?;
+        let builder = config.egress.configure(builder);
+        let client = config
+            .default_headers
+            .configure(builder)
             .build()
             .map_err(|e| GatewayError::Configuration {
                 message: format!("Failed to build HTTP client: {e}"),
             })?;
+        let warmer = ConnectionWarmer::new(client.clone(), &config.base_url, &config.pool);
 
         Ok(Self {
             id: id.into(),
@@ -152,7 +206,10 @@ impl AnthropicProvider {
                 max_context_length: Some(200_000),
                 max_output_tokens: Some(8192),
                 parallel_tool_calls: true,
+                audio_output: false,
             },
+            rate_limits: DashMap::new(),
+            warmer,
         })
     }
 
@@ -186,30 +243,19 @@ impl AnthropicProvider {
         );
         headers
     }
-}
-
-#[async_trait::async_trait]
-impl LLMProvider for AnthropicProvider {
-    fn id(&self) -> &str {
-        &self.id
-    }
-
-    fn provider_type(&self) -> ProviderType {
-        ProviderType::Anthropic
-    }
-
-    #[instrument(skip(self, request), fields(provider = %self.id, model = %request.model))]
-    async fn chat_completion(&self, request: &GatewayRequest) -> Result<GatewayResponse, GatewayError> {
-        let anthropic_request = transform_request(request)?;
-        let url = self.api_url("/messages");
-
-        debug!(url = %url, "Sending chat completion request to Anthropic");
 
+    /// Send a messages request, mapping error statuses
+    async fn send_messages(
+        &self,
+        request: &GatewayRequest,
+        url: &str,
+        anthropic_request: &AnthropicRequest,
+    ) -> Result<reqwest::Response, GatewayError> {
         let response = self
             .client
-            .post(&url)
+            .post(url)
             .headers(self.build_headers())
-            .json(&anthropic_request)
+            .json(anthropic_request)
             .send()
             .await
             .map_err(|e| {
@@ -222,20 +268,53 @@ impl LLMProvider for AnthropicProvider {
                 }
             })?;
 
+        if let Some(limits) = ProviderRateLimits::from_headers(response.headers()) {
+            self.rate_limits.insert(request.model.clone(), limits);
+        }
+
         let status = response.status();
         if !status.is_success() {
             let error_body = response.text().await.unwrap_or_default();
-            return Err(parse_error_response(status, &error_body, &self.id));
+            let context_error = context_length::detect(
+                &self.id,
+                status.as_u16(),
+                &error_body,
+                request,
+                &self.config.models,
+            );
+            return Err(context_error
+                .unwrap_or_else(|| parse_error_response(status, &error_body, &self.id)));
         }
 
-        let anthropic_response: AnthropicResponse = response.json().await.map_err(|e| {
-            GatewayError::Provider {
-                provider: self.id.clone(),
-                message: format!("Failed to parse response: {e}"),
-                status_code: None,
-                retryable: false,
-            }
-        })?;
+        Ok(response)
+    }
+}
+
+#[async_trait::async_trait]
+impl LLMProvider for AnthropicProvider {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn provider_type(&self) -> ProviderType {
+        ProviderType::Anthropic
+    }
+
+    #[instrument(skip(self, request), fields(provider = %self.id, model = %request.model))]
+    async fn chat_completion(&self, request: &GatewayRequest) -> Result<GatewayResponse, GatewayError> {
+        let filtered = self.config.params.apply(&self.id, request)?;
+        let request = &*filtered;
+        let anthropic_request = transform_request(request)?;
+        let url = self.api_url("/messages");
+
+        debug!(url = %url, "Sending chat completion request to Anthropic");
+
+        let response = self.send_messages(request, &url, &anthropic_request).await?;
+
+        let anthropic_response: AnthropicResponse = response
+            .json()
+            .await
+            .map_err(|e| transport::body_error(&self.id, &e))?;
 
         transform_response(anthropic_response, &request.model)
     }
@@ -245,6 +324,8 @@ impl LLMProvider for AnthropicProvider {
         &self,
         request: &GatewayRequest,
     ) -> Result<BoxStream<'static, Result<ChatChunk, GatewayError>>, GatewayError> {
+        let filtered = self.config.params.apply(&self.id, request)?;
+        let request = &*filtered;
         let mut anthropic_request = transform_request(request)?;
         anthropic_request.stream = Some(true);
 
@@ -254,123 +335,99 @@ impl LLMProvider for AnthropicProvider {
 
         debug!(url = %url, "Starting streaming chat completion to Anthropic");
 
-        let request_builder = self
-            .client
-            .post(&url)
-            .headers(self.build_headers())
-            .json(&anthropic_request);
-
-        let event_source = EventSource::new(request_builder).map_err(|e| GatewayError::Provider {
-            provider: provider_id.clone(),
-            message: format!("Failed to create event source: {e}"),
-            status_code: None,
-            retryable: false,
-        })?;
+        let response = self.send_messages(request, &url, &anthropic_request).await?;
+        let mut events = Box::pin(transport::sse_events(&provider_id, response));
 
         let stream = try_stream! {
-            let mut es = event_source;
             let mut input_tokens = 0u32;
             let mut output_tokens = 0u32;
             let mut current_index = 0usize;
 
-            while let Some(event) = es.next().await {
-                match event {
-                    Ok(Event::Open) => {
-                        debug!("SSE connection opened");
-                    }
-                    Ok(Event::Message(msg)) => {
-                        // Skip ping events
-                        if msg.event == "ping" {
-                            continue;
-                        }
+            while let Some(msg) = events.next().await {
+                let msg = msg?;
+                let event = msg.event_type();
 
-                        // Handle message_start (contains input tokens)
-                        if msg.event == "message_start" {
-                            if let Ok(start) = serde_json::from_str::<MessageStartEvent>(&msg.data) {
-                                if let Some(usage) = start.message.usage {
-                                    input_tokens = usage.input_tokens.unwrap_or(0);
-                                }
-                            }
-                            continue;
-                        }
+                // Skip ping events
+                if event == "ping" {
+                    continue;
+                }
 
-                        // Handle message_delta (contains output tokens and stop reason)
-                        if msg.event == "message_delta" {
-                            if let Ok(delta) = serde_json::from_str::<MessageDeltaEvent>(&msg.data) {
-                                if let Some(usage) = delta.usage {
-                                    output_tokens = usage.output_tokens.unwrap_or(0);
-                                }
-                                if let Some(stop_reason) = delta.delta.stop_reason {
-                                    let chunk = ChatChunk::builder()
-                                        .id(format!("chunk-{}", uuid::Uuid::new_v4()))
-                                        .model(model.clone())
-                                        .choice(gateway_core::ChunkChoice::with_finish(
-                                            current_index as u32,
-                                            map_stop_reason(&stop_reason),
-                                        ))
-                                        .usage(Usage::new(input_tokens, output_tokens))
-                                        .build();
-                                    yield chunk;
-                                }
-                            }
-                            continue;
+                // Handle message_start (contains input tokens)
+                if event == "message_start" {
+                    if let Ok(start) = serde_json::from_str::<MessageStartEvent>(&msg.data) {
+                        if let Some(usage) = start.message.usage {
+                            input_tokens = usage.input_tokens.unwrap_or(0);
                         }
+                    }
+                    continue;
+                }
 
-                        // Handle content_block_start
-                        if msg.event == "content_block_start" {
-                            if let Ok(block) = serde_json::from_str::<ContentBlockStartEvent>(&msg.data) {
-                                current_index = block.index;
-                            }
-                            continue;
+                // Handle message_delta (contains output tokens and stop reason)
+                if event == "message_delta" {
+                    if let Ok(delta) = serde_json::from_str::<MessageDeltaEvent>(&msg.data) {
+                        if let Some(usage) = delta.usage {
+                            output_tokens = usage.output_tokens.unwrap_or(0);
                         }
-
-                        // Handle content_block_delta (the actual content)
-                        if msg.event == "content_block_delta" {
-                            if let Ok(delta) = serde_json::from_str::<ContentBlockDeltaEvent>(&msg.data) {
-                                if let Some(text) = delta.delta.text {
-                                    let chunk = ChatChunk::builder()
-                                        .id(format!("chunk-{}", uuid::Uuid::new_v4()))
-                                        .model(model.clone())
-                                        .choice(gateway_core::ChunkChoice::with_content(
-                                            current_index as u32,
-                                            text,
-                                        ))
-                                        .build();
-                                    yield chunk;
-                                }
-                            }
-                            continue;
+                        if let Some(stop_reason) = delta.delta.stop_reason {
+                            let chunk = ChatChunk::builder()
+                                .id(format!("chunk-{}", uuid::Uuid::new_v4()))
+                                .model(model.clone())
+                                .choice(gateway_core::ChunkChoice::with_finish(
+                                    current_index as u32,
+                                    map_stop_reason(&stop_reason),
+                                ))
+                                .usage(Usage::new(input_tokens, output_tokens))
+                                .build();
+                            yield chunk;
                         }
+                    }
+                    continue;
+                }
 
-                        // Handle message_stop
-                        if msg.event == "message_stop" {
-                            debug!("Received message_stop event");
-                            break;
-                        }
+                // Handle content_block_start
+                if event == "content_block_start" {
+                    if let Ok(block) = serde_json::from_str::<ContentBlockStartEvent>(&msg.data) {
+                        current_index = block.index;
+                    }
+                    continue;
+                }
 
-                        // Handle error events
-                        if msg.event == "error" {
-                            if let Ok(err) = serde_json::from_str::<StreamErrorEvent>(&msg.data) {
-                                Err(GatewayError::Provider {
-                                    provider: provider_id.clone(),
-                                    message: err.error.message,
-                                    status_code: None,
-                                    retryable: false,
-                                })?;
-                            }
+                // Handle content_block_delta (the actual content)
+                if event == "content_block_delta" {
+                    if let Ok(delta) = serde_json::from_str::<ContentBlockDeltaEvent>(&msg.data) {
+                        if let Some(text) = delta.delta.text {
+                            let chunk = ChatChunk::builder()
+                                .id(format!("chunk-{}", uuid::Uuid::new_v4()))
+                                .model(model.clone())
+                                .choice(gateway_core::ChunkChoice::with_content(
+                                    current_index as u32,
+                                    text,
+                                ))
+                                .build();
+                            yield chunk;
                         }
                     }
-                    Err(e) => {
-                        // Check if it's a normal close
-                        if matches!(e, reqwest_eventsource::Error::StreamEnded) {
-                            break;
-                        }
-                        error!(error = %e, "SSE error");
-                        Err(GatewayError::Provider {
-                            provider: provider_id.clone(),
-                            message: format!("Stream error: {e}"),
-                            status_code: None,
-                            retryable: false,
+                    continue;
+                }
+
+                // Handle message_stop
+                if event == "message_stop" {
+                    debug!("Received message_stop event");
+                    break;
+                }
+
+                // Handle error events
+                if event == "error" {
+                    if let Ok(err) = serde_json::from_str::<StreamErrorEvent>(&msg.data) {
+                        Err(if err.error.error_type == OVERLOADED_ERROR {
+                            overloaded_error(&provider_id, err.error.message)
+                        } else {
+                            GatewayError::Provider {
+                                provider: provider_id.clone(),
+                                message: err.error.message,
+                                status_code: None,
+                                retryable: false,
+                            }
                         })?;
                     }
                 }
@@ -414,6 +471,9 @@ impl LLMProvider for AnthropicProvider {
             Ok(response) => {
                 if response.status().is_success() {
                     HealthStatus::Healthy
+                } else if response.status().as_u16() == 529 {
+                    // Overloaded: up, but shedding load
+                    HealthStatus::Degraded
                 } else if response.status().is_server_error() {
                     HealthStatus::Unhealthy
                 } else {
@@ -443,6 +503,22 @@ impl LLMProvider for AnthropicProvider {
     fn base_url(&self) -> &str {
         &self.config.base_url
     }
+
+    fn rate_limits(&self, model: &str) -> Option<ProviderRateLimits> {
+        self.rate_limits.get(model).map(|limits| *limits)
+    }
+
+    async fn warm_connections(&self) -> Option<ConnectionPoolStats> {
+        Some(self.warmer.as_ref()?.warm().await)
+    }
+
+    fn connection_pool_stats(&self) -> Option<ConnectionPoolStats> {
+        self.warmer.as_ref().map(ConnectionWarmer::stats)
+    }
+
+    fn connection_keepalive_interval(&self) -> Option<Duration> {
+        self.warmer.as_ref().map(ConnectionWarmer::keepalive_interval)
+    }
 }
 
 // ============================================================================
@@ -563,6 +639,27 @@ enum AnthropicResponseContent {
 struct AnthropicUsage {
     input_tokens: u32,
     output_tokens: u32,
+    #[serde(default)]
+    cache_creation_input_tokens: Option<u32>,
+    #[serde(default)]
+    cache_read_input_tokens: Option<u32>,
+}
+
+impl AnthropicUsage {
+    /// Convert to gateway usage
+    ///
+    /// Anthropic reports cache reads and writes separately from
+    /// `input_tokens`; the gateway counts them all as prompt tokens.
+    fn into_usage(self) -> Usage {
+        let prompt_tokens = self.input_tokens
+            + self.cache_creation_input_tokens.unwrap_or(0)
+            + self.cache_read_input_tokens.unwrap_or(0);
+        let usage = Usage::new(prompt_tokens, self.output_tokens);
+        match self.cache_read_input_tokens {
+            Some(tokens) => usage.with_prompt_tokens_cached(tokens),
+            None => usage,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -576,7 +673,6 @@ struct AnthropicError {
 #[derive(Debug, Deserialize)]
 struct AnthropicErrorDetail {
     #[serde(rename = "type")]
-    #[allow(dead_code)]
     error_type: String,
     message: String,
 }
@@ -634,7 +730,7 @@ struct StreamErrorEvent {
 #[derive(Debug, Deserialize)]
 struct StreamError {
     #[serde(rename = "type")]
-    _type: String,
+    error_type: String,
     message: String,
 }
 
@@ -708,7 +804,7 @@ fn transform_request(request: &GatewayRequest) -> Result<AnthropicRequest, Gatew
     Ok(AnthropicRequest {
         model: request.model.clone(),
         messages,
-        max_tokens: request.max_tokens.unwrap_or(4096),
+        max_tokens: request.token_limit().unwrap_or(4096),
         system: system_message,
         temperature: request.temperature,
         top_p: request.top_p,
@@ -746,6 +842,11 @@ fn transform_content(content: &MessageContent) -> AnthropicContent {
                             None
                         }
                     }
+                    // Prior audio turns are sent as their transcript
+                    gateway_core::ContentPart::Audio { audio } => audio
+                        .transcript
+                        .clone()
+                        .map(|text| AnthropicContentBlock::Text { text }),
                 })
                 .collect();
             AnthropicContent::Blocks(blocks)
@@ -824,10 +925,7 @@ fn transform_response(
         .id(response.id)
         .model(model.to_string())
         .choice(choice)
-        .usage(Usage::new(
-            response.usage.input_tokens,
-            response.usage.output_tokens,
-        ))
+        .usage(response.usage.into_usage())
         .build();
 
     Ok(response)
@@ -844,16 +942,33 @@ fn map_stop_reason(reason: &str) -> FinishReason {
     }
 }
 
+/// Error type Anthropic reports when it is over capacity, with a 529
+/// status or as an `error` event in the middle of a stream
+const OVERLOADED_ERROR: &str = "overloaded_error";
+
+/// An overloaded error, always reported as a retryable 529 so it is
+/// classified as `ProviderErrorKind::Overloaded` whatever status it came with
+fn overloaded_error(provider_id: &str, message: String) -> GatewayError {
+    GatewayError::Provider {
+        provider: provider_id.to_string(),
+        message,
+        status_code: Some(529),
+        retryable: true,
+    }
+}
+
 /// Parse error response from Anthropic
 fn parse_error_response(
     status: reqwest::StatusCode,
     body: &str,
     provider_id: &str,
 ) -> GatewayError {
-    let message = if let Ok(err) = serde_json::from_str::<AnthropicError>(body) {
-        err.error.message
-    } else {
-        format!("HTTP {status}: {body}")
+    let message = match serde_json::from_str::<AnthropicError>(body) {
+        Ok(err) if err.error.error_type == OVERLOADED_ERROR => {
+            return overloaded_error(provider_id, err.error.message);
+        }
+        Ok(err) => err.error.message,
+        Err(_) => format!("HTTP {status}: {body}"),
     };
 
     match status.as_u16() {
@@ -886,6 +1001,7 @@ fn parse_error_response(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use gateway_core::ProviderErrorKind;
 
     #[test]
     fn test_config_builder() {
@@ -948,4 +1064,108 @@ mod tests {
         assert_eq!(media_type, "image/png");
         assert_eq!(data, "iVBORw0KGgoAAAANS");
     }
+
+    #[tokio::test]
+    async fn test_stream_events_with_crlf_framing() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let body = concat!(
+            "event: message_start\r\n",
+            "data: {\"type\":\"message_start\",\"message\":{\"usage\":{\"input_tokens\":9}}}\r\n\r\n",
+            ": keep-alive\r\n\r\n",
+            "event: ping\r\ndata: {\"type\":\"ping\"}\r\n\r\n",
+            "event: content_block_delta\r\n",
+            "data: {\"type\":\"content_block_delta\",\"index\":0,\r\n",
+            "data: \"delta\":{\"type\":\"text_delta\",\"text\":\"Hi\"}}\r\n\r\n",
+            "event: message_delta\r\n",
+            "data: {\"type\":\"message_delta\",\"delta\":{\"stop_reason\":\"end_turn\"},",
+            "\"usage\":{\"output_tokens\":2}}\r\n\r\n",
+            "event: message_stop\r\ndata: {\"type\":\"message_stop\"}\r\n\r\n",
+        );
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/messages"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "text/event-stream"))
+            .mount(&server)
+            .await;
+
+        let provider =
+            AnthropicProvider::new(AnthropicConfig::new("test-key").with_base_url(server.uri()))
+                .unwrap();
+        let request = GatewayRequest::builder()
+            .model("claude-3-5-sonnet-20241022")
+            .message(gateway_core::ChatMessage::user("Hello"))
+            .stream(true)
+            .build()
+            .unwrap();
+
+        let chunks: Vec<ChatChunk> = provider
+            .chat_completion_stream(&request)
+            .await
+            .unwrap()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].content(), Some("Hi"));
+        assert_eq!(chunks[1].finish_reason(), Some(FinishReason::Stop));
+        let usage = chunks[1].usage.as_ref().unwrap();
+        assert_eq!((usage.prompt_tokens, usage.completion_tokens), (9, 2));
+    }
+
+    #[test]
+    fn test_overloaded_error_is_classified_as_overloaded() {
+        let body = r#"{"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#;
+        for status in [529, 500] {
+            let status = reqwest::StatusCode::from_u16(status).unwrap();
+            let err = parse_error_response(status, body, "anthropic");
+            assert_eq!(err.provider_error_kind(), ProviderErrorKind::Overloaded);
+            assert!(err.is_retryable());
+            assert!(err.to_string().contains("Overloaded"));
+        }
+
+        let body = r#"{"type":"error","error":{"type":"api_error","message":"Internal"}}"#;
+        let err = parse_error_response(reqwest::StatusCode::INTERNAL_SERVER_ERROR, body, "anthropic");
+        assert_eq!(err.provider_error_kind(), ProviderErrorKind::ServerError);
+    }
+
+    #[tokio::test]
+    async fn test_overloaded_stream_error_event() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let body = concat!(
+            "event: message_start\n",
+            "data: {\"type\":\"message_start\",\"message\":{\"usage\":{\"input_tokens\":9}}}\n\n",
+            "event: error\n",
+            "data: {\"type\":\"error\",\"error\":{\"type\":\"overloaded_error\",\"message\":\"Overloaded\"}}\n\n",
+        );
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/messages"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "text/event-stream"))
+            .mount(&server)
+            .await;
+
+        let provider =
+            AnthropicProvider::new(AnthropicConfig::new("test-key").with_base_url(server.uri()))
+                .unwrap();
+        let request = GatewayRequest::builder()
+            .model("claude-3-5-sonnet-20241022")
+            .message(gateway_core::ChatMessage::user("Hello"))
+            .stream(true)
+            .build()
+            .unwrap();
+
+        let items: Vec<_> = provider
+            .chat_completion_stream(&request)
+            .await
+            .unwrap()
+            .collect()
+            .await;
+        let err = items.last().unwrap().as_ref().unwrap_err();
+        assert_eq!(err.provider_error_kind(), ProviderErrorKind::Overloaded);
+        assert!(err.is_retryable());
+    }
 }
@@ -13,16 +13,21 @@ use futures_util::StreamExt;
 use gateway_core::{
     ChatChunk, ChatMessage, Choice, ChunkChoice, ChunkDelta, FinishReason, FunctionCall,
     GatewayError, GatewayRequest, GatewayResponse, HealthStatus, LLMProvider, MessageContent,
-    MessageRole, ModelInfo, ProviderCapabilities, ProviderType, ToolCall, Usage,
+    MessageRole, ModelInfo, ProviderCapabilities, ProviderType, RequestMetadata, ToolCall, Usage,
+    is_reasoning_model,
 };
 use gateway_core::response::ResponseMessage;
+use gateway_core::streaming::{StreamOptions, ToolCallDelta};
 use reqwest::Client;
-use reqwest_eventsource::{Event, EventSource};
 use secrecy::{ExposeSecret, SecretString};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::time::Duration;
-use tracing::{debug, error, trace, warn};
+use crate::egress::EgressPolicy;
+use crate::headers::DefaultHeaders;
+use crate::params::ParamFilter;
+use crate::{context_length, transport};
+use tracing::{debug, error, warn};
 
 /// Azure OpenAI API version
 pub const DEFAULT_API_VERSION: &str = "2024-02-15-preview";
@@ -46,6 +51,12 @@ pub struct AzureOpenAIConfig {
     pub use_aad: bool,
     /// Custom domain (if using private endpoint)
     pub custom_domain: Option<String>,
+    /// Headers sent on every request
+    pub default_headers: DefaultHeaders,
+    /// Hosts and networks the client may connect to
+    pub egress: EgressPolicy,
+    /// Request parameters the provider may receive
+    pub params: ParamFilter,
 }
 
 impl AzureOpenAIConfig {
@@ -65,6 +76,9 @@ impl AzureOpenAIConfig {
             deployments: HashMap::new(),
             use_aad: false,
             custom_domain: None,
+            default_headers: DefaultHeaders::default(),
+            egress: EgressPolicy::default(),
+            params: ParamFilter::default(),
         }
     }
 
@@ -89,6 +103,16 @@ impl AzureOpenAIConfig {
         self
     }
 
+    /// Serve a gateway model ID from an Azure deployment
+    ///
+    /// Requests for `model` are sent to `deployment`, and `model` is what
+    /// the provider lists and reports back. Replaces any model already
+    /// mapped to that deployment.
+    #[must_use]
+    pub fn add_deployment(self, model: impl Into<String>, deployment: impl Into<String>) -> Self {
+        self.with_deployment(deployment, ModelInfo::new(model))
+    }
+
     /// Add multiple deployments
     #[must_use]
     pub fn with_deployments(mut self, deployments: HashMap<String, ModelInfo>) -> Self {
@@ -97,6 +121,9 @@ impl AzureOpenAIConfig {
     }
 
     /// Set custom domain (for private endpoints)
+    ///
+    /// A bare host is reached over HTTPS; a value with a scheme is used as
+    /// the base URL as is.
     #[must_use]
     pub fn with_custom_domain(mut self, domain: impl Into<String>) -> Self {
         self.custom_domain = Some(domain.into());
@@ -110,11 +137,36 @@ impl AzureOpenAIConfig {
         self
     }
 
+    /// Set headers sent on every request
+    #[must_use]
+    pub fn with_default_headers(mut self, headers: DefaultHeaders) -> Self {
+        self.default_headers = headers;
+        self
+    }
+
+    /// Set the hosts and networks the client may connect to
+    #[must_use]
+    pub fn with_egress(mut self, egress: EgressPolicy) -> Self {
+        self.egress = egress;
+        self
+    }
+
+    /// Set the request parameters the provider may receive
+    #[must_use]
+    pub fn with_params(mut self, params: ParamFilter) -> Self {
+        self.params = params;
+        self
+    }
+
     /// Get the base URL for the Azure OpenAI resource
     #[must_use]
     pub fn base_url(&self) -> String {
         if let Some(ref domain) = self.custom_domain {
-            format!("https://{domain}")
+            if domain.contains("://") {
+                domain.trim_end_matches('/').to_string()
+            } else {
+                format!("https://{domain}")
+            }
         } else {
             format!("https://{}.openai.azure.com", self.resource_name)
         }
@@ -200,9 +252,14 @@ impl AzureOpenAIProvider {
             });
         }
 
-        let client = Client::builder()
+        let builder = Client::builder()
             .timeout(config.timeout)
-            .pool_max_idle_per_host(100)
+            .pool_max_idle_per_host(100);
+        config.egress.check_url(&config.base_url())?;
+        let builder = config.egress.configure(builder);
+        let client = config
+            .default_headers
+            .configure(builder)
             .build()
             .map_err(|e| GatewayError::internal(format!("Failed to create HTTP client: {e}")))?;
 
@@ -224,6 +281,7 @@ impl AzureOpenAIProvider {
                 max_context_length: Some(128_000),
                 max_output_tokens: Some(16_384),
                 parallel_tool_calls: true,
+                audio_output: false,
             },
             models,
             base_url_string,
@@ -263,15 +321,24 @@ impl AzureOpenAIProvider {
             .map(AzureMessage::from_gateway_message)
             .collect();
 
+        // Reasoning models reject `max_tokens`; others expect it
+        let (max_tokens, max_completion_tokens) = if is_reasoning_model(&request.model) {
+            (None, request.token_limit())
+        } else {
+            (request.token_limit(), None)
+        };
+
         AzureOpenAIRequest {
             messages,
             temperature: request.temperature,
-            max_tokens: request.max_tokens,
+            max_tokens,
+            max_completion_tokens,
             top_p: request.top_p,
             frequency_penalty: request.frequency_penalty,
             presence_penalty: request.presence_penalty,
             stop: request.stop.clone(),
             stream: Some(request.stream),
+            stream_options: None,
             n: request.n,
             seed: request.seed,
             user: request.user.clone(),
@@ -304,11 +371,16 @@ impl AzureOpenAIProvider {
             response_format: request.response_format.as_ref().map(|rf| {
                 serde_json::json!({"type": rf.format_type})
             }),
+            store: request.store,
+            metadata: request
+                .metadata
+                .as_ref()
+                .and_then(RequestMetadata::provider_metadata),
         }
     }
 
     /// Transform Azure response to gateway format
-    fn transform_response(&self, response: AzureResponse, deployment: &str) -> GatewayResponse {
+    fn transform_response(&self, response: AzureResponse, model: &str) -> GatewayResponse {
         let choices: Vec<Choice> = response
             .choices
             .into_iter()
@@ -334,6 +406,7 @@ impl AzureOpenAIProvider {
                         name: fc.name,
                         arguments: fc.arguments.unwrap_or_default(),
                     }),
+                    audio: None,
                 },
                 finish_reason: c.finish_reason.and_then(|r| match r.as_str() {
                     "stop" => Some(FinishReason::Stop),
@@ -350,14 +423,11 @@ impl AzureOpenAIProvider {
             id: response.id,
             object: "chat.completion".to_string(),
             created: response.created as i64,
-            model: deployment.to_string(),
+            model: model.to_string(),
             choices,
-            usage: Usage {
-                prompt_tokens: response.usage.prompt_tokens,
-                completion_tokens: response.usage.completion_tokens,
-                total_tokens: response.usage.total_tokens,
-            },
+            usage: response.usage.into_usage(),
             system_fingerprint: response.system_fingerprint,
+            service_tier: None,
             provider: Some(self.config.id.clone()),
         }
     }
@@ -397,6 +467,53 @@ impl AzureOpenAIProvider {
             ),
         }
     }
+
+    /// Send a chat completion request, mapping error statuses
+    async fn send_completion(
+        &self,
+        request: &GatewayRequest,
+        url: &str,
+        azure_request: &AzureOpenAIRequest,
+    ) -> Result<reqwest::Response, GatewayError> {
+        let response = self
+            .client
+            .post(url)
+            .header("api-key", self.config.api_key.expose_secret())
+            .header("Content-Type", "application/json")
+            .json(azure_request)
+            .send()
+            .await
+            .map_err(|e| GatewayError::provider(&self.config.id, format!("Request failed: {e}"), None, true))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+
+            if let Some(err) = context_length::detect(
+                &self.config.id,
+                status.as_u16(),
+                &body,
+                request,
+                &self.models,
+            ) {
+                return Err(err);
+            }
+
+            // Parse Azure error
+            if let Ok(error) = serde_json::from_str::<AzureErrorResponse>(&body) {
+                return Err(self.map_azure_error(status.as_u16(), &error));
+            }
+
+            return Err(GatewayError::provider(
+                &self.config.id,
+                format!("Azure OpenAI API error: {} - {}", status, body),
+                Some(status.as_u16()),
+                status.is_server_error(),
+            ));
+        }
+
+        Ok(response)
+    }
 }
 
 #[async_trait]
@@ -476,6 +593,8 @@ impl LLMProvider for AzureOpenAIProvider {
         })?;
 
         let url = self.completions_url(&deployment);
+        let filtered = self.config.params.apply(&self.config.id, request)?;
+        let request = &*filtered;
         let azure_request = self.transform_request(request);
 
         debug!(
@@ -484,39 +603,14 @@ impl LLMProvider for AzureOpenAIProvider {
             "Sending request to Azure OpenAI"
         );
 
-        let response = self
-            .client
-            .post(&url)
-            .header("api-key", self.config.api_key.expose_secret())
-            .header("Content-Type", "application/json")
-            .json(&azure_request)
-            .send()
-            .await
-            .map_err(|e| GatewayError::provider(&self.config.id, format!("Request failed: {e}"), None, true))?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-
-            // Parse Azure error
-            if let Ok(error) = serde_json::from_str::<AzureErrorResponse>(&body) {
-                return Err(self.map_azure_error(status.as_u16(), &error));
-            }
-
-            return Err(GatewayError::provider(
-                &self.config.id,
-                format!("Azure OpenAI API error: {} - {}", status, body),
-                Some(status.as_u16()),
-                status.is_server_error(),
-            ));
-        }
+        let response = self.send_completion(request, &url, &azure_request).await?;
 
         let azure_response: AzureResponse = response
             .json()
             .await
-            .map_err(|e| GatewayError::provider(&self.config.id, format!("Failed to parse response: {e}"), None, false))?;
+            .map_err(|e| transport::body_error(&self.config.id, &e))?;
 
-        Ok(self.transform_response(azure_response, &deployment))
+        Ok(self.transform_response(azure_response, &request.model))
     }
 
     async fn chat_completion_stream(
@@ -531,94 +625,81 @@ impl LLMProvider for AzureOpenAIProvider {
         })?;
 
         let url = self.completions_url(&deployment);
+        let filtered = self.config.params.apply(&self.config.id, request)?;
+        let request = &*filtered;
         let mut azure_request = self.transform_request(request);
         azure_request.stream = Some(true);
+        azure_request.stream_options = request.stream_options.clone();
 
         debug!(
             deployment = %deployment,
             "Starting streaming request to Azure OpenAI"
         );
 
-        let request_builder = self
-            .client
-            .post(&url)
-            .header("api-key", self.config.api_key.expose_secret())
-            .header("Content-Type", "application/json")
-            .json(&azure_request);
-
-        let event_source = EventSource::new(request_builder)
-            .map_err(|e| GatewayError::provider(&self.config.id, format!("Failed to create event source: {e}"), None, true))?;
-
-        let deployment_owned = deployment.to_string();
+        let response = self.send_completion(request, &url, &azure_request).await?;
+        let model = request.model.clone();
         let provider_id = self.config.id.clone();
+        let mut events = Box::pin(transport::sse_events(&self.config.id, response));
 
         let stream = try_stream! {
-            let mut es = event_source;
-
-            while let Some(event) = es.next().await {
-                match event {
-                    Ok(Event::Open) => {
-                        trace!("Azure OpenAI stream opened");
-                    }
-                    Ok(Event::Message(msg)) => {
-                        let data = msg.data.trim();
+            while let Some(event) = events.next().await {
+                let event = event?;
+                let data = event.data.trim();
 
-                        // Check for stream end
-                        if data == "[DONE]" {
-                            break;
-                        }
+                // Check for stream end
+                if data == "[DONE]" {
+                    break;
+                }
 
-                        // Parse chunk
-                        match serde_json::from_str::<AzureChunk>(data) {
-                            Ok(chunk) => {
-                                let gateway_chunk = ChatChunk {
-                                    id: chunk.id,
-                                    object: "chat.completion.chunk".to_string(),
-                                    created: chunk.created as i64,
-                                    model: deployment_owned.clone(),
-                                    choices: chunk.choices.into_iter().map(|c| ChunkChoice {
-                                        index: c.index,
-                                        delta: ChunkDelta {
-                                            role: c.delta.role.and_then(|r| match r.as_str() {
-                                                "assistant" => Some(MessageRole::Assistant),
-                                                "user" => Some(MessageRole::User),
-                                                "system" => Some(MessageRole::System),
-                                                _ => None,
-                                            }),
-                                            content: c.delta.content,
-                                            tool_calls: None,
-                                            function_call: None,
-                                        },
-                                        finish_reason: c.finish_reason.and_then(|r| match r.as_str() {
-                                            "stop" => Some(FinishReason::Stop),
-                                            "length" => Some(FinishReason::Length),
-                                            "tool_calls" | "function_call" => Some(FinishReason::ToolCalls),
-                                            "content_filter" => Some(FinishReason::ContentFilter),
-                                            _ => None,
-                                        }),
-                                        logprobs: None,
-                                    }).collect(),
-                                    system_fingerprint: chunk.system_fingerprint,
-                                    usage: None,
-                                };
-                                yield gateway_chunk;
-                            }
-                            Err(e) => {
-                                warn!(error = %e, data = %data, "Failed to parse Azure chunk");
-                            }
-                        }
+                // Parse chunk
+                match serde_json::from_str::<AzureChunk>(data) {
+                    Ok(chunk) => {
+                        let gateway_chunk = ChatChunk {
+                            id: chunk.id,
+                            object: "chat.completion.chunk".to_string(),
+                            created: chunk.created as i64,
+                            model: model.clone(),
+                            choices: chunk.choices.into_iter().map(|c| ChunkChoice {
+                                index: c.index,
+                                delta: ChunkDelta {
+                                    role: c.delta.role.and_then(|r| match r.as_str() {
+                                        "assistant" => Some(MessageRole::Assistant),
+                                        "user" => Some(MessageRole::User),
+                                        "system" => Some(MessageRole::System),
+                                        _ => None,
+                                    }),
+                                    content: c.delta.content,
+                                    tool_calls: c.delta.tool_calls,
+                                    function_call: None,
+                                },
+                                finish_reason: c.finish_reason.and_then(|r| match r.as_str() {
+                                    "stop" => Some(FinishReason::Stop),
+                                    "length" => Some(FinishReason::Length),
+                                    "tool_calls" | "function_call" => Some(FinishReason::ToolCalls),
+                                    "content_filter" => Some(FinishReason::ContentFilter),
+                                    _ => None,
+                                }),
+                                logprobs: None,
+                            }).collect(),
+                            system_fingerprint: chunk.system_fingerprint,
+                            usage: chunk.usage.map(AzureUsage::into_usage),
+                        };
+                        yield gateway_chunk;
                     }
                     Err(e) => {
-                        error!(error = %e, "Azure OpenAI stream error");
-                        Err(GatewayError::streaming(format!("Stream error: {e}")))?;
+                        // Failures after the response starts arrive as an error payload
+                        if let Ok(error) = serde_json::from_str::<AzureErrorResponse>(data) {
+                            Err(GatewayError::streaming(format!(
+                                "{provider_id}: {}",
+                                error.error.message
+                            )))?;
+                        }
+                        warn!(error = %e, data = %data, "Failed to parse Azure chunk");
                     }
                 }
             }
         };
 
-        // Silence unused variable warning
-        let _ = provider_id;
-
         Ok(Box::pin(stream))
     }
 }
@@ -635,6 +716,8 @@ struct AzureOpenAIRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     max_tokens: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    max_completion_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     top_p: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     frequency_penalty: Option<f32>,
@@ -645,6 +728,8 @@ struct AzureOpenAIRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     stream: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    stream_options: Option<StreamOptions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     n: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     seed: Option<i64>,
@@ -656,6 +741,10 @@ struct AzureOpenAIRequest {
     tool_choice: Option<serde_json::Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     response_format: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    store: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    metadata: Option<BTreeMap<String, String>>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -703,6 +792,12 @@ impl AzureMessage {
                                 },
                             }
                         }
+                        // Prior audio turns are sent as their transcript
+                        gateway_core::request::ContentPart::Audio { audio } => {
+                            AzureContentPart::Text {
+                                text: audio.transcript.clone().unwrap_or_default(),
+                            }
+                        }
                     })
                     .collect();
                 Some(AzureContent::Parts(azure_parts))
@@ -818,6 +913,36 @@ struct AzureUsage {
     prompt_tokens: u32,
     completion_tokens: u32,
     total_tokens: u32,
+    #[serde(default)]
+    prompt_tokens_details: Option<AzurePromptTokensDetails>,
+    #[serde(default)]
+    completion_tokens_details: Option<AzureCompletionTokensDetails>,
+}
+
+impl AzureUsage {
+    fn into_usage(self) -> Usage {
+        Usage {
+            prompt_tokens: self.prompt_tokens,
+            completion_tokens: self.completion_tokens,
+            total_tokens: self.total_tokens,
+            prompt_tokens_cached: self.prompt_tokens_details.and_then(|d| d.cached_tokens),
+            completion_tokens_reasoning: self
+                .completion_tokens_details
+                .and_then(|d| d.reasoning_tokens),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct AzurePromptTokensDetails {
+    #[serde(default)]
+    cached_tokens: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AzureCompletionTokensDetails {
+    #[serde(default)]
+    reasoning_tokens: Option<u32>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -827,6 +952,9 @@ struct AzureChunk {
     choices: Vec<AzureChunkChoice>,
     #[serde(default)]
     system_fingerprint: Option<String>,
+    /// Only present on the final chunk when `stream_options.include_usage` is set
+    #[serde(default)]
+    usage: Option<AzureUsage>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -840,6 +968,8 @@ struct AzureChunkChoice {
 struct AzureChunkDelta {
     role: Option<String>,
     content: Option<String>,
+    #[serde(default)]
+    tool_calls: Option<Vec<ToolCallDelta>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -1020,6 +1150,28 @@ mod tests {
         assert!(matches!(azure_msg.content, Some(AzureContent::String(_))));
     }
 
+    #[test]
+    fn test_store_and_metadata_forwarded() {
+        let (name, model) = AzureOpenAIConfig::gpt4_deployment("my-gpt4");
+        let config = AzureOpenAIConfig::new("azure-1", "my-resource", "test-key")
+            .with_deployment(name, model);
+        let provider = AzureOpenAIProvider::new(config).unwrap();
+        let request = GatewayRequest::builder()
+            .model("gpt-4")
+            .message(ChatMessage::user("Hello"))
+            .store(false)
+            .metadata(RequestMetadata {
+                tags: HashMap::from([("team".to_string(), "search".to_string())]),
+                ..RequestMetadata::default()
+            })
+            .build()
+            .unwrap();
+
+        let body = serde_json::to_value(provider.transform_request(&request)).unwrap();
+        assert_eq!(body["store"], false);
+        assert_eq!(body["metadata"], serde_json::json!({"team": "search"}));
+    }
+
     #[test]
     fn test_base_url_method() {
         let (name, model) = AzureOpenAIConfig::gpt4_deployment("my-gpt4");
@@ -1029,4 +1181,117 @@ mod tests {
 
         assert!(provider.base_url().contains("my-resource.openai.azure.com"));
     }
+
+    fn mapped_provider(base_url: &str) -> AzureOpenAIProvider {
+        let config = AzureOpenAIConfig::new("azure-1", "my-resource", "test-key")
+            .with_custom_domain(base_url)
+            .add_deployment("gpt-4o", "prod-gpt4o-eastus")
+            .add_deployment("gpt-4o-mini", "prod-mini");
+        AzureOpenAIProvider::new(config).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_model_ids_resolve_to_deployments() {
+        let provider = mapped_provider("my-private-endpoint.example.com");
+
+        assert_eq!(
+            provider.find_deployment("gpt-4o"),
+            Some("prod-gpt4o-eastus".to_string())
+        );
+        let mut listed: Vec<&str> = provider.models().iter().map(|m| m.id.as_str()).collect();
+        listed.sort_unstable();
+        assert_eq!(listed, ["gpt-4o", "gpt-4o-mini"]);
+
+        let request = GatewayRequest::builder()
+            .model("gpt-4-turbo")
+            .message(ChatMessage::user("hi"))
+            .build()
+            .unwrap();
+        let err = provider.chat_completion(&request).await.unwrap_err();
+        assert!(matches!(err, GatewayError::ModelNotFound { .. }));
+        let err = provider.chat_completion_stream(&request).await.err().unwrap();
+        assert!(matches!(err, GatewayError::ModelNotFound { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_stream_from_mapped_deployment() {
+        use wiremock::matchers::{body_partial_json, method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let sse = concat!(
+            "data: {\"id\":\"\",\"created\":0,\"choices\":[],\"prompt_filter_results\":[]}\n\n",
+            "data: {\"id\":\"c1\",\"created\":1,\"choices\":[{\"index\":0,\"delta\":{\"role\":\"assistant\",",
+            "\"tool_calls\":[{\"index\":0,\"id\":\"call_1\",\"type\":\"function\",",
+            "\"function\":{\"name\":\"lookup\",\"arguments\":\"{}\"}}]},\"finish_reason\":null}]}\n\n",
+            "data: {\"id\":\"c1\",\"created\":1,\"choices\":[{\"index\":0,\"delta\":{\"content\":\"hi\"},",
+            "\"finish_reason\":\"stop\"}]}\n\n",
+            "data: [DONE]\n\n",
+        );
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/openai/deployments/prod-gpt4o-eastus/chat/completions"))
+            .and(query_param("api-version", DEFAULT_API_VERSION))
+            .and(body_partial_json(serde_json::json!({"stream": true})))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(sse, "text/event-stream"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mapped_provider(&server.uri());
+        let request = GatewayRequest::builder()
+            .model("gpt-4o")
+            .message(ChatMessage::user("hi"))
+            .stream(true)
+            .build()
+            .unwrap();
+        let chunks: Vec<ChatChunk> = provider
+            .chat_completion_stream(&request)
+            .await
+            .unwrap()
+            .map(|chunk| chunk.unwrap())
+            .collect()
+            .await;
+
+        assert_eq!(chunks.len(), 3);
+        assert!(chunks.iter().all(|c| c.model == "gpt-4o"));
+        let tool_calls = chunks[1].choices[0].delta.tool_calls.as_ref().unwrap();
+        assert_eq!(tool_calls[0].id.as_deref(), Some("call_1"));
+        assert_eq!(chunks[2].choices[0].delta.content.as_deref(), Some("hi"));
+        assert_eq!(chunks[2].choices[0].finish_reason, Some(FinishReason::Stop));
+    }
+
+    #[tokio::test]
+    async fn test_stream_error_payload_is_surfaced() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let sse = concat!(
+            "data: {\"id\":\"c1\",\"created\":1,\"choices\":[{\"index\":0,\"delta\":{\"content\":\"hi\"},",
+            "\"finish_reason\":null}]}\n\n",
+            "data: {\"error\":{\"message\":\"The server had an error\",\"code\":\"server_error\"}}\n\n",
+        );
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(sse, "text/event-stream"))
+            .mount(&server)
+            .await;
+
+        let provider = mapped_provider(&server.uri());
+        let request = GatewayRequest::builder()
+            .model("gpt-4o-mini")
+            .message(ChatMessage::user("hi"))
+            .stream(true)
+            .build()
+            .unwrap();
+        let results: Vec<_> = provider
+            .chat_completion_stream(&request)
+            .await
+            .unwrap()
+            .collect()
+            .await;
+
+        assert!(results[0].is_ok());
+        let err = results[1].as_ref().unwrap_err();
+        assert!(err.to_string().contains("The server had an error"));
+    }
 }
@@ -9,13 +9,26 @@
 //! - Meta Llama models
 //! - Mistral models
 //!
+//! ## Converse API
+//!
+//! With [`BedrockConfigBuilder::use_converse`] enabled, models that support
+//! it are served through the unified Converse/ConverseStream API, which
+//! takes one message and tool format for every model family. Other models
+//! fall back to the per-family invoke transforms.
+//!
 //! ## Authentication
 //!
 //! AWS Bedrock uses AWS Signature Version 4 authentication.
 //! Credentials can be provided via:
-//! - Environment variables (AWS_ACCESS_KEY_ID, AWS_SECRET_ACCESS_KEY)
-//! - IAM roles (for EC2/ECS/Lambda)
-//! - Instance profile credentials
+//! - Static keys in the configuration
+//! - [`BedrockConfigBuilder::use_default_credential_chain`], which resolves
+//!   them at request time from environment variables (AWS_ACCESS_KEY_ID,
+//!   AWS_SECRET_ACCESS_KEY, AWS_SESSION_TOKEN), the ECS/EKS container
+//!   credential endpoint, or the EC2 instance metadata service (IMDSv2)
+//! - A custom [`CredentialProvider`]
+//!
+//! Temporary credentials are cached until shortly before they expire, and
+//! their session token is sent as `x-amz-security-token`.
 //!
 //! ## Example
 //!
@@ -34,22 +47,31 @@
 
 use async_stream::try_stream;
 use async_trait::async_trait;
+use base64::prelude::{Engine as _, BASE64_STANDARD};
 use futures::stream::BoxStream;
+use futures::StreamExt;
 use gateway_core::{
-    ChatChunk, ChatMessage, Choice, ChunkChoice, ChunkDelta, FinishReason,
+    ChatChunk, ChatMessage, Choice, ChunkChoice, ChunkDelta, FinishReason, FunctionCall,
     GatewayError, GatewayRequest, GatewayResponse, HealthStatus, LLMProvider, MessageContent,
-    MessageRole, ModelInfo, ProviderCapabilities, ProviderType, Usage,
+    MessageRole, ModelInfo, ProviderCapabilities, ProviderType, ToolCall, ToolChoice, Usage,
 };
 use gateway_core::request::ContentPart;
 use gateway_core::response::ResponseMessage;
+use gateway_core::streaming::{FunctionCallDelta, ToolCallDelta};
 use reqwest::Client;
 use serde::Deserialize;
 use std::{
     collections::HashMap,
+    sync::Arc,
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 use tracing::{debug, warn};
 
+use crate::context_length;
+use crate::egress::EgressPolicy;
+use crate::headers::DefaultHeaders;
+use crate::params::ParamFilter;
+
 /// AWS Bedrock configuration
 #[derive(Debug, Clone)]
 pub struct BedrockConfig {
@@ -63,12 +85,25 @@ pub struct BedrockConfig {
     pub secret_access_key: Option<String>,
     /// AWS session token (optional - for temporary credentials)
     pub session_token: Option<String>,
+    /// Resolve credentials from the environment, the container credential
+    /// endpoint, or the instance metadata service when no keys are set
+    pub use_default_credential_chain: bool,
+    /// Custom credential source, used when no keys are set
+    pub credential_provider: Option<Arc<dyn CredentialProvider>>,
     /// Custom endpoint URL (for testing/VPC endpoints)
     pub endpoint_url: Option<String>,
     /// Request timeout
     pub timeout: Duration,
     /// Supported models
     pub models: Vec<ModelInfo>,
+    /// Use the unified Converse API for models that support it
+    pub use_converse: bool,
+    /// Headers sent on every request
+    pub default_headers: DefaultHeaders,
+    /// Hosts and networks the client may connect to
+    pub egress: EgressPolicy,
+    /// Request parameters the provider may receive
+    pub params: ParamFilter,
 }
 
 impl BedrockConfig {
@@ -84,6 +119,12 @@ impl BedrockConfig {
             .unwrap_or_else(|| format!("https://bedrock-runtime.{}.amazonaws.com", self.region))
     }
 
+    /// Whether requests for `model_id` go through the Converse API
+    #[must_use]
+    pub fn uses_converse(&self, model_id: &str) -> bool {
+        self.use_converse && supports_converse(model_id)
+    }
+
     /// Default Bedrock models
     #[must_use]
     pub fn default_models() -> Vec<ModelInfo> {
@@ -144,9 +185,15 @@ pub struct BedrockConfigBuilder {
     access_key_id: Option<String>,
     secret_access_key: Option<String>,
     session_token: Option<String>,
+    use_default_credential_chain: bool,
+    credential_provider: Option<Arc<dyn CredentialProvider>>,
     endpoint_url: Option<String>,
     timeout: Option<Duration>,
     models: Option<Vec<ModelInfo>>,
+    use_converse: bool,
+    default_headers: DefaultHeaders,
+    egress: EgressPolicy,
+    params: ParamFilter,
 }
 
 impl BedrockConfigBuilder {
@@ -180,6 +227,23 @@ impl BedrockConfigBuilder {
         self
     }
 
+    /// Resolve credentials at request time from the environment, the
+    /// ECS/EKS container credential endpoint, or the EC2 instance metadata
+    /// service (IMDSv2), when no keys are configured
+    #[must_use]
+    pub fn use_default_credential_chain(mut self, enabled: bool) -> Self {
+        self.use_default_credential_chain = enabled;
+        self
+    }
+
+    /// Resolve credentials at request time from a custom source, when no
+    /// keys are configured
+    #[must_use]
+    pub fn credential_provider(mut self, provider: Arc<dyn CredentialProvider>) -> Self {
+        self.credential_provider = Some(provider);
+        self
+    }
+
     /// Set custom endpoint URL
     pub fn endpoint_url(mut self, url: impl Into<String>) -> Self {
         self.endpoint_url = Some(url.into());
@@ -198,6 +262,36 @@ impl BedrockConfigBuilder {
         self
     }
 
+    /// Use the Converse API, falling back to invoke for unsupported models
+    #[must_use]
+    pub fn use_converse(mut self, enabled: bool) -> Self {
+        self.use_converse = enabled;
+        self
+    }
+
+    /// Set headers sent on every request
+    ///
+    /// These headers are not part of the request signature.
+    #[must_use]
+    pub fn default_headers(mut self, headers: DefaultHeaders) -> Self {
+        self.default_headers = headers;
+        self
+    }
+
+    /// Set the hosts and networks the client may connect to
+    #[must_use]
+    pub fn egress(mut self, egress: EgressPolicy) -> Self {
+        self.egress = egress;
+        self
+    }
+
+    /// Set the request parameters the provider may receive
+    #[must_use]
+    pub fn params(mut self, params: ParamFilter) -> Self {
+        self.params = params;
+        self
+    }
+
     /// Build the configuration
     pub fn build(self) -> BedrockConfig {
         BedrockConfig {
@@ -206,9 +300,15 @@ impl BedrockConfigBuilder {
             access_key_id: self.access_key_id,
             secret_access_key: self.secret_access_key,
             session_token: self.session_token,
+            use_default_credential_chain: self.use_default_credential_chain,
+            credential_provider: self.credential_provider,
             endpoint_url: self.endpoint_url,
             timeout: self.timeout.unwrap_or(Duration::from_secs(300)),
             models: self.models.unwrap_or_else(BedrockConfig::default_models),
+            use_converse: self.use_converse,
+            default_headers: self.default_headers,
+            egress: self.egress,
+            params: self.params,
         }
     }
 }
@@ -251,12 +351,31 @@ impl ModelFamily {
     }
 }
 
+/// Whether Bedrock serves `model_id` through the Converse API
+///
+/// Legacy text-completion models (Cohere Command, AI21 Jurassic) are only
+/// reachable through invoke. Amazon Nova models have no invoke transform
+/// here and are only served through Converse.
+#[must_use]
+pub fn supports_converse(model_id: &str) -> bool {
+    match ModelFamily::from_model_id(model_id) {
+        Some(ModelFamily::Claude | ModelFamily::Titan | ModelFamily::Llama | ModelFamily::Mistral) => {
+            true
+        }
+        Some(ModelFamily::Cohere) => model_id.starts_with("cohere.command-r"),
+        Some(ModelFamily::Ai21) => model_id.starts_with("ai21.jamba"),
+        None => model_id.starts_with("amazon.nova"),
+    }
+}
+
 /// AWS Bedrock provider
 pub struct BedrockProvider {
     config: BedrockConfig,
     client: Client,
     capabilities: ProviderCapabilities,
     base_url: String,
+    /// Signing credentials, if any source is configured
+    credentials: Option<Arc<dyn CredentialProvider>>,
 }
 
 impl std::fmt::Debug for BedrockProvider {
@@ -271,13 +390,35 @@ impl std::fmt::Debug for BedrockProvider {
 impl BedrockProvider {
     /// Create a new Bedrock provider
     pub fn new(config: BedrockConfig) -> Result<Self, GatewayError> {
-        let client = Client::builder()
-            .timeout(config.timeout)
+        let builder = Client::builder().timeout(config.timeout);
+        config.egress.check_url(&config.base_url())?;
+        let builder = config.egress.configure(builder);
+        let client = config
+            .default_headers
+            .configure(builder)
             .build()
             .map_err(|e| GatewayError::internal(format!("Failed to create HTTP client: {}", e)))?;
 
         let base_url = config.base_url();
 
+        // Configured keys take precedence over any other source
+        let credentials: Option<Arc<dyn CredentialProvider>> =
+            match (&config.access_key_id, &config.secret_access_key) {
+                (Some(access_key_id), Some(secret_access_key)) => {
+                    Some(Arc::new(StaticCredentials(AwsCredentials {
+                        access_key_id: access_key_id.clone(),
+                        secret_access_key: secret_access_key.clone(),
+                        session_token: config.session_token.clone(),
+                        expires_at: None,
+                    })))
+                }
+                _ => config.credential_provider.clone().or_else(|| {
+                    config.use_default_credential_chain.then(|| {
+                        Arc::new(DefaultCredentialChain::from_env()) as Arc<dyn CredentialProvider>
+                    })
+                }),
+            };
+
         Ok(Self {
             config,
             client,
@@ -293,8 +434,10 @@ impl BedrockProvider {
                 max_context_length: Some(200_000),
                 max_output_tokens: Some(8192),
                 parallel_tool_calls: false,
+                audio_output: false,
             },
             base_url,
+            credentials,
         })
     }
 
@@ -312,6 +455,190 @@ impl BedrockProvider {
         )
     }
 
+    /// Get the Converse URL for a model
+    fn converse_url(&self, model_id: &str) -> String {
+        format!("{}/model/{}/converse", self.base_url, model_id)
+    }
+
+    /// Get the ConverseStream URL for a model
+    fn converse_stream_url(&self, model_id: &str) -> String {
+        format!("{}/model/{}/converse-stream", self.base_url, model_id)
+    }
+
+    /// Convert gateway request to the unified Converse format
+    fn transform_converse_request(request: &GatewayRequest) -> serde_json::Value {
+        let mut system = Vec::new();
+        let mut messages: Vec<(&str, Vec<serde_json::Value>)> = Vec::new();
+
+        for msg in &request.messages {
+            let (role, blocks) = match msg.role {
+                MessageRole::System => {
+                    system.push(serde_json::json!({
+                        "text": Self::extract_text_content(&msg.content)
+                    }));
+                    continue;
+                }
+                MessageRole::User => ("user", Self::converse_content(&msg.content)),
+                MessageRole::Assistant => {
+                    let mut blocks: Vec<serde_json::Value> = Self::converse_content(&msg.content)
+                        .into_iter()
+                        .filter(|block| block["text"].as_str() != Some(""))
+                        .collect();
+                    for call in msg.tool_calls.iter().flatten() {
+                        let input = serde_json::from_str(&call.function.arguments)
+                            .unwrap_or_else(|_| serde_json::json!({}));
+                        blocks.push(serde_json::json!({
+                            "toolUse": {
+                                "toolUseId": call.id,
+                                "name": call.function.name,
+                                "input": input
+                            }
+                        }));
+                    }
+                    ("assistant", blocks)
+                }
+                MessageRole::Tool => {
+                    let text = Self::extract_text_content(&msg.content);
+                    let block = match &msg.tool_call_id {
+                        Some(id) => serde_json::json!({
+                            "toolResult": {
+                                "toolUseId": id,
+                                "content": [{ "text": text }]
+                            }
+                        }),
+                        None => serde_json::json!({ "text": text }),
+                    };
+                    ("user", vec![block])
+                }
+            };
+
+            // Converse requires alternating roles, so tool results that
+            // follow one another (or a user turn) share a single message
+            match messages.last_mut() {
+                Some((last_role, last_blocks)) if *last_role == role => {
+                    last_blocks.extend(blocks);
+                }
+                _ => messages.push((role, blocks)),
+            }
+        }
+
+        let mut inference_config = serde_json::json!({
+            "maxTokens": request.token_limit().unwrap_or(4096)
+        });
+
+        if let Some(temp) = request.temperature {
+            inference_config["temperature"] = serde_json::Value::Number(
+                serde_json::Number::from_f64(f64::from(temp)).unwrap_or(serde_json::Number::from(1)),
+            );
+        }
+
+        if let Some(top_p) = request.top_p {
+            inference_config["topP"] = serde_json::Value::Number(
+                serde_json::Number::from_f64(f64::from(top_p)).unwrap_or(serde_json::Number::from(1)),
+            );
+        }
+
+        if let Some(ref stop) = request.stop {
+            inference_config["stopSequences"] = serde_json::Value::Array(
+                stop.iter()
+                    .map(|s| serde_json::Value::String(s.clone()))
+                    .collect(),
+            );
+        }
+
+        let mut body = serde_json::json!({
+            "messages": messages
+                .into_iter()
+                .map(|(role, content)| serde_json::json!({ "role": role, "content": content }))
+                .collect::<Vec<_>>(),
+            "inferenceConfig": inference_config
+        });
+
+        if !system.is_empty() {
+            body["system"] = serde_json::Value::Array(system);
+        }
+
+        if let Some(tool_config) = Self::converse_tool_config(request) {
+            body["toolConfig"] = tool_config;
+        }
+
+        body
+    }
+
+    /// Map message content to Converse content blocks
+    fn converse_content(content: &MessageContent) -> Vec<serde_json::Value> {
+        match content {
+            MessageContent::Text(text) => vec![serde_json::json!({ "text": text })],
+            MessageContent::Parts(parts) => parts
+                .iter()
+                .map(|part| match part {
+                    ContentPart::Text { text } => serde_json::json!({ "text": text }),
+                    ContentPart::ImageUrl { image_url } => {
+                        match Self::parse_data_url(&image_url.url) {
+                            Some((media_type, data)) => serde_json::json!({
+                                "image": {
+                                    "format": media_type
+                                        .strip_prefix("image/")
+                                        .unwrap_or(&media_type),
+                                    "source": { "bytes": data }
+                                }
+                            }),
+                            None => serde_json::json!({
+                                "text": format!("[Image: {}]", image_url.url)
+                            }),
+                        }
+                    }
+                    ContentPart::Audio { audio } => serde_json::json!({
+                        "text": audio.transcript.as_deref().unwrap_or_default()
+                    }),
+                })
+                .collect(),
+        }
+    }
+
+    /// Map tool definitions and tool choice to a Converse `toolConfig`
+    fn converse_tool_config(request: &GatewayRequest) -> Option<serde_json::Value> {
+        let tools = request.tools.as_ref().filter(|tools| !tools.is_empty())?;
+
+        let tool_choice = match &request.tool_choice {
+            // Converse has no "none"; leaving tools out has the same effect
+            Some(ToolChoice::String(choice)) if choice == "none" => return None,
+            Some(ToolChoice::String(choice)) if choice == "required" => {
+                Some(serde_json::json!({ "any": {} }))
+            }
+            Some(ToolChoice::String(choice)) if choice == "auto" => {
+                Some(serde_json::json!({ "auto": {} }))
+            }
+            Some(ToolChoice::Tool { function, .. }) => {
+                Some(serde_json::json!({ "tool": { "name": function.name } }))
+            }
+            _ => None,
+        };
+
+        let tools: Vec<serde_json::Value> = tools
+            .iter()
+            .map(|tool| {
+                let mut spec = serde_json::json!({
+                    "name": tool.function.name,
+                    "inputSchema": {
+                        "json": tool.function.parameters.clone()
+                            .unwrap_or_else(|| serde_json::json!({ "type": "object" }))
+                    }
+                });
+                if let Some(ref description) = tool.function.description {
+                    spec["description"] = serde_json::Value::String(description.clone());
+                }
+                serde_json::json!({ "toolSpec": spec })
+            })
+            .collect();
+
+        let mut config = serde_json::json!({ "tools": tools });
+        if let Some(choice) = tool_choice {
+            config["toolChoice"] = choice;
+        }
+        Some(config)
+    }
+
     /// Convert gateway request to Bedrock format based on model family
     fn transform_request(
         &self,
@@ -364,7 +691,7 @@ impl BedrockProvider {
 
         let mut body = serde_json::json!({
             "anthropic_version": "bedrock-2023-05-31",
-            "max_tokens": request.max_tokens.unwrap_or(4096),
+            "max_tokens": request.token_limit().unwrap_or(4096),
             "messages": messages
         });
 
@@ -403,7 +730,7 @@ impl BedrockProvider {
         let prompt = Self::build_prompt_from_messages(&request.messages);
 
         let mut text_generation_config = serde_json::json!({
-            "maxTokenCount": request.max_tokens.unwrap_or(4096)
+            "maxTokenCount": request.token_limit().unwrap_or(4096)
         });
 
         if let Some(temp) = request.temperature {
@@ -441,7 +768,7 @@ impl BedrockProvider {
 
         let mut body = serde_json::json!({
             "prompt": prompt,
-            "max_gen_len": request.max_tokens.unwrap_or(2048)
+            "max_gen_len": request.token_limit().unwrap_or(2048)
         });
 
         if let Some(temp) = request.temperature {
@@ -468,7 +795,7 @@ impl BedrockProvider {
 
         let mut body = serde_json::json!({
             "prompt": prompt,
-            "max_tokens": request.max_tokens.unwrap_or(4096)
+            "max_tokens": request.token_limit().unwrap_or(4096)
         });
 
         if let Some(temp) = request.temperature {
@@ -503,7 +830,7 @@ impl BedrockProvider {
 
         let mut body = serde_json::json!({
             "prompt": prompt,
-            "max_tokens": request.max_tokens.unwrap_or(4096)
+            "max_tokens": request.token_limit().unwrap_or(4096)
         });
 
         if let Some(temp) = request.temperature {
@@ -538,7 +865,7 @@ impl BedrockProvider {
 
         let mut body = serde_json::json!({
             "prompt": prompt,
-            "maxTokens": request.max_tokens.unwrap_or(4096)
+            "maxTokens": request.token_limit().unwrap_or(4096)
         });
 
         if let Some(temp) = request.temperature {
@@ -683,6 +1010,10 @@ impl BedrockProvider {
                                 })
                             }
                         }
+                        ContentPart::Audio { audio } => serde_json::json!({
+                            "type": "text",
+                            "text": audio.transcript.as_deref().unwrap_or_default()
+                        }),
                     })
                     .collect();
                 serde_json::Value::Array(transformed)
@@ -699,6 +1030,7 @@ impl BedrockProvider {
                     .iter()
                     .filter_map(|p| match p {
                         ContentPart::Text { text } => Some(text.clone()),
+                        ContentPart::Audio { audio } => audio.transcript.clone(),
                         ContentPart::ImageUrl { .. } => None,
                     })
                     .collect::<Vec<_>>()
@@ -762,12 +1094,15 @@ impl BedrockProvider {
             content: Some(content),
             tool_calls: None,
             function_call: None,
+            audio: None,
         };
 
         let usage = Usage {
             prompt_tokens: response.usage.input_tokens,
             completion_tokens: response.usage.output_tokens,
             total_tokens: response.usage.input_tokens + response.usage.output_tokens,
+            prompt_tokens_cached: None,
+            completion_tokens_reasoning: None,
         };
 
         Ok(GatewayResponse::builder()
@@ -783,6 +1118,50 @@ impl BedrockProvider {
             .build())
     }
 
+    /// Parse a Converse response
+    fn parse_converse_response(response: &ConverseResponse, model: &str) -> GatewayResponse {
+        let mut text = String::new();
+        let mut tool_calls = Vec::new();
+
+        for block in &response.output.message.content {
+            if let Some(ref block_text) = block.text {
+                text.push_str(block_text);
+            }
+            if let Some(ref tool_use) = block.tool_use {
+                tool_calls.push(ToolCall {
+                    id: tool_use.tool_use_id.clone(),
+                    tool_type: "function".to_string(),
+                    function: FunctionCall {
+                        name: tool_use.name.clone(),
+                        arguments: tool_use.input.to_string(),
+                    },
+                });
+            }
+        }
+
+        let message = ResponseMessage {
+            role: MessageRole::Assistant,
+            content: (!text.is_empty() || tool_calls.is_empty()).then_some(text),
+            tool_calls: (!tool_calls.is_empty()).then_some(tool_calls),
+            function_call: None,
+            audio: None,
+        };
+
+        let usage = response.usage.as_ref().map(Usage::from).unwrap_or_default();
+
+        GatewayResponse::builder()
+            .id(format!("bedrock-{}", uuid::Uuid::new_v4()))
+            .model(model.to_string())
+            .choice(Choice {
+                index: 0,
+                message,
+                finish_reason: response.stop_reason.as_deref().and_then(map_converse_stop_reason),
+                logprobs: None,
+            })
+            .usage(usage)
+            .build()
+    }
+
     /// Parse Titan response from Bedrock
     fn parse_titan_response(
         &self,
@@ -806,6 +1185,7 @@ impl BedrockProvider {
             content: Some(result.output_text.clone()),
             tool_calls: None,
             function_call: None,
+            audio: None,
         };
 
         let usage = Usage {
@@ -813,6 +1193,8 @@ impl BedrockProvider {
             completion_tokens: result.token_count.unwrap_or(0),
             total_tokens: response.input_text_token_count.unwrap_or(0)
                 + result.token_count.unwrap_or(0),
+            prompt_tokens_cached: None,
+            completion_tokens_reasoning: None,
         };
 
         Ok(GatewayResponse::builder()
@@ -845,6 +1227,7 @@ impl BedrockProvider {
             content: Some(response.generation.clone()),
             tool_calls: None,
             function_call: None,
+            audio: None,
         };
 
         let usage = Usage {
@@ -852,6 +1235,8 @@ impl BedrockProvider {
             completion_tokens: response.generation_token_count.unwrap_or(0),
             total_tokens: response.prompt_token_count.unwrap_or(0)
                 + response.generation_token_count.unwrap_or(0),
+            prompt_tokens_cached: None,
+            completion_tokens_reasoning: None,
         };
 
         Ok(GatewayResponse::builder()
@@ -889,6 +1274,7 @@ impl BedrockProvider {
             content: Some(output.text.clone()),
             tool_calls: None,
             function_call: None,
+            audio: None,
         };
 
         Ok(GatewayResponse::builder()
@@ -926,6 +1312,7 @@ impl BedrockProvider {
             content: Some(generation.text.clone()),
             tool_calls: None,
             function_call: None,
+            audio: None,
         };
 
         Ok(GatewayResponse::builder()
@@ -963,6 +1350,7 @@ impl BedrockProvider {
             content: Some(completion.data.text.clone()),
             tool_calls: None,
             function_call: None,
+            audio: None,
         };
 
         Ok(GatewayResponse::builder()
@@ -978,20 +1366,232 @@ impl BedrockProvider {
             .build())
     }
 
+    /// Sign and send a request, returning the raw response
+    async fn send_signed(
+        &self,
+        url: &str,
+        body_bytes: Vec<u8>,
+        accept: &str,
+    ) -> Result<reqwest::Response, GatewayError> {
+        let mut headers = HashMap::new();
+        headers.insert("content-type".to_string(), "application/json".to_string());
+        headers.insert("accept".to_string(), accept.to_string());
+
+        let credentials = self
+            .credentials
+            .as_ref()
+            .ok_or_else(|| GatewayError::authentication("AWS credentials not configured"))?
+            .credentials()
+            .await?;
+        self.sign_request(&credentials, "POST", url, &body_bytes, &mut headers)?;
+
+        let mut req_builder = self.client.post(url);
+        for (key, value) in &headers {
+            req_builder = req_builder.header(key, value);
+        }
+
+        req_builder
+            .body(body_bytes)
+            .send()
+            .await
+            .map_err(|e| GatewayError::provider("bedrock", format!("Request failed: {e}"), None, true))
+    }
+
+    /// Sign and send a request, returning the body of a successful response
+    async fn send(
+        &self,
+        url: &str,
+        body_bytes: Vec<u8>,
+        accept: &str,
+        request: &GatewayRequest,
+    ) -> Result<bytes::Bytes, GatewayError> {
+        let response = self.send_signed(url, body_bytes, accept).await?;
+
+        let status = response.status();
+        let response_bytes = response.bytes().await.map_err(|e| {
+            GatewayError::provider("bedrock", format!("Failed to read response: {e}"), None, true)
+        })?;
+
+        if !status.is_success() {
+            let error: BedrockError = serde_json::from_slice(&response_bytes)
+                .unwrap_or(BedrockError {
+                    message: Some(String::from_utf8_lossy(&response_bytes).to_string()),
+                    message_alt: None,
+                });
+
+            let is_retryable = status.as_u16() >= 500 || status.as_u16() == 429;
+
+            if status.as_u16() == 429 {
+                return Err(GatewayError::rate_limit(None, None));
+            }
+
+            if let Some(err) = context_length::detect(
+                "bedrock",
+                status.as_u16(),
+                &error.message(),
+                request,
+                &self.config.models,
+            ) {
+                return Err(err);
+            }
+
+            return Err(GatewayError::provider(
+                "bedrock",
+                error.message(),
+                Some(status.as_u16()),
+                is_retryable,
+            ));
+        }
+
+        Ok(response_bytes)
+    }
+
+    /// Send a non-streaming request through the Converse API
+    async fn converse(&self, request: &GatewayRequest) -> Result<GatewayResponse, GatewayError> {
+        let model = &request.model;
+        let body = Self::transform_converse_request(request);
+        let body_bytes = serde_json::to_vec(&body).map_err(|e| {
+            GatewayError::validation(format!("Failed to serialize request: {e}"), None, "serialization_error")
+        })?;
+
+        debug!(model = %model, "Sending Converse request to Bedrock");
+
+        let response_bytes = self
+            .send(&self.converse_url(model), body_bytes, "application/json", request)
+            .await?;
+
+        let parsed: ConverseResponse = serde_json::from_slice(&response_bytes).map_err(|e| {
+            GatewayError::provider(
+                "bedrock",
+                format!("Failed to parse Converse response: {e}"),
+                None,
+                false,
+            )
+        })?;
+        Ok(Self::parse_converse_response(&parsed, model))
+    }
+
+    /// Serve a streaming request for a model that cannot stream as a
+    /// single-chunk stream over the regular invoke API
+    async fn completion_as_stream(
+        &self,
+        request: &GatewayRequest,
+    ) -> Result<BoxStream<'static, Result<ChatChunk, GatewayError>>, GatewayError> {
+        warn!(model = %request.model, "Model does not support streaming; falling back to non-streaming");
+        let response = self.chat_completion(request).await?;
+
+        let stream = try_stream! {
+            let mut chunk = ChatChunk::builder()
+                .id(response.id.clone())
+                .model(response.model.clone())
+                .usage(response.usage.clone());
+            for choice in response.choices {
+                chunk = chunk.choice(ChunkChoice {
+                    index: choice.index,
+                    delta: ChunkDelta {
+                        role: Some(MessageRole::Assistant),
+                        content: choice.message.content,
+                        tool_calls: None,
+                        function_call: None,
+                    },
+                    finish_reason: choice.finish_reason,
+                    logprobs: None,
+                });
+            }
+            yield chunk.build();
+        };
+
+        Ok(Box::pin(stream))
+    }
+
+    /// Stream a request through the ConverseStream API
+    async fn converse_stream(
+        &self,
+        request: &GatewayRequest,
+    ) -> Result<BoxStream<'static, Result<ChatChunk, GatewayError>>, GatewayError> {
+        let model = request.model.clone();
+        let body = Self::transform_converse_request(request);
+        let body_bytes = serde_json::to_vec(&body).map_err(|e| {
+            GatewayError::validation(format!("Failed to serialize request: {e}"), None, "serialization_error")
+        })?;
+
+        debug!(model = %model, "Starting ConverseStream request to Bedrock");
+
+        let response = self
+            .send_signed(
+                &self.converse_stream_url(&model),
+                body_bytes,
+                "application/vnd.amazon.eventstream",
+            )
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            if let Some(err) = context_length::detect(
+                "bedrock",
+                status.as_u16(),
+                &error_text,
+                request,
+                &self.config.models,
+            ) {
+                return Err(err);
+            }
+            return Err(GatewayError::provider(
+                "bedrock",
+                format!("Streaming request failed: {error_text}"),
+                Some(status.as_u16()),
+                status.as_u16() >= 500,
+            ));
+        }
+
+        let id = format!("bedrock-{}", uuid::Uuid::new_v4());
+        let mut body = response.bytes_stream();
+
+        let stream = try_stream! {
+            let mut decoder = EventStreamDecoder::default();
+            let mut state = ConverseStreamState::default();
+
+            while let Some(bytes) = body.next().await {
+                let bytes = bytes.map_err(|e| {
+                    GatewayError::provider("bedrock", format!("Stream read failed: {e}"), None, true)
+                })?;
+                decoder.push(&bytes);
+
+                while let Some(message) = decoder.next_message()? {
+                    if let Some(choice) = state.handle(&message)? {
+                        yield ChatChunk::builder()
+                            .id(id.clone())
+                            .model(model.clone())
+                            .choice(choice)
+                            .build();
+                    }
+                }
+            }
+
+            if let Some(usage) = state.usage.take() {
+                yield ChatChunk::builder()
+                    .id(id.clone())
+                    .model(model.clone())
+                    .usage(usage)
+                    .build();
+            }
+        };
+
+        Ok(Box::pin(stream))
+    }
+
     /// Sign a request with AWS Signature Version 4
     fn sign_request(
         &self,
+        credentials: &AwsCredentials,
         method: &str,
         uri: &str,
         body: &[u8],
         headers: &mut HashMap<String, String>,
     ) -> Result<(), GatewayError> {
-        let access_key = self.config.access_key_id.as_ref().ok_or_else(|| {
-            GatewayError::authentication("AWS access key ID not configured")
-        })?;
-        let secret_key = self.config.secret_access_key.as_ref().ok_or_else(|| {
-            GatewayError::authentication("AWS secret access key not configured")
-        })?;
+        let access_key = &credentials.access_key_id;
+        let secret_key = &credentials.secret_access_key;
 
         let now = SystemTime::now()
             .duration_since(UNIX_EPOCH)
@@ -1023,7 +1623,7 @@ impl BedrockProvider {
         headers.insert("x-amz-date".to_string(), amz_date.clone());
         headers.insert("x-amz-content-sha256".to_string(), payload_hash.clone());
 
-        if let Some(ref token) = self.config.session_token {
+        if let Some(ref token) = credentials.session_token {
             headers.insert("x-amz-security-token".to_string(), token.clone());
         }
 
@@ -1073,23 +1673,305 @@ impl BedrockProvider {
     }
 }
 
-/// Calculate SHA-256 hash
-fn sha256_hash(data: &[u8]) -> [u8; 32] {
-    use sha2::{Digest, Sha256};
-    let mut hasher = Sha256::new();
-    hasher.update(data);
-    hasher.finalize().into()
-}
+/// Refresh temporary credentials this long before they expire
+const CREDENTIAL_REFRESH_WINDOW: Duration = Duration::from_secs(300);
 
-/// Calculate HMAC-SHA256
-fn hmac_sha256(key: &[u8], data: &[u8]) -> [u8; 32] {
-    use hmac::{Hmac, Mac};
-    use sha2::Sha256;
+/// Default instance metadata service endpoint
+const DEFAULT_IMDS_ENDPOINT: &str = "http://169.254.169.254";
 
-    type HmacSha256 = Hmac<Sha256>;
-    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC can take key of any size");
-    mac.update(data);
-    mac.finalize().into_bytes().into()
+/// Base for `AWS_CONTAINER_CREDENTIALS_RELATIVE_URI` (ECS task roles)
+const CONTAINER_CREDENTIALS_HOST: &str = "http://169.254.170.2";
+
+/// AWS credentials used to sign requests
+#[derive(Clone)]
+pub struct AwsCredentials {
+    /// Access key ID
+    pub access_key_id: String,
+    /// Secret access key
+    pub secret_access_key: String,
+    /// Session token, for temporary credentials
+    pub session_token: Option<String>,
+    /// When temporary credentials expire
+    pub expires_at: Option<SystemTime>,
+}
+
+impl std::fmt::Debug for AwsCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AwsCredentials")
+            .field("access_key_id", &self.access_key_id)
+            .field("expires_at", &self.expires_at)
+            .finish_non_exhaustive()
+    }
+}
+
+impl AwsCredentials {
+    /// Whether these credentials should be refreshed before use
+    fn needs_refresh(&self) -> bool {
+        self.expires_at.is_some_and(|expires_at| {
+            expires_at
+                .duration_since(SystemTime::now())
+                .map_or(true, |remaining| remaining < CREDENTIAL_REFRESH_WINDOW)
+        })
+    }
+}
+
+/// Source of AWS credentials, resolved when a request is signed
+#[async_trait]
+pub trait CredentialProvider: Send + Sync + std::fmt::Debug {
+    /// Current credentials
+    ///
+    /// # Errors
+    /// Returns an authentication error if no credentials are available
+    async fn credentials(&self) -> Result<AwsCredentials, GatewayError>;
+}
+
+/// Credentials configured up front
+#[derive(Debug, Clone)]
+pub struct StaticCredentials(pub AwsCredentials);
+
+#[async_trait]
+impl CredentialProvider for StaticCredentials {
+    async fn credentials(&self) -> Result<AwsCredentials, GatewayError> {
+        Ok(self.0.clone())
+    }
+}
+
+/// Where [`DefaultCredentialChain`] looks for credentials
+#[derive(Debug, Clone)]
+enum CredentialSource {
+    /// `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY` and `AWS_SESSION_TOKEN`
+    Environment,
+    /// ECS/EKS container credential endpoint
+    Container {
+        url: String,
+        authorization: Option<String>,
+    },
+    /// EC2 instance metadata service, IMDSv2
+    Imds { endpoint: String },
+}
+
+/// Temporary credentials returned by the container and metadata endpoints
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct MetadataCredentials {
+    access_key_id: String,
+    secret_access_key: String,
+    token: Option<String>,
+    expiration: Option<String>,
+}
+
+/// Credentials from the environment, the container credential endpoint,
+/// or the EC2 instance metadata service, in that order
+///
+/// Resolved credentials are cached and refreshed five minutes before they
+/// expire. Concurrent requests share one refresh.
+#[derive(Debug)]
+pub struct DefaultCredentialChain {
+    sources: Vec<CredentialSource>,
+    /// Client for the metadata endpoints, which are link-local by design
+    /// and so bypass the provider's egress policy
+    client: Client,
+    cached: tokio::sync::Mutex<Option<AwsCredentials>>,
+}
+
+impl DefaultCredentialChain {
+    /// Build the chain from the standard AWS environment variables
+    ///
+    /// The container endpoint is used when `AWS_CONTAINER_CREDENTIALS_FULL_URI`
+    /// or `AWS_CONTAINER_CREDENTIALS_RELATIVE_URI` is set, and the metadata
+    /// service unless `AWS_EC2_METADATA_DISABLED` is `true`.
+    #[must_use]
+    pub fn from_env() -> Self {
+        let var = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+
+        let mut sources = vec![CredentialSource::Environment];
+        let container_url = var("AWS_CONTAINER_CREDENTIALS_FULL_URI").or_else(|| {
+            var("AWS_CONTAINER_CREDENTIALS_RELATIVE_URI")
+                .map(|path| format!("{CONTAINER_CREDENTIALS_HOST}{path}"))
+        });
+        if let Some(url) = container_url {
+            let authorization = var("AWS_CONTAINER_AUTHORIZATION_TOKEN").or_else(|| {
+                var("AWS_CONTAINER_AUTHORIZATION_TOKEN_FILE")
+                    .and_then(|path| std::fs::read_to_string(path).ok())
+                    .map(|token| token.trim().to_string())
+            });
+            sources.push(CredentialSource::Container { url, authorization });
+        }
+        if !var("AWS_EC2_METADATA_DISABLED").is_some_and(|v| v.eq_ignore_ascii_case("true")) {
+            let endpoint = var("AWS_EC2_METADATA_SERVICE_ENDPOINT")
+                .unwrap_or_else(|| DEFAULT_IMDS_ENDPOINT.to_string());
+            sources.push(CredentialSource::Imds { endpoint });
+        }
+        Self::with_sources(sources)
+    }
+
+    fn with_sources(sources: Vec<CredentialSource>) -> Self {
+        Self {
+            sources,
+            client: Client::builder()
+                .timeout(Duration::from_secs(5))
+                .build()
+                .unwrap_or_default(),
+            cached: tokio::sync::Mutex::new(None),
+        }
+    }
+
+    /// Resolve fresh credentials from the first source that has them
+    async fn resolve(&self) -> Result<AwsCredentials, GatewayError> {
+        let mut last_error = None;
+        for source in &self.sources {
+            let result = match source {
+                CredentialSource::Environment => Ok(credentials_from_env()),
+                CredentialSource::Container { url, authorization } => {
+                    let mut request = self.client.get(url);
+                    if let Some(authorization) = authorization {
+                        request = request.header("authorization", authorization);
+                    }
+                    self.fetch_metadata_credentials(request).await.map(Some)
+                }
+                CredentialSource::Imds { endpoint } => self.imds_credentials(endpoint).await.map(Some),
+            };
+            match result {
+                Ok(Some(credentials)) => return Ok(credentials),
+                Ok(None) => {}
+                Err(e) => {
+                    debug!(source = ?source, error = %e, "AWS credential source unavailable");
+                    last_error = Some(e);
+                }
+            }
+        }
+        Err(GatewayError::authentication(match last_error {
+            Some(e) => format!("No AWS credentials found: {e}"),
+            None => "No AWS credentials found".to_string(),
+        }))
+    }
+
+    /// Fetch the instance role's credentials with an IMDSv2 session token
+    async fn imds_credentials(&self, endpoint: &str) -> Result<AwsCredentials, String> {
+        let endpoint = endpoint.trim_end_matches('/');
+        let token = self
+            .client
+            .put(format!("{endpoint}/latest/api/token"))
+            .header("x-aws-ec2-metadata-token-ttl-seconds", "21600")
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|e| format!("instance metadata token request failed: {e}"))?
+            .text()
+            .await
+            .map_err(|e| format!("instance metadata token request failed: {e}"))?;
+
+        let roles_url = format!("{endpoint}/latest/meta-data/iam/security-credentials/");
+        let roles = self
+            .client
+            .get(&roles_url)
+            .header("x-aws-ec2-metadata-token", &token)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|e| format!("instance has no IAM role: {e}"))?
+            .text()
+            .await
+            .map_err(|e| format!("instance has no IAM role: {e}"))?;
+        let role = roles
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .ok_or_else(|| "instance has no IAM role".to_string())?;
+
+        let request = self
+            .client
+            .get(format!("{roles_url}{role}"))
+            .header("x-aws-ec2-metadata-token", &token);
+        self.fetch_metadata_credentials(request).await
+    }
+
+    async fn fetch_metadata_credentials(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<AwsCredentials, String> {
+        let credentials: MetadataCredentials = request
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|e| format!("credential request failed: {e}"))?
+            .json()
+            .await
+            .map_err(|e| format!("invalid credential response: {e}"))?;
+
+        let expires_at = match credentials.expiration {
+            Some(expiration) => {
+                let expiration = chrono::DateTime::parse_from_rfc3339(&expiration)
+                    .map_err(|e| format!("invalid credential expiration '{expiration}': {e}"))?;
+                let secs = u64::try_from(expiration.timestamp()).unwrap_or(0);
+                Some(UNIX_EPOCH + Duration::from_secs(secs))
+            }
+            None => None,
+        };
+        Ok(AwsCredentials {
+            access_key_id: credentials.access_key_id,
+            secret_access_key: credentials.secret_access_key,
+            session_token: credentials.token,
+            expires_at,
+        })
+    }
+}
+
+#[async_trait]
+impl CredentialProvider for DefaultCredentialChain {
+    async fn credentials(&self) -> Result<AwsCredentials, GatewayError> {
+        let mut cached = self.cached.lock().await;
+        if let Some(credentials) = cached.as_ref().filter(|c| !c.needs_refresh()) {
+            return Ok(credentials.clone());
+        }
+        match self.resolve().await {
+            Ok(credentials) => {
+                *cached = Some(credentials.clone());
+                Ok(credentials)
+            }
+            // Credentials inside the refresh window are still usable
+            Err(e) => match cached
+                .as_ref()
+                .filter(|c| c.expires_at.is_some_and(|t| t > SystemTime::now()))
+            {
+                Some(credentials) => {
+                    warn!(error = %e, "Failed to refresh AWS credentials, using cached ones");
+                    Ok(credentials.clone())
+                }
+                None => Err(e),
+            },
+        }
+    }
+}
+
+/// Static credentials from the standard environment variables
+fn credentials_from_env() -> Option<AwsCredentials> {
+    let var = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+    Some(AwsCredentials {
+        access_key_id: var("AWS_ACCESS_KEY_ID")?,
+        secret_access_key: var("AWS_SECRET_ACCESS_KEY")?,
+        session_token: var("AWS_SESSION_TOKEN"),
+        expires_at: None,
+    })
+}
+
+/// Calculate SHA-256 hash
+fn sha256_hash(data: &[u8]) -> [u8; 32] {
+    use sha2::{Digest, Sha256};
+    let mut hasher = Sha256::new();
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+/// Calculate HMAC-SHA256
+fn hmac_sha256(key: &[u8], data: &[u8]) -> [u8; 32] {
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256;
+
+    type HmacSha256 = Hmac<Sha256>;
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC can take key of any size");
+    mac.update(data);
+    mac.finalize().into_bytes().into()
 }
 
 /// Bedrock Claude response format
@@ -1209,6 +2091,443 @@ impl BedrockError {
     }
 }
 
+/// Bedrock Converse response format
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ConverseResponse {
+    output: ConverseOutput,
+    stop_reason: Option<String>,
+    usage: Option<ConverseUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ConverseOutput {
+    message: ConverseMessage,
+}
+
+#[derive(Debug, Deserialize)]
+struct ConverseMessage {
+    #[serde(default)]
+    content: Vec<ConverseContentBlock>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ConverseContentBlock {
+    text: Option<String>,
+    tool_use: Option<ConverseToolUse>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ConverseToolUse {
+    tool_use_id: String,
+    name: String,
+    #[serde(default)]
+    input: serde_json::Value,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct ConverseUsage {
+    input_tokens: u32,
+    output_tokens: u32,
+    total_tokens: u32,
+}
+
+impl From<&ConverseUsage> for Usage {
+    fn from(usage: &ConverseUsage) -> Self {
+        Self {
+            prompt_tokens: usage.input_tokens,
+            completion_tokens: usage.output_tokens,
+            total_tokens: usage.total_tokens,
+            prompt_tokens_cached: None,
+            completion_tokens_reasoning: None,
+        }
+    }
+}
+
+/// Payload of a ConverseStream event; which fields are set depends on the
+/// event type
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct ConverseStreamEvent {
+    content_block_index: Option<u32>,
+    start: Option<ConverseBlockStart>,
+    delta: Option<ConverseBlockDelta>,
+    stop_reason: Option<String>,
+    usage: Option<ConverseUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ConverseBlockStart {
+    tool_use: Option<ConverseToolUseStart>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ConverseToolUseStart {
+    tool_use_id: String,
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ConverseBlockDelta {
+    text: Option<String>,
+    tool_use: Option<ConverseToolUseDelta>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ConverseToolUseDelta {
+    input: String,
+}
+
+/// Map a Converse `stopReason` to a finish reason
+fn map_converse_stop_reason(reason: &str) -> Option<FinishReason> {
+    match reason {
+        "end_turn" | "stop_sequence" => Some(FinishReason::Stop),
+        "max_tokens" => Some(FinishReason::Length),
+        "tool_use" => Some(FinishReason::ToolCalls),
+        "content_filtered" | "guardrail_intervened" => Some(FinishReason::ContentFilter),
+        _ => None,
+    }
+}
+
+/// A decoded `application/vnd.amazon.eventstream` message
+#[derive(Debug)]
+struct EventStreamMessage {
+    /// String-valued headers such as `:event-type` and `:message-type`
+    headers: HashMap<String, String>,
+    payload: Vec<u8>,
+}
+
+impl EventStreamMessage {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).map(String::as_str)
+    }
+}
+
+/// Incremental decoder for AWS event stream framing
+///
+/// Each frame is a 12-byte prelude (total length, headers length, prelude
+/// CRC), the headers, the payload and a trailing message CRC. Checksums are
+/// not verified; the transport is already protected by TLS.
+#[derive(Debug, Default)]
+struct EventStreamDecoder {
+    buffer: Vec<u8>,
+}
+
+impl EventStreamDecoder {
+    const PRELUDE_LEN: usize = 12;
+    const CRC_LEN: usize = 4;
+
+    fn push(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Take the next complete message from the buffer, if there is one
+    fn next_message(&mut self) -> Result<Option<EventStreamMessage>, GatewayError> {
+        if self.buffer.len() < Self::PRELUDE_LEN {
+            return Ok(None);
+        }
+
+        let total_len = read_u32(&self.buffer[0..4]) as usize;
+        let headers_len = read_u32(&self.buffer[4..8]) as usize;
+        if total_len < Self::PRELUDE_LEN + Self::CRC_LEN + headers_len {
+            return Err(malformed_event_stream("frame shorter than its headers"));
+        }
+        if self.buffer.len() < total_len {
+            return Ok(None);
+        }
+
+        let frame: Vec<u8> = self.buffer.drain(..total_len).collect();
+        let headers_end = Self::PRELUDE_LEN + headers_len;
+        let headers = parse_event_stream_headers(&frame[Self::PRELUDE_LEN..headers_end])?;
+        let payload = frame[headers_end..total_len - Self::CRC_LEN].to_vec();
+
+        Ok(Some(EventStreamMessage { headers, payload }))
+    }
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+fn malformed_event_stream(reason: &str) -> GatewayError {
+    GatewayError::provider("bedrock", format!("Malformed event stream: {reason}"), None, false)
+}
+
+/// Parse event stream headers, keeping only string values
+fn parse_event_stream_headers(mut bytes: &[u8]) -> Result<HashMap<String, String>, GatewayError> {
+    fn take<'a>(bytes: &mut &'a [u8], len: usize) -> Result<&'a [u8], GatewayError> {
+        if bytes.len() < len {
+            return Err(malformed_event_stream("truncated header"));
+        }
+        let (head, tail) = bytes.split_at(len);
+        *bytes = tail;
+        Ok(head)
+    }
+
+    let mut headers = HashMap::new();
+    while !bytes.is_empty() {
+        let name_len = take(&mut bytes, 1)?[0] as usize;
+        let name = String::from_utf8_lossy(take(&mut bytes, name_len)?).into_owned();
+        let value_type = take(&mut bytes, 1)?[0];
+
+        let value_len = match value_type {
+            // bool true / bool false carry no value
+            0 | 1 => 0,
+            2 => 1,
+            3 => 2,
+            4 => 4,
+            5 | 8 => 8,
+            9 => 16,
+            6 | 7 => {
+                let len = take(&mut bytes, 2)?;
+                u16::from_be_bytes([len[0], len[1]]) as usize
+            }
+            other => {
+                return Err(malformed_event_stream(&format!("unknown header type {other}")));
+            }
+        };
+        let value = take(&mut bytes, value_len)?;
+
+        if value_type == 7 {
+            headers.insert(name, String::from_utf8_lossy(value).into_owned());
+        }
+    }
+
+    Ok(headers)
+}
+
+/// Error carried by an `exception` or `error` event stream message
+fn event_stream_error(message: &EventStreamMessage) -> GatewayError {
+    let error: BedrockError = serde_json::from_slice(&message.payload)
+        .unwrap_or(BedrockError { message: None, message_alt: None });
+    let kind = message
+        .header(":exception-type")
+        .or_else(|| message.header(":error-code"))
+        .unwrap_or("unknown");
+
+    if kind == "throttlingException" {
+        return GatewayError::rate_limit(None, None);
+    }
+
+    GatewayError::provider(
+        "bedrock",
+        format!("{}: {}", kind, error.message()),
+        None,
+        kind == "serviceUnavailableException" || kind == "internalServerException",
+    )
+}
+
+/// Translates ConverseStream events into chunk choices
+#[derive(Debug, Default)]
+struct ConverseStreamState {
+    /// Content block index to tool call index
+    tool_indices: HashMap<u32, u32>,
+    usage: Option<Usage>,
+}
+
+impl ConverseStreamState {
+    fn handle(&mut self, message: &EventStreamMessage) -> Result<Option<ChunkChoice>, GatewayError> {
+        if message.header(":message-type") != Some("event") {
+            return Err(event_stream_error(message));
+        }
+
+        let event: ConverseStreamEvent = if message.payload.is_empty() {
+            ConverseStreamEvent::default()
+        } else {
+            serde_json::from_slice(&message.payload).map_err(|e| {
+                GatewayError::provider(
+                    "bedrock",
+                    format!("Failed to parse ConverseStream event: {e}"),
+                    None,
+                    false,
+                )
+            })?
+        };
+        let block_index = event.content_block_index.unwrap_or(0);
+
+        let choice = match message.header(":event-type") {
+            Some("messageStart") => Some(ChunkChoice::with_role(0, MessageRole::Assistant)),
+            Some("contentBlockStart") => event
+                .start
+                .and_then(|start| start.tool_use)
+                .map(|tool_use| {
+                    let index = self.tool_indices.len() as u32;
+                    self.tool_indices.insert(block_index, index);
+                    ChunkChoice::with_tool_call(
+                        0,
+                        vec![ToolCallDelta {
+                            index,
+                            id: Some(tool_use.tool_use_id),
+                            tool_type: Some("function".to_string()),
+                            function: Some(FunctionCallDelta {
+                                name: Some(tool_use.name),
+                                arguments: Some(String::new()),
+                            }),
+                        }],
+                    )
+                }),
+            Some("contentBlockDelta") => event.delta.and_then(|delta| {
+                if let Some(text) = delta.text {
+                    Some(ChunkChoice::with_content(0, text))
+                } else {
+                    delta.tool_use.map(|tool_use| {
+                        ChunkChoice::with_tool_call(
+                            0,
+                            vec![ToolCallDelta {
+                                index: self.tool_indices.get(&block_index).copied().unwrap_or(0),
+                                id: None,
+                                tool_type: None,
+                                function: Some(FunctionCallDelta {
+                                    name: None,
+                                    arguments: Some(tool_use.input),
+                                }),
+                            }],
+                        )
+                    })
+                }
+            }),
+            Some("messageStop") => Some(ChunkChoice::with_finish(
+                0,
+                event
+                    .stop_reason
+                    .as_deref()
+                    .and_then(map_converse_stop_reason)
+                    .unwrap_or(FinishReason::Stop),
+            )),
+            Some("metadata") => {
+                self.usage = event.usage.as_ref().map(Usage::from);
+                None
+            }
+            _ => None,
+        };
+
+        Ok(choice)
+    }
+}
+
+/// Payload of an invoke-with-response-stream `chunk` event
+#[derive(Debug, Deserialize)]
+struct InvokeStreamChunk {
+    /// Base64-encoded model-specific JSON event
+    bytes: String,
+}
+
+/// Token counts Bedrock appends to the last chunk of every model family
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InvocationMetrics {
+    input_token_count: u32,
+    output_token_count: u32,
+}
+
+/// Map a streamed stop reason from any invoke model family
+fn map_invoke_stop_reason(reason: &str) -> FinishReason {
+    match reason.to_ascii_lowercase().as_str() {
+        "length" | "max_tokens" => FinishReason::Length,
+        "tool_use" => FinishReason::ToolCalls,
+        "content_filtered" => FinishReason::ContentFilter,
+        _ => FinishReason::Stop,
+    }
+}
+
+/// Translates invoke-with-response-stream chunks into chunk choices
+///
+/// Each `chunk` event wraps the model's own streaming JSON, whose shape
+/// depends on the model family.
+#[derive(Debug)]
+struct InvokeStreamState {
+    family: ModelFamily,
+    /// Whether the assistant role has been sent
+    started: bool,
+    /// Claude reports its stop reason in `message_delta`, ahead of `message_stop`
+    stop_reason: Option<FinishReason>,
+    usage: Option<Usage>,
+}
+
+impl InvokeStreamState {
+    fn new(family: ModelFamily) -> Self {
+        Self {
+            family,
+            started: false,
+            stop_reason: None,
+            usage: None,
+        }
+    }
+
+    fn handle(&mut self, message: &EventStreamMessage) -> Result<Option<ChunkChoice>, GatewayError> {
+        if message.header(":message-type") != Some("event") {
+            return Err(event_stream_error(message));
+        }
+        if message.header(":event-type") != Some("chunk") {
+            return Ok(None);
+        }
+
+        let parse_error = |e: &dyn std::fmt::Display| {
+            GatewayError::provider("bedrock", format!("Failed to parse stream chunk: {e}"), None, false)
+        };
+        let chunk: InvokeStreamChunk =
+            serde_json::from_slice(&message.payload).map_err(|e| parse_error(&e))?;
+        let bytes = BASE64_STANDARD.decode(chunk.bytes).map_err(|e| parse_error(&e))?;
+        let event: serde_json::Value = serde_json::from_slice(&bytes).map_err(|e| parse_error(&e))?;
+
+        if let Some(metrics) = event.get("amazon-bedrock-invocationMetrics") {
+            if let Ok(metrics) = InvocationMetrics::deserialize(metrics) {
+                self.usage = Some(Usage::new(metrics.input_token_count, metrics.output_token_count));
+            }
+        }
+
+        let stop = |value: &serde_json::Value| value.as_str().map(map_invoke_stop_reason);
+        let (text, finish_reason) = match self.family {
+            ModelFamily::Claude => match event["type"].as_str() {
+                Some("content_block_delta") => (event["delta"]["text"].as_str(), None),
+                Some("message_delta") => {
+                    self.stop_reason = stop(&event["delta"]["stop_reason"]);
+                    (None, None)
+                }
+                Some("message_stop") => {
+                    (None, Some(self.stop_reason.take().unwrap_or(FinishReason::Stop)))
+                }
+                _ => (None, None),
+            },
+            ModelFamily::Titan => (event["outputText"].as_str(), stop(&event["completionReason"])),
+            ModelFamily::Llama => (event["generation"].as_str(), stop(&event["stop_reason"])),
+            ModelFamily::Mistral => {
+                let output = &event["outputs"][0];
+                (output["text"].as_str(), stop(&output["stop_reason"]))
+            }
+            ModelFamily::Cohere => (
+                event["text"].as_str(),
+                event["is_finished"]
+                    .as_bool()
+                    .unwrap_or(false)
+                    .then(|| stop(&event["finish_reason"]).unwrap_or(FinishReason::Stop)),
+            ),
+            ModelFamily::Ai21 => (None, None),
+        };
+
+        let mut choice = match (text.filter(|text| !text.is_empty()), finish_reason) {
+            (None, None) => return Ok(None),
+            (Some(text), finish_reason) => {
+                let mut choice = ChunkChoice::with_content(0, text);
+                choice.finish_reason = finish_reason;
+                choice
+            }
+            (None, Some(finish_reason)) => ChunkChoice::with_finish(0, finish_reason),
+        };
+        if !self.started {
+            self.started = true;
+            choice.delta.role = Some(MessageRole::Assistant);
+        }
+        Ok(Some(choice))
+    }
+}
+
 #[async_trait]
 impl LLMProvider for BedrockProvider {
     fn id(&self) -> &str {
@@ -1223,7 +2542,13 @@ impl LLMProvider for BedrockProvider {
         &self,
         request: &GatewayRequest,
     ) -> Result<GatewayResponse, GatewayError> {
+        let filtered = self.config.params.apply(&self.config.id, request)?;
+        let request = &*filtered;
         let model = &request.model;
+        if self.config.uses_converse(model) {
+            return self.converse(request).await;
+        }
+
         let model_family = ModelFamily::from_model_id(model).ok_or_else(|| {
             GatewayError::model_not_found(&format!("Unsupported model family for: {}", model))
         })?;
@@ -1233,51 +2558,11 @@ impl LLMProvider for BedrockProvider {
             GatewayError::validation(format!("Failed to serialize request: {}", e), None, "serialization_error")
         })?;
 
-        let url = self.invoke_url(model);
-        let mut headers = HashMap::new();
-        headers.insert("content-type".to_string(), "application/json".to_string());
-        headers.insert("accept".to_string(), "application/json".to_string());
-
-        self.sign_request("POST", &url, &body_bytes, &mut headers)?;
-
-        let mut req_builder = self.client.post(&url);
-        for (key, value) in &headers {
-            req_builder = req_builder.header(key, value);
-        }
-
         debug!(model = %model, "Sending request to Bedrock");
 
-        let response = req_builder
-            .body(body_bytes)
-            .send()
-            .await
-            .map_err(|e| GatewayError::provider("bedrock", format!("Request failed: {}", e), None, true))?;
-
-        let status = response.status();
-        let response_bytes = response.bytes().await.map_err(|e| {
-            GatewayError::provider("bedrock", format!("Failed to read response: {}", e), None, true)
-        })?;
-
-        if !status.is_success() {
-            let error: BedrockError = serde_json::from_slice(&response_bytes)
-                .unwrap_or(BedrockError {
-                    message: Some(String::from_utf8_lossy(&response_bytes).to_string()),
-                    message_alt: None,
-                });
-
-            let is_retryable = status.as_u16() >= 500 || status.as_u16() == 429;
-
-            if status.as_u16() == 429 {
-                return Err(GatewayError::rate_limit(None, None));
-            }
-
-            return Err(GatewayError::provider(
-                "bedrock",
-                error.message(),
-                Some(status.as_u16()),
-                is_retryable,
-            ));
-        }
+        let response_bytes = self
+            .send(&self.invoke_url(model), body_bytes, "application/json", request)
+            .await?;
 
         // Parse based on model family
         match model_family {
@@ -1361,39 +2646,45 @@ impl LLMProvider for BedrockProvider {
         request: &GatewayRequest,
     ) -> Result<BoxStream<'static, Result<ChatChunk, GatewayError>>, GatewayError>
     {
+        let filtered = self.config.params.apply(&self.config.id, request)?;
+        let request = &*filtered;
+        if self.config.uses_converse(&request.model) {
+            return self.converse_stream(request).await;
+        }
+
         let model = request.model.clone();
         let model_family = ModelFamily::from_model_id(&model).ok_or_else(|| {
             GatewayError::model_not_found(&format!("Unsupported model family for: {}", model))
         })?;
 
+        // AI21 Jurassic models cannot stream on Bedrock
+        if model_family == ModelFamily::Ai21 {
+            return self.completion_as_stream(request).await;
+        }
+
         let body = self.transform_request(request, model_family)?;
         let body_bytes = serde_json::to_vec(&body).map_err(|e| {
             GatewayError::validation(format!("Failed to serialize request: {}", e), None, "serialization_error")
         })?;
 
-        let url = self.stream_url(&model);
-        let mut headers = HashMap::new();
-        headers.insert("content-type".to_string(), "application/json".to_string());
-        headers.insert("accept".to_string(), "application/vnd.amazon.eventstream".to_string());
-
-        self.sign_request("POST", &url, &body_bytes, &mut headers)?;
-
-        let mut req_builder = self.client.post(&url);
-        for (key, value) in &headers {
-            req_builder = req_builder.header(key, value);
-        }
-
         debug!(model = %model, "Starting streaming request to Bedrock");
 
-        let response = req_builder
-            .body(body_bytes)
-            .send()
-            .await
-            .map_err(|e| GatewayError::provider("bedrock", format!("Request failed: {}", e), None, true))?;
+        let response = self
+            .send_signed(&self.stream_url(&model), body_bytes, "application/vnd.amazon.eventstream")
+            .await?;
 
         let status = response.status();
         if !status.is_success() {
             let error_text = response.text().await.unwrap_or_default();
+            if let Some(err) = context_length::detect(
+                "bedrock",
+                status.as_u16(),
+                &error_text,
+                request,
+                &self.config.models,
+            ) {
+                return Err(err);
+            }
             return Err(GatewayError::provider(
                 "bedrock",
                 format!("Streaming request failed: {}", error_text),
@@ -1402,49 +2693,49 @@ impl LLMProvider for BedrockProvider {
             ));
         }
 
-        // Note: AWS Bedrock uses a custom binary event stream format
-        // For simplicity, we fall back to non-streaming and emit a single chunk
-        // A full implementation would parse the binary event stream
-        warn!("Bedrock streaming uses binary event stream; falling back to non-streaming");
-
-        let response_bytes = response.bytes().await.map_err(|e| {
-            GatewayError::provider("bedrock", format!("Failed to read response: {}", e), None, true)
-        })?;
-
-        // Try to parse as JSON (some models return JSON in streaming mode)
-        let content = String::from_utf8_lossy(&response_bytes).to_string();
+        let id = format!("bedrock-{}", uuid::Uuid::new_v4());
+        let mut body = response.bytes_stream();
 
         let stream = try_stream! {
-            let chunk = ChatChunk::builder()
-                .id(format!("bedrock-{}", uuid::Uuid::new_v4()))
-                .model(model.clone())
-                .choice(ChunkChoice {
-                    index: 0,
-                    delta: ChunkDelta {
-                        role: Some(MessageRole::Assistant),
-                        content: Some(content),
-                        tool_calls: None,
-                        function_call: None,
-                    },
-                    finish_reason: Some(FinishReason::Stop),
-                    logprobs: None,
-                })
-                .build();
+            let mut decoder = EventStreamDecoder::default();
+            let mut state = InvokeStreamState::new(model_family);
+
+            while let Some(bytes) = body.next().await {
+                let bytes = bytes.map_err(|e| {
+                    GatewayError::provider("bedrock", format!("Stream read failed: {e}"), None, true)
+                })?;
+                decoder.push(&bytes);
+
+                while let Some(message) = decoder.next_message()? {
+                    if let Some(choice) = state.handle(&message)? {
+                        yield ChatChunk::builder()
+                            .id(id.clone())
+                            .model(model.clone())
+                            .choice(choice)
+                            .build();
+                    }
+                }
+            }
 
-            yield chunk;
+            if let Some(usage) = state.usage.take() {
+                yield ChatChunk::builder()
+                    .id(id.clone())
+                    .model(model.clone())
+                    .usage(usage)
+                    .build();
+            }
         };
 
         Ok(Box::pin(stream))
     }
 
     async fn health_check(&self) -> HealthStatus {
-        // For Bedrock, we can only validate credentials are configured
+        // For Bedrock, we can only validate credentials resolve
         // Actually invoking a model would cost money
-        if self.config.access_key_id.is_none() || self.config.secret_access_key.is_none() {
-            return HealthStatus::Unhealthy;
+        match &self.credentials {
+            Some(credentials) if credentials.credentials().await.is_ok() => HealthStatus::Healthy,
+            _ => HealthStatus::Unhealthy,
         }
-
-        HealthStatus::Healthy
     }
 
     fn capabilities(&self) -> &ProviderCapabilities {
@@ -1666,6 +2957,576 @@ mod tests {
         assert_eq!(provider.provider_type(), ProviderType::Bedrock);
     }
 
+    fn converse_provider(endpoint: &str) -> BedrockProvider {
+        let config = BedrockConfig::builder()
+            .region("us-east-1")
+            .endpoint_url(endpoint)
+            .access_key_id("AKIATEST")
+            .secret_access_key("secret")
+            .use_converse(true)
+            .build();
+        BedrockProvider::new(config).unwrap()
+    }
+
+    /// Encode an event stream frame with zeroed checksums
+    fn event_frame(event_type: &str, payload: &serde_json::Value) -> Vec<u8> {
+        let mut headers = Vec::new();
+        for (name, value) in [(":message-type", "event"), (":event-type", event_type)] {
+            headers.push(name.len() as u8);
+            headers.extend_from_slice(name.as_bytes());
+            headers.push(7);
+            headers.extend_from_slice(&(value.len() as u16).to_be_bytes());
+            headers.extend_from_slice(value.as_bytes());
+        }
+        let payload = serde_json::to_vec(payload).unwrap();
+        let total = 12 + headers.len() + payload.len() + 4;
+
+        let mut frame = Vec::new();
+        frame.extend_from_slice(&(total as u32).to_be_bytes());
+        frame.extend_from_slice(&(headers.len() as u32).to_be_bytes());
+        frame.extend_from_slice(&[0; 4]);
+        frame.extend_from_slice(&headers);
+        frame.extend_from_slice(&payload);
+        frame.extend_from_slice(&[0; 4]);
+        frame
+    }
+
+    /// Encode an invoke-with-response-stream `chunk` frame around a model event
+    fn invoke_chunk(event: &serde_json::Value) -> Vec<u8> {
+        let bytes = BASE64_STANDARD.encode(serde_json::to_vec(event).unwrap());
+        event_frame("chunk", &serde_json::json!({ "bytes": bytes }))
+    }
+
+    fn decode(state: &mut InvokeStreamState, event: &serde_json::Value) -> Option<ChunkChoice> {
+        let frame = invoke_chunk(event);
+        let mut decoder = EventStreamDecoder::default();
+        decoder.push(&frame);
+        state.handle(&decoder.next_message().unwrap().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_converse_support() {
+        assert!(supports_converse("anthropic.claude-3-haiku-20240307-v1:0"));
+        assert!(supports_converse("meta.llama3-8b-instruct-v1:0"));
+        assert!(supports_converse("cohere.command-r-plus-v1:0"));
+        assert!(supports_converse("amazon.nova-pro-v1:0"));
+        assert!(!supports_converse("cohere.command-text-v14"));
+        assert!(!supports_converse("ai21.j2-ultra-v1"));
+
+        let config = BedrockConfig::builder().use_converse(true).build();
+        assert!(config.uses_converse("anthropic.claude-3-haiku-20240307-v1:0"));
+        assert!(!config.uses_converse("ai21.j2-ultra-v1"));
+
+        let legacy = BedrockConfig::builder().build();
+        assert!(!legacy.uses_converse("anthropic.claude-3-haiku-20240307-v1:0"));
+    }
+
+    #[test]
+    fn test_converse_urls() {
+        let provider = converse_provider("https://bedrock.test");
+        assert_eq!(
+            provider.converse_url("meta.llama3-8b-instruct-v1:0"),
+            "https://bedrock.test/model/meta.llama3-8b-instruct-v1:0/converse"
+        );
+        assert_eq!(
+            provider.converse_stream_url("meta.llama3-8b-instruct-v1:0"),
+            "https://bedrock.test/model/meta.llama3-8b-instruct-v1:0/converse-stream"
+        );
+    }
+
+    #[test]
+    fn test_converse_request_body() {
+        let request: GatewayRequest = serde_json::from_value(serde_json::json!({
+            "model": "meta.llama3-8b-instruct-v1:0",
+            "messages": [
+                { "role": "system", "content": "Be brief." },
+                { "role": "user", "content": "Weather in Paris?" },
+                {
+                    "role": "assistant",
+                    "content": "",
+                    "tool_calls": [{
+                        "id": "call_1",
+                        "type": "function",
+                        "function": { "name": "get_weather", "arguments": "{\"city\":\"Paris\"}" }
+                    }]
+                },
+                { "role": "tool", "content": "18C and sunny", "tool_call_id": "call_1" },
+                { "role": "user", "content": "Thanks!" }
+            ],
+            "max_tokens": 256,
+            "temperature": 0.5,
+            "stop": ["END"],
+            "tools": [{
+                "type": "function",
+                "function": {
+                    "name": "get_weather",
+                    "description": "Current weather",
+                    "parameters": { "type": "object", "properties": { "city": { "type": "string" } } }
+                }
+            }],
+            "tool_choice": "required"
+        }))
+        .unwrap();
+
+        let body = BedrockProvider::transform_converse_request(&request);
+
+        assert_eq!(body["system"], serde_json::json!([{ "text": "Be brief." }]));
+        assert_eq!(body["inferenceConfig"]["maxTokens"], 256);
+        assert_eq!(body["inferenceConfig"]["temperature"], 0.5);
+        assert_eq!(body["inferenceConfig"]["stopSequences"], serde_json::json!(["END"]));
+
+        let messages = body["messages"].as_array().unwrap();
+        assert_eq!(messages.len(), 3);
+        assert_eq!(messages[0]["content"], serde_json::json!([{ "text": "Weather in Paris?" }]));
+        assert_eq!(messages[1]["role"], "assistant");
+        assert_eq!(
+            messages[1]["content"],
+            serde_json::json!([{
+                "toolUse": { "toolUseId": "call_1", "name": "get_weather", "input": { "city": "Paris" } }
+            }])
+        );
+        // The tool result and the following user turn share one message
+        assert_eq!(messages[2]["role"], "user");
+        assert_eq!(
+            messages[2]["content"],
+            serde_json::json!([
+                { "toolResult": { "toolUseId": "call_1", "content": [{ "text": "18C and sunny" }] } },
+                { "text": "Thanks!" }
+            ])
+        );
+
+        let tool_config = &body["toolConfig"];
+        assert_eq!(tool_config["toolChoice"], serde_json::json!({ "any": {} }));
+        assert_eq!(tool_config["tools"][0]["toolSpec"]["name"], "get_weather");
+        assert_eq!(
+            tool_config["tools"][0]["toolSpec"]["inputSchema"]["json"]["properties"]["city"]["type"],
+            "string"
+        );
+    }
+
+    #[test]
+    fn test_converse_tool_choice_none_omits_tools() {
+        let mut request = GatewayRequest::builder()
+            .model("anthropic.claude-3-haiku-20240307-v1:0")
+            .message(ChatMessage::user("Hi"))
+            .build()
+            .unwrap();
+        request.tools = Some(vec![gateway_core::request::ToolDefinition::function("lookup")]);
+        request.tool_choice = Some(ToolChoice::String("none".to_string()));
+
+        let body = BedrockProvider::transform_converse_request(&request);
+        assert!(body.get("toolConfig").is_none());
+    }
+
+    #[test]
+    fn test_parse_converse_response() {
+        let response: ConverseResponse = serde_json::from_value(serde_json::json!({
+            "output": {
+                "message": {
+                    "role": "assistant",
+                    "content": [
+                        { "text": "Let me check." },
+                        { "toolUse": { "toolUseId": "tool_1", "name": "get_weather", "input": { "city": "Paris" } } }
+                    ]
+                }
+            },
+            "stopReason": "tool_use",
+            "usage": { "inputTokens": 12, "outputTokens": 8, "totalTokens": 20 },
+            "metrics": { "latencyMs": 321 }
+        }))
+        .unwrap();
+
+        let parsed =
+            BedrockProvider::parse_converse_response(&response, "meta.llama3-8b-instruct-v1:0");
+
+        assert_eq!(parsed.content(), Some("Let me check."));
+        assert_eq!(parsed.finish_reason(), Some(FinishReason::ToolCalls));
+        let tool_calls = parsed.choices[0].message.tool_calls.as_ref().unwrap();
+        assert_eq!(tool_calls[0].id, "tool_1");
+        assert_eq!(tool_calls[0].function.name, "get_weather");
+        assert_eq!(tool_calls[0].function.arguments, r#"{"city":"Paris"}"#);
+        let usage = parsed.usage;
+        assert_eq!(usage.prompt_tokens, 12);
+        assert_eq!(usage.total_tokens, 20);
+    }
+
+    #[test]
+    fn test_event_stream_decoder_handles_split_frames() {
+        let mut bytes = event_frame("messageStart", &serde_json::json!({ "role": "assistant" }));
+        bytes.extend(event_frame(
+            "contentBlockDelta",
+            &serde_json::json!({ "contentBlockIndex": 0, "delta": { "text": "Hi" } }),
+        ));
+
+        let mut decoder = EventStreamDecoder::default();
+        decoder.push(&bytes[..20]);
+        assert!(decoder.next_message().unwrap().is_none());
+
+        decoder.push(&bytes[20..]);
+        let first = decoder.next_message().unwrap().unwrap();
+        assert_eq!(first.header(":event-type"), Some("messageStart"));
+        let second = decoder.next_message().unwrap().unwrap();
+        assert_eq!(second.header(":event-type"), Some("contentBlockDelta"));
+        assert!(decoder.next_message().unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_converse_request_uses_converse_endpoint() {
+        use wiremock::matchers::{method, path_regex};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path_regex("/converse$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "output": { "message": { "role": "assistant", "content": [{ "text": "Bonjour" }] } },
+                "stopReason": "end_turn",
+                "usage": { "inputTokens": 3, "outputTokens": 1, "totalTokens": 4 }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = converse_provider(&server.uri());
+        let request = GatewayRequest::builder()
+            .model("mistral.mistral-large-2402-v1:0")
+            .message(ChatMessage::user("Hello in French"))
+            .build()
+            .unwrap();
+
+        let response = provider.chat_completion(&request).await.unwrap();
+        assert_eq!(response.content(), Some("Bonjour"));
+        assert_eq!(response.finish_reason(), Some(FinishReason::Stop));
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_model_falls_back_to_invoke() {
+        use wiremock::matchers::{method, path_regex};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path_regex("/invoke$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "generations": [{ "id": "gen-1", "text": "legacy", "finish_reason": "COMPLETE" }]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = converse_provider(&server.uri());
+        let request = GatewayRequest::builder()
+            .model("cohere.command-text-v14")
+            .message(ChatMessage::user("Hello"))
+            .build()
+            .unwrap();
+
+        let response = provider.chat_completion(&request).await.unwrap();
+        assert_eq!(response.content(), Some("legacy"));
+    }
+
+    #[tokio::test]
+    async fn test_converse_stream() {
+        use futures::StreamExt;
+        use wiremock::matchers::{method, path_regex};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mut body = event_frame("messageStart", &serde_json::json!({ "role": "assistant" }));
+        for text in ["Hel", "lo"] {
+            body.extend(event_frame(
+                "contentBlockDelta",
+                &serde_json::json!({ "contentBlockIndex": 0, "delta": { "text": text } }),
+            ));
+        }
+        body.extend(event_frame(
+            "contentBlockStart",
+            &serde_json::json!({
+                "contentBlockIndex": 1,
+                "start": { "toolUse": { "toolUseId": "tool_1", "name": "lookup" } }
+            }),
+        ));
+        body.extend(event_frame(
+            "contentBlockDelta",
+            &serde_json::json!({ "contentBlockIndex": 1, "delta": { "toolUse": { "input": "{}" } } }),
+        ));
+        body.extend(event_frame("messageStop", &serde_json::json!({ "stopReason": "tool_use" })));
+        body.extend(event_frame(
+            "metadata",
+            &serde_json::json!({ "usage": { "inputTokens": 5, "outputTokens": 2, "totalTokens": 7 } }),
+        ));
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path_regex("/converse-stream$"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(body))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = converse_provider(&server.uri());
+        let request = GatewayRequest::builder()
+            .model("anthropic.claude-3-haiku-20240307-v1:0")
+            .message(ChatMessage::user("Hello"))
+            .build()
+            .unwrap();
+
+        let chunks: Vec<ChatChunk> = provider
+            .chat_completion_stream(&request)
+            .await
+            .unwrap()
+            .map(|chunk| chunk.unwrap())
+            .collect()
+            .await;
+
+        let text: String = chunks.iter().filter_map(ChatChunk::content).collect();
+        assert_eq!(text, "Hello");
+
+        let tool_call = chunks
+            .iter()
+            .find_map(|c| c.choices.first().and_then(|choice| choice.delta.tool_calls.clone()))
+            .unwrap();
+        assert_eq!(tool_call[0].id.as_deref(), Some("tool_1"));
+
+        assert!(chunks.iter().any(|c| c.finish_reason() == Some(FinishReason::ToolCalls)));
+        assert_eq!(chunks.last().unwrap().usage.as_ref().unwrap().total_tokens, 7);
+    }
+
+    #[test]
+    fn test_invoke_stream_model_families() {
+        let mut titan = InvokeStreamState::new(ModelFamily::Titan);
+        let first = decode(&mut titan, &serde_json::json!({ "outputText": "Hi", "completionReason": null })).unwrap();
+        assert_eq!(first.delta.role, Some(MessageRole::Assistant));
+        assert_eq!(first.delta.content.as_deref(), Some("Hi"));
+        let last = decode(&mut titan, &serde_json::json!({ "outputText": "", "completionReason": "LENGTH" })).unwrap();
+        assert_eq!(last.delta.role, None);
+        assert_eq!(last.finish_reason, Some(FinishReason::Length));
+
+        let mut llama = InvokeStreamState::new(ModelFamily::Llama);
+        let last = decode(
+            &mut llama,
+            &serde_json::json!({
+                "generation": "!",
+                "stop_reason": "stop",
+                "amazon-bedrock-invocationMetrics": { "inputTokenCount": 4, "outputTokenCount": 9 }
+            }),
+        )
+        .unwrap();
+        assert_eq!(last.delta.content.as_deref(), Some("!"));
+        assert_eq!(last.finish_reason, Some(FinishReason::Stop));
+        assert_eq!(llama.usage.unwrap().total_tokens, 13);
+
+        let mut cohere = InvokeStreamState::new(ModelFamily::Cohere);
+        assert!(decode(&mut cohere, &serde_json::json!({ "is_finished": false, "text": "" })).is_none());
+        let last = decode(&mut cohere, &serde_json::json!({ "is_finished": true, "finish_reason": "MAX_TOKENS" })).unwrap();
+        assert_eq!(last.finish_reason, Some(FinishReason::Length));
+    }
+
+    #[tokio::test]
+    async fn test_invoke_stream() {
+        use futures::StreamExt;
+        use wiremock::matchers::{method, path_regex};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mut body = invoke_chunk(&serde_json::json!({
+            "type": "message_start",
+            "message": { "role": "assistant", "content": [] }
+        }));
+        for text in ["Hel", "lo"] {
+            body.extend(invoke_chunk(&serde_json::json!({
+                "type": "content_block_delta",
+                "index": 0,
+                "delta": { "type": "text_delta", "text": text }
+            })));
+        }
+        body.extend(invoke_chunk(&serde_json::json!({
+            "type": "message_delta",
+            "delta": { "stop_reason": "max_tokens" }
+        })));
+        body.extend(invoke_chunk(&serde_json::json!({
+            "type": "message_stop",
+            "amazon-bedrock-invocationMetrics": { "inputTokenCount": 5, "outputTokenCount": 2 }
+        })));
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path_regex("/invoke-with-response-stream$"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(body))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let config = BedrockConfig::builder()
+            .region("us-east-1")
+            .endpoint_url(server.uri())
+            .access_key_id("AKIATEST")
+            .secret_access_key("secret")
+            .build();
+        let provider = BedrockProvider::new(config).unwrap();
+        let request = GatewayRequest::builder()
+            .model("anthropic.claude-3-haiku-20240307-v1:0")
+            .message(ChatMessage::user("Hello"))
+            .build()
+            .unwrap();
+
+        let chunks: Vec<ChatChunk> = provider
+            .chat_completion_stream(&request)
+            .await
+            .unwrap()
+            .map(|chunk| chunk.unwrap())
+            .collect()
+            .await;
+
+        let text: String = chunks.iter().filter_map(ChatChunk::content).collect();
+        assert_eq!(text, "Hello");
+        assert_eq!(chunks[0].choices[0].delta.role, Some(MessageRole::Assistant));
+        assert!(chunks.iter().any(|c| c.finish_reason() == Some(FinishReason::Length)));
+        assert_eq!(chunks.last().unwrap().usage.as_ref().unwrap().total_tokens, 7);
+    }
+
+    /// RFC 3339 timestamp `secs` from now
+    fn expiration_in(secs: u64) -> String {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() + secs;
+        chrono::DateTime::from_timestamp(now as i64, 0).unwrap().to_rfc3339()
+    }
+
+    fn metadata_credentials(expiration: &str) -> serde_json::Value {
+        serde_json::json!({
+            "Code": "Success",
+            "Type": "AWS-HMAC",
+            "AccessKeyId": "ASIATEST",
+            "SecretAccessKey": "secret",
+            "Token": "session-token",
+            "Expiration": expiration
+        })
+    }
+
+    #[tokio::test]
+    async fn test_imds_credentials_are_cached() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/latest/api/token"))
+            .and(header("x-aws-ec2-metadata-token-ttl-seconds", "21600"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("imds-token"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/latest/meta-data/iam/security-credentials/"))
+            .and(header("x-aws-ec2-metadata-token", "imds-token"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("gateway-role\n"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/latest/meta-data/iam/security-credentials/gateway-role"))
+            .and(header("x-aws-ec2-metadata-token", "imds-token"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(metadata_credentials(&expiration_in(3600))),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let chain = DefaultCredentialChain::with_sources(vec![CredentialSource::Imds {
+            endpoint: server.uri(),
+        }]);
+        let first = chain.credentials().await.unwrap();
+        assert_eq!(first.access_key_id, "ASIATEST");
+        assert_eq!(first.session_token.as_deref(), Some("session-token"));
+        assert!(first.expires_at.is_some());
+
+        // Served from the cache until close to expiry
+        let second = chain.credentials().await.unwrap();
+        assert_eq!(second.access_key_id, "ASIATEST");
+    }
+
+    #[tokio::test]
+    async fn test_expiring_credentials_are_refreshed() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v2/credentials/task"))
+            .and(header("authorization", "container-token"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(metadata_credentials(&expiration_in(60))),
+            )
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let chain = DefaultCredentialChain::with_sources(vec![
+            CredentialSource::Imds {
+                endpoint: "http://127.0.0.1:1".to_string(),
+            },
+            CredentialSource::Container {
+                url: format!("{}/v2/credentials/task", server.uri()),
+                authorization: Some("container-token".to_string()),
+            },
+        ]);
+        // Expiring within the refresh window, so each call refreshes
+        chain.credentials().await.unwrap();
+        chain.credentials().await.unwrap();
+
+        let empty = DefaultCredentialChain::with_sources(Vec::new());
+        assert!(matches!(
+            empty.credentials().await,
+            Err(GatewayError::Authentication { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_session_credentials_are_signed() {
+        use wiremock::matchers::{header, header_regex, method, path, path_regex};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/credentials"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(metadata_credentials(&expiration_in(3600))),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path_regex("/converse$"))
+            .and(header("x-amz-security-token", "session-token"))
+            .and(header_regex("authorization", "^AWS4-HMAC-SHA256 Credential=ASIATEST/"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "output": { "message": { "role": "assistant", "content": [{ "text": "Hi" }] } },
+                "stopReason": "end_turn",
+                "usage": { "inputTokens": 3, "outputTokens": 1, "totalTokens": 4 }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let chain = DefaultCredentialChain::with_sources(vec![CredentialSource::Container {
+            url: format!("{}/credentials", server.uri()),
+            authorization: None,
+        }]);
+        let config = BedrockConfig::builder()
+            .region("us-east-1")
+            .endpoint_url(server.uri())
+            .use_converse(true)
+            .credential_provider(Arc::new(chain))
+            .build();
+        let provider = BedrockProvider::new(config).unwrap();
+        assert_eq!(provider.health_check().await, HealthStatus::Healthy);
+
+        let request = GatewayRequest::builder()
+            .model("anthropic.claude-3-haiku-20240307-v1:0")
+            .message(ChatMessage::user("Hello"))
+            .build()
+            .unwrap();
+        let response = provider.chat_completion(&request).await.unwrap();
+        assert_eq!(response.content(), Some("Hi"));
+    }
+
     #[test]
     fn test_provider_id() {
         let config = BedrockConfig::builder().id("my-bedrock").build();
@@ -0,0 +1,167 @@
+//! Context-length error detection shared by the HTTP providers.
+//!
+//! Every provider rejects over-long prompts with a client error, but each
+//! phrases it differently. These helpers recognize the known phrasings and
+//! map them to [`GatewayError::ContextLengthExceeded`], pulling the context
+//! window and request size out of the message where the provider reports
+//! them and falling back to the model catalog and the prompt estimate.
+
+use gateway_core::{GatewayError, GatewayRequest, ModelInfo};
+
+/// Phrases providers use when a prompt does not fit the context window
+const PATTERNS: &[&str] = &[
+    // OpenAI, Azure OpenAI
+    "context_length_exceeded",
+    "maximum context length",
+    // Anthropic
+    "prompt is too long",
+    // Gemini
+    "input token count",
+    // Bedrock
+    "input is too long",
+    "too many input tokens",
+    // Self-hosted OpenAI-compatible servers
+    "context window",
+];
+
+/// Markers preceding the context window size in provider messages
+const MAX_CONTEXT_MARKERS: &[&str] = &[
+    "maximum context length is",
+    "tokens allowed (",
+    "tokens >",
+];
+
+/// Markers preceding the request size in provider messages
+const REQUESTED_MARKERS: &[&str] = &[
+    "resulted in",
+    "you requested",
+    "prompt is too long:",
+    "input token count (",
+];
+
+/// Map a provider error response to a context-length error, if it is one
+pub(crate) fn detect(
+    provider: &str,
+    status: u16,
+    body: &str,
+    request: &GatewayRequest,
+    models: &[ModelInfo],
+) -> Option<GatewayError> {
+    if !(400..500).contains(&status) || status == 429 {
+        return None;
+    }
+
+    let text = body.to_ascii_lowercase();
+    if !PATTERNS.iter().any(|pattern| text.contains(pattern)) {
+        return None;
+    }
+
+    let max_context = first_number(&text, MAX_CONTEXT_MARKERS).or_else(|| {
+        models
+            .iter()
+            .find(|m| m.id == request.model || m.aliases.contains(&request.model))
+            .and_then(|m| m.context_length)
+    });
+    let requested = first_number(&text, REQUESTED_MARKERS)
+        .unwrap_or_else(|| request.estimated_prompt_tokens());
+
+    Some(GatewayError::context_length_exceeded(
+        provider,
+        &request.model,
+        max_context,
+        requested,
+    ))
+}
+
+/// Find the number following the first marker present in `text`
+fn first_number(text: &str, markers: &[&str]) -> Option<u32> {
+    markers.iter().find_map(|marker| {
+        let start = text.find(marker)? + marker.len();
+        let digits: String = text[start..]
+            .trim_start()
+            .chars()
+            .take_while(|c| c.is_ascii_digit() || *c == ',')
+            .filter(char::is_ascii_digit)
+            .collect();
+        digits.parse().ok()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gateway_core::ChatMessage;
+
+    fn request(model: &str) -> GatewayRequest {
+        GatewayRequest::builder()
+            .model(model)
+            .message(ChatMessage::user("a".repeat(400)))
+            .build()
+            .unwrap()
+    }
+
+    fn sizes(err: &GatewayError) -> Option<(Option<u32>, u32)> {
+        match err {
+            GatewayError::ContextLengthExceeded {
+                max_context,
+                requested,
+                ..
+            } => Some((*max_context, *requested)),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_openai_error() {
+        let body = r#"{"error":{"message":"This model's maximum context length is 8192 tokens. However, your messages resulted in 9013 tokens. Please reduce the length of the messages.","type":"invalid_request_error","param":"messages","code":"context_length_exceeded"}}"#;
+        let err = detect("openai", 400, body, &request("gpt-4"), &[]).unwrap();
+
+        assert_eq!(err.error_code(), "context_length_exceeded");
+        assert!(!err.is_retryable());
+        assert_eq!(sizes(&err), Some((Some(8192), 9013)));
+    }
+
+    #[test]
+    fn test_openai_completion_overflow() {
+        let body = r#"{"error":{"message":"This model's maximum context length is 16385 tokens. However, you requested 17000 tokens (9000 in the messages, 8000 in the completion).","code":"context_length_exceeded"}}"#;
+        let err = detect("openai", 400, body, &request("gpt-3.5-turbo"), &[]).unwrap();
+
+        assert_eq!(sizes(&err), Some((Some(16385), 17000)));
+    }
+
+    #[test]
+    fn test_anthropic_error() {
+        let body = r#"{"type":"error","error":{"type":"invalid_request_error","message":"prompt is too long: 210524 tokens > 200000 maximum"}}"#;
+        let err = detect("anthropic", 400, body, &request("claude-3-opus"), &[]).unwrap();
+
+        assert_eq!(sizes(&err), Some((Some(200_000), 210_524)));
+    }
+
+    #[test]
+    fn test_gemini_error() {
+        let body = r#"{"error":{"code":400,"message":"The input token count (1200000) exceeds the maximum number of tokens allowed (1048576).","status":"INVALID_ARGUMENT"}}"#;
+        let err = detect("google", 400, body, &request("gemini-1.5-flash"), &[]).unwrap();
+
+        assert_eq!(sizes(&err), Some((Some(1_048_576), 1_200_000)));
+    }
+
+    #[test]
+    fn test_bedrock_error_uses_catalog_and_estimate() {
+        let body = r#"{"message":"Input is too long for requested model."}"#;
+        let models = [ModelInfo::new("anthropic.claude-v2").with_context_length(100_000)];
+        let err = detect("bedrock", 400, body, &request("anthropic.claude-v2"), &models).unwrap();
+
+        // 100 text tokens plus role and message framing
+        assert_eq!(sizes(&err), Some((Some(100_000), 107)));
+    }
+
+    #[test]
+    fn test_other_errors_are_not_context_length() {
+        let req = request("gpt-4");
+        let overflow = r#"{"error":{"message":"maximum context length is 8192 tokens"}}"#;
+
+        assert!(detect("openai", 400, r#"{"error":{"message":"Invalid temperature"}}"#, &req, &[]).is_none());
+        assert!(detect("openai", 429, overflow, &req, &[]).is_none());
+        assert!(detect("openai", 500, overflow, &req, &[]).is_none());
+    }
+}
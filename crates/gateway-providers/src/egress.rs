@@ -0,0 +1,344 @@
+//! Outbound host policy for provider HTTP clients.
+//!
+//! Provider base URLs can come from tenant-editable configuration, and a
+//! redirect or a DNS answer can point an otherwise trusted host at an
+//! internal address. With an egress policy enabled, provider clients only
+//! connect to allowlisted hosts, and never to loopback, private, link-local
+//! or other internal addresses:
+//!
+//! ```yaml
+//! egress:
+//!   enabled: true
+//!   allowed_hosts:
+//!     - api.openai.com
+//!     - "*.openai.azure.com"
+//! providers:
+//!   - type: openai
+//!     id: openai
+//!     api_key: "..."
+//! ```
+//!
+//! The policy is checked three times: against the base URL when a provider
+//! is built, against every redirect target, and against the addresses a
+//! host name resolves to at connect time. Self-hosted backends on a private
+//! network need `allow_private_networks: true`.
+
+use gateway_core::GatewayError;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::redirect;
+use reqwest::ClientBuilder;
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::sync::Arc;
+use url::{Host, Url};
+
+/// Redirects followed before giving up, as for reqwest's default policy
+const MAX_REDIRECTS: usize = 10;
+
+/// Hosts and networks provider clients may connect to
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EgressPolicy {
+    /// Whether outbound connections are restricted
+    pub enabled: bool,
+    /// Hosts that may be contacted; `*.example.com` matches any subdomain
+    ///
+    /// An empty list allows any host, subject to the internal address check.
+    pub allowed_hosts: Vec<String>,
+    /// Allow loopback, private, link-local and other internal addresses
+    pub allow_private_networks: bool,
+}
+
+impl EgressPolicy {
+    /// Restrict connections to the given hosts, on public addresses only
+    #[must_use]
+    pub fn allowlist<I, S>(hosts: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            enabled: true,
+            allowed_hosts: hosts.into_iter().map(Into::into).collect(),
+            allow_private_networks: false,
+        }
+    }
+
+    /// Allow connections to internal addresses
+    #[must_use]
+    pub fn with_private_networks(mut self, allow: bool) -> Self {
+        self.allow_private_networks = allow;
+        self
+    }
+
+    /// Check that a provider URL may be contacted
+    ///
+    /// # Errors
+    /// Returns a configuration error naming the URL and why it is blocked
+    pub fn check_url(&self, url: &str) -> Result<(), GatewayError> {
+        if !self.enabled {
+            return Ok(());
+        }
+        let parsed = Url::parse(url).map_err(|e| GatewayError::Configuration {
+            message: format!("Invalid provider URL '{url}': {e}"),
+        })?;
+        self.check(&parsed).map_err(|reason| GatewayError::Configuration {
+            message: format!("Outbound request to '{url}' blocked: {reason}"),
+        })
+    }
+
+    /// Apply this policy to an HTTP client builder
+    ///
+    /// Redirect targets are checked before they are followed, and host
+    /// names are checked again, with their resolved addresses, on connect.
+    pub fn configure(&self, builder: ClientBuilder) -> ClientBuilder {
+        if !self.enabled {
+            return builder;
+        }
+        let policy = self.clone();
+        let redirects = redirect::Policy::custom(move |attempt| {
+            if attempt.previous().len() >= MAX_REDIRECTS {
+                return attempt.error("too many redirects");
+            }
+            match policy.check(attempt.url()) {
+                Ok(()) => attempt.follow(),
+                Err(reason) => {
+                    let message = format!("Redirect to '{}' blocked: {reason}", attempt.url());
+                    attempt.error(message)
+                }
+            }
+        });
+        builder
+            .redirect(redirects)
+            .dns_resolver(Arc::new(EgressResolver {
+                policy: self.clone(),
+            }))
+    }
+
+    /// Why `url` may not be contacted, if it may not
+    fn check(&self, url: &Url) -> Result<(), String> {
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(format!("scheme '{}' is not allowed", url.scheme()));
+        }
+        match url.host() {
+            None => Err("URL has no host".to_string()),
+            Some(Host::Domain(domain)) => {
+                self.check_host(domain)?;
+                // Always loopback (RFC 6761), so caught before any lookup
+                let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+                if domain == "localhost" || domain.ends_with(".localhost") {
+                    self.check_ip(Ipv4Addr::LOCALHOST.into())
+                } else {
+                    Ok(())
+                }
+            }
+            Some(Host::Ipv4(ip)) => {
+                self.check_host(&ip.to_string())?;
+                self.check_ip(ip.into())
+            }
+            Some(Host::Ipv6(ip)) => {
+                self.check_host(&ip.to_string())?;
+                self.check_ip(ip.into())
+            }
+        }
+    }
+
+    /// Whether a host name or IP literal is on the allowlist
+    fn check_host(&self, host: &str) -> Result<(), String> {
+        let host = host.trim_end_matches('.').to_ascii_lowercase();
+        let allowed = self.allowed_hosts.is_empty()
+            || self.allowed_hosts.iter().any(|pattern| {
+                let pattern = pattern.trim_end_matches('.').to_ascii_lowercase();
+                match pattern.strip_prefix("*.") {
+                    Some(suffix) => host
+                        .strip_suffix(suffix)
+                        .is_some_and(|label| label.ends_with('.') && label.len() > 1),
+                    None => host == pattern,
+                }
+            });
+        if allowed {
+            Ok(())
+        } else {
+            Err(format!("host '{host}' is not in the egress allowlist"))
+        }
+    }
+
+    /// Whether an address may be connected to
+    fn check_ip(&self, ip: IpAddr) -> Result<(), String> {
+        if self.allow_private_networks || !is_internal(ip) {
+            Ok(())
+        } else {
+            Err(format!("{ip} is an internal address"))
+        }
+    }
+}
+
+/// Resolves host names with the system resolver, refusing hosts that are
+/// not allowlisted or that resolve to an internal address
+#[derive(Debug)]
+struct EgressResolver {
+    policy: EgressPolicy,
+}
+
+impl Resolve for EgressResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let policy = self.policy.clone();
+        Box::pin(async move {
+            let host = name.as_str();
+            policy
+                .check_host(host)
+                .map_err(|reason| format!("Outbound connection blocked: {reason}"))?;
+            let addrs: Vec<_> = tokio::net::lookup_host((host, 0)).await?.collect();
+            // Any internal answer blocks the host, so a mixed answer cannot
+            // be used to reach an internal address on a later connect
+            for addr in &addrs {
+                policy.check_ip(addr.ip()).map_err(|reason| {
+                    format!("Outbound connection to '{host}' blocked: resolves to {reason}")
+                })?;
+            }
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// Whether an address is loopback, private, link-local, or otherwise not
+/// a public internet address
+fn is_internal(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_internal_v4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_internal_v4(ip),
+            None => is_internal_v6(ip),
+        },
+    }
+}
+
+fn is_internal_v4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_unspecified()
+        || ip.is_broadcast()
+        // Carrier-grade NAT, 100.64.0.0/10
+        || (a == 100 && (64..128).contains(&b))
+        // "This network", 0.0.0.0/8
+        || a == 0
+}
+
+fn is_internal_v6(ip: Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+    ip.is_loopback()
+        || ip.is_unspecified()
+        // Unique local, fc00::/7
+        || (first & 0xfe00) == 0xfc00
+        // Link-local, fe80::/10
+        || (first & 0xffc0) == 0xfe80
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allowlist_matching() {
+        let policy = EgressPolicy::allowlist(["api.openai.com", "*.openai.azure.com"]);
+
+        assert!(policy.check_url("https://api.openai.com/v1").is_ok());
+        assert!(policy.check_url("https://API.OpenAI.com./v1").is_ok());
+        assert!(policy.check_url("https://east.openai.azure.com").is_ok());
+
+        for url in [
+            "https://openai.azure.com",
+            "https://evil-openai.azure.com",
+            "https://api.openai.com.evil.example",
+            "https://example.com",
+        ] {
+            let err = policy.check_url(url).unwrap_err();
+            assert!(err.to_string().contains("not in the egress allowlist"), "{url}: {err}");
+        }
+
+        assert!(policy.check_url("file:///etc/passwd").is_err());
+        assert!(EgressPolicy::default().check_url("http://10.0.0.1").is_ok());
+    }
+
+    #[test]
+    fn test_blocks_internal_addresses() {
+        let policy = EgressPolicy::allowlist(Vec::<String>::new());
+
+        for url in [
+            "http://127.0.0.1:11434",
+            "http://10.1.2.3",
+            "http://192.168.0.10",
+            "http://169.254.169.254/latest/meta-data",
+            "http://100.64.0.1",
+            "http://0.0.0.0",
+            "http://[::1]",
+            "http://[fd00::1]",
+            "http://[fe80::1]",
+            "http://[::ffff:127.0.0.1]",
+            "http://localhost:11434",
+        ] {
+            let err = policy.check_url(url).unwrap_err();
+            assert!(err.to_string().contains("internal address"), "{url}: {err}");
+        }
+        assert!(policy.check_url("https://8.8.8.8").is_ok());
+        assert!(policy.check_url("https://[2001:4860:4860::8888]").is_ok());
+
+        let private = policy.with_private_networks(true);
+        assert!(private.check_url("http://10.1.2.3").is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_resolved_internal_address_is_blocked() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        let url = format!("http://localhost:{}/", server.address().port());
+
+        // `localhost` is allowlisted but resolves to loopback
+        let blocked = EgressPolicy::allowlist(["localhost"])
+            .configure(reqwest::Client::builder())
+            .build()
+            .unwrap();
+        let err = blocked.get(&url).send().await.unwrap_err();
+        assert!(format!("{err:?}").contains("internal address"), "{err:?}");
+
+        let allowed = EgressPolicy::allowlist(["localhost"])
+            .with_private_networks(true)
+            .configure(reqwest::Client::builder())
+            .build()
+            .unwrap();
+        assert_eq!(allowed.get(&url).send().await.unwrap().status(), 200);
+    }
+
+    #[tokio::test]
+    async fn test_redirect_to_unlisted_host_is_blocked() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(302)
+                    .insert_header("location", "http://metadata.internal/latest/meta-data"),
+            )
+            .mount(&server)
+            .await;
+
+        let client = EgressPolicy::allowlist(["localhost"])
+            .with_private_networks(true)
+            .configure(reqwest::Client::builder())
+            .build()
+            .unwrap();
+        let url = format!("http://localhost:{}/", server.address().port());
+        let err = client.get(&url).send().await.unwrap_err();
+        assert!(err.is_redirect());
+        assert!(format!("{err:?}").contains("not in the egress allowlist"), "{err:?}");
+    }
+}
@@ -0,0 +1,921 @@
+//! Data-driven provider construction.
+//!
+//! Builds providers and registries from declarative YAML/JSON definitions,
+//! so adding a provider instance is pure configuration:
+//!
+//! ```yaml
+//! providers:
+//!   - type: azure
+//!     id: azure-east
+//!     resource_name: my-resource
+//!     api_key: "..."
+//!     deployments:
+//!       gpt-4o-prod: gpt-4o
+//!   - type: ollama
+//!     id: local
+//!     models: [llama3]
+//!     pool:
+//!       min_idle: 2
+//!     headers:
+//!       X-Org-Id: acme
+//!     params:
+//!       deny: [service_tier]
+//! ```
+//!
+//! A top-level `egress` section restricts the hosts every provider may
+//! connect to; see [`crate::egress`]. Per-provider `params` lists are
+//! described in [`crate::params`].
+
+use crate::egress::EgressPolicy;
+use crate::headers::DefaultHeaders;
+use crate::params::{ParamFilter, ParamRules};
+use crate::pool::PoolConfig;
+use crate::registry::ProviderRegistry;
+use gateway_core::{GatewayError, LLMProvider, ProviderType};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Default Ollama endpoint
+#[cfg(feature = "openai")]
+const DEFAULT_OLLAMA_URL: &str = "http://localhost:11434";
+
+/// Declarative configuration for a single provider instance
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ProviderConfig {
+    /// OpenAI API
+    #[serde(rename = "openai")]
+    OpenAI {
+        /// Provider instance ID
+        id: String,
+        /// API key
+        api_key: String,
+        /// Base URL override
+        #[serde(default)]
+        base_url: Option<String>,
+        /// Organization ID
+        #[serde(default)]
+        organization: Option<String>,
+        /// Project ID
+        #[serde(default)]
+        project: Option<String>,
+        /// Organizations a request may select through metadata
+        #[serde(default)]
+        allowed_organizations: Vec<String>,
+        /// Projects a request may select through metadata
+        #[serde(default)]
+        allowed_projects: Vec<String>,
+    },
+    /// Anthropic API
+    Anthropic {
+        /// Provider instance ID
+        id: String,
+        /// API key
+        api_key: String,
+        /// Base URL override
+        #[serde(default)]
+        base_url: Option<String>,
+    },
+    /// Azure OpenAI Service
+    Azure {
+        /// Provider instance ID
+        id: String,
+        /// Azure resource name
+        resource_name: String,
+        /// API key
+        api_key: String,
+        /// API version override
+        #[serde(default)]
+        api_version: Option<String>,
+        /// Deployment name to model ID mapping
+        #[serde(default)]
+        deployments: HashMap<String, String>,
+    },
+    /// Google AI (Gemini)
+    Google {
+        /// Provider instance ID
+        id: String,
+        /// API key
+        api_key: String,
+    },
+    /// AWS Bedrock
+    Bedrock {
+        /// Provider instance ID
+        id: String,
+        /// AWS region
+        #[serde(default)]
+        region: Option<String>,
+        /// AWS access key ID
+        #[serde(default)]
+        access_key_id: Option<String>,
+        /// AWS secret access key
+        #[serde(default)]
+        secret_access_key: Option<String>,
+        /// AWS session token
+        #[serde(default)]
+        session_token: Option<String>,
+        /// Custom endpoint URL
+        #[serde(default)]
+        endpoint_url: Option<String>,
+        /// Use the Converse API for models that support it
+        #[serde(default)]
+        use_converse: bool,
+        /// Resolve credentials from the environment, container credential
+        /// endpoint, or instance metadata service when no keys are set
+        #[serde(default)]
+        use_default_credential_chain: bool,
+    },
+    /// Any OpenAI-compatible API
+    #[serde(rename = "openai_compatible")]
+    OpenAICompatible {
+        /// Provider instance ID
+        id: String,
+        /// Base URL
+        base_url: String,
+        /// API key (if the backend requires one)
+        #[serde(default)]
+        api_key: Option<String>,
+        /// Served models
+        #[serde(default)]
+        models: Vec<String>,
+    },
+    /// Ollama (self-hosted)
+    Ollama {
+        /// Provider instance ID
+        id: String,
+        /// Base URL (default: `http://localhost:11434`)
+        #[serde(default)]
+        base_url: Option<String>,
+        /// Served models
+        #[serde(default)]
+        models: Vec<String>,
+    },
+    /// vLLM (self-hosted)
+    #[serde(rename = "vllm")]
+    VLLM {
+        /// Provider instance ID
+        id: String,
+        /// Base URL
+        base_url: String,
+        /// API key (if the server requires one)
+        #[serde(default)]
+        api_key: Option<String>,
+        /// Served models
+        #[serde(default)]
+        models: Vec<String>,
+    },
+}
+
+impl ProviderConfig {
+    /// Provider instance ID
+    #[must_use]
+    pub fn id(&self) -> &str {
+        match self {
+            Self::OpenAI { id, .. }
+            | Self::Anthropic { id, .. }
+            | Self::Azure { id, .. }
+            | Self::Google { id, .. }
+            | Self::Bedrock { id, .. }
+            | Self::OpenAICompatible { id, .. }
+            | Self::Ollama { id, .. }
+            | Self::VLLM { id, .. } => id,
+        }
+    }
+
+    /// Provider type this configuration constructs
+    #[must_use]
+    pub fn provider_type(&self) -> ProviderType {
+        match self {
+            Self::OpenAI { .. } => ProviderType::OpenAI,
+            Self::Anthropic { .. } => ProviderType::Anthropic,
+            Self::Azure { .. } => ProviderType::Azure,
+            Self::Google { .. } => ProviderType::Google,
+            Self::Bedrock { .. } => ProviderType::Bedrock,
+            Self::OpenAICompatible { .. } => ProviderType::Custom,
+            Self::Ollama { .. } => ProviderType::Ollama,
+            Self::VLLM { .. } => ProviderType::VLLM,
+        }
+    }
+
+    /// Construct the provider
+    ///
+    /// # Errors
+    /// Returns error if the provider type is not compiled in or the provider
+    /// cannot be created
+    pub fn build(&self) -> Result<Arc<dyn LLMProvider>, GatewayError> {
+        self.build_with_pool(&PoolConfig::default())
+    }
+
+    /// Construct the provider with connection pool settings
+    ///
+    /// Warm connections are only maintained by OpenAI-protocol and
+    /// Anthropic providers; other types use their default pool.
+    ///
+    /// # Errors
+    /// Returns error if the provider type is not compiled in or the provider
+    /// cannot be created
+    pub fn build_with_pool(&self, pool: &PoolConfig) -> Result<Arc<dyn LLMProvider>, GatewayError> {
+        self.build_with_headers(pool, &DefaultHeaders::default())
+    }
+
+    /// Construct the provider with connection pool settings and headers
+    /// sent on every request
+    ///
+    /// # Errors
+    /// Returns error if the provider type is not compiled in or the provider
+    /// cannot be created
+    pub fn build_with_headers(
+        &self,
+        pool: &PoolConfig,
+        headers: &DefaultHeaders,
+    ) -> Result<Arc<dyn LLMProvider>, GatewayError> {
+        self.build_with_egress(pool, headers, &EgressPolicy::default())
+    }
+
+    /// Construct the provider with connection pool settings, headers sent
+    /// on every request, and the hosts it may connect to
+    ///
+    /// # Errors
+    /// Returns error if the provider type is not compiled in, its base URL
+    /// is blocked by the egress policy, or the provider cannot be created
+    pub fn build_with_egress(
+        &self,
+        pool: &PoolConfig,
+        headers: &DefaultHeaders,
+        egress: &EgressPolicy,
+    ) -> Result<Arc<dyn LLMProvider>, GatewayError> {
+        self.build_with_params(pool, headers, egress, &ParamFilter::default())
+    }
+
+    /// Construct the provider with connection pool settings, headers sent
+    /// on every request, the hosts it may connect to, and the request
+    /// parameters it may receive
+    ///
+    /// # Errors
+    /// Returns error if the provider type is not compiled in, its base URL
+    /// is blocked by the egress policy, or the provider cannot be created
+    #[allow(unused_variables, clippy::too_many_lines)]
+    pub fn build_with_params(
+        &self,
+        pool: &PoolConfig,
+        headers: &DefaultHeaders,
+        egress: &EgressPolicy,
+        params: &ParamFilter,
+    ) -> Result<Arc<dyn LLMProvider>, GatewayError> {
+        match self {
+            #[cfg(feature = "openai")]
+            Self::OpenAI {
+                id,
+                api_key,
+                base_url,
+                organization,
+                project,
+                allowed_organizations,
+                allowed_projects,
+            } => {
+                let mut config = crate::openai::OpenAIConfig::new(id, api_key)
+                    .with_pool(pool.clone())
+                    .with_default_headers(headers.clone())
+                    .with_egress(egress.clone())
+                    .with_params(params.clone())
+                    .with_allowed_organizations(allowed_organizations.clone())
+                    .with_allowed_projects(allowed_projects.clone());
+                if let Some(url) = base_url {
+                    config = config.with_base_url(url);
+                }
+                if let Some(org) = organization {
+                    config = config.with_organization(org);
+                }
+                if let Some(project) = project {
+                    config = config.with_project(project);
+                }
+                Ok(Arc::new(crate::OpenAIProvider::new(config)?))
+            }
+            #[cfg(feature = "anthropic")]
+            Self::Anthropic {
+                id,
+                api_key,
+                base_url,
+            } => {
+                let mut config = crate::anthropic::AnthropicConfig::new(api_key)
+                    .with_pool(pool.clone())
+                    .with_default_headers(headers.clone())
+                    .with_egress(egress.clone())
+                    .with_params(params.clone());
+                if let Some(url) = base_url {
+                    config = config.with_base_url(url);
+                }
+                Ok(Arc::new(crate::AnthropicProvider::with_id(id, config)?))
+            }
+            #[cfg(feature = "azure")]
+            Self::Azure {
+                id,
+                resource_name,
+                api_key,
+                api_version,
+                deployments,
+            } => {
+                let mut config = crate::azure::AzureOpenAIConfig::new(id, resource_name, api_key)
+                    .with_default_headers(headers.clone())
+                    .with_egress(egress.clone())
+                    .with_params(params.clone());
+                if let Some(version) = api_version {
+                    config = config.with_api_version(version);
+                }
+                for (deployment, model) in deployments {
+                    config = config.add_deployment(model, deployment);
+                }
+                Ok(Arc::new(crate::AzureOpenAIProvider::new(config)?))
+            }
+            #[cfg(feature = "google")]
+            Self::Google { id, api_key } => {
+                let config = crate::GoogleConfig::google_ai(id, api_key)
+                    .with_default_headers(headers.clone())
+                    .with_egress(egress.clone())
+                    .with_params(params.clone());
+                Ok(Arc::new(crate::GoogleProvider::new(config)?))
+            }
+            #[cfg(feature = "bedrock")]
+            Self::Bedrock {
+                id,
+                region,
+                access_key_id,
+                secret_access_key,
+                session_token,
+                endpoint_url,
+                use_converse,
+                use_default_credential_chain,
+            } => {
+                let config = bedrock(
+                    id,
+                    region.as_deref(),
+                    access_key_id.as_deref(),
+                    secret_access_key.as_deref(),
+                    session_token.as_deref(),
+                    endpoint_url.as_deref(),
+                    *use_converse,
+                )
+                .use_default_credential_chain(*use_default_credential_chain)
+                .default_headers(headers.clone())
+                .egress(egress.clone())
+                .params(params.clone())
+                .build();
+                Ok(Arc::new(crate::BedrockProvider::new(config)?))
+            }
+            #[cfg(feature = "openai")]
+            Self::OpenAICompatible {
+                id,
+                base_url,
+                api_key,
+                models,
+            } => openai_compatible(
+                id,
+                base_url,
+                api_key.as_deref(),
+                models,
+                ProviderType::Custom,
+                pool,
+                headers,
+                egress,
+                params,
+            ),
+            #[cfg(feature = "openai")]
+            Self::Ollama {
+                id,
+                base_url,
+                models,
+            } => openai_compatible(
+                id,
+                base_url.as_deref().unwrap_or(DEFAULT_OLLAMA_URL),
+                None,
+                models,
+                ProviderType::Ollama,
+                pool,
+                headers,
+                egress,
+                params,
+            ),
+            #[cfg(feature = "openai")]
+            Self::VLLM {
+                id,
+                base_url,
+                api_key,
+                models,
+            } => openai_compatible(
+                id,
+                base_url,
+                api_key.as_deref(),
+                models,
+                ProviderType::VLLM,
+                pool,
+                headers,
+                egress,
+                params,
+            ),
+            #[allow(unreachable_patterns)]
+            other => Err(other.not_enabled()),
+        }
+    }
+
+    /// Error for a provider type whose feature is not compiled in
+    fn not_enabled(&self) -> GatewayError {
+        GatewayError::Configuration {
+            message: format!(
+                "Provider type '{}' for '{}' is not enabled in this build",
+                self.provider_type(),
+                self.id()
+            ),
+        }
+    }
+}
+
+/// Configure a Bedrock provider from its declarative configuration
+#[cfg(feature = "bedrock")]
+fn bedrock(
+    id: &str,
+    region: Option<&str>,
+    access_key_id: Option<&str>,
+    secret_access_key: Option<&str>,
+    session_token: Option<&str>,
+    endpoint_url: Option<&str>,
+    use_converse: bool,
+) -> crate::bedrock::BedrockConfigBuilder {
+    let mut builder = crate::BedrockConfig::builder()
+        .id(id)
+        .use_converse(use_converse);
+    if let Some(region) = region {
+        builder = builder.region(region);
+    }
+    if let Some(key) = access_key_id {
+        builder = builder.access_key_id(key);
+    }
+    if let Some(secret) = secret_access_key {
+        builder = builder.secret_access_key(secret);
+    }
+    if let Some(token) = session_token {
+        builder = builder.session_token(token);
+    }
+    if let Some(url) = endpoint_url {
+        builder = builder.endpoint_url(url);
+    }
+    builder
+}
+
+/// Build an OpenAI-protocol provider reporting the given type
+#[cfg(feature = "openai")]
+#[allow(clippy::too_many_arguments)]
+fn openai_compatible(
+    id: &str,
+    base_url: &str,
+    api_key: Option<&str>,
+    models: &[String],
+    provider_type: ProviderType,
+    pool: &PoolConfig,
+    headers: &DefaultHeaders,
+    egress: &EgressPolicy,
+    params: &ParamFilter,
+) -> Result<Arc<dyn LLMProvider>, GatewayError> {
+    let config = crate::openai::OpenAIConfig::new(id, api_key.unwrap_or_default())
+        .with_base_url(base_url.trim_end_matches('/'))
+        .with_models(models.iter().map(gateway_core::ModelInfo::new).collect())
+        .with_provider_type(provider_type)
+        .with_pool(pool.clone())
+        .with_default_headers(headers.clone())
+        .with_egress(egress.clone())
+        .with_params(params.clone());
+    Ok(Arc::new(crate::OpenAIProvider::new(config)?))
+}
+
+/// Provider configuration with registration settings
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProviderDefinition {
+    /// Provider configuration
+    #[serde(flatten)]
+    pub config: ProviderConfig,
+    /// Whether this provider is registered
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Priority for routing (lower = higher priority)
+    #[serde(default = "default_priority")]
+    pub priority: u32,
+    /// Weight for weighted load balancing
+    #[serde(default = "default_weight")]
+    pub weight: u32,
+    /// Connection pool settings (warmup is off unless `min_idle` is set)
+    #[serde(default)]
+    pub pool: PoolConfig,
+    /// Headers sent on every request, unless the request sets them itself
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// Request parameters the provider may or may not receive
+    #[serde(default)]
+    pub params: ParamRules,
+}
+
+impl ProviderDefinition {
+    /// Create a definition with default registration settings
+    #[must_use]
+    pub fn new(config: ProviderConfig) -> Self {
+        Self {
+            config,
+            enabled: true,
+            priority: default_priority(),
+            weight: default_weight(),
+            pool: PoolConfig::default(),
+            headers: HashMap::new(),
+            params: ParamRules::default(),
+        }
+    }
+
+    /// Translate a provider entry of the gateway configuration file
+    ///
+    /// Type-specific settings (e.g. Azure `resource_name`, Bedrock `region`)
+    /// are taken from the entry's `options`. Together and custom providers
+    /// are built as `openai_compatible`.
+    ///
+    /// # Errors
+    /// Returns `GatewayError::Configuration` if the entry lacks a setting its
+    /// provider type requires
+    pub fn from_gateway_config(
+        provider_config: &gateway_config::ProviderConfig,
+    ) -> Result<Self, GatewayError> {
+        let provider_type = match provider_config.provider_type {
+            ProviderType::Together | ProviderType::Custom => "openai_compatible".to_string(),
+            other => other.to_string(),
+        };
+
+        let mut value = serde_json::Map::new();
+        for (key, option) in &provider_config.options {
+            value.insert(key.clone(), option.clone());
+        }
+        value.insert("type".to_string(), provider_type.into());
+        value.insert("id".to_string(), provider_config.id.clone().into());
+        value.insert("priority".to_string(), provider_config.priority.into());
+        value.insert("weight".to_string(), provider_config.weight.into());
+        if !provider_config.endpoint.is_empty() {
+            value.insert("base_url".to_string(), provider_config.endpoint.clone().into());
+        }
+        if !provider_config.models.is_empty() {
+            value.insert("models".to_string(), provider_config.models.clone().into());
+        }
+        if let Some(api_key) = provider_config.resolve_api_key() {
+            value.insert("api_key".to_string(), api_key.into());
+        }
+
+        serde_json::from_value(serde_json::Value::Object(value)).map_err(|e| {
+            GatewayError::Configuration {
+                message: format!(
+                    "Invalid configuration for provider '{}': {e}",
+                    provider_config.id
+                ),
+            }
+        })
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_priority() -> u32 {
+    100
+}
+
+fn default_weight() -> u32 {
+    100
+}
+
+/// Top-level document listing provider definitions
+#[derive(Debug, Deserialize)]
+struct ProvidersDocument {
+    providers: Vec<ProviderDefinition>,
+    #[serde(default)]
+    egress: EgressPolicy,
+}
+
+/// Builds a [`ProviderRegistry`] from provider definitions
+#[derive(Debug, Default)]
+pub struct RegistryBuilder {
+    definitions: Vec<ProviderDefinition>,
+    egress: EgressPolicy,
+}
+
+impl RegistryBuilder {
+    /// Create an empty builder
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse a YAML document with a top-level `providers` list
+    ///
+    /// # Errors
+    /// Returns error if the document is invalid or names an unknown provider type
+    pub fn from_yaml(content: &str) -> Result<Self, GatewayError> {
+        let document: ProvidersDocument =
+            serde_yaml::from_str(content).map_err(|e| GatewayError::Configuration {
+                message: format!("Invalid provider configuration: {e}"),
+            })?;
+        Ok(Self {
+            definitions: document.providers,
+            egress: document.egress,
+        })
+    }
+
+    /// Parse a JSON document with a top-level `providers` list
+    ///
+    /// # Errors
+    /// Returns error if the document is invalid or names an unknown provider type
+    pub fn from_json(content: &str) -> Result<Self, GatewayError> {
+        let document: ProvidersDocument =
+            serde_json::from_str(content).map_err(|e| GatewayError::Configuration {
+                message: format!("Invalid provider configuration: {e}"),
+            })?;
+        Ok(Self {
+            definitions: document.providers,
+            egress: document.egress,
+        })
+    }
+
+    /// Add a provider definition
+    #[must_use]
+    pub fn provider(mut self, definition: ProviderDefinition) -> Self {
+        self.definitions.push(definition);
+        self
+    }
+
+    /// Restrict the hosts every provider may connect to
+    #[must_use]
+    pub fn egress(mut self, egress: EgressPolicy) -> Self {
+        self.egress = egress;
+        self
+    }
+
+    /// Provider definitions added so far
+    #[must_use]
+    pub fn definitions(&self) -> &[ProviderDefinition] {
+        &self.definitions
+    }
+
+    /// Construct and register every enabled provider
+    ///
+    /// # Errors
+    /// Returns error if any provider cannot be constructed or registered
+    pub fn build(self) -> Result<ProviderRegistry, GatewayError> {
+        let registry = ProviderRegistry::new();
+        self.build_into(&registry)?;
+        Ok(registry)
+    }
+
+    /// Construct and register every enabled provider into an existing registry
+    ///
+    /// # Errors
+    /// Returns error if any provider cannot be constructed or registered
+    pub fn build_into(self, registry: &ProviderRegistry) -> Result<(), GatewayError> {
+        for definition in self.definitions.into_iter().filter(|d| d.enabled) {
+            let headers = DefaultHeaders::new(definition.config.id(), &definition.headers)?;
+            let params = ParamFilter::new(definition.config.id(), &definition.params)?;
+            let provider = definition.config.build_with_params(
+                &definition.pool,
+                &headers,
+                &self.egress,
+                &params,
+            )?;
+            registry.register(provider, definition.priority, definition.weight)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "bedrock")]
+    fn test_builds_azure_and_bedrock_from_yaml() {
+        let yaml = r"
+providers:
+  - type: azure
+    id: azure-east
+    resource_name: my-resource
+    api_key: azure-key
+    deployments:
+      gpt-4o-prod: gpt-4o
+  - type: bedrock
+    id: bedrock-west
+    region: us-west-2
+    access_key_id: AKIDEXAMPLE
+    secret_access_key: secret
+    use_converse: true
+    priority: 50
+";
+
+        let registry = RegistryBuilder::from_yaml(yaml).unwrap().build().unwrap();
+
+        assert_eq!(registry.len(), 2);
+        let azure = registry.get("azure-east").unwrap();
+        assert_eq!(azure.provider_type(), ProviderType::Azure);
+        let bedrock = registry.get("bedrock-west").unwrap();
+        assert_eq!(bedrock.provider_type(), ProviderType::Bedrock);
+        assert_eq!(registry.get_entry("bedrock-west").unwrap().priority, 50);
+    }
+
+    #[test]
+    fn test_definition_from_gateway_config() {
+        let provider_config: gateway_config::ProviderConfig = serde_yaml::from_str(
+            r"
+id: azure-east
+type: azure
+endpoint: ''
+api_key: azure-key
+priority: 10
+options:
+  resource_name: my-resource
+",
+        )
+        .unwrap();
+
+        let definition = ProviderDefinition::from_gateway_config(&provider_config).unwrap();
+        assert_eq!(definition.config.id(), "azure-east");
+        assert_eq!(definition.config.provider_type(), ProviderType::Azure);
+        assert_eq!(definition.priority, 10);
+
+        // Azure needs a resource name
+        let mut provider_config = provider_config;
+        provider_config.options.clear();
+        let err = ProviderDefinition::from_gateway_config(&provider_config).unwrap_err();
+        assert!(err.to_string().contains("azure-east"));
+    }
+
+    #[test]
+    fn test_builds_self_hosted_from_json() {
+        let json = r#"{
+            "providers": [
+                {"type": "ollama", "id": "local", "models": ["llama3"]},
+                {"type": "vllm", "id": "gpu", "base_url": "http://vllm:8000/", "models": ["mixtral"]},
+                {"type": "openai_compatible", "id": "together", "base_url": "https://api.together.xyz", "api_key": "k"}
+            ]
+        }"#;
+
+        let registry = RegistryBuilder::from_json(json).unwrap().build().unwrap();
+
+        let ollama = registry.get("local").unwrap();
+        assert_eq!(ollama.provider_type(), ProviderType::Ollama);
+        assert_eq!(ollama.base_url(), DEFAULT_OLLAMA_URL);
+        assert!(ollama.supports_model("llama3"));
+
+        let vllm = registry.get("gpu").unwrap();
+        assert_eq!(vllm.provider_type(), ProviderType::VLLM);
+        assert_eq!(vllm.base_url(), "http://vllm:8000");
+
+        let custom = registry.get("together").unwrap();
+        assert_eq!(custom.provider_type(), ProviderType::Custom);
+    }
+
+    #[test]
+    fn test_unknown_provider_type_is_an_error() {
+        let yaml = r"
+providers:
+  - type: mystery
+    id: nope
+";
+
+        let err = RegistryBuilder::from_yaml(yaml).unwrap_err();
+        assert!(matches!(err, GatewayError::Configuration { .. }));
+        assert!(err.to_string().contains("mystery"));
+    }
+
+    #[test]
+    fn test_disabled_definitions_are_skipped() {
+        let definition = ProviderDefinition {
+            enabled: false,
+            ..ProviderDefinition::new(ProviderConfig::Google {
+                id: "gemini".to_string(),
+                api_key: "key".to_string(),
+            })
+        };
+
+        let registry = RegistryBuilder::new().provider(definition).build().unwrap();
+        assert!(registry.is_empty());
+    }
+
+    #[test]
+    fn test_default_headers_are_validated() {
+        let yaml = r"
+providers:
+  - type: ollama
+    id: local
+    headers:
+      X-Org-Id: acme
+";
+        let registry = RegistryBuilder::from_yaml(yaml).unwrap().build().unwrap();
+        assert!(registry.get("local").is_some());
+
+        let yaml = r"
+providers:
+  - type: ollama
+    id: local
+    headers:
+      Authorization: Bearer other-key
+";
+        let Err(err) = RegistryBuilder::from_yaml(yaml).unwrap().build() else {
+            unreachable!("authorization is reserved");
+        };
+        assert!(matches!(err, GatewayError::Configuration { .. }));
+        assert!(err.to_string().contains("local"));
+    }
+
+    #[test]
+    fn test_param_rules_are_validated() {
+        let yaml = r"
+providers:
+  - type: vllm
+    id: local
+    base_url: http://vllm:8000
+    params:
+      allow: [temperature, max_tokens]
+      deny: [service_tier]
+      reject_unknown: true
+";
+        let registry = RegistryBuilder::from_yaml(yaml).unwrap().build().unwrap();
+        assert!(registry.get("local").is_some());
+
+        let yaml = r"
+providers:
+  - type: vllm
+    id: local
+    base_url: http://vllm:8000
+    params:
+      deny: [temprature]
+";
+        let Err(err) = RegistryBuilder::from_yaml(yaml).unwrap().build() else {
+            unreachable!("unknown parameter names are rejected");
+        };
+        assert!(matches!(err, GatewayError::Configuration { .. }));
+        assert!(err.to_string().contains("temprature"));
+    }
+
+    #[test]
+    fn test_egress_policy_blocks_provider_hosts() {
+        let yaml = r#"
+egress:
+  enabled: true
+  allowed_hosts: [api.openai.com, "*.openai.azure.com"]
+providers:
+  - type: openai
+    id: openai
+    api_key: sk-test
+  - type: azure
+    id: azure-east
+    resource_name: east
+    api_key: azure-key
+    deployments:
+      gpt-4o-prod: gpt-4o
+"#;
+        let registry = RegistryBuilder::from_yaml(yaml).unwrap().build().unwrap();
+        assert!(registry.get("openai").is_some());
+        assert!(registry.get("azure-east").is_some());
+
+        for base_url in ["https://attacker.example.com/v1", "http://169.254.169.254/latest"] {
+            let definition = ProviderDefinition::new(ProviderConfig::OpenAI {
+                id: "openai".to_string(),
+                api_key: "sk-test".to_string(),
+                base_url: Some(base_url.to_string()),
+                organization: None,
+                project: None,
+                allowed_organizations: Vec::new(),
+                allowed_projects: Vec::new(),
+            });
+            let Err(err) = RegistryBuilder::new()
+                .egress(EgressPolicy::allowlist(["api.openai.com", "169.254.169.254"]))
+                .provider(definition)
+                .build()
+            else {
+                unreachable!("{base_url} is blocked");
+            };
+            assert!(matches!(err, GatewayError::Configuration { .. }));
+            assert!(err.to_string().contains("blocked"), "{err}");
+        }
+
+        // Self-hosted backends need private networks allowed
+        let yaml = r"
+egress:
+  enabled: true
+providers:
+  - type: ollama
+    id: local
+";
+        assert!(RegistryBuilder::from_yaml(yaml).unwrap().build().is_err());
+        let yaml = r"
+egress:
+  enabled: true
+  allow_private_networks: true
+providers:
+  - type: ollama
+    id: local
+";
+        assert!(RegistryBuilder::from_yaml(yaml).unwrap().build().is_ok());
+    }
+}
@@ -21,6 +21,10 @@ use reqwest::Client;
 use secrecy::{ExposeSecret, SecretString};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
+use crate::egress::EgressPolicy;
+use crate::headers::DefaultHeaders;
+use crate::params::ParamFilter;
+use crate::{context_length, transport};
 use tracing::{debug, error, trace, warn};
 
 /// Google provider API type
@@ -52,6 +56,12 @@ pub struct GoogleConfig {
     pub timeout: Duration,
     /// Supported models
     pub models: Vec<ModelInfo>,
+    /// Headers sent on every request
+    pub default_headers: DefaultHeaders,
+    /// Hosts and networks the client may connect to
+    pub egress: EgressPolicy,
+    /// Request parameters the provider may receive
+    pub params: ParamFilter,
 }
 
 impl GoogleConfig {
@@ -67,6 +77,9 @@ impl GoogleConfig {
             location: "us-central1".to_string(),
             timeout: Duration::from_secs(120),
             models: Self::default_models(),
+            default_headers: DefaultHeaders::default(),
+            egress: EgressPolicy::default(),
+            params: ParamFilter::default(),
         }
     }
 
@@ -87,6 +100,9 @@ impl GoogleConfig {
             location: location.into(),
             timeout: Duration::from_secs(120),
             models: Self::default_models(),
+            default_headers: DefaultHeaders::default(),
+            egress: EgressPolicy::default(),
+            params: ParamFilter::default(),
         }
     }
 
@@ -104,6 +120,27 @@ impl GoogleConfig {
         self
     }
 
+    /// Set headers sent on every request
+    #[must_use]
+    pub fn with_default_headers(mut self, headers: DefaultHeaders) -> Self {
+        self.default_headers = headers;
+        self
+    }
+
+    /// Set the hosts and networks the client may connect to
+    #[must_use]
+    pub fn with_egress(mut self, egress: EgressPolicy) -> Self {
+        self.egress = egress;
+        self
+    }
+
+    /// Set the request parameters the provider may receive
+    #[must_use]
+    pub fn with_params(mut self, params: ParamFilter) -> Self {
+        self.params = params;
+        self
+    }
+
     /// Default Gemini models
     #[must_use]
     pub fn default_models() -> Vec<ModelInfo> {
@@ -196,9 +233,14 @@ impl GoogleProvider {
             }
         }
 
-        let client = Client::builder()
+        let builder = Client::builder()
             .timeout(config.timeout)
-            .pool_max_idle_per_host(100)
+            .pool_max_idle_per_host(100);
+        config.egress.check_url(&config.base_url())?;
+        let builder = config.egress.configure(builder);
+        let client = config
+            .default_headers
+            .configure(builder)
             .build()
             .map_err(|e| GatewayError::internal(format!("Failed to create HTTP client: {e}")))?;
 
@@ -219,6 +261,7 @@ impl GoogleProvider {
                 max_context_length: Some(2_097_152),
                 max_output_tokens: Some(8_192),
                 parallel_tool_calls: true,
+                audio_output: false,
             },
             base_url_string,
         })
@@ -299,8 +342,10 @@ impl GoogleProvider {
             temperature: request.temperature,
             top_p: request.top_p,
             top_k: request.top_k.map(|k| k as i32),
-            max_output_tokens: request.max_tokens,
+            max_output_tokens: request.token_limit(),
             stop_sequences: request.stop.clone(),
+            presence_penalty: request.presence_penalty,
+            frequency_penalty: request.frequency_penalty,
             response_mime_type: if json_mode {
                 Some("application/json".to_string())
             } else {
@@ -327,7 +372,15 @@ impl GoogleProvider {
             system_instruction,
             generation_config: Some(generation_config),
             tools,
-            safety_settings: None,
+            safety_settings: request.safety_settings.as_ref().map(|settings| {
+                settings
+                    .iter()
+                    .map(|s| GoogleSafetySetting {
+                        category: s.category.clone(),
+                        threshold: s.threshold.clone(),
+                    })
+                    .collect()
+            }),
         }
     }
 
@@ -360,6 +413,11 @@ impl GoogleProvider {
                                 None
                             }
                         }
+                        // Prior audio turns are sent as their transcript
+                        ContentPart::Audio { audio } => audio
+                            .transcript
+                            .clone()
+                            .map(|text| GooglePart::Text { text }),
                     })
                     .collect()
             }
@@ -405,6 +463,10 @@ impl GoogleProvider {
         response: GoogleResponse,
         model: &str,
     ) -> Result<GatewayResponse, GatewayError> {
+        if let Some(err) = Self::safety_block(&response) {
+            return Err(err);
+        }
+
         let candidate = response.candidates.into_iter().next().ok_or_else(|| {
             GatewayError::provider("google", "No candidates in response", None, false)
         })?;
@@ -453,6 +515,7 @@ impl GoogleProvider {
                 content: Some(content),
                 tool_calls: None,
                 function_call: None,
+                audio: None,
             }
         } else {
             ResponseMessage {
@@ -464,6 +527,7 @@ impl GoogleProvider {
                 },
                 tool_calls: Some(tool_calls),
                 function_call: None,
+                audio: None,
             }
         };
 
@@ -473,6 +537,8 @@ impl GoogleProvider {
             total_tokens: u.total_token_count.unwrap_or(
                 u.prompt_token_count + u.candidates_token_count.unwrap_or(0),
             ) as u32,
+            prompt_tokens_cached: u.cached_content_token_count.map(|c| c as u32),
+            completion_tokens_reasoning: None,
         });
 
         Ok(GatewayResponse::builder()
@@ -488,6 +554,57 @@ impl GoogleProvider {
             .build())
     }
 
+    /// Content filter error for a blocked prompt or a safety-stopped candidate
+    fn safety_block(response: &GoogleResponse) -> Option<GatewayError> {
+        if let Some(feedback) = &response.prompt_feedback {
+            if let Some(reason) = &feedback.block_reason {
+                let (categories, detail) = Self::flagged_categories(&feedback.safety_ratings);
+                return Some(GatewayError::content_filter(
+                    "google",
+                    format!("prompt blocked ({reason}){detail}"),
+                    categories,
+                ));
+            }
+        }
+
+        let candidate = response.candidates.first()?;
+        if candidate.finish_reason.as_deref() != Some("SAFETY") {
+            return None;
+        }
+        let (categories, detail) = Self::flagged_categories(&candidate.safety_ratings);
+        Some(GatewayError::content_filter(
+            "google",
+            format!("response blocked by safety filters{detail}"),
+            categories,
+        ))
+    }
+
+    /// Categories that triggered a block, plus a `: CATEGORY (PROBABILITY)` suffix
+    ///
+    /// Ratings Gemini marks as `blocked` win; otherwise any rated MEDIUM or HIGH.
+    fn flagged_categories(ratings: &[GoogleSafetyRating]) -> (Vec<String>, String) {
+        let mut flagged: Vec<&GoogleSafetyRating> = ratings.iter().filter(|r| r.blocked).collect();
+        if flagged.is_empty() {
+            flagged = ratings
+                .iter()
+                .filter(|r| matches!(r.probability.as_str(), "MEDIUM" | "HIGH"))
+                .collect();
+        }
+        if flagged.is_empty() {
+            return (Vec::new(), String::new());
+        }
+
+        let detail = flagged
+            .iter()
+            .map(|r| format!("{} ({})", r.category, r.probability))
+            .collect::<Vec<_>>()
+            .join(", ");
+        (
+            flagged.iter().map(|r| r.category.clone()).collect(),
+            format!(": {detail}"),
+        )
+    }
+
     /// Map Google finish reason to gateway format
     fn map_finish_reason(reason: &str) -> FinishReason {
         match reason {
@@ -531,6 +648,8 @@ impl LLMProvider for GoogleProvider {
         &self,
         request: &GatewayRequest,
     ) -> Result<GatewayResponse, GatewayError> {
+        let filtered = self.config.params.apply(&self.config.id, request)?;
+        let request = &*filtered;
         let model = &request.model;
         let url = self.endpoint_url(model, false);
 
@@ -563,13 +682,17 @@ impl LLMProvider for GoogleProvider {
 
         let status = response.status();
         let body = response.text().await.map_err(|e| {
-            GatewayError::provider("google", format!("Failed to read response: {e}"), None, false)
+            if transport::is_connection_reset(&e) {
+                GatewayError::connection_reset(format!("google: connection reset: {e}"))
+            } else {
+                GatewayError::provider("google", format!("Failed to read response: {e}"), None, false)
+            }
         })?;
 
         trace!(status = %status, body = %body, "Received Google response");
 
         if !status.is_success() {
-            return Err(Self::parse_error(status.as_u16(), &body));
+            return Err(self.parse_error(status.as_u16(), &body, request));
         }
 
         let google_response: GoogleResponse = serde_json::from_str(&body).map_err(|e| {
@@ -583,6 +706,8 @@ impl LLMProvider for GoogleProvider {
         &self,
         request: &GatewayRequest,
     ) -> Result<BoxStream<'static, Result<ChatChunk, GatewayError>>, GatewayError> {
+        let filtered = self.config.params.apply(&self.config.id, request)?;
+        let request = &*filtered;
         let model = request.model.clone();
         let url = self.endpoint_url(&model, true);
 
@@ -622,69 +747,55 @@ impl LLMProvider for GoogleProvider {
         let status = response.status();
         if !status.is_success() {
             let body = response.text().await.unwrap_or_default();
-            return Err(Self::parse_error(status.as_u16(), &body));
+            return Err(self.parse_error(status.as_u16(), &body, request));
         }
 
+        let mut events = Box::pin(transport::sse_events("google", response));
+
         // Create stream
         let stream = try_stream! {
-            let mut byte_stream = response.bytes_stream();
-            let mut buffer = String::new();
-
-            while let Some(chunk_result) = byte_stream.next().await {
-                let chunk = chunk_result.map_err(|e| {
-                    GatewayError::provider("google", format!("Stream error: {e}"), None, false)
-                })?;
-
-                let text = String::from_utf8_lossy(&chunk);
-                buffer.push_str(&text);
-
-                // Process complete SSE events
-                while let Some(pos) = buffer.find("\n\n") {
-                    let event = buffer[..pos].to_string();
-                    buffer = buffer[pos + 2..].to_string();
-
-                    // Parse SSE data
-                    for line in event.lines() {
-                        if let Some(data) = line.strip_prefix("data: ") {
-                            if data == "[DONE]" {
-                                return;
-                            }
+            while let Some(event) = events.next().await {
+                let event = event?;
+                let data = event.data.as_str();
+                if data == "[DONE]" {
+                    return;
+                }
 
-                            // Parse the JSON chunk
-                            if let Ok(response) = serde_json::from_str::<GoogleResponse>(data) {
-                                if let Some(candidate) = response.candidates.into_iter().next() {
-                                    let content = candidate.content.parts.iter()
-                                        .filter_map(|p| match p {
-                                            GooglePart::Text { text } => Some(text.clone()),
-                                            _ => None,
-                                        })
-                                        .collect::<Vec<_>>()
-                                        .join("");
-
-                                    let finish_reason = candidate.finish_reason
-                                        .as_deref()
-                                        .map(Self::map_finish_reason);
-
-                                    let chunk = ChatChunk::builder()
-                                        .id(format!("google-{}", uuid::Uuid::new_v4()))
-                                        .model(model.clone())
-                                        .choice(ChunkChoice {
-                                            index: 0,
-                                            delta: ChunkDelta {
-                                                role: Some(MessageRole::Assistant),
-                                                content: if content.is_empty() { None } else { Some(content) },
-                                                tool_calls: None,
-                                                function_call: None,
-                                            },
-                                            finish_reason,
-                                            logprobs: None,
-                                        })
-                                        .build();
-
-                                    yield chunk;
-                                }
-                            }
-                        }
+                // Parse the JSON chunk
+                if let Ok(response) = serde_json::from_str::<GoogleResponse>(data) {
+                    if let Some(err) = Self::safety_block(&response) {
+                        Err(err)?;
+                    }
+                    if let Some(candidate) = response.candidates.into_iter().next() {
+                        let content = candidate.content.parts.iter()
+                            .filter_map(|p| match p {
+                                GooglePart::Text { text } => Some(text.clone()),
+                                _ => None,
+                            })
+                            .collect::<Vec<_>>()
+                            .join("");
+
+                        let finish_reason = candidate.finish_reason
+                            .as_deref()
+                            .map(Self::map_finish_reason);
+
+                        let chunk = ChatChunk::builder()
+                            .id(format!("google-{}", uuid::Uuid::new_v4()))
+                            .model(model.clone())
+                            .choice(ChunkChoice {
+                                index: 0,
+                                delta: ChunkDelta {
+                                    role: Some(MessageRole::Assistant),
+                                    content: if content.is_empty() { None } else { Some(content) },
+                                    tool_calls: None,
+                                    function_call: None,
+                                },
+                                finish_reason,
+                                logprobs: None,
+                            })
+                            .build();
+
+                        yield chunk;
                     }
                 }
             }
@@ -733,7 +844,12 @@ impl LLMProvider for GoogleProvider {
 
 impl GoogleProvider {
     /// Parse error response
-    fn parse_error(status: u16, body: &str) -> GatewayError {
+    fn parse_error(&self, status: u16, body: &str, request: &GatewayRequest) -> GatewayError {
+        if let Some(err) = context_length::detect("google", status, body, request, &self.config.models) {
+            return err;
+        }
+
+
         // Try to parse Google error format
         #[derive(Deserialize)]
         struct GoogleErrorResponse {
@@ -781,7 +897,7 @@ struct GoogleRequest {
     safety_settings: Option<Vec<GoogleSafetySetting>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct GoogleContent {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -842,6 +958,10 @@ struct GoogleGenerationConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     stop_sequences: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    presence_penalty: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    frequency_penalty: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     response_mime_type: Option<String>,
 }
 
@@ -862,7 +982,6 @@ struct GoogleFunctionDeclaration {
 
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
-#[allow(dead_code)]
 struct GoogleSafetySetting {
     category: String,
     threshold: String,
@@ -871,20 +990,34 @@ struct GoogleSafetySetting {
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct GoogleResponse {
+    /// Absent when the prompt itself was blocked
+    #[serde(default)]
     candidates: Vec<GoogleCandidate>,
     #[serde(default)]
+    prompt_feedback: Option<GooglePromptFeedback>,
+    #[serde(default)]
     usage_metadata: Option<GoogleUsageMetadata>,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GooglePromptFeedback {
+    #[serde(default)]
+    block_reason: Option<String>,
+    #[serde(default)]
+    safety_ratings: Vec<GoogleSafetyRating>,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct GoogleCandidate {
+    /// Absent when the candidate was stopped by safety filters
+    #[serde(default)]
     content: GoogleContent,
     #[serde(default)]
     finish_reason: Option<String>,
-    #[allow(dead_code)]
     #[serde(default)]
-    safety_ratings: Option<Vec<GoogleSafetyRating>>,
+    safety_ratings: Vec<GoogleSafetyRating>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -895,14 +1028,17 @@ struct GoogleUsageMetadata {
     candidates_token_count: Option<i64>,
     #[serde(default)]
     total_token_count: Option<i64>,
+    #[serde(default)]
+    cached_content_token_count: Option<i64>,
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
-#[allow(dead_code)]
 struct GoogleSafetyRating {
     category: String,
     probability: String,
+    #[serde(default)]
+    blocked: bool,
 }
 
 #[cfg(test)]
@@ -989,6 +1125,113 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_safety_settings_in_request_body() {
+        let provider = GoogleProvider::new(GoogleConfig::google_ai("google-1", "test-key")).unwrap();
+        let request = GatewayRequest::builder()
+            .model("gemini-1.5-pro")
+            .message(gateway_core::ChatMessage::user("Hello"))
+            .safety_setting(gateway_core::SafetySetting::new(
+                "HARM_CATEGORY_HATE_SPEECH",
+                "BLOCK_ONLY_HIGH",
+            ))
+            .safety_setting(gateway_core::SafetySetting::new(
+                "HARM_CATEGORY_DANGEROUS_CONTENT",
+                "BLOCK_NONE",
+            ))
+            .build()
+            .unwrap();
+
+        let body = serde_json::to_value(provider.transform_request(&request)).unwrap();
+        assert_eq!(
+            body["safetySettings"],
+            serde_json::json!([
+                {"category": "HARM_CATEGORY_HATE_SPEECH", "threshold": "BLOCK_ONLY_HIGH"},
+                {"category": "HARM_CATEGORY_DANGEROUS_CONTENT", "threshold": "BLOCK_NONE"}
+            ])
+        );
+
+        let without = GatewayRequest::builder()
+            .model("gemini-1.5-pro")
+            .message(gateway_core::ChatMessage::user("Hello"))
+            .build()
+            .unwrap();
+        let body = serde_json::to_value(provider.transform_request(&without)).unwrap();
+        assert!(body.get("safetySettings").is_none());
+    }
+
+    #[test]
+    fn test_penalties_in_generation_config() {
+        let provider = GoogleProvider::new(GoogleConfig::google_ai("google-1", "test-key")).unwrap();
+        let request = GatewayRequest::builder()
+            .model("gemini-1.5-pro")
+            .message(gateway_core::ChatMessage::user("Hello"))
+            .frequency_penalty(0.5)
+            .presence_penalty(-1.0)
+            .build()
+            .unwrap();
+
+        let body = serde_json::to_value(provider.transform_request(&request)).unwrap();
+        assert_eq!(body["generationConfig"]["frequencyPenalty"], 0.5);
+        assert_eq!(body["generationConfig"]["presencePenalty"], -1.0);
+    }
+
+    #[test]
+    fn test_safety_stopped_candidate_is_content_filter() {
+        let provider = GoogleProvider::new(GoogleConfig::google_ai("google-1", "test-key")).unwrap();
+        let response: GoogleResponse = serde_json::from_value(serde_json::json!({
+            "candidates": [{
+                "finishReason": "SAFETY",
+                "safetyRatings": [
+                    {"category": "HARM_CATEGORY_HARASSMENT", "probability": "NEGLIGIBLE"},
+                    {"category": "HARM_CATEGORY_HATE_SPEECH", "probability": "HIGH", "blocked": true}
+                ]
+            }]
+        }))
+        .unwrap();
+
+        let err = provider
+            .transform_response(response, "gemini-1.5-pro")
+            .unwrap_err();
+        assert_eq!(err.error_code(), "content_filter");
+        assert!(err.to_string().contains("HARM_CATEGORY_HATE_SPEECH (HIGH)"));
+        match err {
+            GatewayError::ContentFilter { categories, .. } => {
+                assert_eq!(categories, vec!["HARM_CATEGORY_HATE_SPEECH".to_string()]);
+            }
+            other => unreachable!("expected content filter, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_blocked_prompt_is_content_filter() {
+        let provider = GoogleProvider::new(GoogleConfig::google_ai("google-1", "test-key")).unwrap();
+        let response: GoogleResponse = serde_json::from_value(serde_json::json!({
+            "promptFeedback": {
+                "blockReason": "SAFETY",
+                "safetyRatings": [
+                    {"category": "HARM_CATEGORY_DANGEROUS_CONTENT", "probability": "MEDIUM"},
+                    {"category": "HARM_CATEGORY_HARASSMENT", "probability": "LOW"}
+                ]
+            },
+            "usageMetadata": {"promptTokenCount": 8}
+        }))
+        .unwrap();
+
+        let err = provider
+            .transform_response(response, "gemini-1.5-pro")
+            .unwrap_err();
+        assert_eq!(err.error_code(), "content_filter");
+        assert!(!err.is_retryable());
+        assert!(err.to_string().contains("prompt blocked (SAFETY)"));
+        match err {
+            GatewayError::ContentFilter { categories, .. } => {
+                assert_eq!(categories, vec!["HARM_CATEGORY_DANGEROUS_CONTENT".to_string()]);
+            }
+            other => unreachable!("expected content filter, got {other:?}"),
+        }
+    }
+
     #[test]
     fn test_provider_creation_google_ai() {
         let config = GoogleConfig::google_ai("google-1", "test-key");
@@ -1022,6 +1265,9 @@ mod tests {
             location: "us-central1".to_string(),
             timeout: Duration::from_secs(120),
             models: vec![],
+            default_headers: DefaultHeaders::default(),
+            egress: EgressPolicy::default(),
+            params: ParamFilter::default(),
         };
 
         let result = GoogleProvider::new(config);
@@ -1039,6 +1285,9 @@ mod tests {
             location: "us-central1".to_string(),
             timeout: Duration::from_secs(120),
             models: vec![],
+            default_headers: DefaultHeaders::default(),
+            egress: EgressPolicy::default(),
+            params: ParamFilter::default(),
         };
 
         let result = GoogleProvider::new(config);
@@ -0,0 +1,167 @@
+//! Static default headers for provider HTTP clients.
+//!
+//! Some deployments put a proxy or API gateway in front of a provider that
+//! expects extra headers, such as an organization ID, on every call:
+//!
+//! ```yaml
+//! providers:
+//!   - type: openai
+//!     id: openai
+//!     api_key: "..."
+//!     headers:
+//!       X-Org-Id: acme
+//! ```
+//!
+//! Default headers fill in only what a request does not set itself, so
+//! per-request headers always win. Authentication and message framing
+//! headers are rejected outright, as a default for them would either leak
+//! into every request or be silently ignored.
+
+use gateway_core::GatewayError;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::ClientBuilder;
+use std::collections::HashMap;
+
+/// Longest accepted header name
+const MAX_NAME_LEN: usize = 64;
+
+/// Longest accepted header value
+const MAX_VALUE_LEN: usize = 1024;
+
+/// Headers that carry credentials or are set by the provider per request
+const RESERVED: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "x-api-key",
+    "api-key",
+    "x-goog-api-key",
+    "anthropic-version",
+    "cookie",
+    "host",
+    "content-type",
+    "content-length",
+    "transfer-encoding",
+    "connection",
+];
+
+/// Prefixes of reserved headers (AWS `SigV4` signing)
+const RESERVED_PREFIXES: &[&str] = &["x-amz-"];
+
+/// Validated headers sent on every request to a provider
+#[derive(Debug, Clone, Default)]
+pub struct DefaultHeaders {
+    headers: HeaderMap,
+}
+
+impl DefaultHeaders {
+    /// Validate configured headers
+    ///
+    /// Names must be 1-64 ASCII letters, digits or `-`, and not a reserved
+    /// authentication or framing header. Values must be at most 1024
+    /// printable ASCII characters.
+    ///
+    /// # Errors
+    /// Returns a configuration error naming the first invalid header
+    pub fn new(provider_id: &str, headers: &HashMap<String, String>) -> Result<Self, GatewayError> {
+        let invalid = |name: &str, reason: &str| GatewayError::Configuration {
+            message: format!("Invalid default header '{name}' for provider '{provider_id}': {reason}"),
+        };
+
+        let mut map = HeaderMap::with_capacity(headers.len());
+        for (name, value) in headers {
+            if name.is_empty()
+                || name.len() > MAX_NAME_LEN
+                || !name.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')
+            {
+                return Err(invalid(name, "names must be 1-64 letters, digits or '-'"));
+            }
+            let lower = name.to_ascii_lowercase();
+            if RESERVED.contains(&lower.as_str())
+                || RESERVED_PREFIXES.iter().any(|prefix| lower.starts_with(prefix))
+            {
+                return Err(invalid(name, "header is set by the gateway and cannot be overridden"));
+            }
+            if value.len() > MAX_VALUE_LEN || !value.bytes().all(|b| (0x20..0x7f).contains(&b)) {
+                return Err(invalid(
+                    name,
+                    "values must be at most 1024 printable ASCII characters",
+                ));
+            }
+
+            let name = HeaderName::from_bytes(lower.as_bytes())
+                .map_err(|e| invalid(&lower, &e.to_string()))?;
+            let value = HeaderValue::from_str(value).map_err(|e| invalid(&lower, &e.to_string()))?;
+            map.insert(name, value);
+        }
+        Ok(Self { headers: map })
+    }
+
+    /// Whether no headers are configured
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.headers.is_empty()
+    }
+
+    /// Configured value of a header
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|value| value.to_str().ok())
+    }
+
+    /// Apply these headers to an HTTP client builder
+    ///
+    /// The client only adds a default header when the request has not set
+    /// that header itself.
+    pub fn configure(&self, builder: ClientBuilder) -> ClientBuilder {
+        if self.is_empty() {
+            builder
+        } else {
+            builder.default_headers(self.headers.clone())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(name, value)| ((*name).to_string(), (*value).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_accepts_custom_headers() {
+        let defaults =
+            DefaultHeaders::new("openai", &headers(&[("X-Org-Id", "acme"), ("X-Team", "search ml")]))
+                .unwrap();
+        assert_eq!(defaults.get("x-org-id"), Some("acme"));
+        assert_eq!(defaults.get("x-team"), Some("search ml"));
+        assert!(DefaultHeaders::new("openai", &HashMap::new()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_rejects_reserved_headers() {
+        for name in ["Authorization", "x-api-key", "Content-Length", "X-Amz-Date"] {
+            let err = DefaultHeaders::new("openai", &headers(&[(name, "value")])).unwrap_err();
+            assert!(err.to_string().contains("cannot be overridden"), "{name}: {err}");
+        }
+    }
+
+    #[test]
+    fn test_rejects_unsafe_names_and_values() {
+        for (name, value) in [
+            ("X Org", "acme"),
+            ("X-Org:Id", "acme"),
+            ("", "acme"),
+            ("X-Org-Id", "acme\r\nX-Injected: 1"),
+            ("X-Org-Id", "caf\u{e9}"),
+        ] {
+            assert!(DefaultHeaders::new("openai", &headers(&[(name, value)])).is_err());
+        }
+        let long = "a".repeat(MAX_VALUE_LEN + 1);
+        assert!(DefaultHeaders::new("openai", &headers(&[("X-Org-Id", &long)])).is_err());
+    }
+}
@@ -15,7 +15,14 @@
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
 
+pub mod egress;
+pub mod factory;
+pub mod headers;
+pub mod params;
+pub mod pool;
 pub mod registry;
+mod context_length;
+mod transport;
 
 #[cfg(feature = "openai")]
 pub mod openai;
@@ -33,6 +40,11 @@ pub mod google;
 pub mod bedrock;
 
 // Re-export main types
+pub use egress::EgressPolicy;
+pub use factory::{ProviderConfig, ProviderDefinition, RegistryBuilder};
+pub use headers::DefaultHeaders;
+pub use params::{ParamFilter, ParamRules};
+pub use pool::{ConnectionWarmer, PoolConfig};
 pub use registry::{ProviderEntry, ProviderRegistry};
 
 #[cfg(feature = "openai")]
@@ -49,3 +61,21 @@ pub use google::{GoogleApiType, GoogleConfig, GoogleProvider};
 
 #[cfg(feature = "bedrock")]
 pub use bedrock::{BedrockConfig, BedrockProvider, ModelFamily as BedrockModelFamily};
+
+/// Provider features compiled into this build
+#[must_use]
+pub fn compiled_provider_features() -> Vec<&'static str> {
+    [
+        ("openai", cfg!(feature = "openai")),
+        ("anthropic", cfg!(feature = "anthropic")),
+        ("azure", cfg!(feature = "azure")),
+        ("google", cfg!(feature = "google")),
+        ("bedrock", cfg!(feature = "bedrock")),
+        ("vllm", cfg!(feature = "vllm")),
+        ("ollama", cfg!(feature = "ollama")),
+        ("together", cfg!(feature = "together")),
+    ]
+    .into_iter()
+    .filter_map(|(name, enabled)| enabled.then_some(name))
+    .collect()
+}
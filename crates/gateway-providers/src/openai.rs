@@ -4,21 +4,43 @@
 
 use async_stream::try_stream;
 use async_trait::async_trait;
+use dashmap::DashMap;
 use futures::stream::BoxStream;
 use futures_util::StreamExt;
 use gateway_core::{
+    AudioContent, AudioOutputOptions, BatchItem, BatchItemResult, BatchProgress, BatchProvider, BatchRequestCounts, BatchStatus,
     ChatChunk, ChatMessage, Choice, ChunkChoice, ChunkDelta, FinishReason, FunctionCall,
-    GatewayError, GatewayRequest, GatewayResponse, HealthStatus, LLMProvider, MessageContent,
-    MessageRole, ModelInfo, ProviderCapabilities, ProviderType, ToolCall, Usage,
+    ConnectionPoolStats, Embedding, EmbeddingProvider, EmbeddingRequest, EmbeddingResponse,
+    EmbeddingUsage, GatewayError, GatewayRequest, GatewayResponse, HealthStatus, ImageData,
+    ImageProvider, ImageRequest, ImageResponse, LLMProvider, MessageContent, MessageRole, ModelInfo, ProviderCapabilities, ProviderRateLimits,
+    ProviderType, RequestMetadata, ToolCall, Usage, is_reasoning_model,
 };
 use gateway_core::response::ResponseMessage;
+use gateway_core::streaming::StreamOptions;
+use crate::egress::EgressPolicy;
+use crate::headers::DefaultHeaders;
+use crate::params::ParamFilter;
+use crate::pool::{ConnectionWarmer, PoolConfig};
+use crate::{context_length, transport};
 use reqwest::Client;
-use reqwest_eventsource::{Event, EventSource};
 use secrecy::{ExposeSecret, SecretString};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::time::Duration;
 use tracing::{debug, error, trace, warn};
 
+/// Header selecting the organization a request is billed to
+const ORGANIZATION_HEADER: &str = "OpenAI-Organization";
+
+/// Header selecting the project a request is billed to
+const PROJECT_HEADER: &str = "OpenAI-Project";
+
+/// Metadata tag overriding the organization for one request
+pub const ORGANIZATION_TAG: &str = "openai_organization";
+
+/// Metadata tag overriding the project for one request
+pub const PROJECT_TAG: &str = "openai_project";
+
 /// OpenAI provider configuration
 #[derive(Debug, Clone)]
 pub struct OpenAIConfig {
@@ -30,10 +52,26 @@ pub struct OpenAIConfig {
     pub base_url: String,
     /// Organization ID (optional)
     pub organization_id: Option<String>,
+    /// Project ID (optional)
+    pub project_id: Option<String>,
+    /// Organizations a request may select with [`ORGANIZATION_TAG`]
+    pub allowed_organizations: Vec<String>,
+    /// Projects a request may select with [`PROJECT_TAG`]
+    pub allowed_projects: Vec<String>,
     /// Request timeout
     pub timeout: Duration,
     /// Supported models
     pub models: Vec<ModelInfo>,
+    /// Reported provider type (differs for OpenAI-compatible backends)
+    pub provider_type: ProviderType,
+    /// Connection pool settings
+    pub pool: PoolConfig,
+    /// Headers sent on every request
+    pub default_headers: DefaultHeaders,
+    /// Hosts and networks the client may connect to
+    pub egress: EgressPolicy,
+    /// Request parameters the provider may receive
+    pub params: ParamFilter,
 }
 
 impl OpenAIConfig {
@@ -45,8 +83,16 @@ impl OpenAIConfig {
             api_key: SecretString::new(api_key.into()),
             base_url: "https://api.openai.com".to_string(),
             organization_id: None,
+            project_id: None,
+            allowed_organizations: Vec::new(),
+            allowed_projects: Vec::new(),
             timeout: Duration::from_secs(120),
             models: Self::default_models(),
+            provider_type: ProviderType::OpenAI,
+            pool: PoolConfig::default(),
+            default_headers: DefaultHeaders::default(),
+            egress: EgressPolicy::default(),
+            params: ParamFilter::default(),
         }
     }
 
@@ -64,6 +110,27 @@ impl OpenAIConfig {
         self
     }
 
+    /// Set the project ID
+    #[must_use]
+    pub fn with_project(mut self, project_id: impl Into<String>) -> Self {
+        self.project_id = Some(project_id.into());
+        self
+    }
+
+    /// Let requests select one of these organizations
+    #[must_use]
+    pub fn with_allowed_organizations(mut self, organizations: Vec<String>) -> Self {
+        self.allowed_organizations = organizations;
+        self
+    }
+
+    /// Let requests select one of these projects
+    #[must_use]
+    pub fn with_allowed_projects(mut self, projects: Vec<String>) -> Self {
+        self.allowed_projects = projects;
+        self
+    }
+
     /// Set the timeout
     #[must_use]
     pub fn with_timeout(mut self, timeout: Duration) -> Self {
@@ -78,6 +145,44 @@ impl OpenAIConfig {
         self
     }
 
+    /// Set the reported provider type
+    ///
+    /// Used when pointing this provider at an OpenAI-compatible backend such
+    /// as vLLM or Ollama.
+    #[must_use]
+    pub fn with_provider_type(mut self, provider_type: ProviderType) -> Self {
+        self.provider_type = provider_type;
+        self
+    }
+
+    /// Set the connection pool settings
+    #[must_use]
+    pub fn with_pool(mut self, pool: PoolConfig) -> Self {
+        self.pool = pool;
+        self
+    }
+
+    /// Set headers sent on every request
+    #[must_use]
+    pub fn with_default_headers(mut self, headers: DefaultHeaders) -> Self {
+        self.default_headers = headers;
+        self
+    }
+
+    /// Set the hosts and networks the client may connect to
+    #[must_use]
+    pub fn with_egress(mut self, egress: EgressPolicy) -> Self {
+        self.egress = egress;
+        self
+    }
+
+    /// Set the request parameters the provider may receive
+    #[must_use]
+    pub fn with_params(mut self, params: ParamFilter) -> Self {
+        self.params = params;
+        self
+    }
+
     /// Default OpenAI models
     #[must_use]
     pub fn default_models() -> Vec<ModelInfo> {
@@ -107,6 +212,9 @@ impl OpenAIConfig {
                 .with_context_length(16_385)
                 .with_max_output_tokens(4_096)
                 .with_pricing(0.0005, 0.0015),
+            ModelInfo::new("gpt-image-1").with_name("GPT Image 1"),
+            ModelInfo::new("dall-e-3").with_name("DALL·E 3"),
+            ModelInfo::new("dall-e-2").with_name("DALL·E 2"),
         ]
     }
 }
@@ -116,6 +224,8 @@ pub struct OpenAIProvider {
     config: OpenAIConfig,
     client: Client,
     capabilities: ProviderCapabilities,
+    rate_limits: DashMap<String, ProviderRateLimits>,
+    warmer: Option<ConnectionWarmer>,
 }
 
 impl OpenAIProvider {
@@ -124,11 +234,15 @@ impl OpenAIProvider {
     /// # Errors
     /// Returns error if HTTP client cannot be created
     pub fn new(config: OpenAIConfig) -> Result<Self, GatewayError> {
-        let client = Client::builder()
-            .timeout(config.timeout)
-            .pool_max_idle_per_host(100)
+        let builder = config.pool.configure(Client::builder().timeout(config.timeout));
+        config.egress.check_url(&config.base_url)?;
+        let builder = config.egress.configure(builder);
+        let client = config
+            .default_headers
+            .configure(builder)
             .build()
             .map_err(|e| GatewayError::internal(format!("Failed to create HTTP client: {e}")))?;
+        let warmer = ConnectionWarmer::new(client.clone(), &config.base_url, &config.pool);
 
         Ok(Self {
             config,
@@ -145,7 +259,10 @@ impl OpenAIProvider {
                 max_context_length: Some(128_000),
                 max_output_tokens: Some(16_384),
                 parallel_tool_calls: true,
+                audio_output: true,
             },
+            rate_limits: DashMap::new(),
+            warmer,
         })
     }
 
@@ -154,6 +271,177 @@ impl OpenAIProvider {
         format!("{}/v1/chat/completions", self.config.base_url)
     }
 
+    /// Get the image generations endpoint URL
+    fn images_url(&self) -> String {
+        format!("{}/v1/images/generations", self.config.base_url)
+    }
+
+    /// Get the embeddings endpoint URL
+    fn embeddings_url(&self) -> String {
+        format!("{}/v1/embeddings", self.config.base_url)
+    }
+
+    /// Get the files endpoint URL
+    fn files_url(&self) -> String {
+        format!("{}/v1/files", self.config.base_url)
+    }
+
+    /// Get the batches endpoint URL
+    fn batches_url(&self) -> String {
+        format!("{}/v1/batches", self.config.base_url)
+    }
+
+    /// Add the organization and project headers
+    ///
+    /// A request may select a different organization or project with the
+    /// [`ORGANIZATION_TAG`] and [`PROJECT_TAG`] metadata tags, but only one
+    /// the provider allows.
+    fn account_headers(
+        &self,
+        mut req_builder: reqwest::RequestBuilder,
+        metadata: Option<&RequestMetadata>,
+    ) -> Result<reqwest::RequestBuilder, GatewayError> {
+        let organization = self.account(
+            metadata,
+            ORGANIZATION_TAG,
+            self.config.organization_id.as_deref(),
+            &self.config.allowed_organizations,
+        )?;
+        let project = self.account(
+            metadata,
+            PROJECT_TAG,
+            self.config.project_id.as_deref(),
+            &self.config.allowed_projects,
+        )?;
+
+        if let Some(organization) = organization {
+            req_builder = req_builder.header(ORGANIZATION_HEADER, organization);
+        }
+        if let Some(project) = project {
+            req_builder = req_builder.header(PROJECT_HEADER, project);
+        }
+        Ok(req_builder)
+    }
+
+    /// The configured account, or the one the request selects with `tag`
+    fn account<'a>(
+        &self,
+        metadata: Option<&'a RequestMetadata>,
+        tag: &str,
+        configured: Option<&'a str>,
+        allowed: &[String],
+    ) -> Result<Option<&'a str>, GatewayError> {
+        let Some(requested) = metadata.and_then(|m| m.tags.get(tag)) else {
+            return Ok(configured);
+        };
+        if configured == Some(requested.as_str()) || allowed.contains(requested) {
+            return Ok(Some(requested));
+        }
+        Err(GatewayError::validation(
+            format!(
+                "'{requested}' is not an allowed {} for provider {}",
+                tag.trim_start_matches("openai_"),
+                self.config.id
+            ),
+            Some(format!("metadata.tags.{tag}")),
+            "account_not_allowed",
+        ))
+    }
+
+    /// Send a Files or Batch API request, mapping error statuses to errors
+    async fn send_batch_api(
+        &self,
+        req_builder: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, GatewayError> {
+        let req_builder = req_builder
+            .header("Authorization", format!("Bearer {}", self.config.api_key.expose_secret()));
+        let req_builder = self.account_headers(req_builder, None)?;
+
+        let response = req_builder.send().await.map_err(|e| {
+            GatewayError::provider(
+                &self.config.id,
+                format!("Request failed: {e}"),
+                None,
+                e.is_timeout() || e.is_connect(),
+            )
+        })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_body = response.text().await.unwrap_or_default();
+            let retryable = status.as_u16() >= 500 || status.as_u16() == 429;
+
+            error!(
+                provider = %self.config.id,
+                status = %status,
+                error = %error_body,
+                "OpenAI batch API error"
+            );
+
+            return Err(GatewayError::provider(
+                &self.config.id,
+                error_body,
+                Some(status.as_u16()),
+                retryable,
+            ));
+        }
+
+        Ok(response)
+    }
+
+    /// Fetch a batch object
+    async fn get_batch(&self, batch_id: &str) -> Result<OpenAIBatch, GatewayError> {
+        self.send_batch_api(self.client.get(format!("{}/{batch_id}", self.batches_url())))
+            .await?
+            .json()
+            .await
+            .map_err(|e| transport::body_error(&self.config.id, &e))
+    }
+
+    /// Download a file's content
+    async fn file_content(&self, file_id: &str) -> Result<String, GatewayError> {
+        self.send_batch_api(
+            self.client
+                .get(format!("{}/{file_id}/content", self.files_url())),
+        )
+        .await?
+        .text()
+        .await
+        .map_err(|e| transport::body_error(&self.config.id, &e))
+    }
+
+    /// Convert one line of a batch output or error file
+    fn transform_batch_line(&self, line: OpenAIBatchOutputLine) -> BatchItemResult {
+        if let Some(error) = line.error {
+            return BatchItemResult::failure(
+                line.custom_id,
+                error.code.unwrap_or_else(|| "batch_error".to_string()),
+                error.message,
+            );
+        }
+
+        let Some(response) = line.response else {
+            return BatchItemResult::failure(line.custom_id, "batch_error", "Missing response");
+        };
+        if !(200..300).contains(&response.status_code) {
+            let message = response
+                .body
+                .pointer("/error/message")
+                .and_then(serde_json::Value::as_str)
+                .map_or_else(|| response.body.to_string(), str::to_string);
+            return BatchItemResult::failure(line.custom_id, "provider_error", message);
+        }
+
+        match serde_json::from_value::<OpenAIResponse>(response.body) {
+            Ok(body) => BatchItemResult::success(line.custom_id, self.transform_response(body, None)),
+            Err(e) => BatchItemResult::failure(
+                line.custom_id,
+                "provider_error",
+                format!("Failed to parse response: {e}"),
+            ),
+        }
+    }
+
     /// Transform gateway request to OpenAI format
     fn transform_request(&self, request: &GatewayRequest) -> OpenAIRequest {
         let messages: Vec<OpenAIMessage> = request
@@ -162,16 +450,25 @@ impl OpenAIProvider {
             .map(OpenAIMessage::from_gateway_message)
             .collect();
 
+        // Reasoning models reject `max_tokens`; others expect it
+        let (max_tokens, max_completion_tokens) = if is_reasoning_model(&request.model) {
+            (None, request.token_limit())
+        } else {
+            (request.token_limit(), None)
+        };
+
         OpenAIRequest {
             model: request.model.clone(),
             messages,
             temperature: request.temperature,
-            max_tokens: request.max_tokens,
+            max_tokens,
+            max_completion_tokens,
             top_p: request.top_p,
             frequency_penalty: request.frequency_penalty,
             presence_penalty: request.presence_penalty,
             stop: request.stop.clone(),
             stream: Some(request.stream),
+            stream_options: None,
             n: request.n,
             seed: request.seed,
             user: request.user.clone(),
@@ -204,11 +501,25 @@ impl OpenAIProvider {
             response_format: request.response_format.as_ref().map(|rf| OpenAIResponseFormat {
                 format_type: rf.format_type.clone(),
             }),
+            store: request.store,
+            service_tier: request.service_tier.clone(),
+            metadata: request
+                .metadata
+                .as_ref()
+                .and_then(RequestMetadata::provider_metadata),
+            modalities: request.modalities.clone(),
+            audio: request.audio.clone(),
         }
     }
 
     /// Transform OpenAI response to gateway format
-    fn transform_response(&self, response: OpenAIResponse) -> GatewayResponse {
+    ///
+    /// OpenAI does not echo the audio format, so it is taken from the request.
+    fn transform_response(
+        &self,
+        response: OpenAIResponse,
+        audio_format: Option<&str>,
+    ) -> GatewayResponse {
         let choices: Vec<Choice> = response
             .choices
             .into_iter()
@@ -230,6 +541,13 @@ impl OpenAIProvider {
                             .collect()
                     }),
                     function_call: None,
+                    audio: c.message.audio.map(|audio| AudioContent {
+                        id: Some(audio.id),
+                        data: audio.data,
+                        format: audio_format.map(str::to_string),
+                        transcript: audio.transcript,
+                        expires_at: audio.expires_at,
+                    }),
                 },
                 finish_reason: c.finish_reason.map(|r| match r.as_str() {
                     "length" => FinishReason::Length,
@@ -247,52 +565,28 @@ impl OpenAIProvider {
             created: response.created,
             model: response.model,
             choices,
-            usage: response.usage.map_or_else(Usage::default, |u| Usage {
-                prompt_tokens: u.prompt_tokens,
-                completion_tokens: u.completion_tokens,
-                total_tokens: u.total_tokens,
-            }),
+            usage: response.usage.map_or_else(Usage::default, OpenAIUsage::into_usage),
             system_fingerprint: response.system_fingerprint,
+            service_tier: response.service_tier,
             provider: Some(self.config.id.clone()),
         }
     }
 
-}
-
-#[async_trait]
-impl LLMProvider for OpenAIProvider {
-    fn id(&self) -> &str {
-        &self.config.id
-    }
-
-    fn provider_type(&self) -> ProviderType {
-        ProviderType::OpenAI
-    }
-
-    async fn chat_completion(
+    /// Send a chat completion request, mapping error statuses
+    async fn send_completion(
         &self,
         request: &GatewayRequest,
-    ) -> Result<GatewayResponse, GatewayError> {
-        let openai_request = self.transform_request(request);
-
-        debug!(
-            provider = %self.config.id,
-            model = %request.model,
-            "Sending chat completion request to OpenAI"
-        );
-
-        let mut req_builder = self
+        openai_request: &OpenAIRequest,
+    ) -> Result<reqwest::Response, GatewayError> {
+        let req_builder = self
             .client
             .post(self.completions_url())
             .header("Authorization", format!("Bearer {}", self.config.api_key.expose_secret()))
             .header("Content-Type", "application/json");
-
-        if let Some(ref org_id) = self.config.organization_id {
-            req_builder = req_builder.header("OpenAI-Organization", org_id);
-        }
+        let req_builder = self.account_headers(req_builder, request.metadata.as_ref())?;
 
         let response = req_builder
-            .json(&openai_request)
+            .json(openai_request)
             .send()
             .await
             .map_err(|e| {
@@ -304,6 +598,10 @@ impl LLMProvider for OpenAIProvider {
                 )
             })?;
 
+        if let Some(limits) = ProviderRateLimits::from_headers(response.headers()) {
+            self.rate_limits.insert(request.model.clone(), limits);
+        }
+
         let status = response.status();
         if !status.is_success() {
             let error_body = response.text().await.unwrap_or_default();
@@ -316,32 +614,66 @@ impl LLMProvider for OpenAIProvider {
                 "OpenAI API error"
             );
 
-            return Err(GatewayError::provider(
+            let context_error = context_length::detect(
                 &self.config.id,
-                error_body,
-                Some(status.as_u16()),
-                retryable,
-            ));
+                status.as_u16(),
+                &error_body,
+                request,
+                &self.config.models,
+            );
+            return Err(context_error.unwrap_or_else(|| {
+                GatewayError::provider(&self.config.id, error_body, Some(status.as_u16()), retryable)
+            }));
         }
 
-        let openai_response: OpenAIResponse = response.json().await.map_err(|e| {
-            GatewayError::provider(
-                &self.config.id,
-                format!("Failed to parse response: {e}"),
-                None,
-                false,
-            )
-        })?;
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl LLMProvider for OpenAIProvider {
+    fn id(&self) -> &str {
+        &self.config.id
+    }
+
+    fn provider_type(&self) -> ProviderType {
+        self.config.provider_type
+    }
+
+    async fn chat_completion(
+        &self,
+        request: &GatewayRequest,
+    ) -> Result<GatewayResponse, GatewayError> {
+        let filtered = self.config.params.apply(&self.config.id, request)?;
+        let request = &*filtered;
+        let openai_request = self.transform_request(request);
+
+        debug!(
+            provider = %self.config.id,
+            model = %request.model,
+            "Sending chat completion request to OpenAI"
+        );
 
-        Ok(self.transform_response(openai_response))
+        let response = self.send_completion(request, &openai_request).await?;
+
+        let openai_response: OpenAIResponse = response
+            .json()
+            .await
+            .map_err(|e| transport::body_error(&self.config.id, &e))?;
+
+        let audio_format = request.audio.as_ref().map(|audio| audio.format.as_str());
+        Ok(self.transform_response(openai_response, audio_format))
     }
 
     async fn chat_completion_stream(
         &self,
         request: &GatewayRequest,
     ) -> Result<BoxStream<'static, Result<ChatChunk, GatewayError>>, GatewayError> {
+        let filtered = self.config.params.apply(&self.config.id, request)?;
+        let request = &*filtered;
         let mut openai_request = self.transform_request(request);
         openai_request.stream = Some(true);
+        openai_request.stream_options = request.stream_options.clone();
 
         debug!(
             provider = %self.config.id,
@@ -349,79 +681,53 @@ impl LLMProvider for OpenAIProvider {
             "Starting streaming chat completion to OpenAI"
         );
 
-        let mut req_builder = self
-            .client
-            .post(self.completions_url())
-            .header("Authorization", format!("Bearer {}", self.config.api_key.expose_secret()))
-            .header("Content-Type", "application/json");
-
-        if let Some(ref org_id) = self.config.organization_id {
-            req_builder = req_builder.header("OpenAI-Organization", org_id);
-        }
-
-        let req_builder = req_builder.json(&openai_request);
-
+        let response = self.send_completion(request, &openai_request).await?;
         let provider_id = self.config.id.clone();
+        let mut events = Box::pin(transport::sse_events(&provider_id, response));
 
         let stream = try_stream! {
-            let es = EventSource::new(req_builder).map_err(|e| {
-                GatewayError::streaming(format!("Failed to create event source: {e}"))
-            })?;
-
-            let mut es = Box::pin(es);
-
-            while let Some(event) = es.next().await {
-                match event {
-                    Ok(Event::Open) => {
-                        trace!(provider = %provider_id, "SSE connection opened");
-                    }
-                    Ok(Event::Message(message)) => {
-                        let data = message.data.trim();
-                        if data == "[DONE]" {
-                            trace!(provider = %provider_id, "SSE stream done");
-                            break;
-                        }
+            while let Some(event) = events.next().await {
+                let event = event?;
+                let data = event.data.trim();
+                if data == "[DONE]" {
+                    trace!(provider = %provider_id, "SSE stream done");
+                    break;
+                }
 
-                        match serde_json::from_str::<OpenAIChunk>(data) {
-                            Ok(chunk) => {
-                                let choices: Vec<ChunkChoice> = chunk
-                                    .choices
-                                    .into_iter()
-                                    .map(|c| ChunkChoice {
-                                        index: c.index,
-                                        delta: ChunkDelta {
-                                            role: c.delta.role.map(|_| MessageRole::Assistant),
-                                            content: c.delta.content,
-                                            tool_calls: None,
-                                            function_call: None,
-                                        },
-                                        finish_reason: c.finish_reason.map(|r| match r.as_str() {
-                                            "length" => FinishReason::Length,
-                                            "tool_calls" => FinishReason::ToolCalls,
-                                            _ => FinishReason::Stop,
-                                        }),
-                                        logprobs: None,
-                                    })
-                                    .collect();
-
-                                yield ChatChunk {
-                                    id: chunk.id,
-                                    object: chunk.object,
-                                    created: chunk.created,
-                                    model: chunk.model,
-                                    choices,
-                                    usage: None,
-                                    system_fingerprint: chunk.system_fingerprint,
-                                };
-                            }
-                            Err(e) => {
-                                warn!(provider = %provider_id, error = %e, "Failed to parse chunk");
-                            }
-                        }
+                match serde_json::from_str::<OpenAIChunk>(data) {
+                    Ok(chunk) => {
+                        let choices: Vec<ChunkChoice> = chunk
+                            .choices
+                            .into_iter()
+                            .map(|c| ChunkChoice {
+                                index: c.index,
+                                delta: ChunkDelta {
+                                    role: c.delta.role.map(|_| MessageRole::Assistant),
+                                    content: c.delta.content,
+                                    tool_calls: None,
+                                    function_call: None,
+                                },
+                                finish_reason: c.finish_reason.map(|r| match r.as_str() {
+                                    "length" => FinishReason::Length,
+                                    "tool_calls" => FinishReason::ToolCalls,
+                                    _ => FinishReason::Stop,
+                                }),
+                                logprobs: None,
+                            })
+                            .collect();
+
+                        yield ChatChunk {
+                            id: chunk.id,
+                            object: chunk.object,
+                            created: chunk.created,
+                            model: chunk.model,
+                            choices,
+                            usage: chunk.usage.map(OpenAIUsage::into_usage),
+                            system_fingerprint: chunk.system_fingerprint,
+                        };
                     }
                     Err(e) => {
-                        error!(provider = %provider_id, error = %e, "SSE error");
-                        Err(GatewayError::streaming(format!("SSE error: {e}")))?;
+                        warn!(provider = %provider_id, error = %e, "Failed to parse chunk");
                     }
                 }
             }
@@ -430,6 +736,77 @@ impl LLMProvider for OpenAIProvider {
         Ok(Box::pin(stream))
     }
 
+    fn supports_raw_stream(&self) -> bool {
+        true
+    }
+
+    async fn chat_completion_stream_raw(
+        &self,
+        request: &GatewayRequest,
+    ) -> Result<BoxStream<'static, Result<bytes::Bytes, GatewayError>>, GatewayError> {
+        let filtered = self.config.params.apply(&self.config.id, request)?;
+        let request = &*filtered;
+        let mut openai_request = self.transform_request(request);
+        openai_request.stream = Some(true);
+        openai_request.stream_options = request.stream_options.clone();
+
+        debug!(
+            provider = %self.config.id,
+            model = %request.model,
+            "Starting raw streaming chat completion to OpenAI"
+        );
+
+        let req_builder = self
+            .client
+            .post(self.completions_url())
+            .header("Authorization", format!("Bearer {}", self.config.api_key.expose_secret()))
+            .header("Content-Type", "application/json")
+            .header("Accept", "text/event-stream");
+        let req_builder = self.account_headers(req_builder, request.metadata.as_ref())?;
+
+        let response = req_builder
+            .json(&openai_request)
+            .send()
+            .await
+            .map_err(|e| {
+                GatewayError::provider(
+                    &self.config.id,
+                    format!("Request failed: {e}"),
+                    None,
+                    e.is_timeout() || e.is_connect(),
+                )
+            })?;
+
+        if let Some(limits) = ProviderRateLimits::from_headers(response.headers()) {
+            self.rate_limits.insert(request.model.clone(), limits);
+        }
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_body = response.text().await.unwrap_or_default();
+            let retryable = status.as_u16() >= 500 || status.as_u16() == 429;
+            error!(
+                provider = %self.config.id,
+                status = %status,
+                error = %error_body,
+                "OpenAI API error"
+            );
+            return Err(GatewayError::provider(
+                &self.config.id,
+                error_body,
+                Some(status.as_u16()),
+                retryable,
+            ));
+        }
+
+        let provider_id = self.config.id.clone();
+        let stream = response
+            .bytes_stream()
+            .map(move |chunk| chunk.map_err(|e| transport::body_error(&provider_id, &e)));
+
+        Ok(Box::pin(stream))
+    }
+
     async fn health_check(&self) -> HealthStatus {
         // Use models endpoint for health check
         let url = format!("{}/v1/models", self.config.base_url);
@@ -463,20 +840,284 @@ impl LLMProvider for OpenAIProvider {
     fn timeout(&self) -> Duration {
         self.config.timeout
     }
-}
 
-// OpenAI API types
+    fn rate_limits(&self, model: &str) -> Option<ProviderRateLimits> {
+        self.rate_limits.get(model).map(|limits| *limits)
+    }
 
-#[derive(Debug, Serialize)]
-struct OpenAIRequest {
-    model: String,
-    messages: Vec<OpenAIMessage>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    temperature: Option<f32>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    max_tokens: Option<u32>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    top_p: Option<f32>,
+    async fn warm_connections(&self) -> Option<ConnectionPoolStats> {
+        Some(self.warmer.as_ref()?.warm().await)
+    }
+
+    fn connection_pool_stats(&self) -> Option<ConnectionPoolStats> {
+        self.warmer.as_ref().map(ConnectionWarmer::stats)
+    }
+
+    fn as_image_provider(&self) -> Option<&dyn ImageProvider> {
+        // OpenAI-compatible servers reusing this provider don't serve images
+        (self.config.provider_type == ProviderType::OpenAI).then_some(self as &dyn ImageProvider)
+    }
+
+    fn connection_keepalive_interval(&self) -> Option<Duration> {
+        self.warmer.as_ref().map(ConnectionWarmer::keepalive_interval)
+    }
+
+    fn as_embedding_provider(&self) -> Option<&dyn EmbeddingProvider> {
+        self.capabilities
+            .embeddings
+            .then_some(self as &dyn EmbeddingProvider)
+    }
+
+    fn as_batch_provider(&self) -> Option<&dyn BatchProvider> {
+        // OpenAI-compatible servers reusing this provider have no Batch API
+        (self.config.provider_type == ProviderType::OpenAI).then_some(self as &dyn BatchProvider)
+    }
+}
+
+#[async_trait]
+impl ImageProvider for OpenAIProvider {
+    async fn generate_images(&self, request: &ImageRequest) -> Result<ImageResponse, GatewayError> {
+        debug!(
+            provider = %self.config.id,
+            model = %request.model,
+            "Sending image generation request to OpenAI"
+        );
+
+        let req_builder = self
+            .client
+            .post(self.images_url())
+            .header("Authorization", format!("Bearer {}", self.config.api_key.expose_secret()))
+            .header("Content-Type", "application/json");
+        let req_builder = self.account_headers(req_builder, None)?;
+
+        let response = req_builder.json(request).send().await.map_err(|e| {
+            GatewayError::provider(
+                &self.config.id,
+                format!("Request failed: {e}"),
+                None,
+                e.is_timeout() || e.is_connect(),
+            )
+        })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_body = response.text().await.unwrap_or_default();
+            let retryable = status.as_u16() >= 500 || status.as_u16() == 429;
+
+            error!(
+                provider = %self.config.id,
+                status = %status,
+                error = %error_body,
+                "OpenAI image API error"
+            );
+
+            return Err(GatewayError::provider(
+                &self.config.id,
+                error_body,
+                Some(status.as_u16()),
+                retryable,
+            ));
+        }
+
+        let images: OpenAIImageResponse = response.json().await.map_err(|e| {
+            GatewayError::provider(
+                &self.config.id,
+                format!("Failed to parse response: {e}"),
+                None,
+                false,
+            )
+        })?;
+
+        Ok(ImageResponse {
+            created: images.created,
+            data: images
+                .data
+                .into_iter()
+                .map(|image| ImageData {
+                    url: image.url,
+                    b64_json: image.b64_json,
+                    revised_prompt: image.revised_prompt,
+                })
+                .collect(),
+        })
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for OpenAIProvider {
+    async fn embed(&self, request: &EmbeddingRequest) -> Result<EmbeddingResponse, GatewayError> {
+        debug!(
+            provider = %self.config.id,
+            model = %request.model,
+            inputs = request.input.as_slice().len(),
+            "Sending embeddings request to OpenAI"
+        );
+
+        let req_builder = self
+            .client
+            .post(self.embeddings_url())
+            .header("Authorization", format!("Bearer {}", self.config.api_key.expose_secret()))
+            .header("Content-Type", "application/json");
+        let req_builder = self.account_headers(req_builder, None)?;
+
+        let response = req_builder.json(request).send().await.map_err(|e| {
+            GatewayError::provider(
+                &self.config.id,
+                format!("Request failed: {e}"),
+                None,
+                e.is_timeout() || e.is_connect(),
+            )
+        })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_body = response.text().await.unwrap_or_default();
+            let retryable = status.as_u16() >= 500 || status.as_u16() == 429;
+
+            error!(
+                provider = %self.config.id,
+                status = %status,
+                error = %error_body,
+                "OpenAI embeddings API error"
+            );
+
+            return Err(GatewayError::provider(
+                &self.config.id,
+                error_body,
+                Some(status.as_u16()),
+                retryable,
+            ));
+        }
+
+        let embeddings: OpenAIEmbeddingResponse = response
+            .json()
+            .await
+            .map_err(|e| transport::body_error(&self.config.id, &e))?;
+
+        Ok(EmbeddingResponse {
+            object: "list".to_string(),
+            data: embeddings
+                .data
+                .into_iter()
+                .map(|item| Embedding::new(item.index, item.embedding))
+                .collect(),
+            model: embeddings.model,
+            usage: EmbeddingUsage {
+                prompt_tokens: embeddings.usage.prompt_tokens,
+                total_tokens: embeddings.usage.total_tokens,
+            },
+            errors: Vec::new(),
+        })
+    }
+}
+
+#[async_trait]
+impl BatchProvider for OpenAIProvider {
+    async fn submit_batch(&self, requests: &[BatchItem]) -> Result<String, GatewayError> {
+        debug!(
+            provider = %self.config.id,
+            requests = requests.len(),
+            "Submitting batch to OpenAI"
+        );
+
+        let mut input = String::new();
+        for item in requests {
+            let line = OpenAIBatchInputLine {
+                custom_id: &item.custom_id,
+                method: "POST",
+                url: "/v1/chat/completions",
+                body: self.transform_request(&*self.config.params.apply(&self.config.id, &item.body)?),
+            };
+            let line = serde_json::to_string(&line).map_err(|e| {
+                GatewayError::internal(format!("Failed to serialize batch request: {e}"))
+            })?;
+            input.push_str(&line);
+            input.push('\n');
+        }
+
+        // The Files API only takes multipart uploads
+        let boundary = format!("gateway-batch-{}", uuid::Uuid::new_v4().simple());
+        let upload = format!(
+            "--{boundary}\r\n\
+             Content-Disposition: form-data; name=\"purpose\"\r\n\r\n\
+             batch\r\n\
+             --{boundary}\r\n\
+             Content-Disposition: form-data; name=\"file\"; filename=\"batch.jsonl\"\r\n\
+             Content-Type: application/jsonl\r\n\r\n\
+             {input}\r\n\
+             --{boundary}--\r\n"
+        );
+        let file: OpenAIFile = self
+            .send_batch_api(
+                self.client
+                    .post(self.files_url())
+                    .header("Content-Type", format!("multipart/form-data; boundary={boundary}"))
+                    .body(upload),
+            )
+            .await?
+            .json()
+            .await
+            .map_err(|e| transport::body_error(&self.config.id, &e))?;
+
+        let batch: OpenAIBatch = self
+            .send_batch_api(self.client.post(self.batches_url()).json(&serde_json::json!({
+                "input_file_id": file.id,
+                "endpoint": "/v1/chat/completions",
+                "completion_window": "24h",
+            })))
+            .await?
+            .json()
+            .await
+            .map_err(|e| transport::body_error(&self.config.id, &e))?;
+
+        Ok(batch.id)
+    }
+
+    async fn batch_status(&self, batch_id: &str) -> Result<BatchProgress, GatewayError> {
+        let batch = self.get_batch(batch_id).await?;
+        Ok(BatchProgress {
+            status: batch.status,
+            request_counts: batch.request_counts,
+        })
+    }
+
+    async fn batch_results(&self, batch_id: &str) -> Result<Vec<BatchItemResult>, GatewayError> {
+        let batch = self.get_batch(batch_id).await?;
+
+        let mut results = Vec::new();
+        for file_id in [batch.output_file_id, batch.error_file_id].into_iter().flatten() {
+            let content = self.file_content(&file_id).await?;
+            for line in content.lines().filter(|line| !line.trim().is_empty()) {
+                let line: OpenAIBatchOutputLine = serde_json::from_str(line).map_err(|e| {
+                    GatewayError::provider(
+                        &self.config.id,
+                        format!("Failed to parse batch output: {e}"),
+                        None,
+                        false,
+                    )
+                })?;
+                results.push(self.transform_batch_line(line));
+            }
+        }
+
+        Ok(results)
+    }
+}
+
+// OpenAI API types
+
+#[derive(Debug, Serialize)]
+struct OpenAIRequest {
+    model: String,
+    messages: Vec<OpenAIMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_completion_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     frequency_penalty: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -486,6 +1127,8 @@ struct OpenAIRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     stream: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    stream_options: Option<StreamOptions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     n: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     seed: Option<i64>,
@@ -497,6 +1140,16 @@ struct OpenAIRequest {
     tool_choice: Option<serde_json::Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     response_format: Option<OpenAIResponseFormat>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    store: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    service_tier: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    metadata: Option<BTreeMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    modalities: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    audio: Option<AudioOutputOptions>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -510,6 +1163,14 @@ struct OpenAIMessage {
     tool_calls: Option<Vec<OpenAIToolCall>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tool_call_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    audio: Option<OpenAIAudioRef>,
+}
+
+/// Reference to audio generated in an earlier assistant turn
+#[derive(Debug, Serialize, Deserialize)]
+struct OpenAIAudioRef {
+    id: String,
 }
 
 impl OpenAIMessage {
@@ -521,15 +1182,38 @@ impl OpenAIMessage {
             MessageRole::Tool => "tool",
         };
 
+        // Assistant audio is replayed by ID; OpenAI keeps the audio itself
+        let audio = match (&msg.role, &msg.content) {
+            (MessageRole::Assistant, MessageContent::Parts(parts)) => {
+                parts.iter().find_map(|p| match p {
+                    gateway_core::request::ContentPart::Audio { audio } => {
+                        audio.id.clone().map(|id| OpenAIAudioRef { id })
+                    }
+                    _ => None,
+                })
+            }
+            _ => None,
+        };
+
         let content = match &msg.content {
             MessageContent::Text(s) => Some(serde_json::Value::String(s.clone())),
             MessageContent::Parts(parts) => {
                 let json_parts: Vec<serde_json::Value> = parts
                     .iter()
+                    .filter(|p| {
+                        audio.is_none()
+                            || !matches!(p, gateway_core::request::ContentPart::Audio { .. })
+                    })
                     .map(|p| match p {
                         gateway_core::request::ContentPart::Text { text } => {
                             serde_json::json!({"type": "text", "text": text})
                         }
+                        gateway_core::request::ContentPart::Audio { audio } => {
+                            serde_json::json!({
+                                "type": "text",
+                                "text": audio.transcript.as_deref().unwrap_or_default()
+                            })
+                        }
                         gateway_core::request::ContentPart::ImageUrl { image_url } => {
                             serde_json::json!({
                                 "type": "image_url",
@@ -545,7 +1229,7 @@ impl OpenAIMessage {
                         }
                     })
                     .collect();
-                Some(serde_json::Value::Array(json_parts))
+                (!json_parts.is_empty()).then_some(serde_json::Value::Array(json_parts))
             }
         };
 
@@ -566,6 +1250,7 @@ impl OpenAIMessage {
                     .collect()
             }),
             tool_call_id: msg.tool_call_id.clone(),
+            audio,
         }
     }
 }
@@ -615,6 +1300,8 @@ struct OpenAIResponse {
     choices: Vec<OpenAIChoice>,
     usage: Option<OpenAIUsage>,
     system_fingerprint: Option<String>,
+    #[serde(default)]
+    service_tier: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -630,6 +1317,18 @@ struct OpenAIResponseMessage {
     role: String,
     content: Option<String>,
     tool_calls: Option<Vec<OpenAIToolCall>>,
+    #[serde(default)]
+    audio: Option<OpenAIAudio>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAIAudio {
+    id: String,
+    data: String,
+    #[serde(default)]
+    transcript: Option<String>,
+    #[serde(default)]
+    expires_at: Option<i64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -637,6 +1336,37 @@ struct OpenAIUsage {
     prompt_tokens: u32,
     completion_tokens: u32,
     total_tokens: u32,
+    #[serde(default)]
+    prompt_tokens_details: Option<OpenAIPromptTokensDetails>,
+    #[serde(default)]
+    completion_tokens_details: Option<OpenAICompletionTokensDetails>,
+}
+
+impl OpenAIUsage {
+    /// Convert to gateway usage, keeping the optional token breakdowns
+    fn into_usage(self) -> Usage {
+        Usage {
+            prompt_tokens: self.prompt_tokens,
+            completion_tokens: self.completion_tokens,
+            total_tokens: self.total_tokens,
+            prompt_tokens_cached: self.prompt_tokens_details.and_then(|d| d.cached_tokens),
+            completion_tokens_reasoning: self
+                .completion_tokens_details
+                .and_then(|d| d.reasoning_tokens),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAIPromptTokensDetails {
+    #[serde(default)]
+    cached_tokens: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAICompletionTokensDetails {
+    #[serde(default)]
+    reasoning_tokens: Option<u32>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -646,7 +1376,6 @@ struct OpenAIChunk {
     created: i64,
     model: String,
     choices: Vec<OpenAIChunkChoice>,
-    #[allow(dead_code)]
     usage: Option<OpenAIUsage>,
     system_fingerprint: Option<String>,
 }
@@ -683,9 +1412,91 @@ struct OpenAIFunctionCallDelta {
     arguments: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+struct OpenAIImageResponse {
+    created: i64,
+    data: Vec<OpenAIImage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAIImage {
+    url: Option<String>,
+    b64_json: Option<String>,
+    revised_prompt: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAIEmbeddingResponse {
+    data: Vec<OpenAIEmbedding>,
+    model: String,
+    #[serde(default)]
+    usage: OpenAIEmbeddingUsage,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAIEmbedding {
+    index: usize,
+    embedding: Vec<f32>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct OpenAIEmbeddingUsage {
+    prompt_tokens: u32,
+    total_tokens: u32,
+}
+
+#[derive(Debug, Serialize)]
+struct OpenAIBatchInputLine<'a> {
+    custom_id: &'a str,
+    method: &'static str,
+    url: &'static str,
+    body: OpenAIRequest,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAIFile {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAIBatch {
+    id: String,
+    status: BatchStatus,
+    #[serde(default)]
+    request_counts: BatchRequestCounts,
+    #[serde(default)]
+    output_file_id: Option<String>,
+    #[serde(default)]
+    error_file_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAIBatchOutputLine {
+    custom_id: String,
+    #[serde(default)]
+    response: Option<OpenAIBatchOutputResponse>,
+    #[serde(default)]
+    error: Option<OpenAIBatchError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAIBatchOutputResponse {
+    status_code: u16,
+    body: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAIBatchError {
+    #[serde(default)]
+    code: Option<String>,
+    message: String,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use gateway_core::ContentPart;
+    use std::collections::HashMap;
 
     #[test]
     fn test_config_creation() {
@@ -731,4 +1542,787 @@ mod tests {
             "https://api.openai.com/v1/chat/completions"
         );
     }
+
+    #[test]
+    fn test_transform_response_usage_details() {
+        let provider =
+            OpenAIProvider::new(OpenAIConfig::new("test", "sk-test")).expect("create provider");
+        let response: OpenAIResponse = serde_json::from_str(
+            r#"{
+                "id": "chatcmpl-1",
+                "object": "chat.completion",
+                "created": 1700000000,
+                "model": "o1-mini",
+                "choices": [{
+                    "index": 0,
+                    "message": {"role": "assistant", "content": "42"},
+                    "finish_reason": "stop"
+                }],
+                "usage": {
+                    "prompt_tokens": 1200,
+                    "completion_tokens": 400,
+                    "total_tokens": 1600,
+                    "prompt_tokens_details": {"cached_tokens": 1024},
+                    "completion_tokens_details": {"reasoning_tokens": 320}
+                }
+            }"#,
+        )
+        .expect("parse response");
+
+        let gateway_response = provider.transform_response(response, None);
+        let usage = &gateway_response.usage;
+        assert_eq!(usage.prompt_tokens, 1200);
+        assert_eq!(usage.prompt_tokens_cached, Some(1024));
+        assert_eq!(usage.completion_tokens_reasoning, Some(320));
+
+        let json = serde_json::to_value(&gateway_response).expect("serialize response");
+        assert_eq!(json["usage"]["prompt_tokens_cached"], 1024);
+        assert_eq!(json["usage"]["completion_tokens_reasoning"], 320);
+    }
+
+    #[test]
+    fn test_token_limit_field_follows_model() {
+        let provider =
+            OpenAIProvider::new(OpenAIConfig::new("test", "sk-test")).expect("create provider");
+        let request = |model: &str| {
+            GatewayRequest::builder()
+                .model(model)
+                .message(ChatMessage::user("Hello"))
+                .max_tokens(256)
+                .build()
+                .expect("request")
+        };
+
+        let reasoning = serde_json::to_value(provider.transform_request(&request("o3-mini")))
+            .expect("serialize");
+        assert_eq!(reasoning["max_completion_tokens"], 256);
+        assert!(reasoning.get("max_tokens").is_none());
+
+        let standard = serde_json::to_value(provider.transform_request(&request("gpt-4o")))
+            .expect("serialize");
+        assert_eq!(standard["max_tokens"], 256);
+        assert!(standard.get("max_completion_tokens").is_none());
+
+        // A standard model given only the new field still gets `max_tokens`
+        let migrated = GatewayRequest::builder()
+            .model("gpt-4o")
+            .message(ChatMessage::user("Hello"))
+            .max_completion_tokens(128)
+            .build()
+            .expect("request");
+        let migrated = serde_json::to_value(provider.transform_request(&migrated)).expect("serialize");
+        assert_eq!(migrated["max_tokens"], 128);
+    }
+
+    #[test]
+    fn test_penalties_forwarded() {
+        let provider =
+            OpenAIProvider::new(OpenAIConfig::new("test", "sk-test")).expect("create provider");
+        let request = GatewayRequest::builder()
+            .model("gpt-4o")
+            .message(ChatMessage::user("Hello"))
+            .frequency_penalty(1.5)
+            .presence_penalty(-0.5)
+            .build()
+            .expect("request");
+
+        let body = serde_json::to_value(provider.transform_request(&request)).expect("serialize");
+        assert_eq!(body["frequency_penalty"], 1.5);
+        assert_eq!(body["presence_penalty"], -0.5);
+
+        let without = GatewayRequest::builder()
+            .model("gpt-4o")
+            .message(ChatMessage::user("Hello"))
+            .build()
+            .expect("request");
+        let body = serde_json::to_value(provider.transform_request(&without)).expect("serialize");
+        assert!(body.get("frequency_penalty").is_none());
+        assert!(body.get("presence_penalty").is_none());
+    }
+
+    #[test]
+    fn test_store_and_sanitized_metadata_forwarded() {
+        let provider =
+            OpenAIProvider::new(OpenAIConfig::new("test", "sk-test")).expect("create provider");
+        let mut tags: HashMap<String, String> = (0..20)
+            .map(|i| (format!("run_{i:02}"), "eval".to_string()))
+            .collect();
+        tags.insert("not valid".to_string(), "dropped".to_string());
+        let request = GatewayRequest::builder()
+            .model("gpt-4o")
+            .message(ChatMessage::user("Hello"))
+            .store(true)
+            .metadata(RequestMetadata {
+                tags,
+                ..RequestMetadata::default()
+            })
+            .build()
+            .expect("request");
+
+        let body = serde_json::to_value(provider.transform_request(&request)).expect("serialize");
+        assert_eq!(body["store"], true);
+        let metadata = body["metadata"].as_object().expect("metadata");
+        assert_eq!(metadata.len(), 16);
+        assert_eq!(metadata["run_00"], "eval");
+        assert!(!metadata.contains_key("run_16"));
+        assert!(!metadata.contains_key("not valid"));
+
+        let plain = GatewayRequest::builder()
+            .model("gpt-4o")
+            .message(ChatMessage::user("Hello"))
+            .build()
+            .expect("request");
+        let body = serde_json::to_value(provider.transform_request(&plain)).expect("serialize");
+        assert!(body.get("store").is_none());
+        assert!(body.get("metadata").is_none());
+    }
+
+    #[test]
+    fn test_service_tier_forwarded_and_echoed() {
+        let provider =
+            OpenAIProvider::new(OpenAIConfig::new("test", "sk-test")).expect("create provider");
+        let request = GatewayRequest::builder()
+            .model("o3")
+            .message(ChatMessage::user("Hello"))
+            .service_tier("flex")
+            .build()
+            .expect("request");
+        let body = serde_json::to_value(provider.transform_request(&request)).expect("serialize");
+        assert_eq!(body["service_tier"], "flex");
+
+        let response: OpenAIResponse = serde_json::from_str(
+            r#"{
+                "id": "chatcmpl-1",
+                "object": "chat.completion",
+                "created": 1700000000,
+                "model": "o3",
+                "service_tier": "flex",
+                "choices": [{
+                    "index": 0,
+                    "message": {"role": "assistant", "content": "Hi"},
+                    "finish_reason": "stop"
+                }]
+            }"#,
+        )
+        .expect("parse response");
+        let gateway_response = provider.transform_response(response, None);
+        assert_eq!(gateway_response.service_tier.as_deref(), Some("flex"));
+        let json = serde_json::to_value(&gateway_response).expect("serialize response");
+        assert_eq!(json["service_tier"], "flex");
+    }
+
+    #[test]
+    fn test_audio_output_round_trip() {
+        let provider =
+            OpenAIProvider::new(OpenAIConfig::new("test", "sk-test")).expect("create provider");
+        let earlier = AudioContent {
+            id: Some("audio_abc".to_string()),
+            data: "UklGRg==".to_string(),
+            format: Some("wav".to_string()),
+            transcript: Some("Hello!".to_string()),
+            expires_at: None,
+        };
+        let request = GatewayRequest::builder()
+            .model("gpt-4o-audio-preview")
+            .message(ChatMessage::user("Say hello"))
+            .message(ChatMessage {
+                content: MessageContent::Parts(vec![ContentPart::Audio { audio: earlier }]),
+                ..ChatMessage::assistant("")
+            })
+            .message(ChatMessage::user("Again"))
+            .audio_output("alloy", "wav")
+            .build()
+            .expect("request");
+
+        let body = serde_json::to_value(provider.transform_request(&request)).expect("serialize");
+        assert_eq!(body["modalities"], serde_json::json!(["text", "audio"]));
+        assert_eq!(body["audio"], serde_json::json!({"voice": "alloy", "format": "wav"}));
+        assert_eq!(body["messages"][1]["audio"], serde_json::json!({"id": "audio_abc"}));
+        assert!(body["messages"][1].get("content").is_none());
+
+        let response: OpenAIResponse = serde_json::from_str(
+            r#"{
+                "id": "chatcmpl-2",
+                "object": "chat.completion",
+                "created": 1700000000,
+                "model": "gpt-4o-audio-preview",
+                "choices": [{
+                    "index": 0,
+                    "message": {
+                        "role": "assistant",
+                        "content": null,
+                        "audio": {
+                            "id": "audio_def",
+                            "data": "UklGRg==",
+                            "expires_at": 1700003600,
+                            "transcript": "Hello again!"
+                        }
+                    },
+                    "finish_reason": "stop"
+                }]
+            }"#,
+        )
+        .expect("parse response");
+
+        let gateway_response = provider.transform_response(response, Some("wav"));
+        let parts = gateway_response.choices[0].message.parts();
+        let [ContentPart::Audio { audio }] = parts.as_slice() else {
+            panic!("expected a single audio part, got {parts:?}");
+        };
+        assert_eq!(audio.id.as_deref(), Some("audio_def"));
+        assert_eq!(audio.format.as_deref(), Some("wav"));
+        assert_eq!(audio.transcript.as_deref(), Some("Hello again!"));
+        assert_eq!(audio.expires_at, Some(1_700_003_600));
+    }
+
+    #[tokio::test]
+    async fn test_warm_pool_is_reused_by_requests() {
+        let body = r#"{"id":"c1","object":"chat.completion","created":1,"model":"gpt-4o","choices":[{"index":0,"message":{"role":"assistant","content":"hi"},"finish_reason":"stop"}],"usage":{"prompt_tokens":1,"completion_tokens":1,"total_tokens":2}}"#;
+        let (url, accepted) = crate::pool::test_server::start(body).await;
+        let config = OpenAIConfig::new("openai-1", "sk-test")
+            .with_base_url(&url)
+            .with_pool(PoolConfig::default().with_min_idle(2));
+        let provider = OpenAIProvider::new(config).expect("provider");
+
+        assert_eq!(provider.connection_pool_stats().map(|s| s.idle), Some(0));
+        let stats = provider.warm_connections().await.expect("warmup enabled");
+        assert!(stats.idle >= stats.min_idle);
+        assert_eq!(accepted.load(std::sync::atomic::Ordering::SeqCst), 2);
+
+        let request = GatewayRequest::builder()
+            .model("gpt-4o")
+            .message(ChatMessage::user("hi"))
+            .build()
+            .expect("request");
+        let (a, b) = tokio::join!(
+            provider.chat_completion(&request),
+            provider.chat_completion(&request)
+        );
+        a.expect("first response");
+        b.expect("second response");
+        assert_eq!(accepted.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_stream_options_forwarded_and_usage_parsed() {
+        use wiremock::matchers::{body_partial_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let sse = concat!(
+            "data: {\"id\":\"c1\",\"object\":\"chat.completion.chunk\",\"created\":1,\"model\":\"gpt-4o\",",
+            "\"choices\":[{\"index\":0,\"delta\":{\"content\":\"hi\"},\"finish_reason\":\"stop\"}]}\n\n",
+            "data: {\"id\":\"c1\",\"object\":\"chat.completion.chunk\",\"created\":1,\"model\":\"gpt-4o\",",
+            "\"choices\":[],\"usage\":{\"prompt_tokens\":4,\"completion_tokens\":1,\"total_tokens\":5}}\n\n",
+            "data: [DONE]\n\n",
+        );
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .and(body_partial_json(serde_json::json!({
+                "stream": true,
+                "stream_options": { "include_usage": true }
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(sse, "text/event-stream"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider =
+            OpenAIProvider::new(OpenAIConfig::new("openai-1", "sk-test").with_base_url(server.uri()))
+                .expect("provider");
+        let request = GatewayRequest::builder()
+            .model("gpt-4o")
+            .message(ChatMessage::user("hi"))
+            .stream(true)
+            .stream_options(StreamOptions { include_usage: true })
+            .build()
+            .expect("request");
+
+        let chunks: Vec<ChatChunk> = provider
+            .chat_completion_stream(&request)
+            .await
+            .expect("stream")
+            .map(|chunk| chunk.expect("chunk"))
+            .collect()
+            .await;
+
+        assert_eq!(chunks.len(), 2);
+        assert!(chunks[1].choices.is_empty());
+        assert_eq!(chunks[1].usage.as_ref().map(|u| u.total_tokens), Some(5));
+    }
+
+    #[tokio::test]
+    async fn test_organization_and_project_headers() {
+        use std::collections::HashMap;
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let completion = serde_json::json!({
+            "id": "c1", "object": "chat.completion", "created": 1, "model": "gpt-4o",
+            "choices": [{"index": 0, "message": {"role": "assistant", "content": "hi"}, "finish_reason": "stop"}],
+            "usage": {"prompt_tokens": 1, "completion_tokens": 1, "total_tokens": 2}
+        });
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .and(header("openai-organization", "org-default"))
+            .and(header("openai-project", "proj-default"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(completion.clone()))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .and(header("openai-organization", "org-research"))
+            .and(header("openai-project", "proj-default"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(completion))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let config = OpenAIConfig::new("openai-1", "sk-test")
+            .with_base_url(server.uri())
+            .with_organization("org-default")
+            .with_project("proj-default")
+            .with_allowed_organizations(vec!["org-research".to_string()]);
+        let provider = OpenAIProvider::new(config).expect("provider");
+
+        let request = |tags: &[(&str, &str)]| {
+            GatewayRequest::builder()
+                .model("gpt-4o")
+                .message(ChatMessage::user("hi"))
+                .metadata(RequestMetadata {
+                    tags: tags
+                        .iter()
+                        .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                        .collect::<HashMap<_, _>>(),
+                    ..RequestMetadata::default()
+                })
+                .build()
+                .expect("request")
+        };
+
+        provider.chat_completion(&request(&[])).await.expect("configured account");
+        provider
+            .chat_completion(&request(&[(ORGANIZATION_TAG, "org-research")]))
+            .await
+            .expect("allowlisted organization");
+
+        // Accounts outside the allowlist are rejected before sending
+        for tags in [[(ORGANIZATION_TAG, "org-other")], [(PROJECT_TAG, "proj-other")]] {
+            let err = provider.chat_completion(&request(&tags)).await.unwrap_err();
+            assert!(
+                matches!(&err, GatewayError::Validation { code, .. } if code == "account_not_allowed"),
+                "{err}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_default_headers_sent_on_chat_and_stream() {
+        use std::collections::HashMap;
+        use wiremock::matchers::{body_partial_json, header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .and(header("x-org-id", "acme"))
+            .and(header("authorization", "Bearer sk-test"))
+            .and(header("openai-organization", "org-request"))
+            .and(body_partial_json(serde_json::json!({ "stream": true })))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                "data: {\"id\":\"c1\",\"object\":\"chat.completion.chunk\",\"created\":1,\"model\":\"gpt-4o\",\"choices\":[{\"index\":0,\"delta\":{\"content\":\"hi\"},\"finish_reason\":\"stop\"}]}\n\ndata: [DONE]\n\n",
+                "text/event-stream",
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .and(header("x-org-id", "acme"))
+            .and(header("authorization", "Bearer sk-test"))
+            .and(header("openai-organization", "org-request"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "c1", "object": "chat.completion", "created": 1, "model": "gpt-4o",
+                "choices": [{"index": 0, "message": {"role": "assistant", "content": "hi"}, "finish_reason": "stop"}],
+                "usage": {"prompt_tokens": 1, "completion_tokens": 1, "total_tokens": 2}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let configured = HashMap::from([
+            ("X-Org-Id".to_string(), "acme".to_string()),
+            ("OpenAI-Organization".to_string(), "org-default".to_string()),
+        ]);
+        let headers = DefaultHeaders::new("openai-1", &configured).expect("headers");
+        let config = OpenAIConfig::new("openai-1", "sk-test")
+            .with_base_url(server.uri())
+            .with_organization("org-request")
+            .with_default_headers(headers);
+        let provider = OpenAIProvider::new(config).expect("provider");
+
+        let request = GatewayRequest::builder()
+            .model("gpt-4o")
+            .message(ChatMessage::user("hi"))
+            .build()
+            .expect("request");
+        provider.chat_completion(&request).await.expect("chat");
+
+        let request = GatewayRequest::builder()
+            .model("gpt-4o")
+            .message(ChatMessage::user("hi"))
+            .stream(true)
+            .build()
+            .expect("request");
+        let chunks: Vec<_> = provider
+            .chat_completion_stream(&request)
+            .await
+            .expect("stream")
+            .collect()
+            .await;
+        assert_eq!(chunks.len(), 1);
+        assert!(chunks[0].is_ok());
+
+        let configured = HashMap::from([("Authorization".to_string(), "Bearer other".to_string())]);
+        assert!(DefaultHeaders::new("openai-1", &configured).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_param_rules_filter_outbound_body() {
+        use crate::params::{ParamFilter, ParamRules};
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "c1", "object": "chat.completion", "created": 1, "model": "gpt-4o",
+                "choices": [{"index": 0, "message": {"role": "assistant", "content": "hi"}, "finish_reason": "stop"}],
+                "usage": {"prompt_tokens": 1, "completion_tokens": 1, "total_tokens": 2}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider_with = |rules: ParamRules| {
+            let params = ParamFilter::new("openai-1", &rules).expect("params");
+            let config = OpenAIConfig::new("openai-1", "sk-test")
+                .with_base_url(server.uri())
+                .with_params(params);
+            OpenAIProvider::new(config).expect("provider")
+        };
+        let request = GatewayRequest::builder()
+            .model("gpt-4o")
+            .message(ChatMessage::user("hi"))
+            .temperature(0.2)
+            .max_tokens(64)
+            .build()
+            .expect("request");
+
+        let denying = provider_with(ParamRules {
+            deny: vec!["temperature".to_string()],
+            ..ParamRules::default()
+        });
+        denying.chat_completion(&request).await.expect("chat");
+        let sent = server.received_requests().await.expect("recorded requests");
+        let body: serde_json::Value = serde_json::from_slice(&sent[0].body).expect("json body");
+        assert!(body.get("temperature").is_none());
+        assert_eq!(body["max_tokens"], 64);
+
+        let rejecting = provider_with(ParamRules {
+            allow: Some(vec!["max_tokens".to_string()]),
+            reject_unknown: true,
+            ..ParamRules::default()
+        });
+        let err = rejecting.chat_completion(&request).await.unwrap_err();
+        assert!(matches!(
+            err,
+            GatewayError::Validation { ref field, .. } if field.as_deref() == Some("temperature")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_generate_images_maps_urls_and_b64() {
+        use gateway_core::ImageResponseFormat;
+        use wiremock::matchers::{body_partial_json, header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/images/generations"))
+            .and(header("Authorization", "Bearer sk-test"))
+            .and(body_partial_json(serde_json::json!({
+                "model": "dall-e-3",
+                "prompt": "a lighthouse",
+                "size": "1024x1024",
+                "quality": "hd",
+                "n": 1
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "created": 1_700_000_000,
+                "data": [{
+                    "url": "https://images.example.com/1.png",
+                    "revised_prompt": "a lighthouse at dusk"
+                }]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/images/generations"))
+            .and(body_partial_json(serde_json::json!({ "response_format": "b64_json" })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "created": 1_700_000_001,
+                "data": [{ "b64_json": "aGVsbG8=" }, { "b64_json": "d29ybGQ=" }]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider =
+            OpenAIProvider::new(OpenAIConfig::new("openai-1", "sk-test").with_base_url(server.uri()))
+                .expect("provider");
+        let images = provider.as_image_provider().expect("image capability");
+
+        let request = ImageRequest::new("a lighthouse")
+            .with_model("dall-e-3")
+            .with_size("1024x1024")
+            .with_quality("hd")
+            .with_n(1);
+        let response = images.generate_images(&request).await.expect("url response");
+        assert_eq!(response.created, 1_700_000_000);
+        assert_eq!(response.data.len(), 1);
+        assert_eq!(response.data[0].url.as_deref(), Some("https://images.example.com/1.png"));
+        assert_eq!(response.data[0].revised_prompt.as_deref(), Some("a lighthouse at dusk"));
+        assert!(response.data[0].b64_json.is_none());
+
+        let request = ImageRequest::new("a lighthouse")
+            .with_n(2)
+            .with_response_format(ImageResponseFormat::B64Json);
+        let response = images.generate_images(&request).await.expect("b64 response");
+        assert_eq!(response.data.len(), 2);
+        assert_eq!(response.data[1].b64_json.as_deref(), Some("d29ybGQ="));
+        assert!(response.data[1].url.is_none());
+    }
+
+    #[test]
+    fn test_image_capability_only_for_openai() {
+        let provider = OpenAIProvider::new(OpenAIConfig::new("openai-1", "sk-test")).expect("provider");
+        assert!(provider.as_image_provider().is_some());
+
+        let provider = OpenAIProvider::new(
+            OpenAIConfig::new("vllm-1", "sk-test").with_provider_type(ProviderType::VLLM),
+        )
+        .expect("provider");
+        assert!(provider.as_image_provider().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_embeddings_response_is_parsed() {
+        const BODY: &str = concat!(
+            "{\"object\":\"list\",\"model\":\"text-embedding-3-small\",",
+            "\"data\":[{\"object\":\"embedding\",\"index\":1,\"embedding\":[0.5,-1.0]},",
+            "{\"object\":\"embedding\",\"index\":0,\"embedding\":[0.25,0.0]}],",
+            "\"usage\":{\"prompt_tokens\":4,\"total_tokens\":4}}",
+        );
+        let head: &'static str = Box::leak(
+            format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n",
+                BODY.len()
+            )
+            .into_boxed_str(),
+        );
+        let url = serve_then_reset(head, BODY).await;
+        let provider = OpenAIProvider::new(OpenAIConfig::new("openai-1", "sk-test").with_base_url(url))
+            .expect("provider");
+        let request = EmbeddingRequest::new(
+            "text-embedding-3-small",
+            vec!["a".to_string(), "b".to_string()],
+        );
+
+        let embedder = provider.as_embedding_provider().expect("embeddings");
+        let response = embedder.embed(&request).await.expect("embeddings");
+        assert_eq!(response.data[0], Embedding::new(1, vec![0.5, -1.0]));
+        assert_eq!(response.data[1].index, 0);
+        assert_eq!(response.usage.total_tokens, 4);
+    }
+
+    #[tokio::test]
+    async fn test_batch_submit_poll_and_retrieve() {
+        use wiremock::matchers::{body_partial_json, body_string_contains, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/files"))
+            .and(body_string_contains("\"custom_id\":\"req-1\""))
+            .and(body_string_contains("\"url\":\"/v1/chat/completions\""))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "file-in", "object": "file", "purpose": "batch"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/batches"))
+            .and(body_partial_json(serde_json::json!({
+                "input_file_id": "file-in",
+                "endpoint": "/v1/chat/completions"
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "batch_abc", "object": "batch", "status": "validating"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/batches/batch_abc"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "batch_abc",
+                "object": "batch",
+                "status": "completed",
+                "request_counts": {"total": 2, "completed": 1, "failed": 1},
+                "output_file_id": "file-out",
+                "error_file_id": "file-err"
+            })))
+            .mount(&server)
+            .await;
+        let output = serde_json::json!({
+            "custom_id": "req-1",
+            "response": {"status_code": 200, "body": {
+                "id": "chatcmpl-1", "object": "chat.completion", "created": 1, "model": "gpt-4o",
+                "choices": [{"index": 0, "message": {"role": "assistant", "content": "hi"}, "finish_reason": "stop"}],
+                "usage": {"prompt_tokens": 3, "completion_tokens": 1, "total_tokens": 4}
+            }},
+            "error": null
+        });
+        Mock::given(method("GET"))
+            .and(path("/v1/files/file-out/content"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(format!("{output}\n")))
+            .mount(&server)
+            .await;
+        let failed = serde_json::json!({
+            "custom_id": "req-2",
+            "response": {"status_code": 400, "body": {"error": {"message": "bad request"}}},
+            "error": null
+        });
+        Mock::given(method("GET"))
+            .and(path("/v1/files/file-err/content"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(failed.to_string()))
+            .mount(&server)
+            .await;
+
+        let provider =
+            OpenAIProvider::new(OpenAIConfig::new("openai-1", "sk-test").with_base_url(server.uri()))
+                .expect("provider");
+        let batches = provider.as_batch_provider().expect("batch capability");
+        let items: Vec<BatchItem> = ["req-1", "req-2"]
+            .into_iter()
+            .map(|custom_id| BatchItem {
+                custom_id: custom_id.to_string(),
+                body: GatewayRequest::builder()
+                    .model("gpt-4o")
+                    .message(ChatMessage::user("hi"))
+                    .build()
+                    .expect("request"),
+            })
+            .collect();
+
+        let batch_id = batches.submit_batch(&items).await.expect("submit");
+        assert_eq!(batch_id, "batch_abc");
+
+        let progress = batches.batch_status(&batch_id).await.expect("status");
+        assert_eq!(progress.status, BatchStatus::Completed);
+        assert_eq!(progress.request_counts.failed, 1);
+
+        let results = batches.batch_results(&batch_id).await.expect("results");
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].custom_id, "req-1");
+        assert_eq!(results[0].usage().total_tokens, 4);
+        assert_eq!(results[1].custom_id, "req-2");
+        assert_eq!(results[1].error.as_ref().map(|e| e.message.as_str()), Some("bad request"));
+    }
+
+    #[test]
+    fn test_batch_capability_only_for_openai() {
+        let provider = OpenAIProvider::new(
+            OpenAIConfig::new("vllm-1", "sk-test").with_provider_type(ProviderType::VLLM),
+        )
+        .expect("provider");
+        assert!(provider.as_batch_provider().is_none());
+    }
+
+    /// Serve one raw response, then drop the connection without finishing it
+    async fn serve_then_reset(head: &'static str, body: &'static str) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("addr");
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.expect("accept");
+            let mut buf = vec![0u8; 16 * 1024];
+            let _ = socket.read(&mut buf).await;
+            socket.write_all(head.as_bytes()).await.expect("write head");
+            socket.write_all(body.as_bytes()).await.expect("write body");
+            let _ = socket.shutdown().await;
+        });
+        format!("http://{addr}")
+    }
+
+    #[tokio::test]
+    async fn test_truncated_response_is_connection_reset() {
+        let url = serve_then_reset(
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: 500\r\n\r\n",
+            "{\"id\":\"chatcmpl-1\",",
+        )
+        .await;
+        let provider = OpenAIProvider::new(OpenAIConfig::new("openai-1", "sk-test").with_base_url(url))
+            .expect("provider");
+        let request = GatewayRequest::builder()
+            .model("gpt-4o")
+            .message(ChatMessage::user("hi"))
+            .build()
+            .expect("request");
+
+        let err = provider.chat_completion(&request).await.expect_err("truncated body");
+        assert!(err.is_connection_reset());
+        assert!(err.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_stream_reset_after_partial_content() {
+        let url = serve_then_reset(
+            "HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\ntransfer-encoding: chunked\r\n\r\n",
+            concat!(
+                "98\r\n",
+                "data: {\"id\":\"c1\",\"object\":\"chat.completion.chunk\",\"created\":1,\"model\":\"gpt-4o\",",
+                "\"choices\":[{\"index\":0,\"delta\":{\"content\":\"Hel\"},\"finish_reason\":null}]}\n\n",
+                "\r\n",
+            ),
+        )
+        .await;
+        let provider = OpenAIProvider::new(OpenAIConfig::new("openai-1", "sk-test").with_base_url(url))
+            .expect("provider");
+        let request = GatewayRequest::builder()
+            .model("gpt-4o")
+            .message(ChatMessage::user("hi"))
+            .stream(true)
+            .build()
+            .expect("request");
+
+        let items: Vec<_> = provider
+            .chat_completion_stream(&request)
+            .await
+            .expect("stream")
+            .collect()
+            .await;
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].as_ref().expect("chunk").content(), Some("Hel"));
+        assert!(items[1].as_ref().expect_err("reset").is_connection_reset());
+    }
 }
@@ -0,0 +1,238 @@
+//! Per-provider allow and deny lists for request parameters.
+//!
+//! Some backends reject parameters they do not support, or a deployment may
+//! not want clients to set some of them at all. Each provider can list the
+//! optional request parameters it accepts or refuses:
+//!
+//! ```yaml
+//! providers:
+//!   - type: vllm
+//!     id: local
+//!     base_url: http://vllm:8000
+//!     params:
+//!       deny: [service_tier, store]
+//!   - type: openai
+//!     id: openai
+//!     api_key: "..."
+//!     params:
+//!       allow: [temperature, max_tokens, top_p, stop, tools, tool_choice]
+//!       reject_unknown: true
+//! ```
+//!
+//! Denied parameters are dropped before the request is sent, with a
+//! warning. With an allowlist, any other parameter is dropped too, or the
+//! request is rejected if `reject_unknown` is set. `model`, `messages` and
+//! `stream` are always sent.
+
+use gateway_core::{GatewayError, GatewayRequest};
+use serde::Deserialize;
+use std::borrow::Cow;
+use std::collections::HashSet;
+use tracing::warn;
+
+/// Declares the optional request parameters a filter can drop, by the name
+/// clients send them under
+macro_rules! optional_params {
+    ($($name:ident),* $(,)?) => {
+        /// Optional request parameters, by wire name
+        pub const PARAMS: &[&str] = &[$(stringify!($name)),*];
+
+        /// Whether the request sets a parameter
+        fn is_set(request: &GatewayRequest, name: &str) -> bool {
+            match name {
+                $(stringify!($name) => request.$name.is_some(),)*
+                _ => false,
+            }
+        }
+
+        /// Drop a parameter from the request
+        fn clear(request: &mut GatewayRequest, name: &str) {
+            match name {
+                $(stringify!($name) => request.$name = None,)*
+                _ => {}
+            }
+        }
+    };
+}
+
+optional_params!(
+    temperature,
+    max_tokens,
+    max_completion_tokens,
+    top_p,
+    top_k,
+    frequency_penalty,
+    presence_penalty,
+    stop,
+    stream_options,
+    n,
+    tools,
+    tool_choice,
+    response_format,
+    seed,
+    safety_settings,
+    user,
+    store,
+    service_tier,
+    modalities,
+    audio,
+    metadata,
+);
+
+/// Configured parameter lists for one provider
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ParamRules {
+    /// Only these parameters are sent, when set
+    #[serde(default)]
+    pub allow: Option<Vec<String>>,
+    /// Parameters that are never sent
+    #[serde(default)]
+    pub deny: Vec<String>,
+    /// Reject requests setting a parameter outside `allow` instead of
+    /// dropping it
+    #[serde(default)]
+    pub reject_unknown: bool,
+}
+
+/// Validated parameter lists applied to requests before they are sent
+#[derive(Debug, Clone, Default)]
+pub struct ParamFilter {
+    allow: Option<HashSet<&'static str>>,
+    deny: HashSet<&'static str>,
+    reject_unknown: bool,
+}
+
+impl ParamFilter {
+    /// Validate configured lists
+    ///
+    /// # Errors
+    /// Returns a configuration error naming the first entry that is not an
+    /// optional request parameter
+    pub fn new(provider_id: &str, rules: &ParamRules) -> Result<Self, GatewayError> {
+        let resolve = |names: &[String]| -> Result<HashSet<&'static str>, GatewayError> {
+            names
+                .iter()
+                .map(|name| {
+                    PARAMS
+                        .iter()
+                        .copied()
+                        .find(|param| param == name)
+                        .ok_or_else(|| GatewayError::Configuration {
+                            message: format!(
+                                "Unknown request parameter '{name}' in params for provider '{provider_id}'"
+                            ),
+                        })
+                })
+                .collect()
+        };
+
+        Ok(Self {
+            allow: rules.allow.as_deref().map(resolve).transpose()?,
+            deny: resolve(&rules.deny)?,
+            reject_unknown: rules.reject_unknown,
+        })
+    }
+
+    /// Whether the filter never changes a request
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.allow.is_none() && self.deny.is_empty()
+    }
+
+    /// Drop the parameters this provider may not receive
+    ///
+    /// # Errors
+    /// Returns a validation error if the request sets a parameter outside
+    /// the allowlist and `reject_unknown` is set
+    pub fn apply<'a>(
+        &self,
+        provider_id: &str,
+        request: &'a GatewayRequest,
+    ) -> Result<Cow<'a, GatewayRequest>, GatewayError> {
+        if self.is_empty() {
+            return Ok(Cow::Borrowed(request));
+        }
+
+        let mut request = Cow::Borrowed(request);
+        for &name in PARAMS {
+            if !is_set(&request, name) {
+                continue;
+            }
+            let unlisted = self.allow.as_ref().is_some_and(|allow| !allow.contains(name));
+            if unlisted && self.reject_unknown {
+                return Err(GatewayError::validation(
+                    format!("Parameter '{name}' is not supported by provider '{provider_id}'"),
+                    Some(name.to_string()),
+                    "unsupported_parameter",
+                ));
+            }
+            if unlisted || self.deny.contains(name) {
+                warn!(provider = %provider_id, param = name, "Dropping request parameter");
+                clear(request.to_mut(), name);
+            }
+        }
+        Ok(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gateway_core::ChatMessage;
+
+    fn rules(allow: Option<&[&str]>, deny: &[&str], reject_unknown: bool) -> ParamRules {
+        let names = |names: &[&str]| names.iter().map(ToString::to_string).collect();
+        ParamRules {
+            allow: allow.map(names),
+            deny: names(deny),
+            reject_unknown,
+        }
+    }
+
+    fn request() -> GatewayRequest {
+        GatewayRequest::builder()
+            .model("gpt-4o")
+            .message(ChatMessage::user("Hello"))
+            .temperature(0.2)
+            .max_tokens(64)
+            .build()
+            .expect("valid request")
+    }
+
+    #[test]
+    fn test_denied_param_is_dropped() {
+        let request = request();
+        let filter = ParamFilter::new("local", &rules(None, &["temperature"], false)).unwrap();
+        let filtered = filter.apply("local", &request).unwrap();
+        assert_eq!(filtered.temperature, None);
+        assert_eq!(filtered.max_tokens, Some(64));
+    }
+
+    #[test]
+    fn test_allowlist_drops_or_rejects_unlisted_params() {
+        let request = request();
+        let dropping = ParamFilter::new("openai", &rules(Some(&["max_tokens"]), &[], false)).unwrap();
+        let filtered = dropping.apply("openai", &request).unwrap();
+        assert_eq!(filtered.temperature, None);
+        assert_eq!(filtered.max_tokens, Some(64));
+
+        let rejecting = ParamFilter::new("openai", &rules(Some(&["max_tokens"]), &[], true)).unwrap();
+        let err = rejecting.apply("openai", &request).unwrap_err();
+        assert!(matches!(
+            err,
+            GatewayError::Validation { ref field, ref code, .. }
+                if field.as_deref() == Some("temperature") && code == "unsupported_parameter"
+        ));
+    }
+
+    #[test]
+    fn test_empty_filter_borrows_and_unknown_names_are_rejected() {
+        let request = request();
+        let filter = ParamFilter::default();
+        assert!(matches!(filter.apply("openai", &request).unwrap(), Cow::Borrowed(_)));
+
+        let err = ParamFilter::new("openai", &rules(None, &["temprature"], false)).unwrap_err();
+        assert!(err.to_string().contains("temprature"));
+    }
+}
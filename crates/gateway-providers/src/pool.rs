@@ -0,0 +1,238 @@
+//! Warm connection pools for provider HTTP clients.
+//!
+//! A cold pool pays a TCP + TLS handshake on the first request of every
+//! burst. With `min_idle` set, a [`ConnectionWarmer`] opens that many
+//! connections up front and re-pings them every `keepalive_interval` so they
+//! stay in the client's idle pool instead of being reaped.
+//!
+//! ```yaml
+//! providers:
+//!   - type: openai
+//!     id: openai
+//!     api_key: "..."
+//!     pool:
+//!       min_idle: 4
+//!       keepalive_interval: 30s
+//! ```
+
+use gateway_core::ConnectionPoolStats;
+use reqwest::{Client, ClientBuilder};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tracing::debug;
+
+/// Connection pool settings for a provider's HTTP client
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PoolConfig {
+    /// Idle connections to keep warm (0 disables warmup)
+    pub min_idle: usize,
+    /// Maximum idle connections kept per host
+    pub max_idle_per_host: usize,
+    /// Interval between keep-alive pings
+    #[serde(with = "humantime_serde")]
+    pub keepalive_interval: Duration,
+    /// How long an unused connection stays in the pool
+    ///
+    /// Raised to twice `keepalive_interval` when warmup is enabled so
+    /// connections survive between pings.
+    #[serde(with = "humantime_serde")]
+    pub idle_timeout: Duration,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            min_idle: 0,
+            max_idle_per_host: 100,
+            keepalive_interval: Duration::from_secs(30),
+            idle_timeout: Duration::from_secs(90),
+        }
+    }
+}
+
+impl PoolConfig {
+    /// Whether warm connections are maintained
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.min_idle > 0
+    }
+
+    /// Set the number of idle connections to keep warm
+    #[must_use]
+    pub fn with_min_idle(mut self, min_idle: usize) -> Self {
+        self.min_idle = min_idle;
+        self
+    }
+
+    /// Set the interval between keep-alive pings
+    #[must_use]
+    pub fn with_keepalive_interval(mut self, interval: Duration) -> Self {
+        self.keepalive_interval = interval;
+        self
+    }
+
+    /// Apply these settings to an HTTP client builder
+    pub fn configure(&self, builder: ClientBuilder) -> ClientBuilder {
+        let idle_timeout = if self.is_enabled() {
+            self.idle_timeout.max(self.keepalive_interval * 2)
+        } else {
+            self.idle_timeout
+        };
+
+        builder
+            .pool_max_idle_per_host(self.max_idle_per_host.max(self.min_idle))
+            .pool_idle_timeout(idle_timeout)
+    }
+}
+
+/// Keeps `min_idle` connections to a provider open
+#[derive(Debug)]
+pub struct ConnectionWarmer {
+    client: Client,
+    url: String,
+    config: PoolConfig,
+    idle: AtomicUsize,
+}
+
+impl ConnectionWarmer {
+    /// Create a warmer pinging `url` through `client`
+    ///
+    /// Returns `None` if warmup is disabled in `config`.
+    #[must_use]
+    pub fn new(client: Client, url: impl Into<String>, config: &PoolConfig) -> Option<Self> {
+        config.is_enabled().then(|| Self {
+            client,
+            url: url.into(),
+            config: config.clone(),
+            idle: AtomicUsize::new(0),
+        })
+    }
+
+    /// Send `min_idle` concurrent pings so the pool holds that many connections
+    ///
+    /// Any HTTP response counts: the ping only needs the connection, not a
+    /// successful status.
+    pub async fn warm(&self) -> ConnectionPoolStats {
+        let pings = (0..self.config.min_idle).map(|_| self.client.head(&self.url).send());
+        let idle = futures::future::join_all(pings)
+            .await
+            .iter()
+            .filter(|result| result.is_ok())
+            .count();
+
+        self.idle.store(idle, Ordering::Relaxed);
+        debug!(url = %self.url, idle, min_idle = self.config.min_idle, "Warmed provider connections");
+        self.stats()
+    }
+
+    /// Connection state as of the last [`warm`](Self::warm)
+    #[must_use]
+    pub fn stats(&self) -> ConnectionPoolStats {
+        ConnectionPoolStats {
+            min_idle: self.config.min_idle,
+            idle: self.idle.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Interval between keep-alive rounds
+    #[must_use]
+    pub fn keepalive_interval(&self) -> Duration {
+        self.config.keepalive_interval
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test_server {
+    //! Minimal HTTP/1.1 keep-alive server that counts accepted connections.
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Start the server, returning its base URL and accepted connection count
+    pub(crate) async fn start(body: &'static str) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let accepted = Arc::new(AtomicUsize::new(0));
+
+        let counter = accepted.clone();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                counter.fetch_add(1, Ordering::SeqCst);
+                tokio::spawn(async move {
+                    let mut buf = vec![0u8; 64 * 1024];
+                    loop {
+                        let Ok(read) = socket.read(&mut buf).await else {
+                            return;
+                        };
+                        if read == 0 {
+                            return;
+                        }
+                        let request = String::from_utf8_lossy(&buf[..read]);
+                        // Hold the connection briefly so concurrent requests
+                        // can't share it
+                        tokio::time::sleep(Duration::from_millis(50)).await;
+                        let payload = if request.starts_with("HEAD") { "" } else { body };
+                        let response = format!(
+                            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{payload}",
+                            body.len()
+                        );
+                        if socket.write_all(response.as_bytes()).await.is_err() {
+                            return;
+                        }
+                    }
+                });
+            }
+        });
+
+        (url, accepted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disabled_by_default() {
+        let config = PoolConfig::default();
+        assert!(!config.is_enabled());
+        assert!(ConnectionWarmer::new(Client::new(), "http://localhost", &config).is_none());
+    }
+
+    #[test]
+    fn test_deserialize_pool_config() {
+        let config: PoolConfig =
+            serde_yaml::from_str("min_idle: 4\nkeepalive_interval: 15s").unwrap();
+        assert_eq!(config.min_idle, 4);
+        assert_eq!(config.keepalive_interval, Duration::from_secs(15));
+        assert_eq!(config.max_idle_per_host, 100);
+    }
+
+    #[tokio::test]
+    async fn test_warmup_opens_min_idle_connections_that_requests_reuse() {
+        let (url, accepted) = test_server::start("{}").await;
+        let config = PoolConfig::default().with_min_idle(3);
+        let client = config.configure(Client::builder()).build().unwrap();
+        let warmer = ConnectionWarmer::new(client.clone(), &url, &config).unwrap();
+
+        let stats = warmer.warm().await;
+        assert!(stats.idle >= 3);
+        assert_eq!(accepted.load(Ordering::SeqCst), 3);
+
+        // A burst no larger than the warm pool needs no new handshakes
+        let burst = (0..3).map(|_| client.get(&url).send());
+        for response in futures::future::join_all(burst).await {
+            assert!(response.unwrap().status().is_success());
+        }
+        assert_eq!(accepted.load(Ordering::SeqCst), 3);
+
+        // Keep-alive rounds reuse the same connections too
+        warmer.warm().await;
+        assert_eq!(accepted.load(Ordering::SeqCst), 3);
+    }
+}
@@ -186,6 +186,66 @@ impl ProviderRegistry {
             .unwrap_or_default()
     }
 
+    /// Get the provider to serve image generation for a model
+    ///
+    /// Picks the highest-priority enabled provider for the model that
+    /// implements image generation.
+    ///
+    /// # Errors
+    /// Returns `ModelNotFound` if no enabled provider serves the model, or
+    /// `UnsupportedCapability` if none of them can generate images
+    pub fn get_image_provider(&self, model: &str) -> Result<Arc<dyn LLMProvider>, GatewayError> {
+        self.get_capable_provider(model, "image_generation", |provider| {
+            provider.as_image_provider().is_some()
+        })
+    }
+
+    /// Get the provider to serve embeddings for a model
+    ///
+    /// Picks the highest-priority enabled provider for the model that
+    /// implements embeddings.
+    ///
+    /// # Errors
+    /// Returns `ModelNotFound` if no enabled provider serves the model, or
+    /// `UnsupportedCapability` if none of them can embed
+    pub fn get_embedding_provider(&self, model: &str) -> Result<Arc<dyn LLMProvider>, GatewayError> {
+        self.get_capable_provider(model, "embeddings", |provider| {
+            provider.as_embedding_provider().is_some()
+        })
+    }
+
+    /// Highest-priority enabled provider for a model with a capability
+    fn get_capable_provider(
+        &self,
+        model: &str,
+        capability: &str,
+        capable: impl Fn(&dyn LLMProvider) -> bool,
+    ) -> Result<Arc<dyn LLMProvider>, GatewayError> {
+        let mut candidates: Vec<(u32, Arc<dyn LLMProvider>)> = self
+            .model_index
+            .get(model)
+            .map(|ids| {
+                ids.iter()
+                    .filter_map(|id| self.providers.get(id))
+                    .filter(|entry| entry.enabled)
+                    .map(|entry| (entry.priority, Arc::clone(&entry.provider)))
+                    .collect()
+            })
+            .unwrap_or_default();
+        candidates.sort_by_key(|(priority, _)| *priority);
+
+        let Some((_, first)) = candidates.first() else {
+            return Err(GatewayError::model_not_found(model));
+        };
+        let first_id = first.id().to_string();
+
+        candidates
+            .into_iter()
+            .map(|(_, provider)| provider)
+            .find(|provider| capable(provider.as_ref()))
+            .ok_or_else(|| GatewayError::unsupported_capability(first_id, capability))
+    }
+
     /// Get all enabled providers
     #[must_use]
     pub fn get_enabled_providers(&self) -> Vec<Arc<dyn LLMProvider>> {
@@ -354,6 +414,27 @@ impl ProviderRegistry {
 
         debug!("Health check completed for all providers");
     }
+
+    /// Warm provider connection pools and keep them warm
+    ///
+    /// Spawns one task per provider with warmup enabled. Each task warms
+    /// immediately and then once per keep-alive interval until aborted.
+    #[must_use]
+    pub fn start_pool_maintenance(&self) -> Vec<tokio::task::JoinHandle<()>> {
+        self.providers
+            .iter()
+            .filter_map(|entry| {
+                let provider = Arc::clone(&entry.provider);
+                let interval = provider.connection_keepalive_interval()?;
+                Some(tokio::spawn(async move {
+                    loop {
+                        provider.warm_connections().await;
+                        tokio::time::sleep(interval).await;
+                    }
+                }))
+            })
+            .collect()
+    }
 }
 
 impl Default for ProviderRegistry {
@@ -427,6 +508,7 @@ mod tests {
                 max_context_length: None,
                 max_output_tokens: None,
                 parallel_tool_calls: false,
+                audio_output: false,
             };
             &CAPS
         }
@@ -449,6 +531,30 @@ mod tests {
         assert!(registry.get("test").is_some());
     }
 
+    #[test]
+    fn test_image_provider_lookup_without_support() {
+        let registry = ProviderRegistry::new();
+        let provider = Arc::new(MockProvider::new("test", vec!["dall-e-3"]));
+        registry.register(provider, 100, 100).unwrap();
+
+        let err = registry.get_image_provider("dall-e-3").err().unwrap();
+        assert_eq!(err.error_code(), "unsupported_capability");
+
+        let err = registry.get_image_provider("unknown").err().unwrap();
+        assert_eq!(err.error_code(), "model_not_found");
+    }
+
+    #[test]
+    fn test_embedding_provider_lookup_without_support() {
+        let registry = ProviderRegistry::new();
+        let provider = Arc::new(MockProvider::new("test", vec!["text-embedding-3-small"]));
+        registry.register(provider, 100, 100).unwrap();
+
+        let err = registry.get_embedding_provider("text-embedding-3-small").err().unwrap();
+        assert_eq!(err.error_code(), "unsupported_capability");
+        assert!(err.to_string().contains("embeddings"));
+    }
+
     #[test]
     fn test_duplicate_registration() {
         let registry = ProviderRegistry::new();
@@ -0,0 +1,123 @@
+//! Transport error classification shared by the HTTP providers.
+//!
+//! A provider dropping the connection part-way through a response body is
+//! distinct from a malformed body: the former is worth retrying, the latter
+//! is not. These helpers map `reqwest` failures accordingly, and decode
+//! streaming bodies into server-sent events.
+
+use futures::{Stream, StreamExt};
+use gateway_core::sse::{self, SseEvent};
+use gateway_core::GatewayError;
+use std::error::Error as _;
+use std::io;
+
+/// Check whether a `reqwest` error means the connection was reset or the
+/// body ended before it was complete
+pub(crate) fn is_connection_reset(err: &reqwest::Error) -> bool {
+    if err.is_timeout() {
+        return false;
+    }
+    if err.is_body() {
+        return true;
+    }
+
+    let mut source = err.source();
+    while let Some(cause) = source {
+        if let Some(io_err) = cause.downcast_ref::<io::Error>() {
+            if matches!(
+                io_err.kind(),
+                io::ErrorKind::ConnectionReset
+                    | io::ErrorKind::ConnectionAborted
+                    | io::ErrorKind::BrokenPipe
+                    | io::ErrorKind::UnexpectedEof
+            ) {
+                return true;
+            }
+        }
+        source = cause.source();
+    }
+    false
+}
+
+/// Map a failure reading or decoding a response body
+///
+/// Resets become [`GatewayError::connection_reset`]; anything else is a
+/// non-retryable provider error, as the body itself was bad.
+pub(crate) fn body_error(provider: &str, err: &reqwest::Error) -> GatewayError {
+    if is_connection_reset(err) {
+        GatewayError::connection_reset(format!("{provider}: connection reset: {err}"))
+    } else {
+        GatewayError::provider(provider, format!("Failed to parse response: {err}"), None, false)
+    }
+}
+
+/// Decode a streaming response body into server-sent events
+///
+/// Resets become [`GatewayError::connection_reset`]; any other failure
+/// reading the body is a streaming error.
+pub(crate) fn sse_events(
+    provider: &str,
+    response: reqwest::Response,
+) -> impl Stream<Item = Result<SseEvent, GatewayError>> {
+    let provider = provider.to_string();
+    sse::decode_stream(response.bytes_stream()).map(move |event| {
+        event.map_err(|err| {
+            if is_connection_reset(&err) {
+                GatewayError::connection_reset(format!("{provider}: connection reset mid-stream: {err}"))
+            } else {
+                GatewayError::streaming(format!("{provider}: stream error: {err}"))
+            }
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::AsyncWriteExt;
+    use tokio::net::TcpListener;
+
+    /// Serve one response that promises more body than it sends, then close
+    async fn truncated_server() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 4096];
+            let _ = tokio::io::AsyncReadExt::read(&mut socket, &mut buf).await;
+            socket
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: 100\r\n\r\n{\"id\":")
+                .await
+                .unwrap();
+            socket.shutdown().await.unwrap();
+        });
+        format!("http://{addr}")
+    }
+
+    #[tokio::test]
+    async fn test_truncated_body_is_connection_reset() {
+        let url = truncated_server().await;
+        let response = reqwest::get(&url).await.unwrap();
+        let err = response.json::<serde_json::Value>().await.unwrap_err();
+
+        assert!(is_connection_reset(&err));
+        assert!(body_error("openai", &err).is_connection_reset());
+    }
+
+    #[tokio::test]
+    async fn test_malformed_body_is_not_connection_reset() {
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::any())
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_string("not json"))
+            .mount(&server)
+            .await;
+
+        let response = reqwest::get(server.uri()).await.unwrap();
+        let err = response.json::<serde_json::Value>().await.unwrap_err();
+
+        assert!(!is_connection_reset(&err));
+        let mapped = body_error("openai", &err);
+        assert!(!mapped.is_connection_reset());
+        assert!(!mapped.is_retryable());
+    }
+}
@@ -2,8 +2,17 @@
 //!
 //! Provides an in-memory cache for caching identical requests to reduce
 //! latency and provider costs. Uses a hash of the request as the cache key.
-
-use gateway_core::{GatewayRequest, GatewayResponse};
+//!
+//! With `cache_streaming` enabled, streamed completions are cached as their
+//! chunks: [`ResponseCache::cache_stream`] records a stream as it is
+//! forwarded, and [`ResponseCache::get_stream`] replays it on later requests.
+
+use futures::stream::{BoxStream, Stream, StreamExt};
+use gateway_core::request::ToolDefinition;
+use gateway_core::{
+    ChatChunk, FinishReason, GatewayError, GatewayRequest, GatewayResponse, RequestContext,
+    ToolChoice,
+};
 use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
 use std::sync::Arc;
@@ -22,6 +31,15 @@ pub struct CacheConfig {
     pub default_ttl: Duration,
     /// Whether to cache streaming responses (may be memory intensive)
     pub cache_streaming: bool,
+    /// Delay between chunks when replaying a cached stream, so clients
+    /// still receive incremental deltas; zero replays without pausing
+    pub stream_replay_interval: Duration,
+    /// Whether expired entries may be served when upstream dispatch fails
+    pub serve_stale_on_error: bool,
+    /// How long past expiry an entry may still be served as stale
+    pub max_staleness: Duration,
+    /// How requests are canonicalized into cache keys
+    pub key_normalization: CacheKeyNormalization,
 }
 
 impl Default for CacheConfig {
@@ -31,15 +49,96 @@ impl Default for CacheConfig {
             max_entries: 10000,
             default_ttl: Duration::from_secs(3600), // 1 hour
             cache_streaming: false,
+            stream_replay_interval: Duration::from_millis(10),
+            serve_stale_on_error: false,
+            max_staleness: Duration::from_secs(300),
+            key_normalization: CacheKeyNormalization::default(),
+        }
+    }
+}
+
+/// How a request is canonicalized before it is hashed into a [`CacheKey`]
+///
+/// The request ID, metadata and other fields that never reach the model are
+/// always left out of the key, and order-insensitive lists (tools,
+/// modalities) are always sorted. Conversation turns keep their order, as
+/// reordering them changes the prompt. These rules additionally let requests
+/// that differ only trivially share an entry; [`Self::strict`] turns them off.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheKeyNormalization {
+    /// Trim surrounding whitespace and normalize line endings in message text
+    pub trim_whitespace: bool,
+    /// Decimal places `temperature` is rounded to; `None` keys on the exact
+    /// value
+    pub temperature_precision: Option<u32>,
+    /// Leave the token limit out of the key
+    ///
+    /// Only safe when responses rarely reach the limit, since a truncated
+    /// response would be served to requests allowing more tokens.
+    pub ignore_max_tokens: bool,
+}
+
+impl Default for CacheKeyNormalization {
+    fn default() -> Self {
+        Self {
+            trim_whitespace: true,
+            temperature_precision: Some(1),
+            ignore_max_tokens: false,
+        }
+    }
+}
+
+impl CacheKeyNormalization {
+    /// Key on the request exactly as sent
+    #[must_use]
+    pub fn strict() -> Self {
+        Self {
+            trim_whitespace: false,
+            temperature_precision: None,
+            ignore_max_tokens: false,
+        }
+    }
+
+    /// Hash message text under these rules
+    fn hash_text(&self, text: &str, hasher: &mut impl Hasher) {
+        if !self.trim_whitespace {
+            text.hash(hasher);
+            return;
+        }
+        let text = text.trim();
+        if text.contains('\r') {
+            text.replace("\r\n", "\n").hash(hasher);
+        } else {
+            text.hash(hasher);
+        }
+    }
+
+    /// Key value for a temperature under these rules
+    fn temperature_key(&self, temperature: f32) -> i64 {
+        match self.temperature_precision {
+            Some(places) => {
+                let scale = 10f64.powi(places.min(6) as i32);
+                (f64::from(temperature) * scale).round() as i64
+            }
+            None => i64::from(temperature.to_bits()),
         }
     }
 }
 
+/// What a cache entry holds
+#[derive(Debug, Clone)]
+enum CachedValue {
+    /// A complete response
+    Response(GatewayResponse),
+    /// The chunks of a streamed response, in order
+    Stream(Arc<[ChatChunk]>),
+}
+
 /// A cached response entry
 #[derive(Debug, Clone)]
 struct CacheEntry {
     /// The cached response
-    response: GatewayResponse,
+    value: CachedValue,
     /// When the entry was created
     created_at: Instant,
     /// TTL for this entry
@@ -49,9 +148,9 @@ struct CacheEntry {
 }
 
 impl CacheEntry {
-    fn new(response: GatewayResponse, ttl: Duration) -> Self {
+    fn new(value: CachedValue, ttl: Duration) -> Self {
         Self {
-            response,
+            value,
             created_at: Instant::now(),
             ttl,
             hits: 0,
@@ -61,6 +160,10 @@ impl CacheEntry {
     fn is_expired(&self) -> bool {
         self.created_at.elapsed() > self.ttl
     }
+
+    fn is_within_staleness(&self, max_staleness: Duration) -> bool {
+        self.created_at.elapsed() <= self.ttl.saturating_add(max_staleness)
+    }
 }
 
 /// Cache key derived from request
@@ -70,47 +173,159 @@ pub struct CacheKey {
     model: String,
     /// Hash of the messages
     messages_hash: u64,
-    /// Temperature (discretized for caching)
-    temperature_bucket: u32,
-    /// Max tokens
+    /// Temperature, rounded per [`CacheKeyNormalization`]
+    temperature: Option<i64>,
+    /// Max tokens, unless ignored
     max_tokens: Option<u32>,
+    /// Canonical hash of the tool definitions and tool choice
+    tools_hash: u64,
+    /// Whether the response is streamed
+    stream: bool,
 }
 
 impl CacheKey {
-    /// Create a cache key from a request
+    /// Create a cache key from a request with the default normalization
     pub fn from_request(request: &GatewayRequest) -> Self {
+        Self::normalized(request, &CacheKeyNormalization::default())
+    }
+
+    /// Create a cache key from a request canonicalized under `rules`
+    pub fn normalized(request: &GatewayRequest, rules: &CacheKeyNormalization) -> Self {
         // Hash the messages
         let mut hasher = std::collections::hash_map::DefaultHasher::new();
         for msg in &request.messages {
             msg.role.hash(&mut hasher);
             // Hash the message content
             match &msg.content {
-                gateway_core::MessageContent::Text(text) => text.hash(&mut hasher),
+                gateway_core::MessageContent::Text(text) => rules.hash_text(text, &mut hasher),
                 gateway_core::MessageContent::Parts(parts) => {
                     for part in parts {
                         match part {
-                            gateway_core::ContentPart::Text { text } => text.hash(&mut hasher),
+                            gateway_core::ContentPart::Text { text } => {
+                                rules.hash_text(text, &mut hasher);
+                            }
                             gateway_core::ContentPart::ImageUrl { image_url } => {
                                 image_url.url.hash(&mut hasher);
                             }
+                            gateway_core::ContentPart::Audio { audio } => {
+                                audio.id.hash(&mut hasher);
+                                audio.data.hash(&mut hasher);
+                            }
                         }
                     }
                 }
             }
         }
+        // Text and audio responses to the same messages differ
+        request
+            .modalities
+            .as_deref()
+            .map(|modalities| {
+                let mut sorted: Vec<&String> = modalities.iter().collect();
+                sorted.sort();
+                sorted
+            })
+            .hash(&mut hasher);
+        request
+            .audio
+            .as_ref()
+            .map(|audio| (&audio.voice, &audio.format))
+            .hash(&mut hasher);
         let messages_hash = hasher.finish();
 
-        // Discretize temperature into buckets (0.0-0.1, 0.1-0.2, etc.)
-        let temperature_bucket = request
-            .temperature
-            .map(|t| (t * 10.0) as u32)
-            .unwrap_or(7); // Default temperature ~0.7
-
         Self {
             model: request.model.clone(),
             messages_hash,
-            temperature_bucket,
-            max_tokens: request.max_tokens,
+            temperature: request.temperature.map(|t| rules.temperature_key(t)),
+            max_tokens: if rules.ignore_max_tokens {
+                None
+            } else {
+                request.token_limit()
+            },
+            tools_hash: tools_hash(request),
+            stream: request.stream,
+        }
+    }
+}
+
+/// Hash of a request's `tools` and `tool_choice`, independent of tool order
+///
+/// Tools are hashed sorted by function name, and JSON Schema parameters with
+/// their object keys sorted, so reordering identical tools or schema keys
+/// keeps the hash while any change to a tool's name, description or schema
+/// alters it.
+pub(crate) fn tools_hash(request: &GatewayRequest) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+
+    let mut tools: Vec<&ToolDefinition> = request.tools.iter().flatten().collect();
+    tools.sort_by(|a, b| (&a.function.name, &a.tool_type).cmp(&(&b.function.name, &b.tool_type)));
+    tools.len().hash(&mut hasher);
+    for tool in tools {
+        tool.tool_type.hash(&mut hasher);
+        tool.function.name.hash(&mut hasher);
+        tool.function.description.hash(&mut hasher);
+        match &tool.function.parameters {
+            Some(parameters) => {
+                1u8.hash(&mut hasher);
+                hash_json(parameters, &mut hasher);
+            }
+            None => 0u8.hash(&mut hasher),
+        }
+    }
+
+    match &request.tool_choice {
+        None => 0u8.hash(&mut hasher),
+        Some(ToolChoice::String(choice)) => {
+            1u8.hash(&mut hasher);
+            choice.hash(&mut hasher);
+        }
+        Some(ToolChoice::Tool {
+            tool_type,
+            function,
+        }) => {
+            2u8.hash(&mut hasher);
+            tool_type.hash(&mut hasher);
+            function.name.hash(&mut hasher);
+        }
+    }
+
+    hasher.finish()
+}
+
+/// Hash a JSON value with object keys in sorted order
+fn hash_json(value: &serde_json::Value, hasher: &mut impl Hasher) {
+    use serde_json::Value;
+
+    match value {
+        Value::Null => 0u8.hash(hasher),
+        Value::Bool(b) => {
+            1u8.hash(hasher);
+            b.hash(hasher);
+        }
+        Value::Number(n) => {
+            2u8.hash(hasher);
+            n.to_string().hash(hasher);
+        }
+        Value::String(s) => {
+            3u8.hash(hasher);
+            s.hash(hasher);
+        }
+        Value::Array(items) => {
+            4u8.hash(hasher);
+            items.len().hash(hasher);
+            for item in items {
+                hash_json(item, hasher);
+            }
+        }
+        Value::Object(map) => {
+            5u8.hash(hasher);
+            map.len().hash(hasher);
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            for (key, value) in entries {
+                key.hash(hasher);
+                hash_json(value, hasher);
+            }
         }
     }
 }
@@ -126,6 +341,8 @@ pub struct CacheStats {
     pub entries: usize,
     /// Number of evictions
     pub evictions: u64,
+    /// Stale entries served after upstream failures
+    pub stale_hits: u64,
 }
 
 impl CacheStats {
@@ -142,6 +359,9 @@ impl CacheStats {
 }
 
 /// Response cache for LLM completions
+///
+/// Clones share their entries and statistics.
+#[derive(Clone)]
 pub struct ResponseCache {
     /// Cache configuration
     config: CacheConfig,
@@ -183,6 +403,15 @@ impl ResponseCache {
         self.config.enabled
     }
 
+    /// Whether an entry should be kept in the cache
+    ///
+    /// Expired entries are retained while they can still be served stale.
+    fn should_retain(&self, entry: &CacheEntry) -> bool {
+        !entry.is_expired()
+            || (self.config.serve_stale_on_error
+                && entry.is_within_staleness(self.config.max_staleness))
+    }
+
     /// Check if a request is cacheable
     #[must_use]
     pub fn is_cacheable(&self, request: &GatewayRequest) -> bool {
@@ -207,18 +436,63 @@ impl ResponseCache {
 
     /// Get a cached response
     pub async fn get(&self, request: &GatewayRequest) -> Option<GatewayResponse> {
+        match self.lookup(request, false).await? {
+            CachedValue::Response(response) => Some(response),
+            CachedValue::Stream(_) => None,
+        }
+    }
+
+    /// Get a cached stream, replayed chunk by chunk
+    ///
+    /// Chunks are spaced `stream_replay_interval` apart. Returns `None` on a
+    /// miss, or when the request is not cacheable.
+    pub async fn get_stream(
+        &self,
+        request: &GatewayRequest,
+    ) -> Option<BoxStream<'static, Result<ChatChunk, GatewayError>>> {
+        let CachedValue::Stream(chunks) = self.lookup(request, true).await? else {
+            return None;
+        };
+        let interval = self.config.stream_replay_interval;
+        let replay = futures::stream::iter(0..chunks.len()).then(move |i| {
+            let chunks = Arc::clone(&chunks);
+            async move {
+                if i > 0 && !interval.is_zero() {
+                    tokio::time::sleep(interval).await;
+                }
+                Ok(chunks[i].clone())
+            }
+        });
+        Some(replay.boxed())
+    }
+
+    /// Look up the live entry for a request, recording a hit or miss
+    ///
+    /// An entry holding a stream only counts as a hit when `stream` is set,
+    /// and one holding a complete response only when it is not.
+    /// Cache key for a request under the configured normalization
+    fn key_for(&self, request: &GatewayRequest) -> CacheKey {
+        CacheKey::normalized(request, &self.config.key_normalization)
+    }
+
+    async fn lookup(&self, request: &GatewayRequest, stream: bool) -> Option<CachedValue> {
         if !self.is_cacheable(request) {
             return None;
         }
 
-        let key = CacheKey::from_request(request);
+        let key = self.key_for(request);
 
         let mut entries = self.entries.write().await;
         let mut stats = self.stats.write().await;
 
-        if let Some(entry) = entries.get_mut(&key) {
+        let entry = entries
+            .get_mut(&key)
+            .filter(|entry| matches!(entry.value, CachedValue::Stream(_)) == stream);
+        if let Some(entry) = entry {
             if entry.is_expired() {
-                entries.remove(&key);
+                if !self.should_retain(entry) {
+                    entries.remove(&key);
+                }
                 stats.misses += 1;
                 stats.entries = entries.len();
                 debug!(model = %request.model, "Cache miss (expired)");
@@ -231,7 +505,7 @@ impl ResponseCache {
                     hits = entry.hits,
                     "Cache hit"
                 );
-                Some(entry.response.clone())
+                Some(entry.value.clone())
             }
         } else {
             stats.misses += 1;
@@ -240,28 +514,75 @@ impl ResponseCache {
         }
     }
 
-    /// Put a response in the cache
-    pub async fn put(&self, request: &GatewayRequest, response: GatewayResponse) {
-        if !self.is_cacheable(request) {
-            return;
+    /// Get a cached response within the request's remaining time
+    ///
+    /// Gives up and reports a miss when the deadline passes before the
+    /// lookup completes (e.g. while waiting on a contended lock), leaving the
+    /// rest of the budget to the provider call.
+    pub async fn get_with_context(
+        &self,
+        request: &GatewayRequest,
+        ctx: &RequestContext,
+    ) -> Option<GatewayResponse> {
+        match ctx.run(async { Ok(self.get(request).await) }).await {
+            Ok(response) => response,
+            Err(e) => {
+                debug!(model = %request.model, error = %e, "Cache lookup abandoned");
+                None
+            }
+        }
+    }
+
+    /// Get a cached response to serve after an upstream failure
+    ///
+    /// Returns the entry for the request even if it has expired, as long as
+    /// it is no older than `max_staleness` past its TTL. Returns `None` when
+    /// `serve_stale_on_error` is disabled or the request is not cacheable.
+    pub async fn get_stale(&self, request: &GatewayRequest) -> Option<GatewayResponse> {
+        if !self.config.serve_stale_on_error || !self.is_cacheable(request) {
+            return None;
         }
 
-        let key = CacheKey::from_request(request);
+        let key = self.key_for(request);
 
         let mut entries = self.entries.write().await;
         let mut stats = self.stats.write().await;
 
-        // Check if we need to evict entries
-        if entries.len() >= self.config.max_entries {
-            self.evict_lru(&mut entries, &mut stats);
+        let entry = entries.get_mut(&key)?;
+        if !entry.is_within_staleness(self.config.max_staleness) {
+            entries.remove(&key);
+            stats.entries = entries.len();
+            debug!(model = %request.model, "Stale entry exceeds max staleness");
+            return None;
         }
 
-        entries.insert(key, CacheEntry::new(response, self.config.default_ttl));
-        stats.entries = entries.len();
+        let CachedValue::Response(response) = &entry.value else {
+            return None;
+        };
+        entry.hits += 1;
+        stats.stale_hits += 1;
+        debug!(
+            model = %request.model,
+            age_ms = entry.created_at.elapsed().as_millis(),
+            "Serving stale cache entry"
+        );
+        Some(response.clone())
+    }
+
+    /// Put a response in the cache
+    pub async fn put(&self, request: &GatewayRequest, response: GatewayResponse) {
+        if !self.is_cacheable(request) {
+            return;
+        }
+
+        let key = self.key_for(request);
+        let entries = self
+            .insert(key, CachedValue::Response(response), self.config.default_ttl)
+            .await;
 
         debug!(
             model = %request.model,
-            entries = stats.entries,
+            entries,
             "Response cached"
         );
     }
@@ -277,8 +598,61 @@ impl ResponseCache {
             return;
         }
 
-        let key = CacheKey::from_request(request);
+        let key = self.key_for(request);
+        self.insert(key, CachedValue::Response(response), ttl).await;
+    }
+
+    /// Forward a streamed response, caching it once it completes
+    ///
+    /// Chunks are passed through unchanged as they arrive and recorded on the
+    /// side. The stream is cached only when it ends with a finish reason
+    /// other than `content_filter`; a stream that fails, is cut short, or is
+    /// dropped by the client before its end is not cached. Requests that are
+    /// not cacheable get `upstream` back as is.
+    pub fn cache_stream<S>(
+        &self,
+        request: &GatewayRequest,
+        upstream: S,
+    ) -> BoxStream<'static, Result<ChatChunk, GatewayError>>
+    where
+        S: Stream<Item = Result<ChatChunk, GatewayError>> + Send + 'static,
+    {
+        if !self.is_cacheable(request) {
+            return upstream.boxed();
+        }
 
+        let tee = StreamTee {
+            cache: self.clone(),
+            key: self.key_for(request),
+            model: request.model.clone(),
+            chunks: Vec::new(),
+        };
+        let state = (upstream.boxed(), Some(tee));
+        futures::stream::unfold(state, |(mut upstream, mut tee)| async move {
+            match upstream.next().await {
+                Some(Ok(chunk)) => {
+                    if let Some(tee) = tee.as_mut() {
+                        tee.chunks.push(chunk.clone());
+                    }
+                    Some((Ok(chunk), (upstream, tee)))
+                }
+                // Keep forwarding, but a failed stream is never cached
+                Some(Err(e)) => Some((Err(e), (upstream, None))),
+                None => {
+                    if let Some(tee) = tee {
+                        tee.commit().await;
+                    }
+                    None
+                }
+            }
+        })
+        .boxed()
+    }
+
+    /// Insert an entry, evicting first if the cache is full
+    ///
+    /// Returns the number of entries afterwards.
+    async fn insert(&self, key: CacheKey, value: CachedValue, ttl: Duration) -> usize {
         let mut entries = self.entries.write().await;
         let mut stats = self.stats.write().await;
 
@@ -287,15 +661,16 @@ impl ResponseCache {
             self.evict_lru(&mut entries, &mut stats);
         }
 
-        entries.insert(key, CacheEntry::new(response, ttl));
+        entries.insert(key, CacheEntry::new(value, ttl));
         stats.entries = entries.len();
+        stats.entries
     }
 
     /// Evict least recently used entries
     fn evict_lru(&self, entries: &mut HashMap<CacheKey, CacheEntry>, stats: &mut CacheStats) {
         // First, remove all expired entries
         let before = entries.len();
-        entries.retain(|_, entry| !entry.is_expired());
+        entries.retain(|_, entry| self.should_retain(entry));
         let removed_expired = before - entries.len();
 
         // If we still need to evict, remove entries with lowest hit counts
@@ -343,7 +718,7 @@ impl ResponseCache {
         let mut stats = self.stats.write().await;
 
         let before = entries.len();
-        entries.retain(|_, entry| !entry.is_expired());
+        entries.retain(|_, entry| self.should_retain(entry));
         let removed = before - entries.len();
 
         stats.entries = entries.len();
@@ -378,6 +753,40 @@ impl ResponseCache {
     }
 }
 
+/// Records a stream passing through [`ResponseCache::cache_stream`]
+struct StreamTee {
+    cache: ResponseCache,
+    key: CacheKey,
+    model: String,
+    chunks: Vec<ChatChunk>,
+}
+
+impl StreamTee {
+    /// Cache the recorded chunks if the stream finished cleanly
+    ///
+    /// Only called once upstream has ended without an error.
+    async fn commit(self) {
+        let finish_reasons: Vec<FinishReason> = self
+            .chunks
+            .iter()
+            .flat_map(|chunk| &chunk.choices)
+            .filter_map(|choice| choice.finish_reason)
+            .collect();
+        if finish_reasons.is_empty() || finish_reasons.contains(&FinishReason::ContentFilter) {
+            debug!(model = %self.model, "Stream not cached without a clean finish");
+            return;
+        }
+
+        let chunks = self.chunks.len();
+        let ttl = self.cache.config.default_ttl;
+        let entries = self
+            .cache
+            .insert(self.key, CachedValue::Stream(self.chunks.into()), ttl)
+            .await;
+        debug!(model = %self.model, chunks, entries, "Stream cached");
+    }
+}
+
 /// Cache lookup result for metrics
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CacheLookupResult {
@@ -394,7 +803,7 @@ pub enum CacheLookupResult {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use gateway_core::ChatMessage;
+    use gateway_core::{ChatMessage, ChunkChoice, RequestMetadata};
 
     fn make_request(model: &str, content: &str) -> GatewayRequest {
         GatewayRequest::builder()
@@ -416,10 +825,13 @@ mod tests {
                 prompt_tokens: 10,
                 completion_tokens: 20,
                 total_tokens: 30,
+                prompt_tokens_cached: None,
+                completion_tokens_reasoning: None,
             },
             created: 1234567890,
             provider: Some("test".to_string()),
             system_fingerprint: None,
+            service_tier: None,
         }
     }
 
@@ -438,6 +850,27 @@ mod tests {
         assert_eq!(cached.unwrap().id, response.id);
     }
 
+    #[tokio::test(start_paused = true)]
+    async fn test_get_with_context_respects_remaining_time() {
+        let cache = ResponseCache::with_defaults();
+        let request = make_request("gpt-4o", "Hello");
+        cache.put(&request, make_response()).await;
+
+        let ctx = RequestContext::with_timeout(Duration::from_millis(100));
+        assert!(cache.get_with_context(&request, &ctx).await.is_some());
+
+        // A lookup stuck behind a held lock gives up at the deadline
+        let guard = cache.entries.write().await;
+        let start = tokio::time::Instant::now();
+        assert!(cache.get_with_context(&request, &ctx).await.is_none());
+        assert_eq!(start.elapsed(), Duration::from_millis(100));
+        drop(guard);
+
+        // An expired context skips the lookup entirely
+        assert!(cache.get_with_context(&request, &ctx).await.is_none());
+        assert_eq!(cache.stats().await.hits, 1);
+    }
+
     #[tokio::test]
     async fn test_cache_miss() {
         let cache = ResponseCache::with_defaults();
@@ -487,6 +920,7 @@ mod tests {
             max_entries: 100,
             default_ttl: Duration::from_millis(50), // Very short TTL
             cache_streaming: false,
+            ..CacheConfig::default()
         });
 
         let request = make_request("gpt-4o", "Hello");
@@ -535,6 +969,132 @@ mod tests {
         assert!(cache.get(&request).await.is_none());
     }
 
+    fn streaming_cache() -> ResponseCache {
+        ResponseCache::new(CacheConfig {
+            cache_streaming: true,
+            stream_replay_interval: Duration::from_millis(50),
+            ..CacheConfig::default()
+        })
+    }
+
+    fn streaming_request(content: &str) -> GatewayRequest {
+        GatewayRequest::builder()
+            .model("gpt-4o")
+            .message(ChatMessage::user(content))
+            .stream(true)
+            .build()
+            .expect("valid request")
+    }
+
+    fn chunk(choice: ChunkChoice) -> Result<ChatChunk, GatewayError> {
+        Ok(ChatChunk::builder().id("chunk").model("gpt-4o").choice(choice).build())
+    }
+
+    fn content(chunks: &[Result<ChatChunk, GatewayError>]) -> String {
+        chunks
+            .iter()
+            .filter_map(|c| c.as_ref().ok()?.content())
+            .collect()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_stream_cached_and_replayed_with_pacing() {
+        let cache = streaming_cache();
+        let request = streaming_request("Hello");
+        let upstream = futures::stream::iter(vec![
+            chunk(ChunkChoice::with_content(0, "Hel")),
+            chunk(ChunkChoice::with_content(0, "lo")),
+            chunk(ChunkChoice::with_finish(0, FinishReason::Stop)),
+        ]);
+
+        assert!(cache.get_stream(&request).await.is_none());
+        let forwarded: Vec<_> = cache.cache_stream(&request, upstream).collect().await;
+        assert_eq!(forwarded.len(), 3);
+        assert_eq!(content(&forwarded), "Hello");
+
+        let start = tokio::time::Instant::now();
+        let replayed: Vec<_> = cache
+            .get_stream(&request)
+            .await
+            .expect("cached stream")
+            .collect()
+            .await;
+        assert_eq!(content(&replayed), "Hello");
+        assert_eq!(
+            replayed.last().and_then(|c| c.as_ref().ok()?.finish_reason()),
+            Some(FinishReason::Stop)
+        );
+        assert_eq!(start.elapsed(), Duration::from_millis(100));
+
+        // Streamed and complete responses are cached separately
+        let mut plain = request.clone();
+        plain.stream = false;
+        assert!(cache.get(&plain).await.is_none());
+        assert!(cache.get(&request).await.is_none());
+
+        let stats = cache.stats().await;
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 3);
+        assert_eq!(stats.entries, 1);
+    }
+
+    #[tokio::test]
+    async fn test_incomplete_streams_not_cached() {
+        let cache = streaming_cache();
+
+        let failed = streaming_request("failed");
+        let upstream = futures::stream::iter(vec![
+            chunk(ChunkChoice::with_content(0, "Hel")),
+            Err(GatewayError::streaming("connection reset")),
+            chunk(ChunkChoice::with_finish(0, FinishReason::Stop)),
+        ]);
+        let forwarded: Vec<_> = cache.cache_stream(&failed, upstream).collect().await;
+        assert_eq!(forwarded.len(), 3);
+        assert!(forwarded[1].is_err());
+        assert!(cache.get_stream(&failed).await.is_none());
+
+        let unfinished = streaming_request("unfinished");
+        let upstream = futures::stream::iter(vec![chunk(ChunkChoice::with_content(0, "Hel"))]);
+        let _: Vec<_> = cache.cache_stream(&unfinished, upstream).collect().await;
+        assert!(cache.get_stream(&unfinished).await.is_none());
+
+        let filtered = streaming_request("filtered");
+        let upstream = futures::stream::iter(vec![chunk(ChunkChoice::with_finish(
+            0,
+            FinishReason::ContentFilter,
+        ))]);
+        let _: Vec<_> = cache.cache_stream(&filtered, upstream).collect().await;
+        assert!(cache.get_stream(&filtered).await.is_none());
+
+        // Dropped by the client before the finish chunk was read
+        let dropped = streaming_request("dropped");
+        let upstream = futures::stream::iter(vec![
+            chunk(ChunkChoice::with_content(0, "Hel")),
+            chunk(ChunkChoice::with_finish(0, FinishReason::Stop)),
+        ]);
+        let mut stream = cache.cache_stream(&dropped, upstream);
+        assert!(stream.next().await.is_some());
+        drop(stream);
+        assert!(cache.get_stream(&dropped).await.is_none());
+
+        assert_eq!(cache.stats().await.entries, 0);
+    }
+
+    #[tokio::test]
+    async fn test_stream_passed_through_when_streaming_cache_disabled() {
+        let cache = ResponseCache::with_defaults();
+        let request = streaming_request("Hello");
+        let upstream = futures::stream::iter(vec![
+            chunk(ChunkChoice::with_content(0, "Hi")),
+            chunk(ChunkChoice::with_finish(0, FinishReason::Stop)),
+        ]);
+
+        let forwarded: Vec<_> = cache.cache_stream(&request, upstream).collect().await;
+        assert_eq!(content(&forwarded), "Hi");
+        assert!(cache.get_stream(&request).await.is_none());
+        assert_eq!(cache.stats().await.entries, 0);
+    }
+
     #[tokio::test]
     async fn test_cache_stats() {
         let cache = ResponseCache::with_defaults();
@@ -558,6 +1118,53 @@ mod tests {
         assert!((stats.hit_rate() - 50.0).abs() < 0.1);
     }
 
+    #[tokio::test]
+    async fn test_get_stale_within_max_staleness() {
+        let cache = ResponseCache::new(CacheConfig {
+            default_ttl: Duration::from_millis(20),
+            serve_stale_on_error: true,
+            max_staleness: Duration::from_secs(60),
+            ..CacheConfig::default()
+        });
+
+        let request = make_request("gpt-4o", "Hello");
+        cache.put(&request, make_response()).await;
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        // Expired for normal lookups, but retained for stale serving
+        assert!(cache.get(&request).await.is_none());
+        assert!(cache.get_stale(&request).await.is_some());
+        assert_eq!(cache.stats().await.stale_hits, 1);
+    }
+
+    #[tokio::test]
+    async fn test_get_stale_beyond_max_staleness() {
+        let cache = ResponseCache::new(CacheConfig {
+            default_ttl: Duration::from_millis(10),
+            serve_stale_on_error: true,
+            max_staleness: Duration::from_millis(10),
+            ..CacheConfig::default()
+        });
+
+        let request = make_request("gpt-4o", "Hello");
+        cache.put(&request, make_response()).await;
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        assert!(cache.get_stale(&request).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_get_stale_disabled() {
+        let cache = ResponseCache::with_defaults();
+
+        let request = make_request("gpt-4o", "Hello");
+        cache.put(&request, make_response()).await;
+
+        assert!(cache.get_stale(&request).await.is_none());
+    }
+
     #[tokio::test]
     async fn test_cache_eviction() {
         let cache = ResponseCache::new(CacheConfig {
@@ -565,6 +1172,7 @@ mod tests {
             max_entries: 2,
             default_ttl: Duration::from_secs(3600),
             cache_streaming: false,
+            ..CacheConfig::default()
         });
 
         let request1 = make_request("gpt-4o", "First");
@@ -640,4 +1248,146 @@ mod tests {
 
         assert_ne!(key1, key2);
     }
+
+    fn with_tools(tools: Vec<ToolDefinition>) -> GatewayRequest {
+        let mut request = make_request("gpt-4o", "Weather in Paris?");
+        request.tools = Some(tools);
+        request
+    }
+
+    fn weather_tool(unit_enum: &[&str]) -> ToolDefinition {
+        let mut tool = ToolDefinition::function("get_weather");
+        tool.function.parameters = Some(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "city": {"type": "string"},
+                "unit": {"type": "string", "enum": unit_enum}
+            },
+            "required": ["city"]
+        }));
+        tool
+    }
+
+    #[test]
+    fn test_cache_key_ignores_tool_order() {
+        let time = ToolDefinition::function("get_time");
+        let a = with_tools(vec![weather_tool(&["c", "f"]), time.clone()]);
+        let b = with_tools(vec![time, weather_tool(&["c", "f"])]);
+
+        assert_eq!(CacheKey::from_request(&a), CacheKey::from_request(&b));
+        assert_ne!(
+            CacheKey::from_request(&a),
+            CacheKey::from_request(&make_request("gpt-4o", "Weather in Paris?"))
+        );
+    }
+
+    #[test]
+    fn test_cache_key_changes_with_tool_schema_and_choice() {
+        let base = with_tools(vec![weather_tool(&["c", "f"])]);
+        let changed = with_tools(vec![weather_tool(&["c", "f", "k"])]);
+        assert_ne!(
+            CacheKey::from_request(&base),
+            CacheKey::from_request(&changed)
+        );
+
+        let mut forced = base.clone();
+        forced.tool_choice = Some(ToolChoice::String("required".to_string()));
+        assert_ne!(
+            CacheKey::from_request(&base),
+            CacheKey::from_request(&forced)
+        );
+    }
+
+    #[test]
+    fn test_equivalent_requests_share_a_key() {
+        let rules = CacheKeyNormalization::default();
+        let base = with_tools(vec![weather_tool(&["c", "f"]), ToolDefinition::function("get_time")]);
+
+        let mut equivalent = with_tools(vec![ToolDefinition::function("get_time"), weather_tool(&["c", "f"])]);
+        equivalent.messages = vec![ChatMessage::user("  Weather in Paris?\r\n")];
+        equivalent.temperature = Some(0.71);
+        equivalent.user = Some("someone-else".to_string());
+        equivalent.metadata = Some(RequestMetadata {
+            project_id: Some("billing-only".to_string()),
+            ..RequestMetadata::default()
+        });
+
+        assert_eq!(
+            CacheKey::normalized(&base, &rules),
+            CacheKey::normalized(&equivalent, &rules)
+        );
+
+        // Modalities are a set
+        let mut a = base.clone();
+        a.modalities = Some(vec!["text".to_string(), "audio".to_string()]);
+        let mut b = base;
+        b.modalities = Some(vec!["audio".to_string(), "text".to_string()]);
+        assert_eq!(CacheKey::normalized(&a, &rules), CacheKey::normalized(&b, &rules));
+    }
+
+    #[test]
+    fn test_normalization_keeps_meaningful_differences() {
+        let rules = CacheKeyNormalization::default();
+        let base = make_request("gpt-4o", "Hello");
+
+        let mut warmer = base.clone();
+        warmer.temperature = Some(0.9);
+        assert_ne!(CacheKey::normalized(&base, &rules), CacheKey::normalized(&warmer, &rules));
+
+        let mut unset = base.clone();
+        unset.temperature = None;
+        assert_ne!(CacheKey::normalized(&base, &rules), CacheKey::normalized(&unset, &rules));
+
+        // Turn order is part of the prompt
+        let mut a = base.clone();
+        a.messages = vec![ChatMessage::user("one"), ChatMessage::user("two")];
+        let mut b = base.clone();
+        b.messages = vec![ChatMessage::user("two"), ChatMessage::user("one")];
+        assert_ne!(CacheKey::normalized(&a, &rules), CacheKey::normalized(&b, &rules));
+
+        let mut longer = base.clone();
+        longer.max_tokens = Some(500);
+        assert_ne!(CacheKey::normalized(&base, &rules), CacheKey::normalized(&longer, &rules));
+        let ignoring = CacheKeyNormalization {
+            ignore_max_tokens: true,
+            ..CacheKeyNormalization::default()
+        };
+        assert_eq!(
+            CacheKey::normalized(&base, &ignoring),
+            CacheKey::normalized(&longer, &ignoring)
+        );
+    }
+
+    #[test]
+    fn test_strict_normalization_keys_on_exact_request() {
+        let strict = CacheKeyNormalization::strict();
+        let base = make_request("gpt-4o", "Hello");
+
+        let mut padded = base.clone();
+        padded.messages = vec![ChatMessage::user("Hello ")];
+        assert_ne!(CacheKey::normalized(&base, &strict), CacheKey::normalized(&padded, &strict));
+
+        let mut nudged = base.clone();
+        nudged.temperature = Some(0.71);
+        assert_ne!(CacheKey::normalized(&base, &strict), CacheKey::normalized(&nudged, &strict));
+    }
+
+    #[tokio::test]
+    async fn test_cache_applies_configured_normalization() {
+        let response = make_response();
+        let request = make_request("gpt-4o", "Hello");
+        let mut padded = request.clone();
+        padded.messages = vec![ChatMessage::user("Hello\n")];
+
+        let cache = ResponseCache::new(CacheConfig::default());
+        cache.put(&request, response.clone()).await;
+        assert!(cache.get(&padded).await.is_some());
+
+        let strict = ResponseCache::new(CacheConfig {
+            key_normalization: CacheKeyNormalization::strict(),
+            ..CacheConfig::default()
+        });
+        strict.put(&request, response).await;
+        assert!(strict.get(&padded).await.is_none());
+    }
 }
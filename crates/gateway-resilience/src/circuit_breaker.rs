@@ -3,7 +3,7 @@
 //! The circuit breaker prevents cascading failures by stopping requests
 //! to a failing service and allowing it time to recover.
 
-use gateway_core::GatewayError;
+use gateway_core::{GatewayError, ProviderErrorKind};
 use parking_lot::RwLock;
 use std::sync::atomic::{AtomicU32, AtomicU64, AtomicU8, Ordering};
 use std::time::Duration;
@@ -37,14 +37,21 @@ impl From<u8> for CircuitState {
 pub struct CircuitBreakerConfig {
     /// Number of failures before opening the circuit
     pub failure_threshold: u32,
-    /// Number of successes required to close the circuit
+    /// Number of consecutive successes required to close the circuit
     pub success_threshold: u32,
+    /// Trial requests allowed in flight while half-open; 0 means unlimited
+    ///
+    /// Callers beyond the limit are rejected as if the circuit were open,
+    /// so a recovering provider isn't hit by every queued request at once.
+    pub half_open_max_probes: u32,
     /// Time to wait before testing the circuit (half-open)
     pub timeout: Duration,
     /// Sliding window size for failure rate calculation
     pub window_size: u32,
     /// Minimum requests before failure rate is considered
     pub min_requests: u32,
+    /// Error kinds [`CircuitBreaker::record_error`] counts as failures
+    pub failure_kinds: Vec<ProviderErrorKind>,
 }
 
 impl Default for CircuitBreakerConfig {
@@ -52,9 +59,11 @@ impl Default for CircuitBreakerConfig {
         Self {
             failure_threshold: 5,
             success_threshold: 3,
+            half_open_max_probes: 3,
             timeout: Duration::from_secs(30),
             window_size: 100,
             min_requests: 10,
+            failure_kinds: ProviderErrorKind::PROVIDER_FAULTS.to_vec(),
         }
     }
 }
@@ -71,6 +80,10 @@ pub struct CircuitBreaker {
     failure_count: AtomicU32,
     /// Success count in half-open state
     half_open_successes: AtomicU32,
+    /// Trial requests in flight in half-open state
+    half_open_probes: AtomicU32,
+    /// When the current round of half-open probes started (milliseconds since epoch)
+    probes_started_at: AtomicU64,
     /// Total request count in window
     request_count: AtomicU32,
     /// Timestamp when circuit opened (milliseconds since epoch)
@@ -89,6 +102,8 @@ impl CircuitBreaker {
             state: AtomicU8::new(CircuitState::Closed as u8),
             failure_count: AtomicU32::new(0),
             half_open_successes: AtomicU32::new(0),
+            half_open_probes: AtomicU32::new(0),
+            probes_started_at: AtomicU64::new(0),
             request_count: AtomicU32::new(0),
             opened_at: AtomicU64::new(0),
             transition_lock: RwLock::new(()),
@@ -117,22 +132,23 @@ impl CircuitBreaker {
     ///
     /// Returns Ok if request can proceed, Err if circuit is open
     ///
+    /// While half-open, an Ok takes one of `half_open_max_probes` probe
+    /// slots, released when the request's outcome is recorded.
+    ///
     /// # Errors
-    /// Returns `GatewayError::CircuitBreakerOpen` if circuit is open
+    /// Returns `GatewayError::CircuitBreakerOpen` if circuit is open, or
+    /// half-open with every probe slot taken
     pub fn check(&self) -> Result<(), GatewayError> {
         let current_state = self.state();
 
         match current_state {
             CircuitState::Closed => Ok(()),
-            CircuitState::HalfOpen => {
-                // Allow limited requests in half-open state
-                Ok(())
-            }
+            CircuitState::HalfOpen => self.acquire_probe(),
             CircuitState::Open => {
                 // Check if timeout has elapsed
                 if self.should_attempt_reset() {
                     self.transition_to_half_open();
-                    Ok(())
+                    self.acquire_probe()
                 } else {
                     Err(GatewayError::circuit_breaker_open(&self.provider_id))
                 }
@@ -140,6 +156,55 @@ impl CircuitBreaker {
         }
     }
 
+    /// Take a half-open probe slot
+    ///
+    /// A probe whose outcome is never recorded would hold its slot forever,
+    /// so once `timeout` passes since the round of probes started, the
+    /// slots are freed for a new round.
+    fn acquire_probe(&self) -> Result<(), GatewayError> {
+        let max = self.config.half_open_max_probes;
+        if max == 0 {
+            return Ok(());
+        }
+
+        let acquired = self
+            .half_open_probes
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |probes| {
+                (probes < max).then_some(probes + 1)
+            })
+            .is_ok();
+        if acquired {
+            return Ok(());
+        }
+
+        let started = self.probes_started_at.load(Ordering::Acquire);
+        let now = now_millis();
+        if now.saturating_sub(started) >= self.config.timeout.as_millis() as u64
+            && self
+                .probes_started_at
+                .compare_exchange(started, now, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+        {
+            debug!(
+                provider = %self.provider_id,
+                "Circuit breaker half-open probes unanswered, starting a new round"
+            );
+            self.half_open_probes.store(1, Ordering::Release);
+            return Ok(());
+        }
+
+        Err(GatewayError::circuit_breaker_open(&self.provider_id))
+    }
+
+    /// Free a half-open probe slot
+    fn release_probe(&self) {
+        let _ = self
+            .half_open_probes
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |probes| {
+                probes.checked_sub(1)
+            });
+    }
+
     /// Record a successful request
     pub fn record_success(&self) {
         self.request_count.fetch_add(1, Ordering::Relaxed);
@@ -151,6 +216,7 @@ impl CircuitBreaker {
                 // (optional, depends on your failure rate calculation strategy)
             }
             CircuitState::HalfOpen => {
+                self.release_probe();
                 let successes = self.half_open_successes.fetch_add(1, Ordering::Relaxed) + 1;
                 debug!(
                     provider = %self.provider_id,
@@ -204,6 +270,56 @@ impl CircuitBreaker {
         }
     }
 
+    /// Record a failed request by its error
+    ///
+    /// Only errors of a kind in `failure_kinds` count as failures. Any other
+    /// error is ignored: a caller sending malformed requests says nothing
+    /// about the provider either way. It still frees its half-open probe
+    /// slot, without counting towards closing the circuit.
+    pub fn record_error(&self, error: &GatewayError) {
+        let kind = error.provider_error_kind();
+        if self.config.failure_kinds.contains(&kind) {
+            self.record_failure();
+        } else {
+            debug!(
+                provider = %self.provider_id,
+                kind = ?kind,
+                "Error not counted against circuit breaker"
+            );
+            if self.state() == CircuitState::HalfOpen {
+                self.release_probe();
+            }
+        }
+    }
+
+    /// Record the outcome of a health-check probe
+    ///
+    /// Health checks bypass [`Self::check`] since they are the signal that
+    /// lets an open circuit recover. A healthy probe closes an open or
+    /// half-open circuit; an unhealthy probe reopens a half-open circuit.
+    /// Probe results never trip a closed circuit, which is left to real
+    /// traffic.
+    pub fn record_probe_result(&self, healthy: bool) {
+        let current_state = self.state();
+        match (current_state, healthy) {
+            (CircuitState::Open | CircuitState::HalfOpen, true) => {
+                debug!(
+                    provider = %self.provider_id,
+                    "Circuit breaker health probe succeeded"
+                );
+                self.transition_to_closed();
+            }
+            (CircuitState::HalfOpen, false) => {
+                debug!(
+                    provider = %self.provider_id,
+                    "Circuit breaker health probe failed, reopening"
+                );
+                self.transition_to_open();
+            }
+            (CircuitState::Closed, _) | (CircuitState::Open, false) => {}
+        }
+    }
+
     /// Check if we should attempt to reset (timeout elapsed)
     fn should_attempt_reset(&self) -> bool {
         let opened_at = self.opened_at.load(Ordering::Acquire);
@@ -211,12 +327,7 @@ impl CircuitBreaker {
             return false;
         }
 
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map(|d| d.as_millis() as u64)
-            .unwrap_or(0);
-
-        let elapsed = now.saturating_sub(opened_at);
+        let elapsed = now_millis().saturating_sub(opened_at);
         elapsed >= self.config.timeout.as_millis() as u64
     }
 
@@ -227,12 +338,9 @@ impl CircuitBreaker {
         let prev_state = self.state.swap(CircuitState::Open as u8, Ordering::Release);
 
         if prev_state != CircuitState::Open as u8 {
-            let now = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .map(|d| d.as_millis() as u64)
-                .unwrap_or(0);
-            self.opened_at.store(now, Ordering::Release);
+            self.opened_at.store(now_millis(), Ordering::Release);
             self.half_open_successes.store(0, Ordering::Relaxed);
+            self.half_open_probes.store(0, Ordering::Release);
 
             warn!(
                 provider = %self.provider_id,
@@ -245,10 +353,18 @@ impl CircuitBreaker {
     fn transition_to_half_open(&self) {
         let _guard = self.transition_lock.write();
 
-        let prev_state = self.state.swap(CircuitState::HalfOpen as u8, Ordering::Release);
+        // Only the first caller past the timeout starts the half-open round
+        let opened = self.state.compare_exchange(
+            CircuitState::Open as u8,
+            CircuitState::HalfOpen as u8,
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        );
 
-        if prev_state == CircuitState::Open as u8 {
+        if opened.is_ok() {
             self.half_open_successes.store(0, Ordering::Relaxed);
+            self.half_open_probes.store(0, Ordering::Release);
+            self.probes_started_at.store(now_millis(), Ordering::Release);
 
             info!(
                 provider = %self.provider_id,
@@ -265,6 +381,7 @@ impl CircuitBreaker {
             .store(CircuitState::Closed as u8, Ordering::Release);
         self.failure_count.store(0, Ordering::Relaxed);
         self.half_open_successes.store(0, Ordering::Relaxed);
+        self.half_open_probes.store(0, Ordering::Release);
         self.request_count.store(0, Ordering::Relaxed);
         self.opened_at.store(0, Ordering::Release);
 
@@ -292,10 +409,19 @@ impl CircuitBreaker {
             failure_count: self.failure_count.load(Ordering::Relaxed),
             request_count: self.request_count.load(Ordering::Relaxed),
             half_open_successes: self.half_open_successes.load(Ordering::Relaxed),
+            half_open_probes: self.half_open_probes.load(Ordering::Acquire),
         }
     }
 }
 
+/// Milliseconds since the Unix epoch
+fn now_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
 /// Circuit breaker statistics
 #[derive(Debug, Clone)]
 pub struct CircuitBreakerStats {
@@ -307,6 +433,8 @@ pub struct CircuitBreakerStats {
     pub request_count: u32,
     /// Success count in half-open state
     pub half_open_successes: u32,
+    /// Trial requests in flight in half-open state
+    pub half_open_probes: u32,
 }
 
 impl CircuitBreakerStats {
@@ -448,6 +576,108 @@ mod tests {
         assert!((stats.failure_rate() - 0.666).abs() < 0.01);
     }
 
+    #[test]
+    fn test_probe_result_closes_open_circuit() {
+        let config = CircuitBreakerConfig {
+            failure_threshold: 2,
+            min_requests: 1,
+            ..Default::default()
+        };
+        let cb = CircuitBreaker::new("test-provider", config);
+
+        cb.record_failure();
+        cb.record_failure();
+        assert_eq!(cb.state(), CircuitState::Open);
+
+        // A failed probe leaves the circuit open
+        cb.record_probe_result(false);
+        assert_eq!(cb.state(), CircuitState::Open);
+
+        cb.record_probe_result(true);
+        assert_eq!(cb.state(), CircuitState::Closed);
+        assert!(cb.check().is_ok());
+    }
+
+    #[test]
+    fn test_probe_failure_does_not_trip_closed_circuit() {
+        let config = CircuitBreakerConfig {
+            failure_threshold: 1,
+            min_requests: 1,
+            ..Default::default()
+        };
+        let cb = CircuitBreaker::new("test-provider", config);
+
+        cb.record_probe_result(false);
+        assert_eq!(cb.state(), CircuitState::Closed);
+        assert_eq!(cb.stats().request_count, 0);
+    }
+
+    #[test]
+    fn test_invalid_requests_do_not_open_circuit() {
+        let config = CircuitBreakerConfig {
+            failure_threshold: 3,
+            min_requests: 1,
+            ..Default::default()
+        };
+        let cb = CircuitBreaker::new("test-provider", config);
+
+        for status in [400, 422, 400, 404, 422, 401, 429] {
+            cb.record_error(&GatewayError::provider("test-provider", "rejected", Some(status), false));
+        }
+        assert_eq!(cb.state(), CircuitState::Closed);
+        assert_eq!(cb.stats().failure_count, 0);
+        assert_eq!(cb.stats().request_count, 0);
+    }
+
+    #[test]
+    fn test_ignored_errors_free_probe_slot_without_closing() {
+        let cb = ready_to_probe(1);
+
+        for _ in 0..5 {
+            assert!(cb.check().is_ok());
+            cb.record_error(&GatewayError::provider("test-provider", "bad request", Some(400), false));
+        }
+        assert_eq!(cb.state(), CircuitState::HalfOpen);
+        assert_eq!(cb.stats().half_open_successes, 0);
+        assert_eq!(cb.stats().half_open_probes, 0);
+    }
+
+    #[test]
+    fn test_provider_faults_open_circuit() {
+        let config = CircuitBreakerConfig {
+            failure_threshold: 3,
+            min_requests: 1,
+            ..Default::default()
+        };
+        let cb = CircuitBreaker::new("test-provider", config);
+
+        cb.record_error(&GatewayError::provider("test-provider", "overloaded", Some(529), true));
+        cb.record_error(&GatewayError::provider("test-provider", "bad request", Some(400), false));
+        cb.record_error(&GatewayError::timeout(Duration::from_secs(30)));
+        assert_eq!(cb.state(), CircuitState::Closed);
+
+        cb.record_error(&GatewayError::provider("test-provider", "unavailable", Some(503), true));
+        assert_eq!(cb.state(), CircuitState::Open);
+    }
+
+    #[test]
+    fn test_failure_kinds_configurable() {
+        let config = CircuitBreakerConfig {
+            failure_threshold: 2,
+            min_requests: 1,
+            failure_kinds: vec![ProviderErrorKind::RateLimited],
+            ..Default::default()
+        };
+        let cb = CircuitBreaker::new("test-provider", config);
+
+        cb.record_error(&GatewayError::timeout(Duration::from_secs(30)));
+        assert_eq!(cb.stats().failure_count, 0);
+
+        cb.record_error(&GatewayError::provider("test-provider", "slow down", Some(429), true));
+        cb.record_error(&GatewayError::provider("test-provider", "slow down", Some(429), true));
+        assert_eq!(cb.state(), CircuitState::Open);
+    }
+
     #[test]
     fn test_min_requests_threshold() {
         let config = CircuitBreakerConfig {
@@ -465,4 +695,92 @@ mod tests {
         // Should still be closed because we haven't hit min_requests
         assert_eq!(cb.state(), CircuitState::Closed);
     }
+
+    /// Breaker opened long enough ago that the next check goes half-open
+    fn ready_to_probe(max_probes: u32) -> CircuitBreaker {
+        let config = CircuitBreakerConfig {
+            success_threshold: 3,
+            half_open_max_probes: max_probes,
+            timeout: Duration::from_secs(60),
+            ..Default::default()
+        };
+        let cb = CircuitBreaker::new("test-provider", config);
+        cb.force_open();
+        cb.opened_at.store(1, Ordering::Release);
+        cb
+    }
+
+    #[test]
+    fn test_half_open_admits_limited_concurrent_probes() {
+        use std::sync::{Arc, Barrier};
+
+        let cb = Arc::new(ready_to_probe(2));
+        let callers = 32;
+        let barrier = Arc::new(Barrier::new(callers));
+        let reached = Arc::new(AtomicU32::new(0));
+
+        let handles: Vec<_> = (0..callers)
+            .map(|_| {
+                let (cb, barrier, reached) = (Arc::clone(&cb), Arc::clone(&barrier), Arc::clone(&reached));
+                std::thread::spawn(move || {
+                    barrier.wait();
+                    if cb.check().is_ok() {
+                        // The wrapped call; outcomes are recorded below
+                        reached.fetch_add(1, Ordering::SeqCst);
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(reached.load(Ordering::SeqCst), 2);
+        assert_eq!(cb.state(), CircuitState::HalfOpen);
+        assert_eq!(cb.stats().half_open_probes, 2);
+        assert!(cb.check().is_err());
+
+        // A finished probe frees its slot for the next caller
+        cb.record_success();
+        assert_eq!(cb.stats().half_open_probes, 1);
+        assert!(cb.check().is_ok());
+        assert!(cb.check().is_err());
+    }
+
+    #[test]
+    fn test_half_open_closes_after_consecutive_probe_successes() {
+        let cb = ready_to_probe(1);
+
+        for _ in 0..2 {
+            assert!(cb.check().is_ok());
+            cb.record_success();
+            assert_eq!(cb.state(), CircuitState::HalfOpen);
+        }
+        assert!(cb.check().is_ok());
+        cb.record_success();
+        assert_eq!(cb.state(), CircuitState::Closed);
+        assert_eq!(cb.stats().half_open_probes, 0);
+
+        // A failed probe reopens and the successes start over
+        let cb = ready_to_probe(1);
+        assert!(cb.check().is_ok());
+        cb.record_success();
+        assert!(cb.check().is_ok());
+        cb.record_failure();
+        assert_eq!(cb.state(), CircuitState::Open);
+        assert_eq!(cb.stats().half_open_successes, 0);
+    }
+
+    #[test]
+    fn test_unanswered_probes_free_their_slots_after_timeout() {
+        let cb = ready_to_probe(1);
+        assert!(cb.check().is_ok());
+        assert!(cb.check().is_err());
+
+        // The probe's outcome is never recorded
+        cb.probes_started_at.store(1, Ordering::Release);
+        assert!(cb.check().is_ok());
+        assert_eq!(cb.stats().half_open_probes, 1);
+        assert!(cb.check().is_err());
+    }
 }
@@ -67,6 +67,13 @@ pub trait CacheBackend: Send + Sync {
     /// Check if the backend is healthy
     async fn health_check(&self) -> CacheResult<()>;
 
+    /// Report entry count and memory usage
+    ///
+    /// Backends that can't report usage return empty info.
+    async fn info(&self) -> CacheResult<CacheBackendInfo> {
+        Ok(CacheBackendInfo::default())
+    }
+
     /// Get backend name for metrics
     fn name(&self) -> &'static str;
 
@@ -74,6 +81,51 @@ pub trait CacheBackend: Send + Sync {
     fn is_distributed(&self) -> bool;
 }
 
+/// Usage reported by a cache backend
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheBackendInfo {
+    /// Number of live entries (keys)
+    pub entries: Option<u64>,
+    /// Memory used by the backend in bytes (estimated for in-memory backends)
+    pub memory_bytes: Option<u64>,
+    /// Keys evicted due to memory pressure
+    pub evicted_keys: Option<u64>,
+}
+
+impl CacheBackendInfo {
+    /// Extract usage from the output of the Redis `INFO` command
+    ///
+    /// Reads `used_memory`, `evicted_keys` and sums `keys=` across the
+    /// `dbN` lines of the keyspace section.
+    #[must_use]
+    pub fn from_redis_info(info: &str) -> Self {
+        let mut parsed = Self::default();
+
+        for line in info.lines() {
+            let Some((field, value)) = line.trim().split_once(':') else {
+                continue;
+            };
+
+            match field {
+                "used_memory" => parsed.memory_bytes = value.parse().ok(),
+                "evicted_keys" => parsed.evicted_keys = value.parse().ok(),
+                db if db.starts_with("db") => {
+                    let keys = value
+                        .split(',')
+                        .find_map(|kv| kv.strip_prefix("keys="))
+                        .and_then(|keys| keys.parse::<u64>().ok());
+                    if let Some(keys) = keys {
+                        parsed.entries = Some(parsed.entries.unwrap_or(0) + keys);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        parsed
+    }
+}
+
 /// Configuration for distributed cache
 #[derive(Debug, Clone)]
 pub struct DistributedCacheConfig {
@@ -259,6 +311,9 @@ pub struct DistributedCacheKey {
     pub temperature_bucket: u32,
     /// Max tokens
     pub max_tokens: Option<u32>,
+    /// Canonical hash of the tool definitions and tool choice
+    #[serde(default)]
+    pub tools_hash: u64,
 }
 
 impl DistributedCacheKey {
@@ -277,11 +332,22 @@ impl DistributedCacheKey {
                             gateway_core::ContentPart::ImageUrl { image_url } => {
                                 image_url.url.hash(&mut hasher);
                             }
+                            gateway_core::ContentPart::Audio { audio } => {
+                                audio.id.hash(&mut hasher);
+                                audio.data.hash(&mut hasher);
+                            }
                         }
                     }
                 }
             }
         }
+        // Text and audio responses to the same messages differ
+        request.modalities.hash(&mut hasher);
+        request
+            .audio
+            .as_ref()
+            .map(|audio| (&audio.voice, &audio.format))
+            .hash(&mut hasher);
         let messages_hash = hasher.finish();
 
         let temperature_bucket = request
@@ -294,6 +360,7 @@ impl DistributedCacheKey {
             messages_hash,
             temperature_bucket,
             max_tokens: request.max_tokens,
+            tools_hash: crate::cache::tools_hash(request),
         }
     }
 
@@ -301,12 +368,13 @@ impl DistributedCacheKey {
     #[must_use]
     pub fn to_string_key(&self, prefix: &str) -> String {
         format!(
-            "{}:cache:{}:{}:{}:{}",
+            "{}:cache:{}:{}:{}:{}:{}",
             prefix,
             self.model,
             self.messages_hash,
             self.temperature_bucket,
-            self.max_tokens.unwrap_or(0)
+            self.max_tokens.unwrap_or(0),
+            self.tools_hash
         )
     }
 }
@@ -483,6 +551,21 @@ impl CacheBackend for MemoryCacheBackend {
         Ok(())
     }
 
+    async fn info(&self) -> CacheResult<CacheBackendInfo> {
+        let entries = self.entries.read().await;
+        let live = entries.iter().filter(|(_, entry)| !entry.is_expired());
+
+        let (count, bytes) = live.fold((0u64, 0u64), |(count, bytes), (key, entry)| {
+            (count + 1, bytes + (key.len() + entry.data.len()) as u64)
+        });
+
+        Ok(CacheBackendInfo {
+            entries: Some(count),
+            memory_bytes: Some(bytes),
+            evicted_keys: None,
+        })
+    }
+
     fn name(&self) -> &'static str {
         "memory"
     }
@@ -648,6 +731,21 @@ impl CacheBackend for RedisCacheBackend {
         Ok(())
     }
 
+    async fn info(&self) -> CacheResult<CacheBackendInfo> {
+        if !*self.is_connected.read().await {
+            return Err(DistributedCacheError::Unavailable(
+                "Redis not connected".to_string(),
+            ));
+        }
+
+        // In production:
+        // let info: String = redis::cmd("INFO").query_async(&mut conn).await?;
+        // Ok(CacheBackendInfo::from_redis_info(&info))
+
+        debug!("Redis INFO (mock)");
+        Ok(CacheBackendInfo::default())
+    }
+
     fn name(&self) -> &'static str {
         "redis"
     }
@@ -670,6 +768,8 @@ pub struct DistributedCacheStats {
     pub l2_misses: u64,
     /// Total entries in L1 cache
     pub l1_entries: usize,
+    /// Lookups skipped because the request was not cacheable
+    pub skipped: u64,
     /// Backend errors
     pub backend_errors: u64,
     /// Compression savings (bytes)
@@ -806,6 +906,9 @@ impl DistributedCache {
     /// Get a cached response
     pub async fn get(&self, request: &GatewayRequest) -> Option<GatewayResponse> {
         if !self.is_cacheable(request) {
+            if self.config.enabled {
+                self.stats.write().await.skipped += 1;
+            }
             return None;
         }
 
@@ -982,6 +1085,29 @@ impl DistributedCache {
         }
         Ok(())
     }
+
+    /// Name of the backend that holds the authoritative copy
+    ///
+    /// This is the L2 backend when one is configured, otherwise the local cache.
+    #[must_use]
+    pub fn backend_name(&self) -> &'static str {
+        match (&self.l2_backend, &self.l1_backend) {
+            (Some(l2), _) => l2.name(),
+            (None, Some(l1)) => l1.name(),
+            (None, None) => "none",
+        }
+    }
+
+    /// Usage reported by the authoritative backend
+    pub async fn backend_info(&self) -> CacheResult<CacheBackendInfo> {
+        if let Some(ref l2) = self.l2_backend {
+            return l2.info().await;
+        }
+        match self.l1_backend {
+            Some(ref l1) => l1.info().await,
+            None => Ok(CacheBackendInfo::default()),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -1009,10 +1135,13 @@ mod tests {
                 prompt_tokens: 10,
                 completion_tokens: 20,
                 total_tokens: 30,
+                prompt_tokens_cached: None,
+                completion_tokens_reasoning: None,
             },
             created: 1234567890,
             provider: Some("test".to_string()),
             system_fingerprint: None,
+            service_tier: None,
         }
     }
 
@@ -1249,6 +1378,67 @@ mod tests {
         assert!(backend.get("key3").await.expect("get should succeed").is_some());
     }
 
+    #[tokio::test]
+    async fn test_memory_backend_info() {
+        let backend = MemoryCacheBackend::new(100, Duration::from_secs(3600));
+
+        backend
+            .set("key1", b"value1".to_vec(), Duration::from_secs(60))
+            .await
+            .expect("set should succeed");
+        backend
+            .set("key2", b"value22".to_vec(), Duration::from_secs(60))
+            .await
+            .expect("set should succeed");
+
+        let info = backend.info().await.expect("info should succeed");
+        assert_eq!(info.entries, Some(2));
+        assert_eq!(info.memory_bytes, Some(4 + 6 + 4 + 7));
+        assert_eq!(info.evicted_keys, None);
+    }
+
+    #[tokio::test]
+    async fn test_disconnected_redis_info_unavailable() {
+        let backend = RedisCacheBackend::new("redis://localhost:6379", "test", Duration::from_secs(1))
+            .await
+            .expect("backend should be created");
+
+        assert!(matches!(
+            backend.info().await,
+            Err(DistributedCacheError::Unavailable(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_redis_info() {
+        let info = "# Memory\r\nused_memory:1048576\r\nused_memory_human:1.00M\r\n\
+                    # Stats\r\nevicted_keys:42\r\n\
+                    # Keyspace\r\ndb0:keys=10,expires=10,avg_ttl=0\r\ndb1:keys=5,expires=0,avg_ttl=0\r\n";
+
+        let parsed = CacheBackendInfo::from_redis_info(info);
+        assert_eq!(parsed.memory_bytes, Some(1_048_576));
+        assert_eq!(parsed.evicted_keys, Some(42));
+        assert_eq!(parsed.entries, Some(15));
+    }
+
+    #[tokio::test]
+    async fn test_skipped_lookups_counted() {
+        let cache = DistributedCache::with_defaults();
+
+        let request = GatewayRequest::builder()
+            .model("gpt-4o")
+            .message(ChatMessage::user("Hello"))
+            .temperature(2.0)
+            .build()
+            .expect("valid request");
+
+        assert!(cache.get(&request).await.is_none());
+
+        let stats = cache.stats().await;
+        assert_eq!(stats.skipped, 1);
+        assert_eq!(stats.l1_misses, 0);
+    }
+
     #[tokio::test]
     async fn test_delete_pattern() {
         let backend = MemoryCacheBackend::new(100, Duration::from_secs(3600));
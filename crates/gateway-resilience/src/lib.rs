@@ -25,10 +25,13 @@ pub use circuit_breaker::{CircuitBreaker, CircuitBreakerConfig, CircuitState};
 pub use retry::{RetryPolicy, RetryConfig, RetryResult};
 pub use bulkhead::{Bulkhead, BulkheadConfig, BulkheadPermit};
 pub use timeout::{TimeoutManager, TimeoutConfig};
-pub use rate_limiter::{RateLimiter, RateLimiterConfig, RateLimitType, RateLimitExceeded, BucketStats};
-pub use cache::{ResponseCache, CacheConfig, CacheKey, CacheStats, CacheLookupResult};
+pub use rate_limiter::{
+    BucketStats, ProactiveBackoff, ProactiveBackoffConfig, RateLimitExceeded, RateLimitSnapshot,
+    RateLimitType, RateLimiter, RateLimiterConfig,
+};
+pub use cache::{ResponseCache, CacheConfig, CacheKey, CacheKeyNormalization, CacheStats, CacheLookupResult};
 pub use distributed_cache::{
-    CacheBackend, CacheResult, CachedEntry, DistributedCache, DistributedCacheConfig,
+    CacheBackend, CacheBackendInfo, CacheResult, CachedEntry, DistributedCache, DistributedCacheConfig,
     DistributedCacheConfigBuilder, DistributedCacheError, DistributedCacheKey,
     DistributedCacheStats, MemoryCacheBackend, RedisCacheBackend,
 };
@@ -2,8 +2,13 @@
 //!
 //! Provides rate limiting for requests per minute (RPM) and tokens per minute (TPM).
 //! Supports multiple keys for per-tenant, per-IP, or per-API-key rate limiting.
+//!
+//! [`ProactiveBackoff`] covers the upstream side: it watches the quota
+//! providers report in their responses and signals a delay before the
+//! provider starts answering with 429s.
 
-use gateway_core::GatewayError;
+use gateway_core::tokenizer::{CharHeuristicEstimator, SharedTokenEstimator};
+use gateway_core::{GatewayError, GatewayRequest, ProviderRateLimits};
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -106,12 +111,16 @@ impl TokenBucket {
         self.last_refill = now;
     }
 
-    /// Try to consume tokens for a request
-    fn try_consume(&mut self, token_count: Option<u32>) -> Result<(), RateLimitExceeded> {
+    /// Try to consume `requests` request tokens and `token_count` tokens
+    fn try_consume(
+        &mut self,
+        requests: f64,
+        token_count: Option<u32>,
+    ) -> Result<(), RateLimitExceeded> {
         self.refill();
 
         // Check request tokens
-        if self.request_tokens < 1.0 {
+        if self.request_tokens < requests {
             return Err(RateLimitExceeded {
                 limit_type: RateLimitType::Requests,
                 limit: self.config.requests_per_window,
@@ -134,7 +143,7 @@ impl TokenBucket {
         }
 
         // Consume tokens
-        self.request_tokens -= 1.0;
+        self.request_tokens -= requests;
         if let (Some(tokens), Some(count)) = (&mut self.token_tokens, token_count) {
             *tokens -= f64::from(count);
         }
@@ -167,6 +176,36 @@ impl TokenBucket {
         Duration::from_secs_f64(secs.min(window_secs))
     }
 
+    /// Capture the current state as of now without consuming anything
+    fn snapshot(&self) -> RateLimitSnapshot {
+        let mut bucket = self.clone();
+        bucket.refill();
+
+        let window_secs = bucket.config.window.as_secs_f64();
+        let burst_mult = if bucket.config.enable_burst {
+            f64::from(bucket.config.burst_multiplier)
+        } else {
+            1.0
+        };
+        let capacity = f64::from(bucket.config.requests_per_window) * burst_mult;
+        let reset_after = if bucket.config.requests_per_window == 0 || window_secs <= 0.0 {
+            Duration::ZERO
+        } else {
+            let missing = (capacity - bucket.request_tokens).max(0.0);
+            let refill_rate = f64::from(bucket.config.requests_per_window) / window_secs;
+            Duration::from_secs_f64(missing / refill_rate)
+        };
+
+        RateLimitSnapshot {
+            limit: bucket.config.requests_per_window,
+            remaining: bucket.request_tokens.max(0.0) as u32,
+            reset_after,
+            window: bucket.config.window,
+            token_limit: bucket.config.tokens_per_window,
+            tokens_remaining: bucket.token_tokens.map(|t| t.max(0.0) as u32),
+        }
+    }
+
     /// Get current statistics
     fn stats(&self) -> BucketStats {
         BucketStats {
@@ -213,6 +252,23 @@ pub struct BucketStats {
     pub tokens_per_window: Option<u32>,
 }
 
+/// Point-in-time view of a key's bucket, as exposed to clients
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitSnapshot {
+    /// Requests allowed per window
+    pub limit: u32,
+    /// Whole requests currently available
+    pub remaining: u32,
+    /// Time until the request bucket is full again
+    pub reset_after: Duration,
+    /// Window duration
+    pub window: Duration,
+    /// Tokens allowed per window (if configured)
+    pub token_limit: Option<u32>,
+    /// Tokens currently available (if configured)
+    pub tokens_remaining: Option<u32>,
+}
+
 impl BucketStats {
     /// Calculate request utilization percentage
     #[must_use]
@@ -248,6 +304,8 @@ pub struct RateLimiter {
     buckets: Arc<RwLock<HashMap<String, TokenBucket>>>,
     /// Whether rate limiting is enabled
     enabled: bool,
+    /// Sizes requests for TPM limiting
+    estimator: SharedTokenEstimator,
 }
 
 impl RateLimiter {
@@ -259,6 +317,7 @@ impl RateLimiter {
             default_config: config,
             buckets: Arc::new(RwLock::new(HashMap::new())),
             enabled: true,
+            estimator: Arc::new(CharHeuristicEstimator::default()),
         }
     }
 
@@ -276,9 +335,19 @@ impl RateLimiter {
             default_config: RateLimiterConfig::default(),
             buckets: Arc::new(RwLock::new(HashMap::new())),
             enabled: false,
+            estimator: Arc::new(CharHeuristicEstimator::default()),
         }
     }
 
+    /// Size requests with `estimator` for TPM limiting
+    ///
+    /// Share the estimator the router uses so both agree on request sizes.
+    #[must_use]
+    pub fn with_estimator(mut self, estimator: SharedTokenEstimator) -> Self {
+        self.estimator = estimator;
+        self
+    }
+
     /// Get the rate limiter ID
     #[must_use]
     pub fn id(&self) -> &str {
@@ -300,6 +369,37 @@ impl RateLimiter {
     /// # Errors
     /// Returns error if rate limit is exceeded
     pub async fn check(&self, key: &str, token_count: Option<u32>) -> Result<(), GatewayError> {
+        self.consume(key, 1.0, token_count).await
+    }
+
+    /// Charge a request's estimated tokens against the key's TPM limit
+    ///
+    /// The request itself is not counted again, so this pairs with a
+    /// [`check`](Self::check) made before the body was parsed. The estimate
+    /// covers the prompt and the requested completion limit. Returns the
+    /// tokens charged, which is zero when rate limiting is disabled.
+    ///
+    /// # Errors
+    /// Returns error if the token limit is exceeded
+    pub async fn check_request(
+        &self,
+        key: &str,
+        request: &GatewayRequest,
+    ) -> Result<u32, GatewayError> {
+        if !self.enabled {
+            return Ok(0);
+        }
+        let tokens = self.estimator.estimate_request(request);
+        self.consume(key, 0.0, Some(tokens)).await?;
+        Ok(tokens)
+    }
+
+    async fn consume(
+        &self,
+        key: &str,
+        requests: f64,
+        token_count: Option<u32>,
+    ) -> Result<(), GatewayError> {
         if !self.enabled {
             return Ok(());
         }
@@ -310,7 +410,7 @@ impl RateLimiter {
             .entry(key.to_string())
             .or_insert_with(|| TokenBucket::new(self.default_config.clone()));
 
-        match bucket.try_consume(token_count) {
+        match bucket.try_consume(requests, token_count) {
             Ok(()) => {
                 debug!(
                     rate_limiter = %self.id,
@@ -351,6 +451,18 @@ impl RateLimiter {
         buckets.get(key).map(|b| b.stats())
     }
 
+    /// Inspect the bucket for a key without consuming a token
+    ///
+    /// Keys that have not been seen yet report a full bucket; no bucket is
+    /// created as a side effect.
+    pub async fn peek(&self, key: &str) -> RateLimitSnapshot {
+        let buckets = self.buckets.read().await;
+        match buckets.get(key) {
+            Some(bucket) => bucket.snapshot(),
+            None => TokenBucket::new(self.default_config.clone()).snapshot(),
+        }
+    }
+
     /// Get all keys with their statistics
     pub async fn all_stats(&self) -> HashMap<String, BucketStats> {
         let buckets = self.buckets.read().await;
@@ -389,6 +501,117 @@ impl RateLimiter {
     }
 }
 
+/// Proactive backoff configuration
+#[derive(Debug, Clone)]
+pub struct ProactiveBackoffConfig {
+    /// Remaining quota fraction at or below which backoff starts
+    pub threshold: f64,
+    /// Upper bound on a single backoff delay
+    pub max_delay: Duration,
+}
+
+impl Default for ProactiveBackoffConfig {
+    fn default() -> Self {
+        Self {
+            threshold: 0.05,
+            max_delay: Duration::from_secs(5),
+        }
+    }
+}
+
+/// Backoff signal derived from provider-reported rate limits
+///
+/// Each observation of a provider's rate limit headers either arms a
+/// backoff for that key (when the remaining quota is low) or clears it.
+/// Callers consult [`Self::backoff`] before dispatching and delay by the
+/// returned amount.
+pub struct ProactiveBackoff {
+    /// Configuration
+    config: ProactiveBackoffConfig,
+    /// Backoff deadlines per key (e.g. `provider/model`)
+    until: parking_lot::Mutex<HashMap<String, Instant>>,
+    /// Whether observations arm backoffs
+    enabled: bool,
+}
+
+impl ProactiveBackoff {
+    /// Create a new proactive backoff signal
+    #[must_use]
+    pub fn new(config: ProactiveBackoffConfig) -> Self {
+        Self {
+            config,
+            until: parking_lot::Mutex::new(HashMap::new()),
+            enabled: true,
+        }
+    }
+
+    /// Create a disabled signal (never requests a delay)
+    #[must_use]
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            ..Self::new(ProactiveBackoffConfig::default())
+        }
+    }
+
+    /// Check if proactive backoff is enabled
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Record rate limits reported for `key`
+    ///
+    /// Returns the backoff armed by this observation, if the remaining
+    /// quota is at or below the threshold. The delay lasts until the
+    /// depleted quota resets, capped at `max_delay`.
+    pub fn observe(&self, key: &str, limits: &ProviderRateLimits) -> Option<Duration> {
+        if !self.enabled {
+            return None;
+        }
+
+        let low = limits
+            .min_remaining_fraction()
+            .is_some_and(|fraction| fraction <= self.config.threshold);
+
+        let mut until = self.until.lock();
+        if !low {
+            until.remove(key);
+            return None;
+        }
+
+        let delay = limits
+            .reset_after()
+            .map_or(self.config.max_delay, |reset| reset.min(self.config.max_delay));
+        if delay.is_zero() {
+            until.remove(key);
+            return None;
+        }
+
+        debug!(
+            key = %key,
+            delay_ms = delay.as_millis(),
+            "Provider quota low, backing off"
+        );
+        until.insert(key.to_string(), Instant::now() + delay);
+        Some(delay)
+    }
+
+    /// Remaining backoff for `key`, if one is active
+    #[must_use]
+    pub fn backoff(&self, key: &str) -> Option<Duration> {
+        let mut until = self.until.lock();
+        let deadline = *until.get(key)?;
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            until.remove(key);
+            None
+        } else {
+            Some(remaining)
+        }
+    }
+}
+
 /// Rate limiter middleware result
 #[derive(Debug, Clone)]
 pub struct RateLimitResult {
@@ -566,6 +789,40 @@ mod tests {
         assert!(limiter.check(key, Some(200)).await.is_err());
     }
 
+    #[tokio::test]
+    async fn test_check_request_charges_estimated_tokens() {
+        let limiter = RateLimiter::new(
+            "test",
+            RateLimiterConfig {
+                requests_per_window: 10,
+                tokens_per_window: Some(1000),
+                window: Duration::from_secs(60),
+                enable_burst: false,
+                burst_multiplier: 1.0,
+            },
+        )
+        .with_estimator(Arc::new(CharHeuristicEstimator::new(1.0)));
+
+        let request = GatewayRequest::builder()
+            .model("gpt-4")
+            .message(gateway_core::ChatMessage::user("x".repeat(100)))
+            .max_tokens(300)
+            .build()
+            .unwrap();
+
+        // Prompt (100 chars + role + framing) plus the completion limit
+        let charged = limiter.check_request("key", &request).await.unwrap();
+        assert_eq!(charged, 100 + 4 + 3 + 3 + 300);
+
+        // Only tokens are charged; the request was counted by `check`
+        let snapshot = limiter.peek("key").await;
+        assert_eq!(snapshot.remaining, 10);
+        assert_eq!(snapshot.tokens_remaining, Some(1000 - charged));
+
+        assert!(limiter.check_request("key", &request).await.is_ok());
+        assert!(limiter.check_request("key", &request).await.is_err());
+    }
+
     #[tokio::test]
     async fn test_rate_limiter_stats() {
         let limiter = RateLimiter::new(
@@ -656,6 +913,101 @@ mod tests {
         assert!(limiter.acquire("regular").await.is_err());
     }
 
+    #[tokio::test]
+    async fn test_peek_does_not_consume() {
+        let limiter = RateLimiter::new(
+            "test",
+            RateLimiterConfig {
+                requests_per_window: 5,
+                tokens_per_window: Some(1000),
+                window: Duration::from_secs(60),
+                enable_burst: false,
+                burst_multiplier: 1.0,
+            },
+        );
+
+        let key = "test-key";
+
+        // Unknown key reports a full bucket and is not created
+        let snapshot = limiter.peek(key).await;
+        assert_eq!(snapshot.limit, 5);
+        assert_eq!(snapshot.remaining, 5);
+        assert_eq!(snapshot.tokens_remaining, Some(1000));
+        assert_eq!(snapshot.reset_after, Duration::ZERO);
+        assert_eq!(limiter.key_count().await, 0);
+
+        limiter.check(key, Some(200)).await.ok();
+
+        for _ in 0..10 {
+            let snapshot = limiter.peek(key).await;
+            assert_eq!(snapshot.remaining, 4);
+            assert_eq!(snapshot.tokens_remaining, Some(800));
+            assert!(snapshot.reset_after > Duration::ZERO);
+        }
+
+        // Peeking left the remaining four requests intact
+        for i in 0..4 {
+            assert!(limiter.acquire(key).await.is_ok(), "Request {i} should be allowed");
+        }
+        assert!(limiter.acquire(key).await.is_err());
+    }
+
+    fn provider_limits(remaining: u64, limit: u64, reset: Duration) -> ProviderRateLimits {
+        ProviderRateLimits {
+            requests: gateway_core::RateLimitWindow {
+                limit: Some(limit),
+                remaining: Some(remaining),
+                reset: Some(reset),
+            },
+            tokens: gateway_core::RateLimitWindow::default(),
+        }
+    }
+
+    #[test]
+    fn test_proactive_backoff_on_low_remaining() {
+        let backoff = ProactiveBackoff::new(ProactiveBackoffConfig::default());
+
+        let delay = backoff.observe("openai/gpt-4", &provider_limits(2, 100, Duration::from_secs(2)));
+        assert_eq!(delay, Some(Duration::from_secs(2)));
+
+        let remaining = backoff.backoff("openai/gpt-4").unwrap();
+        assert!(remaining > Duration::from_millis(1900) && remaining <= Duration::from_secs(2));
+        assert!(backoff.backoff("openai/gpt-3.5-turbo").is_none());
+    }
+
+    #[test]
+    fn test_proactive_backoff_cleared_when_quota_recovers() {
+        let backoff = ProactiveBackoff::new(ProactiveBackoffConfig::default());
+
+        assert!(backoff
+            .observe("anthropic/claude", &provider_limits(1, 50, Duration::from_secs(1)))
+            .is_some());
+        assert!(backoff
+            .observe("anthropic/claude", &provider_limits(40, 50, Duration::from_secs(1)))
+            .is_none());
+        assert!(backoff.backoff("anthropic/claude").is_none());
+    }
+
+    #[test]
+    fn test_proactive_backoff_capped_at_max_delay() {
+        let backoff = ProactiveBackoff::new(ProactiveBackoffConfig {
+            threshold: 0.1,
+            max_delay: Duration::from_millis(500),
+        });
+
+        let delay = backoff.observe("openai/gpt-4", &provider_limits(0, 100, Duration::from_secs(60)));
+        assert_eq!(delay, Some(Duration::from_millis(500)));
+    }
+
+    #[test]
+    fn test_proactive_backoff_disabled() {
+        let backoff = ProactiveBackoff::disabled();
+        assert!(backoff
+            .observe("openai/gpt-4", &provider_limits(0, 100, Duration::from_secs(1)))
+            .is_none());
+        assert!(backoff.backoff("openai/gpt-4").is_none());
+    }
+
     #[test]
     fn test_bucket_stats_utilization() {
         let stats = BucketStats {
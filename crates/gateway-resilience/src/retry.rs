@@ -2,7 +2,7 @@
 //!
 //! Provides configurable retry logic with jitter for retryable errors.
 
-use gateway_core::GatewayError;
+use gateway_core::{GatewayError, ProviderErrorKind, RequestContext};
 use rand::Rng;
 use std::future::Future;
 use std::time::Duration;
@@ -23,6 +23,12 @@ pub struct RetryConfig {
     pub jitter: f64,
     /// HTTP status codes to retry on
     pub retry_on_status: Vec<u16>,
+    /// Base delay after a provider reports itself overloaded (503, or 529
+    /// from Anthropic), grown by `multiplier` like `base_delay`
+    pub overloaded_base_delay: Duration,
+    /// Whether to retry a provider that reports itself overloaded; callers
+    /// that fail over to another provider instead turn this off
+    pub retry_overloaded: bool,
 }
 
 impl Default for RetryConfig {
@@ -34,6 +40,8 @@ impl Default for RetryConfig {
             multiplier: 2.0,
             jitter: 0.25,
             retry_on_status: vec![429, 500, 502, 503, 504],
+            overloaded_base_delay: Duration::from_secs(1),
+            retry_overloaded: true,
         }
     }
 }
@@ -66,10 +74,39 @@ impl RetryPolicy {
         })
     }
 
+    /// A copy of this policy that leaves overloaded providers alone
+    ///
+    /// For callers that hand an overloaded request to another provider
+    /// rather than retrying the same one.
+    #[must_use]
+    pub fn without_overloaded_retries(&self) -> Self {
+        let mut policy = self.clone();
+        policy.config.retry_overloaded = false;
+        policy
+    }
+
     /// Calculate delay for a given attempt (0-indexed)
     #[must_use]
     pub fn delay_for_attempt(&self, attempt: u32) -> Duration {
-        let base = self.config.base_delay.as_millis() as f64;
+        self.backoff(self.config.base_delay, attempt)
+    }
+
+    /// Calculate delay for retrying after `error` on a given attempt
+    ///
+    /// An overloaded provider backs off from `overloaded_base_delay`
+    /// instead of `base_delay`, giving it longer to recover.
+    #[must_use]
+    pub fn delay_for_error(&self, attempt: u32, error: &GatewayError) -> Duration {
+        if error.provider_error_kind() == ProviderErrorKind::Overloaded {
+            self.backoff(self.config.overloaded_base_delay, attempt)
+        } else {
+            self.delay_for_attempt(attempt)
+        }
+    }
+
+    /// Exponential backoff from `base` with jitter, capped at `max_delay`
+    fn backoff(&self, base: Duration, attempt: u32) -> Duration {
+        let base = base.as_millis() as f64;
         let delay = base * self.config.multiplier.powi(attempt as i32);
         let delay = delay.min(self.config.max_delay.as_millis() as f64);
 
@@ -84,6 +121,11 @@ impl RetryPolicy {
     /// Check if an error is retryable
     #[must_use]
     pub fn is_retryable(&self, error: &GatewayError) -> bool {
+        if !self.config.retry_overloaded
+            && error.provider_error_kind() == ProviderErrorKind::Overloaded
+        {
+            return false;
+        }
         match error {
             GatewayError::Provider {
                 retryable,
@@ -110,6 +152,27 @@ impl RetryPolicy {
     /// # Errors
     /// Returns the last error if all retries are exhausted
     pub async fn execute<F, Fut, T>(&self, operation: F) -> Result<T, GatewayError>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, GatewayError>>,
+    {
+        self.execute_with_context(&RequestContext::new(), operation)
+            .await
+    }
+
+    /// Execute an operation with retry logic, bounded by a request context
+    ///
+    /// Each attempt is cancelled when the context's deadline passes, and no
+    /// retry is started if the backoff delay would not leave any time for it.
+    ///
+    /// # Errors
+    /// Returns the last error if all retries are exhausted, or
+    /// `GatewayError::Timeout` if the deadline is hit
+    pub async fn execute_with_context<F, Fut, T>(
+        &self,
+        ctx: &RequestContext,
+        operation: F,
+    ) -> Result<T, GatewayError>
     where
         F: Fn() -> Fut,
         Fut: Future<Output = Result<T, GatewayError>>,
@@ -117,7 +180,7 @@ impl RetryPolicy {
         let mut last_error: Option<GatewayError> = None;
 
         for attempt in 0..=self.config.max_retries {
-            match operation().await {
+            match ctx.run(operation()).await {
                 Ok(result) => {
                     if attempt > 0 {
                         debug!(attempt = attempt, "Retry succeeded");
@@ -125,11 +188,22 @@ impl RetryPolicy {
                     return Ok(result);
                 }
                 Err(error) => {
-                    if !self.is_retryable(&error) || attempt == self.config.max_retries {
+                    if ctx.is_done()
+                        || !self.is_retryable(&error)
+                        || attempt == self.config.max_retries
+                    {
                         return Err(error);
                     }
 
-                    let delay = self.delay_for_attempt(attempt);
+                    let delay = self.delay_for_error(attempt, &error);
+                    if ctx.remaining().is_some_and(|remaining| remaining <= delay) {
+                        debug!(
+                            attempt = attempt + 1,
+                            delay_ms = delay.as_millis(),
+                            "Deadline leaves no time to retry"
+                        );
+                        return Err(error);
+                    }
                     warn!(
                         attempt = attempt + 1,
                         max_retries = self.config.max_retries,
@@ -265,6 +339,20 @@ impl RetryPolicyBuilder {
         self
     }
 
+    /// Set base delay after an overloaded response
+    #[must_use]
+    pub fn overloaded_base_delay(mut self, delay: Duration) -> Self {
+        self.config.overloaded_base_delay = delay;
+        self
+    }
+
+    /// Set whether overloaded providers are retried
+    #[must_use]
+    pub fn retry_overloaded(mut self, enabled: bool) -> Self {
+        self.config.retry_overloaded = enabled;
+        self
+    }
+
     /// Build the policy
     #[must_use]
     pub fn build(self) -> RetryPolicy {
@@ -363,7 +451,7 @@ mod tests {
                 async move {
                     let attempt = c.fetch_add(1, Ordering::Relaxed);
                     if attempt < 2 {
-                        Err(GatewayError::provider("test", "error", Some(503), true))
+                        Err(GatewayError::provider("test", "error", Some(502), true))
                     } else {
                         Ok(42)
                     }
@@ -392,7 +480,7 @@ mod tests {
                 let c = Arc::clone(&counter_clone);
                 async move {
                     c.fetch_add(1, Ordering::Relaxed);
-                    Err(GatewayError::provider("test", "error", Some(503), true))
+                    Err(GatewayError::provider("test", "error", Some(502), true))
                 }
             })
             .await;
@@ -421,6 +509,50 @@ mod tests {
         assert_eq!(counter.load(Ordering::Relaxed), 1); // No retries
     }
 
+    #[tokio::test(start_paused = true)]
+    async fn test_retries_stop_at_deadline() {
+        let policy = RetryPolicyBuilder::new()
+            .max_retries(10)
+            .base_delay(Duration::from_millis(100))
+            .multiplier(1.0)
+            .jitter(0.0)
+            .build();
+        let ctx = RequestContext::with_timeout(Duration::from_millis(250));
+        let counter = Arc::new(AtomicU32::new(0));
+        let counter_clone = Arc::clone(&counter);
+        let start = tokio::time::Instant::now();
+
+        let result: Result<u32, GatewayError> = policy
+            .execute_with_context(&ctx, || {
+                let c = Arc::clone(&counter_clone);
+                async move {
+                    c.fetch_add(1, Ordering::Relaxed);
+                    Err(GatewayError::provider("test", "bad gateway", Some(502), true))
+                }
+            })
+            .await;
+
+        assert!(result.is_err());
+        // Attempts at 0ms, 100ms and 200ms; a fourth would start past the deadline
+        assert_eq!(counter.load(Ordering::Relaxed), 3);
+        assert!(start.elapsed() < Duration::from_millis(250));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_deadline_cancels_in_flight_attempt() {
+        let policy = RetryPolicy::with_max_retries(3);
+        let ctx = RequestContext::with_timeout(Duration::from_millis(50));
+
+        let result: Result<u32, GatewayError> = policy
+            .execute_with_context(&ctx, || async {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                Ok(1)
+            })
+            .await;
+
+        assert!(matches!(result, Err(GatewayError::Timeout { .. })));
+    }
+
     #[test]
     fn test_builder() {
         let policy = RetryPolicyBuilder::new()
@@ -437,4 +569,64 @@ mod tests {
         assert!((policy.config().multiplier - 3.0).abs() < 0.001);
         assert!((policy.config().jitter - 0.5).abs() < 0.001);
     }
+
+    #[test]
+    fn test_overloaded_backs_off_longer() {
+        let policy = RetryPolicyBuilder::new()
+            .base_delay(Duration::from_millis(100))
+            .overloaded_base_delay(Duration::from_secs(1))
+            .jitter(0.0)
+            .build();
+        let overloaded = GatewayError::provider("anthropic", "Overloaded", Some(529), true);
+        let server_error = GatewayError::provider("anthropic", "boom", Some(500), true);
+
+        assert_eq!(policy.delay_for_error(0, &server_error), Duration::from_millis(100));
+        assert_eq!(policy.delay_for_error(0, &overloaded), Duration::from_secs(1));
+        assert_eq!(policy.delay_for_error(2, &overloaded), Duration::from_secs(4));
+        // Still capped at max_delay
+        assert_eq!(policy.delay_for_error(5, &overloaded), Duration::from_secs(10));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_overloaded_retry_waits_for_overloaded_delay() {
+        let policy = RetryPolicyBuilder::new()
+            .max_retries(1)
+            .base_delay(Duration::from_millis(10))
+            .overloaded_base_delay(Duration::from_secs(2))
+            .jitter(0.0)
+            .build();
+        let counter = AtomicU32::new(0);
+        let start = tokio::time::Instant::now();
+
+        let result: Result<u32, GatewayError> = policy
+            .execute(|| async {
+                if counter.fetch_add(1, Ordering::Relaxed) == 0 {
+                    Err(GatewayError::provider("anthropic", "Overloaded", Some(529), true))
+                } else {
+                    Ok(7)
+                }
+            })
+            .await;
+
+        assert_eq!(result.unwrap(), 7);
+        assert!(start.elapsed() >= Duration::from_secs(2));
+    }
+
+    #[tokio::test]
+    async fn test_without_overloaded_retries() {
+        let policy = RetryPolicy::with_max_retries(3).without_overloaded_retries();
+        assert!(!policy.config().retry_overloaded);
+        assert!(policy.is_retryable(&GatewayError::provider("test", "error", Some(500), true)));
+
+        let counter = AtomicU32::new(0);
+        let result: Result<u32, GatewayError> = policy
+            .execute(|| async {
+                counter.fetch_add(1, Ordering::Relaxed);
+                Err(GatewayError::provider("anthropic", "Overloaded", Some(529), true))
+            })
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(counter.load(Ordering::Relaxed), 1);
+    }
 }
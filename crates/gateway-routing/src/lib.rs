@@ -19,8 +19,10 @@ pub mod strategy;
 pub mod selector;
 
 // Re-export main types
-pub use router::{Router, RouterConfig, RouteDecision};
+pub use router::{ContextRoutingConfig, Router, RouterConfig, RouteDecision};
 pub use rules::{RoutingRule, RuleMatcher, RuleAction};
-pub use load_balancer::{LoadBalancer, LoadBalancerConfig};
-pub use strategy::{LoadBalancingStrategy, StrategyFactory};
+pub use load_balancer::{
+    ActiveRequestGuard, CooldownConfig, CooldownState, LoadBalancer, LoadBalancerConfig,
+};
+pub use strategy::{CompositeStrategy, CompositeWeights, LoadBalancingStrategy, StrategyFactory};
 pub use selector::{ProviderSelector, SelectionCriteria, ProviderCandidate};
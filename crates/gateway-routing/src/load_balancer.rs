@@ -4,8 +4,10 @@
 //! to make intelligent routing decisions.
 
 use crate::selector::{ProviderCandidate, ProviderSelector, SelectionCriteria};
-use crate::strategy::{LoadBalancingStrategy, ProviderStats, StrategyFactory};
-use gateway_core::{GatewayError, HealthStatus, LLMProvider};
+use crate::strategy::{
+    CompositeStrategy, CompositeWeights, LoadBalancingStrategy, ProviderStats, StrategyFactory,
+};
+use gateway_core::{GatewayError, HealthStatus, LLMProvider, ProviderErrorKind};
 use parking_lot::RwLock;
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -26,6 +28,10 @@ pub struct LoadBalancerConfig {
     pub sticky_sessions: bool,
     /// Sticky session TTL
     pub sticky_ttl: Duration,
+    /// Score weights used by the `composite` strategy
+    pub composite_weights: CompositeWeights,
+    /// Cooldown applied to providers that keep failing
+    pub cooldown: CooldownConfig,
 }
 
 impl Default for LoadBalancerConfig {
@@ -36,6 +42,8 @@ impl Default for LoadBalancerConfig {
             min_healthy: 1,
             sticky_sessions: false,
             sticky_ttl: Duration::from_secs(300),
+            composite_weights: CompositeWeights::default(),
+            cooldown: CooldownConfig::default(),
         }
     }
 }
@@ -61,6 +69,14 @@ impl LoadBalancerConfig {
         self
     }
 
+    /// Use the composite strategy with the given score weights
+    #[must_use]
+    pub fn with_composite_weights(mut self, weights: CompositeWeights) -> Self {
+        self.strategy = "composite".to_string();
+        self.composite_weights = weights;
+        self
+    }
+
     /// Enable sticky sessions
     #[must_use]
     pub fn with_sticky_sessions(mut self, enabled: bool, ttl: Duration) -> Self {
@@ -68,6 +84,96 @@ impl LoadBalancerConfig {
         self.sticky_ttl = ttl;
         self
     }
+
+    /// Put failing providers into exponential cooldown
+    #[must_use]
+    pub fn with_cooldown(mut self, cooldown: CooldownConfig) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+}
+
+/// Exponential cooldown for providers that keep failing
+///
+/// Each consecutive failure doubles a provider's cooldown, from `base` up
+/// to `max`, and each success steps it back down. While cooling down a
+/// provider is only routed to when every other candidate is cooling down
+/// too, and its weight is halved for every step of cooldown until
+/// successes bring it back. Unlike a circuit breaker, this never takes a
+/// provider out of rotation outright.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CooldownConfig {
+    /// Whether failures put providers into cooldown
+    pub enabled: bool,
+    /// Cooldown after the first failure
+    pub base: Duration,
+    /// Longest cooldown, however many failures
+    pub max: Duration,
+}
+
+impl Default for CooldownConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            base: Duration::from_secs(1),
+            max: Duration::from_secs(60),
+        }
+    }
+}
+
+impl CooldownConfig {
+    /// Enabled cooldown growing from `base` to at most `max`
+    #[must_use]
+    pub fn new(base: Duration, max: Duration) -> Self {
+        Self {
+            enabled: true,
+            base,
+            max,
+        }
+    }
+
+    /// Cooldown after `level` unoffset failures
+    fn duration(&self, level: u32) -> Duration {
+        match level {
+            0 => Duration::ZERO,
+            level => self
+                .base
+                .saturating_mul(1 << (level - 1).min(MAX_COOLDOWN_LEVEL))
+                .min(self.max),
+        }
+    }
+}
+
+/// Cooldown steps beyond which the weight and duration stop changing
+const MAX_COOLDOWN_LEVEL: u32 = 16;
+
+/// Cooldown steps taken by a provider that reports itself overloaded,
+/// where any other failure takes one
+const OVERLOADED_COOLDOWN_STEPS: u32 = 3;
+
+/// Current cooldown of a provider
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CooldownState {
+    /// Consecutive failures not yet offset by successes
+    pub level: u32,
+    /// Length of the current cooldown
+    pub duration: Duration,
+    /// Time left before the provider is routed to normally
+    pub remaining: Duration,
+}
+
+impl CooldownState {
+    /// Whether the provider is still cooling down
+    #[must_use]
+    pub fn is_cooling_down(&self) -> bool {
+        !self.remaining.is_zero()
+    }
+
+    /// A routing weight reduced for this cooldown, never below 1
+    #[must_use]
+    pub fn apply_to_weight(&self, weight: u32) -> u32 {
+        (weight >> self.level.min(MAX_COOLDOWN_LEVEL)).max(1).min(weight)
+    }
 }
 
 /// Load balancer for provider selection
@@ -81,7 +187,35 @@ pub struct LoadBalancer {
     /// Sticky session mappings (tenant_id -> provider_id)
     sticky_map: RwLock<HashMap<String, StickyEntry>>,
     /// Active connections per provider
-    connections: dashmap::DashMap<String, AtomicU64>,
+    connections: dashmap::DashMap<String, Arc<AtomicU64>>,
+}
+
+/// Holds a provider's in-flight slot in the [`LoadBalancer`] until dropped
+///
+/// Connection-aware strategies count the request against the provider for
+/// as long as the guard lives, so a guard kept alongside a streamed response
+/// covers the whole stream rather than just its first byte.
+#[derive(Debug)]
+#[must_use = "the in-flight slot is released as soon as the guard is dropped"]
+pub struct ActiveRequestGuard {
+    provider_id: String,
+    count: Arc<AtomicU64>,
+}
+
+impl ActiveRequestGuard {
+    /// Provider whose slot this guard holds
+    #[must_use]
+    pub fn provider_id(&self) -> &str {
+        &self.provider_id
+    }
+}
+
+impl Drop for ActiveRequestGuard {
+    fn drop(&mut self) {
+        let _ = self
+            .count
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1));
+    }
 }
 
 /// Metrics tracked per provider
@@ -95,6 +229,12 @@ struct ProviderMetrics {
     failed_requests: u64,
     /// Total latency (for average calculation)
     total_latency_ms: u64,
+    /// Exponentially weighted moving average of successful latencies
+    recent_latency_ms: Option<f64>,
+    /// Consecutive failures not yet offset by successes
+    cooldown_level: u32,
+    /// When the provider last failed
+    last_failure: Option<Instant>,
     /// Last updated
     last_updated: Instant,
 }
@@ -106,6 +246,9 @@ impl Default for ProviderMetrics {
             successful_requests: 0,
             failed_requests: 0,
             total_latency_ms: 0,
+            recent_latency_ms: None,
+            cooldown_level: 0,
+            last_failure: None,
             last_updated: Instant::now(),
         }
     }
@@ -127,8 +270,24 @@ impl ProviderMetrics {
             self.total_latency_ms as f64 / self.successful_requests as f64
         }
     }
+
+    fn cooldown(&self, config: &CooldownConfig) -> Option<CooldownState> {
+        if !config.enabled || self.cooldown_level == 0 {
+            return None;
+        }
+        let duration = config.duration(self.cooldown_level);
+        let elapsed = self.last_failure.map_or(duration, |at| at.elapsed());
+        Some(CooldownState {
+            level: self.cooldown_level,
+            duration,
+            remaining: duration.saturating_sub(elapsed),
+        })
+    }
 }
 
+/// Weight of the newest sample in the recent latency average
+const RECENT_LATENCY_ALPHA: f64 = 0.3;
+
 /// Sticky session entry
 #[derive(Debug, Clone)]
 struct StickyEntry {
@@ -147,7 +306,11 @@ impl LoadBalancer {
     /// Create a new load balancer
     #[must_use]
     pub fn new(config: LoadBalancerConfig) -> Self {
-        let strategy = StrategyFactory::create(&config.strategy);
+        let strategy: Box<dyn LoadBalancingStrategy> = if config.strategy == "composite" {
+            Box::new(CompositeStrategy::new(config.composite_weights))
+        } else {
+            StrategyFactory::create(&config.strategy)
+        };
         info!(strategy = %config.strategy, "Load balancer initialized");
 
         Self {
@@ -180,12 +343,24 @@ impl LoadBalancer {
         criteria: &SelectionCriteria,
         tenant_id: Option<&str>,
     ) -> Result<Arc<dyn LLMProvider>, GatewayError> {
+        self.select_scored(candidates, criteria, tenant_id)
+            .map(|(provider, _)| provider)
+    }
+
+    /// Select a provider, also returning the per-provider scores behind the
+    /// choice (empty unless the strategy is score-based)
+    pub fn select_scored(
+        &self,
+        candidates: &[ProviderCandidate],
+        criteria: &SelectionCriteria,
+        tenant_id: Option<&str>,
+    ) -> Result<(Arc<dyn LLMProvider>, HashMap<String, f64>), GatewayError> {
         // Check sticky session first
         if self.config.sticky_sessions {
             if let Some(tenant) = tenant_id {
                 if let Some(provider) = self.get_sticky_provider(tenant, candidates) {
                     debug!(tenant = %tenant, provider = %provider.id(), "Using sticky session");
-                    return Ok(provider);
+                    return Ok((provider, HashMap::new()));
                 }
             }
         }
@@ -214,6 +389,15 @@ impl LoadBalancer {
             // Continue anyway with degraded providers
         }
 
+        let (filtered, cooldowns) = self.skip_cooling_down(filtered);
+        let filtered: Vec<ProviderCandidate> = filtered
+            .into_iter()
+            .map(|c| {
+                let active = self.active_requests(&c.id);
+                c.with_active_requests(active)
+            })
+            .collect();
+
         // Convert to provider stats for strategy
         let provider_stats: Vec<ProviderStats> = filtered
             .iter()
@@ -223,14 +407,15 @@ impl LoadBalancer {
 
                 ProviderStats {
                     id: c.id.clone(),
-                    weight: c.weight,
-                    active_connections: self
-                        .connections
+                    weight: cooldowns
                         .get(&c.id)
-                        .map_or(0, |c| c.load(Ordering::Relaxed)),
+                        .map_or(c.weight, |cooldown| cooldown.apply_to_weight(c.weight)),
+                    active_connections: c.active_requests,
                     avg_latency_ms: metrics.map_or(0.0, ProviderMetrics::avg_latency_ms),
                     success_rate: metrics.map_or(1.0, ProviderMetrics::success_rate),
                     is_healthy: c.health.should_route(),
+                    projected_cost: c.projected_cost,
+                    priority: c.priority,
                 }
             })
             .collect();
@@ -246,6 +431,18 @@ impl LoadBalancer {
         let selected = &filtered[selected_idx];
         let provider = Arc::clone(&selected.provider);
 
+        let scores: HashMap<String, f64> = self
+            .strategy
+            .scores(&provider_stats)
+            .map(|scores| {
+                provider_stats
+                    .iter()
+                    .zip(scores)
+                    .filter_map(|(p, score)| score.map(|s| (p.id.clone(), s)))
+                    .collect()
+            })
+            .unwrap_or_default();
+
         // Record sticky session
         if self.config.sticky_sessions {
             if let Some(tenant) = tenant_id {
@@ -254,10 +451,7 @@ impl LoadBalancer {
         }
 
         // Increment connection count
-        self.connections
-            .entry(selected.id.clone())
-            .or_insert_with(|| AtomicU64::new(0))
-            .fetch_add(1, Ordering::Relaxed);
+        self.counter(&selected.id).fetch_add(1, Ordering::Relaxed);
 
         debug!(
             provider = %selected.id,
@@ -265,7 +459,114 @@ impl LoadBalancer {
             "Provider selected"
         );
 
-        Ok(provider)
+        Ok((provider, scores))
+    }
+
+    /// Select a provider, returning a guard that holds its in-flight slot
+    ///
+    /// Record the outcome with [`Self::record_outcome`]; dropping the guard
+    /// releases the slot.
+    pub fn select_guarded(
+        &self,
+        candidates: &[ProviderCandidate],
+        criteria: &SelectionCriteria,
+        tenant_id: Option<&str>,
+    ) -> Result<(Arc<dyn LLMProvider>, ActiveRequestGuard), GatewayError> {
+        let provider = self.select(candidates, criteria, tenant_id)?;
+        let guard = self.in_flight_guard(provider.id());
+        Ok((provider, guard))
+    }
+
+    /// Take over the in-flight slot of a provider already selected
+    ///
+    /// The slot is released when the guard is dropped, in place of a call
+    /// to [`Self::release`] or [`Self::record_completion`].
+    pub fn in_flight_guard(&self, provider_id: &str) -> ActiveRequestGuard {
+        ActiveRequestGuard {
+            provider_id: provider_id.to_string(),
+            count: self.counter(provider_id),
+        }
+    }
+
+    /// Requests currently in flight on a provider
+    #[must_use]
+    pub fn active_requests(&self, provider_id: &str) -> u64 {
+        self.connections
+            .get(provider_id)
+            .map_or(0, |c| c.load(Ordering::Relaxed))
+    }
+
+    /// In-flight counter for a provider, created on first use
+    fn counter(&self, provider_id: &str) -> Arc<AtomicU64> {
+        if let Some(count) = self.connections.get(provider_id) {
+            return Arc::clone(&count);
+        }
+        Arc::clone(
+            &self
+                .connections
+                .entry(provider_id.to_string())
+                .or_insert_with(|| Arc::new(AtomicU64::new(0))),
+        )
+    }
+
+    /// Select the routable candidate with the lowest recent latency
+    ///
+    /// Candidates without a latency sample yet score 0 so they are tried
+    /// (and measured) first. Ties go to the higher-priority candidate. The
+    /// returned scores are each candidate's recent latency in milliseconds.
+    pub fn select_lowest_latency(
+        &self,
+        candidates: &[ProviderCandidate],
+        criteria: &SelectionCriteria,
+    ) -> Result<(Arc<dyn LLMProvider>, HashMap<String, f64>), GatewayError> {
+        let no_provider = || GatewayError::NoHealthyProviders {
+            model: criteria.model.clone().unwrap_or_default(),
+        };
+
+        let routable = ProviderSelector::filter(candidates, criteria)
+            .into_iter()
+            .filter(|c| c.health.should_route())
+            .collect();
+        let (routable, _) = self.skip_cooling_down(routable);
+
+        let scored: Vec<(ProviderCandidate, f64)> = {
+            let stats = self.stats.read();
+            routable
+                .into_iter()
+                .map(|c| {
+                    let latency = stats
+                        .get(&c.id)
+                        .and_then(|m| m.recent_latency_ms)
+                        .unwrap_or(0.0);
+                    (c, latency)
+                })
+                .collect()
+        };
+
+        let (selected, latency) = scored
+            .iter()
+            .min_by(|(a, a_latency), (b, b_latency)| {
+                a_latency
+                    .total_cmp(b_latency)
+                    .then(a.priority.cmp(&b.priority))
+                    .then_with(|| a.id.cmp(&b.id))
+            })
+            .ok_or_else(no_provider)?;
+
+        self.counter(&selected.id).fetch_add(1, Ordering::Relaxed);
+
+        debug!(
+            provider = %selected.id,
+            region = ?selected.region,
+            latency_ms = latency,
+            "Lowest-latency provider selected"
+        );
+
+        let scores = scored
+            .iter()
+            .map(|(c, latency)| (c.id.clone(), *latency))
+            .collect();
+        Ok((Arc::clone(&selected.provider), scores))
     }
 
     /// Record completion of a request
@@ -275,6 +576,48 @@ impl LoadBalancer {
         latency: Duration,
         success: bool,
     ) {
+        self.record_outcome(provider_id, latency, success);
+
+        // Decrement connection count
+        if let Some(count) = self.connections.get(provider_id) {
+            count.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Record completion of a request that failed with `error`
+    ///
+    /// Like `record_completion` with `success` false, except that an
+    /// overloaded provider (503, or 529 from Anthropic) is pushed further
+    /// into cooldown than other failures, so traffic moves elsewhere while
+    /// it recovers. Client errors (4xx) say nothing about the provider:
+    /// they only release the in-flight slot.
+    pub fn record_failure(&self, provider_id: &str, latency: Duration, error: &GatewayError) {
+        let steps = match error.provider_error_kind() {
+            ProviderErrorKind::InvalidRequest
+            | ProviderErrorKind::Authentication
+            | ProviderErrorKind::RateLimited
+            | ProviderErrorKind::ContentFilter => {
+                self.release(provider_id);
+                return;
+            }
+            ProviderErrorKind::Overloaded => OVERLOADED_COOLDOWN_STEPS,
+            _ => 1,
+        };
+        self.record(provider_id, latency, false, steps);
+
+        if let Some(count) = self.connections.get(provider_id) {
+            count.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Record a request's latency and success, leaving its in-flight slot
+    /// to an [`ActiveRequestGuard`]
+    pub fn record_outcome(&self, provider_id: &str, latency: Duration, success: bool) {
+        self.record(provider_id, latency, success, 1);
+    }
+
+    /// Update stats, taking `cooldown_steps` into cooldown on failure
+    fn record(&self, provider_id: &str, latency: Duration, success: bool, cooldown_steps: u32) {
         // Update internal stats
         {
             let mut stats = self.stats.write();
@@ -284,8 +627,16 @@ impl LoadBalancer {
             if success {
                 metrics.successful_requests += 1;
                 metrics.total_latency_ms += latency.as_millis() as u64;
+                let sample = latency.as_secs_f64() * 1000.0;
+                metrics.recent_latency_ms = Some(metrics.recent_latency_ms.map_or(sample, |avg| {
+                    RECENT_LATENCY_ALPHA.mul_add(sample - avg, avg)
+                }));
+                metrics.cooldown_level = metrics.cooldown_level.saturating_sub(1);
             } else {
                 metrics.failed_requests += 1;
+                metrics.cooldown_level =
+                    (metrics.cooldown_level + cooldown_steps).min(MAX_COOLDOWN_LEVEL);
+                metrics.last_failure = Some(Instant::now());
             }
             metrics.last_updated = Instant::now();
         }
@@ -294,13 +645,29 @@ impl LoadBalancer {
         if let Some(idx) = self.get_provider_index(provider_id) {
             self.strategy.record_completion(idx, latency, success);
         }
+    }
 
-        // Decrement connection count
+    /// Release a selection that was never dispatched or whose outcome is
+    /// not recorded, such as a stream handed to the client
+    ///
+    /// Only the in-flight count drops; latency and success stats are left
+    /// unchanged.
+    pub fn release(&self, provider_id: &str) {
         if let Some(count) = self.connections.get(provider_id) {
-            count.fetch_sub(1, Ordering::Relaxed);
+            let _ = count.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1));
         }
     }
 
+    /// Get a provider's cooldown, if cooldown is enabled and the provider
+    /// has failures not yet offset by successes
+    #[must_use]
+    pub fn cooldown(&self, provider_id: &str) -> Option<CooldownState> {
+        self.stats
+            .read()
+            .get(provider_id)
+            .and_then(|m| m.cooldown(&self.config.cooldown))
+    }
+
     /// Get provider statistics
     #[must_use]
     pub fn get_stats(&self, provider_id: &str) -> Option<LoadBalancerStats> {
@@ -311,10 +678,8 @@ impl LoadBalancer {
             failed_requests: m.failed_requests,
             success_rate: m.success_rate(),
             avg_latency_ms: m.avg_latency_ms(),
-            active_connections: self
-                .connections
-                .get(provider_id)
-                .map_or(0, |c| c.load(Ordering::Relaxed)),
+            active_connections: self.active_requests(provider_id),
+            cooldown: m.cooldown(&self.config.cooldown),
         })
     }
 
@@ -333,10 +698,8 @@ impl LoadBalancer {
                         failed_requests: m.failed_requests,
                         success_rate: m.success_rate(),
                         avg_latency_ms: m.avg_latency_ms(),
-                        active_connections: self
-                            .connections
-                            .get(id)
-                            .map_or(0, |c| c.load(Ordering::Relaxed)),
+                        active_connections: self.active_requests(id),
+                        cooldown: m.cooldown(&self.config.cooldown),
                     },
                 )
             })
@@ -383,6 +746,36 @@ impl LoadBalancer {
         );
     }
 
+    /// Drop candidates in cooldown, unless every candidate is, returning
+    /// the remaining candidates with their cooldowns
+    fn skip_cooling_down(
+        &self,
+        candidates: Vec<ProviderCandidate>,
+    ) -> (Vec<ProviderCandidate>, HashMap<String, CooldownState>) {
+        let cooldowns: HashMap<String, CooldownState> = {
+            let stats = self.stats.read();
+            candidates
+                .iter()
+                .filter_map(|c| {
+                    let cooldown = stats.get(&c.id)?.cooldown(&self.config.cooldown)?;
+                    Some((c.id.clone(), cooldown))
+                })
+                .collect()
+        };
+        let cooling = |c: &ProviderCandidate| {
+            cooldowns
+                .get(&c.id)
+                .is_some_and(CooldownState::is_cooling_down)
+        };
+
+        if candidates.iter().all(cooling) || !candidates.iter().any(cooling) {
+            return (candidates, cooldowns);
+        }
+        debug!(cooldowns = ?cooldowns, "Skipping providers in cooldown");
+        let candidates = candidates.into_iter().filter(|c| !cooling(c)).collect();
+        (candidates, cooldowns)
+    }
+
     fn get_provider_index(&self, provider_id: &str) -> Option<usize> {
         let stats = self.stats.read();
         stats.keys().position(|k| k == provider_id)
@@ -404,6 +797,8 @@ pub struct LoadBalancerStats {
     pub avg_latency_ms: f64,
     /// Current active connections
     pub active_connections: u64,
+    /// Current cooldown, if the provider is in one
+    pub cooldown: Option<CooldownState>,
 }
 
 #[cfg(test)]
@@ -464,6 +859,7 @@ mod tests {
                 max_context_length: None,
                 max_output_tokens: None,
                 parallel_tool_calls: false,
+                audio_output: false,
             };
             &CAPS
         }
@@ -559,6 +955,99 @@ mod tests {
         assert!((stats.success_rate - 0.666).abs() < 0.01);
     }
 
+    #[test]
+    fn test_weighted_least_connections_shifts_to_less_loaded() {
+        let config = LoadBalancerConfig::new().with_strategy("weighted_least_connections");
+        let lb = LoadBalancer::new(config);
+        let slow = Arc::new(MockProvider::new("slow"));
+        let fast = Arc::new(MockProvider::new("fast"));
+        let candidates = vec![
+            ProviderCandidate::new(slow)
+                .with_health(HealthStatus::Healthy)
+                .with_weight(200),
+            ProviderCandidate::new(fast)
+                .with_health(HealthStatus::Healthy)
+                .with_weight(100),
+        ];
+        let criteria = SelectionCriteria::new();
+
+        // One request per tick; "slow" answers after 10 ticks, "fast" after 1
+        let mut in_flight: Vec<(String, u32)> = Vec::new();
+        let mut counts: HashMap<String, u32> = HashMap::new();
+        for tick in 0..300 {
+            in_flight.retain(|(id, done_at)| {
+                let done = *done_at <= tick;
+                if done {
+                    lb.record_completion(id, Duration::from_millis(10), true);
+                }
+                !done
+            });
+
+            let provider = lb.select(&candidates, &criteria, None).unwrap();
+            let latency = if provider.id() == "slow" { 10 } else { 1 };
+            *counts.entry(provider.id().to_string()).or_default() += 1;
+            in_flight.push((provider.id().to_string(), tick + latency));
+        }
+
+        // Weighted round-robin would send "slow" two requests in three
+        assert!(counts["fast"] > counts["slow"] * 2, "{counts:?}");
+        assert!(lb.get_stats("slow").unwrap().active_connections <= 2);
+    }
+
+    #[test]
+    fn test_release_drops_in_flight_only() {
+        let lb = LoadBalancer::new(LoadBalancerConfig::new());
+        let candidates = create_candidates(1);
+        let criteria = SelectionCriteria::new();
+
+        let provider = lb.select(&candidates, &criteria, None).unwrap();
+        lb.record_completion(provider.id(), Duration::from_millis(100), true);
+        lb.select(&candidates, &criteria, None).unwrap();
+        lb.release(provider.id());
+        lb.release(provider.id());
+
+        let stats = lb.get_stats(provider.id()).unwrap();
+        assert_eq!(stats.active_connections, 0);
+        assert_eq!(stats.total_requests, 1);
+    }
+
+    #[test]
+    fn test_active_request_guard_steers_least_connections() {
+        let lb = LoadBalancer::new(
+            LoadBalancerConfig::new().with_strategy("weighted_least_connections"),
+        );
+        let mut candidates = create_candidates(3);
+        candidates[0] = candidates[0].clone().with_priority(10);
+        candidates[2] = candidates[2].clone().with_health(HealthStatus::Unhealthy);
+        let criteria = SelectionCriteria::new();
+
+        // A long-lived stream holds the preferred provider's slot
+        let (stream, stream_guard) = lb.select_guarded(&candidates, &criteria, None).unwrap();
+        assert_eq!(stream.id(), "provider-0");
+        assert_eq!(stream_guard.provider_id(), "provider-0");
+        assert_eq!(lb.active_requests("provider-0"), 1);
+
+        // Short requests go around it while it lasts, never to the unhealthy one
+        for _ in 0..3 {
+            let (provider, guard) = lb.select_guarded(&candidates, &criteria, None).unwrap();
+            assert_eq!(provider.id(), "provider-1");
+            lb.record_outcome(guard.provider_id(), Duration::from_millis(10), true);
+        }
+        assert_eq!(lb.active_requests("provider-1"), 0);
+        assert_eq!(lb.get_stats("provider-1").unwrap().total_requests, 3);
+
+        drop(stream_guard);
+        assert_eq!(lb.active_requests("provider-0"), 0);
+        let provider = lb.select(&candidates, &criteria, None).unwrap();
+        assert_eq!(provider.id(), "provider-0");
+
+        // A guard can also take over a slot from a plain selection
+        let guard = lb.in_flight_guard(provider.id());
+        assert_eq!(lb.active_requests("provider-0"), 1);
+        drop(guard);
+        assert_eq!(lb.active_requests("provider-0"), 0);
+    }
+
     #[test]
     fn test_no_available_provider() {
         let lb = LoadBalancer::new(LoadBalancerConfig::new());
@@ -587,4 +1076,121 @@ mod tests {
 
         assert_eq!(provider.id(), "healthy");
     }
+
+    #[test]
+    fn test_cooldown_grows_with_failures_and_decays_on_success() {
+        let cooldown = CooldownConfig::new(Duration::from_secs(1), Duration::from_secs(8));
+        let lb = LoadBalancer::new(LoadBalancerConfig::new().with_cooldown(cooldown));
+        let fail = || lb.record_completion("flaky", Duration::from_millis(10), false);
+        let succeed = || lb.record_completion("flaky", Duration::from_millis(10), true);
+
+        let durations: Vec<_> = (0..6)
+            .map(|_| {
+                fail();
+                lb.cooldown("flaky").unwrap().duration.as_secs()
+            })
+            .collect();
+        assert_eq!(durations, [1, 2, 4, 8, 8, 8]);
+        assert!(lb.cooldown("flaky").unwrap().is_cooling_down());
+
+        succeed();
+        let state = lb.cooldown("flaky").unwrap();
+        assert_eq!(state.level, 5);
+        assert_eq!(state.duration, Duration::from_secs(8));
+        for _ in 0..4 {
+            succeed();
+        }
+        let state = lb.cooldown("flaky").unwrap();
+        assert_eq!(state.duration, Duration::from_secs(1));
+        assert!(state.remaining <= Duration::from_secs(1));
+        assert_eq!(lb.get_stats("flaky").unwrap().cooldown.map(|c| c.level), Some(1));
+
+        succeed();
+        assert_eq!(lb.cooldown("flaky"), None);
+
+        // Disabled by default
+        let lb = LoadBalancer::new(LoadBalancerConfig::new());
+        lb.record_completion("flaky", Duration::from_millis(10), false);
+        assert_eq!(lb.cooldown("flaky"), None);
+    }
+
+    #[test]
+    fn test_overloaded_failure_cools_down_further() {
+        let cooldown = CooldownConfig::new(Duration::from_secs(1), Duration::from_secs(60));
+        let lb = LoadBalancer::new(LoadBalancerConfig::new().with_cooldown(cooldown));
+        let latency = Duration::from_millis(10);
+
+        let server_error = GatewayError::provider("provider-0", "boom", Some(500), true);
+        lb.record_failure("provider-0", latency, &server_error);
+        assert_eq!(lb.cooldown("provider-0").unwrap().duration, Duration::from_secs(1));
+
+        let overloaded = GatewayError::provider("provider-1", "Overloaded", Some(529), true);
+        lb.record_failure("provider-1", latency, &overloaded);
+        let state = lb.cooldown("provider-1").unwrap();
+        assert_eq!(state.level, OVERLOADED_COOLDOWN_STEPS);
+        assert_eq!(state.duration, Duration::from_secs(4));
+        assert_eq!(lb.get_stats("provider-1").unwrap().failed_requests, 1);
+
+        // With both cooling down, the overloaded provider weighs less
+        let weight = |id: &str| lb.cooldown(id).unwrap().apply_to_weight(100);
+        assert!(weight("provider-1") < weight("provider-0"));
+    }
+
+    #[test]
+    fn test_client_errors_do_not_cool_down() {
+        let cooldown = CooldownConfig::new(Duration::from_secs(1), Duration::from_secs(60));
+        let lb = LoadBalancer::new(LoadBalancerConfig::new().with_cooldown(cooldown));
+        let latency = Duration::from_millis(10);
+
+        for status in [400, 401, 429] {
+            let error = GatewayError::provider("provider-0", "rejected", Some(status), false);
+            lb.record_failure("provider-0", latency, &error);
+        }
+        assert_eq!(lb.cooldown("provider-0"), None);
+        assert!(lb.get_stats("provider-0").is_none());
+    }
+
+    #[test]
+    fn test_cooling_down_provider_is_skipped_unless_all_are() {
+        let cooldown = CooldownConfig::new(Duration::from_secs(60), Duration::from_secs(600));
+        let lb = LoadBalancer::new(LoadBalancerConfig::new().with_cooldown(cooldown));
+        let candidates = create_candidates(2);
+        let criteria = SelectionCriteria::new();
+
+        lb.record_completion("provider-0", Duration::from_millis(10), false);
+        for _ in 0..4 {
+            let provider = lb.select(&candidates, &criteria, None).unwrap();
+            assert_eq!(provider.id(), "provider-1");
+            lb.release(provider.id());
+        }
+
+        lb.record_completion("provider-1", Duration::from_millis(10), false);
+        lb.record_completion("provider-1", Duration::from_millis(10), false);
+        assert!(lb.select(&candidates, &criteria, None).is_ok());
+    }
+
+    #[test]
+    fn test_routing_weight_reflects_cooldown() {
+        // A zero cooldown never skips a provider, leaving only the weight
+        let cooldown = CooldownConfig::new(Duration::ZERO, Duration::ZERO);
+        let config = LoadBalancerConfig::new()
+            .with_strategy("weighted_round_robin")
+            .with_cooldown(cooldown);
+        let lb = LoadBalancer::new(config);
+        let candidates = create_candidates(2);
+        let criteria = SelectionCriteria::new();
+
+        lb.record_completion("provider-0", Duration::from_millis(10), false);
+        lb.record_completion("provider-0", Duration::from_millis(10), false);
+        assert_eq!(lb.cooldown("provider-0").unwrap().apply_to_weight(100), 25);
+
+        let mut counts: HashMap<String, u32> = HashMap::new();
+        for _ in 0..125 {
+            let provider = lb.select(&candidates, &criteria, None).unwrap();
+            *counts.entry(provider.id().to_string()).or_default() += 1;
+            lb.release(provider.id());
+        }
+        assert_eq!(counts["provider-0"], 25, "{counts:?}");
+        assert_eq!(counts["provider-1"], 100, "{counts:?}");
+    }
 }
@@ -3,9 +3,10 @@
 //! Combines rules engine, load balancer, and provider selection
 //! to make intelligent routing decisions.
 
-use crate::load_balancer::{LoadBalancer, LoadBalancerConfig};
+use crate::load_balancer::{ActiveRequestGuard, LoadBalancer, LoadBalancerConfig};
 use crate::rules::{MatchContext, RuleAction, RoutingRule, RulesEngine};
 use crate::selector::{ProviderCandidate, SelectionCriteria};
+use gateway_core::tokenizer::{CharHeuristicEstimator, SharedTokenEstimator};
 use gateway_core::{GatewayError, GatewayRequest, HealthStatus, LLMProvider};
 use parking_lot::RwLock;
 use std::collections::HashMap;
@@ -24,6 +25,14 @@ pub struct RouterConfig {
     pub rules_enabled: bool,
     /// Default strategy when no rule specifies one
     pub default_strategy: String,
+    /// Context-size routing
+    pub context_routing: ContextRoutingConfig,
+    /// Providers each tenant may be routed to; tenants not listed are
+    /// unrestricted
+    pub tenant_allowlists: HashMap<String, Vec<String>>,
+    /// Route to the region-tagged instance with the lowest recent latency
+    /// whenever the candidates include one
+    pub region_selection: bool,
 }
 
 impl Default for RouterConfig {
@@ -33,6 +42,9 @@ impl Default for RouterConfig {
             default_providers: Vec::new(),
             rules_enabled: true,
             default_strategy: "round_robin".to_string(),
+            context_routing: ContextRoutingConfig::default(),
+            tenant_allowlists: HashMap::new(),
+            region_selection: false,
         }
     }
 }
@@ -64,6 +76,101 @@ impl RouterConfig {
         self.rules_enabled = enabled;
         self
     }
+
+    /// Set context-size routing config
+    #[must_use]
+    pub fn with_context_routing(mut self, config: ContextRoutingConfig) -> Self {
+        self.context_routing = config;
+        self
+    }
+
+    /// Restrict a tenant to the given provider IDs
+    #[must_use]
+    pub fn with_tenant_allowlist(
+        mut self,
+        tenant_id: impl Into<String>,
+        providers: Vec<String>,
+    ) -> Self {
+        self.tenant_allowlists.insert(tenant_id.into(), providers);
+        self
+    }
+
+    /// Enable/disable lowest-latency region selection
+    #[must_use]
+    pub fn with_region_selection(mut self, enabled: bool) -> Self {
+        self.region_selection = enabled;
+        self
+    }
+}
+
+/// Routing by estimated request size against provider context windows
+///
+/// Off unless enabled. Providers that don't report `max_context_length`
+/// are never excluded.
+#[derive(Debug, Clone)]
+pub struct ContextRoutingConfig {
+    /// Exclude providers whose context window would overflow
+    pub enabled: bool,
+    /// Required margin over the estimated prompt plus `max_tokens`
+    /// (1.2 = 20% headroom)
+    pub headroom: f64,
+    /// Prompts of at least this many estimated tokens are routed only to
+    /// the largest-context providers available
+    pub large_prompt_threshold: Option<u32>,
+}
+
+impl Default for ContextRoutingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            headroom: 1.2,
+            large_prompt_threshold: None,
+        }
+    }
+}
+
+impl ContextRoutingConfig {
+    /// Create a new context routing configuration
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enable/disable context routing
+    #[must_use]
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Set the required headroom factor
+    #[must_use]
+    pub fn with_headroom(mut self, headroom: f64) -> Self {
+        self.headroom = headroom;
+        self
+    }
+
+    /// Prefer the largest-context providers for prompts of at least
+    /// `tokens` estimated tokens
+    #[must_use]
+    pub fn with_large_prompt_threshold(mut self, tokens: u32) -> Self {
+        self.large_prompt_threshold = Some(tokens);
+        self
+    }
+
+    /// Context length a provider needs to serve a request of
+    /// `prompt_tokens` estimated tokens comfortably
+    #[must_use]
+    pub fn required_context(&self, request: &GatewayRequest, prompt_tokens: u32) -> u32 {
+        let needed = u64::from(prompt_tokens)
+            + u64::from(request.token_limit().unwrap_or(0));
+        let required = (needed as f64 * self.headroom.max(1.0)).ceil();
+        if required >= f64::from(u32::MAX) {
+            u32::MAX
+        } else {
+            required as u32
+        }
+    }
 }
 
 /// Route decision made by the router
@@ -79,6 +186,18 @@ pub struct RouteDecision {
     pub matched_rules: Vec<String>,
     /// Strategy used
     pub strategy: String,
+    /// Per-provider scores from score-based strategies (lower is better)
+    pub scores: HashMap<String, f64>,
+    /// Region of the selected provider instance, if it is region-tagged
+    pub region: Option<String>,
+    /// Stream passthrough override from the first matching rule that sets one
+    pub stream_passthrough: Option<bool>,
+    /// Provider the caller pinned the request to, bypassing selection
+    pub provider_override: Option<String>,
+    /// Candidates that could not be selected: outside the tenant's
+    /// allowlist, too small a context window, unhealthy, or already
+    /// failed over from
+    pub excluded: Vec<String>,
 }
 
 /// Main router for making routing decisions
@@ -91,6 +210,8 @@ pub struct Router {
     load_balancer: LoadBalancer,
     /// Registered providers
     providers: RwLock<HashMap<String, ProviderEntry>>,
+    /// Sizes requests for context routing
+    estimator: SharedTokenEstimator,
 }
 
 /// Provider entry in the router
@@ -99,6 +220,7 @@ struct ProviderEntry {
     weight: u32,
     priority: u32,
     health: HealthStatus,
+    region: Option<String>,
 }
 
 impl Router {
@@ -112,28 +234,67 @@ impl Router {
             rules: RwLock::new(RulesEngine::new()),
             load_balancer,
             providers: RwLock::new(HashMap::new()),
+            estimator: Arc::new(CharHeuristicEstimator::default()),
         }
     }
 
+    /// Size requests with `estimator` for context routing
+    #[must_use]
+    pub fn with_estimator(mut self, estimator: SharedTokenEstimator) -> Self {
+        self.estimator = estimator;
+        self
+    }
+
+    /// The estimator used to size requests
+    #[must_use]
+    pub fn estimator(&self) -> &SharedTokenEstimator {
+        &self.estimator
+    }
+
     /// Register a provider
     pub fn register_provider(
         &self,
         provider: Arc<dyn LLMProvider>,
         weight: u32,
         priority: u32,
+    ) {
+        self.insert_provider(provider, None, weight, priority);
+    }
+
+    /// Register a provider instance serving from `region`
+    ///
+    /// Instances of the same provider in different regions are registered
+    /// separately, each with its own provider ID.
+    pub fn register_regional_provider(
+        &self,
+        provider: Arc<dyn LLMProvider>,
+        region: impl Into<String>,
+        weight: u32,
+        priority: u32,
+    ) {
+        self.insert_provider(provider, Some(region.into()), weight, priority);
+    }
+
+    fn insert_provider(
+        &self,
+        provider: Arc<dyn LLMProvider>,
+        region: Option<String>,
+        weight: u32,
+        priority: u32,
     ) {
         let id = provider.id().to_string();
         let mut providers = self.providers.write();
+        info!(provider_id = %id, region = ?region, weight, priority, "Provider registered with router");
         providers.insert(
-            id.clone(),
+            id,
             ProviderEntry {
                 provider,
                 weight,
                 priority,
                 health: HealthStatus::Unknown,
+                region,
             },
         );
-        info!(provider_id = %id, weight, priority, "Provider registered with router");
     }
 
     /// Deregister a provider
@@ -174,11 +335,80 @@ impl Router {
     }
 
     /// Route a request to a provider
-    #[instrument(skip(self, request), fields(model = %request.model))]
+    ///
+    /// Tenants with an entry in [`RouterConfig::tenant_allowlists`] are only
+    /// routed to the providers listed there.
     pub fn route(
         &self,
         request: &GatewayRequest,
         tenant_id: Option<&str>,
+    ) -> Result<(Arc<dyn LLMProvider>, RouteDecision), GatewayError> {
+        self.route_with_allowlist(request, tenant_id, None)
+    }
+
+    /// Route a request, considering only the `allowed` provider IDs
+    ///
+    /// `allowed` takes precedence over the configured allowlist for the
+    /// tenant; `None` falls back to it.
+    ///
+    /// # Errors
+    /// Returns `GatewayError::Authorization` if providers are available for
+    /// the request but none of them is allowed
+    pub fn route_with_allowlist(
+        &self,
+        request: &GatewayRequest,
+        tenant_id: Option<&str>,
+        allowed: Option<&[String]>,
+    ) -> Result<(Arc<dyn LLMProvider>, RouteDecision), GatewayError> {
+        self.route_excluding(request, tenant_id, allowed, &[])
+    }
+
+    /// Route a request, skipping the `excluded` provider IDs
+    ///
+    /// Used to fail over to another provider (or region) after an error.
+    ///
+    /// # Errors
+    /// Returns `GatewayError::Authorization` if providers are available for
+    /// the request but none of them is allowed
+    #[instrument(skip(self, request, allowed), fields(model = %request.model))]
+    pub fn route_excluding(
+        &self,
+        request: &GatewayRequest,
+        tenant_id: Option<&str>,
+        allowed: Option<&[String]>,
+        excluded: &[String],
+    ) -> Result<(Arc<dyn LLMProvider>, RouteDecision), GatewayError> {
+        self.route_inner(request, tenant_id, allowed, excluded, None)
+    }
+
+    /// Route a request to `provider_id`, which the caller asked for
+    ///
+    /// Rules still apply their model transforms and headers, but not their
+    /// provider targets. The provider must be allowed, healthy and able to
+    /// serve the request like any other candidate.
+    ///
+    /// # Errors
+    /// Returns `GatewayError::Validation` if no such provider is registered,
+    /// `GatewayError::Authorization` if it is not allowed, and
+    /// `GatewayError::NoHealthyProviders` if it cannot serve the request
+    #[instrument(skip(self, request, allowed), fields(model = %request.model))]
+    pub fn route_to_provider(
+        &self,
+        request: &GatewayRequest,
+        tenant_id: Option<&str>,
+        allowed: Option<&[String]>,
+        provider_id: &str,
+    ) -> Result<(Arc<dyn LLMProvider>, RouteDecision), GatewayError> {
+        self.route_inner(request, tenant_id, allowed, &[], Some(provider_id))
+    }
+
+    fn route_inner(
+        &self,
+        request: &GatewayRequest,
+        tenant_id: Option<&str>,
+        allowed: Option<&[String]>,
+        excluded: &[String],
+        pinned: Option<&str>,
     ) -> Result<(Arc<dyn LLMProvider>, RouteDecision), GatewayError> {
         // Build match context
         let context = self.build_match_context(request, tenant_id);
@@ -193,23 +423,81 @@ impl Router {
         let matched_action_refs: Vec<&RuleAction> = matched_actions.iter().collect();
 
         // Merge actions to get routing parameters
-        let (target_providers, strategy, model_transform, headers, matched_rules) =
+        let (mut target_providers, mut strategy, model_transform, headers, matched_rules) =
             self.merge_actions(&matched_action_refs, &request.model);
+        let stream_passthrough = matched_actions
+            .iter()
+            .find_map(|action| action.stream_passthrough);
+
+        if let Some(pinned) = pinned {
+            target_providers = vec![pinned.to_string()];
+            strategy = "provider_override".to_string();
+        }
 
         // Get provider candidates
         let candidates = self.build_candidates(&target_providers);
+        let considered: Vec<String> = candidates.iter().map(|c| c.id.clone()).collect();
 
         if candidates.is_empty() {
+            if let Some(pinned) = pinned {
+                return Err(GatewayError::validation(
+                    format!("Unknown provider '{pinned}'"),
+                    Some("provider".to_string()),
+                    "unknown_provider",
+                ));
+            }
             return Err(GatewayError::NoHealthyProviders {
                 model: request.model.clone(),
             });
         }
 
-        // Build selection criteria from request
-        let criteria = SelectionCriteria::from_request(request);
+        let allowed = allowed.or_else(|| {
+            tenant_id
+                .and_then(|tenant| self.config.tenant_allowlists.get(tenant))
+                .map(Vec::as_slice)
+        });
+        let candidates = Self::apply_allowlist(request, tenant_id, allowed, candidates)?;
+
+        let prompt_tokens = self.estimator.estimate_prompt(request);
+        let candidates: Vec<ProviderCandidate> = self
+            .apply_context_routing(request, prompt_tokens, candidates)?
+            .into_iter()
+            .map(|c| match c.project_cost(request, prompt_tokens) {
+                Some(cost) => c.with_projected_cost(cost),
+                None => c,
+            })
+            .collect();
 
-        // Select provider via load balancer
-        let provider = self.load_balancer.select(&candidates, &criteria, tenant_id)?;
+        // Build selection criteria from request
+        let mut criteria = SelectionCriteria::from_request(request);
+        criteria.exclude_providers.extend_from_slice(excluded);
+
+        // Select provider: the fastest region when regional instances are
+        // available, otherwise via the load balancer strategy
+        let regional = self.config.region_selection
+            && pinned.is_none()
+            && candidates.iter().any(|c| c.region.is_some());
+        let (provider, scores) = if regional {
+            strategy = "region_latency".to_string();
+            self.load_balancer.select_lowest_latency(&candidates, &criteria)?
+        } else {
+            self.load_balancer
+                .select_scored(&candidates, &criteria, tenant_id)?
+        };
+        let region = candidates
+            .iter()
+            .find(|c| c.id == provider.id())
+            .and_then(|c| c.region.clone());
+        let unselectable = considered
+            .iter()
+            .filter(|id| {
+                !candidates
+                    .iter()
+                    .any(|c| &c.id == *id && c.health.should_route())
+                    || excluded.contains(id)
+            })
+            .cloned()
+            .collect();
 
         // Apply model transform if any
         let model = model_transform.map_or_else(|| request.model.clone(), |t| t.apply(&request.model));
@@ -220,11 +508,17 @@ impl Router {
             headers,
             matched_rules,
             strategy,
+            scores,
+            region,
+            stream_passthrough,
+            provider_override: pinned.map(str::to_string),
+            excluded: unselectable,
         };
 
         debug!(
             provider = %decision.provider_id,
             model = %decision.model,
+            region = ?decision.region,
             rules = ?decision.matched_rules,
             "Route decision made"
         );
@@ -242,6 +536,27 @@ impl Router {
         self.load_balancer.record_completion(provider_id, latency, success);
     }
 
+    /// Record a failed request, cooling overloaded providers down further
+    ///
+    /// See [`LoadBalancer::record_failure`].
+    pub fn record_failure(&self, provider_id: &str, latency: Duration, error: &GatewayError) {
+        self.load_balancer.record_failure(provider_id, latency, error);
+    }
+
+    /// Release a routed provider without recording an outcome
+    ///
+    /// See [`LoadBalancer::release`].
+    pub fn release(&self, provider_id: &str) {
+        self.load_balancer.release(provider_id);
+    }
+
+    /// Hold a routed provider's in-flight slot until the guard is dropped
+    ///
+    /// See [`LoadBalancer::in_flight_guard`].
+    pub fn in_flight_guard(&self, provider_id: &str) -> ActiveRequestGuard {
+        self.load_balancer.in_flight_guard(provider_id)
+    }
+
     /// Get the load balancer for direct access
     #[must_use]
     pub fn load_balancer(&self) -> &LoadBalancer {
@@ -325,6 +640,92 @@ impl Router {
         (providers, strategy, model_transform, headers, matched_rules)
     }
 
+    /// Drop candidates the tenant is not permitted to use
+    fn apply_allowlist(
+        request: &GatewayRequest,
+        tenant_id: Option<&str>,
+        allowed: Option<&[String]>,
+        mut candidates: Vec<ProviderCandidate>,
+    ) -> Result<Vec<ProviderCandidate>, GatewayError> {
+        let Some(allowed) = allowed else {
+            return Ok(candidates);
+        };
+
+        let available: Vec<String> = candidates.iter().map(|c| c.id.clone()).collect();
+        candidates.retain(|c| allowed.contains(&c.id));
+
+        if candidates.is_empty() {
+            let tenant = tenant_id.unwrap_or("caller");
+            return Err(GatewayError::authorization(format!(
+                "Tenant '{tenant}' is not permitted to use any provider available for model '{}' (allowed: [{}], available: [{}])",
+                request.model,
+                allowed.join(", "),
+                available.join(", "),
+            )));
+        }
+
+        debug!(
+            tenant = ?tenant_id,
+            remaining = candidates.len(),
+            "Candidates restricted by provider allowlist"
+        );
+        Ok(candidates)
+    }
+
+    /// Drop candidates whose context window the request would overflow,
+    /// keeping only the largest-context ones for very large prompts
+    fn apply_context_routing(
+        &self,
+        request: &GatewayRequest,
+        prompt_tokens: u32,
+        mut candidates: Vec<ProviderCandidate>,
+    ) -> Result<Vec<ProviderCandidate>, GatewayError> {
+        let config = &self.config.context_routing;
+        if !config.enabled {
+            return Ok(candidates);
+        }
+
+        let required = config.required_context(request, prompt_tokens);
+        candidates.retain(|c| {
+            let fits = c
+                .provider
+                .capabilities()
+                .max_context_length
+                .map_or(true, |max| max >= required);
+            if !fits {
+                debug!(provider = %c.id, required, "Excluded: context window too small");
+            }
+            fits
+        });
+
+        if candidates.is_empty() {
+            return Err(GatewayError::validation(
+                format!(
+                    "Request needs ~{required} tokens of context, more than any available provider supports"
+                ),
+                Some("messages".to_string()),
+                "context_length_exceeded",
+            ));
+        }
+
+        if config
+            .large_prompt_threshold
+            .is_some_and(|threshold| prompt_tokens >= threshold)
+        {
+            let largest = candidates
+                .iter()
+                .filter_map(|c| c.provider.capabilities().max_context_length)
+                .max();
+            if let Some(largest) = largest {
+                candidates
+                    .retain(|c| c.provider.capabilities().max_context_length == Some(largest));
+                debug!(prompt_tokens, largest, "Large prompt routed to largest-context providers");
+            }
+        }
+
+        Ok(candidates)
+    }
+
     fn build_candidates(&self, target_providers: &[String]) -> Vec<ProviderCandidate> {
         let providers = self.providers.read();
 
@@ -332,10 +733,14 @@ impl Router {
             .iter()
             .filter_map(|id| {
                 providers.get(id).map(|entry| {
-                    ProviderCandidate::new(Arc::clone(&entry.provider))
+                    let candidate = ProviderCandidate::new(Arc::clone(&entry.provider))
                         .with_health(entry.health)
                         .with_weight(entry.weight)
-                        .with_priority(entry.priority)
+                        .with_priority(entry.priority);
+                    match &entry.region {
+                        Some(region) => candidate.with_region(region.clone()),
+                        None => candidate,
+                    }
                 })
             })
             .collect()
@@ -352,6 +757,7 @@ mod tests {
     struct MockProvider {
         id: String,
         models: Vec<ModelInfo>,
+        capabilities: ProviderCapabilities,
     }
 
     impl MockProvider {
@@ -359,8 +765,35 @@ mod tests {
             Self {
                 id: id.to_string(),
                 models: models.into_iter().map(ModelInfo::new).collect(),
+                capabilities: ProviderCapabilities {
+                    chat: true,
+                    streaming: true,
+                    function_calling: false,
+                    vision: false,
+                    embeddings: false,
+                    json_mode: false,
+                    seed: false,
+                    logprobs: false,
+                    max_context_length: None,
+                    max_output_tokens: None,
+                    parallel_tool_calls: false,
+                    audio_output: false,
+                },
             }
         }
+
+        fn with_context_length(mut self, tokens: u32) -> Self {
+            self.capabilities.max_context_length = Some(tokens);
+            self
+        }
+
+        fn with_pricing(mut self, input: f64, output: f64) -> Self {
+            for model in &mut self.models {
+                model.input_cost_per_1k = Some(input);
+                model.output_cost_per_1k = Some(output);
+            }
+            self
+        }
     }
 
     #[async_trait::async_trait]
@@ -389,20 +822,7 @@ mod tests {
         }
 
         fn capabilities(&self) -> &ProviderCapabilities {
-            static CAPS: ProviderCapabilities = ProviderCapabilities {
-                chat: true,
-                streaming: true,
-                function_calling: false,
-                vision: false,
-                embeddings: false,
-                json_mode: false,
-                seed: false,
-                logprobs: false,
-                max_context_length: None,
-                max_output_tokens: None,
-                parallel_tool_calls: false,
-            };
-            &CAPS
+            &self.capabilities
         }
 
         fn models(&self) -> &[ModelInfo] {
@@ -533,4 +953,378 @@ mod tests {
         let (provider, _) = router.route(&request, None).unwrap();
         assert_eq!(provider.id(), "anthropic");
     }
+
+    fn create_residency_router(config: RouterConfig) -> Router {
+        let router = Router::new(config);
+        for id in ["openai", "azure-eu"] {
+            router.register_provider(Arc::new(MockProvider::new(id, vec!["gpt-4"])), 100, 100);
+            router.update_health(id, HealthStatus::Healthy);
+        }
+        router
+    }
+
+    #[test]
+    fn test_tenant_allowlist_restricts_routing() {
+        let router = create_residency_router(
+            RouterConfig::new().with_tenant_allowlist("acme-eu", vec!["azure-eu".to_string()]),
+        );
+        let request = prompt_request(40);
+
+        for _ in 0..10 {
+            let (provider, decision) = router.route(&request, Some("acme-eu")).unwrap();
+            assert_eq!(provider.id(), "azure-eu");
+            assert!(decision.excluded.contains(&"openai".to_string()));
+            assert!(!decision.excluded.contains(&"azure-eu".to_string()));
+        }
+
+        // Other tenants are unrestricted
+        let routed: std::collections::HashSet<String> = (0..10)
+            .map(|_| router.route(&request, Some("other")).unwrap().0.id().to_string())
+            .collect();
+        assert!(routed.contains("openai"));
+    }
+
+    #[test]
+    fn test_explicit_allowlist_overrides_config() {
+        let router = create_residency_router(
+            RouterConfig::new().with_tenant_allowlist("acme-eu", vec!["azure-eu".to_string()]),
+        );
+        let allowed = vec!["openai".to_string()];
+
+        let (provider, _) = router
+            .route_with_allowlist(&prompt_request(40), Some("acme-eu"), Some(&allowed))
+            .unwrap();
+        assert_eq!(provider.id(), "openai");
+    }
+
+    #[test]
+    fn test_empty_allowed_set_is_descriptive_error() {
+        let router = create_residency_router(
+            RouterConfig::new().with_tenant_allowlist("acme-eu", vec!["bedrock-eu".to_string()]),
+        );
+
+        let err = router
+            .route(&prompt_request(40), Some("acme-eu"))
+            .err()
+            .expect("routing should fail");
+        assert!(matches!(err, GatewayError::Authorization { .. }));
+        let message = err.to_string();
+        assert!(message.contains("acme-eu"));
+        assert!(message.contains("gpt-4"));
+        assert!(message.contains("bedrock-eu"));
+    }
+
+    #[test]
+    fn test_provider_override_pins_provider() {
+        let router = create_residency_router(RouterConfig::new());
+        router.add_rule(
+            RoutingRule::new("gpt-rule", "GPT Models")
+                .with_matcher(RuleMatcher::new().with_model("gpt-*"))
+                .with_action(RuleAction::new().with_providers(vec!["openai".to_string()])),
+        );
+
+        for _ in 0..5 {
+            let (provider, decision) = router
+                .route_to_provider(&prompt_request(40), None, None, "azure-eu")
+                .unwrap();
+            assert_eq!(provider.id(), "azure-eu");
+            assert_eq!(decision.provider_override.as_deref(), Some("azure-eu"));
+            assert_eq!(decision.strategy, "provider_override");
+        }
+
+        let (_, decision) = router.route(&prompt_request(40), None).unwrap();
+        assert_eq!(decision.provider_override, None);
+    }
+
+    #[test]
+    fn test_provider_override_is_still_checked() {
+        let router = create_residency_router(RouterConfig::new());
+        let request = prompt_request(40);
+
+        let err = router
+            .route_to_provider(&request, None, None, "bedrock")
+            .err()
+            .expect("unknown provider");
+        assert!(matches!(err, GatewayError::Validation { .. }));
+
+        let allowed = vec!["openai".to_string()];
+        let err = router
+            .route_to_provider(&request, None, Some(&allowed), "azure-eu")
+            .err()
+            .expect("provider not allowed");
+        assert!(matches!(err, GatewayError::Authorization { .. }));
+
+        router.update_health("azure-eu", HealthStatus::Unhealthy);
+        let err = router
+            .route_to_provider(&request, None, None, "azure-eu")
+            .err()
+            .expect("provider unhealthy");
+        assert!(matches!(err, GatewayError::NoHealthyProviders { .. }));
+    }
+
+    fn create_context_router(config: ContextRoutingConfig) -> Router {
+        let config = config.with_enabled(true);
+        let router = Router::new(RouterConfig::new().with_context_routing(config));
+
+        let small = MockProvider::new("small", vec!["gpt-4"]).with_context_length(8_000);
+        let large = MockProvider::new("large", vec!["gpt-4"]).with_context_length(128_000);
+        let unbounded = MockProvider::new("unbounded", vec!["gpt-4"]);
+
+        router.register_provider(Arc::new(small), 100, 100);
+        router.register_provider(Arc::new(large), 100, 100);
+        router.register_provider(Arc::new(unbounded), 100, 100);
+
+        for id in ["small", "large", "unbounded"] {
+            router.update_health(id, HealthStatus::Healthy);
+        }
+
+        router
+    }
+
+    fn prompt_request(chars: usize) -> GatewayRequest {
+        GatewayRequest::builder()
+            .model("gpt-4")
+            .message(gateway_core::ChatMessage::user("x".repeat(chars)))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_large_prompt_excludes_small_context_provider() {
+        let router = create_context_router(
+            ContextRoutingConfig::new().with_large_prompt_threshold(20_000),
+        );
+
+        // ~25k estimated tokens: overflows the 8k provider
+        let request = prompt_request(100_000);
+
+        for _ in 0..10 {
+            let (provider, decision) = router.route(&request, None).unwrap();
+            assert_eq!(provider.id(), "large");
+            assert!(decision.excluded.contains(&"small".to_string()));
+        }
+    }
+
+    #[test]
+    fn test_small_prompt_keeps_all_providers() {
+        let router = create_context_router(ContextRoutingConfig::new());
+        let request = prompt_request(40);
+
+        let routed: std::collections::HashSet<String> = (0..9)
+            .map(|_| router.route(&request, None).unwrap().0.id().to_string())
+            .collect();
+        assert!(routed.contains("small"));
+    }
+
+    #[test]
+    fn test_overflowing_request_rejected() {
+        let router = Router::new(
+            RouterConfig::new().with_context_routing(ContextRoutingConfig::new().with_enabled(true)),
+        );
+        let provider = MockProvider::new("small", vec!["gpt-4"]).with_context_length(8_000);
+        router.register_provider(Arc::new(provider), 100, 100);
+        router.update_health("small", HealthStatus::Healthy);
+
+        let result = router.route(&prompt_request(100_000), None);
+        assert!(matches!(
+            result,
+            Err(GatewayError::Validation { ref code, .. }) if code == "context_length_exceeded"
+        ));
+    }
+
+    #[test]
+    fn test_context_routing_off_by_default() {
+        let router = Router::new(RouterConfig::new());
+        let provider = MockProvider::new("small", vec!["gpt-4"]).with_context_length(8_000);
+        router.register_provider(Arc::new(provider), 100, 100);
+        router.update_health("small", HealthStatus::Healthy);
+
+        let (provider, decision) = router.route(&prompt_request(100_000), None).unwrap();
+        assert_eq!(provider.id(), "small");
+        assert!(decision.excluded.is_empty());
+    }
+
+    #[test]
+    fn test_required_context_includes_headroom_and_max_tokens() {
+        // 993 text tokens plus role and framing
+        let mut request = prompt_request(3_972);
+        request.max_tokens = Some(1_000);
+
+        let config = ContextRoutingConfig::new().with_headroom(1.5);
+        let prompt_tokens = gateway_core::TokenEstimator::estimate_prompt(
+            &CharHeuristicEstimator::default(),
+            &request,
+        );
+        assert_eq!(prompt_tokens, 1_000);
+        assert_eq!(config.required_context(&request, prompt_tokens), 3_000);
+    }
+
+    #[test]
+    fn test_injected_estimator_sizes_requests() {
+        // ~5k tokens at the default 4 chars per token: fits the 8k provider
+        let request = prompt_request(20_000);
+        let router = create_context_router(ContextRoutingConfig::new());
+        let routed: std::collections::HashSet<String> = (0..9)
+            .map(|_| router.route(&request, None).unwrap().0.id().to_string())
+            .collect();
+        assert!(routed.contains("small"));
+
+        // ~20k tokens at one char per token: overflows it
+        let router = create_context_router(ContextRoutingConfig::new())
+            .with_estimator(Arc::new(CharHeuristicEstimator::new(1.0)));
+        for _ in 0..9 {
+            let (provider, _) = router.route(&request, None).unwrap();
+            assert_ne!(provider.id(), "small");
+        }
+    }
+
+    /// Router using composite scoring over a cheap, slow provider and an
+    /// expensive, fast one
+    fn create_composite_router(weights: crate::strategy::CompositeWeights) -> Router {
+        let config = RouterConfig::new()
+            .with_load_balancer(LoadBalancerConfig::new().with_composite_weights(weights));
+        let router = Router::new(config);
+
+        let cheap = MockProvider::new("cheap", vec!["gpt-4"]).with_pricing(0.5, 1.5);
+        let fast = MockProvider::new("fast", vec!["gpt-4"]).with_pricing(10.0, 30.0);
+        router.register_provider(Arc::new(cheap), 100, 100);
+        router.register_provider(Arc::new(fast), 100, 100);
+        router.update_health("cheap", HealthStatus::Healthy);
+        router.update_health("fast", HealthStatus::Healthy);
+
+        router.record_completion("cheap", Duration::from_millis(900), true);
+        router.record_completion("fast", Duration::from_millis(120), true);
+        router
+    }
+
+    fn composite_request() -> GatewayRequest {
+        GatewayRequest::builder()
+            .model("gpt-4")
+            .message(gateway_core::ChatMessage::user("Summarize this document for me"))
+            .max_tokens(500)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_composite_weighted_toward_cost_routes_to_cheaper() {
+        let router =
+            create_composite_router(crate::strategy::CompositeWeights::new(3.0, 1.0, 0.0));
+
+        let (provider, decision) = router.route(&composite_request(), None).unwrap();
+        assert_eq!(provider.id(), "cheap");
+        assert_eq!(decision.scores.len(), 2);
+        assert!(decision.scores["cheap"] < decision.scores["fast"]);
+    }
+
+    #[test]
+    fn test_composite_weighted_toward_latency_routes_to_faster() {
+        let router =
+            create_composite_router(crate::strategy::CompositeWeights::new(1.0, 3.0, 0.0));
+
+        let (provider, decision) = router.route(&composite_request(), None).unwrap();
+        assert_eq!(provider.id(), "fast");
+        assert!(decision.scores["fast"] < decision.scores["cheap"]);
+    }
+
+    #[test]
+    fn test_non_scoring_strategy_reports_no_scores() {
+        let router = create_test_router();
+
+        let (_, decision) = router.route(&composite_request(), None).unwrap();
+        assert!(decision.scores.is_empty());
+    }
+
+    /// Router with the same provider registered in two regions, the EU
+    /// instance measured slower than the US one
+    fn create_regional_router() -> Router {
+        let router = Router::new(RouterConfig::new().with_region_selection(true));
+        for (id, region) in [("openai-us", "us-east-1"), ("openai-eu", "eu-west-1")] {
+            router.register_regional_provider(
+                Arc::new(MockProvider::new(id, vec!["gpt-4"])),
+                region,
+                100,
+                100,
+            );
+            router.update_health(id, HealthStatus::Healthy);
+        }
+
+        router.record_completion("openai-us", Duration::from_millis(80), true);
+        router.record_completion("openai-eu", Duration::from_millis(350), true);
+        router
+    }
+
+    #[test]
+    fn test_region_selection_picks_lowest_latency_region() {
+        let router = create_regional_router();
+
+        for _ in 0..5 {
+            let (provider, decision) = router.route(&composite_request(), None).unwrap();
+            assert_eq!(provider.id(), "openai-us");
+            assert_eq!(decision.region.as_deref(), Some("us-east-1"));
+            assert_eq!(decision.strategy, "region_latency");
+            assert!(decision.scores["openai-us"] < decision.scores["openai-eu"]);
+        }
+    }
+
+    #[test]
+    fn test_region_selection_follows_recent_latency() {
+        let router = create_regional_router();
+
+        // The US region slows down
+        for _ in 0..5 {
+            router.record_completion("openai-us", Duration::from_millis(900), true);
+        }
+
+        let (_, decision) = router.route(&composite_request(), None).unwrap();
+        assert_eq!(decision.region.as_deref(), Some("eu-west-1"));
+    }
+
+    #[test]
+    fn test_region_failover_moves_to_next_region() {
+        let router = create_regional_router();
+        let request = composite_request();
+
+        let (failed, _) = router.route(&request, None).unwrap();
+        router.record_completion(failed.id(), Duration::from_millis(50), false);
+
+        let excluded = vec![failed.id().to_string()];
+        let (provider, decision) = router
+            .route_excluding(&request, None, None, &excluded)
+            .unwrap();
+        assert_eq!(provider.id(), "openai-eu");
+        assert_eq!(decision.region.as_deref(), Some("eu-west-1"));
+        assert_eq!(decision.excluded, excluded);
+
+        // No region left
+        let excluded = vec!["openai-us".to_string(), "openai-eu".to_string()];
+        assert!(router.route_excluding(&request, None, None, &excluded).is_err());
+    }
+
+    #[test]
+    fn test_unmeasured_region_is_tried_first() {
+        let router = create_regional_router();
+        router.register_regional_provider(
+            Arc::new(MockProvider::new("openai-ap", vec!["gpt-4"])),
+            "ap-southeast-1",
+            100,
+            100,
+        );
+        router.update_health("openai-ap", HealthStatus::Healthy);
+
+        let (_, decision) = router.route(&composite_request(), None).unwrap();
+        assert_eq!(decision.region.as_deref(), Some("ap-southeast-1"));
+    }
+
+    #[test]
+    fn test_untagged_providers_keep_load_balancer_strategy() {
+        let config = RouterConfig::new().with_region_selection(true);
+        let router = Router::new(config);
+        router.register_provider(Arc::new(MockProvider::new("openai", vec!["gpt-4"])), 100, 100);
+        router.update_health("openai", HealthStatus::Healthy);
+
+        let (_, decision) = router.route(&composite_request(), None).unwrap();
+        assert_eq!(decision.strategy, "round_robin");
+        assert_eq!(decision.region, None);
+    }
 }
@@ -294,6 +294,8 @@ pub struct RuleAction {
     /// Whether to skip other rules after this one
     #[serde(default)]
     pub terminal: bool,
+    /// Override whether streams are forwarded verbatim from the provider
+    pub stream_passthrough: Option<bool>,
 }
 
 impl RuleAction {
@@ -333,6 +335,13 @@ impl RuleAction {
         self
     }
 
+    /// Set whether streams on this route are forwarded verbatim
+    #[must_use]
+    pub fn with_stream_passthrough(mut self, passthrough: bool) -> Self {
+        self.stream_passthrough = Some(passthrough);
+        self
+    }
+
     /// Make this a terminal rule
     #[must_use]
     pub fn terminal(mut self) -> Self {
@@ -489,7 +498,8 @@ impl Default for RulesEngine {
 }
 
 /// Check if a pattern matches a value (supports glob-like patterns)
-fn matches_pattern(pattern: &str, value: &str) -> bool {
+#[must_use]
+pub fn matches_pattern(pattern: &str, value: &str) -> bool {
     // Handle exact match
     if !pattern.contains('*') && !pattern.contains('?') {
         return pattern == value;
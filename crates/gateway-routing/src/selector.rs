@@ -68,6 +68,13 @@ impl SelectionCriteria {
         self
     }
 
+    /// Require audio output support
+    #[must_use]
+    pub fn require_audio_output(mut self) -> Self {
+        self.capabilities.audio_output = true;
+        self
+    }
+
     /// Set minimum health status
     #[must_use]
     pub fn with_min_health(mut self, health: HealthStatus) -> Self {
@@ -118,6 +125,10 @@ impl SelectionCriteria {
             criteria = criteria.require_vision();
         }
 
+        if request.wants_audio_output() {
+            criteria = criteria.require_audio_output();
+        }
+
         criteria
     }
 }
@@ -135,6 +146,8 @@ pub struct RequiredCapabilities {
     pub embeddings: bool,
     /// Requires JSON mode
     pub json_mode: bool,
+    /// Requires audio output
+    pub audio_output: bool,
     /// Minimum context length
     pub min_context_length: Option<usize>,
 }
@@ -158,6 +171,9 @@ impl RequiredCapabilities {
         if self.json_mode && !caps.json_mode {
             return false;
         }
+        if self.audio_output && !caps.audio_output {
+            return false;
+        }
         if let Some(min_ctx) = self.min_context_length {
             if let Some(max_ctx) = caps.max_context_length {
                 if (max_ctx as usize) < min_ctx {
@@ -188,6 +204,12 @@ pub struct ProviderCandidate {
     pub success_rate: Option<f64>,
     /// Whether this is a preferred provider
     pub preferred: bool,
+    /// Projected cost of the request on this provider (USD)
+    pub projected_cost: Option<f64>,
+    /// Region this provider instance serves from
+    pub region: Option<String>,
+    /// Requests currently in flight on this provider
+    pub active_requests: u64,
 }
 
 impl ProviderCandidate {
@@ -203,6 +225,9 @@ impl ProviderCandidate {
             avg_latency_ms: None,
             success_rate: None,
             preferred: false,
+            projected_cost: None,
+            region: None,
+            active_requests: 0,
         }
     }
 
@@ -234,6 +259,46 @@ impl ProviderCandidate {
         self
     }
 
+    /// Set the projected request cost
+    #[must_use]
+    pub fn with_projected_cost(mut self, cost: f64) -> Self {
+        self.projected_cost = Some(cost);
+        self
+    }
+
+    /// Set the region
+    #[must_use]
+    pub fn with_region(mut self, region: impl Into<String>) -> Self {
+        self.region = Some(region.into());
+        self
+    }
+
+    /// Set the number of requests in flight
+    #[must_use]
+    pub fn with_active_requests(mut self, active_requests: u64) -> Self {
+        self.active_requests = active_requests;
+        self
+    }
+
+    /// Project the cost of `request` from the provider's model pricing
+    ///
+    /// Uses the estimated `prompt_tokens` and `max_tokens` (if set). `None`
+    /// if the model isn't listed or has no input pricing.
+    #[must_use]
+    pub fn project_cost(&self, request: &GatewayRequest, prompt_tokens: u32) -> Option<f64> {
+        let model = self
+            .provider
+            .models()
+            .iter()
+            .find(|m| m.matches(&request.model))?;
+        let input = model.input_cost_per_1k?;
+        let output = model.output_cost_per_1k.unwrap_or(input);
+
+        let prompt_tokens = f64::from(prompt_tokens);
+        let completion_tokens = f64::from(request.token_limit().unwrap_or(0));
+        Some(prompt_tokens.mul_add(input, completion_tokens * output) / 1000.0)
+    }
+
     /// Calculate a composite score for this candidate
     #[must_use]
     pub fn score(&self) -> f64 {
@@ -269,6 +334,11 @@ impl ProviderCandidate {
         // Weight factor
         score *= f64::from(self.weight) / 100.0;
 
+        // Load factor: in-flight requests per 100 units of weight
+        if self.active_requests > 0 && self.weight > 0 {
+            score /= 1.0 + self.active_requests as f64 * 100.0 / f64::from(self.weight);
+        }
+
         score
     }
 }
@@ -379,7 +449,7 @@ fn meets_health_requirement(actual: HealthStatus, required: HealthStatus) -> boo
 #[cfg(test)]
 mod tests {
     use super::*;
-    use gateway_core::{ChatChunk, GatewayError, GatewayResponse, ModelInfo, ProviderType};
+    use gateway_core::{ChatChunk, ChatMessage, GatewayError, GatewayResponse, ModelInfo, ProviderType};
     use futures::stream::BoxStream;
 
     struct MockProvider {
@@ -480,6 +550,30 @@ mod tests {
         assert_eq!(filtered[0].id, "p1");
     }
 
+    #[test]
+    fn test_audio_request_requires_audio_output() {
+        let mut audio = MockProvider::new("audio").with_model("gpt-4o-audio-preview");
+        audio.capabilities.audio_output = true;
+        let text = MockProvider::new("text").with_model("gpt-4o-audio-preview");
+
+        let candidates = vec![
+            ProviderCandidate::new(Arc::new(audio)).with_health(HealthStatus::Healthy),
+            ProviderCandidate::new(Arc::new(text)).with_health(HealthStatus::Healthy),
+        ];
+
+        let request = GatewayRequest::builder()
+            .model("gpt-4o-audio-preview")
+            .message(ChatMessage::user("Say hello"))
+            .audio_output("alloy", "wav")
+            .build()
+            .unwrap();
+        let criteria = SelectionCriteria::from_request(&request);
+        let filtered = ProviderSelector::filter(&candidates, &criteria);
+
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].id, "audio");
+    }
+
     #[test]
     fn test_filter_by_health() {
         let provider1 = Arc::new(MockProvider::new("p1"));
@@ -561,4 +655,25 @@ mod tests {
 
         assert!(healthy.score() > degraded.score());
     }
+
+    #[test]
+    fn test_select_best_accounts_for_active_requests() {
+        let provider1 = Arc::new(MockProvider::new("p1"));
+        let provider2 = Arc::new(MockProvider::new("p2"));
+
+        let candidates = vec![
+            ProviderCandidate::new(provider1)
+                .with_health(HealthStatus::Healthy)
+                .with_weight(200)
+                .with_active_requests(8),
+            ProviderCandidate::new(provider2)
+                .with_health(HealthStatus::Healthy)
+                .with_active_requests(1),
+        ];
+        // 8 requests on double the capacity is busier than 1
+        assert_eq!(ProviderSelector::select_best(&candidates).map(|c| c.id.as_str()), Some("p2"));
+
+        let idle = candidates[0].clone().with_active_requests(0);
+        assert!(idle.score() > candidates[0].score());
+    }
 }
@@ -5,7 +5,9 @@
 //! - Weighted Round Robin
 //! - Random
 //! - Least Connections
+//! - Weighted Least Connections
 //! - Latency-based
+//! - Composite (weighted cost, latency and error rate)
 
 use parking_lot::Mutex;
 use rand::Rng;
@@ -24,6 +26,14 @@ pub trait LoadBalancingStrategy: Send + Sync {
 
     /// Get the strategy name
     fn name(&self) -> &'static str;
+
+    /// Per-provider scores behind the selection, aligned with `providers`
+    ///
+    /// Only score-based strategies report scores; lower is better and
+    /// unhealthy providers score `None`.
+    fn scores(&self, _providers: &[ProviderStats]) -> Option<Vec<Option<f64>>> {
+        None
+    }
 }
 
 /// Statistics about a provider for load balancing decisions
@@ -41,6 +51,10 @@ pub struct ProviderStats {
     pub success_rate: f64,
     /// Whether the provider is healthy
     pub is_healthy: bool,
+    /// Projected cost of the request on this provider (USD)
+    pub projected_cost: Option<f64>,
+    /// Priority (lower = higher priority)
+    pub priority: u32,
 }
 
 impl ProviderStats {
@@ -54,6 +68,8 @@ impl ProviderStats {
             avg_latency_ms: 0.0,
             success_rate: 1.0,
             is_healthy: true,
+            projected_cost: None,
+            priority: 100,
         }
     }
 
@@ -63,6 +79,13 @@ impl ProviderStats {
         self.weight = weight;
         self
     }
+
+    /// Set the priority
+    #[must_use]
+    pub fn with_priority(mut self, priority: u32) -> Self {
+        self.priority = priority;
+        self
+    }
 }
 
 /// Round Robin load balancing strategy
@@ -388,6 +411,83 @@ impl LoadBalancingStrategy for LeastConnectionsStrategy {
     }
 }
 
+/// Weighted Least Connections load balancing strategy
+///
+/// Selects the provider with the fewest in-flight requests per unit of
+/// weight, so a heavily weighted provider that is currently busy yields to a
+/// lighter one with spare capacity. Ties go to the higher-priority provider,
+/// then rotate. Unhealthy providers and providers with zero weight are never
+/// selected.
+///
+/// Suited to long-lived streaming requests, whose in-flight counts round
+/// robin ignores.
+pub struct WeightedLeastConnectionsStrategy {
+    counter: AtomicUsize,
+}
+
+impl WeightedLeastConnectionsStrategy {
+    /// Create a new weighted least connections strategy
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            counter: AtomicUsize::new(0),
+        }
+    }
+
+    /// In-flight requests per unit of weight
+    fn load(provider: &ProviderStats) -> Option<f64> {
+        (provider.is_healthy && provider.weight > 0)
+            .then(|| provider.active_connections as f64 / f64::from(provider.weight))
+    }
+}
+
+impl Default for WeightedLeastConnectionsStrategy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LoadBalancingStrategy for WeightedLeastConnectionsStrategy {
+    fn select(&self, providers: &[ProviderStats]) -> Option<usize> {
+        let loads: Vec<(usize, f64)> = providers
+            .iter()
+            .enumerate()
+            .filter_map(|(i, p)| Self::load(p).map(|load| (i, load)))
+            .collect();
+
+        let min_load = loads.iter().map(|(_, load)| *load).min_by(f64::total_cmp)?;
+        let top_priority = loads
+            .iter()
+            .filter(|(_, load)| load.total_cmp(&min_load).is_eq())
+            .map(|(i, _)| providers[*i].priority)
+            .min()?;
+        let candidates: Vec<usize> = loads
+            .iter()
+            .filter(|(i, load)| {
+                load.total_cmp(&min_load).is_eq() && providers[*i].priority == top_priority
+            })
+            .map(|(i, _)| *i)
+            .collect();
+
+        let counter = self.counter.fetch_add(1, Ordering::Relaxed);
+        let selected = candidates[counter % candidates.len()];
+        debug!(provider_index = selected, load = min_load, "Selected by weighted least connections");
+        Some(selected)
+    }
+
+    fn record_completion(&self, _provider_index: usize, _latency: Duration, _success: bool) {
+        // In-flight counts arrive with the provider stats
+    }
+
+    fn name(&self) -> &'static str {
+        "weighted_least_connections"
+    }
+
+    fn scores(&self, providers: &[ProviderStats]) -> Option<Vec<Option<f64>>> {
+        Some(providers.iter().map(Self::load).collect())
+    }
+}
+
 /// Latency-based load balancing strategy
 pub struct LatencyBasedStrategy {
     latencies: dashmap::DashMap<usize, LatencyTracker>,
@@ -573,6 +673,152 @@ impl LoadBalancingStrategy for LatencyBasedStrategy {
     }
 }
 
+/// Relative weights of the composite strategy's score dimensions
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CompositeWeights {
+    /// Weight of projected request cost
+    pub cost: f64,
+    /// Weight of average latency
+    pub latency: f64,
+    /// Weight of error rate
+    pub error: f64,
+}
+
+impl Default for CompositeWeights {
+    fn default() -> Self {
+        Self {
+            cost: 1.0,
+            latency: 1.0,
+            error: 1.0,
+        }
+    }
+}
+
+impl CompositeWeights {
+    /// Create weights for cost, latency and error rate
+    #[must_use]
+    pub fn new(cost: f64, latency: f64, error: f64) -> Self {
+        Self {
+            cost,
+            latency,
+            error,
+        }
+    }
+
+    /// Weights scaled to sum to 1, or `None` if no weight is positive
+    fn normalized(self) -> Option<Self> {
+        let cost = self.cost.max(0.0);
+        let latency = self.latency.max(0.0);
+        let error = self.error.max(0.0);
+        let total = cost + latency + error;
+        (total > 0.0).then(|| Self::new(cost / total, latency / total, error / total))
+    }
+}
+
+/// Composite load balancing strategy
+///
+/// Scores each healthy provider by a weighted sum of its projected cost,
+/// average latency and error rate, each min-max normalized across the
+/// candidates, and picks the lowest score. Providers without a projected
+/// cost are scored as the most expensive. With all weights zero it falls
+/// back to round robin.
+pub struct CompositeStrategy {
+    weights: Option<CompositeWeights>,
+    fallback: RoundRobinStrategy,
+}
+
+impl CompositeStrategy {
+    /// Create a composite strategy with the given weights
+    #[must_use]
+    pub fn new(weights: CompositeWeights) -> Self {
+        Self {
+            weights: weights.normalized(),
+            fallback: RoundRobinStrategy::new(),
+        }
+    }
+}
+
+impl Default for CompositeStrategy {
+    fn default() -> Self {
+        Self::new(CompositeWeights::default())
+    }
+}
+
+/// Min-max normalize `values` to 0..=1 (all zero when they're equal)
+fn normalize(values: &[f64]) -> Vec<f64> {
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let range = max - min;
+    values
+        .iter()
+        .map(|v| if range > 0.0 { (v - min) / range } else { 0.0 })
+        .collect()
+}
+
+impl LoadBalancingStrategy for CompositeStrategy {
+    fn select(&self, providers: &[ProviderStats]) -> Option<usize> {
+        let Some(scores) = self.scores(providers) else {
+            return self.fallback.select(providers);
+        };
+
+        let selected = scores
+            .iter()
+            .enumerate()
+            .filter_map(|(i, score)| score.map(|s| (i, s)))
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(i, _)| i);
+
+        if let Some(idx) = selected {
+            debug!(provider_index = idx, score = ?scores[idx], "Selected by composite score");
+        }
+        selected
+    }
+
+    fn record_completion(&self, _provider_index: usize, _latency: Duration, _success: bool) {
+        // Latency and error rate come from the load balancer's provider stats
+    }
+
+    fn name(&self) -> &'static str {
+        "composite"
+    }
+
+    fn scores(&self, providers: &[ProviderStats]) -> Option<Vec<Option<f64>>> {
+        let weights = self.weights?;
+        let healthy: Vec<&ProviderStats> = providers.iter().filter(|p| p.is_healthy).collect();
+
+        let max_cost = healthy
+            .iter()
+            .filter_map(|p| p.projected_cost)
+            .fold(0.0, f64::max);
+        let costs: Vec<f64> = healthy
+            .iter()
+            .map(|p| p.projected_cost.unwrap_or(max_cost))
+            .collect();
+        let latencies: Vec<f64> = healthy.iter().map(|p| p.avg_latency_ms).collect();
+        let errors: Vec<f64> = healthy
+            .iter()
+            .map(|p| 1.0 - p.success_rate.clamp(0.0, 1.0))
+            .collect();
+
+        let costs = normalize(&costs);
+        let latencies = normalize(&latencies);
+        let errors = normalize(&errors);
+
+        let mut healthy_scores = (0..healthy.len()).map(|i| {
+            weights.error.mul_add(
+                errors[i],
+                weights.latency.mul_add(latencies[i], weights.cost * costs[i]),
+            )
+        });
+        Some(
+            providers
+                .iter()
+                .map(|p| if p.is_healthy { healthy_scores.next() } else { None })
+                .collect(),
+        )
+    }
+}
+
 /// Factory for creating load balancing strategies
 pub struct StrategyFactory;
 
@@ -588,7 +834,11 @@ impl StrategyFactory {
             "random" => Box::new(RandomStrategy::new()),
             "weighted_random" | "weightedrandom" => Box::new(WeightedRandomStrategy::new()),
             "least_connections" | "leastconnections" => Box::new(LeastConnectionsStrategy::new()),
+            "weighted_least_connections" | "weightedleastconnections" => {
+                Box::new(WeightedLeastConnectionsStrategy::new())
+            }
             "latency" | "latency_based" => Box::new(LatencyBasedStrategy::new()),
+            "composite" => Box::new(CompositeStrategy::default()),
             _ => Box::new(RoundRobinStrategy::new()), // Default fallback
         }
     }
@@ -698,6 +948,54 @@ mod tests {
         assert_eq!(selected, Some(2));
     }
 
+    #[test]
+    fn test_weighted_least_connections_divides_by_weight() {
+        let strategy = WeightedLeastConnectionsStrategy::new();
+        let mut providers = create_test_providers(3);
+        providers[0].weight = 300;
+        providers[0].active_connections = 2;
+        providers[1].weight = 100;
+        providers[1].active_connections = 1;
+        providers[2].weight = 0;
+
+        // 2/300 beats 1/100 despite more connections; zero weight never wins
+        assert_eq!(strategy.select(&providers), Some(0));
+        let scores = strategy.scores(&providers).unwrap();
+        assert!((scores[0].unwrap() - 2.0 / 300.0).abs() < 1e-9);
+        assert_eq!(scores[2], None);
+
+        // Once busy enough, the heavier provider yields
+        providers[0].active_connections = 4;
+        assert_eq!(strategy.select(&providers), Some(1));
+    }
+
+    #[test]
+    fn test_weighted_least_connections_ties() {
+        let strategy = WeightedLeastConnectionsStrategy::new();
+        let mut providers = create_test_providers(4);
+        providers[0].priority = 10;
+        providers[1].priority = 10;
+        providers[3].is_healthy = false;
+
+        // Idle providers: the higher-priority ones, in rotation
+        let selections: Vec<usize> = (0..4).filter_map(|_| strategy.select(&providers)).collect();
+        assert_eq!(selections, vec![0, 1, 0, 1]);
+
+        // Load outranks priority; unhealthy providers are never picked, even idle
+        providers[0].active_connections = 1;
+        providers[1].active_connections = 1;
+        providers[2].active_connections = 1;
+        let selections: Vec<usize> = (0..3).filter_map(|_| strategy.select(&providers)).collect();
+        assert_eq!(selections, vec![0, 1, 0]);
+        providers[2].active_connections = 0;
+        assert_eq!(strategy.select(&providers), Some(2));
+
+        for provider in &mut providers {
+            provider.is_healthy = false;
+        }
+        assert_eq!(strategy.select(&providers), None);
+    }
+
     #[test]
     fn test_latency_based() {
         let strategy = LatencyBasedStrategy::new().with_min_samples(2);
@@ -725,7 +1023,14 @@ mod tests {
 
     #[test]
     fn test_strategy_factory() {
-        let strategies = ["round_robin", "random", "least_connections", "latency"];
+        let strategies = [
+            "round_robin",
+            "random",
+            "least_connections",
+            "weighted_least_connections",
+            "latency",
+            "composite",
+        ];
 
         for name in strategies {
             let strategy = StrategyFactory::create(name);
@@ -751,4 +1056,66 @@ mod tests {
 
         assert!(strategy.select(&providers).is_none());
     }
+
+    /// Cheap but slow provider and an expensive but fast one
+    fn cost_latency_tradeoff() -> Vec<ProviderStats> {
+        let mut cheap = ProviderStats::new("cheap");
+        cheap.projected_cost = Some(0.001);
+        cheap.avg_latency_ms = 900.0;
+
+        let mut fast = ProviderStats::new("fast");
+        fast.projected_cost = Some(0.010);
+        fast.avg_latency_ms = 150.0;
+
+        vec![cheap, fast]
+    }
+
+    #[test]
+    fn test_composite_weighted_toward_cost_picks_cheaper() {
+        let strategy = CompositeStrategy::new(CompositeWeights::new(0.8, 0.2, 0.0));
+        assert_eq!(strategy.select(&cost_latency_tradeoff()), Some(0));
+    }
+
+    #[test]
+    fn test_composite_weighted_toward_latency_picks_faster() {
+        let strategy = CompositeStrategy::new(CompositeWeights::new(0.2, 0.8, 0.0));
+        assert_eq!(strategy.select(&cost_latency_tradeoff()), Some(1));
+    }
+
+    #[test]
+    fn test_composite_weights_are_sum_normalized() {
+        let providers = cost_latency_tradeoff();
+        let scaled = CompositeStrategy::new(CompositeWeights::new(8.0, 2.0, 0.0));
+        let unit = CompositeStrategy::new(CompositeWeights::new(0.8, 0.2, 0.0));
+
+        let scaled = scaled.scores(&providers).unwrap();
+        let unit = unit.scores(&providers).unwrap();
+        for (a, b) in scaled.iter().zip(&unit) {
+            assert!((a.unwrap() - b.unwrap()).abs() < f64::EPSILON);
+        }
+        // Cheap: 0.8 * 0 + 0.2 * 1; fast: 0.8 * 1 + 0.2 * 0
+        assert!((unit[0].unwrap() - 0.2).abs() < 1e-9);
+        assert!((unit[1].unwrap() - 0.8).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_composite_penalizes_errors_and_skips_unhealthy() {
+        let mut providers = create_test_providers(3);
+        providers[0].success_rate = 0.5;
+        providers[2].is_healthy = false;
+
+        let strategy = CompositeStrategy::new(CompositeWeights::new(0.0, 0.0, 1.0));
+        assert_eq!(strategy.select(&providers), Some(1));
+        assert_eq!(strategy.scores(&providers).unwrap()[2], None);
+    }
+
+    #[test]
+    fn test_composite_zero_weights_fall_back_to_round_robin() {
+        let strategy = CompositeStrategy::new(CompositeWeights::new(0.0, 0.0, 0.0));
+        let providers = cost_latency_tradeoff();
+
+        assert!(strategy.scores(&providers).is_none());
+        let selections: Vec<usize> = (0..4).filter_map(|_| strategy.select(&providers)).collect();
+        assert_eq!(selections, vec![0, 1, 0, 1]);
+    }
 }
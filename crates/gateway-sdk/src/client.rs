@@ -4,7 +4,7 @@ use crate::config::ClientConfig;
 use crate::error::{ApiErrorResponse, Error, Result};
 use crate::request::{ChatRequest, ChatRequestBuilder, Message};
 use crate::response::{ChatResponse, HealthResponse, ModelsListResponse};
-use crate::streaming::ChatStream;
+use crate::streaming::{ChatStream, StreamChunk};
 use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION, CONTENT_TYPE, USER_AGENT};
 use secrecy::Secret;
 use std::sync::Arc;
@@ -12,6 +12,9 @@ use std::time::Duration;
 use tracing::{debug, instrument};
 use url::Url;
 
+/// Header carrying the gateway's hash of the response content.
+const RESPONSE_HASH_HEADER: &str = "x-gateway-response-hash";
+
 /// Client for interacting with the LLM Inference Gateway.
 ///
 /// # Example
@@ -144,7 +147,14 @@ impl Client {
         })
         .await?;
 
-        self.handle_response(response).await
+        let response_hash = response
+            .headers()
+            .get(RESPONSE_HASH_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .map(String::from);
+        let mut completion: ChatResponse = self.handle_response(response).await?;
+        completion.response_hash = response_hash;
+        Ok(completion)
     }
 
     /// Send a streaming chat completion request.
@@ -158,9 +168,66 @@ impl Client {
 
         debug!("Sending streaming chat completion request to {}", url);
 
-        let response = self.http
+        let response = self.open_stream(url.clone(), &request, None).await?;
+
+        let mut stream = ChatStream::new(response.bytes_stream())
+            .with_max_duration(self.config.max_stream_duration)
+            .with_idle_timeout(self.config.stream_idle_timeout);
+
+        if self.config.stream_resume {
+            let client = self.clone();
+            let url = url.clone();
+            let request = request.clone();
+            stream = stream.with_resume(self.config.stream_resume_attempts, move |last_event_id| {
+                let client = client.clone();
+                let url = url.clone();
+                let request = request.clone();
+                async move {
+                    debug!("Resuming stream to {} from event {:?}", url, last_event_id);
+                    let response = client
+                        .open_stream(url, &request, last_event_id.as_deref())
+                        .await?;
+                    Ok(response.bytes_stream())
+                }
+            });
+        }
+
+        if self.config.stream_reconnect_attempts == 0 {
+            return Ok(stream);
+        }
+
+        let client = self.clone();
+        Ok(stream.with_reconnect(self.config.stream_reconnect_attempts, move || {
+            let client = client.clone();
+            let url = url.clone();
+            let request = request.clone();
+            async move {
+                debug!("Reconnecting stalled stream to {}", url);
+                let response = client.open_stream(url, &request, None).await?;
+                Ok(response.bytes_stream())
+            }
+        }))
+    }
+
+    /// Send a streaming request and check the response status.
+    ///
+    /// `last_event_id` is sent as the `Last-Event-ID` header when resuming.
+    async fn open_stream(
+        &self,
+        url: Url,
+        request: &ChatRequest,
+        last_event_id: Option<&str>,
+    ) -> Result<reqwest::Response> {
+        // The stream is bounded by its own max duration rather than the
+        // (typically shorter) request timeout
+        let mut builder = self.http
             .post(url)
-            .json(&request)
+            .json(request)
+            .timeout(self.config.max_stream_duration);
+        if let Some(last_event_id) = last_event_id {
+            builder = builder.header("Last-Event-ID", last_event_id);
+        }
+        let response = builder
             .send()
             .await
             .map_err(|e| self.map_reqwest_error(e))?;
@@ -169,7 +236,7 @@ impl Client {
             return Err(self.handle_error_response(response).await);
         }
 
-        Ok(ChatStream::new(response.bytes_stream()))
+        Ok(response)
     }
 
     /// List available models.
@@ -390,6 +457,11 @@ pub struct ClientBuilder {
     api_key: Option<Secret<String>>,
     timeout: Option<Duration>,
     connect_timeout: Option<Duration>,
+    max_stream_duration: Option<Duration>,
+    stream_idle_timeout: Option<Duration>,
+    stream_reconnect_attempts: u32,
+    stream_resume: bool,
+    stream_resume_attempts: Option<u32>,
     max_retries: Option<u32>,
     retry_initial_delay: Option<Duration>,
     retry_max_delay: Option<Duration>,
@@ -408,6 +480,11 @@ impl ClientBuilder {
             api_key: None,
             timeout: None,
             connect_timeout: None,
+            max_stream_duration: None,
+            stream_idle_timeout: None,
+            stream_reconnect_attempts: 0,
+            stream_resume: false,
+            stream_resume_attempts: None,
             max_retries: None,
             retry_initial_delay: None,
             retry_max_delay: None,
@@ -443,6 +520,54 @@ impl ClientBuilder {
         self
     }
 
+    /// Set the maximum total duration of a streaming response.
+    ///
+    /// Streams are terminated with a timeout error once this elapses,
+    /// regardless of chunk activity. Independent of the request timeout.
+    pub fn max_stream_duration(mut self, duration: Duration) -> Self {
+        self.max_stream_duration = Some(duration);
+        self
+    }
+
+    /// Set the maximum time a stream may go without data or heartbeats.
+    ///
+    /// Server keep-alive comments count as activity, so this should span
+    /// several heartbeat intervals. Stalled streams end with
+    /// [`Error::StreamStalled`].
+    pub fn stream_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.stream_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Set how many times a stream that stalls before delivering any data
+    /// is re-requested.
+    ///
+    /// Streams that stall after data has arrived are never reconnected, as
+    /// a new request would replay the completion.
+    pub fn stream_reconnect_attempts(mut self, attempts: u32) -> Self {
+        self.stream_reconnect_attempts = attempts;
+        self
+    }
+
+    /// Resume streams whose connection drops or stalls mid-response.
+    ///
+    /// The request is re-issued with a `Last-Event-ID` header naming the
+    /// last event received, and events the server replays are skipped. If
+    /// the server does not send event IDs the stream ends with
+    /// [`Error::StreamNotResumable`] instead of restarting the completion.
+    /// The gateway does not send event IDs, so resuming only works against
+    /// upstreams that do.
+    pub fn stream_resume(mut self, enable: bool) -> Self {
+        self.stream_resume = enable;
+        self
+    }
+
+    /// Set how many times a single stream may be resumed.
+    pub fn stream_resume_attempts(mut self, attempts: u32) -> Self {
+        self.stream_resume_attempts = Some(attempts);
+        self
+    }
+
     /// Set the maximum number of retries.
     pub fn max_retries(mut self, retries: u32) -> Self {
         self.max_retries = Some(retries);
@@ -502,6 +627,11 @@ impl ClientBuilder {
             api_key: self.api_key,
             timeout: self.timeout.unwrap_or(ClientConfig::DEFAULT_TIMEOUT),
             connect_timeout: self.connect_timeout.unwrap_or(ClientConfig::DEFAULT_CONNECT_TIMEOUT),
+            max_stream_duration: self.max_stream_duration.unwrap_or(ClientConfig::DEFAULT_MAX_STREAM_DURATION),
+            stream_idle_timeout: self.stream_idle_timeout.unwrap_or(ClientConfig::DEFAULT_STREAM_IDLE_TIMEOUT),
+            stream_reconnect_attempts: self.stream_reconnect_attempts,
+            stream_resume: self.stream_resume,
+            stream_resume_attempts: self.stream_resume_attempts.unwrap_or(ClientConfig::DEFAULT_STREAM_RESUME_ATTEMPTS),
             max_retries: self.max_retries.unwrap_or(ClientConfig::DEFAULT_MAX_RETRIES),
             retry_initial_delay: self.retry_initial_delay.unwrap_or(ClientConfig::DEFAULT_RETRY_INITIAL_DELAY),
             retry_max_delay: self.retry_max_delay.unwrap_or(ClientConfig::DEFAULT_RETRY_MAX_DELAY),
@@ -612,6 +742,21 @@ impl ChatBuilder {
         let request = self.builder.build()?;
         self.client.chat_completion_stream(&request).await
     }
+
+    /// Send as a streaming request, pushing each chunk or error to `sink`.
+    ///
+    /// Returning [`ControlFlow::Break`] from `sink` stops consumption and
+    /// aborts the upstream request. Errors opening the stream are returned
+    /// rather than passed to `sink`.
+    ///
+    /// [`ControlFlow::Break`]: std::ops::ControlFlow::Break
+    pub async fn stream_to<F>(self, sink: F) -> Result<()>
+    where
+        F: FnMut(Result<StreamChunk>) -> std::ops::ControlFlow<()>,
+    {
+        self.stream().await?.stream_to(sink).await;
+        Ok(())
+    }
 }
 
 impl std::fmt::Debug for Client {
@@ -652,6 +797,38 @@ mod tests {
         assert_eq!(client.config.base_url.as_str(), "http://localhost:8080/");
     }
 
+    #[tokio::test]
+    async fn test_chat_completion_reads_response_hash() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header(RESPONSE_HASH_HEADER, "ab12")
+                    .set_body_json(serde_json::json!({
+                        "id": "c1",
+                        "object": "chat.completion",
+                        "created": 1,
+                        "model": "gpt-4o",
+                        "choices": [{
+                            "index": 0,
+                            "message": {"role": "assistant", "content": "hi"},
+                            "finish_reason": "stop"
+                        }]
+                    })),
+            )
+            .mount(&server)
+            .await;
+
+        let client = Client::builder().base_url(server.uri()).build().unwrap();
+        let response = client.chat().model("gpt-4o").user_message("hi").send().await.unwrap();
+
+        assert_eq!(response.response_hash(), Some("ab12"));
+    }
+
     #[test]
     fn test_should_retry_status() {
         assert!(should_retry_status(429));
@@ -15,6 +15,16 @@ pub struct ClientConfig {
     pub(crate) timeout: Duration,
     /// Connection timeout duration.
     pub(crate) connect_timeout: Duration,
+    /// Maximum total duration of a streaming response.
+    pub(crate) max_stream_duration: Duration,
+    /// Maximum time a stream may go without data or heartbeats.
+    pub(crate) stream_idle_timeout: Duration,
+    /// Reconnection attempts for a stream that stalls before any data.
+    pub(crate) stream_reconnect_attempts: u32,
+    /// Resume streams that drop mid-response from the last event ID.
+    pub(crate) stream_resume: bool,
+    /// Maximum resumption attempts per stream.
+    pub(crate) stream_resume_attempts: u32,
     /// Maximum number of retry attempts.
     pub(crate) max_retries: u32,
     /// Initial retry delay.
@@ -38,6 +48,12 @@ impl ClientConfig {
     pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
     /// Default connection timeout (10 seconds).
     pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+    /// Default maximum stream duration (10 minutes).
+    pub const DEFAULT_MAX_STREAM_DURATION: Duration = Duration::from_secs(600);
+    /// Default stream idle timeout (60 seconds, several server heartbeats).
+    pub const DEFAULT_STREAM_IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+    /// Default maximum resumption attempts per stream.
+    pub const DEFAULT_STREAM_RESUME_ATTEMPTS: u32 = 3;
     /// Default maximum retries.
     pub const DEFAULT_MAX_RETRIES: u32 = 3;
     /// Default initial retry delay (1 second).
@@ -57,6 +73,11 @@ impl ClientConfig {
             api_key: None,
             timeout: Self::DEFAULT_TIMEOUT,
             connect_timeout: Self::DEFAULT_CONNECT_TIMEOUT,
+            max_stream_duration: Self::DEFAULT_MAX_STREAM_DURATION,
+            stream_idle_timeout: Self::DEFAULT_STREAM_IDLE_TIMEOUT,
+            stream_reconnect_attempts: 0,
+            stream_resume: false,
+            stream_resume_attempts: Self::DEFAULT_STREAM_RESUME_ATTEMPTS,
             max_retries: Self::DEFAULT_MAX_RETRIES,
             retry_initial_delay: Self::DEFAULT_RETRY_INITIAL_DELAY,
             retry_max_delay: Self::DEFAULT_RETRY_MAX_DELAY,
@@ -93,6 +114,31 @@ impl ClientConfig {
         self.connect_timeout
     }
 
+    /// Get the maximum stream duration.
+    pub fn max_stream_duration(&self) -> Duration {
+        self.max_stream_duration
+    }
+
+    /// Get the stream idle timeout.
+    pub fn stream_idle_timeout(&self) -> Duration {
+        self.stream_idle_timeout
+    }
+
+    /// Get the number of reconnection attempts for a stalled stream.
+    pub fn stream_reconnect_attempts(&self) -> u32 {
+        self.stream_reconnect_attempts
+    }
+
+    /// Check if dropped streams are resumed.
+    pub fn stream_resume(&self) -> bool {
+        self.stream_resume
+    }
+
+    /// Get the maximum number of resumption attempts per stream.
+    pub fn stream_resume_attempts(&self) -> u32 {
+        self.stream_resume_attempts
+    }
+
     /// Get the maximum number of retries.
     pub fn max_retries(&self) -> u32 {
         self.max_retries
@@ -143,6 +189,7 @@ mod tests {
         assert!(!config.has_api_key());
         assert_eq!(config.timeout(), ClientConfig::DEFAULT_TIMEOUT);
         assert_eq!(config.max_retries(), ClientConfig::DEFAULT_MAX_RETRIES);
+        assert!(!config.stream_resume());
     }
 
     #[test]
@@ -78,6 +78,20 @@ pub enum Error {
         message: String,
     },
 
+    /// Stream went silent, with neither data nor keep-alive heartbeats.
+    #[error("Stream stalled: no activity for {idle_ms}ms")]
+    StreamStalled {
+        /// Idle window in milliseconds that elapsed without activity.
+        idle_ms: u64,
+    },
+
+    /// Stream dropped mid-response and the server cannot resume it.
+    #[error("Stream not resumable: {message}")]
+    StreamNotResumable {
+        /// Why the stream could not be resumed.
+        message: String,
+    },
+
     /// Timeout waiting for response.
     #[error("Request timed out after {duration_ms}ms")]
     Timeout {
@@ -193,6 +207,18 @@ impl Error {
         }
     }
 
+    /// Create a stream stalled error.
+    pub fn stream_stalled(idle_ms: u64) -> Self {
+        Self::StreamStalled { idle_ms }
+    }
+
+    /// Create a stream not resumable error.
+    pub fn stream_not_resumable(message: impl Into<String>) -> Self {
+        Self::StreamNotResumable {
+            message: message.into(),
+        }
+    }
+
     /// Create a timeout error.
     pub fn timeout(duration_ms: u64) -> Self {
         Self::Timeout { duration_ms }
@@ -234,6 +260,7 @@ impl Error {
             Self::RateLimited { .. } => true,
             Self::Unavailable { .. } => true,
             Self::Timeout { .. } => true,
+            Self::StreamStalled { .. } => true,
             Self::Connection { .. } => true,
             Self::Api { status, .. } => {
                 matches!(status, 429 | 500 | 502 | 503 | 504)
@@ -323,6 +350,8 @@ mod tests {
         assert!(Error::rate_limited(Some(60)).is_retryable());
         assert!(Error::unavailable("service down").is_retryable());
         assert!(Error::timeout(5000).is_retryable());
+        assert!(Error::stream_stalled(30000).is_retryable());
+        assert!(!Error::stream_not_resumable("no event IDs").is_retryable());
         assert!(!Error::authentication("invalid key").is_retryable());
         assert!(!Error::invalid_request("bad param").is_retryable());
     }
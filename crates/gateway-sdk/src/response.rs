@@ -21,6 +21,9 @@ pub struct ChatResponse {
     /// System fingerprint.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub system_fingerprint: Option<String>,
+    /// Content hash from the `X-Gateway-Response-Hash` header.
+    #[serde(skip)]
+    pub response_hash: Option<String>,
 }
 
 impl ChatResponse {
@@ -75,6 +78,14 @@ impl ChatResponse {
     pub fn completion_tokens(&self) -> Option<u32> {
         self.usage.as_ref().map(|u| u.completion_tokens)
     }
+
+    /// Get the gateway's SHA-256 hash of the response content.
+    ///
+    /// Only present when the gateway has response hashing enabled. Equal
+    /// content always has an equal hash, so it can key a client-side cache.
+    pub fn response_hash(&self) -> Option<&str> {
+        self.response_hash.as_deref()
+    }
 }
 
 /// A single completion choice.
@@ -155,6 +166,12 @@ pub struct Usage {
     pub completion_tokens: u32,
     /// Total number of tokens.
     pub total_tokens: u32,
+    /// Prompt tokens served from the provider's prompt cache, if reported.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_tokens_cached: Option<u32>,
+    /// Completion tokens spent on reasoning, if reported.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completion_tokens_reasoning: Option<u32>,
 }
 
 impl Usage {
@@ -164,6 +181,8 @@ impl Usage {
             prompt_tokens,
             completion_tokens,
             total_tokens: prompt_tokens + completion_tokens,
+            prompt_tokens_cached: None,
+            completion_tokens_reasoning: None,
         }
     }
 
@@ -181,6 +200,11 @@ impl std::ops::Add for Usage {
             prompt_tokens: self.prompt_tokens + other.prompt_tokens,
             completion_tokens: self.completion_tokens + other.completion_tokens,
             total_tokens: self.total_tokens + other.total_tokens,
+            prompt_tokens_cached: add_optional(self.prompt_tokens_cached, other.prompt_tokens_cached),
+            completion_tokens_reasoning: add_optional(
+                self.completion_tokens_reasoning,
+                other.completion_tokens_reasoning,
+            ),
         }
     }
 }
@@ -190,6 +214,17 @@ impl std::ops::AddAssign for Usage {
         self.prompt_tokens += other.prompt_tokens;
         self.completion_tokens += other.completion_tokens;
         self.total_tokens += other.total_tokens;
+        self.prompt_tokens_cached = add_optional(self.prompt_tokens_cached, other.prompt_tokens_cached);
+        self.completion_tokens_reasoning =
+            add_optional(self.completion_tokens_reasoning, other.completion_tokens_reasoning);
+    }
+}
+
+/// Sum two optional counts, staying `None` only if neither is reported.
+fn add_optional(a: Option<u32>, b: Option<u32>) -> Option<u32> {
+    match (a, b) {
+        (None, None) => None,
+        (a, b) => Some(a.unwrap_or(0) + b.unwrap_or(0)),
     }
 }
 
@@ -262,6 +297,7 @@ mod tests {
             }],
             usage: Some(Usage::new(10, 5)),
             system_fingerprint: None,
+            response_hash: None,
         };
 
         assert_eq!(response.content(), "Hello, world!");
@@ -281,6 +317,23 @@ mod tests {
         assert_eq!(total.total_tokens, 50);
     }
 
+    #[test]
+    fn test_usage_token_details() {
+        let usage: Usage = serde_json::from_str(
+            r#"{"prompt_tokens":100,"completion_tokens":50,"total_tokens":150,"prompt_tokens_cached":80,"completion_tokens_reasoning":30}"#,
+        )
+        .unwrap();
+        assert_eq!(usage.prompt_tokens_cached, Some(80));
+        assert_eq!(usage.completion_tokens_reasoning, Some(30));
+
+        let plain: Usage = serde_json::from_str(
+            r#"{"prompt_tokens":1,"completion_tokens":1,"total_tokens":2}"#,
+        )
+        .unwrap();
+        assert_eq!(plain.prompt_tokens_cached, None);
+        assert_eq!((usage + plain).prompt_tokens_cached, Some(80));
+    }
+
     #[test]
     fn test_health_response() {
         let response = HealthResponse {
@@ -2,11 +2,15 @@
 
 use crate::error::{Error, Result};
 use bytes::Bytes;
+use futures::future::BoxFuture;
 use futures::stream::Stream;
 use pin_project_lite::pin_project;
 use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::ops::ControlFlow;
 use std::pin::Pin;
 use std::task::{Context, Poll};
+use std::time::Duration;
 
 /// A chunk from a streaming response.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -115,13 +119,43 @@ pub struct StreamFunctionCall {
     pub arguments: Option<String>,
 }
 
+/// Boxed stream of parsed SSE events.
+type EventStream = Pin<Box<dyn Stream<Item = Result<SseEvent>> + Send>>;
+
+/// Re-issues the streaming request after a stall.
+type Reconnect = Box<dyn Fn() -> BoxFuture<'static, Result<EventStream>> + Send>;
+
+/// Re-issues the streaming request after a dropped connection, passing the
+/// last received event ID if any.
+type Resume = Box<dyn Fn(Option<String>) -> BoxFuture<'static, Result<EventStream>> + Send>;
+
+/// An event parsed from the SSE byte stream.
+enum SseEvent {
+    /// A completion chunk with the event ID in effect when it arrived.
+    Chunk(StreamChunk, Option<String>),
+    /// Bytes arrived without a complete chunk, e.g. a keep-alive comment.
+    Heartbeat,
+}
+
 pin_project! {
     /// A stream of chat completion chunks.
     pub struct ChatStream {
         #[pin]
-        inner: Pin<Box<dyn Stream<Item = Result<StreamChunk>> + Send>>,
+        inner: EventStream,
         buffer: String,
         done: bool,
+        deadline: Option<Pin<Box<tokio::time::Sleep>>>,
+        max_duration: Option<Duration>,
+        idle: Option<Pin<Box<tokio::time::Sleep>>>,
+        idle_timeout: Option<Duration>,
+        reconnect: Option<Reconnect>,
+        reconnects_left: u32,
+        reconnecting: Option<BoxFuture<'static, Result<EventStream>>>,
+        received_data: bool,
+        resume: Option<Resume>,
+        resumes_left: u32,
+        last_event_id: Option<String>,
+        resumed: bool,
     }
 }
 
@@ -136,9 +170,101 @@ impl ChatStream {
             inner,
             buffer: String::new(),
             done: false,
+            deadline: None,
+            max_duration: None,
+            idle: None,
+            idle_timeout: None,
+            reconnect: None,
+            reconnects_left: 0,
+            reconnecting: None,
+            received_data: false,
+            resume: None,
+            resumes_left: 0,
+            last_event_id: None,
+            resumed: false,
         }
     }
 
+    /// Terminate the stream with a timeout error once `max_duration` elapses.
+    ///
+    /// The deadline is absolute and is not extended by incoming chunks.
+    pub fn with_max_duration(mut self, max_duration: Duration) -> Self {
+        self.deadline = Some(Box::pin(tokio::time::sleep(max_duration)));
+        self.max_duration = Some(max_duration);
+        self
+    }
+
+    /// Terminate the stream with [`Error::StreamStalled`] if no data or
+    /// heartbeat arrives within `idle_timeout`.
+    ///
+    /// Unlike [`Self::with_max_duration`], the window restarts on every
+    /// activity, including keep-alive comments.
+    pub fn with_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle = Some(Box::pin(tokio::time::sleep(idle_timeout)));
+        self.idle_timeout = Some(idle_timeout);
+        self
+    }
+
+    /// Re-issue the request up to `max_attempts` times if the stream stalls
+    /// before delivering any data.
+    ///
+    /// Only takes effect together with [`Self::with_idle_timeout`]. Once a
+    /// chunk has been delivered a stall is always surfaced, since a new
+    /// request would replay the completion.
+    pub fn with_reconnect<F, Fut, S>(mut self, max_attempts: u32, reconnect: F) -> Self
+    where
+        F: Fn() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<S>> + Send + 'static,
+        S: Stream<Item = std::result::Result<Bytes, reqwest::Error>> + Send + 'static,
+    {
+        self.reconnect = Some(Box::new(move || {
+            let connect = reconnect();
+            Box::pin(async move {
+                let stream = connect.await?;
+                Ok(Box::pin(parse_sse_stream(stream)) as EventStream)
+            })
+        }));
+        self.reconnects_left = max_attempts;
+        self
+    }
+
+    /// Resume the stream up to `max_attempts` times after the connection
+    /// drops or stalls mid-stream.
+    ///
+    /// `resume` receives the ID of the last event received, to be sent as
+    /// the `Last-Event-ID` header, or `None` if nothing has arrived yet and
+    /// the request can simply be re-issued. Only the last ID is kept. After
+    /// resuming, events up to the first new one are skipped if they repeat
+    /// that ID or, for numeric IDs, do not exceed it. A server may therefore
+    /// continue after `Last-Event-ID`, or replay from the start if its IDs
+    /// are increasing numbers. If the server sends no event IDs the stream
+    /// ends with [`Error::StreamNotResumable`] rather than restarting the
+    /// completion.
+    ///
+    /// The gateway itself sends no event IDs, so this only helps with
+    /// upstreams reached directly that do.
+    pub fn with_resume<F, Fut, S>(mut self, max_attempts: u32, resume: F) -> Self
+    where
+        F: Fn(Option<String>) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<S>> + Send + 'static,
+        S: Stream<Item = std::result::Result<Bytes, reqwest::Error>> + Send + 'static,
+    {
+        self.resume = Some(Box::new(move |last_event_id| {
+            let connect = resume(last_event_id);
+            Box::pin(async move {
+                let stream = connect.await?;
+                Ok(Box::pin(parse_sse_stream(stream)) as EventStream)
+            })
+        }));
+        self.resumes_left = max_attempts;
+        self
+    }
+
+    /// ID of the last event received, if the server sends event IDs.
+    pub fn last_event_id(&self) -> Option<&str> {
+        self.last_event_id.as_deref()
+    }
+
     /// Collect all content from the stream.
     pub async fn collect_content(mut self) -> Result<String> {
         use futures::StreamExt;
@@ -153,6 +279,23 @@ impl ChatStream {
         Ok(content)
     }
 
+    /// Drive the stream, pushing each chunk or error to `sink`.
+    ///
+    /// Stops when the stream ends or `sink` returns [`ControlFlow::Break`].
+    /// Breaking drops the stream, which closes the upstream connection.
+    pub async fn stream_to<F>(mut self, mut sink: F)
+    where
+        F: FnMut(Result<StreamChunk>) -> ControlFlow<()>,
+    {
+        use futures::StreamExt;
+
+        while let Some(item) = self.next().await {
+            if sink(item).is_break() {
+                break;
+            }
+        }
+    }
+
     /// Get the accumulated buffer content.
     pub fn buffer(&self) -> &str {
         &self.buffer
@@ -168,38 +311,205 @@ impl Stream for ChatStream {
     type Item = Result<StreamChunk>;
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        let this = self.project();
+        let mut this = self.project();
 
         if *this.done {
             return Poll::Ready(None);
         }
 
-        match this.inner.poll_next(cx) {
-            Poll::Ready(Some(Ok(chunk))) => {
-                // Accumulate content in buffer
-                this.buffer.push_str(chunk.content());
+        if let Some(deadline) = this.deadline.as_mut() {
+            if deadline.as_mut().poll(cx).is_ready() {
+                *this.done = true;
+                // Drop the underlying connection so the server stops sending
+                this.inner.set(Box::pin(futures::stream::empty()));
+                *this.reconnecting = None;
+                let duration_ms = this.max_duration.unwrap_or_default().as_millis() as u64;
+                return Poll::Ready(Some(Err(Error::timeout(duration_ms))));
+            }
+        }
+
+        loop {
+            if let Some(reconnecting) = this.reconnecting.as_mut() {
+                match reconnecting.as_mut().poll(cx) {
+                    Poll::Ready(Ok(events)) => {
+                        *this.reconnecting = None;
+                        this.inner.set(events);
+                        reset_idle(this.idle, *this.idle_timeout);
+                    }
+                    Poll::Ready(Err(e)) => {
+                        *this.reconnecting = None;
+                        *this.done = true;
+                        return Poll::Ready(Some(Err(e)));
+                    }
+                    Poll::Pending => return Poll::Pending,
+                }
+            }
+
+            match this.inner.as_mut().poll_next(cx) {
+                Poll::Ready(Some(Ok(SseEvent::Heartbeat))) => {
+                    reset_idle(this.idle, *this.idle_timeout);
+                    continue;
+                }
+                Poll::Ready(Some(Ok(SseEvent::Chunk(chunk, id)))) => {
+                    reset_idle(this.idle, *this.idle_timeout);
+
+                    if *this.resumed {
+                        match &id {
+                            // Already delivered before the connection dropped
+                            Some(id) if is_replayed(id, this.last_event_id.as_deref()) => continue,
+                            Some(_) => *this.resumed = false,
+                            None => {
+                                *this.done = true;
+                                this.inner.set(Box::pin(futures::stream::empty()));
+                                return Poll::Ready(Some(Err(Error::stream_not_resumable(
+                                    "resumed stream carries no event IDs",
+                                ))));
+                            }
+                        }
+                    }
+                    if this.resume.is_some() && id.is_some() {
+                        *this.last_event_id = id;
+                    }
+                    *this.received_data = true;
+
+                    // Accumulate content in buffer
+                    this.buffer.push_str(chunk.content());
 
-                if chunk.is_final() {
+                    if chunk.is_final() {
+                        *this.done = true;
+                    }
+
+                    return Poll::Ready(Some(Ok(chunk)));
+                }
+                Poll::Ready(Some(Err(e))) => {
+                    if matches!(e, Error::Http(_)) {
+                        match begin_resume(
+                            this.resume,
+                            this.resumes_left,
+                            this.last_event_id,
+                            *this.received_data,
+                        ) {
+                            Some(Ok(resuming)) => {
+                                tracing::debug!("Stream connection dropped, resuming: {}", e);
+                                this.inner.set(Box::pin(futures::stream::empty()));
+                                *this.resumed = this.last_event_id.is_some();
+                                *this.reconnecting = Some(resuming);
+                                continue;
+                            }
+                            Some(Err(not_resumable)) => {
+                                *this.done = true;
+                                return Poll::Ready(Some(Err(not_resumable)));
+                            }
+                            None => {}
+                        }
+                    }
+                    *this.done = true;
+                    return Poll::Ready(Some(Err(e)));
+                }
+                Poll::Ready(None) => {
                     *this.done = true;
+                    return Poll::Ready(None);
                 }
+                Poll::Pending => {}
+            }
 
-                Poll::Ready(Some(Ok(chunk)))
+            let Some(idle) = this.idle.as_mut() else {
+                return Poll::Pending;
+            };
+            if idle.as_mut().poll(cx).is_pending() {
+                return Poll::Pending;
             }
-            Poll::Ready(Some(Err(e))) => {
-                *this.done = true;
-                Poll::Ready(Some(Err(e)))
+
+            // Drop the dead connection before reconnecting or giving up
+            this.inner.set(Box::pin(futures::stream::empty()));
+
+            if let Some(reconnect) = this.reconnect.as_ref() {
+                if !*this.received_data && *this.reconnects_left > 0 {
+                    *this.reconnects_left -= 1;
+                    tracing::debug!("Stream stalled before any data, reconnecting");
+                    *this.reconnecting = Some(reconnect());
+                    continue;
+                }
             }
-            Poll::Ready(None) => {
-                *this.done = true;
-                Poll::Ready(None)
+
+            match begin_resume(
+                this.resume,
+                this.resumes_left,
+                this.last_event_id,
+                *this.received_data,
+            ) {
+                Some(Ok(resuming)) => {
+                    tracing::debug!("Stream stalled, resuming");
+                    *this.resumed = this.last_event_id.is_some();
+                    *this.reconnecting = Some(resuming);
+                    continue;
+                }
+                Some(Err(not_resumable)) => {
+                    *this.done = true;
+                    return Poll::Ready(Some(Err(not_resumable)));
+                }
+                None => {}
             }
-            Poll::Pending => Poll::Pending,
+
+            *this.done = true;
+            let idle_ms = this.idle_timeout.unwrap_or_default().as_millis() as u64;
+            return Poll::Ready(Some(Err(Error::stream_stalled(idle_ms))));
         }
     }
 }
 
+/// Start resuming a dropped stream.
+///
+/// Returns `None` when resumption is not configured or its attempts are
+/// spent, and an error when data has arrived without an event ID to resume
+/// from.
+fn begin_resume(
+    resume: &Option<Resume>,
+    resumes_left: &mut u32,
+    last_event_id: &Option<String>,
+    received_data: bool,
+) -> Option<Result<BoxFuture<'static, Result<EventStream>>>> {
+    let resume = resume.as_ref()?;
+    if *resumes_left == 0 {
+        return None;
+    }
+    if received_data && last_event_id.is_none() {
+        return Some(Err(Error::stream_not_resumable(
+            "server sent no event IDs to resume from",
+        )));
+    }
+    *resumes_left -= 1;
+    Some(Ok(resume(last_event_id.clone())))
+}
+
+/// Whether an event arriving after a resume was delivered before it.
+///
+/// Numeric IDs are compared as numbers, so a server replaying from the
+/// start is skipped up to the last event received.
+fn is_replayed(id: &str, last_event_id: Option<&str>) -> bool {
+    let Some(last) = last_event_id else {
+        return false;
+    };
+    match (id.parse::<u64>(), last.parse::<u64>()) {
+        (Ok(id), Ok(last)) => id <= last,
+        _ => id == last,
+    }
+}
+
+/// Restart the idle window after stream activity.
+fn reset_idle(idle: &mut Option<Pin<Box<tokio::time::Sleep>>>, idle_timeout: Option<Duration>) {
+    if let (Some(idle), Some(idle_timeout)) = (idle.as_mut(), idle_timeout) {
+        idle.as_mut().reset(tokio::time::Instant::now() + idle_timeout);
+    }
+}
+
 /// Parse an SSE stream into chunks.
-fn parse_sse_stream<S>(stream: S) -> impl Stream<Item = Result<StreamChunk>>
+///
+/// Reads that complete no chunk (keep-alive comments, partial events) are
+/// reported as heartbeats so callers can track stream liveness. Each chunk
+/// carries the last `id:` field seen, which per the SSE spec persists
+/// across events until replaced.
+fn parse_sse_stream<S>(stream: S) -> impl Stream<Item = Result<SseEvent>>
 where
     S: Stream<Item = std::result::Result<Bytes, reqwest::Error>> + Send,
 {
@@ -208,6 +518,7 @@ where
 
         let mut stream = std::pin::pin!(stream);
         let mut buffer = String::new();
+        let mut last_id: Option<String> = None;
 
         while let Some(result) = stream.next().await {
             let bytes = match result {
@@ -227,6 +538,7 @@ where
             };
 
             buffer.push_str(text);
+            let mut yielded = false;
 
             // Process complete SSE events
             while let Some(event_end) = buffer.find("\n\n") {
@@ -235,7 +547,10 @@ where
 
                 // Parse SSE event
                 for line in event.lines() {
-                    if let Some(data) = line.strip_prefix("data: ") {
+                    if let Some(id) = line.strip_prefix("id:") {
+                        let id = id.strip_prefix(' ').unwrap_or(id);
+                        last_id = Some(id.to_string());
+                    } else if let Some(data) = line.strip_prefix("data: ") {
                         // Check for [DONE] marker
                         if data.trim() == "[DONE]" {
                             return;
@@ -243,7 +558,10 @@ where
 
                         // Parse JSON chunk
                         match serde_json::from_str::<StreamChunk>(data) {
-                            Ok(chunk) => yield Ok(chunk),
+                            Ok(chunk) => {
+                                yielded = true;
+                                yield Ok(SseEvent::Chunk(chunk, last_id.clone()));
+                            }
                             Err(e) => {
                                 // Log but don't fail on parse errors for individual chunks
                                 tracing::debug!("Failed to parse chunk: {} - data: {}", e, data);
@@ -252,15 +570,21 @@ where
                     }
                 }
             }
+
+            if !yielded {
+                yield Ok(SseEvent::Heartbeat);
+            }
         }
 
         // Process any remaining data in buffer
         if !buffer.is_empty() {
             for line in buffer.lines() {
-                if let Some(data) = line.strip_prefix("data: ") {
+                if let Some(id) = line.strip_prefix("id:") {
+                    last_id = Some(id.strip_prefix(' ').unwrap_or(id).to_string());
+                } else if let Some(data) = line.strip_prefix("data: ") {
                     if data.trim() != "[DONE]" {
                         if let Ok(chunk) = serde_json::from_str::<StreamChunk>(data) {
-                            yield Ok(chunk);
+                            yield Ok(SseEvent::Chunk(chunk, last_id.clone()));
                         }
                     }
                 }
@@ -443,4 +767,319 @@ mod tests {
         assert_eq!(chunk.content(), "Hello");
         assert!(!chunk.is_final());
     }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_max_duration_terminates_trickling_stream() {
+        use futures::StreamExt;
+
+        let chunk = r#"data: {"id":"t","object":"chat.completion.chunk","created":0,"model":"m","choices":[{"index":0,"delta":{"content":"."}}]}"#;
+        let trickle = futures::stream::unfold((), move |()| async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            Some((Ok::<_, reqwest::Error>(Bytes::from(format!("{chunk}\n\n"))), ()))
+        });
+
+        let start = tokio::time::Instant::now();
+        let mut stream = ChatStream::new(trickle).with_max_duration(Duration::from_secs(1));
+
+        let mut chunks = 0;
+        let mut error = None;
+        while let Some(item) = stream.next().await {
+            match item {
+                Ok(_) => chunks += 1,
+                Err(e) => error = Some(e),
+            }
+        }
+
+        assert_eq!(start.elapsed(), Duration::from_secs(1));
+        assert!(chunks > 0);
+        assert!(stream.is_done());
+        assert!(matches!(error, Some(Error::Timeout { duration_ms: 1000 })));
+    }
+
+    const FINAL_CHUNK: &str = r#"data: {"id":"t","object":"chat.completion.chunk","created":0,"model":"m","choices":[{"index":0,"delta":{"content":"done"},"finish_reason":"stop"}]}"#;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_silent_stream_yields_stream_stalled() {
+        use futures::StreamExt;
+
+        let silent = futures::stream::pending::<std::result::Result<Bytes, reqwest::Error>>();
+
+        let start = tokio::time::Instant::now();
+        let mut stream = ChatStream::new(silent).with_idle_timeout(Duration::from_secs(30));
+
+        let item = stream.next().await;
+        assert!(matches!(
+            item,
+            Some(Err(Error::StreamStalled { idle_ms: 30_000 }))
+        ));
+        assert_eq!(start.elapsed(), Duration::from_secs(30));
+        assert!(stream.is_done());
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_heartbeats_keep_stream_alive() {
+        // Keep-alive comments every 10s for a minute, then the final chunk
+        let heartbeats = futures::stream::unfold(0u32, |sent| async move {
+            tokio::time::sleep(Duration::from_secs(10)).await;
+            let bytes = match sent {
+                0..=5 => ":\n\n".to_string(),
+                6 => format!("{FINAL_CHUNK}\n\n"),
+                _ => return None,
+            };
+            Some((Ok::<_, reqwest::Error>(Bytes::from(bytes)), sent + 1))
+        });
+
+        let stream = ChatStream::new(heartbeats).with_idle_timeout(Duration::from_secs(30));
+        let content = stream.collect_content().await.unwrap();
+
+        assert_eq!(content, "done");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_stalled_stream_reconnects_before_data() {
+        use futures::StreamExt;
+        use std::sync::atomic::{AtomicU32, Ordering};
+        use std::sync::Arc;
+
+        let attempts = Arc::new(AtomicU32::new(0));
+        let counter = Arc::clone(&attempts);
+        let silent = futures::stream::pending::<std::result::Result<Bytes, reqwest::Error>>();
+
+        let mut stream = ChatStream::new(silent)
+            .with_idle_timeout(Duration::from_secs(30))
+            .with_reconnect(1, move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+                async {
+                    Ok(futures::stream::iter(vec![Ok::<_, reqwest::Error>(Bytes::from(
+                        format!("{FINAL_CHUNK}\n\n"),
+                    ))]))
+                }
+            });
+
+        let chunk = stream.next().await.unwrap().unwrap();
+        assert_eq!(chunk.content(), "done");
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+
+    fn content_chunk(content: &str) -> String {
+        format!(
+            "data: {{\"id\":\"t\",\"object\":\"chat.completion.chunk\",\"created\":0,\"model\":\"m\",\"choices\":[{{\"index\":0,\"delta\":{{\"content\":\"{content}\"}}}}]}}\n\n"
+        )
+    }
+
+    #[tokio::test]
+    async fn test_stream_to_delivers_chunks_in_order() {
+        let body: Vec<std::result::Result<Bytes, reqwest::Error>> = ["a", "b", "c"]
+            .iter()
+            .map(|c| Ok(Bytes::from(content_chunk(c))))
+            .chain(std::iter::once(Ok(Bytes::from("data: [DONE]\n\n"))))
+            .collect();
+
+        let mut received = Vec::new();
+        ChatStream::new(futures::stream::iter(body))
+            .stream_to(|item| {
+                received.push(item.unwrap().content().to_string());
+                ControlFlow::Continue(())
+            })
+            .await;
+
+        assert_eq!(received, vec!["a", "b", "c"]);
+    }
+
+    #[tokio::test]
+    async fn test_stream_to_break_stops_and_aborts_upstream() {
+        use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+        use std::sync::Arc;
+
+        /// Flags when the upstream byte stream is dropped
+        struct Upstream(Arc<AtomicBool>);
+
+        impl Drop for Upstream {
+            fn drop(&mut self) {
+                self.0.store(true, Ordering::SeqCst);
+            }
+        }
+
+        let dropped = Arc::new(AtomicBool::new(false));
+        let sent = Arc::new(AtomicU32::new(0));
+        let upstream = Upstream(Arc::clone(&dropped));
+        let counter = Arc::clone(&sent);
+        let endless = futures::stream::unfold(upstream, move |upstream| {
+            let n = counter.fetch_add(1, Ordering::SeqCst);
+            async move {
+                Some((Ok::<_, reqwest::Error>(Bytes::from(content_chunk(&n.to_string()))), upstream))
+            }
+        });
+
+        let mut received = 0;
+        ChatStream::new(endless)
+            .stream_to(|item| {
+                assert!(item.is_ok());
+                received += 1;
+                if received == 2 {
+                    ControlFlow::Break(())
+                } else {
+                    ControlFlow::Continue(())
+                }
+            })
+            .await;
+
+        assert_eq!(received, 2);
+        assert_eq!(sent.load(Ordering::SeqCst), 2);
+        assert!(dropped.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_stream_to_delivers_errors() {
+        let silent = futures::stream::pending::<std::result::Result<Bytes, reqwest::Error>>();
+
+        let mut errors = Vec::new();
+        ChatStream::new(silent)
+            .with_idle_timeout(Duration::from_secs(5))
+            .stream_to(|item| {
+                if let Err(e) = item {
+                    errors.push(e);
+                }
+                ControlFlow::Continue(())
+            })
+            .await;
+
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0], Error::StreamStalled { .. }));
+    }
+
+    fn identified_chunk(id: u32, content: &str) -> String {
+        format!("id: {id}\n{}", content_chunk(content))
+    }
+
+    /// A transport error, as produced when a connection drops.
+    async fn transport_error() -> reqwest::Error {
+        reqwest::get("http://127.0.0.1:1").await.unwrap_err()
+    }
+
+    #[tokio::test]
+    async fn test_dropped_stream_resumes_from_last_event_id() {
+        use std::sync::{Arc, Mutex};
+
+        let dropped = transport_error().await;
+        let first = futures::stream::iter(vec![
+            Ok(Bytes::from(identified_chunk(1, "a"))),
+            Ok(Bytes::from(identified_chunk(2, "b"))),
+            Err(dropped),
+        ]);
+
+        let resumed_from = Arc::new(Mutex::new(Vec::new()));
+        let record = Arc::clone(&resumed_from);
+        let stream = ChatStream::new(first).with_resume(1, move |last_event_id| {
+            record.lock().unwrap().push(last_event_id);
+            // Replays event 2 before continuing
+            async {
+                Ok(futures::stream::iter(vec![
+                    Ok::<_, reqwest::Error>(Bytes::from(identified_chunk(2, "b"))),
+                    Ok(Bytes::from(identified_chunk(3, "c"))),
+                    Ok(Bytes::from(format!("id: 4\n{FINAL_CHUNK}\n\n"))),
+                ]))
+            }
+        });
+
+        let content = stream.collect_content().await.unwrap();
+
+        assert_eq!(content, "abcdone");
+        assert_eq!(*resumed_from.lock().unwrap(), vec![Some("2".to_string())]);
+    }
+
+    #[tokio::test]
+    async fn test_resume_skips_numeric_replay_from_start() {
+        let dropped = transport_error().await;
+        let first = futures::stream::iter(vec![
+            Ok(Bytes::from(identified_chunk(1, "a"))),
+            Ok(Bytes::from(identified_chunk(2, "b"))),
+            Err(dropped),
+        ]);
+
+        let stream = ChatStream::new(first).with_resume(1, |_| async {
+            Ok(futures::stream::iter(vec![
+                Ok::<_, reqwest::Error>(Bytes::from(identified_chunk(1, "a"))),
+                Ok(Bytes::from(identified_chunk(2, "b"))),
+                Ok(Bytes::from(identified_chunk(3, "c"))),
+                Ok(Bytes::from(format!("id: 4\n{FINAL_CHUNK}\n\n"))),
+            ]))
+        });
+
+        assert_eq!(stream.collect_content().await.unwrap(), "abcdone");
+    }
+
+    #[tokio::test]
+    async fn test_resume_continues_after_opaque_event_id() {
+        let dropped = transport_error().await;
+        let first = futures::stream::iter(vec![
+            Ok(Bytes::from(format!("id: evt-b\n{}", content_chunk("a")))),
+            Err(dropped),
+        ]);
+
+        let stream = ChatStream::new(first).with_resume(1, |_| async {
+            Ok(futures::stream::iter(vec![
+                Ok::<_, reqwest::Error>(Bytes::from(format!("id: evt-a\n{}", content_chunk("b")))),
+                Ok(Bytes::from(format!("id: evt-c\n{FINAL_CHUNK}\n\n"))),
+            ]))
+        });
+
+        assert_eq!(stream.collect_content().await.unwrap(), "abdone");
+    }
+
+    #[tokio::test]
+    async fn test_stream_without_event_ids_is_not_resumable() {
+        use futures::StreamExt;
+
+        let dropped = transport_error().await;
+        let first = futures::stream::iter(vec![
+            Ok(Bytes::from(content_chunk("a"))),
+            Err(dropped),
+        ]);
+
+        let mut stream = ChatStream::new(first).with_resume(3, |_| async {
+            Ok(futures::stream::iter(vec![Ok::<_, reqwest::Error>(Bytes::from(
+                content_chunk("a"),
+            ))]))
+        });
+
+        assert_eq!(stream.next().await.unwrap().unwrap().content(), "a");
+        assert!(matches!(
+            stream.next().await,
+            Some(Err(Error::StreamNotResumable { .. }))
+        ));
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_stalled_stream_resumes_until_attempts_spent() {
+        use futures::StreamExt;
+        use std::sync::atomic::{AtomicU32, Ordering};
+        use std::sync::Arc;
+
+        let attempts = Arc::new(AtomicU32::new(0));
+        let counter = Arc::clone(&attempts);
+        let stalls = || {
+            futures::stream::iter(vec![Ok::<_, reqwest::Error>(Bytes::from(identified_chunk(
+                1, "a",
+            )))])
+            .chain(futures::stream::pending())
+        };
+
+        let mut stream = ChatStream::new(stalls())
+            .with_idle_timeout(Duration::from_secs(30))
+            .with_resume(2, move |_| {
+                counter.fetch_add(1, Ordering::SeqCst);
+                async move { Ok(stalls()) }
+            });
+
+        assert_eq!(stream.next().await.unwrap().unwrap().content(), "a");
+        assert!(matches!(
+            stream.next().await,
+            Some(Err(Error::StreamStalled { .. }))
+        ));
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+        assert_eq!(stream.last_event_id(), Some("1"));
+    }
 }
@@ -10,6 +10,7 @@
 //! - **Request Signing**: HMAC-based request authentication
 //! - **IP Filtering**: Allow/deny lists and rate limiting by IP
 //! - **Content Security**: XSS and injection prevention
+//! - **Unicode Canonicalization**: Opt-in homoglyph and zero-width stripping
 //!
 //! ## Example
 //!
@@ -32,6 +33,7 @@ pub mod middleware;
 pub mod sanitize;
 pub mod secrets;
 pub mod signing;
+pub mod unicode;
 pub mod validation;
 
 pub use config::{SecurityConfig, SecurityConfigBuilder};
@@ -43,4 +45,5 @@ pub use middleware::SecurityLayer;
 pub use sanitize::{Sanitizer, SanitizeConfig};
 pub use secrets::{SecretStore, SecretValue};
 pub use signing::{RequestSigner, SignatureVerifier};
+pub use unicode::{Canonicalized, UnicodeCanonicalizer, UnicodeConfig};
 pub use validation::{InputValidator, ValidationResult};
@@ -2,6 +2,7 @@
 
 use crate::config::ContentSecurityConfig;
 use crate::error::{Result, SecurityError};
+use crate::unicode::{Canonicalized, UnicodeCanonicalizer, UnicodeConfig};
 use once_cell::sync::Lazy;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
@@ -96,6 +97,10 @@ pub struct SanitizeConfig {
     /// Allowed HTML tags (for partial sanitization).
     #[serde(default)]
     pub allowed_tags: Vec<String>,
+
+    /// Unicode canonicalization (opt-in).
+    #[serde(default)]
+    pub unicode: UnicodeConfig,
 }
 
 fn default_true() -> bool {
@@ -113,6 +118,7 @@ impl Default for SanitizeConfig {
             normalize_line_endings: false,
             max_length: 0,
             allowed_tags: Vec::new(),
+            unicode: UnicodeConfig::default(),
         }
     }
 }
@@ -156,11 +162,24 @@ impl Sanitizer {
         self
     }
 
+    /// Canonicalize Unicode in a string.
+    ///
+    /// Returns the input unchanged unless `unicode.enabled` is set.
+    #[must_use]
+    pub fn canonicalize(&self, input: &str) -> Canonicalized {
+        UnicodeCanonicalizer::new(self.config.unicode.clone()).canonicalize(input)
+    }
+
     /// Sanitize a string.
     #[must_use]
     pub fn sanitize(&self, input: &str) -> String {
         let mut result = input.to_string();
 
+        // Canonicalize Unicode before any pattern matching
+        if self.config.unicode.enabled {
+            result = self.canonicalize(&result).text;
+        }
+
         // Strip null bytes
         if self.config.strip_null_bytes {
             result = result.replace('\0', "");
@@ -418,6 +437,18 @@ mod tests {
         assert_eq!(encode_html("\"quoted\""), "&quot;quoted&quot;");
     }
 
+    #[test]
+    fn test_sanitize_canonicalizes_unicode_when_enabled() {
+        let input = "<b>ig\u{200B}nore</b>";
+        assert_eq!(Sanitizer::default_sanitizer().sanitize(input), "ig\u{200B}nore");
+
+        let sanitizer = Sanitizer::new(SanitizeConfig {
+            unicode: UnicodeConfig::enabled(),
+            ..Default::default()
+        });
+        assert_eq!(sanitizer.sanitize(input), "ignore");
+    }
+
     #[test]
     fn test_decode_html() {
         assert_eq!(decode_html("&lt;script&gt;"), "<script>");
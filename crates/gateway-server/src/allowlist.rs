@@ -0,0 +1,126 @@
+//! Per-tenant provider allowlists.
+//!
+//! Tenants under data-residency or contractual restrictions can be limited to
+//! specific providers. The allowlist comes from an `allowed_providers` claim
+//! on the caller (a JWT claim or API key metadata entry), or failing that
+//! from `routing.tenant_provider_allowlists` in the config. Routing then only
+//! considers the listed providers.
+
+use std::collections::HashMap;
+
+use crate::auth::AuthenticatedEntity;
+
+/// Claim (or API key metadata entry) listing the providers a caller may use
+///
+/// Either an array of provider IDs or a comma-separated string.
+pub const ALLOWED_PROVIDERS_CLAIM: &str = "allowed_providers";
+
+/// Providers the caller may be routed to, or `None` if unrestricted
+///
+/// The caller's claim takes precedence over the configured allowlist for
+/// its tenant. The tenant is taken from the authenticated entity when it
+/// has one, otherwise from `tenant_id`.
+#[must_use]
+pub fn allowed_providers(
+    entity: Option<&AuthenticatedEntity>,
+    tenant_id: Option<&str>,
+    configured: &HashMap<String, Vec<String>>,
+) -> Option<Vec<String>> {
+    let from_claim = entity
+        .and_then(|entity| entity.claims.get(ALLOWED_PROVIDERS_CLAIM))
+        .and_then(|value| match value {
+            serde_json::Value::Array(items) => Some(
+                items
+                    .iter()
+                    .filter_map(|item| item.as_str().map(str::to_string))
+                    .collect(),
+            ),
+            serde_json::Value::String(s) => Some(
+                s.split(',')
+                    .map(str::trim)
+                    .filter(|id| !id.is_empty())
+                    .map(str::to_string)
+                    .collect(),
+            ),
+            _ => None,
+        });
+    if from_claim.is_some() {
+        return from_claim;
+    }
+
+    entity
+        .and_then(|entity| entity.tenant_id.as_deref())
+        .or(tenant_id)
+        .and_then(|tenant| configured.get(tenant))
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::AuthMethod;
+
+    fn entity(
+        tenant_id: Option<&str>,
+        claims: HashMap<String, serde_json::Value>,
+    ) -> AuthenticatedEntity {
+        AuthenticatedEntity {
+            id: "user".to_string(),
+            tenant_id: tenant_id.map(str::to_string),
+            email: None,
+            name: None,
+            auth_method: AuthMethod::Jwt,
+            credential_id: None,
+            scopes: vec![],
+            expires_at: None,
+            claims,
+        }
+    }
+
+    fn configured() -> HashMap<String, Vec<String>> {
+        HashMap::from([("acme-eu".to_string(), vec!["azure-eu".to_string()])])
+    }
+
+    #[test]
+    fn test_claim_takes_precedence_over_config() {
+        let from_jwt = entity(
+            Some("acme-eu"),
+            HashMap::from([(
+                ALLOWED_PROVIDERS_CLAIM.to_string(),
+                serde_json::json!(["azure-eu", "azure-west"]),
+            )]),
+        );
+        let from_metadata = entity(
+            None,
+            HashMap::from([(
+                ALLOWED_PROVIDERS_CLAIM.to_string(),
+                serde_json::Value::String("azure-eu, bedrock-eu".to_string()),
+            )]),
+        );
+
+        assert_eq!(
+            allowed_providers(Some(&from_jwt), None, &configured()),
+            Some(vec!["azure-eu".to_string(), "azure-west".to_string()])
+        );
+        assert_eq!(
+            allowed_providers(Some(&from_metadata), None, &configured()),
+            Some(vec!["azure-eu".to_string(), "bedrock-eu".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_config_allowlist_by_tenant() {
+        let authenticated = entity(Some("acme-eu"), HashMap::new());
+
+        assert_eq!(
+            allowed_providers(Some(&authenticated), Some("other"), &configured()),
+            Some(vec!["azure-eu".to_string()])
+        );
+        assert_eq!(
+            allowed_providers(None, Some("acme-eu"), &configured()),
+            Some(vec!["azure-eu".to_string()])
+        );
+        assert_eq!(allowed_providers(None, Some("other"), &configured()), None);
+        assert_eq!(allowed_providers(None, None, &configured()), None);
+    }
+}
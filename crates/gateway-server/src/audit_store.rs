@@ -0,0 +1,830 @@
+//! Tamper-evident persistence of audit events.
+//!
+//! Events are appended to a hash chain: each record stores the hash of the
+//! record before it, and its own hash covers that link plus the serialized
+//! event, so editing or deleting a record breaks every later link. Retention
+//! purges from the old end of the chain (archiving to JSONL first when an
+//! archive directory is configured), which leaves the remaining records
+//! verifiable from the first one's `prev_hash`. Date-range exports carry the
+//! chain metadata needed to verify them offline.
+//!
+//! Records are ordered and filtered by the time they were persisted, which
+//! never goes backwards along the chain; the event's own timestamp is kept in
+//! the payload.
+
+use chrono::{DateTime, TimeZone, Utc};
+use gateway_config::PersistenceConfig;
+use gateway_migrations::{
+    sqlx::{self, Row},
+    DatabasePool, MigrationConfig, MigrationError, Result,
+};
+use gateway_telemetry::{AuditEvent, ContentLimit};
+use serde::Serialize;
+use std::fmt;
+use std::io::Write;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+/// Table holding persisted audit events
+const EVENTS_TABLE: &str = "gateway_audit_events";
+
+/// Single-row table tracking the end of the chain, so appends still link
+/// correctly after retention has purged every event
+const HEAD_TABLE: &str = "gateway_audit_chain_head";
+
+/// `prev_hash` of the first event ever appended
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// An audit event together with its position in the hash chain
+#[derive(Debug, Clone, Serialize)]
+pub struct ChainedAuditEvent {
+    /// Position in the chain (starts at 1)
+    pub seq: i64,
+    /// When the event was persisted
+    pub persisted_at: DateTime<Utc>,
+    /// Hash of the previous event in the chain
+    pub prev_hash: String,
+    /// Hash of this event, covering `prev_hash` and the serialized event
+    pub hash: String,
+    /// The audit event
+    pub event: AuditEvent,
+}
+
+/// File format for audit exports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditExportFormat {
+    /// A header line with the export summary, then one event per line
+    Jsonl,
+    /// A `#`-prefixed summary line, a header row, then one event per row
+    Csv,
+}
+
+impl fmt::Display for AuditExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Jsonl => write!(f, "jsonl"),
+            Self::Csv => write!(f, "csv"),
+        }
+    }
+}
+
+impl FromStr for AuditExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "jsonl" | "ndjson" => Ok(Self::Jsonl),
+            "csv" => Ok(Self::Csv),
+            other => Err(format!("unknown audit export format: {other}")),
+        }
+    }
+}
+
+/// Result of verifying a contiguous run of the hash chain
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AuditChainInfo {
+    /// Whether every link and hash in the run checked out
+    pub verified: bool,
+    /// `prev_hash` of the first event, anchoring the run to earlier history
+    pub anchor_prev_hash: Option<String>,
+    /// Hash of the last event in the run
+    pub head_hash: Option<String>,
+    /// Sequence number of the first event
+    pub first_seq: Option<i64>,
+    /// Sequence number of the last event
+    pub last_seq: Option<i64>,
+    /// Sequence number of the first event that failed verification
+    pub broken_at: Option<i64>,
+}
+
+/// Summary of an audit export, written into the export itself
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditExport {
+    /// Start of the range (inclusive)
+    pub from: DateTime<Utc>,
+    /// End of the range (exclusive)
+    pub to: DateTime<Utc>,
+    /// Export format
+    pub format: AuditExportFormat,
+    /// Number of events exported
+    pub event_count: usize,
+    /// Chain verification for the exported events
+    pub chain: AuditChainInfo,
+}
+
+/// A stored row, with the payload exactly as it was hashed
+struct StoredEvent {
+    seq: i64,
+    persisted_at: i64,
+    prev_hash: String,
+    hash: String,
+    payload: String,
+}
+
+impl StoredEvent {
+    fn into_chained(self) -> Result<ChainedAuditEvent> {
+        Ok(ChainedAuditEvent {
+            seq: self.seq,
+            persisted_at: millis_to_datetime(self.persisted_at),
+            prev_hash: self.prev_hash,
+            hash: self.hash,
+            event: serde_json::from_str(&self.payload)?,
+        })
+    }
+}
+
+/// Hash linking `payload` to the event before it
+#[must_use]
+pub fn chain_hash(prev_hash: &str, payload: &str) -> String {
+    use sha2::{Digest, Sha256};
+    let mut hasher = Sha256::new();
+    hasher.update(prev_hash.as_bytes());
+    hasher.update(b"\n");
+    hasher.update(payload.as_bytes());
+    crate::auth::hex::encode(hasher.finalize())
+}
+
+fn millis_to_datetime(millis: i64) -> DateTime<Utc> {
+    Utc.timestamp_millis_opt(millis)
+        .single()
+        .unwrap_or_default()
+}
+
+/// Store for tamper-evident audit events
+pub struct AuditStore {
+    pool: DatabasePool,
+    retention: Duration,
+    archive_dir: Option<PathBuf>,
+    content_limit: Option<ContentLimit>,
+    /// Serializes appends so each one links to the current head
+    append_lock: Mutex<()>,
+}
+
+impl AuditStore {
+    /// Connect to the configured database
+    ///
+    /// Returns `Ok(None)` when persistence is disabled.
+    ///
+    /// # Errors
+    /// Returns an error if persistence is enabled without a database URL or
+    /// the database cannot be reached
+    pub async fn connect(config: &PersistenceConfig) -> Result<Option<Self>> {
+        if !config.enabled {
+            return Ok(None);
+        }
+
+        let Some(url) = config.database_url.as_deref() else {
+            return Err(MigrationError::Config(
+                "persistence is enabled but no database_url is set".to_string(),
+            ));
+        };
+
+        let migration_config = MigrationConfig::builder()
+            .database_url(url)
+            .max_connections(config.max_connections)
+            .build()?;
+        let pool = DatabasePool::new(migration_config).await?;
+
+        Self::new(pool, config).await.map(Some)
+    }
+
+    /// Create a store on an existing pool, creating the tables if needed
+    ///
+    /// # Errors
+    /// Returns an error if the tables cannot be created
+    pub async fn new(pool: DatabasePool, config: &PersistenceConfig) -> Result<Self> {
+        let store = Self {
+            pool,
+            retention: config.audit_retention,
+            archive_dir: config.audit_archive_dir.as_ref().map(PathBuf::from),
+            content_limit: config.max_stored_content_length.map(ContentLimit::new),
+            append_lock: Mutex::new(()),
+        };
+        store.ensure_schema().await?;
+        Ok(store)
+    }
+
+    async fn ensure_schema(&self) -> Result<()> {
+        sqlx::query(&format!(
+            "CREATE TABLE IF NOT EXISTS {EVENTS_TABLE} (
+                seq BIGINT PRIMARY KEY,
+                event_id TEXT NOT NULL,
+                event_type TEXT NOT NULL,
+                tenant_id TEXT,
+                persisted_at BIGINT NOT NULL,
+                payload TEXT NOT NULL,
+                prev_hash TEXT NOT NULL,
+                hash TEXT NOT NULL
+            )"
+        ))
+        .execute(self.pool.inner())
+        .await?;
+
+        sqlx::query(&format!(
+            "CREATE INDEX IF NOT EXISTS idx_{EVENTS_TABLE}_persisted_at ON {EVENTS_TABLE}(persisted_at)"
+        ))
+        .execute(self.pool.inner())
+        .await?;
+
+        sqlx::query(&format!(
+            "CREATE TABLE IF NOT EXISTS {HEAD_TABLE} (
+                id BIGINT PRIMARY KEY,
+                seq BIGINT NOT NULL,
+                hash TEXT NOT NULL,
+                persisted_at BIGINT NOT NULL
+            )"
+        ))
+        .execute(self.pool.inner())
+        .await?;
+
+        let heads: i64 = sqlx::query(&format!("SELECT COUNT(*) AS n FROM {HEAD_TABLE}"))
+            .fetch_one(self.pool.inner())
+            .await?
+            .try_get("n")?;
+        if heads == 0 {
+            sqlx::query(&format!(
+                "INSERT INTO {HEAD_TABLE} (id, seq, hash, persisted_at) VALUES (1, 0, $1, 0)"
+            ))
+            .bind(GENESIS_HASH)
+            .execute(self.pool.inner())
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Retention TTL applied by [`purge_expired`](Self::purge_expired)
+    #[must_use]
+    pub fn retention(&self) -> Duration {
+        self.retention
+    }
+
+    /// Append an event to the chain
+    ///
+    /// Long content is truncated first when `max_stored_content_length` is
+    /// set, so the chain hash covers the stored form.
+    ///
+    /// # Errors
+    /// Returns an error if the event cannot be serialized or the insert fails
+    pub async fn append(&self, event: &AuditEvent) -> Result<ChainedAuditEvent> {
+        let mut event = event.clone();
+        if let Some(limit) = &self.content_limit {
+            event.truncate_content(limit);
+        }
+        let _guard = self.append_lock.lock().await;
+        let payload = serde_json::to_string(&event)?;
+
+        let mut tx = self.pool.inner().begin().await?;
+        let head = sqlx::query(&format!(
+            "SELECT seq, hash, persisted_at FROM {HEAD_TABLE} WHERE id = 1"
+        ))
+        .fetch_one(&mut *tx)
+        .await?;
+        let head_seq: i64 = head.try_get("seq")?;
+        let prev_hash: String = head.try_get("hash")?;
+        let head_persisted_at: i64 = head.try_get("persisted_at")?;
+
+        // Never step backwards, so time ranges always cover contiguous runs
+        let persisted_at = Utc::now().timestamp_millis().max(head_persisted_at);
+        let seq = head_seq + 1;
+        let hash = chain_hash(&prev_hash, &payload);
+
+        sqlx::query(&format!(
+            "INSERT INTO {EVENTS_TABLE}
+                (seq, event_id, event_type, tenant_id, persisted_at, payload, prev_hash, hash)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)"
+        ))
+        .bind(seq)
+        .bind(&event.id)
+        .bind(event.event_type.to_string())
+        .bind(event.tenant_id.as_deref())
+        .bind(persisted_at)
+        .bind(&payload)
+        .bind(&prev_hash)
+        .bind(&hash)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(&format!(
+            "UPDATE {HEAD_TABLE} SET seq = $1, hash = $2, persisted_at = $3 WHERE id = 1"
+        ))
+        .bind(seq)
+        .bind(&hash)
+        .bind(persisted_at)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(ChainedAuditEvent {
+            seq,
+            persisted_at: millis_to_datetime(persisted_at),
+            prev_hash,
+            hash,
+            event,
+        })
+    }
+
+    async fn stored_between(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<StoredEvent>> {
+        let rows = sqlx::query(&format!(
+            "SELECT seq, persisted_at, payload, prev_hash, hash FROM {EVENTS_TABLE}
+             WHERE persisted_at >= $1 AND persisted_at < $2
+             ORDER BY seq"
+        ))
+        .bind(from.timestamp_millis())
+        .bind(to.timestamp_millis())
+        .fetch_all(self.pool.inner())
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(StoredEvent {
+                    seq: row.try_get("seq")?,
+                    persisted_at: row.try_get("persisted_at")?,
+                    payload: row.try_get("payload")?,
+                    prev_hash: row.try_get("prev_hash")?,
+                    hash: row.try_get("hash")?,
+                })
+            })
+            .collect()
+    }
+
+    /// Events persisted in `[from, to)`, in chain order
+    ///
+    /// # Errors
+    /// Returns an error if the query fails or a payload cannot be decoded
+    pub async fn events_between(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<ChainedAuditEvent>> {
+        self.stored_between(from, to)
+            .await?
+            .into_iter()
+            .map(StoredEvent::into_chained)
+            .collect()
+    }
+
+    /// Verify the chain over events persisted in `[from, to)`
+    ///
+    /// # Errors
+    /// Returns an error if the query fails
+    pub async fn verify_range(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<AuditChainInfo> {
+        Ok(verify_chain(&self.stored_between(from, to).await?))
+    }
+
+    /// Write events persisted in `[from, to)` to `out`
+    ///
+    /// The chain is verified as the events are read, and the result is
+    /// written at the top of the export alongside each event's hashes.
+    ///
+    /// # Errors
+    /// Returns an error if the query or a write fails
+    pub async fn export<W: Write>(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        format: AuditExportFormat,
+        out: &mut W,
+    ) -> Result<AuditExport> {
+        let stored = self.stored_between(from, to).await?;
+        let export = AuditExport {
+            from,
+            to,
+            format,
+            event_count: stored.len(),
+            chain: verify_chain(&stored),
+        };
+
+        match format {
+            AuditExportFormat::Jsonl => {
+                serde_json::to_writer(&mut *out, &serde_json::json!({ "export": &export }))?;
+                out.write_all(b"\n")?;
+                for row in stored {
+                    serde_json::to_writer(&mut *out, &row.into_chained()?)?;
+                    out.write_all(b"\n")?;
+                }
+            }
+            AuditExportFormat::Csv => {
+                writeln!(out, "# {}", serde_json::to_string(&export)?)?;
+                writeln!(
+                    out,
+                    "seq,persisted_at,event_id,timestamp,event_type,severity,outcome,tenant_id,description,prev_hash,hash,event"
+                )?;
+                for row in stored {
+                    let payload = row.payload.clone();
+                    let chained = row.into_chained()?;
+                    let event = &chained.event;
+                    let fields = [
+                        chained.seq.to_string(),
+                        chained.persisted_at.to_rfc3339(),
+                        event.id.clone(),
+                        event.timestamp.to_rfc3339(),
+                        event.event_type.to_string(),
+                        event.severity.to_string(),
+                        event.outcome.to_string(),
+                        event.tenant_id.clone().unwrap_or_default(),
+                        event.description.clone(),
+                        chained.prev_hash.clone(),
+                        chained.hash.clone(),
+                        payload,
+                    ];
+                    let line: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+                    writeln!(out, "{}", line.join(","))?;
+                }
+            }
+        }
+        out.flush()?;
+
+        Ok(export)
+    }
+
+    /// Purge events older than the retention TTL
+    ///
+    /// Returns the number of events removed.
+    ///
+    /// # Errors
+    /// Returns an error if archiving or the delete fails
+    pub async fn purge_expired(&self) -> Result<u64> {
+        let retention = chrono::Duration::from_std(self.retention)
+            .unwrap_or(chrono::Duration::MAX);
+        let cutoff = Utc::now()
+            .checked_sub_signed(retention)
+            .unwrap_or(DateTime::<Utc>::MIN_UTC);
+        self.purge_before(cutoff).await
+    }
+
+    /// Purge events persisted before `cutoff`
+    ///
+    /// When an archive directory is configured the events are first exported
+    /// there as JSONL, so nothing is deleted unless the archive was written.
+    /// Returns the number of events removed.
+    ///
+    /// # Errors
+    /// Returns an error if archiving or the delete fails
+    pub async fn purge_before(&self, cutoff: DateTime<Utc>) -> Result<u64> {
+        if let Some(dir) = &self.archive_dir {
+            let stored = self
+                .stored_between(DateTime::<Utc>::MIN_UTC, cutoff)
+                .await?;
+            if let (Some(first), Some(last)) = (stored.first(), stored.last()) {
+                std::fs::create_dir_all(dir)?;
+                let path = dir.join(format!("audit-{}-{}.jsonl", first.seq, last.seq));
+                let mut file = std::io::BufWriter::new(std::fs::File::create(&path)?);
+                let export = self
+                    .export(
+                        DateTime::<Utc>::MIN_UTC,
+                        cutoff,
+                        AuditExportFormat::Jsonl,
+                        &mut file,
+                    )
+                    .await?;
+                info!(
+                    path = %path.display(),
+                    events = export.event_count,
+                    verified = export.chain.verified,
+                    "Archived expired audit events"
+                );
+            }
+        }
+
+        let result = sqlx::query(&format!(
+            "DELETE FROM {EVENTS_TABLE} WHERE persisted_at < $1"
+        ))
+        .bind(cutoff.timestamp_millis())
+        .execute(self.pool.inner())
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Spawn a background task that purges expired events on `interval`
+    #[must_use]
+    pub fn spawn_retention(self: Arc<Self>, interval: Duration) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                match self.purge_expired().await {
+                    Ok(0) => {}
+                    Ok(removed) => debug!(removed, "Purged expired audit events"),
+                    Err(e) => warn!(error = %e, "Audit retention purge failed"),
+                }
+            }
+        })
+    }
+}
+
+/// Check every link and hash in a contiguous run of stored events
+fn verify_chain(stored: &[StoredEvent]) -> AuditChainInfo {
+    let mut broken_at = None;
+    let mut expected_prev = stored.first().map(|row| row.prev_hash.as_str());
+
+    for row in stored {
+        let linked = expected_prev == Some(row.prev_hash.as_str());
+        if !linked || chain_hash(&row.prev_hash, &row.payload) != row.hash {
+            broken_at = Some(row.seq);
+            break;
+        }
+        expected_prev = Some(row.hash.as_str());
+    }
+
+    AuditChainInfo {
+        verified: broken_at.is_none(),
+        anchor_prev_hash: stored.first().map(|row| row.prev_hash.clone()),
+        head_hash: stored.last().map(|row| row.hash.clone()),
+        first_seq: stored.first().map(|row| row.seq),
+        last_seq: stored.last().map(|row| row.seq),
+        broken_at,
+    }
+}
+
+/// Quote a CSV field when it contains a separator, quote or newline
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gateway_telemetry::{AuditEventBuilder, AuditEventType, AuditOutcome};
+
+    async fn sqlite_store(config: &PersistenceConfig) -> AuditStore {
+        // A single connection keeps every query on the same in-memory database
+        let migration_config = MigrationConfig::builder()
+            .database_url("sqlite::memory:")
+            .max_connections(1)
+            .build()
+            .expect("valid config");
+        let pool = DatabasePool::new(migration_config).await.expect("pool");
+        AuditStore::new(pool, config).await.expect("store")
+    }
+
+    fn event(description: &str) -> AuditEvent {
+        AuditEventBuilder::new(AuditEventType::ConfigChange)
+            .description(description)
+            .outcome(AuditOutcome::Success)
+            .tenant_id("acme")
+            .build()
+    }
+
+    /// Backdate stored events so they fall outside the retention window
+    async fn set_persisted_at(store: &AuditStore, seq: i64, at: DateTime<Utc>) {
+        sqlx::query(&format!(
+            "UPDATE {EVENTS_TABLE} SET persisted_at = $1 WHERE seq = $2"
+        ))
+        .bind(at.timestamp_millis())
+        .bind(seq)
+        .execute(store.pool.inner())
+        .await
+        .expect("update");
+    }
+
+    #[tokio::test]
+    async fn test_append_links_events() {
+        let store = sqlite_store(&PersistenceConfig::default()).await;
+
+        let first = store.append(&event("one")).await.expect("append");
+        let second = store.append(&event("two")).await.expect("append");
+
+        assert_eq!(first.seq, 1);
+        assert_eq!(first.prev_hash, GENESIS_HASH);
+        assert_eq!(second.seq, 2);
+        assert_eq!(second.prev_hash, first.hash);
+
+        let info = store
+            .verify_range(
+                DateTime::<Utc>::MIN_UTC,
+                Utc::now() + chrono::Duration::hours(1),
+            )
+            .await
+            .expect("verify");
+        assert!(info.verified);
+        assert_eq!(info.head_hash, Some(second.hash));
+    }
+
+    #[tokio::test]
+    async fn test_long_content_truncated_before_hashing() {
+        let config = PersistenceConfig {
+            max_stored_content_length: Some(4),
+            ..PersistenceConfig::default()
+        };
+        let store = sqlite_store(&config).await;
+        let long = AuditEventBuilder::new(AuditEventType::ResponseSent)
+            .description("response sent")
+            .metadata("content", "y".repeat(64))
+            .build();
+
+        let chained = store.append(&long).await.expect("append");
+        assert_eq!(chained.event.description, "resp...[truncated from 13 bytes]");
+        assert_eq!(chained.event.metadata["content"], "yyyy...[truncated from 64 bytes]");
+
+        let to = Utc::now() + chrono::Duration::hours(1);
+        let stored = store.events_between(DateTime::<Utc>::MIN_UTC, to).await.expect("events");
+        assert_eq!(stored[0].event.metadata["content"], "yyyy...[truncated from 64 bytes]");
+        assert!(store.verify_range(DateTime::<Utc>::MIN_UTC, to).await.expect("verify").verified);
+    }
+
+    #[tokio::test]
+    async fn test_tampering_breaks_chain() {
+        let store = sqlite_store(&PersistenceConfig::default()).await;
+        for i in 0..3 {
+            store
+                .append(&event(&format!("event {i}")))
+                .await
+                .expect("append");
+        }
+
+        sqlx::query(&format!(
+            "UPDATE {EVENTS_TABLE} SET payload = REPLACE(payload, 'event 1', 'edited') WHERE seq = 2"
+        ))
+        .execute(store.pool.inner())
+        .await
+        .expect("tamper");
+
+        let info = store
+            .verify_range(
+                DateTime::<Utc>::MIN_UTC,
+                Utc::now() + chrono::Duration::hours(1),
+            )
+            .await
+            .expect("verify");
+        assert!(!info.verified);
+        assert_eq!(info.broken_at, Some(2));
+    }
+
+    #[tokio::test]
+    async fn test_purge_removes_events_past_ttl() {
+        let config = PersistenceConfig {
+            audit_retention: Duration::from_secs(24 * 60 * 60),
+            ..PersistenceConfig::default()
+        };
+        let store = sqlite_store(&config).await;
+        for i in 0..4 {
+            store
+                .append(&event(&format!("event {i}")))
+                .await
+                .expect("append");
+        }
+        let old = Utc::now() - chrono::Duration::days(3);
+        set_persisted_at(&store, 1, old).await;
+        set_persisted_at(&store, 2, old).await;
+
+        assert_eq!(store.purge_expired().await.expect("purge"), 2);
+
+        let remaining = store
+            .events_between(
+                DateTime::<Utc>::MIN_UTC,
+                Utc::now() + chrono::Duration::hours(1),
+            )
+            .await
+            .expect("events");
+        let seqs: Vec<i64> = remaining.iter().map(|e| e.seq).collect();
+        assert_eq!(seqs, vec![3, 4]);
+
+        // The surviving tail still verifies, and new events keep linking to it
+        let appended = store.append(&event("after purge")).await.expect("append");
+        assert_eq!(appended.seq, 5);
+        let info = store
+            .verify_range(
+                DateTime::<Utc>::MIN_UTC,
+                Utc::now() + chrono::Duration::hours(1),
+            )
+            .await
+            .expect("verify");
+        assert!(info.verified);
+        assert_eq!(info.first_seq, Some(3));
+    }
+
+    #[tokio::test]
+    async fn test_purge_archives_before_deleting() {
+        let dir = std::env::temp_dir().join(format!("audit-archive-{}", uuid::Uuid::new_v4()));
+        let config = PersistenceConfig {
+            audit_retention: Duration::from_secs(60 * 60),
+            audit_archive_dir: Some(dir.to_string_lossy().into_owned()),
+            ..PersistenceConfig::default()
+        };
+        let store = sqlite_store(&config).await;
+        store.append(&event("old")).await.expect("append");
+        store.append(&event("new")).await.expect("append");
+        set_persisted_at(&store, 1, Utc::now() - chrono::Duration::hours(2)).await;
+
+        assert_eq!(store.purge_expired().await.expect("purge"), 1);
+
+        let archived = std::fs::read_to_string(dir.join("audit-1-1.jsonl")).expect("archive");
+        let lines: Vec<serde_json::Value> = archived
+            .lines()
+            .map(|l| serde_json::from_str(l).expect("json"))
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["export"]["chain"]["verified"], true);
+        assert_eq!(lines[1]["event"]["description"], "old");
+
+        std::fs::remove_dir_all(dir).expect("cleanup");
+    }
+
+    #[tokio::test]
+    async fn test_export_range_contains_expected_events() {
+        let store = sqlite_store(&PersistenceConfig::default()).await;
+        let mut appended = Vec::new();
+        for i in 0..5 {
+            appended.push(
+                store
+                    .append(&event(&format!("event {i}")))
+                    .await
+                    .expect("append"),
+            );
+        }
+        let base = Utc::now() - chrono::Duration::days(10);
+        for (day, chained) in (0..).zip(&appended) {
+            set_persisted_at(&store, chained.seq, base + chrono::Duration::days(day)).await;
+        }
+
+        // Days 1..=3 of the five
+        let from = base + chrono::Duration::days(1);
+        let to = base + chrono::Duration::days(4);
+
+        let mut jsonl = Vec::new();
+        let export = store
+            .export(from, to, AuditExportFormat::Jsonl, &mut jsonl)
+            .await
+            .expect("export");
+        assert_eq!(export.event_count, 3);
+        assert!(export.chain.verified);
+        assert_eq!(export.chain.first_seq, Some(2));
+        assert_eq!(export.chain.last_seq, Some(4));
+        assert_eq!(
+            export.chain.anchor_prev_hash.as_deref(),
+            Some(appended[0].hash.as_str())
+        );
+        assert_eq!(
+            export.chain.head_hash.as_deref(),
+            Some(appended[3].hash.as_str())
+        );
+
+        let lines: Vec<serde_json::Value> = String::from_utf8(jsonl)
+            .expect("utf8")
+            .lines()
+            .map(|l| serde_json::from_str(l).expect("json"))
+            .collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0]["export"]["event_count"], 3);
+        assert_eq!(lines[0]["export"]["chain"]["verified"], true);
+        let ids: Vec<&str> = lines[1..]
+            .iter()
+            .map(|l| l["event"]["id"].as_str().expect("id"))
+            .collect();
+        let expected: Vec<&str> = appended[1..4].iter().map(|c| c.event.id.as_str()).collect();
+        assert_eq!(ids, expected);
+        for (line, chained) in lines[1..].iter().zip(&appended[1..4]) {
+            assert_eq!(line["prev_hash"], chained.prev_hash.as_str());
+            assert_eq!(line["hash"], chained.hash.as_str());
+        }
+
+        let mut csv = Vec::new();
+        store
+            .export(from, to, AuditExportFormat::Csv, &mut csv)
+            .await
+            .expect("export");
+        let csv = String::from_utf8(csv).expect("utf8");
+        let rows: Vec<&str> = csv.lines().collect();
+        assert!(rows[0].starts_with("# {"));
+        assert!(rows[1].starts_with("seq,persisted_at,event_id"));
+        assert_eq!(rows.len(), 5);
+        assert!(rows[2].starts_with("2,"));
+        assert!(rows[2].contains(&appended[1].event.id));
+        assert!(rows[2].contains(&appended[1].hash));
+    }
+
+    #[test]
+    fn test_csv_field_quoting() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn test_export_format_parse() {
+        assert_eq!("jsonl".parse(), Ok(AuditExportFormat::Jsonl));
+        assert_eq!("CSV".parse(), Ok(AuditExportFormat::Csv));
+        assert!("xml".parse::<AuditExportFormat>().is_err());
+    }
+}
@@ -12,6 +12,10 @@
 //!
 //! - **JWT Validation**: Validates JWT tokens with configurable claims
 //! - **JWKS Caching**: Automatic key rotation with configurable refresh
+//! - **Lazy Key Loading**: Optionally defer JWKS fetching to first use so the
+//!   server can start while the identity provider is unreachable
+//! - **Background Refresh**: Optionally re-fetch JWKS ahead of expiry,
+//!   keeping the last-known-good keys if the identity provider fails
 //! - **Multiple Issuers**: Support for multiple OIDC providers
 //! - **Custom Claims**: Extract custom claims for tenant isolation
 //! - **Flexible Auth**: Support both JWT and API key authentication
@@ -31,13 +35,14 @@
 
 use axum::{
     extract::{Request, State},
-    http::{header, StatusCode},
+    http::{header, HeaderMap, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
 };
 use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
 use chrono::{DateTime, Utc};
 use dashmap::DashMap;
+use gateway_integrations::{WebhookEmitter, WebhookEvent, WebhookEventType};
 use jsonwebtoken::{
     decode, decode_header, jwk::JwkSet, Algorithm, DecodingKey, TokenData, Validation,
 };
@@ -45,7 +50,11 @@ use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::{
     collections::{HashMap, HashSet},
-    sync::Arc,
+    future::Future,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
     time::{Duration, Instant},
 };
 use tracing::{debug, error, info, warn};
@@ -218,6 +227,13 @@ pub struct JwtConfig {
     pub leeway: Duration,
     /// JWKS cache TTL
     pub jwks_cache_ttl: Duration,
+    /// Defer key fetching to first use instead of failing startup
+    pub lazy_jwks: bool,
+    /// Initial delay between key fetch retries in lazy mode (doubles up to
+    /// a minute)
+    pub jwks_retry_interval: Duration,
+    /// Refresh keys in the background ahead of the cache TTL
+    pub background_refresh: bool,
 }
 
 impl JwtConfig {
@@ -236,6 +252,9 @@ impl JwtConfig {
             algorithms: vec![Algorithm::RS256, Algorithm::RS384, Algorithm::RS512],
             leeway: Duration::from_secs(60),
             jwks_cache_ttl: Duration::from_secs(3600),
+            lazy_jwks: false,
+            jwks_retry_interval: Duration::from_secs(1),
+            background_refresh: false,
         }
     }
 
@@ -250,6 +269,9 @@ impl JwtConfig {
             algorithms: vec![Algorithm::RS256, Algorithm::RS384, Algorithm::RS512],
             leeway: Duration::from_secs(60),
             jwks_cache_ttl: Duration::from_secs(3600),
+            lazy_jwks: false,
+            jwks_retry_interval: Duration::from_secs(1),
+            background_refresh: false,
         }
     }
 
@@ -264,6 +286,9 @@ impl JwtConfig {
             algorithms: vec![Algorithm::HS256, Algorithm::HS384, Algorithm::HS512],
             leeway: Duration::from_secs(60),
             jwks_cache_ttl: Duration::from_secs(0), // Not used for secrets
+            lazy_jwks: false,
+            jwks_retry_interval: Duration::from_secs(1),
+            background_refresh: false,
         }
     }
 
@@ -276,6 +301,9 @@ impl JwtConfig {
             algorithms: vec![Algorithm::RS256, Algorithm::RS384, Algorithm::RS512],
             leeway: Duration::from_secs(60),
             jwks_cache_ttl: Duration::from_secs(0), // Not used for static keys
+            lazy_jwks: false,
+            jwks_retry_interval: Duration::from_secs(1),
+            background_refresh: false,
         }
     }
 
@@ -308,6 +336,40 @@ impl JwtConfig {
         self.jwks_cache_ttl = ttl;
         self
     }
+
+    /// Defer JWKS/OIDC fetching to first use
+    ///
+    /// Startup no longer fails when the identity provider is unreachable.
+    /// Until keys load, JWT requests are rejected with 503
+    /// `auth_unavailable` while fetching is retried in the background.
+    pub fn with_lazy_jwks(mut self, lazy: bool) -> Self {
+        self.lazy_jwks = lazy;
+        self
+    }
+
+    /// Set the initial delay between key fetch retries in lazy mode
+    pub fn with_jwks_retry_interval(mut self, interval: Duration) -> Self {
+        self.jwks_retry_interval = interval;
+        self
+    }
+
+    /// Refresh JWKS in the background instead of on the first request
+    /// after the cache TTL expires
+    ///
+    /// Keys are re-fetched at 80% of the TTL. A failed refresh keeps the
+    /// last key set that loaded, so requests never wait on the identity
+    /// provider once keys are cached.
+    pub fn with_background_refresh(mut self, enabled: bool) -> Self {
+        self.background_refresh = enabled;
+        self
+    }
+
+    /// Whether a background task keeps this mode's keys fresh
+    fn refreshes_in_background(&self) -> bool {
+        self.background_refresh
+            && !self.jwks_cache_ttl.is_zero()
+            && matches!(self.mode, JwtMode::Oidc { .. } | JwtMode::Jwks { .. })
+    }
 }
 
 /// JWT validation mode
@@ -335,6 +397,9 @@ pub enum JwtMode {
     },
 }
 
+/// Response header giving the seconds left before the API key expires
+pub const EXPIRES_IN_HEADER: &str = "x-api-key-expires-in";
+
 /// API key configuration
 #[derive(Debug, Clone)]
 pub struct ApiKeyConfig {
@@ -346,6 +411,9 @@ pub struct ApiKeyConfig {
     pub keys: HashMap<String, ApiKeyMetadata>,
     /// Enable hashed key storage
     pub hash_keys: bool,
+    /// Window before a key's expiry in which responses carry
+    /// `X-API-Key-Expires-In` (disabled if `None`)
+    pub expiry_warning: Option<Duration>,
 }
 
 impl Default for ApiKeyConfig {
@@ -355,6 +423,7 @@ impl Default for ApiKeyConfig {
             query_param: None,
             keys: HashMap::new(),
             hash_keys: false,
+            expiry_warning: None,
         }
     }
 }
@@ -392,6 +461,24 @@ impl ApiKeyConfig {
         self.hash_keys = enabled;
         self
     }
+
+    /// Warn clients once their key expires within `window`
+    pub fn with_expiry_warning(mut self, window: Duration) -> Self {
+        self.expiry_warning = Some(window);
+        self
+    }
+
+    /// Time left on a key expiring at `expires_at`, if within the warning
+    /// window
+    pub fn expires_within_warning(
+        &self,
+        expires_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Option<Duration> {
+        let window = self.expiry_warning?;
+        let remaining = (expires_at - now).to_std().ok()?;
+        (remaining <= window).then_some(remaining)
+    }
 }
 
 /// API key metadata
@@ -447,6 +534,24 @@ impl ApiKeyMetadata {
         self.expires_at = Some(expires_at);
         self
     }
+
+    /// Restrict this key to the given provider IDs
+    pub fn with_allowed_providers(mut self, providers: &[&str]) -> Self {
+        self.metadata.insert(
+            crate::allowlist::ALLOWED_PROVIDERS_CLAIM.to_string(),
+            providers.join(","),
+        );
+        self
+    }
+
+    /// Override the per-tenant cap on concurrent streams for this key
+    pub fn with_max_concurrent_streams(mut self, max: u32) -> Self {
+        self.metadata.insert(
+            crate::streams::MAX_CONCURRENT_STREAMS_CLAIM.to_string(),
+            max.to_string(),
+        );
+        self
+    }
 }
 
 /// Hash an API key for secure storage
@@ -457,8 +562,8 @@ fn hash_api_key(key: &str) -> String {
     hex::encode(hasher.finalize())
 }
 
-/// Hex encoding for API key hashing
-mod hex {
+/// Hex encoding for API key and audit chain hashes
+pub(crate) mod hex {
     pub fn encode(bytes: impl AsRef<[u8]>) -> String {
         bytes
             .as_ref()
@@ -481,6 +586,9 @@ pub struct AuthenticatedEntity {
     pub name: Option<String>,
     /// Authentication method used
     pub auth_method: AuthMethod,
+    /// Fingerprint of the credential itself, for API keys
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credential_id: Option<String>,
     /// Scopes/permissions
     pub scopes: Vec<String>,
     /// Token expiration (if JWT)
@@ -489,6 +597,15 @@ pub struct AuthenticatedEntity {
     pub claims: HashMap<String, serde_json::Value>,
 }
 
+impl AuthenticatedEntity {
+    /// Identity of the credential the request was made with: the API key
+    /// fingerprint, or the subject for tokens
+    #[must_use]
+    pub fn principal(&self) -> &str {
+        self.credential_id.as_deref().unwrap_or(&self.id)
+    }
+}
+
 /// Authentication method
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -587,6 +704,12 @@ struct OidcDiscovery {
     id_token_signing_alg_values_supported: Vec<String>,
 }
 
+/// Upper bound on the delay between lazy key fetch retries
+const MAX_JWKS_RETRY_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Fraction of the JWKS cache TTL after which background refresh runs
+const BACKGROUND_REFRESH_FRACTION: f64 = 0.8;
+
 /// Cached JWKS
 struct CachedJwks {
     jwks: JwkSet,
@@ -601,6 +724,51 @@ pub struct AuthState {
     jwks_cache: Arc<DashMap<String, CachedJwks>>,
     oidc_cache: Arc<DashMap<String, OidcDiscovery>>,
     static_key: Option<DecodingKey>,
+    /// Set while a background key fetch retry loop is running
+    key_retry_running: Arc<AtomicBool>,
+    /// Raises `key.expiring` webhooks, when configured
+    key_expiry: Option<Arc<KeyExpiryNotifier>>,
+    /// Background key refresh, aborted once the last clone is dropped
+    refresh_task: Option<Arc<RefreshTask>>,
+}
+
+/// Aborts the background key refresh task when dropped
+///
+/// The task runs on a clone of the state taken before this handle is
+/// attached, so it never keeps itself alive.
+struct RefreshTask(tokio::task::AbortHandle);
+
+impl Drop for RefreshTask {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// Sends one `key.expiring` event per key and expiry time
+struct KeyExpiryNotifier {
+    emitter: Arc<WebhookEmitter>,
+    /// Expiry already notified, by key ID
+    notified: DashMap<String, DateTime<Utc>>,
+}
+
+impl KeyExpiryNotifier {
+    fn notify(&self, entity: &AuthenticatedEntity, expires_at: DateTime<Utc>, expires_in: Duration) {
+        if !self.emitter.is_subscribed(WebhookEventType::KeyExpiring) {
+            return;
+        }
+        if self.notified.insert(entity.id.clone(), expires_at) == Some(expires_at) {
+            return;
+        }
+        self.emitter.emit(&WebhookEvent::new(
+            WebhookEventType::KeyExpiring,
+            serde_json::json!({
+                "key_id": entity.id,
+                "tenant_id": entity.tenant_id,
+                "expires_at": expires_at,
+                "expires_in_secs": expires_in.as_secs(),
+            }),
+        ));
+    }
 }
 
 impl AuthState {
@@ -628,28 +796,156 @@ impl AuthState {
             None
         };
 
-        let state = Self {
+        let mut state = Self {
             config: Arc::new(config),
             http_client,
             jwks_cache: Arc::new(DashMap::new()),
             oidc_cache: Arc::new(DashMap::new()),
             static_key,
+            key_retry_running: Arc::new(AtomicBool::new(false)),
+            key_expiry: None,
+            refresh_task: None,
+        };
+
+        // Pre-fetch JWKS if using OIDC/JWKS mode, unless deferred to first use
+        if state.config.jwt.as_ref().is_some_and(|jwt| !jwt.lazy_jwks) {
+            state.prefetch_keys().await?;
+        }
+        state.spawn_background_refresh();
+
+        Ok(state)
+    }
+
+    /// Keep keys fresh in the background, when enabled for a JWKS/OIDC mode
+    fn spawn_background_refresh(&mut self) {
+        let Some(jwt_config) = self
+            .config
+            .jwt
+            .as_ref()
+            .filter(|jwt| jwt.refreshes_in_background())
+        else {
+            return;
         };
 
-        // Pre-fetch JWKS if using OIDC/JWKS mode
-        if let Some(jwt_config) = &state.config.jwt {
+        let period = jwt_config.jwks_cache_ttl.mul_f64(BACKGROUND_REFRESH_FRACTION);
+        let state = self.clone();
+        let task = tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(period).await;
+                match state.refresh_keys().await {
+                    Ok(()) => debug!("Refreshed signing keys in the background"),
+                    Err(e) => warn!(
+                        error = %e,
+                        "Background signing key refresh failed, keeping last-known-good keys"
+                    ),
+                }
+            }
+        });
+        self.refresh_task = Some(Arc::new(RefreshTask(task.abort_handle())));
+    }
+
+    /// Re-fetch the JWKS for the configured mode, bypassing the cache
+    async fn refresh_keys(&self) -> Result<(), AuthError> {
+        if let Some(jwt_config) = &self.config.jwt {
             match &jwt_config.mode {
                 JwtMode::Oidc { discovery_url } => {
-                    state.fetch_oidc_config(discovery_url).await?;
+                    let discovery = self.fetch_oidc_config(discovery_url).await?;
+                    self.load_jwks(&discovery.jwks_uri).await?;
                 }
                 JwtMode::Jwks { url } => {
-                    state.fetch_jwks(url).await?;
+                    self.load_jwks(url).await?;
                 }
                 _ => {}
             }
         }
+        Ok(())
+    }
 
-        Ok(state)
+    /// Fetch the OIDC discovery document and/or JWKS for the configured mode
+    async fn prefetch_keys(&self) -> Result<(), AuthError> {
+        if let Some(jwt_config) = &self.config.jwt {
+            match &jwt_config.mode {
+                JwtMode::Oidc { discovery_url } => {
+                    self.fetch_oidc_config(discovery_url).await?;
+                }
+                JwtMode::Jwks { url } => {
+                    self.fetch_jwks(url).await?;
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Run a key fetch, applying lazy-mode semantics
+    ///
+    /// In lazy mode a failed fetch is reported as [`AuthError::Unavailable`]
+    /// and starts a background retry loop; requests arriving while it runs
+    /// fail fast instead of waiting on the identity provider.
+    async fn fetch_keys_with<T, F>(&self, fetch: F) -> Result<T, AuthError>
+    where
+        F: Future<Output = Result<T, AuthError>>,
+    {
+        let Some(jwt_config) = self.config.jwt.as_ref().filter(|jwt| jwt.lazy_jwks) else {
+            return fetch.await;
+        };
+
+        if self.key_retry_running.load(Ordering::SeqCst) {
+            return Err(AuthError::Unavailable(
+                "Signing keys are not loaded yet".to_string(),
+            ));
+        }
+
+        match fetch.await {
+            Err(AuthError::Configuration(message)) => {
+                self.spawn_key_retry(jwt_config.jwks_retry_interval);
+                Err(AuthError::Unavailable(message))
+            }
+            result => result,
+        }
+    }
+
+    /// Retry fetching keys in the background until it succeeds
+    fn spawn_key_retry(&self, initial_interval: Duration) {
+        if self.key_retry_running.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let state = self.clone();
+        tokio::spawn(async move {
+            let mut interval = initial_interval;
+            loop {
+                tokio::time::sleep(interval).await;
+                match state.prefetch_keys().await {
+                    Ok(()) => {
+                        info!("Loaded signing keys after retry");
+                        break;
+                    }
+                    Err(e) => {
+                        interval = (interval * 2).min(MAX_JWKS_RETRY_INTERVAL);
+                        warn!(
+                            error = %e,
+                            retry_in_ms = interval.as_millis(),
+                            "Signing key fetch failed, retrying"
+                        );
+                    }
+                }
+            }
+            state.key_retry_running.store(false, Ordering::SeqCst);
+        });
+    }
+
+    /// Raise `key.expiring` webhooks for API keys inside the warning window
+    ///
+    /// Each key is reported once per expiry time, on its first use inside
+    /// the window.
+    #[must_use]
+    pub fn with_webhooks(mut self, emitter: Arc<WebhookEmitter>) -> Self {
+        self.key_expiry = Some(Arc::new(KeyExpiryNotifier {
+            emitter,
+            notified: DashMap::new(),
+        }));
+        self
     }
 
     /// Create a disabled auth state
@@ -665,6 +961,9 @@ impl AuthState {
             jwks_cache: Arc::new(DashMap::new()),
             oidc_cache: Arc::new(DashMap::new()),
             static_key: None,
+            key_retry_running: Arc::new(AtomicBool::new(false)),
+            key_expiry: None,
+            refresh_task: None,
         }
     }
 
@@ -709,15 +1008,23 @@ impl AuthState {
 
     /// Fetch JWKS from URL
     async fn fetch_jwks(&self, jwks_url: &str) -> Result<JwkSet, AuthError> {
-        // Check cache first
+        // Check cache first; with background refresh the cached set stays
+        // in use until a refresh replaces it
         if let Some(cached) = self.jwks_cache.get(jwks_url) {
             if let Some(jwt_config) = &self.config.jwt {
-                if cached.fetched_at.elapsed() < jwt_config.jwks_cache_ttl {
+                if jwt_config.refreshes_in_background()
+                    || cached.fetched_at.elapsed() < jwt_config.jwks_cache_ttl
+                {
                     return Ok(cached.jwks.clone());
                 }
             }
         }
 
+        self.load_jwks(jwks_url).await
+    }
+
+    /// Download JWKS from URL and replace the cached set
+    async fn load_jwks(&self, jwks_url: &str) -> Result<JwkSet, AuthError> {
         debug!(url = %jwks_url, "Fetching JWKS");
 
         let response = self
@@ -774,7 +1081,9 @@ impl AuthState {
                 Ok((key, algo))
             }
             JwtMode::Oidc { discovery_url } => {
-                let discovery = self.fetch_oidc_config(discovery_url).await?;
+                let discovery = self
+                    .fetch_keys_with(self.fetch_oidc_config(discovery_url))
+                    .await?;
                 self.get_key_from_jwks(&discovery.jwks_uri, token).await
             }
             JwtMode::Jwks { url } => self.get_key_from_jwks(url, token).await,
@@ -791,7 +1100,7 @@ impl AuthState {
             AuthError::InvalidToken(format!("Failed to decode token header: {e}"))
         })?;
 
-        let jwks = self.fetch_jwks(jwks_url).await?;
+        let jwks = self.fetch_keys_with(self.fetch_jwks(jwks_url)).await?;
 
         // Find the key by kid
         let kid = header
@@ -874,6 +1183,7 @@ impl AuthState {
             email: claims.email.clone(),
             name: claims.name.clone(),
             auth_method: AuthMethod::Jwt,
+            credential_id: None,
             scopes,
             expires_at: claims.exp.map(|e| {
                 DateTime::from_timestamp(e, 0).unwrap_or_else(Utc::now)
@@ -997,6 +1307,7 @@ impl AuthState {
             email: None,
             name: metadata.name.clone(),
             auth_method: AuthMethod::ApiKey,
+            credential_id: Some(format!("api-key:{}", &hash_api_key(&lookup_key)[..16])),
             scopes: metadata.scopes.clone(),
             expires_at: metadata.expires_at,
             claims: metadata
@@ -1009,8 +1320,19 @@ impl AuthState {
 
     /// Authenticate a request
     pub async fn authenticate(&self, request: &Request) -> Result<AuthenticatedEntity, AuthError> {
+        self.authenticate_headers(request.headers()).await
+    }
+
+    /// Authenticate from request headers
+    ///
+    /// Used by the middleware so the request body is not borrowed across an
+    /// await point.
+    pub async fn authenticate_headers(
+        &self,
+        headers: &HeaderMap,
+    ) -> Result<AuthenticatedEntity, AuthError> {
         // Check for Bearer token
-        if let Some(auth_header) = request.headers().get(header::AUTHORIZATION) {
+        if let Some(auth_header) = headers.get(header::AUTHORIZATION) {
             if let Ok(auth_str) = auth_header.to_str() {
                 if let Some(token) = auth_str.strip_prefix("Bearer ") {
                     return self.validate_jwt(token.trim()).await;
@@ -1021,14 +1343,14 @@ impl AuthState {
         // Check for API key
         if let Some(api_config) = &self.config.api_keys {
             // Check header
-            if let Some(key_header) = request.headers().get(&api_config.header_name) {
+            if let Some(key_header) = headers.get(&api_config.header_name) {
                 if let Ok(key) = key_header.to_str() {
                     return self.validate_api_key(key);
                 }
             }
 
             // Check Authorization header with Basic scheme (for API keys)
-            if let Some(auth_header) = request.headers().get(header::AUTHORIZATION) {
+            if let Some(auth_header) = headers.get(header::AUTHORIZATION) {
                 if let Ok(auth_str) = auth_header.to_str() {
                     if let Some(encoded) = auth_str.strip_prefix("Basic ") {
                         if let Ok(decoded) = URL_SAFE_NO_PAD.decode(encoded.trim()) {
@@ -1057,6 +1379,7 @@ impl AuthState {
                 email: None,
                 name: None,
                 auth_method: AuthMethod::Anonymous,
+                credential_id: None,
                 scopes: Vec::new(),
                 expires_at: None,
                 claims: HashMap::new(),
@@ -1095,6 +1418,10 @@ pub enum AuthError {
     /// Configuration error
     #[error("Configuration error: {0}")]
     Configuration(String),
+
+    /// Signing keys could not be loaded from the identity provider
+    #[error("Authentication unavailable: {0}")]
+    Unavailable(String),
 }
 
 impl AuthError {
@@ -1107,6 +1434,7 @@ impl AuthError {
             Self::MissingClaim(_) => StatusCode::FORBIDDEN,
             Self::InsufficientScope(_) => StatusCode::FORBIDDEN,
             Self::Configuration(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::Unavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
         }
     }
 
@@ -1120,6 +1448,7 @@ impl AuthError {
             Self::MissingClaim(_) => "missing_claim",
             Self::InsufficientScope(_) => "insufficient_scope",
             Self::Configuration(_) => "configuration_error",
+            Self::Unavailable(_) => "auth_unavailable",
         }
     }
 }
@@ -1143,7 +1472,7 @@ pub async fn auth_middleware(
     }
 
     // Authenticate the request
-    match state.authenticate(&request).await {
+    match state.authenticate_headers(request.headers()).await {
         Ok(entity) => {
             debug!(
                 user_id = %entity.id,
@@ -1152,10 +1481,30 @@ pub async fn auth_middleware(
                 "Request authenticated"
             );
 
+            // Keys close to expiry are still accepted, with a warning so
+            // the client can rotate in time
+            let expires_in = match (&state.config.api_keys, entity.auth_method, entity.expires_at) {
+                (Some(api_keys), AuthMethod::ApiKey, Some(expires_at)) => {
+                    api_keys.expires_within_warning(expires_at, Utc::now())
+                }
+                _ => None,
+            };
+            if let (Some(notifier), Some(expires_in), Some(expires_at)) =
+                (&state.key_expiry, expires_in, entity.expires_at)
+            {
+                notifier.notify(&entity, expires_at, expires_in);
+            }
+
             // Add authenticated entity to request extensions
             request.extensions_mut().insert(entity);
 
-            next.run(request).await
+            let mut response = next.run(request).await;
+            if let Some(expires_in) = expires_in {
+                response
+                    .headers_mut()
+                    .insert(EXPIRES_IN_HEADER, expires_in.as_secs().into());
+            }
+            response
         }
         Err(err) => {
             warn!(error = %err, path = %path, "Authentication failed");
@@ -1180,6 +1529,7 @@ pub async fn auth_middleware(
                             AuthError::ExpiredCredential => "Credentials expired",
                             AuthError::MissingClaim(_) | AuthError::InsufficientScope(_) => "Access denied",
                             AuthError::Configuration(_) => "Authentication service error",
+                            AuthError::Unavailable(_) => "Authentication temporarily unavailable",
                         },
                     }
                 })
@@ -1373,6 +1723,10 @@ mod tests {
             AuthError::Configuration("test".to_string()).status_code(),
             StatusCode::INTERNAL_SERVER_ERROR
         );
+        assert_eq!(
+            AuthError::Unavailable("test".to_string()).status_code(),
+            StatusCode::SERVICE_UNAVAILABLE
+        );
     }
 
     #[test]
@@ -1392,6 +1746,10 @@ mod tests {
             AuthError::InsufficientScope("read".to_string()).error_code(),
             "insufficient_scope"
         );
+        assert_eq!(
+            AuthError::Unavailable("test".to_string()).error_code(),
+            "auth_unavailable"
+        );
     }
 
     #[test]
@@ -1402,6 +1760,7 @@ mod tests {
             email: Some("user@example.com".to_string()),
             name: Some("Test User".to_string()),
             auth_method: AuthMethod::Jwt,
+            credential_id: None,
             scopes: vec!["read".to_string(), "write".to_string()],
             expires_at: None,
             claims: HashMap::new(),
@@ -1455,6 +1814,9 @@ mod tests {
             jwks_cache: Arc::new(DashMap::new()),
             oidc_cache: Arc::new(DashMap::new()),
             static_key: None,
+            key_retry_running: Arc::new(AtomicBool::new(false)),
+            key_expiry: None,
+            refresh_task: None,
         };
 
         let request = make_request_with_header("/api", Some(("X-API-Key", "valid-api-key")));
@@ -1485,6 +1847,9 @@ mod tests {
             jwks_cache: Arc::new(DashMap::new()),
             oidc_cache: Arc::new(DashMap::new()),
             static_key: None,
+            key_retry_running: Arc::new(AtomicBool::new(false)),
+            key_expiry: None,
+            refresh_task: None,
         };
 
         let request = make_request_with_header("/api", Some(("X-API-Key", "invalid-key")));
@@ -1507,6 +1872,9 @@ mod tests {
             jwks_cache: Arc::new(DashMap::new()),
             oidc_cache: Arc::new(DashMap::new()),
             static_key: None,
+            key_retry_running: Arc::new(AtomicBool::new(false)),
+            key_expiry: None,
+            refresh_task: None,
         };
 
         let request = make_request_with_header("/api", None);
@@ -1528,6 +1896,9 @@ mod tests {
             jwks_cache: Arc::new(DashMap::new()),
             oidc_cache: Arc::new(DashMap::new()),
             static_key: None,
+            key_retry_running: Arc::new(AtomicBool::new(false)),
+            key_expiry: None,
+            refresh_task: None,
         };
 
         let request = make_request_with_header("/api", None);
@@ -1558,6 +1929,9 @@ mod tests {
             jwks_cache: Arc::new(DashMap::new()),
             oidc_cache: Arc::new(DashMap::new()),
             static_key: None,
+            key_retry_running: Arc::new(AtomicBool::new(false)),
+            key_expiry: None,
+            refresh_task: None,
         };
 
         let request = make_request_with_header("/api", Some(("X-API-Key", "expired-key")));
@@ -1567,6 +1941,23 @@ mod tests {
         assert!(matches!(result.unwrap_err(), AuthError::ExpiredCredential));
     }
 
+    #[test]
+    fn test_expiry_warning_window() {
+        let now = Utc::now();
+        let config = ApiKeyConfig::new().with_expiry_warning(Duration::from_secs(7 * 86_400));
+
+        assert_eq!(
+            config.expires_within_warning(now + chrono::Duration::hours(1), now),
+            Some(Duration::from_secs(3600))
+        );
+        assert_eq!(config.expires_within_warning(now + chrono::Duration::days(30), now), None);
+        assert_eq!(config.expires_within_warning(now - chrono::Duration::hours(1), now), None);
+        assert_eq!(
+            ApiKeyConfig::new().expires_within_warning(now + chrono::Duration::hours(1), now),
+            None
+        );
+    }
+
     #[tokio::test]
     async fn test_authenticate_disabled_api_key() {
         let mut metadata = ApiKeyMetadata::new();
@@ -1585,6 +1976,9 @@ mod tests {
             jwks_cache: Arc::new(DashMap::new()),
             oidc_cache: Arc::new(DashMap::new()),
             static_key: None,
+            key_retry_running: Arc::new(AtomicBool::new(false)),
+            key_expiry: None,
+            refresh_task: None,
         };
 
         let request = make_request_with_header("/api", Some(("X-API-Key", "disabled-key")));
@@ -1613,6 +2007,9 @@ mod tests {
             jwks_cache: Arc::new(DashMap::new()),
             oidc_cache: Arc::new(DashMap::new()),
             static_key: None,
+            key_retry_running: Arc::new(AtomicBool::new(false)),
+            key_expiry: None,
+            refresh_task: None,
         };
 
         let request = make_request_with_header("/api", Some(("X-API-Key", "limited-key")));
@@ -1639,6 +2036,9 @@ mod tests {
             jwks_cache: Arc::new(DashMap::new()),
             oidc_cache: Arc::new(DashMap::new()),
             static_key: None,
+            key_retry_running: Arc::new(AtomicBool::new(false)),
+            key_expiry: None,
+            refresh_task: None,
         };
 
         // Original key should still work (it gets hashed during validation)
@@ -1720,4 +2120,178 @@ mod tests {
             .build();
         assert!(!config_with_api_keys.is_disabled());
     }
+
+    mod lazy_jwks {
+        use super::*;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        const SECRET: &[u8] = b"lazy-jwks-test-secret";
+
+        fn jwt_config(server: &MockServer, lazy: bool) -> JwtConfig {
+            JwtConfig::oidc(server.uri())
+                .with_algorithms(vec![Algorithm::HS256])
+                .with_lazy_jwks(lazy)
+                .with_jwks_retry_interval(Duration::from_millis(20))
+        }
+
+        async fn mount_idp_down(server: &MockServer) {
+            Mock::given(method("GET"))
+                .respond_with(ResponseTemplate::new(503))
+                .mount(server)
+                .await;
+        }
+
+        async fn mount_idp_up(server: &MockServer) {
+            Mock::given(method("GET"))
+                .and(path("/.well-known/openid-configuration"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "issuer": server.uri(),
+                    "jwks_uri": format!("{}/jwks", server.uri()),
+                })))
+                .mount(server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path("/jwks"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "keys": [{
+                        "kty": "oct",
+                        "kid": "test-key",
+                        "alg": "HS256",
+                        "k": URL_SAFE_NO_PAD.encode(SECRET),
+                    }]
+                })))
+                .mount(server)
+                .await;
+        }
+
+        fn bearer_request(server: &MockServer) -> Request {
+            let header = jsonwebtoken::Header {
+                kid: Some("test-key".to_string()),
+                ..jsonwebtoken::Header::new(Algorithm::HS256)
+            };
+            let claims = serde_json::json!({
+                "sub": "user-1",
+                "iss": server.uri(),
+                "exp": Utc::now().timestamp() + 3600,
+            });
+            let token = jsonwebtoken::encode(
+                &header,
+                &claims,
+                &jsonwebtoken::EncodingKey::from_secret(SECRET),
+            )
+            .unwrap();
+
+            make_request_with_header("/v1/models", Some(("Authorization", &format!("Bearer {token}"))))
+        }
+
+        #[tokio::test]
+        async fn test_eager_mode_fails_startup_when_idp_down() {
+            let server = MockServer::start().await;
+            mount_idp_down(&server).await;
+
+            let config = AuthConfig::builder().jwt(jwt_config(&server, false)).build();
+            assert!(AuthState::new(config).await.is_err());
+        }
+
+        #[tokio::test]
+        async fn test_lazy_mode_starts_and_recovers_when_idp_returns() {
+            let server = MockServer::start().await;
+            mount_idp_down(&server).await;
+
+            let config = AuthConfig::builder()
+                .jwt(jwt_config(&server, true))
+                .required(true)
+                .build();
+            let state = AuthState::new(config).await.expect("lazy mode should start");
+
+            let err = state.authenticate(&bearer_request(&server)).await.unwrap_err();
+            assert!(matches!(err, AuthError::Unavailable(_)));
+            assert_eq!(err.status_code(), StatusCode::SERVICE_UNAVAILABLE);
+            assert_eq!(err.error_code(), "auth_unavailable");
+
+            server.reset().await;
+            mount_idp_up(&server).await;
+
+            let entity = tokio::time::timeout(Duration::from_secs(5), async {
+                loop {
+                    match state.authenticate(&bearer_request(&server)).await {
+                        Ok(entity) => break entity,
+                        Err(AuthError::Unavailable(_)) => {
+                            tokio::time::sleep(Duration::from_millis(10)).await;
+                        }
+                        Err(e) => panic!("unexpected auth error: {e}"),
+                    }
+                }
+            })
+            .await
+            .expect("keys should load once the IdP is reachable");
+
+            assert_eq!(entity.id, "user-1");
+            assert_eq!(entity.auth_method, AuthMethod::Jwt);
+        }
+
+        #[tokio::test]
+        async fn test_background_refresh_keeps_last_known_good_keys() {
+            let server = MockServer::start().await;
+            mount_idp_up(&server).await;
+
+            let jwt = jwt_config(&server, false)
+                .with_cache_ttl(Duration::from_millis(100))
+                .with_background_refresh(true);
+            let config = AuthConfig::builder().jwt(jwt).required(true).build();
+            let state = AuthState::new(config).await.unwrap();
+
+            // Refreshed ahead of the TTL without any request asking for keys
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            let jwks_fetches = server
+                .received_requests()
+                .await
+                .unwrap()
+                .iter()
+                .filter(|r| r.url.path() == "/jwks")
+                .count();
+            assert!(jwks_fetches >= 2, "expected background fetches, got {jwks_fetches}");
+
+            // Past the TTL with the IdP down, the last key set still verifies
+            server.reset().await;
+            mount_idp_down(&server).await;
+            tokio::time::sleep(Duration::from_millis(250)).await;
+            let entity = state.authenticate(&bearer_request(&server)).await.unwrap();
+            assert_eq!(entity.id, "user-1");
+        }
+
+        #[tokio::test]
+        async fn test_background_refresh_stops_when_state_dropped() {
+            let server = MockServer::start().await;
+            mount_idp_up(&server).await;
+
+            let jwt = jwt_config(&server, false)
+                .with_cache_ttl(Duration::from_millis(100))
+                .with_background_refresh(true);
+            let config = AuthConfig::builder().jwt(jwt).required(true).build();
+            let state = AuthState::new(config).await.unwrap();
+            let clone = state.clone();
+            drop(state);
+
+            // A surviving clone keeps the refresh running
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            let jwks_fetches = || async {
+                server
+                    .received_requests()
+                    .await
+                    .unwrap()
+                    .iter()
+                    .filter(|r| r.url.path() == "/jwks")
+                    .count()
+            };
+            assert!(jwks_fetches().await >= 2);
+
+            drop(clone);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            let after_drop = jwks_fetches().await;
+            tokio::time::sleep(Duration::from_millis(250)).await;
+            assert_eq!(jwks_fetches().await, after_drop);
+        }
+    }
 }
@@ -0,0 +1,319 @@
+//! Batch jobs submitted through `/v1/batches`.
+//!
+//! Batches for providers with a native batch endpoint are forwarded to it
+//! and polled when the caller asks for their status. For every other
+//! provider the gateway emulates the batch, dispatching its requests in the
+//! background `server.batch_emulation_concurrency` at a time.
+//!
+//! A batch belongs to the tenant that submitted it; lookups from any other
+//! tenant find nothing. Each request's usage is recorded in the metrics and
+//! the cost tracker against that tenant once its result is known.
+
+use dashmap::DashMap;
+use futures::stream::{self, StreamExt};
+use gateway_core::{
+    Batch, BatchItem, BatchItemResult, BatchMode, BatchRequest, BatchRequestCounts, BatchStatus,
+    GatewayError, LLMProvider, Usage,
+};
+use gateway_telemetry::TokenSource;
+use parking_lot::Mutex;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+use crate::state::AppState;
+
+/// Submitted batches by gateway batch id
+#[derive(Default)]
+pub struct BatchStore {
+    jobs: DashMap<String, Arc<BatchJob>>,
+}
+
+/// A submitted batch
+pub struct BatchJob {
+    id: String,
+    tenant: Option<String>,
+    model: String,
+    provider: Arc<dyn LLMProvider>,
+    created_at: i64,
+    submitted: Instant,
+    /// Provider's id for the batch, absent when emulated
+    native_id: Option<String>,
+    progress: Mutex<BatchProgressState>,
+    /// Serializes provider polls so results are loaded and billed once
+    poll: tokio::sync::Mutex<()>,
+}
+
+struct BatchProgressState {
+    status: BatchStatus,
+    request_counts: BatchRequestCounts,
+    completed_at: Option<i64>,
+    usage: Usage,
+    results: Vec<BatchItemResult>,
+}
+
+impl BatchStore {
+    /// Create an empty store
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Look up a batch submitted by `tenant`
+    #[must_use]
+    pub fn get(&self, id: &str, tenant: Option<&str>) -> Option<Arc<BatchJob>> {
+        self.jobs
+            .get(id)
+            .filter(|job| job.tenant.as_deref() == tenant)
+            .map(|job| Arc::clone(&job))
+    }
+
+    fn insert(&self, job: Arc<BatchJob>) {
+        self.jobs.insert(job.id.clone(), job);
+    }
+}
+
+impl BatchJob {
+    /// Gateway batch id
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Current state of the batch as returned to the caller
+    #[must_use]
+    pub fn snapshot(&self) -> Batch {
+        let progress = self.progress.lock();
+        Batch {
+            id: self.id.clone(),
+            object: "batch".to_string(),
+            model: self.model.clone(),
+            provider: self.provider.id().to_string(),
+            mode: if self.native_id.is_some() {
+                BatchMode::Native
+            } else {
+                BatchMode::Emulated
+            },
+            status: progress.status,
+            created_at: self.created_at,
+            completed_at: progress.completed_at,
+            request_counts: progress.request_counts,
+            usage: progress.usage.clone(),
+        }
+    }
+
+    /// Results of a completed batch, in submission order when emulated
+    ///
+    /// Returns `None` until the batch has completed.
+    #[must_use]
+    pub fn results(&self) -> Option<Vec<BatchItemResult>> {
+        let progress = self.progress.lock();
+        (progress.status == BatchStatus::Completed).then(|| progress.results.clone())
+    }
+
+    /// Record one request's outcome
+    async fn record(&self, state: &AppState, result: BatchItemResult, latency: Duration) {
+        let usage = result.usage();
+        let success = result.error.is_none();
+
+        state.metrics.record_request(&gateway_telemetry::RequestMetrics {
+            model: self.model.clone(),
+            provider: self.provider.id().to_string(),
+            latency,
+            success,
+            status_code: if success { 200 } else { 500 },
+            input_tokens: success.then_some(usage.prompt_tokens),
+            output_tokens: success.then_some(usage.completion_tokens),
+            streaming: false,
+            tenant_id: self.tenant.clone(),
+        });
+        if success {
+            state
+                .metrics
+                .record_prompt_tokens(&self.model, TokenSource::Usage, usage.prompt_tokens);
+            state
+                .metrics
+                .record_completion_tokens(&self.model, usage.completion_tokens);
+        }
+
+        state
+            .cost_tracker
+            .record_response_usage(
+                format!("{}:{}", self.id, result.custom_id),
+                self.tenant.clone(),
+                &self.model,
+                self.provider.id(),
+                &usage,
+                latency,
+                success,
+            )
+            .await;
+
+        let mut progress = self.progress.lock();
+        if success {
+            progress.request_counts.completed += 1;
+        } else {
+            progress.request_counts.failed += 1;
+        }
+        let total = &mut progress.usage;
+        total.prompt_tokens = total.prompt_tokens.saturating_add(usage.prompt_tokens);
+        total.completion_tokens = total.completion_tokens.saturating_add(usage.completion_tokens);
+        total.total_tokens = total.total_tokens.saturating_add(usage.total_tokens);
+        progress.results.push(result);
+    }
+
+    /// Move to `status`, stamping the completion time on terminal states
+    fn set_status(&self, status: BatchStatus) {
+        let mut progress = self.progress.lock();
+        progress.status = status;
+        if status.is_terminal() && progress.completed_at.is_none() {
+            progress.completed_at = Some(chrono::Utc::now().timestamp());
+        }
+    }
+}
+
+/// Submit a batch to `provider`, natively if it has a batch endpoint
+///
+/// # Errors
+/// Returns the provider's error if it rejects a native batch
+pub async fn submit(
+    state: &AppState,
+    tenant: Option<&str>,
+    provider: Arc<dyn LLMProvider>,
+    request: BatchRequest,
+) -> Result<Arc<BatchJob>, GatewayError> {
+    let native_id = match provider.as_batch_provider() {
+        Some(batches) => Some(batches.submit_batch(&request.requests).await?),
+        None => None,
+    };
+
+    let total = u32::try_from(request.requests.len()).unwrap_or(u32::MAX);
+    let job = Arc::new(BatchJob {
+        id: format!("batch_{}", uuid::Uuid::new_v4().simple()),
+        tenant: tenant.map(str::to_string),
+        model: request.model().to_string(),
+        provider,
+        created_at: chrono::Utc::now().timestamp(),
+        submitted: Instant::now(),
+        progress: Mutex::new(BatchProgressState {
+            status: if native_id.is_some() {
+                BatchStatus::Validating
+            } else {
+                BatchStatus::InProgress
+            },
+            request_counts: BatchRequestCounts {
+                total,
+                ..BatchRequestCounts::default()
+            },
+            completed_at: None,
+            usage: Usage::default(),
+            results: Vec::new(),
+        }),
+        native_id,
+        poll: tokio::sync::Mutex::new(()),
+    });
+    state.batches.insert(Arc::clone(&job));
+
+    if job.native_id.is_none() {
+        tokio::spawn(run_emulated(state.clone(), Arc::clone(&job), request.requests));
+    }
+
+    info!(
+        batch_id = %job.id,
+        provider = %job.provider.id(),
+        native = job.native_id.is_some(),
+        requests = total,
+        "Batch submitted"
+    );
+    Ok(job)
+}
+
+/// Bring a native batch up to date with the provider
+///
+/// Once the provider reports the batch complete its results are fetched and
+/// their usage recorded. Emulated batches are always current.
+///
+/// # Errors
+/// Returns the provider's error if polling fails
+pub async fn refresh(state: &AppState, job: &BatchJob) -> Result<(), GatewayError> {
+    let Some(native_id) = &job.native_id else {
+        return Ok(());
+    };
+    let Some(batches) = job.provider.as_batch_provider() else {
+        return Err(GatewayError::unsupported_capability(job.provider.id(), "batches"));
+    };
+
+    let _poll = job.poll.lock().await;
+    if job.progress.lock().status.is_terminal() {
+        return Ok(());
+    }
+
+    let progress = batches.batch_status(native_id).await?;
+    debug!(batch_id = %job.id, status = ?progress.status, "Polled native batch");
+
+    if progress.status != BatchStatus::Completed {
+        let mut current = job.progress.lock();
+        current.request_counts = BatchRequestCounts {
+            total: current.request_counts.total,
+            ..progress.request_counts
+        };
+        drop(current);
+        job.set_status(progress.status);
+        return Ok(());
+    }
+
+    // Counts are rebuilt from the results as their usage is recorded
+    let results = batches.batch_results(native_id).await?;
+    {
+        let mut current = job.progress.lock();
+        current.request_counts.completed = 0;
+        current.request_counts.failed = 0;
+    }
+    let turnaround = job.submitted.elapsed();
+    for result in results {
+        job.record(state, result, turnaround).await;
+    }
+    job.set_status(BatchStatus::Completed);
+    Ok(())
+}
+
+/// Dispatch an emulated batch's requests and collect their results
+async fn run_emulated(state: AppState, job: Arc<BatchJob>, requests: Vec<BatchItem>) {
+    let concurrency = state.config().server.batch_emulation_concurrency.max(1);
+    let provider = Arc::clone(&job.provider);
+
+    let mut outcomes = stream::iter(requests)
+        .map(|item| {
+            let state = &state;
+            let provider = &provider;
+            let batch_id = job.id.as_str();
+            async move {
+                let start = Instant::now();
+                let result = state
+                    .retry_policy
+                    .execute(|| provider.chat_completion(&item.body))
+                    .await;
+                let result = match result {
+                    Ok(response) => BatchItemResult::success(item.custom_id, response),
+                    Err(e) => {
+                        warn!(
+                            batch_id = %batch_id,
+                            custom_id = %item.custom_id,
+                            error = %e,
+                            "Batch request failed"
+                        );
+                        BatchItemResult::failure(item.custom_id, e.error_code(), e.to_string())
+                    }
+                };
+                (result, start.elapsed())
+            }
+        })
+        .buffered(concurrency);
+
+    while let Some((result, latency)) = outcomes.next().await {
+        job.record(&state, result, latency).await;
+    }
+
+    job.set_status(BatchStatus::Completed);
+    info!(batch_id = %job.id, "Emulated batch completed");
+}
@@ -0,0 +1,178 @@
+//! Compatibility shims for legacy client request bodies.
+//!
+//! Older clients still send deprecated fields that predate the current
+//! [`gateway_core::GatewayRequest`] shape:
+//! - `max_tokens_to_sample` instead of `max_tokens`
+//! - `prompt` instead of `messages` for chat
+//! - `functions` / `function_call` instead of `tools` / `tool_choice`
+//!
+//! [`normalize_legacy_request`] rewrites these in place before the body is
+//! deserialized and validated. Current fields always win when both forms
+//! are present.
+
+use serde_json::{json, Map, Value};
+
+/// Header flagging that a request used deprecated fields
+pub const DEPRECATION_HEADER: &str = "deprecation";
+
+/// Map deprecated request fields onto the current request shape
+///
+/// Returns the names of the deprecated fields that were found, in a stable
+/// order. An empty result means the body was left untouched.
+pub fn normalize_legacy_request(body: &mut Value) -> Vec<&'static str> {
+    let Some(obj) = body.as_object_mut() else {
+        return Vec::new();
+    };
+
+    let mut deprecated = Vec::new();
+
+    if let Some(max_tokens) = obj.remove("max_tokens_to_sample") {
+        deprecated.push("max_tokens_to_sample");
+        obj.entry("max_tokens").or_insert(max_tokens);
+    }
+
+    if let Some(prompt) = obj.remove("prompt") {
+        deprecated.push("prompt");
+        if !obj.contains_key("messages") {
+            if let Some(content) = prompt_text(&prompt) {
+                obj.insert(
+                    "messages".to_string(),
+                    json!([{ "role": "user", "content": content }]),
+                );
+            }
+        }
+    }
+
+    if let Some(functions) = obj.remove("functions") {
+        deprecated.push("functions");
+        if !obj.contains_key("tools") {
+            if let Value::Array(functions) = functions {
+                let tools: Vec<Value> = functions
+                    .into_iter()
+                    .map(|function| json!({ "type": "function", "function": function }))
+                    .collect();
+                obj.insert("tools".to_string(), Value::Array(tools));
+            }
+        }
+    }
+
+    if let Some(function_call) = obj.remove("function_call") {
+        deprecated.push("function_call");
+        if !obj.contains_key("tool_choice") {
+            if let Some(choice) = tool_choice(function_call) {
+                obj.insert("tool_choice".to_string(), choice);
+            }
+        }
+    }
+
+    deprecated
+}
+
+/// Build the `Warning` header value naming the deprecated fields
+#[must_use]
+pub fn deprecation_warning(fields: &[&str]) -> String {
+    format!(
+        "299 - \"Deprecated request fields: {}\"",
+        fields.join(", ")
+    )
+}
+
+/// Flatten a legacy `prompt` (string or list of strings) into message text
+fn prompt_text(prompt: &Value) -> Option<String> {
+    match prompt {
+        Value::String(text) => Some(text.clone()),
+        Value::Array(parts) => {
+            let parts: Option<Vec<&str>> = parts.iter().map(Value::as_str).collect();
+            parts.map(|parts| parts.join("\n"))
+        }
+        _ => None,
+    }
+}
+
+/// Convert a legacy `function_call` into a `tool_choice`
+fn tool_choice(function_call: Value) -> Option<Value> {
+    match function_call {
+        Value::String(mode) => Some(Value::String(mode)),
+        Value::Object(call) => {
+            let name = call.get("name")?.clone();
+            let mut function = Map::new();
+            function.insert("name".to_string(), name);
+            Some(json!({ "type": "function", "function": function }))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gateway_core::GatewayRequest;
+
+    #[test]
+    fn test_normalize_functions_and_max_tokens_to_sample() {
+        let mut body = json!({
+            "model": "gpt-4o",
+            "messages": [{ "role": "user", "content": "What's the weather?" }],
+            "max_tokens_to_sample": 256,
+            "functions": [{
+                "name": "get_weather",
+                "parameters": { "type": "object", "properties": {} }
+            }],
+            "function_call": { "name": "get_weather" }
+        });
+
+        let deprecated = normalize_legacy_request(&mut body);
+        assert_eq!(
+            deprecated,
+            vec!["max_tokens_to_sample", "functions", "function_call"]
+        );
+
+        let request: GatewayRequest = serde_json::from_value(body).unwrap();
+        assert_eq!(request.max_tokens, Some(256));
+        let tools = request.tools.unwrap();
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].tool_type, "function");
+        assert_eq!(tools[0].function.name, "get_weather");
+        assert!(matches!(
+            request.tool_choice,
+            Some(gateway_core::ToolChoice::Tool { ref function, .. }) if function.name == "get_weather"
+        ));
+    }
+
+    #[test]
+    fn test_normalize_prompt_to_messages() {
+        let mut body = json!({ "model": "gpt-4o", "prompt": "Hello" });
+
+        assert_eq!(normalize_legacy_request(&mut body), vec!["prompt"]);
+
+        let request: GatewayRequest = serde_json::from_value(body).unwrap();
+        assert_eq!(request.messages.len(), 1);
+        assert_eq!(request.messages[0].text_content(), Some("Hello"));
+    }
+
+    #[test]
+    fn test_current_fields_take_precedence() {
+        let mut body = json!({
+            "model": "gpt-4o",
+            "messages": [{ "role": "user", "content": "Hi" }],
+            "max_tokens": 10,
+            "max_tokens_to_sample": 500
+        });
+
+        assert_eq!(normalize_legacy_request(&mut body), vec!["max_tokens_to_sample"]);
+        assert_eq!(body["max_tokens"], 10);
+    }
+
+    #[test]
+    fn test_current_request_untouched() {
+        let mut body = json!({
+            "model": "gpt-4o",
+            "messages": [{ "role": "user", "content": "Hi" }],
+            "max_tokens": 10
+        });
+        let original = body.clone();
+
+        assert!(normalize_legacy_request(&mut body).is_empty());
+        assert_eq!(body, original);
+    }
+}
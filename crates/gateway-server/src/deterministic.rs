@@ -0,0 +1,185 @@
+//! Deterministic sampling overrides for evaluation runs.
+//!
+//! Reproducible evaluations need every request in a run to sample the same
+//! way. Requests from a configured tenant, or carrying an
+//! `X-Deterministic-Session` header when sessions may opt in, have
+//! `temperature` forced to 0, `top_p` to 1 and a seed derived from the
+//! tenant or session. What was overridden is recorded in the request
+//! metadata tags.
+
+use gateway_config::DeterministicConfig;
+use gateway_core::GatewayRequest;
+
+/// Header opting a session in to deterministic sampling
+pub const SESSION_HEADER: &str = "x-deterministic-session";
+
+/// Metadata tag holding the injected seed
+pub const SEED_TAG: &str = "deterministic_seed";
+
+/// Metadata tag listing the parameters that were overridden
+pub const OVERRIDES_TAG: &str = "deterministic_overrides";
+
+/// Scope key for a request, or `None` if it is not in scope
+///
+/// A session takes precedence over the tenant so that separate runs by the
+/// same tenant get separate seeds.
+#[must_use]
+pub fn scope(
+    config: &DeterministicConfig,
+    tenant_id: Option<&str>,
+    session: Option<&str>,
+) -> Option<String> {
+    if !config.enabled {
+        return None;
+    }
+    if let Some(session) = session.filter(|_| config.allow_session_header) {
+        return Some(format!("session:{session}"));
+    }
+    tenant_id
+        .filter(|tenant| config.tenants.iter().any(|t| t == tenant))
+        .map(|tenant| format!("tenant:{tenant}"))
+}
+
+/// Seed for a scope, stable across processes and releases
+///
+/// FNV-1a over the base seed and scope key, kept to 31 bits since some
+/// providers only accept 32-bit signed seeds.
+#[must_use]
+pub fn derive_seed(base: u64, scope: &str) -> i64 {
+    const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+    let hash = base
+        .to_le_bytes()
+        .iter()
+        .chain(scope.as_bytes())
+        .fold(FNV_OFFSET, |hash, byte| {
+            (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+        });
+    i64::try_from(hash & 0x7fff_ffff).unwrap_or_default()
+}
+
+/// Force deterministic sampling parameters and record the override
+pub fn apply(request: &mut GatewayRequest, seed: i64) {
+    let mut overrides = Vec::new();
+    if request.temperature != Some(0.0) {
+        overrides.push(format!("temperature={}", describe(request.temperature)));
+        request.temperature = Some(0.0);
+    }
+    if request.top_p != Some(1.0) {
+        overrides.push(format!("top_p={}", describe(request.top_p)));
+        request.top_p = Some(1.0);
+    }
+    if request.seed != Some(seed) {
+        overrides.push(format!("seed={}", describe(request.seed)));
+        request.seed = Some(seed);
+    }
+
+    let tags = &mut request.metadata.get_or_insert_with(Default::default).tags;
+    tags.insert(SEED_TAG.to_string(), seed.to_string());
+    tags.insert(OVERRIDES_TAG.to_string(), overrides.join(","));
+}
+
+/// Apply the overrides if the request is in scope, returning the seed
+pub fn enforce(
+    config: &DeterministicConfig,
+    tenant_id: Option<&str>,
+    session: Option<&str>,
+    request: &mut GatewayRequest,
+) -> Option<i64> {
+    let scope = scope(config, tenant_id, session)?;
+    let seed = derive_seed(config.seed, &scope);
+    apply(request, seed);
+    Some(seed)
+}
+
+/// Original value of an overridden parameter
+fn describe<T: ToString>(value: Option<T>) -> String {
+    value.map_or_else(|| "unset".to_string(), |value| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gateway_core::ChatMessage;
+
+    fn config() -> DeterministicConfig {
+        DeterministicConfig {
+            enabled: true,
+            tenants: vec!["eval".to_string()],
+            allow_session_header: true,
+            seed: 42,
+        }
+    }
+
+    fn request() -> GatewayRequest {
+        GatewayRequest::builder()
+            .model("gpt-4o")
+            .message(ChatMessage::user("Hello"))
+            .temperature(0.7)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_scope() {
+        let config = config();
+        assert_eq!(
+            scope(&config, Some("eval"), None).as_deref(),
+            Some("tenant:eval")
+        );
+        assert_eq!(
+            scope(&config, Some("eval"), Some("run-1")).as_deref(),
+            Some("session:run-1")
+        );
+        assert_eq!(scope(&config, Some("other"), None), None);
+
+        let no_sessions = DeterministicConfig {
+            allow_session_header: false,
+            ..config.clone()
+        };
+        assert_eq!(scope(&no_sessions, Some("other"), Some("run-1")), None);
+
+        let disabled = DeterministicConfig {
+            enabled: false,
+            ..config
+        };
+        assert_eq!(scope(&disabled, Some("eval"), Some("run-1")), None);
+    }
+
+    #[test]
+    fn test_derive_seed_is_stable_per_scope() {
+        let seed = derive_seed(42, "session:run-1");
+        assert_eq!(seed, derive_seed(42, "session:run-1"));
+        assert_ne!(seed, derive_seed(42, "session:run-2"));
+        assert_ne!(seed, derive_seed(7, "session:run-1"));
+        assert!((0..=i64::from(i32::MAX)).contains(&seed));
+    }
+
+    #[test]
+    fn test_enforce_forces_params_and_records_override() {
+        let mut request = request();
+        let seed = enforce(&config(), Some("eval"), None, &mut request).unwrap();
+
+        assert_eq!(request.temperature, Some(0.0));
+        assert_eq!(request.top_p, Some(1.0));
+        assert_eq!(request.seed, Some(seed));
+        let tags = &request.metadata.unwrap().tags;
+        assert_eq!(tags[SEED_TAG], seed.to_string());
+        assert_eq!(
+            tags[OVERRIDES_TAG],
+            "temperature=0.7,top_p=unset,seed=unset"
+        );
+    }
+
+    #[test]
+    fn test_enforce_out_of_scope_leaves_request_unchanged() {
+        let mut request = request();
+        assert_eq!(enforce(&config(), Some("other"), None, &mut request), None);
+
+        assert_eq!(request.temperature, Some(0.7));
+        assert_eq!(request.top_p, None);
+        assert_eq!(request.seed, None);
+        assert!(request.metadata.is_none());
+    }
+}
@@ -5,12 +5,12 @@ use agentics_contracts::{
 };
 use axum::{
     extract::{Path, State},
-    http::{header, StatusCode},
+    http::{header, HeaderMap, StatusCode},
     response::{
         sse::{Event, Sse},
         IntoResponse, Response,
     },
-    Json,
+    Extension, Json,
 };
 use chrono::Utc;
 use futures::stream::StreamExt;
@@ -18,16 +18,44 @@ use gateway_agents::{
     AgentMetadata, AgentStatus, InferenceRoutingInput, InferenceRoutingOutput, RoutingInspection,
     AGENT_ID, AGENT_VERSION,
 };
-use gateway_core::{GatewayRequest, GatewayResponse, ModelObject, ModelsResponse};
-use gateway_telemetry::RequestInfo;
+use gateway_core::json_repair::repair_json;
+use gateway_core::streaming::{
+    with_cancellation, with_max_duration, with_reset_restart, with_retry_before_first_chunk,
+    StreamOptions,
+};
+use gateway_core::embedding::{embed_batched, embed_batched_stream};
+use gateway_core::{
+    Batch, BatchItemResult, BatchRequest, ChatChunk, EmbeddingProgress, EmbeddingRequest, GatewayError, GatewayRequest,
+    GatewayResponse, ImageRequest, ImageResponse, JsonRepairOutcome, ModelObject, ModelsResponse, ProviderErrorKind, RequestContext, Usage,
+};
+use gateway_integrations::WebhookEventType;
+use gateway_telemetry::{
+    AuditActor, AuditEventBuilder, AuditEventType, AuditOutcome, AuditResource, AuditSeverity,
+    RequestInfo, StreamCostMeter, TokenSource,
+};
 use serde::{Deserialize, Serialize};
 use std::{convert::Infallible, time::Instant};
-use tracing::{debug, error, info, instrument};
+use tracing::{debug, error, info, instrument, warn};
 
 use crate::{
+    allowlist::allowed_providers,
+    batches,
+    auth::{AuthMethod, AuthenticatedEntity},
+    deterministic,
     error::ApiError,
     extractors::{ExecutionCtx, JsonBody, RequestId, TenantId},
+    image_limits,
+    middleware::rate_limit_key,
+    model_defaults,
+    passthrough::{self, SseRelay},
+    postprocess,
+    provider_override,
+    response_hash::{self, ResponseHasher, StreamSummary},
+    routing_log::{self, RoutingOutcome},
     state::AppState,
+    streams::{stream_limit, StreamPermit},
+    trace::{self, AttemptTrace, AuthTrace, CacheLookup, CacheTrace, PolicyTrace, RoutingTrace},
+    webhooks,
 };
 
 /// Repo name used in all execution spans for this gateway.
@@ -46,29 +74,62 @@ pub struct HealthResponse {
 }
 
 /// Health check endpoint
-pub async fn health_check() -> Json<HealthResponse> {
+pub async fn health_check(State(state): State<AppState>) -> Json<HealthResponse> {
+    let status = if state.health.is_shutting_down() {
+        "shutting_down"
+    } else {
+        "healthy"
+    };
     Json(HealthResponse {
-        status: "healthy".to_string(),
+        status: status.to_string(),
         version: env!("CARGO_PKG_VERSION").to_string(),
-        uptime_seconds: None,
+        uptime_seconds: Some(state.health.uptime().as_secs()),
     })
 }
 
 /// Readiness check endpoint
+///
+/// Ready once `server.readiness.min_healthy_providers` enabled providers
+/// with the required capabilities report healthy. Provider health comes
+/// from the health checker's cache: a provider is checked live only when it
+/// has no result yet, and results older than
+/// `server.readiness.provider_cache_ttl` are refreshed in the background.
+/// Live checks bypass the provider's circuit breaker and feed it their
+/// outcome. Never ready before startup completes or once shutdown begins.
 pub async fn readiness_check(State(state): State<AppState>) -> impl IntoResponse {
-    // Check if we have any providers
-    let provider_count = state.providers.len();
+    let config = state.config();
+    let gate = &config.server.readiness;
 
-    if provider_count > 0 {
-        (StatusCode::OK, "ready")
+    let eligible: Vec<_> = state
+        .providers
+        .get_enabled_providers()
+        .into_iter()
+        .filter(|provider| {
+            gate.required_capabilities
+                .iter()
+                .all(|capability| provider.capabilities().supports(capability))
+        })
+        .collect();
+    let healthy = state
+        .health
+        .check_providers(&eligible)
+        .await
+        .iter()
+        .filter(|result| result.status.is_healthy())
+        .count();
+
+    let readiness = state.health.check_readiness(eligible.len(), healthy).await;
+    let status = if readiness.ready {
+        StatusCode::OK
     } else {
-        (StatusCode::SERVICE_UNAVAILABLE, "no providers available")
-    }
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(readiness))
 }
 
 /// Liveness check endpoint
-pub async fn liveness_check() -> impl IntoResponse {
-    (StatusCode::OK, "alive")
+pub async fn liveness_check(State(state): State<AppState>) -> impl IntoResponse {
+    (StatusCode::OK, state.health.check_liveness().status)
 }
 
 /// Metrics endpoint (Prometheus format)
@@ -114,17 +175,69 @@ pub async fn get_model(
 /// Requires `X-Parent-Span-Id` header for execution context.
 /// Non-streaming responses are wrapped in [`ExecutionOutput`].
 /// Streaming responses emit an `execution_output` SSE event after `[DONE]`.
-#[instrument(skip(state, exec_ctx, body), fields(model = %body.model))]
+#[instrument(skip(state, exec_ctx, entity, headers, body), fields(model = %body.model))]
 pub async fn chat_completion(
     State(state): State<AppState>,
     ExecutionCtx(exec_ctx): ExecutionCtx,
     RequestId(request_id): RequestId,
     TenantId(tenant_id): TenantId,
+    entity: Option<Extension<AuthenticatedEntity>>,
+    headers: HeaderMap,
     JsonBody(body): JsonBody<GatewayRequest>,
 ) -> Result<Response, ApiError> {
-    let request = body;
+    let mut request = body;
     let streaming = request.stream;
 
+    // `max_tokens` and `max_completion_tokens` must agree if both are sent
+    request.validated_token_limit()?;
+
+    // Inline images are decoded and checked before anything else holds them
+    image_limits::validate(&state.config().server.images, &request)?;
+
+    // Configured per-model defaults fill parameters the client omitted
+    let applied = model_defaults::apply(&state.config().routing.model_defaults, &mut request);
+    if !applied.is_empty() {
+        debug!(request_id = %request_id, defaults = %applied.join(","), "Applied model defaults");
+    }
+
+    // Evaluation tenants and sessions get deterministic sampling
+    let session = headers
+        .get(deterministic::SESSION_HEADER)
+        .and_then(|value| value.to_str().ok());
+    let tenant = caller_tenant(entity.as_deref(), tenant_id.as_deref());
+    if let Some(seed) = deterministic::enforce(
+        &state.config().routing.deterministic,
+        tenant,
+        session,
+        &mut request,
+    ) {
+        debug!(request_id = %request_id, seed = seed, "Applied deterministic sampling");
+    }
+
+    // Post-processors for the assistant content, checked before dispatch
+    let post_process = postprocess::select(
+        &state.config().server.post_processing,
+        tenant,
+        headers
+            .get(postprocess::HEADER)
+            .and_then(|value| value.to_str().ok()),
+    );
+    state.post_processors.validate(&post_process)?;
+
+    // The middleware counted the request; charge its tokens now the body
+    // has been read
+    let key = rate_limit_key(&headers, entity.as_deref());
+    state.rate_limiter.limiter.check_request(&key, &request).await?;
+
+    state.metrics.record_prompt_tokens(
+        &request.model,
+        TokenSource::Estimate,
+        state.token_estimator.estimate_prompt(&request),
+    );
+
+    // Single deadline shared by routing, retries, and provider dispatch
+    let ctx = RequestContext::with_timeout(state.config().server.request_timeout);
+
     debug!(
         request_id = %request_id,
         execution_id = %exec_ctx.execution_id,
@@ -134,23 +247,116 @@ pub async fn chat_completion(
         "Processing chat completion request"
     );
 
-    // Track request
-    let request_info = RequestInfo::new(&request_id, &request.model)
+    // Track request; the context lets it be cancelled by id
+    let mut request_info = RequestInfo::new(&request_id, &request.model)
         .with_streaming(streaming);
-    state.tracker.start(request_info);
+    if let Some(tenant) = tenant {
+        request_info = request_info.with_tenant(tenant);
+    }
+    if let Some(entity) = entity.as_deref().filter(|e| e.auth_method != AuthMethod::Anonymous) {
+        request_info = request_info.with_owner(entity.principal(), entity.tenant_id.clone());
+    }
+    state.tracker.start_with_context(request_info, ctx.clone());
+    state.traces.record(&request_id, |trace| {
+        trace.auth = Some(AuthTrace::new(entity.as_deref(), tenant));
+    });
+
+    if let Some(gate) = &state.policy_gate {
+        let checked = gate.check(&request, &request_id, tenant_id.as_deref()).await;
+        state.traces.record(&request_id, |trace| {
+            trace.policy = Some(PolicyTrace {
+                allowed: checked.is_ok(),
+                reason: checked.as_ref().err().map(|e| e.message.clone()),
+            });
+        });
+        if let Err(e) = checked {
+            state.tracker.complete_error(&request_id, e.status.as_u16(), e.message.clone());
+            return Err(e);
+        }
+    }
+
+    // Authorized callers may pin the request to a provider
+    let pinned = provider_override::resolve(
+        &state.config().routing.provider_override,
+        entity.as_deref(),
+        provider_override::requested(
+            &request,
+            headers
+                .get(provider_override::HEADER)
+                .and_then(|value| value.to_str().ok()),
+        ),
+    )
+    .map_err(|e| {
+        state.tracker.complete_error(&request_id, e.status.as_u16(), e.message.clone());
+        e
+    })?;
+
+    // Held for the life of the response stream
+    let stream_permit = if streaming {
+        acquire_stream_slot(&state, &headers, entity.as_deref()).map_err(|e| {
+            state.tracker.complete_error(&request_id, e.status.as_u16(), e.message.clone());
+            e
+        })?
+    } else {
+        None
+    };
 
     // Create execution collector
     let mut collector = ExecutionCollector::new(&exec_ctx, REPO_NAME);
 
+    // A fresh cached response is served without dispatch
+    if !streaming {
+        if let Some(cached) =
+            fresh_cached_response(&state, &ctx, &request, &request_id, &post_process).await?
+        {
+            let cache_span_id = collector.start_agent_span("response-cache-agent");
+            collector.end_agent_span(cache_span_id, SpanStatus::Succeeded, None);
+            return Ok(cached_response(&state, collector, cached, "HIT"));
+        }
+    }
+
     // --- Agent span: routing ---
     let routing_span_id = collector.start_agent_span("inference-routing-agent");
 
-    // Route the request
-    let (provider, _decision) = match state.router.route(&request, tenant_id.as_deref()) {
+    // Route the request, limited to the providers the tenant may use
+    let allowed = allowed_providers(
+        entity.as_deref(),
+        tenant_id.as_deref(),
+        &state.config().routing.tenant_provider_allowlists,
+    );
+    let routed = ctx.check().and_then(|()| match &pinned {
+        Some(provider_id) => state.router.route_to_provider(
+            &request,
+            tenant_id.as_deref(),
+            allowed.as_deref(),
+            provider_id,
+        ),
+        None => state
+            .router
+            .route_with_allowlist(&request, tenant_id.as_deref(), allowed.as_deref()),
+    });
+    state.traces.record(&request_id, |trace| {
+        trace.routing = Some(match &routed {
+            Ok((_, decision)) => RoutingTrace::selected(decision),
+            Err(e) => RoutingTrace::failed(e.to_string()),
+        });
+    });
+    let (provider, decision) = match routed {
         Ok(result) => {
             collector.end_agent_span(routing_span_id, SpanStatus::Succeeded, None);
             result
         }
+        Err(e @ GatewayError::Authorization { .. }) => {
+            collector.end_agent_span(routing_span_id, SpanStatus::Failed, Some(e.to_string()));
+            state.tracker.complete_error(&request_id, 403, e.to_string());
+            warn!(request_id = %request_id, tenant = ?tenant_id, error = %e, "No allowed provider");
+            return Err(e.into());
+        }
+        Err(e @ GatewayError::Validation { .. }) => {
+            collector.end_agent_span(routing_span_id, SpanStatus::Failed, Some(e.to_string()));
+            state.tracker.complete_error(&request_id, 400, e.to_string());
+            return Err(e.into());
+        }
         Err(e) => {
             collector.end_agent_span(
                 routing_span_id,
@@ -158,6 +364,9 @@ pub async fn chat_completion(
                 Some(e.to_string()),
             );
             state.tracker.complete_error(&request_id, 503, e.to_string());
+            if let Some(stale) = stale_cached_response(&state, &request, &request_id, &post_process).await {
+                return Ok(cached_response(&state, collector, stale, "stale"));
+            }
             let output: ExecutionOutput<GatewayResponse> =
                 collector.finalize_failure(&e.to_string());
             return Ok(Json(output).into_response());
@@ -171,20 +380,63 @@ pub async fn chat_completion(
 
     // Check circuit breaker
     if let Err(err) = circuit_breaker.check() {
+        state.router.release(provider.id());
         state.tracker.complete_error(&request_id, 503, err.to_string());
+        let stale = stale_cached_response(&state, &request, &request_id, &post_process).await;
+        routing_log::record(
+            &state,
+            &request_id,
+            &decision,
+            &RoutingOutcome {
+                stale_cache: stale.is_some(),
+                error: Some(&err.to_string()),
+                ..RoutingOutcome::default()
+            },
+        );
+        if let Some(stale) = stale {
+            return Ok(cached_response(&state, collector, stale, "stale"));
+        }
         let output: ExecutionOutput<GatewayResponse> =
             collector.finalize_failure(&err.to_string());
         return Ok(Json(output).into_response());
     }
 
+    wait_for_provider_backoff(&state, &ctx, provider.id(), &request.model).await;
+
     let start = Instant::now();
 
+    // Streams are logged at dispatch, as failover only applies to
+    // non-streaming requests
+    if streaming {
+        routing_log::record(&state, &request_id, &decision, &RoutingOutcome::default());
+    }
+
+    if streaming && stream_passthrough(&state, provider.as_ref(), &decision) {
+        return handle_passthrough_request(
+            state,
+            request,
+            decision.model,
+            request_id,
+            ctx,
+            tenant.map(str::to_string),
+            stream_permit,
+            provider,
+            circuit_breaker,
+            start,
+            collector,
+        )
+        .await;
+    }
+
     // Handle streaming vs non-streaming
     if streaming {
         handle_streaming_request(
             state,
             request,
             request_id,
+            ctx,
+            tenant.map(str::to_string),
+            stream_permit,
             provider,
             circuit_breaker,
             start,
@@ -196,6 +448,16 @@ pub async fn chat_completion(
             state,
             request,
             request_id,
+            ctx,
+            RouteScope {
+                tenant_id: tenant_id.as_deref(),
+                caller: tenant,
+                allowed: allowed.as_deref(),
+                region: decision.region.clone(),
+                pinned: decision.provider_override.is_some(),
+                decision: &decision,
+            },
+            &post_process,
             provider,
             circuit_breaker,
             start,
@@ -205,31 +467,543 @@ pub async fn chat_completion(
     }
 }
 
+/// Routing inputs a request was routed with, kept for regional failover
+struct RouteScope<'a> {
+    tenant_id: Option<&'a str>,
+    /// Tenant the request's usage is billed to
+    caller: Option<&'a str>,
+    allowed: Option<&'a [String]>,
+    /// Region of the selected provider instance
+    region: Option<String>,
+    /// The caller pinned the provider, so there is no regional failover
+    pinned: bool,
+    /// Decision the request was first routed with
+    decision: &'a gateway_routing::RouteDecision,
+}
+
+/// Tenant a request is scoped to
+///
+/// The authenticated entity's tenant takes precedence over the
+/// `X-Tenant-ID` header.
+fn caller_tenant<'a>(
+    entity: Option<&'a AuthenticatedEntity>,
+    tenant_id: Option<&'a str>,
+) -> Option<&'a str> {
+    entity
+        .and_then(|entity| entity.tenant_id.as_deref())
+        .or(tenant_id)
+}
+
+/// Claim a concurrent stream slot for the caller
+///
+/// Returns `None` when no cap applies to the caller.
+fn acquire_stream_slot(
+    state: &AppState,
+    headers: &HeaderMap,
+    entity: Option<&AuthenticatedEntity>,
+) -> Result<Option<StreamPermit>, ApiError> {
+    let default = state.config().server.max_concurrent_streams_per_tenant;
+    let Some(limit) = stream_limit(entity, default) else {
+        return Ok(None);
+    };
+
+    let key = rate_limit_key(headers, entity);
+    match state.stream_limiter.try_acquire(&key, limit) {
+        Some(permit) => Ok(Some(permit)),
+        None => {
+            warn!(key = %key, limit = limit, "Concurrent stream limit reached");
+            Err(ApiError::rate_limited(format!(
+                "Too many concurrent streams: limit of {limit} reached"
+            ))
+            .with_code("concurrent_stream_limit_exceeded"))
+        }
+    }
+}
+
+/// Delay dispatch while the provider's reported quota is nearly exhausted
+///
+/// The delay never extends past the request deadline.
+async fn wait_for_provider_backoff(
+    state: &AppState,
+    ctx: &RequestContext,
+    provider_id: &str,
+    model: &str,
+) {
+    let key = format!("{provider_id}/{model}");
+    let Some(delay) = state.provider_backoff.backoff(&key) else {
+        return;
+    };
+    let delay = ctx.remaining().map_or(delay, |remaining| delay.min(remaining));
+
+    debug!(
+        provider = %provider_id,
+        model = %model,
+        delay_ms = delay.as_millis(),
+        "Delaying request until provider quota recovers"
+    );
+    tokio::time::sleep(delay).await;
+}
+
+/// Export the provider's reported rate limits and feed the backoff signal
+fn observe_provider_rate_limits(
+    state: &AppState,
+    provider: &dyn gateway_core::LLMProvider,
+    model: &str,
+) {
+    let Some(limits) = provider.rate_limits(model) else {
+        return;
+    };
+
+    state
+        .metrics
+        .update_provider_rate_limits(provider.id(), model, &limits);
+    state
+        .provider_backoff
+        .observe(&format!("{}/{model}", provider.id()), &limits);
+}
+
+/// Reprompt for valid JSON when the request opted into structured output repair
+///
+/// Returns the response to send and, if repair was requested, the outcome.
+/// With `server.json_repair_model` set, reprompts are routed to that model
+/// and each is billed to it as it completes.
+async fn repair_structured_output(
+    state: &AppState,
+    ctx: &RequestContext,
+    scope: &RouteScope<'_>,
+    request_id: &str,
+    provider: &dyn gateway_core::LLMProvider,
+    request: &GatewayRequest,
+    response: GatewayResponse,
+) -> (GatewayResponse, Option<JsonRepairOutcome>) {
+    let max_attempts = match &request.response_format {
+        Some(format) if format.expects_json() => format
+            .max_repair_attempts
+            .unwrap_or(0)
+            .min(state.config().server.max_json_repair_attempts),
+        _ => 0,
+    };
+    if max_attempts == 0 {
+        return (response, None);
+    }
+
+    let repair_model = state.config().server.json_repair_model.clone();
+    let outcome = repair_json(request, response, max_attempts, |mut repair| {
+        let repair_model = repair_model.clone();
+        async move {
+            let Some(model) = repair_model else {
+                state.metrics.record_provider_request_bytes(
+                    provider.id(),
+                    &repair.model,
+                    payload_size(&repair),
+                );
+                return provider.chat_completion_with_context(&repair, ctx).await;
+            };
+
+            repair.model = model;
+            let (repair_provider, _) =
+                state
+                    .router
+                    .route_with_allowlist(&repair, scope.tenant_id, scope.allowed)?;
+            state.metrics.record_provider_request_bytes(
+                repair_provider.id(),
+                &repair.model,
+                payload_size(&repair),
+            );
+            let start = Instant::now();
+            let result = repair_provider.chat_completion_with_context(&repair, ctx).await;
+            match &result {
+                Ok(response) => {
+                    state.router.record_completion(repair_provider.id(), start.elapsed(), true);
+                    state
+                        .cost_tracker
+                        .record_response_usage(
+                            request_id,
+                            scope.caller.map(str::to_string),
+                            &repair.model,
+                            repair_provider.id(),
+                            &response.usage,
+                            start.elapsed(),
+                            true,
+                        )
+                        .await;
+                }
+                Err(e) => state.router.record_failure(repair_provider.id(), start.elapsed(), e),
+            }
+            result
+        }
+    })
+    .await;
+
+    if let Some(error) = &outcome.error {
+        warn!(
+            provider = %provider.id(),
+            model = %request.model,
+            attempts = outcome.attempts,
+            error = %error,
+            "Structured output still invalid after repair attempts"
+        );
+    }
+
+    let response = outcome.response.clone();
+    (response, Some(outcome))
+}
+
+/// Serialized size of a provider payload in bytes
+fn payload_size<T: Serialize>(payload: &T) -> usize {
+    serde_json::to_vec(payload).map_or(0, |bytes| bytes.len())
+}
+
+/// Records the cumulative size of a streamed provider response when dropped
+struct ResponseSizeRecorder {
+    metrics: std::sync::Arc<gateway_telemetry::Metrics>,
+    provider: String,
+    model: String,
+    bytes: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+}
+
+/// How a streamed response ended, as seen by [`StreamCompletion`]
+#[derive(Debug, Default)]
+enum StreamEnd {
+    /// Still streaming; dropped in this state, the client went away
+    #[default]
+    Open,
+    /// The last event was sent
+    Finished,
+    /// The upstream failed mid-stream
+    Failed(String),
+}
+
+/// Completes a streaming request in the tracker when its stream is dropped
+///
+/// A stream dropped while still open was abandoned by the client: the
+/// request is recorded as a client disconnect, which also cancels its
+/// context and with it the upstream call.
+struct StreamCompletion {
+    tracker: std::sync::Arc<gateway_telemetry::RequestTracker>,
+    request_id: String,
+    input_tokens: Option<u32>,
+    end: std::sync::Arc<parking_lot::Mutex<StreamEnd>>,
+}
+
+impl StreamCompletion {
+    fn new(state: &AppState, request_id: &str, input_tokens: Option<u32>) -> Self {
+        Self {
+            tracker: state.tracker.clone(),
+            request_id: request_id.to_string(),
+            input_tokens,
+            end: std::sync::Arc::default(),
+        }
+    }
+
+    /// Mark the stream finished, unless it already failed
+    fn finish(end: &parking_lot::Mutex<StreamEnd>) {
+        let mut end = end.lock();
+        if matches!(*end, StreamEnd::Open) {
+            *end = StreamEnd::Finished;
+        }
+    }
+}
+
+impl Drop for StreamCompletion {
+    fn drop(&mut self) {
+        let end = std::mem::take(&mut *self.end.lock());
+        match end {
+            StreamEnd::Open => self
+                .tracker
+                .complete_client_disconnect(&self.request_id, self.input_tokens),
+            StreamEnd::Finished => {
+                self.tracker
+                    .complete_success(&self.request_id, 200, self.input_tokens, None);
+            }
+            StreamEnd::Failed(error) => self.tracker.complete_error(&self.request_id, 500, error),
+        }
+    }
+}
+
+impl Drop for ResponseSizeRecorder {
+    fn drop(&mut self) {
+        self.metrics.record_provider_response_bytes(
+            &self.provider,
+            &self.model,
+            self.bytes.load(std::sync::atomic::Ordering::Relaxed),
+        );
+    }
+}
+
+/// Look up a stale cached response to serve after a dispatch failure
+async fn stale_cached_response(
+    state: &AppState,
+    request: &GatewayRequest,
+    request_id: &str,
+    post_process: &[String],
+) -> Option<GatewayResponse> {
+    if request.stream {
+        return None;
+    }
+    let cached = state.response_cache.as_ref()?.get_stale(request).await;
+    state.traces.record(request_id, |trace| {
+        trace.cache.get_or_insert_with(CacheTrace::default).stale_lookup = Some(if cached.is_some() {
+            CacheLookup::Hit
+        } else {
+            CacheLookup::Miss
+        });
+    });
+    let mut response = cached?;
+    state.post_processors.apply(post_process, &mut response);
+    info!(
+        request_id = %request_id,
+        model = %request.model,
+        "Serving stale cached response after upstream failure"
+    );
+    Some(response)
+}
+
+/// Look up a fresh cached response before dispatch
+///
+/// The lookup shares the request's deadline. A request cancelled or out of
+/// time by the end of it fails with 499 or 408 rather than being dispatched.
+async fn fresh_cached_response(
+    state: &AppState,
+    ctx: &RequestContext,
+    request: &GatewayRequest,
+    request_id: &str,
+    post_process: &[String],
+) -> Result<Option<GatewayResponse>, ApiError> {
+    let Some(cache) = &state.response_cache else {
+        return Ok(None);
+    };
+    let cached = cache.get_with_context(request, ctx).await;
+    if cached.is_none() && ctx.is_done() {
+        let error = if ctx.is_cancelled() {
+            ApiError::cancelled("Request cancelled")
+        } else {
+            ApiError::request_timeout("Request deadline exceeded")
+        };
+        state.tracker.complete_error(request_id, error.status.as_u16(), error.message.clone());
+        return Err(error);
+    }
+    state.metrics.record_cache_operation("get", cached.is_some());
+    state.traces.record(request_id, |trace| {
+        trace.cache.get_or_insert_with(CacheTrace::default).lookup = Some(if cached.is_some() {
+            CacheLookup::Hit
+        } else {
+            CacheLookup::Miss
+        });
+    });
+    let Some(mut response) = cached else {
+        return Ok(None);
+    };
+    state.tracker.complete_success(
+        request_id,
+        200,
+        Some(response.usage.prompt_tokens),
+        Some(response.usage.completion_tokens),
+    );
+    state.post_processors.apply(post_process, &mut response);
+    debug!(request_id = %request_id, model = %request.model, "Serving cached response");
+    Ok(Some(response))
+}
+
+/// Build a response for a cache entry, marked with `X-Cache: <x_cache>`
+fn cached_response(
+    state: &AppState,
+    collector: ExecutionCollector,
+    response: GatewayResponse,
+    x_cache: &'static str,
+) -> Response {
+    let hash = content_hash(state, &response);
+    let output = collector.finalize_success(response);
+    let mut response = Json(output).into_response();
+    let headers = response.headers_mut();
+    headers.insert("x-cache", header::HeaderValue::from_static(x_cache));
+    if let Some(hash) = hash {
+        headers.insert(response_hash::HEADER, hash);
+    }
+    response
+}
+
+/// Content hash header value, when `server.response_hash` is enabled
+fn content_hash(state: &AppState, response: &GatewayResponse) -> Option<header::HeaderValue> {
+    if !state.config().server.response_hash {
+        return None;
+    }
+    header::HeaderValue::from_str(&ResponseHasher::hash_response(response)).ok()
+}
+
+/// Persist a completed exchange in the background when a store is configured
+#[cfg(feature = "persistence")]
+fn persist_exchange(
+    state: &AppState,
+    request_id: &str,
+    provider_id: &str,
+    request: &GatewayRequest,
+    response: &GatewayResponse,
+) {
+    let Some(store) = state.exchange_store.clone() else {
+        return;
+    };
+
+    match crate::persistence::ExchangeRecord::from_completion(
+        request_id,
+        provider_id,
+        request,
+        response,
+    ) {
+        Ok(record) => {
+            tokio::spawn(async move {
+                if let Err(e) = store.save(&record).await {
+                    error!(request_id = %record.request_id, error = %e, "Failed to persist exchange");
+                }
+            });
+        }
+        Err(e) => error!(request_id = %request_id, error = %e, "Failed to serialize exchange"),
+    }
+}
+
 async fn handle_non_streaming_request(
     state: AppState,
     request: GatewayRequest,
     request_id: String,
-    provider: std::sync::Arc<dyn gateway_core::LLMProvider>,
-    circuit_breaker: std::sync::Arc<gateway_resilience::CircuitBreaker>,
+    ctx: RequestContext,
+    scope: RouteScope<'_>,
+    post_process: &[String],
+    mut provider: std::sync::Arc<dyn gateway_core::LLMProvider>,
+    mut circuit_breaker: std::sync::Arc<gateway_resilience::CircuitBreaker>,
     start: Instant,
     mut collector: ExecutionCollector,
 ) -> Result<Response, ApiError> {
+    let allowed = scope.allowed;
+
     // --- Agent span: provider call ---
     let provider_span_id = collector.start_agent_span(&format!("provider-{}", provider.id()));
 
-    // Execute with retry
-    let result = state
-        .retry_policy
-        .execute(|| async {
-            provider.chat_completion(&request).await
-        })
-        .await;
+    // Execute with retry, all attempts bounded by the request deadline. A
+    // regional instance that fails hands over to the next-fastest region,
+    // and an overloaded provider to any other provider rather than being
+    // retried, unless the route is pinned or nothing else can serve it.
+    let request_bytes = payload_size(&request);
+    let mut region = scope.region.clone();
+    let mut failed_over = Vec::new();
+    let handoff_policy = state.retry_policy.without_overloaded_retries();
+    let mut policy = if scope.pinned {
+        state.retry_policy.as_ref()
+    } else {
+        &handoff_policy
+    };
+    // Duration of the latest provider call, excluding retry backoff
+    let generation_time = parking_lot::Mutex::new(std::time::Duration::ZERO);
+    let result = loop {
+        let attempt_start = Instant::now();
+        let calls = std::sync::atomic::AtomicU32::new(0);
+        let result = policy
+            .execute_with_context(&ctx, || async {
+                calls.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                state
+                    .metrics
+                    .record_provider_request_bytes(provider.id(), &request.model, request_bytes);
+                let call_start = Instant::now();
+                let result = provider.chat_completion_with_context(&request, &ctx).await;
+                *generation_time.lock() = call_start.elapsed();
+                result
+            })
+            .await;
+
+        let attempt = AttemptTrace::new(
+            provider.id(),
+            region.clone(),
+            calls.into_inner(),
+            attempt_start.elapsed(),
+        );
+        state.traces.record(&request_id, |trace| {
+            trace.attempts.push(match &result {
+                Ok(_) => attempt,
+                Err(e) => attempt.with_error(e.to_string()),
+            });
+        });
+
+        // Requests the provider rejected would fail in every region
+        let Err(e) = &result else { break result };
+        let rejected = e.status_code().is_client_error() && !e.is_retryable();
+        let overloaded = e.provider_error_kind() == ProviderErrorKind::Overloaded;
+        if (region.is_none() && !overloaded) || scope.pinned || rejected || ctx.is_done() {
+            break result;
+        }
+        failed_over.push(provider.id().to_string());
+        let next = state
+            .router
+            .route_excluding(&request, scope.tenant_id, allowed, &failed_over)
+            .ok()
+            .filter(|(_, decision)| overloaded || decision.region.is_some());
+        let Some((next, decision)) = next else {
+            if !overloaded || policy.config().retry_overloaded {
+                break result;
+            }
+            // Nowhere else to go: back off, then retry the overloaded provider
+            let delay = policy.delay_for_error(0, e);
+            if ctx.remaining().is_some_and(|remaining| remaining <= delay) {
+                break result;
+            }
+            tokio::time::sleep(delay).await;
+            failed_over.pop();
+            policy = state.retry_policy.as_ref();
+            continue;
+        };
+
+        if overloaded {
+            warn!(
+                request_id = %request_id,
+                from = %provider.id(),
+                to = %next.id(),
+                error = %e,
+                "Provider overloaded, failing over to another provider"
+            );
+        } else {
+            warn!(
+                request_id = %request_id,
+                from = ?region,
+                to = ?decision.region,
+                error = %e,
+                "Regional provider failed, failing over to next region"
+            );
+        }
+        webhooks::record_breaker_error(&state, &circuit_breaker, e);
+        state.metrics.record_error(provider.id(), &e.to_string());
+        state
+            .router
+            .record_failure(provider.id(), attempt_start.elapsed(), e);
+        state.tracker.update_provider(&request_id, next.id());
+
+        circuit_breaker = state.circuit_breakers.get_or_create(next.id());
+        provider = next;
+        region = decision.region;
+    };
 
     let duration = start.elapsed();
+    observe_provider_rate_limits(&state, provider.as_ref(), &request.model);
 
     match result {
         Ok(response) => {
             circuit_breaker.record_success();
+            state.metrics.record_provider_response_bytes(
+                provider.id(),
+                &request.model,
+                payload_size(&response),
+            );
+
+            // Usage of the original call alone, billed to the requested
+            // model when repairs are billed to a separate repair model
+            let primary_usage = response.usage.clone();
+            let (response, repair) = repair_structured_output(
+                &state,
+                &ctx,
+                &scope,
+                &request_id,
+                provider.as_ref(),
+                &request,
+                response,
+            )
+            .await;
 
             // Attach usage metrics as artifact on the provider span
             collector.attach_artifact(
@@ -271,8 +1045,51 @@ async fn handle_non_streaming_request(
                 streaming: false,
                 tenant_id: None,
             });
+            state
+                .metrics
+                .record_prompt_tokens(&request.model, TokenSource::Usage, usage.prompt_tokens);
+            state
+                .metrics
+                .record_completion_tokens(&request.model, usage.completion_tokens);
+            state.metrics.record_generation_throughput(
+                provider.id(),
+                &request.model,
+                usage.completion_tokens,
+                generation_time.into_inner(),
+            );
 
             state.router.record_completion(provider.id(), duration, true);
+            let was_over_budget = webhooks::over_budget(&state, scope.caller).await;
+            let billed_usage = if state.config().server.json_repair_model.is_some() {
+                &primary_usage
+            } else {
+                usage
+            };
+            state
+                .cost_tracker
+                .record_response_usage(
+                    &request_id,
+                    scope.caller.map(str::to_string),
+                    &request.model,
+                    provider.id(),
+                    billed_usage,
+                    duration,
+                    true,
+                )
+                .await;
+            webhooks::notify_budget_exceeded(&state, scope.caller, was_over_budget).await;
+            webhooks::emit(&state, WebhookEventType::RequestCompleted, || {
+                serde_json::json!({
+                    "request_id": request_id,
+                    "tenant_id": scope.caller,
+                    "model": request.model,
+                    "provider": provider.id(),
+                    "streaming": false,
+                    "prompt_tokens": usage.prompt_tokens,
+                    "completion_tokens": usage.completion_tokens,
+                    "latency_ms": duration.as_millis(),
+                })
+            });
 
             info!(
                 request_id = %request_id,
@@ -280,12 +1097,50 @@ async fn handle_non_streaming_request(
                 duration_ms = duration.as_millis(),
                 "Chat completion successful"
             );
+            routing_log::record(
+                &state,
+                &request_id,
+                scope.decision,
+                &RoutingOutcome {
+                    failed_over_to: (!failed_over.is_empty()).then(|| provider.id()),
+                    ..RoutingOutcome::default()
+                },
+            );
+
+            #[cfg(feature = "persistence")]
+            persist_exchange(&state, &request_id, provider.id(), &request, &response);
+
+            crate::shadow::mirror_request(&state, &request, provider.id(), &response, allowed);
+
+            let repaired = repair.as_ref().map_or(true, JsonRepairOutcome::is_valid);
+            if let (Some(cache), true) = (&state.response_cache, repaired) {
+                cache.put(&request, response.clone()).await;
+                state.traces.record(&request_id, |trace| {
+                    trace.cache.get_or_insert_with(CacheTrace::default).stored = true;
+                });
+            }
+
+            // Cached unprocessed, as other callers may select other processors
+            let mut response = response;
+            state.post_processors.apply(post_process, &mut response);
+            let hash = content_hash(&state, &response);
 
             let output = collector.finalize_success(response);
-            Ok(Json(output).into_response())
+            let mut response = Json(output).into_response();
+            let headers = response.headers_mut();
+            if let Some(repair) = repair {
+                headers.insert("x-json-repair-attempts", repair.attempts.into());
+                if !repair.is_valid() {
+                    headers.insert("x-json-repair", header::HeaderValue::from_static("failed"));
+                }
+            }
+            if let Some(hash) = hash {
+                headers.insert(response_hash::HEADER, hash);
+            }
+            Ok(response)
         }
         Err(e) => {
-            circuit_breaker.record_failure();
+            webhooks::record_breaker_error(&state, &circuit_breaker, &e);
 
             collector.end_agent_span(
                 provider_span_id,
@@ -293,9 +1148,11 @@ async fn handle_non_streaming_request(
                 Some(e.to_string()),
             );
 
-            state.tracker.complete_error(&request_id, 500, e.to_string());
+            // A cancelled request is recorded as closed by the client
+            let status = if ctx.is_cancelled() { 499 } else { 500 };
+            state.tracker.complete_error(&request_id, status, e.to_string());
             state.metrics.record_error(provider.id(), &e.to_string());
-            state.router.record_completion(provider.id(), duration, false);
+            state.router.record_failure(provider.id(), duration, &e);
 
             error!(
                 request_id = %request_id,
@@ -304,6 +1161,21 @@ async fn handle_non_streaming_request(
                 "Chat completion failed"
             );
 
+            let stale = stale_cached_response(&state, &request, &request_id, post_process).await;
+            routing_log::record(
+                &state,
+                &request_id,
+                scope.decision,
+                &RoutingOutcome {
+                    failed_over_to: (!failed_over.is_empty()).then(|| provider.id()),
+                    stale_cache: stale.is_some(),
+                    error: Some(&e.to_string()),
+                },
+            );
+            if let Some(stale) = stale {
+                return Ok(cached_response(&state, collector, stale, "stale"));
+            }
+
             let output: ExecutionOutput<GatewayResponse> =
                 collector.finalize_failure(&e.to_string());
             Ok(Json(output).into_response())
@@ -315,74 +1187,269 @@ async fn handle_streaming_request(
     state: AppState,
     request: GatewayRequest,
     request_id: String,
+    ctx: RequestContext,
+    caller: Option<String>,
+    stream_permit: Option<StreamPermit>,
     provider: std::sync::Arc<dyn gateway_core::LLMProvider>,
     circuit_breaker: std::sync::Arc<gateway_resilience::CircuitBreaker>,
-    _start: Instant,
+    start: Instant,
     mut collector: ExecutionCollector,
 ) -> Result<Response, ApiError> {
     // --- Agent span: streaming provider call ---
     let provider_span_id = collector.start_agent_span(&format!("provider-{}-stream", provider.id()));
 
-    // Get streaming response
-    let stream_result = provider.chat_completion_stream(&request).await;
+    // Get streaming response; the deadline bounds connection setup, the
+    // stream itself is bounded by `max_stream_duration`
+    state
+        .metrics
+        .record_provider_request_bytes(provider.id(), &request.model, payload_size(&request));
+    let retry_before_first_chunk = state.config().server.stream_retry_before_first_chunk;
+    let connect_start = Instant::now();
+    let calls = std::sync::atomic::AtomicU32::new(0);
+    let connect = || {
+        calls.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        provider.chat_completion_stream(&request)
+    };
+    let stream_result = if retry_before_first_chunk {
+        state.retry_policy.execute_with_context(&ctx, connect).await
+    } else {
+        ctx.run(connect()).await
+    };
+    observe_provider_rate_limits(&state, provider.as_ref(), &request.model);
+
+    let attempt =
+        AttemptTrace::new(provider.id(), None, calls.into_inner(), connect_start.elapsed());
+    state.traces.record(&request_id, |trace| {
+        trace.attempts.push(match &stream_result {
+            Ok(_) => attempt,
+            Err(e) => attempt.with_error(e.to_string()),
+        });
+    });
 
     match stream_result {
         Ok(chunk_stream) => {
+            // A failure before any chunk is invisible to the client, so the
+            // provider can be asked again
+            let restart_provider = provider.clone();
+            let restart_request = request.clone();
+            let restart = move || {
+                let provider = restart_provider.clone();
+                let request = restart_request.clone();
+                async move {
+                    warn!(provider = %provider.id(), "Stream failed before first chunk, restarting");
+                    provider.chat_completion_stream(&request).await
+                }
+            };
+            let chunk_stream = if retry_before_first_chunk {
+                let policy = state.retry_policy.clone();
+                with_retry_before_first_chunk(
+                    chunk_stream,
+                    move |error, attempt| {
+                        (attempt < policy.config().max_retries && policy.is_retryable(error))
+                            .then(|| policy.delay_for_error(attempt, error))
+                    },
+                    restart,
+                )
+            } else {
+                with_reset_restart(
+                    chunk_stream,
+                    state.config().server.stream_reset_restarts,
+                    restart,
+                )
+            };
+
+            let max_stream_duration = state.config().server.max_stream_duration;
+            let chunk_stream = with_max_duration(chunk_stream, max_stream_duration);
+            let chunk_stream = with_cancellation(Box::pin(chunk_stream), ctx.clone());
+
             // End the agent span and finalize for the metadata event
             collector.end_agent_span(provider_span_id, SpanStatus::Succeeded, None);
             let exec_output: ExecutionOutput<()> = collector.finalize_success(());
-            let exec_json = serde_json::to_string(&exec_output).unwrap_or_default();
+
+            // Content is hashed as it streams and reported in the
+            // execution_output event
+            let hasher = state
+                .config()
+                .server
+                .response_hash
+                .then(|| std::sync::Arc::new(parking_lot::Mutex::new(ResponseHasher::new())));
+            let chunk_hasher = hasher.clone();
 
             // Record first chunk time
             let first_chunk_received = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
             let first_chunk_flag = first_chunk_received;
             let tracker = state.tracker.clone();
             let request_id_clone = request_id.clone();
+            let response_size = ResponseSizeRecorder {
+                metrics: state.metrics.clone(),
+                provider: provider.id().to_string(),
+                model: request.model.clone(),
+                bytes: std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0)),
+            };
+            let response_bytes = response_size.bytes.clone();
+            let in_flight = state.router.in_flight_guard(provider.id());
+            let prompt_tokens = state.token_estimator.estimate_prompt(&request);
+            let completion = StreamCompletion::new(&state, &request_id, Some(prompt_tokens));
+            let chunk_end = completion.end.clone();
+            let done_end = completion.end.clone();
+            let estimator = state.token_estimator.clone();
+            let estimate_model = request.model.clone();
+            let chunk_metrics = state.metrics.clone();
+            let chunk_provider = provider.id().to_string();
+            let usage_end = completion.end.clone();
+
+            // Generated tokens are billed even if the client disconnects
+            // mid-stream, when the meter is dropped unfinished
+            let meter = std::sync::Arc::new(
+                StreamCostMeter::new(
+                    state.cost_tracker.clone(),
+                    &request_id,
+                    &request.model,
+                    provider.id(),
+                    prompt_tokens,
+                )
+                .with_tenant(caller),
+            );
+            let chunk_meter = meter.clone();
+
+            // Usage is pulled out of the chunks it arrives in and re-emitted
+            // as one terminal chunk, only if the client asked for it
+            let usage = std::sync::Arc::new(parking_lot::Mutex::new(StreamUsage::default()));
+            let chunk_usage = usage.clone();
+            let tail_bytes = response_bytes.clone();
 
             // Create SSE stream
-            let sse_stream = chunk_stream.map(move |chunk_result| {
-                match chunk_result {
-                    Ok(chunk) => {
+            let sse_stream = chunk_stream.filter_map(move |chunk_result| {
+                let event = match chunk_result {
+                    Ok(mut chunk) => {
                         // Record first token time
                         if !first_chunk_flag.swap(true, std::sync::atomic::Ordering::Relaxed) {
                             tracker.record_first_token(&request_id_clone);
                         }
 
+                        if let Some(hasher) = &chunk_hasher {
+                            hasher.lock().push_chunk(&chunk);
+                        }
+
                         // Count tokens
                         if let Some(choice) = chunk.choices.first() {
                             if let Some(content) = &choice.delta.content {
-                                // Rough token estimate: ~4 chars per token
-                                let token_count = (content.len() / 4).max(1) as u32;
+                                let token_count =
+                                    estimator.count_text(&estimate_model, content).max(1);
                                 tracker.record_tokens(&request_id_clone, token_count);
+                                let mut usage = chunk_usage.lock();
+                                usage.estimated_completion_tokens += token_count;
+                                chunk_meter.add_completion_tokens(token_count);
+                                if !content.is_empty() {
+                                    if usage.timing.first.is_none() {
+                                        chunk_metrics.record_first_token(
+                                            &chunk_provider,
+                                            &estimate_model,
+                                            start.elapsed(),
+                                        );
+                                    }
+                                    usage.timing.mark();
+                                }
+                            }
+                        }
+
+                        let reported = chunk.usage.take();
+                        {
+                            let mut usage = chunk_usage.lock();
+                            usage.chunk_id = Some(chunk.id.clone());
+                            if let Some(reported) = reported {
+                                chunk_meter.observe_usage(&reported);
+                                usage.reported = Some(reported);
                             }
                         }
 
-                        let data = serde_json::to_string(&chunk).unwrap_or_default();
-                        Ok::<_, Infallible>(Event::default().data(data))
+                        if chunk.choices.is_empty() {
+                            None
+                        } else {
+                            let data = serde_json::to_string(&chunk).unwrap_or_default();
+                            response_bytes
+                                .fetch_add(data.len(), std::sync::atomic::Ordering::Relaxed);
+                            Some(Ok::<_, Infallible>(Event::default().data(data)))
+                        }
                     }
                     Err(e) => {
+                        *chunk_end.lock() = StreamEnd::Failed(e.to_string());
                         let error_event = serde_json::json!({
                             "error": {
                                 "message": e.to_string(),
-                                "type": "stream_error"
+                                "type": "stream_error",
+                                "code": e.error_code()
                             }
                         });
-                        Ok(Event::default().data(error_event.to_string()))
+                        Some(Ok(Event::default().data(error_event.to_string())))
                     }
-                }
+                };
+                futures::future::ready(event)
             });
 
-            // Add [DONE] event followed by execution_output event
-            let done_stream = futures::stream::iter(vec![
-                Ok::<_, Infallible>(Event::default().data("[DONE]")),
-                Ok::<_, Infallible>(
-                    Event::default()
-                        .event("execution_output")
-                        .data(exec_json),
-                ),
-            ]);
-
-            let full_stream = sse_stream.chain(done_stream);
+            let include_usage = request.includes_stream_usage();
+            let estimate = state.config().server.estimate_stream_usage;
+            let model = request.model.clone();
+            let stream_provider = provider.id().to_string();
+            let stream_metrics = state.metrics.clone();
+            let usage_stream = futures::stream::once(async move {
+                let usage = std::mem::take(&mut *usage.lock());
+                usage.record(&stream_metrics, &stream_provider, &model);
+                let failed = matches!(*usage_end.lock(), StreamEnd::Failed(_));
+                if usage.timing.first.is_none() && failed {
+                    stream_metrics.record_stream_no_token(&stream_provider, &model);
+                }
+                meter.finish(!failed).await;
+                if !include_usage {
+                    return None;
+                }
+                let chunk = usage.final_chunk(&model, prompt_tokens, estimate)?;
+                let data = serde_json::to_string(&chunk).unwrap_or_default();
+                tail_bytes.fetch_add(data.len(), std::sync::atomic::Ordering::Relaxed);
+                Some(Ok::<_, Infallible>(Event::default().data(data)))
+            })
+            .filter_map(futures::future::ready);
+
+            // Add [DONE] event followed by execution_output event, built
+            // once the content hash is known
+            let done_stream = futures::stream::once(async move {
+                StreamCompletion::finish(&done_end);
+                let exec_json = match hasher {
+                    Some(hasher) => {
+                        let summary = StreamSummary {
+                            response_hash: hasher.lock().finish(),
+                        };
+                        serde_json::to_string(&ExecutionOutput {
+                            execution_id: exec_output.execution_id,
+                            repo_span: exec_output.repo_span,
+                            agent_spans: exec_output.agent_spans,
+                            result: exec_output.result.map(|()| summary),
+                            success: exec_output.success,
+                        })
+                    }
+                    None => serde_json::to_string(&exec_output),
+                };
+                futures::stream::iter(vec![
+                    Ok::<_, Infallible>(Event::default().data("[DONE]")),
+                    Ok::<_, Infallible>(
+                        Event::default()
+                            .event("execution_output")
+                            .data(exec_json.unwrap_or_default()),
+                    ),
+                ])
+            })
+            .flatten();
+
+            // The permit, size recorder, in-flight slot, and completion live
+            // in the stream, so they are released when the stream finishes or
+            // the client disconnects
+            let full_stream = sse_stream.chain(usage_stream).chain(done_stream).map(move |event| {
+                let _permit = &stream_permit;
+                let _response_size = &response_size;
+                let _in_flight = &in_flight;
+                let _completion = &completion;
+                event
+            });
 
             // Record success after stream setup
             circuit_breaker.record_success();
@@ -392,7 +1459,7 @@ async fn handle_streaming_request(
                 .into_response())
         }
         Err(e) => {
-            circuit_breaker.record_failure();
+            webhooks::record_breaker_error(&state, &circuit_breaker, &e);
 
             collector.end_agent_span(
                 provider_span_id,
@@ -401,6 +1468,8 @@ async fn handle_streaming_request(
             );
 
             state.tracker.complete_error(&request_id, 500, e.to_string());
+            state.router.record_failure(provider.id(), start.elapsed(), &e);
+            state.metrics.record_stream_no_token(provider.id(), &request.model);
 
             error!(
                 request_id = %request_id,
@@ -415,6 +1484,291 @@ async fn handle_streaming_request(
     }
 }
 
+/// Whether a stream is forwarded verbatim
+///
+/// A matching routing rule's setting takes precedence over the provider's.
+/// Providers without raw stream support are always parsed.
+fn stream_passthrough(
+    state: &AppState,
+    provider: &dyn gateway_core::LLMProvider,
+    decision: &gateway_routing::RouteDecision,
+) -> bool {
+    let enabled = decision.stream_passthrough.unwrap_or_else(|| {
+        state
+            .config()
+            .providers
+            .iter()
+            .any(|config| config.id == provider.id() && config.stream_passthrough)
+    });
+    enabled && provider.supports_raw_stream()
+}
+
+/// Stream the provider's SSE body to the client without re-serializing it
+///
+/// The request is dispatched under the routed model, and the client's model
+/// name is written back into the events if the two differ. Usage from the
+/// terminal event is billed once the stream ends.
+async fn handle_passthrough_request(
+    state: AppState,
+    request: GatewayRequest,
+    routed_model: String,
+    request_id: String,
+    ctx: RequestContext,
+    caller: Option<String>,
+    stream_permit: Option<StreamPermit>,
+    provider: std::sync::Arc<dyn gateway_core::LLMProvider>,
+    circuit_breaker: std::sync::Arc<gateway_resilience::CircuitBreaker>,
+    start: Instant,
+    mut collector: ExecutionCollector,
+) -> Result<Response, ApiError> {
+    let provider_span_id = collector.start_agent_span(&format!("provider-{}-stream", provider.id()));
+
+    // Usage is always requested so the stream can be billed
+    let mut upstream = request.clone();
+    upstream.model = routed_model;
+    upstream.stream_options = Some(StreamOptions { include_usage: true });
+    let alias = (upstream.model != request.model).then(|| request.model.clone());
+    let relay = SseRelay::new(alias, !request.includes_stream_usage());
+    let progress = relay.progress();
+
+    state
+        .metrics
+        .record_provider_request_bytes(provider.id(), &request.model, payload_size(&upstream));
+    let connect_start = Instant::now();
+    let stream_result = ctx.run(provider.chat_completion_stream_raw(&upstream)).await;
+    observe_provider_rate_limits(&state, provider.as_ref(), &upstream.model);
+
+    let attempt = AttemptTrace::new(provider.id(), None, 1, connect_start.elapsed());
+    state.traces.record(&request_id, |trace| {
+        trace.attempts.push(match &stream_result {
+            Ok(_) => attempt,
+            Err(e) => attempt.with_error(e.to_string()),
+        });
+    });
+
+    match stream_result {
+        Ok(body) => {
+            collector.end_agent_span(provider_span_id, SpanStatus::Succeeded, None);
+
+            let usage = relay.usage();
+            let response_size = ResponseSizeRecorder {
+                metrics: state.metrics.clone(),
+                provider: provider.id().to_string(),
+                model: request.model.clone(),
+                bytes: std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0)),
+            };
+            let response_bytes = response_size.bytes.clone();
+            let in_flight = state.router.in_flight_guard(provider.id());
+
+            let prompt_tokens = state.token_estimator.estimate_prompt(&request);
+            let completion = StreamCompletion::new(&state, &request_id, Some(prompt_tokens));
+            let billing_end = completion.end.clone();
+            let tracker = state.tracker.clone();
+            let first_chunk_request_id = request_id.clone();
+            let first_chunk_received = std::sync::atomic::AtomicBool::new(false);
+            let timing = std::sync::Arc::new(parking_lot::Mutex::new(TokenTiming::default()));
+            let chunk_timing = timing.clone();
+            let chunk_progress = progress.clone();
+            let chunk_metrics = state.metrics.clone();
+            let chunk_provider = provider.id().to_string();
+            let chunk_model = request.model.clone();
+            let mut first_token_recorded = false;
+            let body = passthrough::relay_stream(
+                body,
+                relay,
+                ctx,
+                state.config().server.max_stream_duration,
+            )
+            .inspect(move |bytes| {
+                if !first_chunk_received.swap(true, std::sync::atomic::Ordering::Relaxed) {
+                    tracker.record_first_token(&first_chunk_request_id);
+                }
+                if !first_token_recorded && chunk_progress.has_content() {
+                    first_token_recorded = true;
+                    chunk_metrics.record_first_token(&chunk_provider, &chunk_model, start.elapsed());
+                }
+                if let Ok(bytes) = bytes {
+                    response_bytes.fetch_add(bytes.len(), std::sync::atomic::Ordering::Relaxed);
+                    chunk_timing.lock().mark();
+                }
+            });
+
+            // Bill the usage the upstream reported once the body has ended
+            let billing_state = state.clone();
+            let provider_id = provider.id().to_string();
+            let model = request.model.clone();
+            let billing = futures::stream::once(async move {
+                StreamCompletion::finish(&billing_end);
+                if progress.failed() && !progress.has_content() {
+                    billing_state.metrics.record_stream_no_token(&provider_id, &model);
+                }
+                let reported = usage.lock().take();
+                if let Some(usage) = reported {
+                    let metrics = &billing_state.metrics;
+                    metrics.record_prompt_tokens(&model, TokenSource::Usage, usage.prompt_tokens);
+                    metrics.record_completion_tokens(&model, usage.completion_tokens);
+                    metrics.record_generation_throughput(
+                        &provider_id,
+                        &model,
+                        usage.completion_tokens,
+                        timing.lock().elapsed(),
+                    );
+                    billing_state.tracker.record_tokens(&request_id, usage.completion_tokens);
+                    let was_over_budget =
+                        webhooks::over_budget(&billing_state, caller.as_deref()).await;
+                    billing_state
+                        .cost_tracker
+                        .record_response_usage(
+                            &request_id,
+                            caller.clone(),
+                            &model,
+                            &provider_id,
+                            &usage,
+                            start.elapsed(),
+                            true,
+                        )
+                        .await;
+                    webhooks::notify_budget_exceeded(
+                        &billing_state,
+                        caller.as_deref(),
+                        was_over_budget,
+                    )
+                    .await;
+                    webhooks::emit(&billing_state, WebhookEventType::RequestCompleted, || {
+                        serde_json::json!({
+                            "request_id": request_id,
+                            "tenant_id": caller,
+                            "model": model,
+                            "provider": provider_id,
+                            "streaming": true,
+                            "prompt_tokens": usage.prompt_tokens,
+                            "completion_tokens": usage.completion_tokens,
+                            "latency_ms": start.elapsed().as_millis(),
+                        })
+                    });
+                }
+                None::<Result<bytes::Bytes, Infallible>>
+            })
+            .filter_map(futures::future::ready);
+
+            // As for parsed streams, the permit, size recorder, in-flight
+            // slot, and completion are released when the body is dropped
+            let full_stream = body.chain(billing).map(move |bytes| {
+                let _permit = &stream_permit;
+                let _response_size = &response_size;
+                let _in_flight = &in_flight;
+                let _completion = &completion;
+                bytes
+            });
+
+            circuit_breaker.record_success();
+
+            Ok((
+                [
+                    (header::CONTENT_TYPE, "text/event-stream"),
+                    (header::CACHE_CONTROL, "no-cache"),
+                ],
+                axum::body::Body::from_stream(full_stream),
+            )
+                .into_response())
+        }
+        Err(e) => {
+            webhooks::record_breaker_error(&state, &circuit_breaker, &e);
+            collector.end_agent_span(provider_span_id, SpanStatus::Failed, Some(e.to_string()));
+
+            state.tracker.complete_error(&request_id, 500, e.to_string());
+            state.router.record_failure(provider.id(), start.elapsed(), &e);
+            state.metrics.record_stream_no_token(provider.id(), &request.model);
+
+            error!(
+                request_id = %request_id,
+                provider = %provider.id(),
+                error = %e,
+                "Passthrough streaming request failed"
+            );
+
+            let output: ExecutionOutput<()> = collector.finalize_failure(&e.to_string());
+            Ok(Json(output).into_response())
+        }
+    }
+}
+
+/// First and last token arrival times of a streamed response
+#[derive(Debug, Default, Clone, Copy)]
+struct TokenTiming {
+    first: Option<Instant>,
+    last: Option<Instant>,
+}
+
+impl TokenTiming {
+    /// Note that a token arrived now
+    fn mark(&mut self) {
+        let now = Instant::now();
+        self.first.get_or_insert(now);
+        self.last = Some(now);
+    }
+
+    /// Time from the first to the last token
+    fn elapsed(&self) -> std::time::Duration {
+        match (self.first, self.last) {
+            (Some(first), Some(last)) => last.duration_since(first),
+            _ => std::time::Duration::ZERO,
+        }
+    }
+}
+
+/// Token usage bookkeeping for one streamed response
+#[derive(Debug, Default)]
+struct StreamUsage {
+    /// Last usage reported by the provider
+    reported: Option<Usage>,
+    /// Completion tokens counted by the gateway's own ~4 chars/token estimate
+    estimated_completion_tokens: u32,
+    /// ID of the provider's chunks, reused for the usage chunk
+    chunk_id: Option<String>,
+    /// When content was first and last streamed
+    timing: TokenTiming,
+}
+
+impl StreamUsage {
+    /// Record the stream's token counts once it has ended
+    ///
+    /// Without reported usage, the completion count is the gateway's
+    /// estimate and no prompt usage is recorded.
+    fn record(&self, metrics: &gateway_telemetry::Metrics, provider: &str, model: &str) {
+        let completion_tokens = match &self.reported {
+            Some(usage) => {
+                metrics.record_prompt_tokens(model, TokenSource::Usage, usage.prompt_tokens);
+                usage.completion_tokens
+            }
+            None => self.estimated_completion_tokens,
+        };
+        metrics.record_completion_tokens(model, completion_tokens);
+        metrics.record_generation_throughput(
+            provider,
+            model,
+            completion_tokens,
+            self.timing.elapsed(),
+        );
+    }
+
+    /// The terminal usage chunk, if usage is known
+    ///
+    /// Falls back to the gateway's estimate when the provider reported
+    /// nothing and `estimate` is set.
+    fn final_chunk(self, model: &str, prompt_tokens: u32, estimate: bool) -> Option<ChatChunk> {
+        let usage = self.reported.or_else(|| {
+            estimate.then(|| Usage::new(prompt_tokens, self.estimated_completion_tokens))
+        })?;
+
+        let mut chunk = ChatChunk::builder().model(model).usage(usage);
+        if let Some(id) = self.chunk_id {
+            chunk = chunk.id(id);
+        }
+        Some(chunk.build())
+    }
+}
+
 /// Provider status response
 #[derive(Debug, Serialize)]
 pub struct ProviderStatus {
@@ -475,6 +1829,593 @@ pub async fn gateway_stats(State(state): State<AppState>) -> Json<GatewayStats>
     })
 }
 
+/// GET /admin/requests/{id}/trace - Lifecycle trace of one request
+///
+/// Requires an authenticated caller holding `server.request_trace.scope`.
+/// Unknown requests, and requests older than the retention window, return
+/// `404`.
+pub async fn request_trace(
+    State(state): State<AppState>,
+    Path(request_id): Path<String>,
+    entity: Option<Extension<AuthenticatedEntity>>,
+) -> Result<Json<trace::RequestTraceDocument>, ApiError> {
+    let entity = entity
+        .map(|Extension(e)| e)
+        .filter(|e| e.auth_method != AuthMethod::Anonymous)
+        .ok_or_else(|| ApiError::unauthorized("Authentication required"))?;
+
+    let scope = &state.config().server.request_trace.scope;
+    if !entity.scopes.contains(scope) {
+        warn!(user_id = %entity.id, request_id = %request_id, "Refused request trace without admin scope");
+        return Err(ApiError::forbidden(format!(
+            "Reading request traces requires the '{scope}' scope"
+        )));
+    }
+
+    trace::export(&state, &request_id)
+        .await
+        .map(Json)
+        .ok_or_else(|| {
+            ApiError::not_found(format!("No trace for request '{request_id}'"))
+                .with_code("trace_not_found")
+        })
+}
+
+/// Rate limit status for the calling tenant or entity
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RateLimitStatus {
+    /// Whether rate limiting is enforced
+    pub enabled: bool,
+    /// Requests allowed per window
+    pub limit: u32,
+    /// Requests remaining right now
+    pub remaining: u32,
+    /// Seconds until the bucket is full again
+    pub reset_seconds: u64,
+    /// Window length in seconds
+    pub window_seconds: u64,
+    /// Tokens allowed per window (if a token budget is configured)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_limit: Option<u32>,
+    /// Tokens remaining right now (if a token budget is configured)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tokens_remaining: Option<u32>,
+}
+
+/// POST /v1/images/generations - Generate images (OpenAI compatible)
+///
+/// Dispatches to the highest-priority provider for the requested model that
+/// supports image generation. Models served only by providers without image
+/// support are rejected with `unsupported_capability`, and providers outside
+/// the tenant's allowlist with `403`.
+#[instrument(skip(state, entity, body), fields(model = %body.model))]
+pub async fn image_generation(
+    State(state): State<AppState>,
+    RequestId(request_id): RequestId,
+    TenantId(tenant_id): TenantId,
+    entity: Option<Extension<AuthenticatedEntity>>,
+    JsonBody(body): JsonBody<ImageRequest>,
+) -> Result<Json<ImageResponse>, ApiError> {
+    body.validate()?;
+
+    let provider = state.providers.get_image_provider(&body.model)?;
+    ensure_provider_allowed(
+        &state,
+        entity.as_deref(),
+        tenant_id.as_deref(),
+        provider.id(),
+        &body.model,
+    )?;
+    let images = provider
+        .as_image_provider()
+        .ok_or_else(|| GatewayError::unsupported_capability(provider.id(), "image_generation"))?;
+
+    debug!(
+        request_id = %request_id,
+        provider = %provider.id(),
+        model = %body.model,
+        n = ?body.n,
+        "Processing image generation request"
+    );
+
+    let ctx = RequestContext::with_timeout(state.config().server.request_timeout);
+    let start = Instant::now();
+    let result = ctx.run(images.generate_images(&body)).await;
+    let duration = start.elapsed();
+
+    let status_code = match &result {
+        Ok(_) => 200,
+        Err(e) => e.status_code().as_u16(),
+    };
+    state.metrics.record_request(&gateway_telemetry::RequestMetrics {
+        model: body.model.clone(),
+        provider: provider.id().to_string(),
+        latency: duration,
+        success: result.is_ok(),
+        status_code,
+        input_tokens: None,
+        output_tokens: None,
+        streaming: false,
+        tenant_id: None,
+    });
+
+    match result {
+        Ok(response) => {
+            info!(
+                request_id = %request_id,
+                provider = %provider.id(),
+                images = response.data.len(),
+                duration_ms = duration.as_millis(),
+                "Image generation successful"
+            );
+            Ok(Json(response))
+        }
+        Err(e) => {
+            state.metrics.record_error(provider.id(), &e.to_string());
+            error!(
+                request_id = %request_id,
+                provider = %provider.id(),
+                error = %e,
+                "Image generation failed"
+            );
+            Err(e.into())
+        }
+    }
+}
+
+/// POST /v1/embeddings - Embed texts (OpenAI compatible)
+///
+/// Dispatches to the highest-priority provider for the requested model that
+/// supports embeddings. Batches over the provider's per-request input limit
+/// are split, sent `server.embedding_batch_concurrency` at a time, and
+/// reassembled in order; inputs of failed sub-requests are listed in
+/// `errors`. With `stream: true`, each sub-request's results are sent as a
+/// server-sent event as it finishes, followed by one with the total usage.
+/// Streams count against the caller's concurrent stream limit.
+#[instrument(skip(state, headers, entity, body), fields(model = %body.model))]
+pub async fn embeddings(
+    State(state): State<AppState>,
+    RequestId(request_id): RequestId,
+    TenantId(tenant_id): TenantId,
+    headers: HeaderMap,
+    entity: Option<Extension<AuthenticatedEntity>>,
+    JsonBody(body): JsonBody<EmbeddingRequest>,
+) -> Result<Response, ApiError> {
+    body.validate()?;
+
+    let provider = state.providers.get_embedding_provider(&body.model)?;
+    ensure_provider_allowed(
+        &state,
+        entity.as_deref(),
+        tenant_id.as_deref(),
+        provider.id(),
+        &body.model,
+    )?;
+    let embedder = provider
+        .as_embedding_provider()
+        .ok_or_else(|| GatewayError::unsupported_capability(provider.id(), "embeddings"))?;
+
+    debug!(
+        request_id = %request_id,
+        provider = %provider.id(),
+        model = %body.model,
+        inputs = body.input.as_slice().len(),
+        "Processing embeddings request"
+    );
+
+    if body.stream {
+        let stream_permit = acquire_stream_slot(&state, &headers, entity.as_deref())?;
+        return Ok(stream_embeddings(state, request_id, provider, body, stream_permit));
+    }
+
+    let config = state.config();
+    let ctx = RequestContext::with_timeout(config.server.request_timeout);
+    let start = Instant::now();
+    let result = ctx
+        .run(embed_batched(embedder, &body, config.server.embedding_batch_concurrency))
+        .await;
+    let duration = start.elapsed();
+
+    let status_code = match &result {
+        Ok(_) => 200,
+        Err(e) => e.status_code().as_u16(),
+    };
+    state.metrics.record_request(&gateway_telemetry::RequestMetrics {
+        model: body.model.clone(),
+        provider: provider.id().to_string(),
+        latency: duration,
+        success: result.is_ok(),
+        status_code,
+        input_tokens: result.as_ref().ok().map(|r| r.usage.prompt_tokens),
+        output_tokens: None,
+        streaming: false,
+        tenant_id: None,
+    });
+
+    match result {
+        Ok(response) => {
+            if !response.errors.is_empty() {
+                warn!(
+                    request_id = %request_id,
+                    provider = %provider.id(),
+                    failed = response.errors.len(),
+                    "Some embedding inputs failed"
+                );
+            }
+            info!(
+                request_id = %request_id,
+                provider = %provider.id(),
+                embeddings = response.data.len(),
+                duration_ms = duration.as_millis(),
+                "Embeddings successful"
+            );
+            Ok(Json(response).into_response())
+        }
+        Err(e) => {
+            state.metrics.record_error(provider.id(), &e.to_string());
+            error!(
+                request_id = %request_id,
+                provider = %provider.id(),
+                error = %e,
+                "Embeddings failed"
+            );
+            Err(e.into())
+        }
+    }
+}
+
+/// Stream the progress of an embeddings request as server-sent events
+///
+/// Sub-requests run in a task that stops dispatching once the client
+/// disconnects or `server.max_stream_duration` elapses, whichever is first.
+/// On timeout an error event ends the stream.
+fn stream_embeddings(
+    state: AppState,
+    request_id: String,
+    provider: std::sync::Arc<dyn gateway_core::LLMProvider>,
+    body: EmbeddingRequest,
+    stream_permit: Option<StreamPermit>,
+) -> Response {
+    let config = state.config();
+    let concurrency = config.server.embedding_batch_concurrency;
+    let max_duration = config.server.max_stream_duration;
+    let (sender, receiver) = tokio::sync::mpsc::channel::<String>(16);
+
+    tokio::spawn(async move {
+        // Held until the stream finishes, fails or times out
+        let _stream_permit = stream_permit;
+        let Some(embedder) = provider.as_embedding_provider() else {
+            return;
+        };
+        let start = Instant::now();
+        let streamed = tokio::time::timeout(max_duration, async {
+            let mut progress = std::pin::pin!(embed_batched_stream(embedder, &body, concurrency));
+            while let Some(event) = progress.next().await {
+                if let EmbeddingProgress::Done {
+                    usage,
+                    embedded,
+                    failed,
+                    ..
+                } = &event
+                {
+                    let duration = start.elapsed();
+                    state.metrics.record_request(&gateway_telemetry::RequestMetrics {
+                        model: body.model.clone(),
+                        provider: provider.id().to_string(),
+                        latency: duration,
+                        success: *embedded > 0,
+                        status_code: 200,
+                        input_tokens: Some(usage.prompt_tokens),
+                        output_tokens: None,
+                        streaming: true,
+                        tenant_id: None,
+                    });
+                    info!(
+                        request_id = %request_id,
+                        provider = %provider.id(),
+                        embeddings = embedded,
+                        failed = failed,
+                        duration_ms = duration.as_millis(),
+                        "Embeddings stream completed"
+                    );
+                }
+                let data = serde_json::to_string(&event).unwrap_or_default();
+                if sender.send(data).await.is_err() {
+                    debug!(request_id = %request_id, "Client disconnected from embeddings stream");
+                    return;
+                }
+            }
+        })
+        .await;
+
+        if streamed.is_err() {
+            let error = GatewayError::timeout(max_duration);
+            state.metrics.record_request(&gateway_telemetry::RequestMetrics {
+                model: body.model.clone(),
+                provider: provider.id().to_string(),
+                latency: start.elapsed(),
+                success: false,
+                status_code: error.status_code().as_u16(),
+                input_tokens: None,
+                output_tokens: None,
+                streaming: true,
+                tenant_id: None,
+            });
+            warn!(
+                request_id = %request_id,
+                provider = %provider.id(),
+                max_duration_ms = max_duration.as_millis(),
+                "Embeddings stream exceeded its maximum duration"
+            );
+            let error_event = serde_json::json!({
+                "error": {
+                    "message": error.to_string(),
+                    "type": "stream_error",
+                    "code": error.error_code()
+                }
+            });
+            let _ = sender.send(error_event.to_string()).await;
+        }
+    });
+
+    let events = futures::stream::unfold(receiver, |mut receiver| async move {
+        let data = receiver.recv().await?;
+        Some((Ok::<_, Infallible>(Event::default().data(data)), receiver))
+    });
+    Sse::new(events)
+        .keep_alive(axum::response::sse::KeepAlive::default())
+        .into_response()
+}
+
+/// Reject a provider outside the caller's tenant allowlist
+fn ensure_provider_allowed(
+    state: &AppState,
+    entity: Option<&AuthenticatedEntity>,
+    tenant_id: Option<&str>,
+    provider_id: &str,
+    model: &str,
+) -> Result<(), GatewayError> {
+    let allowed = allowed_providers(
+        entity,
+        tenant_id,
+        &state.config().routing.tenant_provider_allowlists,
+    );
+    match allowed.filter(|allowed| !allowed.iter().any(|id| id == provider_id)) {
+        Some(allowed) => Err(GatewayError::authorization(format!(
+            "Tenant '{}' is not permitted to use provider '{provider_id}' for model '{model}' (allowed: [{}])",
+            tenant_id.unwrap_or("caller"),
+            allowed.join(", "),
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// POST /v1/batches - Submit a batch of chat completion requests
+///
+/// The batch is routed as its first request would be, within the caller's
+/// tenant allowlist. Providers with a native batch endpoint receive it
+/// directly; for the rest the gateway processes the requests in the
+/// background. Returns the batch object to poll.
+#[instrument(skip(state, entity, body), fields(model = %body.model()))]
+pub async fn create_batch(
+    State(state): State<AppState>,
+    RequestId(request_id): RequestId,
+    TenantId(tenant_id): TenantId,
+    entity: Option<Extension<AuthenticatedEntity>>,
+    JsonBody(body): JsonBody<BatchRequest>,
+) -> Result<Json<Batch>, ApiError> {
+    body.validate()?;
+
+    let tenant = caller_tenant(entity.as_deref(), tenant_id.as_deref());
+    let allowed = allowed_providers(
+        entity.as_deref(),
+        tenant_id.as_deref(),
+        &state.config().routing.tenant_provider_allowlists,
+    );
+    let (provider, _) = state.router.route_with_allowlist(
+        &body.requests[0].body,
+        tenant_id.as_deref(),
+        allowed.as_deref(),
+    )?;
+
+    debug!(
+        request_id = %request_id,
+        provider = %provider.id(),
+        model = %body.model(),
+        requests = body.requests.len(),
+        tenant = ?tenant,
+        "Processing batch submission"
+    );
+
+    let job = batches::submit(&state, tenant, provider, body)
+        .await
+        .map_err(|e| {
+            error!(request_id = %request_id, error = %e, "Batch submission failed");
+            e
+        })?;
+    Ok(Json(job.snapshot()))
+}
+
+/// GET /v1/batches/{id} - Get a batch's status
+///
+/// Native batches are polled from the provider. Batches of other tenants
+/// are reported as not found.
+pub async fn get_batch(
+    State(state): State<AppState>,
+    Path(batch_id): Path<String>,
+    TenantId(tenant_id): TenantId,
+    entity: Option<Extension<AuthenticatedEntity>>,
+) -> Result<Json<Batch>, ApiError> {
+    let tenant = caller_tenant(entity.as_deref(), tenant_id.as_deref());
+    let job = state
+        .batches
+        .get(&batch_id, tenant)
+        .ok_or_else(|| ApiError::not_found(format!("No batch '{batch_id}'")))?;
+
+    batches::refresh(&state, &job).await?;
+    Ok(Json(job.snapshot()))
+}
+
+/// Results of a completed batch
+#[derive(Debug, Serialize)]
+pub struct BatchResultsResponse {
+    /// Batch ID
+    pub id: String,
+    /// Object type (always "list")
+    pub object: &'static str,
+    /// One result per request
+    pub data: Vec<BatchItemResult>,
+}
+
+/// GET /v1/batches/{id}/results - Get a completed batch's results
+///
+/// Returns `409` until the batch has completed.
+pub async fn get_batch_results(
+    State(state): State<AppState>,
+    Path(batch_id): Path<String>,
+    TenantId(tenant_id): TenantId,
+    entity: Option<Extension<AuthenticatedEntity>>,
+) -> Result<Json<BatchResultsResponse>, ApiError> {
+    let tenant = caller_tenant(entity.as_deref(), tenant_id.as_deref());
+    let job = state
+        .batches
+        .get(&batch_id, tenant)
+        .ok_or_else(|| ApiError::not_found(format!("No batch '{batch_id}'")))?;
+
+    batches::refresh(&state, &job).await?;
+    let data = job.results().ok_or_else(|| {
+        ApiError::new(
+            StatusCode::CONFLICT,
+            "invalid_request_error",
+            format!(
+                "Batch '{batch_id}' has not completed (status: {})",
+                job.snapshot().status.as_str()
+            ),
+        )
+        .with_code("batch_not_completed")
+    })?;
+
+    Ok(Json(BatchResultsResponse {
+        id: batch_id,
+        object: "list",
+        data,
+    }))
+}
+
+/// GET /v1/rate_limit - Inspect the caller's rate limit bucket
+///
+/// Requires an authenticated caller. The bucket is scoped to the caller's
+/// tenant (or entity) and is read without consuming a token.
+pub async fn rate_limit_status(
+    State(state): State<AppState>,
+    entity: Option<Extension<AuthenticatedEntity>>,
+    headers: HeaderMap,
+) -> Result<Json<RateLimitStatus>, ApiError> {
+    let entity = entity
+        .map(|Extension(e)| e)
+        .filter(|e| e.auth_method != AuthMethod::Anonymous)
+        .ok_or_else(|| ApiError::unauthorized("Authentication required"))?;
+
+    let limiter = &state.rate_limiter.limiter;
+    let key = rate_limit_key(&headers, Some(&entity));
+    let snapshot = limiter.peek(&key).await;
+
+    debug!(key = %key, remaining = snapshot.remaining, "Rate limit status requested");
+
+    Ok(Json(RateLimitStatus {
+        enabled: limiter.is_enabled(),
+        limit: snapshot.limit,
+        remaining: snapshot.remaining,
+        reset_seconds: snapshot.reset_after.as_secs(),
+        window_seconds: snapshot.window.as_secs(),
+        token_limit: snapshot.token_limit,
+        tokens_remaining: snapshot.tokens_remaining,
+    }))
+}
+
+/// Result of a request cancellation
+#[derive(Debug, Serialize)]
+pub struct CancelResponse {
+    /// Request ID
+    pub id: String,
+    /// Object type (always "request.cancellation")
+    pub object: &'static str,
+    /// Whether the request was cancelled by this call
+    pub cancelled: bool,
+}
+
+/// POST /v1/requests/{id}/cancel - Cancel an in-flight request
+///
+/// Requires an authenticated caller, who may only cancel requests made with
+/// a credential of their own tenant. Credentials without a tenant may only
+/// cancel their own requests; `X-Tenant-ID` is not considered. Aborts the upstream call; `cancelled` is `false` if the
+/// request was already cancelled.
+pub async fn cancel_request(
+    State(state): State<AppState>,
+    Path(request_id): Path<String>,
+    entity: Option<Extension<AuthenticatedEntity>>,
+) -> Result<Json<CancelResponse>, ApiError> {
+    let entity = entity
+        .map(|Extension(e)| e)
+        .filter(|e| e.auth_method != AuthMethod::Anonymous)
+        .ok_or_else(|| ApiError::unauthorized("Authentication required"))?;
+
+    let not_found = || ApiError::not_found(format!("No in-flight request '{request_id}'"));
+    let info = state.tracker.get_active(&request_id).ok_or_else(not_found)?;
+    // Only the credential's own tenant counts, never the X-Tenant-ID header;
+    // credentials without a tenant may cancel only their own requests
+    let authorized = info.owner.as_ref().is_some_and(|owner| {
+        match (entity.tenant_id.as_deref(), owner.tenant_id.as_deref()) {
+            (Some(caller), Some(owner_tenant)) => caller == owner_tenant,
+            _ => owner.principal == entity.principal(),
+        }
+    });
+    let caller = entity.tenant_id.as_deref();
+    let audit = |event_type, outcome, severity| {
+        let mut event = AuditEventBuilder::new(event_type)
+            .severity(severity)
+            .actor(AuditActor::user(entity.principal()))
+            .resource(AuditResource::new("request", &request_id))
+            .description("Request cancellation")
+            .outcome(outcome)
+            .request_id(&request_id);
+        if let Some(tenant) = caller {
+            event = event.tenant_id(tenant);
+        }
+        state.audit(event.build());
+    };
+    if !authorized {
+        audit(
+            AuditEventType::SecurityEvent,
+            AuditOutcome::Denied,
+            AuditSeverity::Warning,
+        );
+        warn!(
+            request_id = %request_id,
+            tenant = ?caller,
+            owner = ?info.tenant_id,
+            "Refused to cancel another tenant's request"
+        );
+        return Err(ApiError::forbidden(format!(
+            "Request '{request_id}' belongs to another tenant"
+        )));
+    }
+
+    let cancelled = state.tracker.cancel(&request_id);
+    audit(
+        AuditEventType::AdminAction,
+        AuditOutcome::Success,
+        AuditSeverity::Info,
+    );
+    info!(request_id = %request_id, tenant = ?caller, cancelled, "Cancel requested");
+
+    Ok(Json(CancelResponse {
+        id: request_id,
+        object: "request.cancellation",
+        cancelled,
+    }))
+}
+
 // =============================================================================
 // Agent Endpoints
 // =============================================================================
@@ -697,8 +2638,16 @@ mod tests {
 
     #[tokio::test]
     async fn test_health_check() {
-        let response = health_check().await;
+        let state = AppState::builder()
+            .config(gateway_config::GatewayConfig::default())
+            .build();
+        let response = health_check(State(state.clone())).await;
         assert_eq!(response.0.status, "healthy");
+        assert!(response.0.uptime_seconds.is_some());
+
+        state.health.mark_shutting_down();
+        let response = health_check(State(state)).await;
+        assert_eq!(response.0.status, "shutting_down");
     }
 
     #[test]
@@ -7,9 +7,12 @@
 //! - Deep health checks (provider connectivity)
 //! - Health aggregation and scoring
 
+use crate::state::CircuitBreakerManager;
+use gateway_core::LLMProvider;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 use tracing::{debug, info};
@@ -23,6 +26,8 @@ pub struct HealthConfig {
     pub cache_duration: Duration,
     /// Timeout for individual provider checks
     pub provider_check_timeout: Duration,
+    /// How long a provider health result is served without a live check
+    pub provider_cache_ttl: Duration,
     /// Whether to include provider health in readiness
     pub include_providers_in_readiness: bool,
     /// Minimum healthy providers for readiness
@@ -37,6 +42,7 @@ impl Default for HealthConfig {
             detailed_response: true,
             cache_duration: Duration::from_secs(5),
             provider_check_timeout: Duration::from_secs(5),
+            provider_cache_ttl: Duration::from_secs(10),
             include_providers_in_readiness: true,
             min_healthy_providers: 1,
             parallel_checks: true,
@@ -71,6 +77,13 @@ impl HealthConfig {
         self.provider_check_timeout = timeout;
         self
     }
+
+    /// Set provider health result TTL
+    #[must_use]
+    pub fn with_provider_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.provider_cache_ttl = ttl;
+        self
+    }
 }
 
 /// Health status
@@ -299,6 +312,12 @@ pub struct HealthChecker {
     startup_time: Instant,
     /// Cached health result
     cached_health: RwLock<Option<(Instant, HealthResponse)>>,
+    /// Cached per-provider health results
+    provider_results: Arc<RwLock<HashMap<String, ProviderHealthResult>>>,
+    /// Providers with a background refresh in flight
+    refreshing: Arc<parking_lot::Mutex<HashSet<String>>>,
+    /// Circuit breakers fed by provider health probes
+    circuit_breakers: Option<Arc<CircuitBreakerManager>>,
     /// Whether startup is complete
     startup_complete: AtomicBool,
     /// Startup progress (0-100)
@@ -319,6 +338,9 @@ impl HealthChecker {
             config,
             startup_time: Instant::now(),
             cached_health: RwLock::new(None),
+            provider_results: Arc::new(RwLock::new(HashMap::new())),
+            refreshing: Arc::new(parking_lot::Mutex::new(HashSet::new())),
+            circuit_breakers: None,
             startup_complete: AtomicBool::new(false),
             startup_progress: AtomicU64::new(0),
             initialized_components: RwLock::new(Vec::new()),
@@ -338,6 +360,16 @@ impl HealthChecker {
         Self::new(HealthConfig::default())
     }
 
+    /// Feed provider health probe results into these circuit breakers
+    ///
+    /// Probes bypass the breakers so an open circuit can still be checked,
+    /// and a healthy probe closes it.
+    #[must_use]
+    pub fn with_circuit_breakers(mut self, breakers: Arc<CircuitBreakerManager>) -> Self {
+        self.circuit_breakers = Some(breakers);
+        self
+    }
+
     /// Get uptime
     #[must_use]
     pub fn uptime(&self) -> Duration {
@@ -351,8 +383,25 @@ impl HealthChecker {
             initialized.push(component.to_string());
             info!(component = component, "Component initialized");
         }
+        self.update_progress(&initialized);
+    }
+
+    /// Mark components as initialized before the checker is shared
+    #[must_use]
+    pub fn with_initialized(mut self, components: &[&str]) -> Self {
+        let initialized = self.initialized_components.get_mut();
+        for component in components {
+            if !initialized.iter().any(|c| c == component) {
+                initialized.push((*component).to_string());
+            }
+        }
+        let initialized = initialized.clone();
+        self.update_progress(&initialized);
+        self
+    }
 
-        // Update progress
+    /// Recompute startup progress from the initialized components
+    fn update_progress(&self, initialized: &[String]) {
         let total = self.required_components.len();
         let completed = initialized
             .iter()
@@ -470,6 +519,89 @@ impl HealthChecker {
         }
     }
 
+    /// Perform readiness check against live provider health
+    ///
+    /// Provider results are served from cache; see [`Self::check_providers`].
+    pub async fn check_readiness_with_providers(
+        &self,
+        providers: &[Arc<dyn LLMProvider>],
+    ) -> ReadinessResponse {
+        let results = self.check_providers(providers).await;
+        let healthy = results.iter().filter(|r| r.status.is_healthy()).count();
+        self.check_readiness(results.len(), healthy).await
+    }
+
+    /// Check provider health, serving cached results within the TTL
+    ///
+    /// Providers without a cached result are checked inline. Results older
+    /// than `provider_cache_ttl` are returned marked stale while a refresh
+    /// runs in the background.
+    pub async fn check_providers(
+        &self,
+        providers: &[Arc<dyn LLMProvider>],
+    ) -> Vec<ProviderHealthResult> {
+        let ttl = self.config.provider_cache_ttl;
+        let mut results = Vec::with_capacity(providers.len());
+        let mut missing = Vec::new();
+
+        {
+            let cache = self.provider_results.read().await;
+            for provider in providers {
+                match cache.get(provider.id()) {
+                    Some(cached) if cached.checked_at.elapsed() < ttl => {
+                        results.push(cached.clone());
+                    }
+                    Some(cached) => {
+                        let mut stale = cached.clone();
+                        stale.stale = true;
+                        results.push(stale);
+                        self.spawn_refresh(Arc::clone(provider));
+                    }
+                    None => missing.push(Arc::clone(provider)),
+                }
+            }
+        }
+
+        if !missing.is_empty() {
+            let timeout = self.config.provider_check_timeout;
+            let breakers = self.circuit_breakers.as_deref();
+            let checked = futures::future::join_all(
+                missing
+                    .iter()
+                    .map(|provider| check_provider_live(provider.as_ref(), timeout, breakers)),
+            )
+            .await;
+
+            let mut cache = self.provider_results.write().await;
+            for result in checked {
+                cache.insert(result.provider_id.clone(), result.clone());
+                results.push(result);
+            }
+        }
+
+        results
+    }
+
+    /// Refresh a provider's cached result in the background
+    fn spawn_refresh(&self, provider: Arc<dyn LLMProvider>) {
+        let provider_id = provider.id().to_string();
+        if !self.refreshing.lock().insert(provider_id.clone()) {
+            return;
+        }
+
+        let results = Arc::clone(&self.provider_results);
+        let refreshing = Arc::clone(&self.refreshing);
+        let timeout = self.config.provider_check_timeout;
+        let breakers = self.circuit_breakers.clone();
+        tokio::spawn(async move {
+            let result =
+                check_provider_live(provider.as_ref(), timeout, breakers.as_deref()).await;
+            results.write().await.insert(provider_id.clone(), result);
+            refreshing.lock().remove(&provider_id);
+            debug!(provider_id = %provider_id, "Refreshed provider health");
+        });
+    }
+
     /// Perform deep health check
     pub async fn check_deep(&self, components: Vec<ComponentHealth>) -> HealthResponse {
         // Check cache first
@@ -522,9 +654,35 @@ impl HealthChecker {
     pub async fn clear_cache(&self) {
         let mut cache = self.cached_health.write().await;
         *cache = None;
+        self.provider_results.write().await.clear();
     }
 }
 
+/// Run a live health check against a provider
+///
+/// The probe never consults the provider's circuit breaker, but is still
+/// bounded by `timeout` and the provider client's connection limits. Its
+/// outcome is recorded on the breaker, if one exists.
+async fn check_provider_live(
+    provider: &dyn LLMProvider,
+    timeout: Duration,
+    breakers: Option<&CircuitBreakerManager>,
+) -> ProviderHealthResult {
+    let start = Instant::now();
+    let result = match tokio::time::timeout(timeout, provider.health_check()).await {
+        Ok(status) => {
+            ProviderHealthResult::from_provider_status(provider.id(), status, start.elapsed())
+        }
+        Err(_) => ProviderHealthResult::unhealthy(provider.id(), "health check timed out"),
+    };
+
+    if let Some(breaker) = breakers.and_then(|b| b.get(provider.id())) {
+        breaker.record_probe_result(result.status.is_healthy());
+    }
+
+    result
+}
+
 /// Provider health check result
 #[derive(Debug, Clone)]
 pub struct ProviderHealthResult {
@@ -538,6 +696,10 @@ pub struct ProviderHealthResult {
     pub error: Option<String>,
     /// Last successful check
     pub last_success: Option<Instant>,
+    /// When this result was produced
+    pub checked_at: Instant,
+    /// Whether the result is older than the cache TTL
+    pub stale: bool,
 }
 
 impl ProviderHealthResult {
@@ -550,6 +712,8 @@ impl ProviderHealthResult {
             response_time: Some(response_time),
             error: None,
             last_success: Some(Instant::now()),
+            checked_at: Instant::now(),
+            stale: false,
         }
     }
 
@@ -562,6 +726,32 @@ impl ProviderHealthResult {
             response_time: None,
             error: Some(error.into()),
             last_success: None,
+            checked_at: Instant::now(),
+            stale: false,
+        }
+    }
+
+    /// Create a result from a provider-reported status
+    #[must_use]
+    pub fn from_provider_status(
+        provider_id: impl Into<String>,
+        status: gateway_core::HealthStatus,
+        response_time: Duration,
+    ) -> Self {
+        match status {
+            gateway_core::HealthStatus::Healthy => Self::healthy(provider_id, response_time),
+            gateway_core::HealthStatus::Degraded => Self {
+                status: HealthStatus::Degraded,
+                ..Self::healthy(provider_id, response_time)
+            },
+            gateway_core::HealthStatus::Unhealthy => {
+                Self::unhealthy(provider_id, "provider reported unhealthy")
+            }
+            gateway_core::HealthStatus::Unknown => Self {
+                status: HealthStatus::Unknown,
+                error: None,
+                ..Self::unhealthy(provider_id, "")
+            },
         }
     }
 }
@@ -609,6 +799,13 @@ pub fn aggregate_provider_health(results: &[ProviderHealthResult]) -> ComponentH
         .details
         .insert("healthy".to_string(), serde_json::json!(healthy_count));
 
+    let stale_count = results.iter().filter(|r| r.stale).count();
+    if stale_count > 0 {
+        component
+            .details
+            .insert("stale".to_string(), serde_json::json!(stale_count));
+    }
+
     // Add individual provider status
     let provider_details: HashMap<String, String> = results
         .iter()
@@ -814,4 +1011,153 @@ mod tests {
         let response3 = checker.check_deep(vec![]).await;
         assert_ne!(response1.timestamp, response3.timestamp);
     }
+
+    struct CountingProvider {
+        checks: std::sync::atomic::AtomicUsize,
+        capabilities: gateway_core::ProviderCapabilities,
+    }
+
+    impl CountingProvider {
+        fn new() -> Self {
+            Self {
+                checks: std::sync::atomic::AtomicUsize::new(0),
+                capabilities: gateway_core::ProviderCapabilities::default(),
+            }
+        }
+
+        fn checks(&self) -> usize {
+            self.checks.load(Ordering::SeqCst)
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl LLMProvider for CountingProvider {
+        fn id(&self) -> &str {
+            "counting"
+        }
+
+        fn provider_type(&self) -> gateway_core::ProviderType {
+            gateway_core::ProviderType::Custom
+        }
+
+        async fn chat_completion(
+            &self,
+            _: &gateway_core::GatewayRequest,
+        ) -> Result<gateway_core::GatewayResponse, gateway_core::GatewayError> {
+            unimplemented!()
+        }
+
+        async fn chat_completion_stream(
+            &self,
+            _: &gateway_core::GatewayRequest,
+        ) -> Result<
+            futures::stream::BoxStream<
+                'static,
+                Result<gateway_core::ChatChunk, gateway_core::GatewayError>,
+            >,
+            gateway_core::GatewayError,
+        > {
+            unimplemented!()
+        }
+
+        async fn health_check(&self) -> gateway_core::HealthStatus {
+            self.checks.fetch_add(1, Ordering::SeqCst);
+            gateway_core::HealthStatus::Healthy
+        }
+
+        fn capabilities(&self) -> &gateway_core::ProviderCapabilities {
+            &self.capabilities
+        }
+
+        fn models(&self) -> &[gateway_core::ModelInfo] {
+            &[]
+        }
+
+        fn base_url(&self) -> &str {
+            "http://localhost"
+        }
+    }
+
+    async fn ready_checker(ttl: Duration) -> HealthChecker {
+        let checker = HealthChecker::new(HealthConfig::new().with_provider_cache_ttl(ttl));
+        for comp in &["config", "providers", "router", "metrics"] {
+            checker.mark_initialized(comp).await;
+        }
+        checker
+    }
+
+    #[tokio::test]
+    async fn test_rapid_readiness_checks_share_provider_result() {
+        let checker = ready_checker(Duration::from_secs(60)).await;
+        let provider = Arc::new(CountingProvider::new());
+        let providers: Vec<Arc<dyn LLMProvider>> = vec![provider.clone()];
+
+        let first = checker.check_readiness_with_providers(&providers).await;
+        let second = checker.check_readiness_with_providers(&providers).await;
+
+        assert!(first.ready);
+        assert!(second.ready);
+        assert_eq!(second.healthy_providers, 1);
+        assert_eq!(provider.checks(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_provider_results_refresh_after_ttl() {
+        let checker = ready_checker(Duration::from_millis(50)).await;
+        let provider = Arc::new(CountingProvider::new());
+        let providers: Vec<Arc<dyn LLMProvider>> = vec![provider.clone()];
+
+        let results = checker.check_providers(&providers).await;
+        assert!(!results[0].stale);
+        assert_eq!(provider.checks(), 1);
+
+        tokio::time::sleep(Duration::from_millis(80)).await;
+
+        // Expired result is served stale while a refresh runs in the background
+        let results = checker.check_providers(&providers).await;
+        assert!(results[0].stale);
+        assert_eq!(results[0].status, HealthStatus::Healthy);
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(provider.checks(), 2);
+
+        let results = checker.check_providers(&providers).await;
+        assert!(!results[0].stale);
+        assert_eq!(provider.checks(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_health_probe_bypasses_open_circuit_breaker() {
+        let breakers = Arc::new(CircuitBreakerManager::new());
+        let breaker = breakers.get_or_create("counting");
+        breaker.force_open();
+        assert!(breaker.check().is_err());
+
+        let checker = ready_checker(Duration::from_secs(60))
+            .await
+            .with_circuit_breakers(Arc::clone(&breakers));
+        let provider = Arc::new(CountingProvider::new());
+        let providers: Vec<Arc<dyn LLMProvider>> = vec![provider.clone()];
+
+        let results = checker.check_providers(&providers).await;
+
+        assert_eq!(provider.checks(), 1);
+        assert_eq!(results[0].status, HealthStatus::Healthy);
+        assert_eq!(breaker.state(), gateway_resilience::CircuitState::Closed);
+        assert!(breaker.check().is_ok());
+    }
+
+    #[test]
+    fn test_aggregate_reports_stale_results() {
+        let mut stale = ProviderHealthResult::healthy("openai", Duration::from_millis(100));
+        stale.stale = true;
+        let results = vec![
+            stale,
+            ProviderHealthResult::healthy("anthropic", Duration::from_millis(150)),
+        ];
+
+        let component = aggregate_provider_health(&results);
+        assert_eq!(component.status, HealthStatus::Healthy);
+        assert_eq!(component.details.get("stale"), Some(&serde_json::json!(1)));
+    }
 }
@@ -0,0 +1,382 @@
+//! Validation of images in chat requests.
+//!
+//! Inline images arrive as base64 `data:` URLs, and a huge or corrupt one
+//! would otherwise only fail deep inside the provider call, after the
+//! gateway has held the decoded copy in memory. Each inline image is
+//! decoded here and checked against `server.images`: its size, its
+//! dimensions when limits are configured, and that its bytes are the media
+//! type it was declared as. Violations are rejected with a 422 naming the
+//! offending message part.
+
+use axum::http::StatusCode;
+use base64::Engine;
+use gateway_config::ImageLimitsConfig;
+use gateway_core::{ContentPart, GatewayRequest, MessageContent};
+
+use crate::error::ApiError;
+
+/// Image formats accepted inline
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ImageFormat {
+    Png,
+    Jpeg,
+    Gif,
+    Webp,
+}
+
+impl ImageFormat {
+    /// Format for a declared media type
+    fn from_media_type(media_type: &str) -> Option<Self> {
+        match media_type.to_ascii_lowercase().as_str() {
+            "image/png" => Some(Self::Png),
+            "image/jpeg" | "image/jpg" => Some(Self::Jpeg),
+            "image/gif" => Some(Self::Gif),
+            "image/webp" => Some(Self::Webp),
+            _ => None,
+        }
+    }
+
+    /// Format identified by the file signature
+    fn sniff(bytes: &[u8]) -> Option<Self> {
+        match bytes {
+            [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a, ..] => Some(Self::Png),
+            [0xff, 0xd8, 0xff, ..] => Some(Self::Jpeg),
+            [b'G', b'I', b'F', b'8', b'7' | b'9', b'a', ..] => Some(Self::Gif),
+            [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => Some(Self::Webp),
+            _ => None,
+        }
+    }
+
+    fn media_type(self) -> &'static str {
+        match self {
+            Self::Png => "image/png",
+            Self::Jpeg => "image/jpeg",
+            Self::Gif => "image/gif",
+            Self::Webp => "image/webp",
+        }
+    }
+
+    /// Width and height read from the image header
+    fn dimensions(self, bytes: &[u8]) -> Option<(u32, u32)> {
+        match self {
+            Self::Png => Some((be_u32(bytes, 16)?, be_u32(bytes, 20)?)),
+            Self::Gif => Some((u32::from(le_u16(bytes, 6)?), u32::from(le_u16(bytes, 8)?))),
+            Self::Jpeg => jpeg_dimensions(bytes),
+            Self::Webp => webp_dimensions(bytes),
+        }
+    }
+}
+
+/// Check every image in `request` against `config`
+///
+/// # Errors
+/// Returns a 422 `ApiError` for the first image that is malformed, of the
+/// wrong media type, or over a limit, or if there are too many images
+pub fn validate(config: &ImageLimitsConfig, request: &GatewayRequest) -> Result<(), ApiError> {
+    if !config.enabled {
+        return Ok(());
+    }
+
+    let mut count = 0;
+    for (message_index, message) in request.messages.iter().enumerate() {
+        let MessageContent::Parts(parts) = &message.content else {
+            continue;
+        };
+        for (part_index, part) in parts.iter().enumerate() {
+            let ContentPart::ImageUrl { image_url } = part else {
+                continue;
+            };
+            let param = format!("messages[{message_index}].content[{part_index}].image_url");
+
+            count += 1;
+            if count > config.max_count {
+                return Err(rejection(
+                    format!("Request has more than {} images", config.max_count),
+                    &param,
+                    "too_many_images",
+                ));
+            }
+
+            if let Some(data_url) = image_url.url.strip_prefix("data:") {
+                check_inline(config, data_url, &param)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Decode and check one `data:` URL, given without its scheme
+fn check_inline(config: &ImageLimitsConfig, data_url: &str, param: &str) -> Result<(), ApiError> {
+    let Some((declared, data)) = data_url
+        .split_once(',')
+        .and_then(|(header, data)| Some((header.strip_suffix(";base64")?, data)))
+    else {
+        return Err(rejection(
+            "Inline image must be a base64 data URL",
+            param,
+            "invalid_image",
+        ));
+    };
+
+    let Some(format) = ImageFormat::from_media_type(declared) else {
+        return Err(rejection(
+            format!("Unsupported image media type '{declared}'"),
+            param,
+            "unsupported_image_type",
+        ));
+    };
+
+    // Reject by the encoded length before decoding anything
+    let data = data.trim_end();
+    if data.len() / 4 * 3 > config.max_bytes + 2 {
+        return Err(too_large(config.max_bytes, param));
+    }
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(data)
+        .map_err(|e| rejection(format!("Inline image is not valid base64: {e}"), param, "invalid_image"))?;
+    if bytes.len() > config.max_bytes {
+        return Err(too_large(config.max_bytes, param));
+    }
+
+    match ImageFormat::sniff(&bytes) {
+        Some(actual) if actual == format => {}
+        Some(actual) => {
+            return Err(rejection(
+                format!(
+                    "Inline image is declared as {} but contains {}",
+                    format.media_type(),
+                    actual.media_type()
+                ),
+                param,
+                "image_media_type_mismatch",
+            ));
+        }
+        None => {
+            return Err(rejection(
+                format!("Inline image is not a valid {}", format.media_type()),
+                param,
+                "invalid_image",
+            ));
+        }
+    }
+
+    if config.max_width.is_some() || config.max_height.is_some() {
+        let Some((width, height)) = format.dimensions(&bytes) else {
+            return Err(rejection(
+                "Could not read the inline image's dimensions",
+                param,
+                "invalid_image",
+            ));
+        };
+        let too_wide = config.max_width.is_some_and(|max| width > max);
+        let too_tall = config.max_height.is_some_and(|max| height > max);
+        if too_wide || too_tall {
+            return Err(rejection(
+                format!(
+                    "Inline image is {width}x{height}, over the {}x{} limit",
+                    limit(config.max_width),
+                    limit(config.max_height)
+                ),
+                param,
+                "image_too_large",
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn rejection(message: impl Into<String>, param: &str, code: &str) -> ApiError {
+    ApiError::new(StatusCode::UNPROCESSABLE_ENTITY, "invalid_request_error", message)
+        .with_param(param)
+        .with_code(code)
+}
+
+fn too_large(max_bytes: usize, param: &str) -> ApiError {
+    rejection(
+        format!("Inline image exceeds the {max_bytes} byte limit"),
+        param,
+        "image_too_large",
+    )
+}
+
+fn limit(max: Option<u32>) -> String {
+    max.map_or_else(|| "any".to_string(), |max| max.to_string())
+}
+
+fn be_u16(bytes: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(bytes.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn be_u32(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn le_u16(bytes: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(bytes.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn le_u24(bytes: &[u8], at: usize) -> Option<u32> {
+    let b = bytes.get(at..at + 3)?;
+    Some(u32::from(b[0]) | u32::from(b[1]) << 8 | u32::from(b[2]) << 16)
+}
+
+/// Dimensions from the first start-of-frame segment
+fn jpeg_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    let mut at = 2;
+    loop {
+        if *bytes.get(at)? != 0xff {
+            return None;
+        }
+        let marker = *bytes.get(at + 1)?;
+        // SOF0-SOF15, excluding DHT (C4), JPG (C8) and DAC (CC)
+        if (0xc0..=0xcf).contains(&marker) && !matches!(marker, 0xc4 | 0xc8 | 0xcc) {
+            let height = be_u16(bytes, at + 5)?;
+            let width = be_u16(bytes, at + 7)?;
+            return Some((u32::from(width), u32::from(height)));
+        }
+        at += 2 + usize::from(be_u16(bytes, at + 2)?);
+    }
+}
+
+/// Dimensions from a lossy, lossless, or extended WebP header
+fn webp_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    match bytes.get(12..16)? {
+        b"VP8 " => Some((
+            u32::from(le_u16(bytes, 26)? & 0x3fff),
+            u32::from(le_u16(bytes, 28)? & 0x3fff),
+        )),
+        b"VP8L" => {
+            let bits = u32::from_le_bytes(bytes.get(21..25)?.try_into().ok()?);
+            Some(((bits & 0x3fff) + 1, ((bits >> 14) & 0x3fff) + 1))
+        }
+        b"VP8X" => Some((le_u24(bytes, 24)? + 1, le_u24(bytes, 27)? + 1)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gateway_core::request::ImageUrl;
+    use gateway_core::{ChatMessage, MessageRole};
+
+    /// PNG signature and IHDR chunk, enough to read the dimensions
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut bytes = vec![0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a, 0, 0, 0, 13];
+        bytes.extend_from_slice(b"IHDR");
+        bytes.extend_from_slice(&width.to_be_bytes());
+        bytes.extend_from_slice(&height.to_be_bytes());
+        bytes.extend_from_slice(&[8, 6, 0, 0, 0]);
+        bytes
+    }
+
+    fn data_url(media_type: &str, bytes: &[u8]) -> String {
+        format!(
+            "data:{media_type};base64,{}",
+            base64::engine::general_purpose::STANDARD.encode(bytes)
+        )
+    }
+
+    fn request(urls: &[String]) -> GatewayRequest {
+        let parts = urls
+            .iter()
+            .map(|url| ContentPart::ImageUrl {
+                image_url: ImageUrl {
+                    url: url.clone(),
+                    detail: None,
+                },
+            })
+            .collect();
+        GatewayRequest::builder()
+            .model("gpt-4o")
+            .message(ChatMessage {
+                role: MessageRole::User,
+                content: MessageContent::Parts(parts),
+                name: None,
+                tool_calls: None,
+                tool_call_id: None,
+            })
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_valid_image_accepted() {
+        let config = ImageLimitsConfig {
+            max_width: Some(4),
+            max_height: Some(4),
+            ..ImageLimitsConfig::default()
+        };
+        let request = request(&[data_url("image/png", &png(2, 3))]);
+
+        assert!(validate(&config, &request).is_ok());
+    }
+
+    #[test]
+    fn test_oversized_image_rejected() {
+        let config = ImageLimitsConfig {
+            max_bytes: 16,
+            ..ImageLimitsConfig::default()
+        };
+        let error = validate(&config, &request(&[data_url("image/png", &png(2, 3))])).unwrap_err();
+
+        assert_eq!(error.status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(error.code.as_deref(), Some("image_too_large"));
+        assert_eq!(error.param.as_deref(), Some("messages[0].content[0].image_url"));
+    }
+
+    #[test]
+    fn test_dimensions_over_limit_rejected() {
+        let config = ImageLimitsConfig {
+            max_width: Some(1),
+            ..ImageLimitsConfig::default()
+        };
+        let error = validate(&config, &request(&[data_url("image/png", &png(2, 3))])).unwrap_err();
+
+        assert_eq!(error.code.as_deref(), Some("image_too_large"));
+        assert!(error.message.contains("2x3"));
+    }
+
+    #[test]
+    fn test_malformed_base64_rejected() {
+        let request = request(&["data:image/png;base64,not*base64!".to_string()]);
+        let error = validate(&ImageLimitsConfig::default(), &request).unwrap_err();
+
+        assert_eq!(error.status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(error.code.as_deref(), Some("invalid_image"));
+    }
+
+    #[test]
+    fn test_media_type_mismatch_rejected() {
+        let request = request(&[data_url("image/jpeg", &png(2, 3))]);
+        let error = validate(&ImageLimitsConfig::default(), &request).unwrap_err();
+
+        assert_eq!(error.code.as_deref(), Some("image_media_type_mismatch"));
+    }
+
+    #[test]
+    fn test_image_count_limit() {
+        let config = ImageLimitsConfig {
+            max_count: 1,
+            ..ImageLimitsConfig::default()
+        };
+        let urls = ["https://example.com/a.png".to_string(), "https://example.com/b.png".to_string()];
+        let error = validate(&config, &request(&urls)).unwrap_err();
+
+        assert_eq!(error.code.as_deref(), Some("too_many_images"));
+        assert_eq!(error.param.as_deref(), Some("messages[0].content[1].image_url"));
+    }
+
+    #[test]
+    fn test_header_dimensions() {
+        let mut gif = b"GIF89a".to_vec();
+        gif.extend_from_slice(&[5, 0, 7, 0]);
+        assert_eq!(ImageFormat::Gif.dimensions(&gif), Some((5, 7)));
+
+        // SOI, APP0 of length 4, SOF0 with height 7 and width 5
+        let jpeg = [
+            0xff, 0xd8, 0xff, 0xe0, 0, 4, 0, 0, 0xff, 0xc0, 0, 11, 8, 0, 7, 0, 5,
+        ];
+        assert_eq!(ImageFormat::Jpeg.dimensions(&jpeg), Some((5, 7)));
+    }
+}
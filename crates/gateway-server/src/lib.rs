@@ -10,20 +10,48 @@
 //! - Enterprise health check system
 //! - Graceful shutdown handling
 //! - JWT/OIDC authentication
+//! - Inline policy enforcement
+//! - Shadow traffic mirroring with response diffing
+//! - Deterministic sampling overrides for evaluation runs
+//! - Per-request provider overrides for authorized callers
+//! - Response content hashes for downstream caching
+//! - Signed webhooks for lifecycle events
+//! - Opt-in request/response persistence (`persistence` feature)
+//! - Tamper-evident audit event retention and export (`persistence` feature)
 
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
 
+pub mod allowlist;
+#[cfg(feature = "persistence")]
+pub mod audit_store;
 pub mod auth;
+pub mod batches;
+pub mod compat;
+pub mod deterministic;
 pub mod error;
 pub mod extractors;
 pub mod handlers;
 pub mod health;
+pub mod image_limits;
 pub mod middleware;
+pub mod model_defaults;
+pub mod passthrough;
+#[cfg(feature = "persistence")]
+pub mod persistence;
+pub mod policy;
+pub mod postprocess;
+pub mod provider_override;
+pub mod response_hash;
 pub mod routes;
+pub mod routing_log;
 pub mod server;
+pub mod shadow;
 pub mod shutdown;
 pub mod state;
+pub mod streams;
+pub mod trace;
+pub mod webhooks;
 
 // Re-export main types
 pub use auth::{
@@ -35,9 +63,12 @@ pub use health::{
     ComponentHealth, HealthChecker, HealthConfig, HealthResponse, HealthStatus,
     LivenessResponse, ProviderHealthResult, ReadinessResponse, StartupResponse,
 };
+pub use policy::PolicyGate;
 pub use server::{Server, ServerConfig};
+pub use shadow::ShadowMirror;
 pub use shutdown::{
-    GracefulServer, RequestGuard, ShutdownConfig, ShutdownCoordinator, ShutdownEvent,
-    ShutdownPhase, ShutdownStats,
+    GracefulServer, PhaseStats, RequestGuard, ShutdownConfig, ShutdownCoordinator,
+    ShutdownEvent, ShutdownHook, ShutdownPhase, ShutdownStats,
 };
 pub use state::AppState;
+pub use streams::{StreamLimiter, StreamPermit};
@@ -6,13 +6,18 @@
 //! - CORS handling
 //! - Response timing
 //! - Rate limiting
+//! - Error detail levels
+//! - Edge request timeout
+//! - Legacy request compatibility
 
 use axum::{
     extract::{Request, State},
-    http::{header, HeaderValue, Method, StatusCode},
+    http::{header, HeaderMap, HeaderValue, Method, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
 };
+use gateway_config::ErrorDetailLevel;
+use gateway_core::tokenizer::{CharHeuristicEstimator, SharedTokenEstimator};
 use gateway_resilience::{RateLimiter, RateLimiterConfig};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -20,6 +25,11 @@ use tower_http::cors::{Any, CorsLayer};
 use tracing::{info, info_span, warn, Instrument};
 use uuid::Uuid;
 
+use crate::auth::{AuthMethod, AuthenticatedEntity};
+use crate::compat::{deprecation_warning, normalize_legacy_request, DEPRECATION_HEADER};
+use crate::error::{resolve_detail_level, ApiError};
+use crate::state::AppState;
+
 /// Create CORS middleware layer
 pub fn cors_layer() -> CorsLayer {
     CorsLayer::new()
@@ -186,6 +196,116 @@ pub async fn security_headers_middleware(request: Request, next: Next) -> Respon
     response
 }
 
+/// Error detail middleware
+///
+/// Re-renders [`ApiError`] responses at the detail level resolved for the
+/// caller from `security.error_detail` and the authenticated entity.
+pub async fn error_detail_middleware(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let level = resolve_detail_level(
+        &state.config().security.error_detail,
+        request.extensions().get::<AuthenticatedEntity>(),
+    );
+
+    let response = next.run(request).await;
+    if level == ErrorDetailLevel::Standard {
+        return response;
+    }
+
+    let Some(api_error) = response.extensions().get::<ApiError>() else {
+        return response;
+    };
+
+    let Ok(body) = serde_json::to_vec(&api_error.render(level)) else {
+        return response;
+    };
+
+    let (mut parts, _) = response.into_parts();
+    parts.headers.remove(header::CONTENT_LENGTH);
+    Response::from_parts(parts, axum::body::Body::from(body))
+}
+
+/// Edge timeout middleware
+///
+/// Bounds the time to produce a response with `server.edge_timeout`,
+/// whatever phase the request is stuck in. On expiry the inner future is
+/// dropped, aborting downstream work, and a `504` is returned. Streaming
+/// bodies are not covered once their headers are out; they are bounded by
+/// `server.max_stream_duration` instead.
+pub async fn edge_timeout_middleware(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let timeout = state.config().server.edge_timeout;
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+
+    tokio::time::timeout(timeout, next.run(request))
+        .await
+        .unwrap_or_else(|_| {
+            warn!(%method, %path, timeout = ?timeout, "Request exceeded edge timeout");
+            ApiError::from(gateway_core::GatewayError::timeout(timeout)).into_response()
+        })
+}
+
+/// Legacy request compatibility middleware
+///
+/// Rewrites deprecated request body fields to the current request shape
+/// before the handler deserializes them, and flags the response with
+/// `Deprecation` and `Warning` headers. Disabled by
+/// `server.legacy_request_compat`.
+pub async fn legacy_request_compat_middleware(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let config = state.config();
+    if !config.server.legacy_request_compat {
+        return next.run(request).await;
+    }
+
+    let (mut parts, body) = request.into_parts();
+    let bytes = match axum::body::to_bytes(body, config.server.max_request_body_size).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            return ApiError::bad_request(format!("Failed to read request body: {e}"))
+                .into_response();
+        }
+    };
+
+    // Bodies that aren't JSON objects are left for the handler to reject
+    let mut value: serde_json::Value = match serde_json::from_slice(&bytes) {
+        Ok(value) => value,
+        Err(_) => return next.run(Request::from_parts(parts, bytes.into())).await,
+    };
+
+    let deprecated = normalize_legacy_request(&mut value);
+    if deprecated.is_empty() {
+        return next.run(Request::from_parts(parts, bytes.into())).await;
+    }
+
+    let Ok(body) = serde_json::to_vec(&value) else {
+        return next.run(Request::from_parts(parts, bytes.into())).await;
+    };
+    parts.headers.remove(header::CONTENT_LENGTH);
+
+    warn!(fields = ?deprecated, "Request used deprecated fields");
+
+    let mut response = next
+        .run(Request::from_parts(parts, axum::body::Body::from(body)))
+        .await;
+    let headers = response.headers_mut();
+    headers.insert(DEPRECATION_HEADER, HeaderValue::from_static("true"));
+    if let Ok(warning) = HeaderValue::from_str(&deprecation_warning(&deprecated)) {
+        headers.insert(header::WARNING, warning);
+    }
+    response
+}
+
 /// Rate limiter state for middleware
 #[derive(Clone)]
 pub struct RateLimiterState {
@@ -213,19 +333,34 @@ impl RateLimiterState {
     /// Create from config schema
     #[must_use]
     pub fn from_config(config: &gateway_config::RateLimitConfig) -> Self {
-        if !config.enabled {
-            return Self::disabled();
-        }
+        Self::from_config_with_estimator(config, Arc::new(CharHeuristicEstimator::default()))
+    }
 
-        let limiter_config = RateLimiterConfig {
-            requests_per_window: config.default_rpm,
-            tokens_per_window: config.default_tpm,
-            window: config.window,
-            enable_burst: true,
-            burst_multiplier: 1.2,
+    /// Create from config schema, sizing requests for TPM limits with
+    /// `estimator`
+    #[must_use]
+    pub fn from_config_with_estimator(
+        config: &gateway_config::RateLimitConfig,
+        estimator: SharedTokenEstimator,
+    ) -> Self {
+        let limiter = if config.enabled {
+            RateLimiter::new(
+                "gateway",
+                RateLimiterConfig {
+                    requests_per_window: config.default_rpm,
+                    tokens_per_window: config.default_tpm,
+                    window: config.window,
+                    enable_burst: true,
+                    burst_multiplier: 1.2,
+                },
+            )
+        } else {
+            RateLimiter::disabled("gateway")
         };
 
-        Self::new(limiter_config)
+        Self {
+            limiter: Arc::new(limiter.with_estimator(estimator)),
+        }
     }
 }
 
@@ -287,8 +422,26 @@ pub async fn rate_limit_middleware(
 
 /// Extract rate limit key from request
 fn extract_rate_limit_key(request: &Request) -> String {
+    rate_limit_key(
+        request.headers(),
+        request.extensions().get::<AuthenticatedEntity>(),
+    )
+}
+
+/// Derive the rate limit key for a caller
+///
+/// Authenticated callers are keyed by tenant, or by entity when they have no
+/// tenant. Anonymous callers fall back to the API key, tenant header, or IP.
+pub(crate) fn rate_limit_key(headers: &HeaderMap, entity: Option<&AuthenticatedEntity>) -> String {
+    if let Some(entity) = entity.filter(|e| e.auth_method != AuthMethod::Anonymous) {
+        return entity.tenant_id.as_ref().map_or_else(
+            || format!("entity:{}", entity.id),
+            |tenant| format!("tenant:{tenant}"),
+        );
+    }
+
     // Try API key from Authorization header
-    if let Some(auth) = request.headers().get(header::AUTHORIZATION) {
+    if let Some(auth) = headers.get(header::AUTHORIZATION) {
         if let Ok(auth_str) = auth.to_str() {
             if let Some(key) = auth_str.strip_prefix("Bearer ") {
                 // Hash the API key for privacy in logs
@@ -298,14 +451,14 @@ fn extract_rate_limit_key(request: &Request) -> String {
     }
 
     // Try tenant ID header
-    if let Some(tenant) = request.headers().get("x-tenant-id") {
+    if let Some(tenant) = headers.get("x-tenant-id") {
         if let Ok(tenant_str) = tenant.to_str() {
             return format!("tenant:{tenant_str}");
         }
     }
 
     // Fall back to IP address
-    if let Some(forwarded) = request.headers().get("x-forwarded-for") {
+    if let Some(forwarded) = headers.get("x-forwarded-for") {
         if let Ok(forwarded_str) = forwarded.to_str() {
             if let Some(ip) = forwarded_str.split(',').next() {
                 return format!("ip:{}", ip.trim());
@@ -319,23 +472,24 @@ fn extract_rate_limit_key(request: &Request) -> String {
 
 /// Add rate limit headers to response
 async fn add_rate_limit_headers(response: &mut Response, limiter: &RateLimiter, key: &str) {
-    if let Some(stats) = limiter.stats(key).await {
-        let headers = response.headers_mut();
+    if !limiter.is_enabled() {
+        return;
+    }
 
-        // Standard rate limit headers
-        if let Ok(v) = HeaderValue::from_str(&stats.requests_per_window.to_string()) {
-            headers.insert("x-ratelimit-limit", v);
-        }
+    let snapshot = limiter.peek(key).await;
+    let headers = response.headers_mut();
 
-        let remaining = stats.request_tokens_available.max(0.0) as u32;
-        if let Ok(v) = HeaderValue::from_str(&remaining.to_string()) {
-            headers.insert("x-ratelimit-remaining", v);
-        }
+    // Standard rate limit headers
+    if let Ok(v) = HeaderValue::from_str(&snapshot.limit.to_string()) {
+        headers.insert("x-ratelimit-limit", v);
+    }
 
-        // Reset time (simplified - uses window duration)
-        if let Ok(v) = HeaderValue::from_str("60") {
-            headers.insert("x-ratelimit-reset", v);
-        }
+    if let Ok(v) = HeaderValue::from_str(&snapshot.remaining.to_string()) {
+        headers.insert("x-ratelimit-remaining", v);
+    }
+
+    if let Ok(v) = HeaderValue::from_str(&snapshot.reset_after.as_secs().to_string()) {
+        headers.insert("x-ratelimit-reset", v);
     }
 }
 
@@ -355,12 +509,38 @@ pub fn default_rate_limiter() -> RateLimiterState {
 mod tests {
     use super::*;
     use axum::{body::Body, routing::get, Router};
+    use std::sync::atomic::{AtomicBool, Ordering};
     use tower::ServiceExt;
 
     async fn test_handler() -> &'static str {
         "OK"
     }
 
+    fn edge_timeout_app(timeout: Duration, stall: Duration, reached: Arc<AtomicBool>) -> Router {
+        let mut config = gateway_config::GatewayConfig::default();
+        config.server.edge_timeout = timeout;
+        let state = AppState::builder().config(config).build();
+
+        Router::new()
+            .route(
+                "/",
+                get(move || async move {
+                    reached.store(true, Ordering::SeqCst);
+                    "OK"
+                }),
+            )
+            .layer(axum::middleware::from_fn(
+                move |request: Request, next: Next| async move {
+                    tokio::time::sleep(stall).await;
+                    next.run(request).await
+                },
+            ))
+            .layer(axum::middleware::from_fn_with_state(
+                state,
+                edge_timeout_middleware,
+            ))
+    }
+
     #[tokio::test]
     async fn test_cors_layer() {
         let _cors = cors_layer();
@@ -429,6 +609,48 @@ mod tests {
         assert!(response.headers().contains_key("x-response-time"));
     }
 
+    #[tokio::test]
+    async fn test_edge_timeout_terminates_stalled_request() {
+        let reached = Arc::new(AtomicBool::new(false));
+        let app = edge_timeout_app(
+            Duration::from_millis(50),
+            Duration::from_secs(5),
+            reached.clone(),
+        );
+
+        let started = Instant::now();
+        let response = app
+            .oneshot(Request::builder().uri("/").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert!(response.extensions().get::<ApiError>().is_some());
+
+        // The stalled work was dropped rather than left running
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(!reached.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_edge_timeout_passes_normal_request() {
+        let reached = Arc::new(AtomicBool::new(false));
+        let app = edge_timeout_app(
+            Duration::from_secs(5),
+            Duration::from_millis(10),
+            reached.clone(),
+        );
+
+        let response = app
+            .oneshot(Request::builder().uri("/").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(reached.load(Ordering::SeqCst));
+    }
+
     #[test]
     fn test_extract_rate_limit_key_from_auth() {
         let request = Request::builder()
@@ -465,6 +687,29 @@ mod tests {
         assert_eq!(key, "ip:192.168.1.1");
     }
 
+    #[test]
+    fn test_extract_rate_limit_key_from_authenticated_entity() {
+        let mut request = Request::builder()
+            .uri("/")
+            .header("Authorization", "Bearer sk-test-key-12345")
+            .header("x-tenant-id", "spoofed")
+            .body(Body::empty())
+            .unwrap();
+        request.extensions_mut().insert(AuthenticatedEntity {
+            id: "user-1".to_string(),
+            tenant_id: Some("acme".to_string()),
+            email: None,
+            name: None,
+            auth_method: AuthMethod::ApiKey,
+            credential_id: None,
+            scopes: Vec::new(),
+            expires_at: None,
+            claims: std::collections::HashMap::new(),
+        });
+
+        assert_eq!(extract_rate_limit_key(&request), "tenant:acme");
+    }
+
     #[test]
     fn test_extract_rate_limit_key_unknown() {
         let request = Request::builder()
@@ -584,4 +829,77 @@ mod tests {
         assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
         assert!(response.headers().contains_key("retry-after"));
     }
+
+    async fn echo_request(
+        crate::extractors::JsonBody(request): crate::extractors::JsonBody<
+            gateway_core::GatewayRequest,
+        >,
+    ) -> axum::Json<serde_json::Value> {
+        axum::Json(serde_json::json!({
+            "max_tokens": request.max_tokens,
+            "tools": request.tools.map(|tools| {
+                tools.into_iter().map(|t| t.function.name).collect::<Vec<_>>()
+            }),
+        }))
+    }
+
+    fn legacy_app(enabled: bool) -> Router {
+        let mut config = gateway_config::GatewayConfig::default();
+        config.server.legacy_request_compat = enabled;
+        let state = AppState::builder().config(config).build();
+
+        Router::new()
+            .route("/", axum::routing::post(echo_request))
+            .layer(axum::middleware::from_fn_with_state(
+                state,
+                legacy_request_compat_middleware,
+            ))
+    }
+
+    fn legacy_request() -> Request<Body> {
+        let body = serde_json::json!({
+            "model": "gpt-4o",
+            "messages": [{ "role": "user", "content": "Weather?" }],
+            "max_tokens_to_sample": 128,
+            "functions": [{ "name": "get_weather" }]
+        });
+        Request::builder()
+            .method("POST")
+            .uri("/")
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_legacy_request_compat_normalizes_fields() {
+        let response = legacy_app(true).oneshot(legacy_request()).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers().get(DEPRECATION_HEADER).unwrap(), "true");
+        let warning = response.headers().get(header::WARNING).unwrap().to_str().unwrap();
+        assert!(warning.starts_with("299"));
+        assert!(warning.contains("functions"));
+        assert!(warning.contains("max_tokens_to_sample"));
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let echoed: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(echoed["max_tokens"], 128);
+        assert_eq!(echoed["tools"], serde_json::json!(["get_weather"]));
+    }
+
+    #[tokio::test]
+    async fn test_legacy_request_compat_disabled() {
+        let response = legacy_app(false).oneshot(legacy_request()).await.unwrap();
+
+        assert!(response.headers().get(DEPRECATION_HEADER).is_none());
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let echoed: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert!(echoed["max_tokens"].is_null());
+        assert!(echoed["tools"].is_null());
+    }
 }
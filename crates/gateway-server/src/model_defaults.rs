@@ -0,0 +1,147 @@
+//! Per-model default sampling parameters.
+//!
+//! Operators can configure defaults such as `temperature` for model globs
+//! under `routing.model_defaults`. A default only fills a field the client
+//! omitted; explicit values are never overridden. Where several patterns
+//! match, the longest pattern wins field by field. The defaults that were
+//! applied are recorded in the request metadata tags.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use gateway_config::ModelDefaults;
+use gateway_core::GatewayRequest;
+use gateway_routing::rules::matches_pattern;
+
+/// Metadata tag listing the defaults that were applied
+pub const DEFAULTS_TAG: &str = "model_defaults";
+
+/// Fill omitted parameters from the defaults matching the request's model
+///
+/// Returns the applied defaults as `field=value` entries, empty if the
+/// request was left unchanged.
+pub fn apply(
+    defaults: &HashMap<String, ModelDefaults>,
+    request: &mut GatewayRequest,
+) -> Vec<String> {
+    let mut matching: Vec<(&String, &ModelDefaults)> = defaults
+        .iter()
+        .filter(|(pattern, _)| matches_pattern(pattern, &request.model))
+        .collect();
+    if matching.is_empty() {
+        return Vec::new();
+    }
+    // Most specific first, ties broken by name so the order is stable
+    matching.sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+
+    let mut applied = Vec::new();
+    for (_, model_defaults) in matching {
+        fill(&mut request.temperature, model_defaults.temperature, "temperature", &mut applied);
+        fill(&mut request.top_p, model_defaults.top_p, "top_p", &mut applied);
+        fill(&mut request.top_k, model_defaults.top_k, "top_k", &mut applied);
+        if request.token_limit().is_none() {
+            fill(&mut request.max_tokens, model_defaults.max_tokens, "max_tokens", &mut applied);
+        }
+        fill(
+            &mut request.frequency_penalty,
+            model_defaults.frequency_penalty,
+            "frequency_penalty",
+            &mut applied,
+        );
+        fill(
+            &mut request.presence_penalty,
+            model_defaults.presence_penalty,
+            "presence_penalty",
+            &mut applied,
+        );
+    }
+
+    if !applied.is_empty() {
+        request
+            .metadata
+            .get_or_insert_with(Default::default)
+            .tags
+            .insert(DEFAULTS_TAG.to_string(), applied.join(","));
+    }
+    applied
+}
+
+/// Set an absent field to its default, recording the change
+fn fill<T: Copy + Display>(
+    field: &mut Option<T>,
+    default: Option<T>,
+    name: &str,
+    applied: &mut Vec<String>,
+) {
+    if let (None, Some(default)) = (*field, default) {
+        *field = Some(default);
+        applied.push(format!("{name}={default}"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gateway_core::ChatMessage;
+
+    fn request(model: &str) -> GatewayRequest {
+        GatewayRequest::builder()
+            .model(model)
+            .message(ChatMessage::user("Hello"))
+            .build()
+            .unwrap()
+    }
+
+    fn defaults() -> HashMap<String, ModelDefaults> {
+        HashMap::from([
+            (
+                "gpt-4o".to_string(),
+                ModelDefaults {
+                    temperature: Some(0.2),
+                    ..ModelDefaults::default()
+                },
+            ),
+            (
+                "gpt-*".to_string(),
+                ModelDefaults {
+                    temperature: Some(0.7),
+                    max_tokens: Some(1024),
+                    ..ModelDefaults::default()
+                },
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_longest_pattern_wins_per_field() {
+        let mut request = request("gpt-4o");
+        let applied = apply(&defaults(), &mut request);
+
+        assert_eq!(request.temperature, Some(0.2));
+        assert_eq!(request.max_tokens, Some(1024));
+        assert_eq!(applied, vec!["temperature=0.2", "max_tokens=1024"]);
+        let tags = &request.metadata.unwrap().tags;
+        assert_eq!(tags[DEFAULTS_TAG], "temperature=0.2,max_tokens=1024");
+    }
+
+    #[test]
+    fn test_explicit_values_are_kept() {
+        let mut request = request("gpt-4o");
+        request.temperature = Some(0.9);
+        request.max_completion_tokens = Some(64);
+
+        assert!(apply(&defaults(), &mut request).is_empty());
+        assert_eq!(request.temperature, Some(0.9));
+        assert_eq!(request.max_tokens, None);
+        assert!(request.metadata.is_none());
+    }
+
+    #[test]
+    fn test_unmatched_model_is_unchanged() {
+        let mut request = request("claude-3-5-sonnet");
+
+        assert!(apply(&defaults(), &mut request).is_empty());
+        assert_eq!(request.temperature, None);
+        assert!(request.metadata.is_none());
+    }
+}
@@ -0,0 +1,408 @@
+//! Verbatim SSE proxying for OpenAI-compatible providers.
+//!
+//! In passthrough mode the upstream SSE body is piped to the client rather
+//! than parsed into chunks and re-serialized. Events are still split on
+//! their blank-line terminator so that usage can be read from the terminal
+//! event for billing, and so the `model` field can be rewritten back to the
+//! client's model name when a routing rule transformed it. Every other byte
+//! is forwarded unchanged.
+//!
+//! Usage is always requested from the upstream. If the client did not ask
+//! for it, the usage-only event is dropped before it reaches the client.
+//! Events are only parsed until the first one carrying assistant content,
+//! for time-to-first-token, and then only when they may carry usage.
+
+use std::borrow::Cow;
+use std::convert::Infallible;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use bytes::Bytes;
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use gateway_core::{GatewayError, RequestContext, Usage};
+use parking_lot::Mutex;
+use serde_json::Value;
+
+/// Splits an upstream SSE body into events and applies the passthrough
+/// rewrites
+#[derive(Debug, Default)]
+pub struct SseRelay {
+    /// Bytes of an event that has not been terminated yet
+    buffer: Vec<u8>,
+    /// Model name to report to the client, if the upstream model differs
+    alias: Option<String>,
+    /// Drop the usage-only event, as the client did not ask for it
+    strip_usage: bool,
+    /// Last usage reported by the upstream
+    usage: Arc<Mutex<Option<Usage>>>,
+    /// What the relayed stream has carried so far
+    progress: Arc<RelayProgress>,
+}
+
+/// Progress of a relayed stream, shared with the request handler
+#[derive(Debug, Default)]
+pub struct RelayProgress {
+    content: AtomicBool,
+    failed: AtomicBool,
+}
+
+impl RelayProgress {
+    /// Whether an event carrying assistant content has been relayed
+    #[must_use]
+    pub fn has_content(&self) -> bool {
+        self.content.load(Ordering::Acquire)
+    }
+
+    /// Whether the stream ended with an error event
+    #[must_use]
+    pub fn failed(&self) -> bool {
+        self.failed.load(Ordering::Acquire)
+    }
+}
+
+impl SseRelay {
+    /// Create a relay
+    ///
+    /// `alias` is the client's model name, set only when the request was
+    /// dispatched under a different one.
+    #[must_use]
+    pub fn new(alias: Option<String>, strip_usage: bool) -> Self {
+        Self {
+            alias,
+            strip_usage,
+            ..Self::default()
+        }
+    }
+
+    /// Shared slot holding the usage reported by the upstream, once seen
+    #[must_use]
+    pub fn usage(&self) -> Arc<Mutex<Option<Usage>>> {
+        self.usage.clone()
+    }
+
+    /// Shared progress of the stream
+    #[must_use]
+    pub fn progress(&self) -> Arc<RelayProgress> {
+        self.progress.clone()
+    }
+
+    /// Feed upstream bytes, returning the complete events to forward
+    pub fn push(&mut self, bytes: &[u8]) -> Bytes {
+        self.buffer.extend_from_slice(bytes);
+        let mut out = Vec::new();
+        while let Some(end) = event_end(&self.buffer) {
+            let event: Vec<u8> = self.buffer.drain(..end).collect();
+            self.relay_event(&event, &mut out);
+        }
+        Bytes::from(out)
+    }
+
+    /// Flush an unterminated trailing event once the upstream has ended
+    pub fn finish(&mut self) -> Bytes {
+        let event = std::mem::take(&mut self.buffer);
+        let mut out = Vec::new();
+        if !event.is_empty() {
+            self.relay_event(&event, &mut out);
+        }
+        Bytes::from(out)
+    }
+
+    fn relay_event(&self, event: &[u8], out: &mut Vec<u8>) {
+        let Ok(text) = std::str::from_utf8(event) else {
+            out.extend_from_slice(event);
+            return;
+        };
+        let Some(data) = data_payload(text).filter(|data| data != "[DONE]") else {
+            out.extend_from_slice(event);
+            return;
+        };
+
+        if !self.progress.has_content() && has_content(&data) {
+            self.progress.content.store(true, Ordering::Release);
+        }
+
+        if data.contains("\"usage\"") {
+            if let Ok(value) = serde_json::from_str::<Value>(&data) {
+                if let Some(usage) = parse_usage(&value) {
+                    *self.usage.lock() = Some(usage);
+                    let has_choices = value["choices"].as_array().is_some_and(|c| !c.is_empty());
+                    if self.strip_usage && !has_choices {
+                        return;
+                    }
+                }
+            }
+        }
+
+        match &self.alias {
+            Some(model) => out.extend_from_slice(rewrite_model(text, model).as_bytes()),
+            None => out.extend_from_slice(event),
+        }
+    }
+}
+
+/// Pipe an upstream SSE body through `relay`
+///
+/// The stream ends with an error event if the context is cancelled, if
+/// `max_duration` elapses, or if reading the upstream fails. Dropping the
+/// returned stream aborts the upstream connection.
+pub fn relay_stream(
+    upstream: BoxStream<'static, Result<Bytes, GatewayError>>,
+    relay: SseRelay,
+    ctx: RequestContext,
+    max_duration: Duration,
+) -> BoxStream<'static, Result<Bytes, Infallible>> {
+    struct State {
+        upstream: Option<BoxStream<'static, Result<Bytes, GatewayError>>>,
+        relay: SseRelay,
+        ctx: RequestContext,
+        deadline: Pin<Box<tokio::time::Sleep>>,
+        max_duration: Duration,
+    }
+
+    let state = State {
+        upstream: Some(upstream),
+        relay,
+        ctx,
+        deadline: Box::pin(tokio::time::sleep(max_duration)),
+        max_duration,
+    };
+
+    Box::pin(futures::stream::unfold(state, |mut state| async move {
+        loop {
+            let upstream = state.upstream.as_mut()?;
+            let item = tokio::select! {
+                biased;
+                () = state.ctx.cancelled() => Some(Err(GatewayError::internal("Request cancelled"))),
+                () = state.deadline.as_mut() => Some(Err(GatewayError::timeout(state.max_duration))),
+                item = upstream.next() => item,
+            };
+
+            let out = match item {
+                Some(Ok(bytes)) => {
+                    let out = state.relay.push(&bytes);
+                    if out.is_empty() {
+                        continue;
+                    }
+                    out
+                }
+                Some(Err(e)) => {
+                    state.upstream = None;
+                    state.relay.progress.failed.store(true, Ordering::Release);
+                    let mut out = state.relay.finish().to_vec();
+                    out.extend_from_slice(error_event(&e).as_bytes());
+                    Bytes::from(out)
+                }
+                None => {
+                    state.upstream = None;
+                    let out = state.relay.finish();
+                    if out.is_empty() {
+                        return None;
+                    }
+                    out
+                }
+            };
+            return Some((Ok(out), state));
+        }
+    }))
+}
+
+/// SSE event reporting a failure mid-stream, as sent for parsed streams
+fn error_event(error: &GatewayError) -> String {
+    let event = serde_json::json!({
+        "error": {
+            "message": error.to_string(),
+            "type": "stream_error",
+            "code": error.error_code()
+        }
+    });
+    format!("data: {event}\n\n")
+}
+
+/// Offset just past the first event terminator (a blank line) in `buffer`
+fn event_end(buffer: &[u8]) -> Option<usize> {
+    buffer.iter().enumerate().find_map(|(i, byte)| {
+        if *byte != b'\n' {
+            return None;
+        }
+        match &buffer[i + 1..] {
+            [b'\n', ..] => Some(i + 2),
+            [b'\r', b'\n', ..] => Some(i + 3),
+            _ => None,
+        }
+    })
+}
+
+/// Joined `data:` lines of an event, if it has any
+fn data_payload(event: &str) -> Option<String> {
+    let lines: Vec<&str> = event
+        .lines()
+        .filter_map(|line| line.strip_prefix("data:"))
+        .map(|data| data.strip_prefix(' ').unwrap_or(data))
+        .collect();
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+/// Whether a chunk carries non-empty assistant content
+fn has_content(data: &str) -> bool {
+    serde_json::from_str::<Value>(data).is_ok_and(|chunk| {
+        chunk["choices"].as_array().is_some_and(|choices| {
+            choices
+                .iter()
+                .any(|choice| choice["delta"]["content"].as_str().is_some_and(|c| !c.is_empty()))
+        })
+    })
+}
+
+/// Usage carried by a chunk, if it is present and not `null`
+fn parse_usage(chunk: &Value) -> Option<Usage> {
+    let value = chunk.get("usage").filter(|usage| !usage.is_null())?;
+    let mut usage: Usage = serde_json::from_value(value.clone()).ok()?;
+    if usage.prompt_tokens_cached.is_none() {
+        usage.prompt_tokens_cached = value["prompt_tokens_details"]["cached_tokens"]
+            .as_u64()
+            .and_then(|tokens| u32::try_from(tokens).ok());
+    }
+    Some(usage)
+}
+
+/// Replace the value of the first `"model"` string field in an event
+///
+/// The rest of the event is left byte-for-byte intact.
+fn rewrite_model<'a>(event: &'a str, model: &str) -> Cow<'a, str> {
+    let Some(key) = event.find("\"model\"") else {
+        return Cow::Borrowed(event);
+    };
+    let after_key = key + "\"model\"".len();
+    let rest = &event[after_key..];
+    let Some(colon) = rest.find(|c: char| !c.is_whitespace()).filter(|&i| rest[i..].starts_with(':')) else {
+        return Cow::Borrowed(event);
+    };
+    let value_rest = &rest[colon + 1..];
+    let Some(open) = value_rest
+        .find(|c: char| !c.is_whitespace())
+        .filter(|&i| value_rest[i..].starts_with('"'))
+    else {
+        return Cow::Borrowed(event);
+    };
+    let start = after_key + colon + 1 + open;
+
+    // Find the closing quote, skipping escaped characters
+    let mut escaped = false;
+    let Some(len) = event[start + 1..].char_indices().find_map(|(i, c)| match c {
+        _ if escaped => {
+            escaped = false;
+            None
+        }
+        '\\' => {
+            escaped = true;
+            None
+        }
+        '"' => Some(i),
+        _ => None,
+    }) else {
+        return Cow::Borrowed(event);
+    };
+    let end = start + 1 + len + 1;
+
+    let replacement = serde_json::to_string(model).unwrap_or_default();
+    Cow::Owned(format!("{}{replacement}{}", &event[..start], &event[end..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &str = "data: {\"id\":\"chatcmpl-1\",\"model\":\"gpt-4o-2024-08-06\",\"choices\":[{\"index\":0,\"delta\":{\"content\":\"Hi\"}}]}\n\n";
+    const USAGE: &str = "data: {\"id\":\"chatcmpl-1\",\"model\":\"gpt-4o-2024-08-06\",\"choices\":[],\"usage\":{\"prompt_tokens\":9,\"completion_tokens\":1,\"total_tokens\":10,\"prompt_tokens_details\":{\"cached_tokens\":4}}}\n\n";
+    const DONE: &str = "data: [DONE]\n\n";
+
+    #[test]
+    fn test_events_forwarded_verbatim_across_splits() {
+        let body = format!("{CONTENT}{USAGE}{DONE}");
+        let mut relay = SseRelay::new(None, false);
+
+        let (first, second) = body.split_at(30);
+        let mut out = relay.push(first.as_bytes()).to_vec();
+        out.extend_from_slice(&relay.push(second.as_bytes()));
+        out.extend_from_slice(&relay.finish());
+
+        assert_eq!(String::from_utf8(out).unwrap(), body);
+        let usage = relay.usage().lock().clone().unwrap();
+        assert_eq!(usage.prompt_tokens, 9);
+        assert_eq!(usage.prompt_tokens_cached, Some(4));
+    }
+
+    #[test]
+    fn test_usage_event_stripped_when_not_requested() {
+        let mut relay = SseRelay::new(None, true);
+
+        let out = relay.push(format!("{CONTENT}{USAGE}{DONE}").as_bytes());
+
+        assert_eq!(String::from_utf8(out.to_vec()).unwrap(), format!("{CONTENT}{DONE}"));
+        assert_eq!(relay.usage().lock().as_ref().unwrap().total_tokens, 10);
+    }
+
+    #[test]
+    fn test_alias_rewrites_only_model() {
+        let mut relay = SseRelay::new(Some("fast".to_string()), false);
+
+        let out = relay.push(CONTENT.as_bytes());
+
+        assert_eq!(
+            String::from_utf8(out.to_vec()).unwrap(),
+            CONTENT.replace("gpt-4o-2024-08-06", "fast")
+        );
+    }
+
+    #[test]
+    fn test_progress_marks_first_content() {
+        let mut relay = SseRelay::new(None, false);
+        let progress = relay.progress();
+
+        relay.push(b"data: {\"choices\":[{\"index\":0,\"delta\":{\"role\":\"assistant\",\"content\":\"\"}}]}\n\n");
+        assert!(!progress.has_content());
+
+        relay.push(CONTENT.as_bytes());
+        assert!(progress.has_content());
+        assert!(!progress.failed());
+    }
+
+    #[tokio::test]
+    async fn test_upstream_error_marks_progress_failed() {
+        let relay = SseRelay::new(None, false);
+        let progress = relay.progress();
+        let upstream = futures::stream::iter(vec![Err(GatewayError::internal("reset"))]);
+
+        let events: Vec<_> = relay_stream(
+            Box::pin(upstream),
+            relay,
+            RequestContext::new(),
+            Duration::from_secs(60),
+        )
+        .collect()
+        .await;
+
+        assert_eq!(events.len(), 1);
+        assert!(progress.failed());
+        assert!(!progress.has_content());
+    }
+
+    #[test]
+    fn test_event_end_handles_crlf() {
+        assert_eq!(event_end(b"data: x\r\n\r\ndata: y"), Some(11));
+        assert_eq!(event_end(b"data: x\n\n"), Some(9));
+        assert_eq!(event_end(b"data: x\n"), None);
+    }
+
+    #[test]
+    fn test_rewrite_model_handles_escapes_and_spacing() {
+        assert_eq!(
+            rewrite_model(r#"data: {"model" : "a\"b", "x": 1}"#, "c"),
+            r#"data: {"model" : "c", "x": 1}"#
+        );
+        assert_eq!(rewrite_model("data: {\"x\":1}", "c"), "data: {\"x\":1}");
+    }
+}
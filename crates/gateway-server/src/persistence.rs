@@ -0,0 +1,404 @@
+//! Opt-in persistence of completed request/response exchanges.
+//!
+//! Records are keyed by request id and tenant, optionally PII-redacted before
+//! they are written, and deleted by a background sweeper once their retention
+//! TTL has elapsed. The table is created on startup with portable SQL so the
+//! same store works against PostgreSQL and SQLite.
+
+use chrono::{DateTime, TimeZone, Utc};
+use gateway_config::PersistenceConfig;
+use gateway_core::{GatewayRequest, GatewayResponse};
+use gateway_migrations::{
+    sqlx::{self, Row},
+    DatabasePool, MigrationConfig, MigrationError, Result,
+};
+use gateway_telemetry::{ContentLimit, PiiConfig, PiiRedactor};
+use std::borrow::Cow;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+
+/// Table holding persisted exchanges
+const EXCHANGES_TABLE: &str = "gateway_exchanges";
+
+/// A persisted request/response exchange
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExchangeRecord {
+    /// Request ID (primary key)
+    pub request_id: String,
+    /// Tenant the request belongs to
+    pub tenant_id: Option<String>,
+    /// Requested model
+    pub model: String,
+    /// Provider that served the request
+    pub provider: String,
+    /// Serialized request messages
+    pub prompt: String,
+    /// Serialized response
+    pub response: String,
+    /// HTTP status returned to the client
+    pub status_code: u16,
+    /// When the exchange completed
+    pub created_at: DateTime<Utc>,
+}
+
+impl ExchangeRecord {
+    /// Build a record from a completed chat completion
+    ///
+    /// # Errors
+    /// Returns an error if the request or response cannot be serialized
+    pub fn from_completion(
+        request_id: impl Into<String>,
+        provider: impl Into<String>,
+        request: &GatewayRequest,
+        response: &GatewayResponse,
+    ) -> Result<Self> {
+        Ok(Self {
+            request_id: request_id.into(),
+            tenant_id: request.metadata.as_ref().and_then(|m| m.tenant_id.clone()),
+            model: request.model.clone(),
+            provider: provider.into(),
+            prompt: serde_json::to_string(&request.messages)?,
+            response: serde_json::to_string(response)?,
+            status_code: 200,
+            created_at: Utc::now(),
+        })
+    }
+}
+
+/// Store for request/response exchanges
+pub struct ExchangeStore {
+    pool: DatabasePool,
+    redactor: Option<PiiRedactor>,
+    content_limit: Option<ContentLimit>,
+    retention: Duration,
+}
+
+impl ExchangeStore {
+    /// Connect to the configured database
+    ///
+    /// Returns `Ok(None)` when persistence is disabled.
+    ///
+    /// # Errors
+    /// Returns an error if persistence is enabled without a database URL or
+    /// the database cannot be reached
+    pub async fn connect(config: &PersistenceConfig) -> Result<Option<Self>> {
+        if !config.enabled {
+            return Ok(None);
+        }
+
+        let Some(url) = config.database_url.as_deref() else {
+            return Err(MigrationError::Config(
+                "persistence is enabled but no database_url is set".to_string(),
+            ));
+        };
+
+        let migration_config = MigrationConfig::builder()
+            .database_url(url)
+            .max_connections(config.max_connections)
+            .build()?;
+        let pool = DatabasePool::new(migration_config).await?;
+
+        Self::new(pool, config).await.map(Some)
+    }
+
+    /// Create a store on an existing pool, creating the table if needed
+    ///
+    /// # Errors
+    /// Returns an error if the table cannot be created
+    pub async fn new(pool: DatabasePool, config: &PersistenceConfig) -> Result<Self> {
+        let store = Self {
+            pool,
+            redactor: config
+                .redact_pii
+                .then(|| PiiRedactor::new(PiiConfig::all_patterns())),
+            content_limit: config.max_stored_content_length.map(ContentLimit::new),
+            retention: config.retention,
+        };
+        store.ensure_schema().await?;
+        Ok(store)
+    }
+
+    async fn ensure_schema(&self) -> Result<()> {
+        sqlx::query(&format!(
+            "CREATE TABLE IF NOT EXISTS {EXCHANGES_TABLE} (
+                request_id TEXT PRIMARY KEY,
+                tenant_id TEXT,
+                model TEXT NOT NULL,
+                provider TEXT NOT NULL,
+                prompt TEXT NOT NULL,
+                response TEXT NOT NULL,
+                status_code BIGINT NOT NULL,
+                created_at BIGINT NOT NULL,
+                expires_at BIGINT NOT NULL
+            )"
+        ))
+        .execute(self.pool.inner())
+        .await?;
+
+        sqlx::query(&format!(
+            "CREATE INDEX IF NOT EXISTS idx_{EXCHANGES_TABLE}_expires_at ON {EXCHANGES_TABLE}(expires_at)"
+        ))
+        .execute(self.pool.inner())
+        .await?;
+
+        sqlx::query(&format!(
+            "CREATE INDEX IF NOT EXISTS idx_{EXCHANGES_TABLE}_tenant ON {EXCHANGES_TABLE}(tenant_id)"
+        ))
+        .execute(self.pool.inner())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Retention TTL applied to new records
+    #[must_use]
+    pub fn retention(&self) -> Duration {
+        self.retention
+    }
+
+    /// Truncate and redact `text` as configured
+    ///
+    /// Truncation runs first, while `text` is still the serialized JSON it
+    /// can cut string by string.
+    fn for_storage(&self, text: &str) -> String {
+        let text = self
+            .content_limit
+            .as_ref()
+            .map_or(Cow::Borrowed(text), |limit| limit.truncate_serialized(text));
+        self.redactor
+            .as_ref()
+            .map_or_else(|| text.to_string(), |r| r.redact(&text).into_owned())
+    }
+
+    /// Persist an exchange, redacting prompt and response if configured and
+    /// truncating content past `max_stored_content_length`
+    ///
+    /// # Errors
+    /// Returns an error if the insert fails
+    pub async fn save(&self, record: &ExchangeRecord) -> Result<()> {
+        let created_at = record.created_at.timestamp();
+        let ttl_secs = i64::try_from(self.retention.as_secs()).unwrap_or(i64::MAX);
+        let expires_at = created_at.saturating_add(ttl_secs);
+
+        sqlx::query(&format!(
+            "INSERT INTO {EXCHANGES_TABLE}
+                (request_id, tenant_id, model, provider, prompt, response, status_code, created_at, expires_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)"
+        ))
+        .bind(&record.request_id)
+        .bind(record.tenant_id.as_deref())
+        .bind(&record.model)
+        .bind(&record.provider)
+        .bind(self.for_storage(&record.prompt))
+        .bind(self.for_storage(&record.response))
+        .bind(i64::from(record.status_code))
+        .bind(created_at)
+        .bind(expires_at)
+        .execute(self.pool.inner())
+        .await?;
+
+        debug!(request_id = %record.request_id, "Persisted exchange");
+        Ok(())
+    }
+
+    /// Load a persisted exchange by request ID
+    ///
+    /// # Errors
+    /// Returns an error if the query fails
+    pub async fn get(&self, request_id: &str) -> Result<Option<ExchangeRecord>> {
+        let row = sqlx::query(&format!(
+            "SELECT request_id, tenant_id, model, provider, prompt, response, status_code, created_at
+             FROM {EXCHANGES_TABLE} WHERE request_id = $1"
+        ))
+        .bind(request_id)
+        .fetch_optional(self.pool.inner())
+        .await?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        let status_code: i64 = row.try_get("status_code")?;
+        let created_at: i64 = row.try_get("created_at")?;
+
+        Ok(Some(ExchangeRecord {
+            request_id: row.try_get("request_id")?,
+            tenant_id: row.try_get("tenant_id")?,
+            model: row.try_get("model")?,
+            provider: row.try_get("provider")?,
+            prompt: row.try_get("prompt")?,
+            response: row.try_get("response")?,
+            status_code: u16::try_from(status_code).unwrap_or_default(),
+            created_at: Utc
+                .timestamp_opt(created_at, 0)
+                .single()
+                .unwrap_or_default(),
+        }))
+    }
+
+    /// Delete records whose retention TTL has elapsed
+    ///
+    /// Returns the number of records removed.
+    ///
+    /// # Errors
+    /// Returns an error if the delete fails
+    pub async fn sweep_expired(&self) -> Result<u64> {
+        let result = sqlx::query(&format!(
+            "DELETE FROM {EXCHANGES_TABLE} WHERE expires_at <= $1"
+        ))
+        .bind(Utc::now().timestamp())
+        .execute(self.pool.inner())
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Spawn a background task that sweeps expired records on `interval`
+    #[must_use]
+    pub fn spawn_sweeper(self: Arc<Self>, interval: Duration) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                match self.sweep_expired().await {
+                    Ok(0) => {}
+                    Ok(removed) => debug!(removed, "Swept expired exchanges"),
+                    Err(e) => warn!(error = %e, "Exchange retention sweep failed"),
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gateway_core::{ChatMessage, Choice, FinishReason, RequestMetadata, Usage};
+
+    async fn sqlite_store(config: &PersistenceConfig) -> ExchangeStore {
+        // A single connection keeps every query on the same in-memory database
+        let migration_config = MigrationConfig::builder()
+            .database_url("sqlite::memory:")
+            .max_connections(1)
+            .build()
+            .expect("valid config");
+        let pool = DatabasePool::new(migration_config).await.expect("pool");
+        ExchangeStore::new(pool, config).await.expect("store")
+    }
+
+    fn completed_exchange(request_id: &str) -> ExchangeRecord {
+        let request = GatewayRequest::builder()
+            .model("gpt-4o")
+            .message(ChatMessage::user(
+                "Email me at jane.doe@example.com or call 555-123-4567",
+            ))
+            .metadata(RequestMetadata {
+                tenant_id: Some("acme".to_string()),
+                ..RequestMetadata::default()
+            })
+            .build()
+            .expect("valid request");
+        let response = GatewayResponse::builder()
+            .id("chatcmpl-1")
+            .model("gpt-4o")
+            .choice(Choice::new(0, "Sure, I'll email jane.doe@example.com", FinishReason::Stop))
+            .usage(Usage::new(12, 8))
+            .build();
+
+        ExchangeRecord::from_completion(request_id, "openai", &request, &response)
+            .expect("serializable")
+    }
+
+    #[tokio::test]
+    async fn test_disabled_by_default() {
+        let store = ExchangeStore::connect(&PersistenceConfig::default())
+            .await
+            .expect("connect");
+        assert!(store.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_completed_request_persisted_redacted() {
+        let store = sqlite_store(&PersistenceConfig::default()).await;
+        let record = completed_exchange("req-1");
+        store.save(&record).await.expect("save");
+
+        let stored = store.get("req-1").await.expect("get").expect("record");
+        assert_eq!(stored.tenant_id.as_deref(), Some("acme"));
+        assert_eq!(stored.model, "gpt-4o");
+        assert_eq!(stored.provider, "openai");
+        assert_eq!(stored.status_code, 200);
+        assert!(!stored.prompt.contains("jane.doe@example.com"));
+        assert!(!stored.response.contains("jane.doe@example.com"));
+        assert!(stored.prompt.contains("[EMAIL]"));
+    }
+
+    #[tokio::test]
+    async fn test_redaction_can_be_disabled() {
+        let config = PersistenceConfig {
+            redact_pii: false,
+            ..PersistenceConfig::default()
+        };
+        let store = sqlite_store(&config).await;
+        store.save(&completed_exchange("req-1")).await.expect("save");
+
+        let stored = store.get("req-1").await.expect("get").expect("record");
+        assert!(stored.prompt.contains("jane.doe@example.com"));
+    }
+
+    #[tokio::test]
+    async fn test_long_content_truncated_in_storage() {
+        let config = PersistenceConfig {
+            redact_pii: false,
+            max_stored_content_length: Some(16),
+            ..PersistenceConfig::default()
+        };
+        let store = sqlite_store(&config).await;
+        let request = GatewayRequest::builder()
+            .model("gpt-4o")
+            .message(ChatMessage::user("Tell a story"))
+            .build()
+            .expect("valid request");
+        let generation = "Once upon a time ".repeat(100);
+        let response = GatewayResponse::builder()
+            .id("chatcmpl-1")
+            .model("gpt-4o")
+            .choice(Choice::new(0, generation.as_str(), FinishReason::Stop))
+            .build();
+        let record = ExchangeRecord::from_completion("req-1", "openai", &request, &response)
+            .expect("serializable");
+        store.save(&record).await.expect("save");
+
+        let stored = store.get("req-1").await.expect("get").expect("record");
+        let stored_response: serde_json::Value =
+            serde_json::from_str(&stored.response).expect("still valid JSON");
+        assert_eq!(
+            stored_response["choices"][0]["message"]["content"],
+            "Once upon a time...[truncated from 1700 bytes]"
+        );
+        assert_eq!(stored_response["id"], "chatcmpl-1");
+        assert!(stored.prompt.contains("Tell a story"));
+        // The record handed to the store is left whole
+        assert!(record.response.contains(&generation));
+    }
+
+    #[tokio::test]
+    async fn test_sweeper_removes_expired_records() {
+        let config = PersistenceConfig {
+            retention: Duration::from_secs(60 * 60),
+            ..PersistenceConfig::default()
+        };
+        let store = sqlite_store(&config).await;
+
+        let mut expired = completed_exchange("req-old");
+        expired.created_at = Utc::now() - chrono::Duration::hours(2);
+        store.save(&expired).await.expect("save");
+        store.save(&completed_exchange("req-new")).await.expect("save");
+
+        assert_eq!(store.sweep_expired().await.expect("sweep"), 1);
+        assert!(store.get("req-old").await.expect("get").is_none());
+        assert!(store.get("req-new").await.expect("get").is_some());
+    }
+}
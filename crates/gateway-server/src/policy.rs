@@ -0,0 +1,390 @@
+//! Inline policy enforcement.
+//!
+//! [`PolicyGate`] asks the configured [`PolicyConsumer`] whether a request
+//! may be dispatched. Denied requests are rejected with 403 and the policy
+//! reason, and a [`DecisionEvent`] is recorded for the audit trail.
+//! Decisions are cached per model and tenant for a short TTL so the policy
+//! engine isn't consulted on every request.
+
+use dashmap::DashMap;
+use gateway_core::{GatewayRequest, RequestMetadata};
+use gateway_integrations::config::PolicyEngineConfig;
+use gateway_integrations::traits::PolicyDecision;
+use gateway_integrations::{DecisionEvent, PolicyConsumer, RuVectorPersistence};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+use crate::error::ApiError;
+
+/// Agent ID recorded on policy decision events
+const POLICY_AGENT_ID: &str = "policy-gate";
+
+/// Pre-dispatch policy check
+pub struct PolicyGate {
+    consumer: Arc<dyn PolicyConsumer>,
+    recorder: Option<Arc<dyn RuVectorPersistence>>,
+    fail_open: bool,
+    cache_ttl: Duration,
+    decisions: DashMap<String, (PolicyDecision, Instant)>,
+}
+
+impl PolicyGate {
+    /// Create a gate using the fail mode and cache settings from `config`
+    #[must_use]
+    pub fn new(consumer: Arc<dyn PolicyConsumer>, config: &PolicyEngineConfig) -> Self {
+        Self {
+            consumer,
+            recorder: None,
+            fail_open: config.fail_open,
+            cache_ttl: if config.cache_decisions {
+                config.cache_ttl
+            } else {
+                Duration::ZERO
+            },
+            decisions: DashMap::new(),
+        }
+    }
+
+    /// Record deny decisions to `recorder`
+    #[must_use]
+    pub fn with_recorder(mut self, recorder: Arc<dyn RuVectorPersistence>) -> Self {
+        self.recorder = Some(recorder);
+        self
+    }
+
+    /// Check whether `request` may be dispatched
+    ///
+    /// # Errors
+    /// Returns 403 with the policy reason on a deny decision, or 503 if the
+    /// policy engine is unreachable and the gate fails closed
+    pub async fn check(
+        &self,
+        request: &GatewayRequest,
+        request_id: &str,
+        tenant_id: Option<&str>,
+    ) -> Result<(), ApiError> {
+        let key = format!("{}\u{0}{}", request.model, tenant_id.unwrap_or_default());
+
+        let decision = match self.cached(&key) {
+            Some(decision) => decision,
+            None => match self.evaluate(request, tenant_id).await {
+                Ok(decision) => {
+                    if !self.cache_ttl.is_zero() {
+                        self.decisions.insert(key, (decision.clone(), Instant::now()));
+                    }
+                    decision
+                }
+                Err(error) if self.fail_open => {
+                    warn!(request_id = %request_id, error = %error, "Policy engine unreachable, allowing request");
+                    return Ok(());
+                }
+                Err(error) => {
+                    warn!(request_id = %request_id, error = %error, "Policy engine unreachable, rejecting request");
+                    self.record(request, request_id, tenant_id, None, Some(&error))
+                        .await;
+                    return Err(ApiError::service_unavailable(
+                        "Policy engine unavailable",
+                    ));
+                }
+            },
+        };
+
+        if decision.allowed {
+            return Ok(());
+        }
+
+        let reason = deny_reason(&decision);
+        debug!(request_id = %request_id, reason = %reason, "Request denied by policy");
+        self.record(request, request_id, tenant_id, Some(&decision), None)
+            .await;
+        Err(ApiError::forbidden(format!("Request denied by policy: {reason}")))
+    }
+
+    /// Cached decision for `key`, if still fresh
+    fn cached(&self, key: &str) -> Option<PolicyDecision> {
+        let entry = self.decisions.get(key)?;
+        let (decision, cached_at) = entry.value();
+        (cached_at.elapsed() < self.cache_ttl).then(|| decision.clone())
+    }
+
+    /// Ask the policy engine, passing the tenant along in request metadata
+    async fn evaluate(
+        &self,
+        request: &GatewayRequest,
+        tenant_id: Option<&str>,
+    ) -> Result<PolicyDecision, String> {
+        let has_tenant = request
+            .metadata
+            .as_ref()
+            .map_or(false, |m| m.tenant_id.is_some());
+
+        let result = match tenant_id {
+            Some(tenant) if !has_tenant => {
+                let mut request = request.clone();
+                request
+                    .metadata
+                    .get_or_insert_with(RequestMetadata::default)
+                    .tenant_id = Some(tenant.to_string());
+                self.consumer.evaluate_request(&request).await
+            }
+            _ => self.consumer.evaluate_request(request).await,
+        };
+        result.map_err(|e| e.to_string())
+    }
+
+    /// Persist a decision event for a denied or unevaluated request
+    async fn record(
+        &self,
+        request: &GatewayRequest,
+        request_id: &str,
+        tenant_id: Option<&str>,
+        decision: Option<&PolicyDecision>,
+        error: Option<&str>,
+    ) {
+        let Some(recorder) = &self.recorder else {
+            return;
+        };
+
+        let mut builder = DecisionEvent::builder()
+            .execution_ref(request_id)
+            .agent_id(POLICY_AGENT_ID)
+            .decision_type("policy")
+            .input(serde_json::json!({
+                "model": request.model,
+                "tenant_id": tenant_id,
+            }))
+            .output(decision.map_or(serde_json::Value::Null, |d| {
+                serde_json::to_value(d).unwrap_or_default()
+            }))
+            .success(error.is_none());
+        if let Some(error) = error {
+            builder = builder.error_message(error);
+        }
+
+        let Ok(event) = builder.build() else {
+            return;
+        };
+        if let Err(e) = recorder.persist_decision_event(&event).await {
+            warn!(request_id = %request_id, error = %e, "Failed to record policy decision");
+        }
+    }
+}
+
+impl std::fmt::Debug for PolicyGate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PolicyGate")
+            .field("fail_open", &self.fail_open)
+            .field("cache_ttl", &self.cache_ttl)
+            .field("cached_decisions", &self.decisions.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Human-readable reason for a deny decision
+fn deny_reason(decision: &PolicyDecision) -> String {
+    if !decision.violations.is_empty() {
+        return decision
+            .violations
+            .iter()
+            .map(|v| v.description.as_str())
+            .collect::<Vec<_>>()
+            .join("; ");
+    }
+    if !decision.matched_policies.is_empty() {
+        return decision.matched_policies.join(", ");
+    }
+    "no reason given".to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use gateway_core::{ChatMessage, GatewayResponse};
+    use gateway_integrations::adapters::ruvector::EventsResponse;
+    use gateway_integrations::traits::{EnforcementReport, Policy, PolicyViolation};
+    use gateway_integrations::{EventQuery, IntegrationError, IntegrationResult};
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    /// Policy consumer returning a fixed outcome and counting calls
+    struct FixedPolicy {
+        outcome: Option<PolicyDecision>,
+        calls: AtomicUsize,
+        tenants: Mutex<Vec<Option<String>>>,
+    }
+
+    impl FixedPolicy {
+        fn new(outcome: Option<PolicyDecision>) -> Arc<Self> {
+            Arc::new(Self {
+                outcome,
+                calls: AtomicUsize::new(0),
+                tenants: Mutex::new(Vec::new()),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl PolicyConsumer for FixedPolicy {
+        async fn evaluate_request(
+            &self,
+            request: &GatewayRequest,
+        ) -> IntegrationResult<PolicyDecision> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            self.tenants
+                .lock()
+                .unwrap()
+                .push(request.metadata.as_ref().and_then(|m| m.tenant_id.clone()));
+            self.outcome
+                .clone()
+                .ok_or_else(|| IntegrationError::Connection("connection refused".to_string()))
+        }
+
+        async fn evaluate_response(
+            &self,
+            _request: &GatewayRequest,
+            _response: &GatewayResponse,
+        ) -> IntegrationResult<PolicyDecision> {
+            Ok(decision(true, None))
+        }
+
+        async fn consume_policy_updates(&self) -> IntegrationResult<Vec<Policy>> {
+            Ok(Vec::new())
+        }
+
+        async fn report_enforcement(&self, _report: EnforcementReport) -> IntegrationResult<()> {
+            Ok(())
+        }
+    }
+
+    /// Persistence double keeping recorded events in memory
+    #[derive(Default)]
+    struct RecordedEvents(Mutex<Vec<DecisionEvent>>);
+
+    #[async_trait]
+    impl RuVectorPersistence for RecordedEvents {
+        async fn persist_decision_event(&self, event: &DecisionEvent) -> IntegrationResult<String> {
+            self.0.lock().unwrap().push(event.clone());
+            Ok(event.id.clone())
+        }
+
+        async fn get_events_by_execution(
+            &self,
+            _execution_ref: &str,
+        ) -> IntegrationResult<Vec<DecisionEvent>> {
+            Ok(Vec::new())
+        }
+
+        async fn search_events(&self, _query: &EventQuery) -> IntegrationResult<EventsResponse> {
+            Err(IntegrationError::NotEnabled("search".to_string()))
+        }
+
+        async fn delete_event(&self, _event_id: &str) -> IntegrationResult<bool> {
+            Ok(false)
+        }
+
+        async fn persist_batch(&self, _events: &[DecisionEvent]) -> IntegrationResult<Vec<String>> {
+            Ok(Vec::new())
+        }
+    }
+
+    fn decision(allowed: bool, violation: Option<&str>) -> PolicyDecision {
+        PolicyDecision {
+            allowed,
+            matched_policies: Vec::new(),
+            violations: violation
+                .map(|description| PolicyViolation {
+                    policy_id: "p1".to_string(),
+                    policy_name: "no-gpt4".to_string(),
+                    description: description.to_string(),
+                    severity: 80,
+                })
+                .into_iter()
+                .collect(),
+            required_actions: Vec::new(),
+            metadata: HashMap::new(),
+        }
+    }
+
+    fn request() -> GatewayRequest {
+        GatewayRequest::builder()
+            .model("gpt-4o")
+            .message(ChatMessage::user("hi"))
+            .build()
+            .unwrap()
+    }
+
+    fn config(fail_open: bool) -> PolicyEngineConfig {
+        PolicyEngineConfig {
+            enabled: true,
+            fail_open,
+            ..PolicyEngineConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_allow_passes_and_is_cached() {
+        let policy = FixedPolicy::new(Some(decision(true, None)));
+        let gate = PolicyGate::new(policy.clone(), &config(false));
+
+        gate.check(&request(), "req-1", Some("acme")).await.unwrap();
+        gate.check(&request(), "req-2", Some("acme")).await.unwrap();
+
+        assert_eq!(policy.calls.load(Ordering::SeqCst), 1);
+        assert_eq!(policy.tenants.lock().unwrap()[0].as_deref(), Some("acme"));
+
+        // Another tenant gets its own decision
+        gate.check(&request(), "req-3", Some("globex")).await.unwrap();
+        assert_eq!(policy.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_deny_blocks_with_reason_and_records_event() {
+        let policy = FixedPolicy::new(Some(decision(false, Some("model not approved"))));
+        let events = Arc::new(RecordedEvents::default());
+        let gate = PolicyGate::new(policy, &config(true)).with_recorder(events.clone());
+
+        let err = gate.check(&request(), "req-1", None).await.unwrap_err();
+        assert_eq!(err.status, axum::http::StatusCode::FORBIDDEN);
+        assert!(err.message.contains("model not approved"));
+
+        let events = events.0.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].execution_ref, "req-1");
+        assert_eq!(events[0].decision_type, "policy");
+        assert_eq!(events[0].output["allowed"], false);
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_engine_fails_open() {
+        let policy = FixedPolicy::new(None);
+        let gate = PolicyGate::new(policy.clone(), &config(true));
+
+        gate.check(&request(), "req-1", None).await.unwrap();
+        gate.check(&request(), "req-2", None).await.unwrap();
+
+        // Failures aren't cached, so the engine is retried
+        assert_eq!(policy.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_engine_fails_closed() {
+        let events = Arc::new(RecordedEvents::default());
+        let gate = PolicyGate::new(FixedPolicy::new(None), &config(false))
+            .with_recorder(events.clone());
+
+        let err = gate.check(&request(), "req-1", None).await.unwrap_err();
+        assert_eq!(err.status, axum::http::StatusCode::SERVICE_UNAVAILABLE);
+
+        let events = events.0.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        assert!(!events[0].success);
+        assert!(events[0]
+            .error_message
+            .as_deref()
+            .unwrap()
+            .contains("connection refused"));
+    }
+}
@@ -0,0 +1,69 @@
+//! Response post-processor selection.
+//!
+//! Which processors from [`gateway_core::PostProcessors`] run on a response
+//! is decided per tenant in the config, or per request with the
+//! `X-Post-Process` header when callers may choose. Only non-streaming
+//! responses are processed.
+
+use gateway_config::PostProcessingConfig;
+
+/// Header listing the processors to run, comma separated
+pub const HEADER: &str = "x-post-process";
+
+/// Processors selected for a request, in the order they run
+///
+/// An allowed header replaces the tenant's list; `none` selects no
+/// processors.
+#[must_use]
+pub fn select(
+    config: &PostProcessingConfig,
+    tenant_id: Option<&str>,
+    header: Option<&str>,
+) -> Vec<String> {
+    if let Some(header) = header.filter(|_| config.allow_header) {
+        return header
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty() && *name != "none")
+            .map(str::to_string)
+            .collect();
+    }
+    tenant_id
+        .and_then(|tenant| config.tenants.get(tenant))
+        .cloned()
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> PostProcessingConfig {
+        let mut config = PostProcessingConfig::default();
+        config
+            .tenants
+            .insert("tools".to_string(), vec!["strip_code_fences".to_string()]);
+        config
+    }
+
+    #[test]
+    fn test_select_by_tenant() {
+        let config = config();
+        assert_eq!(select(&config, Some("tools"), None), ["strip_code_fences"]);
+        assert!(select(&config, Some("other"), None).is_empty());
+        assert!(select(&config, None, None).is_empty());
+    }
+
+    #[test]
+    fn test_header_replaces_tenant_list() {
+        let mut config = config();
+        assert_eq!(
+            select(&config, None, Some("strip_code_fences, custom")),
+            ["strip_code_fences", "custom"]
+        );
+        assert!(select(&config, Some("tools"), Some("none")).is_empty());
+
+        config.allow_header = false;
+        assert_eq!(select(&config, Some("tools"), Some("none")), ["strip_code_fences"]);
+    }
+}
@@ -0,0 +1,141 @@
+//! Per-request provider overrides.
+//!
+//! Callers debugging a provider, or comparing providers, can pin a request
+//! to one with the `X-Gateway-Provider` header or
+//! `metadata.preferred_provider`. Only authenticated callers holding
+//! `routing.provider_override.scope` may do so; overrides from anyone else
+//! are rejected or ignored per `routing.provider_override.unauthorized`.
+//! The pinned provider still goes through the router's allowlist, health
+//! and capability checks.
+
+use gateway_config::{ProviderOverrideConfig, UnauthorizedOverride};
+use gateway_core::GatewayRequest;
+
+use crate::auth::{AuthMethod, AuthenticatedEntity};
+use crate::error::ApiError;
+
+/// Header naming the provider to pin the request to
+pub const HEADER: &str = "x-gateway-provider";
+
+/// Provider requested by the caller, the header taking precedence
+#[must_use]
+pub fn requested<'a>(request: &'a GatewayRequest, header: Option<&'a str>) -> Option<&'a str> {
+    header
+        .or_else(|| {
+            request
+                .metadata
+                .as_ref()
+                .and_then(|metadata| metadata.preferred_provider.as_deref())
+        })
+        .map(str::trim)
+        .filter(|provider| !provider.is_empty())
+}
+
+/// Provider to pin the request to, or `None` to route normally
+///
+/// # Errors
+/// Returns `403` if the caller may not override routing and unauthorized
+/// overrides are rejected
+pub fn resolve(
+    config: &ProviderOverrideConfig,
+    entity: Option<&AuthenticatedEntity>,
+    requested: Option<&str>,
+) -> Result<Option<String>, ApiError> {
+    let Some(provider) = requested.filter(|_| config.enabled) else {
+        return Ok(None);
+    };
+
+    let authorized = entity.is_some_and(|entity| {
+        entity.auth_method != AuthMethod::Anonymous && entity.scopes.contains(&config.scope)
+    });
+    if authorized {
+        return Ok(Some(provider.to_string()));
+    }
+
+    match config.unauthorized {
+        UnauthorizedOverride::Reject => Err(ApiError::forbidden(format!(
+            "Overriding the provider requires the '{}' scope",
+            config.scope
+        ))
+        .with_code("provider_override_forbidden")),
+        UnauthorizedOverride::Ignore => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gateway_core::{ChatMessage, RequestMetadata};
+    use std::collections::HashMap;
+
+    fn config(unauthorized: UnauthorizedOverride) -> ProviderOverrideConfig {
+        ProviderOverrideConfig {
+            enabled: true,
+            unauthorized,
+            ..ProviderOverrideConfig::default()
+        }
+    }
+
+    fn entity(scopes: &[&str]) -> AuthenticatedEntity {
+        AuthenticatedEntity {
+            id: "user".to_string(),
+            tenant_id: None,
+            email: None,
+            name: None,
+            auth_method: AuthMethod::Jwt,
+            credential_id: None,
+            scopes: scopes.iter().map(|s| (*s).to_string()).collect(),
+            expires_at: None,
+            claims: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_header_takes_precedence_over_metadata() {
+        let mut request = GatewayRequest::builder()
+            .model("gpt-4o")
+            .message(ChatMessage::user("Hello"))
+            .build()
+            .unwrap();
+        assert_eq!(requested(&request, None), None);
+
+        request.metadata = Some(RequestMetadata {
+            preferred_provider: Some("anthropic".to_string()),
+            ..RequestMetadata::default()
+        });
+        assert_eq!(requested(&request, None), Some("anthropic"));
+        assert_eq!(requested(&request, Some(" openai ")), Some("openai"));
+    }
+
+    #[test]
+    fn test_authorized_caller_pins_provider() {
+        let caller = entity(&["gateway:provider_override"]);
+        let resolved = resolve(
+            &config(UnauthorizedOverride::Reject),
+            Some(&caller),
+            Some("openai"),
+        );
+        assert_eq!(resolved.unwrap().as_deref(), Some("openai"));
+    }
+
+    #[test]
+    fn test_unauthorized_override_rejected_or_ignored() {
+        let caller = entity(&["gateway:read"]);
+
+        let err = resolve(&config(UnauthorizedOverride::Reject), Some(&caller), Some("openai"))
+            .unwrap_err();
+        assert_eq!(err.status.as_u16(), 403);
+        let err = resolve(&config(UnauthorizedOverride::Reject), None, Some("openai")).unwrap_err();
+        assert_eq!(err.status.as_u16(), 403);
+
+        let resolved =
+            resolve(&config(UnauthorizedOverride::Ignore), Some(&caller), Some("openai"));
+        assert_eq!(resolved.unwrap(), None);
+    }
+
+    #[test]
+    fn test_disabled_ignores_overrides() {
+        let resolved = resolve(&ProviderOverrideConfig::default(), None, Some("openai"));
+        assert_eq!(resolved.unwrap(), None);
+    }
+}
@@ -0,0 +1,186 @@
+//! Deterministic response content hashes.
+//!
+//! Clients running their own caches or deduplication need a stable key for
+//! what the model said. With `server.response_hash` enabled, non-streaming
+//! responses carry an `X-Gateway-Response-Hash` header and streaming
+//! responses report `response_hash` in the result of the terminal
+//! `execution_output` event.
+//!
+//! The hash is a hex SHA-256 over each choice's assistant content and tool
+//! calls (function name and arguments), so equal content hashes equally
+//! whether it was streamed or not. IDs, timestamps, usage and tool call IDs
+//! are left out, as they differ between otherwise identical responses.
+
+use gateway_core::{ChatChunk, GatewayResponse};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+/// Header carrying the hash of a non-streaming response
+pub const HEADER: &str = "x-gateway-response-hash";
+
+/// Result of a streaming request, reported in its `execution_output` event
+#[derive(Debug, Clone, Serialize)]
+pub struct StreamSummary {
+    /// Hash of the streamed content
+    pub response_hash: String,
+}
+
+/// Accumulates response content and hashes it
+#[derive(Debug, Default)]
+pub struct ResponseHasher {
+    choices: BTreeMap<u32, HashedChoice>,
+}
+
+/// Content of one choice, in the form that is hashed
+#[derive(Debug, Default, Serialize)]
+struct HashedChoice {
+    content: String,
+    tool_calls: BTreeMap<u32, HashedToolCall>,
+}
+
+/// A tool call without its ID
+#[derive(Debug, Default, Serialize)]
+struct HashedToolCall {
+    name: String,
+    arguments: String,
+}
+
+impl ResponseHasher {
+    /// Create an empty hasher
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hash a complete response
+    #[must_use]
+    pub fn hash_response(response: &GatewayResponse) -> String {
+        let mut hasher = Self::new();
+        for choice in &response.choices {
+            let hashed = hasher.choices.entry(choice.index).or_default();
+            if let Some(content) = &choice.message.content {
+                hashed.content.push_str(content);
+            }
+            for (index, call) in (0u32..).zip(choice.message.tool_calls.iter().flatten()) {
+                hashed.tool_calls.insert(
+                    index,
+                    HashedToolCall {
+                        name: call.function.name.clone(),
+                        arguments: call.function.arguments.clone(),
+                    },
+                );
+            }
+        }
+        hasher.finish()
+    }
+
+    /// Add a streamed chunk's deltas
+    pub fn push_chunk(&mut self, chunk: &ChatChunk) {
+        for choice in &chunk.choices {
+            let hashed = self.choices.entry(choice.index).or_default();
+            if let Some(content) = &choice.delta.content {
+                hashed.content.push_str(content);
+            }
+            for delta in choice.delta.tool_calls.iter().flatten() {
+                let call = hashed.tool_calls.entry(delta.index).or_default();
+                if let Some(function) = &delta.function {
+                    if let Some(name) = &function.name {
+                        call.name.push_str(name);
+                    }
+                    if let Some(arguments) = &function.arguments {
+                        call.arguments.push_str(arguments);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Hex SHA-256 of the accumulated content
+    #[must_use]
+    pub fn finish(&self) -> String {
+        let canonical = serde_json::to_vec(&self.choices).unwrap_or_default();
+        crate::auth::hex::encode(Sha256::digest(canonical))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gateway_core::response::{Choice, FinishReason};
+    use gateway_core::streaming::{ChunkChoice, FunctionCallDelta, ToolCallDelta};
+    use gateway_core::{FunctionCall, ToolCall};
+
+    fn response(id: &str, content: &str) -> GatewayResponse {
+        GatewayResponse::builder()
+            .id(id)
+            .model("gpt-4o")
+            .choice(Choice::new(0, content, FinishReason::Stop))
+            .build()
+    }
+
+    #[test]
+    fn test_identical_content_hashes_identically() {
+        let first = ResponseHasher::hash_response(&response("resp-1", "Hello there"));
+        let second = ResponseHasher::hash_response(&response("resp-2", "Hello there"));
+        let other = ResponseHasher::hash_response(&response("resp-3", "Hello"));
+
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+        assert_eq!(first.len(), 64);
+    }
+
+    #[test]
+    fn test_streamed_content_matches_response_hash() {
+        let mut hasher = ResponseHasher::new();
+        for part in ["Hel", "lo ", "there"] {
+            hasher.push_chunk(
+                &ChatChunk::builder()
+                    .model("gpt-4o")
+                    .choice(ChunkChoice::with_content(0, part))
+                    .build(),
+            );
+        }
+
+        assert_eq!(hasher.finish(), ResponseHasher::hash_response(&response("resp", "Hello there")));
+    }
+
+    #[test]
+    fn test_tool_calls_are_hashed_without_ids() {
+        let call = |id: &str| ToolCall {
+            id: id.to_string(),
+            tool_type: "function".to_string(),
+            function: FunctionCall {
+                name: "lookup".to_string(),
+                arguments: "{\"q\":\"rust\"}".to_string(),
+            },
+        };
+        let with_call = |id: &str| {
+            GatewayResponse::builder()
+                .id("resp")
+                .model("gpt-4o")
+                .choice(Choice::with_tool_calls(0, vec![call(id)], FinishReason::ToolCalls))
+                .build()
+        };
+        let hash = ResponseHasher::hash_response(&with_call("call_1"));
+        assert_eq!(hash, ResponseHasher::hash_response(&with_call("call_2")));
+        assert_ne!(hash, ResponseHasher::hash_response(&response("resp", "")));
+
+        let mut hasher = ResponseHasher::new();
+        for (name, arguments) in [(Some("lookup"), "{\"q\":"), (None, "\"rust\"}")] {
+            let mut choice = ChunkChoice::with_content(0, "");
+            choice.delta.content = None;
+            choice.delta.tool_calls = Some(vec![ToolCallDelta {
+                index: 0,
+                id: name.map(|_| "call_9".to_string()),
+                tool_type: None,
+                function: Some(FunctionCallDelta {
+                    name: name.map(str::to_string),
+                    arguments: Some(arguments.to_string()),
+                }),
+            }]);
+            hasher.push_chunk(&ChatChunk::builder().model("gpt-4o").choice(choice).build());
+        }
+        assert_eq!(hasher.finish(), hash);
+    }
+}
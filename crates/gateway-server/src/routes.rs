@@ -15,17 +15,26 @@ pub fn create_router(state: AppState) -> Router {
         .route("/healthz", get(handlers::health_check))
         .route("/ready", get(handlers::readiness_check))
         .route("/readyz", get(handlers::readiness_check))
+        .route("/health/ready", get(handlers::readiness_check))
         .route("/live", get(handlers::liveness_check))
         .route("/livez", get(handlers::liveness_check))
         // Metrics endpoint
         .route("/metrics", get(handlers::metrics_endpoint))
         // OpenAI-compatible endpoints
-        .nest("/v1", openai_routes())
+        .nest("/v1", openai_routes(&state))
         // Admin endpoints
         .nest("/admin", admin_routes())
         // Agent endpoints
         .nest("/", agent_routes())
         // Apply middleware
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            middleware::edge_timeout_middleware,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            middleware::error_detail_middleware,
+        ))
         .layer(axum::middleware::from_fn(middleware::request_id_middleware))
         .layer(axum::middleware::from_fn(middleware::response_time_middleware))
         .layer(axum::middleware::from_fn(middleware::logging_middleware))
@@ -36,13 +45,38 @@ pub fn create_router(state: AppState) -> Router {
 }
 
 /// OpenAI-compatible API routes
-fn openai_routes() -> Router<AppState> {
+///
+/// Everything except rate limit introspection and request cancellation
+/// counts against the caller's rate limit.
+fn openai_routes(state: &AppState) -> Router<AppState> {
     Router::new()
         // Chat completions
-        .route("/chat/completions", post(handlers::chat_completion))
+        .route(
+            "/chat/completions",
+            post(handlers::chat_completion).layer(axum::middleware::from_fn_with_state(
+                state.clone(),
+                middleware::legacy_request_compat_middleware,
+            )),
+        )
+        // Images
+        .route("/images/generations", post(handlers::image_generation))
+        // Embeddings
+        .route("/embeddings", post(handlers::embeddings))
+        // Batches
+        .route("/batches", post(handlers::create_batch))
+        .route("/batches/:batch_id", get(handlers::get_batch))
+        .route("/batches/:batch_id/results", get(handlers::get_batch_results))
         // Models
         .route("/models", get(handlers::list_models))
         .route("/models/:model_id", get(handlers::get_model))
+        .route_layer(axum::middleware::from_fn_with_state(
+            state.rate_limiter.clone(),
+            middleware::rate_limit_middleware,
+        ))
+        // Rate limit introspection
+        .route("/rate_limit", get(handlers::rate_limit_status))
+        // Cancellation, allowed even when the caller is rate limited
+        .route("/requests/:request_id/cancel", post(handlers::cancel_request))
 }
 
 /// Admin/management routes
@@ -50,6 +84,7 @@ fn admin_routes() -> Router<AppState> {
     Router::new()
         .route("/providers", get(handlers::list_providers))
         .route("/stats", get(handlers::gateway_stats))
+        .route("/requests/:request_id/trace", get(handlers::request_trace))
 }
 
 /// Agent routes for the Inference Routing Agent
@@ -0,0 +1,190 @@
+//! Sampled log of routing decisions.
+//!
+//! When `observability.logging.routing_decision_sample_rate` is above zero,
+//! that share of routed requests gets one structured `info` event on the
+//! `gateway::routing` target once the request is dispatched: the provider
+//! chosen and why, the candidates that were passed over, and whether a
+//! failover or a stale cached response was needed. Header values added by
+//! routing rules are never logged, only their names, and error text is
+//! redacted.
+
+use gateway_routing::RouteDecision;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tracing::info;
+
+use crate::error::redact_secrets;
+use crate::state::AppState;
+
+/// Target routing decision events are logged under
+pub const TARGET: &str = "gateway::routing";
+
+/// Picks which routing decisions are logged
+#[derive(Debug, Default)]
+pub struct RoutingLog {
+    seen: AtomicU64,
+}
+
+impl RoutingLog {
+    /// Create a sampler that has seen no requests
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the next decision should be logged
+    ///
+    /// Sampling is deterministic: exactly `rate` of decisions are picked,
+    /// spread evenly over the request sequence.
+    #[must_use]
+    pub fn sample(&self, rate: f64) -> bool {
+        if rate <= 0.0 {
+            return false;
+        }
+        let n = self.seen.fetch_add(1, Ordering::Relaxed) as f64;
+        ((n + 1.0) * rate).floor() > (n * rate).floor()
+    }
+}
+
+/// How a routed request was dispatched
+#[derive(Debug, Clone, Default)]
+pub struct RoutingOutcome<'a> {
+    /// Provider the request failed over to, if it left the first choice
+    pub failed_over_to: Option<&'a str>,
+    /// Whether a stale cached response was served instead
+    pub stale_cache: bool,
+    /// Why dispatch failed
+    pub error: Option<&'a str>,
+}
+
+/// Log `decision` for `request_id` if sampling picks it
+pub fn record(
+    state: &AppState,
+    request_id: &str,
+    decision: &RouteDecision,
+    outcome: &RoutingOutcome<'_>,
+) {
+    let rate = state.config().observability.logging.routing_decision_sample_rate;
+    if !state.routing_log.sample(rate) {
+        return;
+    }
+
+    let mut header_names: Vec<&str> = decision.headers.keys().map(String::as_str).collect();
+    header_names.sort_unstable();
+    info!(
+        target: TARGET,
+        request_id = %request_id,
+        provider = %decision.provider_id,
+        model = %decision.model,
+        strategy = %decision.strategy,
+        score = decision.scores.get(&decision.provider_id).copied(),
+        candidates_scored = decision.scores.len(),
+        excluded = %decision.excluded.join(","),
+        matched_rules = %decision.matched_rules.join(","),
+        region = decision.region.as_deref(),
+        pinned = decision.provider_override.is_some(),
+        rule_headers = %header_names.join(","),
+        fallback = outcome.failed_over_to.is_some(),
+        fallback_provider = outcome.failed_over_to,
+        stale_cache = outcome.stale_cache,
+        error = outcome.error.map(redact_secrets).as_deref(),
+        "Routing decision"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gateway_config::GatewayConfig;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn state_sampling(rate: f64) -> AppState {
+        let mut config = GatewayConfig::default();
+        config.observability.logging.routing_decision_sample_rate = rate;
+        AppState::builder().config(config).build()
+    }
+
+    fn decision() -> RouteDecision {
+        RouteDecision {
+            provider_id: "openai-eu".to_string(),
+            model: "gpt-4o".to_string(),
+            headers: HashMap::from([("x-api-key".to_string(), "sk-live-secret".to_string())]),
+            matched_rules: vec!["eu-residency".to_string()],
+            strategy: "composite".to_string(),
+            scores: HashMap::from([("openai-eu".to_string(), 0.25), ("azure-eu".to_string(), 0.5)]),
+            region: Some("eu-west-1".to_string()),
+            stream_passthrough: None,
+            provider_override: None,
+            excluded: vec!["openai-us".to_string()],
+        }
+    }
+
+    fn capture(state: &AppState, outcome: &RoutingOutcome<'_>) -> String {
+        let captured = Captured::default();
+        let writer = captured.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .json()
+            .with_writer(move || writer.clone())
+            .finish();
+        tracing::subscriber::with_default(subscriber, || {
+            record(state, "req-1", &decision(), outcome);
+        });
+        let output = captured.0.lock().unwrap().clone();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_sampled_decision_is_logged() {
+        let state = state_sampling(1.0);
+        let output = capture(
+            &state,
+            &RoutingOutcome {
+                failed_over_to: Some("azure-eu"),
+                ..RoutingOutcome::default()
+            },
+        );
+
+        let line: serde_json::Value = serde_json::from_str(output.trim()).unwrap();
+        assert_eq!(line["target"], TARGET);
+        let fields = &line["fields"];
+        assert_eq!(fields["request_id"], "req-1");
+        assert_eq!(fields["provider"], "openai-eu");
+        assert_eq!(fields["strategy"], "composite");
+        assert_eq!(fields["score"], 0.25);
+        assert_eq!(fields["excluded"], "openai-us");
+        assert_eq!(fields["fallback"], true);
+        assert_eq!(fields["fallback_provider"], "azure-eu");
+        assert_eq!(fields["stale_cache"], false);
+        assert_eq!(fields["rule_headers"], "x-api-key");
+        assert!(!output.contains("sk-live-secret"));
+    }
+
+    #[test]
+    fn test_zero_sample_rate_logs_nothing() {
+        let state = state_sampling(0.0);
+        for _ in 0..10 {
+            assert!(capture(&state, &RoutingOutcome::default()).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_sample_rate_is_exact() {
+        let log = RoutingLog::new();
+        let picked = (0..100).filter(|_| log.sample(0.1)).count();
+        assert_eq!(picked, 10);
+    }
+}
@@ -2,6 +2,7 @@
 
 use crate::{routes::create_router, state::AppState};
 use std::net::SocketAddr;
+use std::sync::Arc;
 use std::time::Duration;
 use tokio::net::TcpListener;
 use tokio::signal;
@@ -90,10 +91,13 @@ impl Server {
 
     /// Run the server
     ///
+    /// Readiness probes report not-ready once the shutdown signal arrives.
+    ///
     /// # Errors
     /// Returns error if the server fails to start or encounters a fatal error
     pub async fn run(self) -> Result<(), ServerError> {
         let addr = self.config.socket_addr();
+        let health = Arc::clone(&self.state.health);
         let router = create_router(self.state);
 
         info!(
@@ -109,7 +113,10 @@ impl Server {
         info!(address = %addr, "Server listening");
 
         axum::serve(listener, router)
-            .with_graceful_shutdown(shutdown_signal())
+            .with_graceful_shutdown(async move {
+                shutdown_signal().await;
+                health.mark_shutting_down();
+            })
             .await
             .map_err(|e| ServerError::Serve(e.to_string()))?;
 
@@ -127,6 +134,7 @@ impl Server {
         F: std::future::Future<Output = ()> + Send + 'static,
     {
         let addr = self.config.socket_addr();
+        let health = Arc::clone(&self.state.health);
         let router = create_router(self.state);
 
         info!(
@@ -142,7 +150,10 @@ impl Server {
         info!(address = %addr, "Server listening");
 
         axum::serve(listener, router)
-            .with_graceful_shutdown(shutdown)
+            .with_graceful_shutdown(async move {
+                shutdown.await;
+                health.mark_shutting_down();
+            })
             .await
             .map_err(|e| ServerError::Serve(e.to_string()))?;
 
@@ -0,0 +1,146 @@
+//! Shadow traffic mirroring.
+//!
+//! A sampled share of successful non-streaming requests is replayed
+//! against the configured shadow provider in a background task, so the
+//! client never waits on it. The shadow's answer is compared with the
+//! primary's and the resulting [`ResponseDiff`] is recorded in telemetry.
+
+use gateway_core::{GatewayRequest, GatewayResponse, ResponseDiff, SimilarityHook};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+use crate::state::AppState;
+
+/// Sampling state and comparison hooks for mirrored requests
+#[derive(Default)]
+pub struct ShadowMirror {
+    seen: AtomicU64,
+    similarity: Option<Arc<dyn SimilarityHook>>,
+}
+
+impl ShadowMirror {
+    /// Create a mirror without embedding similarity
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also score responses with `hook`
+    #[must_use]
+    pub fn with_similarity_hook(mut self, hook: Arc<dyn SimilarityHook>) -> Self {
+        self.similarity = Some(hook);
+        self
+    }
+
+    /// Whether the next request should be mirrored
+    ///
+    /// Sampling is deterministic: exactly `rate` of requests are picked,
+    /// spread evenly over the request sequence.
+    #[must_use]
+    pub fn sample(&self, rate: f64) -> bool {
+        if rate <= 0.0 {
+            return false;
+        }
+        let n = self.seen.fetch_add(1, Ordering::Relaxed) as f64;
+        ((n + 1.0) * rate).floor() > (n * rate).floor()
+    }
+}
+
+impl std::fmt::Debug for ShadowMirror {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ShadowMirror")
+            .field("seen", &self.seen.load(Ordering::Relaxed))
+            .field("similarity", &self.similarity.is_some())
+            .finish()
+    }
+}
+
+/// Replay `request` against the shadow provider if mirroring picks it
+///
+/// Returns immediately; the shadow call and diff run in a spawned task.
+/// Requests restricted to `allowed` providers are never mirrored elsewhere.
+pub(crate) fn mirror_request(
+    state: &AppState,
+    request: &GatewayRequest,
+    primary_id: &str,
+    primary: &GatewayResponse,
+    allowed: Option<&[String]>,
+) {
+    let config = state.config();
+    let mirroring = &config.routing.mirroring;
+    if !mirroring.enabled {
+        return;
+    }
+    let Some(shadow_id) = mirroring.shadow_provider.as_deref() else {
+        return;
+    };
+    if shadow_id == primary_id || !state.shadow_mirror.sample(mirroring.sample_rate) {
+        return;
+    }
+    if allowed.is_some_and(|allowed| !allowed.iter().any(|id| id == shadow_id)) {
+        debug!(shadow = %shadow_id, "Shadow provider not allowed for tenant, skipping mirror");
+        return;
+    }
+    let Some(shadow) = state.providers.get(shadow_id) else {
+        debug!(shadow = %shadow_id, "Shadow provider not registered, skipping mirror");
+        return;
+    };
+
+    let mirror = Arc::clone(&state.shadow_mirror);
+    let metrics = Arc::clone(&state.metrics);
+    let timeout = config.server.request_timeout;
+    let request = request.clone();
+    let primary = primary.clone();
+    let primary_id = primary_id.to_string();
+
+    tokio::spawn(async move {
+        let result = tokio::time::timeout(timeout, shadow.chat_completion(&request)).await;
+        let response = match result {
+            Ok(Ok(response)) => response,
+            Ok(Err(e)) => {
+                warn!(shadow = %shadow.id(), error = %e, "Shadow request failed");
+                return;
+            }
+            Err(_) => {
+                warn!(shadow = %shadow.id(), "Shadow request timed out");
+                return;
+            }
+        };
+
+        let diff = match &mirror.similarity {
+            Some(hook) => ResponseDiff::between_with_hook(&primary, &response, hook.as_ref()).await,
+            None => ResponseDiff::between(&primary, &response),
+        };
+        metrics.record_shadow_diff(&primary_id, shadow.id(), &diff);
+        info!(
+            primary = %primary_id,
+            shadow = %shadow.id(),
+            model = %request.model,
+            token_overlap = diff.token_overlap,
+            length_delta = diff.length_delta,
+            finish_reason_match = diff.finish_reason_match,
+            embedding_similarity = ?diff.embedding_similarity,
+            "Shadow response compared"
+        );
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_rate_is_exact() {
+        let mirror = ShadowMirror::new();
+        let picked = (0..100).filter(|_| mirror.sample(0.25)).count();
+        assert_eq!(picked, 25);
+    }
+
+    #[test]
+    fn test_sample_bounds() {
+        let mirror = ShadowMirror::new();
+        assert!((0..10).all(|_| mirror.sample(1.0)));
+        assert!((0..10).all(|_| !mirror.sample(0.0)));
+    }
+}
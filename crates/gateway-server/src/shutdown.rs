@@ -7,10 +7,20 @@
 //! - Background task cancellation
 //! - Shutdown state broadcasting
 //! - Health endpoint coordination
-
+//! - Ordered subsystem hooks with per-phase deadlines
+//!
+//! Shutdown runs in a fixed order: stop accepting new requests, drain
+//! in-flight requests, then flush telemetry/audit/cost, flush event buffers,
+//! and finally close database and Redis pools. Subsystems take part by
+//! registering a [`ShutdownHook`], which is called once for each of the
+//! [`ShutdownPhase::SUBSYSTEM_PHASES`] in order.
+
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::signal;
 use tokio::sync::{broadcast, watch, Notify};
 use tokio::time::{sleep, timeout};
@@ -29,6 +39,10 @@ pub struct ShutdownConfig {
     pub reject_new_requests: bool,
     /// Time to wait after signaling shutdown before starting drain
     pub pre_drain_delay: Duration,
+    /// Deadline for the hooks of a subsystem phase without an override
+    pub phase_timeout: Duration,
+    /// Per-phase hook deadline overrides
+    pub phase_timeouts: HashMap<ShutdownPhase, Duration>,
 }
 
 impl Default for ShutdownConfig {
@@ -39,6 +53,8 @@ impl Default for ShutdownConfig {
             progress_interval: Duration::from_secs(1),
             reject_new_requests: true,
             pre_drain_delay: Duration::from_millis(500),
+            phase_timeout: Duration::from_secs(5),
+            phase_timeouts: HashMap::new(),
         }
     }
 }
@@ -70,10 +86,26 @@ impl ShutdownConfig {
         self.reject_new_requests = reject;
         self
     }
+
+    /// Set the hook deadline for one subsystem phase
+    #[must_use]
+    pub fn with_phase_timeout(mut self, phase: ShutdownPhase, timeout: Duration) -> Self {
+        self.phase_timeouts.insert(phase, timeout);
+        self
+    }
+
+    /// Hook deadline for a subsystem phase
+    #[must_use]
+    pub fn timeout_for(&self, phase: ShutdownPhase) -> Duration {
+        self.phase_timeouts
+            .get(&phase)
+            .copied()
+            .unwrap_or(self.phase_timeout)
+    }
 }
 
 /// Shutdown phase
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ShutdownPhase {
     /// Normal operation
     Running,
@@ -81,21 +113,52 @@ pub enum ShutdownPhase {
     Draining,
     /// Force closing remaining connections
     ForceClose,
+    /// Flushing telemetry, audit and cost records
+    FlushTelemetry,
+    /// Flushing event buffers
+    FlushEvents,
+    /// Closing database and Redis pools
+    ClosePools,
     /// Shutdown complete
     Complete,
 }
 
+impl ShutdownPhase {
+    /// Phases in which registered [`ShutdownHook`]s run, in order
+    pub const SUBSYSTEM_PHASES: [Self; 3] =
+        [Self::FlushTelemetry, Self::FlushEvents, Self::ClosePools];
+}
+
 impl std::fmt::Display for ShutdownPhase {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Running => write!(f, "running"),
             Self::Draining => write!(f, "draining"),
             Self::ForceClose => write!(f, "force_close"),
+            Self::FlushTelemetry => write!(f, "flush_telemetry"),
+            Self::FlushEvents => write!(f, "flush_events"),
+            Self::ClosePools => write!(f, "close_pools"),
             Self::Complete => write!(f, "complete"),
         }
     }
 }
 
+/// A subsystem that takes part in shutdown
+///
+/// [`ShutdownHook::shutdown`] is called once for each of the
+/// [`ShutdownPhase::SUBSYSTEM_PHASES`], in order, after in-flight requests
+/// have drained. Hooks ignore phases they have nothing to do in. All hooks
+/// of a phase run concurrently, and any still running at the phase deadline
+/// are abandoned so that later phases still run.
+#[async_trait]
+pub trait ShutdownHook: Send + Sync {
+    /// Subsystem name, reported in [`PhaseStats`]
+    fn name(&self) -> &str;
+
+    /// Do this subsystem's work for a shutdown phase
+    async fn shutdown(&self, phase: ShutdownPhase);
+}
+
 /// Shutdown coordinator for managing graceful shutdown
 pub struct ShutdownCoordinator {
     config: ShutdownConfig,
@@ -113,6 +176,10 @@ pub struct ShutdownCoordinator {
     shutdown_tx: broadcast::Sender<ShutdownEvent>,
     /// List of registered background tasks
     background_tasks: Arc<tokio::sync::Mutex<Vec<tokio::task::JoinHandle<()>>>>,
+    /// Subsystem hooks, in registration order
+    hooks: Mutex<Vec<Arc<dyn ShutdownHook>>>,
+    /// Progress of the current shutdown
+    stats: Mutex<ShutdownStats>,
 }
 
 /// Shutdown event
@@ -153,6 +220,8 @@ impl ShutdownCoordinator {
             in_flight_requests: AtomicU64::new(0),
             shutdown_tx,
             background_tasks: Arc::new(tokio::sync::Mutex::new(Vec::new())),
+            hooks: Mutex::new(Vec::new()),
+            stats: Mutex::new(ShutdownStats::default()),
         }
     }
 
@@ -217,6 +286,17 @@ impl ShutdownCoordinator {
         tasks.push(handle);
     }
 
+    /// Register a subsystem shutdown hook
+    pub fn register_hook(&self, hook: Arc<dyn ShutdownHook>) {
+        self.hooks.lock().push(hook);
+    }
+
+    /// Progress of the shutdown so far
+    #[must_use]
+    pub fn stats(&self) -> ShutdownStats {
+        self.stats.lock().clone()
+    }
+
     /// Should accept new requests?
     #[must_use]
     pub fn should_accept_requests(&self) -> bool {
@@ -238,6 +318,7 @@ impl ShutdownCoordinator {
         }
 
         info!(reason = %reason, "Initiating graceful shutdown");
+        self.stats.lock().initiated_at = Some(Instant::now());
 
         // Broadcast shutdown initiated
         let _ = self.shutdown_tx.send(ShutdownEvent::Initiated {
@@ -255,6 +336,7 @@ impl ShutdownCoordinator {
 
         // Enter draining phase
         self.set_phase(ShutdownPhase::Draining).await;
+        let in_flight = self.in_flight_count();
 
         // Wait for in-flight requests with timeout
         let drain_result =
@@ -263,6 +345,7 @@ impl ShutdownCoordinator {
         match drain_result {
             Ok(()) => {
                 info!("All in-flight requests completed");
+                self.stats.lock().requests_drained = in_flight;
             }
             Err(_) => {
                 let remaining = self.in_flight_count();
@@ -270,6 +353,11 @@ impl ShutdownCoordinator {
                     remaining = remaining,
                     "Graceful timeout exceeded, forcing shutdown"
                 );
+                {
+                    let mut stats = self.stats.lock();
+                    stats.requests_drained = in_flight.saturating_sub(remaining);
+                    stats.requests_force_closed = remaining;
+                }
                 self.set_phase(ShutdownPhase::ForceClose).await;
 
                 // Additional drain timeout
@@ -277,11 +365,19 @@ impl ShutdownCoordinator {
             }
         }
 
+        // Flush and close subsystems in order
+        for phase in ShutdownPhase::SUBSYSTEM_PHASES {
+            self.set_phase(phase).await;
+            let phase_stats = self.run_hooks(phase).await;
+            self.stats.lock().phases.push(phase_stats);
+        }
+
         // Cancel background tasks
         self.cancel_background_tasks().await;
 
         // Complete
         self.set_phase(ShutdownPhase::Complete).await;
+        self.stats.lock().completed_at = Some(Instant::now());
         let _ = self.shutdown_tx.send(ShutdownEvent::Complete);
 
         // Notify waiters
@@ -299,6 +395,40 @@ impl ShutdownCoordinator {
             .send(ShutdownEvent::PhaseChanged { phase });
     }
 
+    /// Run every hook for a phase concurrently, up to the phase deadline
+    async fn run_hooks(&self, phase: ShutdownPhase) -> PhaseStats {
+        let hooks = self.hooks.lock().clone();
+        let deadline = self.config.timeout_for(phase);
+        let started = Instant::now();
+
+        let results = futures::future::join_all(hooks.iter().map(|hook| async move {
+            let finished = timeout(deadline, hook.shutdown(phase)).await.is_ok();
+            (hook.name().to_string(), finished)
+        }))
+        .await;
+
+        let mut stats = PhaseStats {
+            phase,
+            duration: started.elapsed(),
+            completed: Vec::new(),
+            timed_out: Vec::new(),
+        };
+        for (name, finished) in results {
+            if finished {
+                stats.completed.push(name);
+            } else {
+                warn!(
+                    phase = %phase,
+                    subsystem = %name,
+                    deadline_ms = deadline.as_millis(),
+                    "Shutdown hook missed its phase deadline"
+                );
+                stats.timed_out.push(name);
+            }
+        }
+        stats
+    }
+
     /// Wait for all in-flight requests to complete
     async fn wait_for_drain(&self) {
         let mut last_logged = std::time::Instant::now();
@@ -329,6 +459,7 @@ impl ShutdownCoordinator {
 
         if task_count > 0 {
             info!(count = task_count, "Cancelling background tasks");
+            self.stats.lock().tasks_cancelled = task_count as u64;
 
             for handle in tasks.drain(..) {
                 handle.abort();
@@ -482,6 +613,29 @@ pub struct ShutdownStats {
     pub requests_force_closed: u64,
     /// Number of background tasks cancelled
     pub tasks_cancelled: u64,
+    /// Subsystem phases run so far, in order
+    pub phases: Vec<PhaseStats>,
+}
+
+/// Outcome of one subsystem shutdown phase
+#[derive(Debug, Clone)]
+pub struct PhaseStats {
+    /// The phase
+    pub phase: ShutdownPhase,
+    /// Time the phase's hooks took, capped by the phase deadline
+    pub duration: Duration,
+    /// Subsystems whose hook finished in time
+    pub completed: Vec<String>,
+    /// Subsystems whose hook was abandoned at the deadline
+    pub timed_out: Vec<String>,
+}
+
+impl PhaseStats {
+    /// Whether every hook finished before the deadline
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.timed_out.is_empty()
+    }
 }
 
 #[cfg(test)]
@@ -542,6 +696,7 @@ mod tests {
             progress_interval: Duration::from_millis(10),
             pre_drain_delay: Duration::from_millis(0),
             reject_new_requests: true,
+            ..Default::default()
         }));
 
         // Subscribe to events
@@ -637,6 +792,9 @@ mod tests {
         assert_eq!(ShutdownPhase::Running.to_string(), "running");
         assert_eq!(ShutdownPhase::Draining.to_string(), "draining");
         assert_eq!(ShutdownPhase::ForceClose.to_string(), "force_close");
+        assert_eq!(ShutdownPhase::FlushTelemetry.to_string(), "flush_telemetry");
+        assert_eq!(ShutdownPhase::FlushEvents.to_string(), "flush_events");
+        assert_eq!(ShutdownPhase::ClosePools.to_string(), "close_pools");
         assert_eq!(ShutdownPhase::Complete.to_string(), "complete");
     }
 
@@ -662,6 +820,7 @@ mod tests {
             progress_interval: Duration::from_millis(10),
             pre_drain_delay: Duration::from_millis(0),
             reject_new_requests: false, // Allow requests during drain for test
+            ..Default::default()
         }));
 
         // Start some "requests"
@@ -694,4 +853,110 @@ mod tests {
         assert!(coordinator.is_shutting_down());
         assert_eq!(coordinator.in_flight_count(), 0);
     }
+
+    /// Hook recording the phases it sees, stalling in one of them
+    struct RecordingHook {
+        name: &'static str,
+        calls: Arc<Mutex<Vec<(&'static str, ShutdownPhase)>>>,
+        stall_in: Option<ShutdownPhase>,
+    }
+
+    #[async_trait]
+    impl ShutdownHook for RecordingHook {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        async fn shutdown(&self, phase: ShutdownPhase) {
+            self.calls.lock().push((self.name, phase));
+            if self.stall_in == Some(phase) {
+                std::future::pending::<()>().await;
+            }
+        }
+    }
+
+    fn hook_config() -> ShutdownConfig {
+        ShutdownConfig {
+            graceful_timeout: Duration::from_millis(100),
+            drain_timeout: Duration::from_millis(10),
+            pre_drain_delay: Duration::ZERO,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_hooks_run_in_phase_order() {
+        let coordinator = ShutdownCoordinator::new(hook_config());
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        for name in ["audit", "redis"] {
+            coordinator.register_hook(Arc::new(RecordingHook {
+                name,
+                calls: calls.clone(),
+                stall_in: None,
+            }));
+        }
+
+        coordinator.trigger_shutdown("test").await;
+
+        let phases: Vec<ShutdownPhase> = calls.lock().iter().map(|(_, phase)| *phase).collect();
+        assert_eq!(
+            phases,
+            vec![
+                ShutdownPhase::FlushTelemetry,
+                ShutdownPhase::FlushTelemetry,
+                ShutdownPhase::FlushEvents,
+                ShutdownPhase::FlushEvents,
+                ShutdownPhase::ClosePools,
+                ShutdownPhase::ClosePools,
+            ]
+        );
+
+        let stats = coordinator.stats();
+        assert!(stats.initiated_at.is_some() && stats.completed_at.is_some());
+        let reported: Vec<ShutdownPhase> = stats.phases.iter().map(|p| p.phase).collect();
+        assert_eq!(reported, ShutdownPhase::SUBSYSTEM_PHASES);
+        for phase in &stats.phases {
+            assert!(phase.is_complete());
+            assert_eq!(phase.completed, vec!["audit", "redis"]);
+        }
+        assert_eq!(coordinator.current_phase(), ShutdownPhase::Complete);
+    }
+
+    #[tokio::test]
+    async fn test_phase_deadline_abandons_stalled_hook() {
+        let coordinator = ShutdownCoordinator::new(
+            hook_config().with_phase_timeout(ShutdownPhase::FlushEvents, Duration::from_millis(50)),
+        );
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        coordinator.register_hook(Arc::new(RecordingHook {
+            name: "events",
+            calls: calls.clone(),
+            stall_in: Some(ShutdownPhase::FlushEvents),
+        }));
+        coordinator.register_hook(Arc::new(RecordingHook {
+            name: "pools",
+            calls: calls.clone(),
+            stall_in: None,
+        }));
+
+        let started = std::time::Instant::now();
+        coordinator.trigger_shutdown("test").await;
+        assert!(started.elapsed() < Duration::from_secs(2));
+
+        let stats = coordinator.stats();
+        let flush_events = &stats.phases[1];
+        assert_eq!(flush_events.phase, ShutdownPhase::FlushEvents);
+        assert!(!flush_events.is_complete());
+        assert_eq!(flush_events.timed_out, vec!["events"]);
+        assert_eq!(flush_events.completed, vec!["pools"]);
+        assert!(flush_events.duration >= Duration::from_millis(50));
+
+        // Later phases still run after a missed deadline
+        let close_pools = &stats.phases[2];
+        assert_eq!(close_pools.phase, ShutdownPhase::ClosePools);
+        assert_eq!(close_pools.completed, vec!["events", "pools"]);
+        assert!(calls
+            .lock()
+            .contains(&("events", ShutdownPhase::ClosePools)));
+    }
 }
@@ -3,15 +3,33 @@
 use arc_swap::ArcSwap;
 use gateway_agents::InferenceRoutingAgent;
 use gateway_config::GatewayConfig;
+use gateway_core::tokenizer::{self, SharedTokenEstimator};
+use gateway_core::{PostProcessors, ProviderErrorKind};
+use gateway_integrations::WebhookEmitter;
 use gateway_providers::ProviderRegistry;
-use gateway_resilience::{CircuitBreaker, RetryPolicy};
+use gateway_resilience::{
+    CacheConfig, CircuitBreaker, ProactiveBackoff, ProactiveBackoffConfig, ResponseCache,
+    RetryPolicy,
+};
 use gateway_routing::Router;
-use gateway_telemetry::{Metrics, RequestTracker};
+use gateway_telemetry::{
+    AuditEvent, AuditEventBuilder, AuditEventType, AuditOutcome, BurnWindow, ContentLimit,
+    CostTracker, Metrics, MetricsConfig, RequestTracker, SloConfig, SloMonitor,
+};
 use parking_lot::RwLock;
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 
+use crate::batches::BatchStore;
+use crate::health::{HealthChecker, HealthConfig};
+use crate::middleware::RateLimiterState;
+use crate::policy::PolicyGate;
+use crate::routing_log::RoutingLog;
+use crate::shadow::ShadowMirror;
+use crate::streams::StreamLimiter;
+use crate::trace::RequestTraceStore;
+
 /// Application state shared across all handlers
 #[derive(Clone)]
 pub struct AppState {
@@ -23,14 +41,51 @@ pub struct AppState {
     pub router: Arc<Router>,
     /// Circuit breakers per provider
     pub circuit_breakers: Arc<CircuitBreakerManager>,
+    /// Health and readiness checks, with cached provider probe results
+    pub health: Arc<HealthChecker>,
     /// Retry policy
     pub retry_policy: Arc<RetryPolicy>,
     /// Metrics collector
     pub metrics: Arc<Metrics>,
+    /// SLO burn-rate monitor, fed by the request tracker when enabled
+    pub slo: Arc<SloMonitor>,
     /// Request tracker
     pub tracker: Arc<RequestTracker>,
     /// Inference routing agent
     pub inference_routing_agent: Arc<InferenceRoutingAgent>,
+    /// Client rate limiter
+    pub rate_limiter: RateLimiterState,
+    /// Sizes requests before dispatch; shared with the router and rate
+    /// limiter
+    pub token_estimator: SharedTokenEstimator,
+    /// Response cache (used to serve stale responses on upstream failure)
+    pub response_cache: Option<Arc<ResponseCache>>,
+    /// Open streaming responses per tenant
+    pub stream_limiter: StreamLimiter,
+    /// Backoff signal from provider-reported rate limits
+    pub provider_backoff: Arc<ProactiveBackoff>,
+    /// Pre-dispatch policy check (absent when no policy engine is configured)
+    pub policy_gate: Option<Arc<PolicyGate>>,
+    /// Shadow traffic sampling and comparison
+    pub shadow_mirror: Arc<ShadowMirror>,
+    /// Sampling of logged routing decisions
+    pub routing_log: Arc<RoutingLog>,
+    /// Named response post-processors requests may select
+    pub post_processors: Arc<PostProcessors>,
+    /// Usage cost per tenant, model, and provider
+    pub cost_tracker: Arc<CostTracker>,
+    /// Submitted batch jobs
+    pub batches: Arc<BatchStore>,
+    /// Recent request lifecycle traces
+    pub traces: Arc<RequestTraceStore>,
+    /// Lifecycle event webhooks (absent when none are configured)
+    pub webhooks: Option<Arc<WebhookEmitter>>,
+    /// Request/response store (present only when persistence is enabled)
+    #[cfg(feature = "persistence")]
+    pub exchange_store: Option<Arc<crate::persistence::ExchangeStore>>,
+    /// Tamper-evident audit store (present only when persistence is enabled)
+    #[cfg(feature = "persistence")]
+    pub audit_store: Option<Arc<crate::audit_store::AuditStore>>,
 }
 
 impl AppState {
@@ -49,6 +104,28 @@ impl AppState {
     /// Update configuration
     pub fn update_config(&self, config: GatewayConfig) {
         self.config.store(Arc::new(config));
+        self.audit(
+            AuditEventBuilder::new(AuditEventType::ConfigChange)
+                .outcome(AuditOutcome::Success)
+                .description("Configuration updated")
+                .build(),
+        );
+    }
+
+    /// Append an audit event to the audit store in the background
+    ///
+    /// Does nothing when no audit store is attached.
+    pub fn audit(&self, event: AuditEvent) {
+        #[cfg(feature = "persistence")]
+        if let Some(store) = self.audit_store.clone() {
+            tokio::spawn(async move {
+                if let Err(e) = store.append(&event).await {
+                    tracing::error!(event_id = %event.id, error = %e, "Failed to persist audit event");
+                }
+            });
+        }
+        #[cfg(not(feature = "persistence"))]
+        let _ = event;
     }
 }
 
@@ -60,6 +137,18 @@ pub struct AppStateBuilder {
     retry_policy: Option<RetryPolicy>,
     metrics: Option<Metrics>,
     inference_routing_agent: Option<Arc<InferenceRoutingAgent>>,
+    rate_limiter: Option<RateLimiterState>,
+    token_estimator: Option<SharedTokenEstimator>,
+    response_cache: Option<Arc<ResponseCache>>,
+    policy_gate: Option<Arc<PolicyGate>>,
+    shadow_mirror: Option<ShadowMirror>,
+    post_processors: Option<PostProcessors>,
+    cost_tracker: Option<Arc<CostTracker>>,
+    webhooks: Option<Arc<WebhookEmitter>>,
+    #[cfg(feature = "persistence")]
+    exchange_store: Option<Arc<crate::persistence::ExchangeStore>>,
+    #[cfg(feature = "persistence")]
+    audit_store: Option<Arc<crate::audit_store::AuditStore>>,
 }
 
 impl AppStateBuilder {
@@ -73,6 +162,18 @@ impl AppStateBuilder {
             retry_policy: None,
             metrics: None,
             inference_routing_agent: None,
+            rate_limiter: None,
+            token_estimator: None,
+            response_cache: None,
+            policy_gate: None,
+            shadow_mirror: None,
+            post_processors: None,
+            cost_tracker: None,
+            webhooks: None,
+            #[cfg(feature = "persistence")]
+            exchange_store: None,
+            #[cfg(feature = "persistence")]
+            audit_store: None,
         }
     }
 
@@ -118,6 +219,92 @@ impl AppStateBuilder {
         self
     }
 
+    /// Set the client rate limiter
+    ///
+    /// Defaults to one built from `security.rate_limiting` in the config.
+    #[must_use]
+    pub fn rate_limiter(mut self, rate_limiter: RateLimiterState) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Set the token estimator
+    ///
+    /// Defaults to [`tokenizer::default_estimator`]. It is also given to the
+    /// router and to the default rate limiter, so every pre-dispatch
+    /// estimate agrees.
+    #[must_use]
+    pub fn token_estimator(mut self, estimator: SharedTokenEstimator) -> Self {
+        self.token_estimator = Some(estimator);
+        self
+    }
+
+    /// Set the response cache
+    #[must_use]
+    pub fn response_cache(mut self, cache: Arc<ResponseCache>) -> Self {
+        self.response_cache = Some(cache);
+        self
+    }
+
+    /// Set the policy gate
+    #[must_use]
+    pub fn policy_gate(mut self, gate: PolicyGate) -> Self {
+        self.policy_gate = Some(Arc::new(gate));
+        self
+    }
+
+    /// Set the lifecycle event webhook emitter
+    #[must_use]
+    pub fn webhooks(mut self, emitter: Arc<WebhookEmitter>) -> Self {
+        self.webhooks = Some(emitter);
+        self
+    }
+
+    /// Set the shadow mirror
+    ///
+    /// Defaults to one without embedding similarity. Whether requests are
+    /// mirrored is controlled by `routing.mirroring` in the config.
+    #[must_use]
+    pub fn shadow_mirror(mut self, mirror: ShadowMirror) -> Self {
+        self.shadow_mirror = Some(mirror);
+        self
+    }
+
+    /// Set the response post-processors
+    ///
+    /// Defaults to the built-ins. Which ones run is selected per tenant by
+    /// `server.post_processing` in the config, or per request.
+    #[must_use]
+    pub fn post_processors(mut self, processors: PostProcessors) -> Self {
+        self.post_processors = Some(processors);
+        self
+    }
+
+    /// Set the cost tracker
+    ///
+    /// Defaults to a disabled tracker.
+    #[must_use]
+    pub fn cost_tracker(mut self, tracker: Arc<CostTracker>) -> Self {
+        self.cost_tracker = Some(tracker);
+        self
+    }
+
+    /// Set the request/response store
+    #[cfg(feature = "persistence")]
+    #[must_use]
+    pub fn exchange_store(mut self, store: Arc<crate::persistence::ExchangeStore>) -> Self {
+        self.exchange_store = Some(store);
+        self
+    }
+
+    /// Set the audit store
+    #[cfg(feature = "persistence")]
+    #[must_use]
+    pub fn audit_store(mut self, store: Arc<crate::audit_store::AuditStore>) -> Self {
+        self.audit_store = Some(store);
+        self
+    }
+
     /// Build the application state
     ///
     /// # Panics
@@ -127,9 +314,32 @@ impl AppStateBuilder {
     pub fn build(self) -> AppState {
         let config = self.config.expect("config is required");
 
-        let router = Arc::new(self.router.unwrap_or_else(|| {
-            Router::new(gateway_routing::RouterConfig::default())
-        }));
+        let token_estimator = self
+            .token_estimator
+            .unwrap_or_else(tokenizer::default_estimator);
+
+        let rate_limiter = self.rate_limiter.unwrap_or_else(|| {
+            RateLimiterState::from_config_with_estimator(
+                &config.security.rate_limiting,
+                Arc::clone(&token_estimator),
+            )
+        });
+
+        let backoff_config = &config.resilience.proactive_backoff;
+        let provider_backoff = Arc::new(if backoff_config.enabled {
+            ProactiveBackoff::new(ProactiveBackoffConfig {
+                threshold: backoff_config.threshold,
+                max_delay: backoff_config.max_delay,
+            })
+        } else {
+            ProactiveBackoff::disabled()
+        });
+
+        let router = Arc::new(
+            self.router
+                .unwrap_or_else(|| Router::new(gateway_routing::RouterConfig::default()))
+                .with_estimator(Arc::clone(&token_estimator)),
+        );
 
         // Create inference routing agent, wrapping the router
         let inference_routing_agent = self.inference_routing_agent.unwrap_or_else(|| {
@@ -141,22 +351,120 @@ impl AppStateBuilder {
             )
         });
 
+        let metrics = Arc::new(self.metrics.unwrap_or_else(|| {
+            let metrics_config = MetricsConfig {
+                token_buckets: config.observability.metrics.token_buckets.clone(),
+                ..MetricsConfig::default()
+            };
+            Metrics::new(&metrics_config).expect("metrics")
+        }));
+
+        let slo_config = &config.observability.slo;
+        let slo = Arc::new(
+            SloMonitor::new(SloConfig {
+                availability_target: slo_config.availability_target,
+                latency_threshold: slo_config.latency_threshold,
+                latency_target: slo_config.latency_target,
+                fast_burn: BurnWindow {
+                    long: slo_config.fast_burn.long_window,
+                    short: slo_config.fast_burn.short_window,
+                    threshold: slo_config.fast_burn.threshold,
+                },
+                slow_burn: BurnWindow {
+                    long: slo_config.slow_burn.long_window,
+                    short: slo_config.slow_burn.short_window,
+                    threshold: slo_config.slow_burn.threshold,
+                },
+                ..SloConfig::default()
+            })
+            .with_metrics(Arc::clone(&metrics)),
+        );
+        let mut tracker = RequestTracker::new(10000);
+        if slo_config.enabled {
+            tracker = tracker.with_slo(Arc::clone(&slo));
+        }
+        if let Some(max_len) = config.persistence.max_stored_content_length {
+            tracker = tracker.with_content_limit(ContentLimit::new(max_len));
+        }
+
+        let trace_config = config.server.request_trace.clone();
+
+        let breaker_config = &config.resilience.circuit_breaker;
+        let circuit_breakers = Arc::new(CircuitBreakerManager::with_config(CircuitBreakerConfig {
+            failure_threshold: breaker_config.failure_threshold,
+            success_threshold: breaker_config.success_threshold,
+            half_open_max_probes: breaker_config.half_open_max_probes,
+            timeout: breaker_config.timeout,
+            failure_kinds: breaker_config.failure_kinds.clone(),
+        }));
+
+        let health = Arc::new(
+            HealthChecker::new(health_config(&config.server.readiness))
+                .with_circuit_breakers(Arc::clone(&circuit_breakers))
+                .with_initialized(&["config", "providers", "router", "metrics"]),
+        );
+
+        let response_cache = self.response_cache.or_else(|| response_cache(&config.cache));
+
         AppState {
             config: Arc::new(ArcSwap::new(Arc::new(config))),
             providers: Arc::new(self.providers.unwrap_or_default()),
             router,
-            circuit_breakers: Arc::new(CircuitBreakerManager::new()),
+            circuit_breakers,
+            health,
             retry_policy: Arc::new(self.retry_policy.unwrap_or_else(RetryPolicy::with_defaults)),
-            metrics: Arc::new(
-                self.metrics
-                    .unwrap_or_else(|| Metrics::new(&Default::default()).expect("metrics")),
-            ),
-            tracker: Arc::new(RequestTracker::new(10000)),
+            metrics,
+            slo,
+            tracker: Arc::new(tracker),
             inference_routing_agent,
+            rate_limiter,
+            token_estimator,
+            response_cache,
+            stream_limiter: StreamLimiter::new(),
+            provider_backoff,
+            policy_gate: self.policy_gate,
+            shadow_mirror: Arc::new(self.shadow_mirror.unwrap_or_default()),
+            routing_log: Arc::new(RoutingLog::new()),
+            post_processors: Arc::new(self.post_processors.unwrap_or_default()),
+            cost_tracker: self
+                .cost_tracker
+                .unwrap_or_else(|| Arc::new(CostTracker::disabled())),
+            batches: Arc::new(BatchStore::new()),
+            traces: Arc::new(RequestTraceStore::new(&trace_config)),
+            webhooks: self.webhooks,
+            #[cfg(feature = "persistence")]
+            exchange_store: self.exchange_store,
+            #[cfg(feature = "persistence")]
+            audit_store: self.audit_store,
         }
     }
 }
 
+/// Health checker settings for the readiness configuration
+///
+/// A `min_healthy_providers` of 0 leaves providers out of readiness.
+fn health_config(readiness: &gateway_config::ReadinessConfig) -> HealthConfig {
+    HealthConfig {
+        include_providers_in_readiness: readiness.min_healthy_providers > 0,
+        min_healthy_providers: readiness.min_healthy_providers,
+        ..HealthConfig::new().with_provider_cache_ttl(readiness.provider_cache_ttl)
+    }
+}
+
+/// Build the response cache the configuration asks for, if any
+fn response_cache(config: &gateway_config::ResponseCacheConfig) -> Option<Arc<ResponseCache>> {
+    config.enabled.then(|| {
+        Arc::new(ResponseCache::new(CacheConfig {
+            enabled: true,
+            max_entries: config.max_entries,
+            default_ttl: config.default_ttl,
+            serve_stale_on_error: config.serve_stale_on_error,
+            max_staleness: config.max_staleness,
+            ..CacheConfig::default()
+        }))
+    })
+}
+
 impl Default for AppStateBuilder {
     fn default() -> Self {
         Self::new()
@@ -176,8 +484,12 @@ pub struct CircuitBreakerConfig {
     pub failure_threshold: u32,
     /// Success threshold before closing
     pub success_threshold: u32,
+    /// Trial requests allowed in flight while half-open; 0 means unlimited
+    pub half_open_max_probes: u32,
     /// Timeout before half-open
     pub timeout: Duration,
+    /// Error kinds counted as failures
+    pub failure_kinds: Vec<ProviderErrorKind>,
 }
 
 impl Default for CircuitBreakerConfig {
@@ -185,7 +497,9 @@ impl Default for CircuitBreakerConfig {
         Self {
             failure_threshold: 5,
             success_threshold: 2,
+            half_open_max_probes: 2,
             timeout: Duration::from_secs(30),
+            failure_kinds: ProviderErrorKind::PROVIDER_FAULTS.to_vec(),
         }
     }
 }
@@ -225,9 +539,11 @@ impl CircuitBreakerManager {
                 let cb_config = gateway_resilience::CircuitBreakerConfig {
                     failure_threshold: self.config.failure_threshold,
                     success_threshold: self.config.success_threshold,
+                    half_open_max_probes: self.config.half_open_max_probes,
                     timeout: self.config.timeout,
                     window_size: 100,
                     min_requests: 10,
+                    failure_kinds: self.config.failure_kinds.clone(),
                 };
                 Arc::new(CircuitBreaker::new(provider_id, cb_config))
             })
@@ -293,6 +609,55 @@ mod tests {
         assert!(state.config().server.host.is_empty() || !state.config().server.host.is_empty());
     }
 
+    #[test]
+    fn test_response_cache_from_config() {
+        let state = AppState::builder().config(GatewayConfig::default()).build();
+        assert!(state.response_cache.is_none());
+
+        let mut config = GatewayConfig::default();
+        config.cache.enabled = true;
+        config.cache.serve_stale_on_error = true;
+        let state = AppState::builder().config(config).build();
+        assert!(state.response_cache.as_ref().is_some_and(|cache| cache.is_enabled()));
+    }
+
+    #[cfg(feature = "persistence")]
+    #[tokio::test]
+    async fn test_config_update_is_audited() {
+        use crate::audit_store::AuditStore;
+        use chrono::Utc;
+
+        let persistence = gateway_config::PersistenceConfig {
+            enabled: true,
+            database_url: Some("sqlite::memory:".to_string()),
+            max_connections: 1,
+            ..gateway_config::PersistenceConfig::default()
+        };
+        let store = Arc::new(AuditStore::connect(&persistence).await.unwrap().unwrap());
+        let state = AppState::builder()
+            .config(GatewayConfig::default())
+            .audit_store(Arc::clone(&store))
+            .build();
+
+        state.update_config(GatewayConfig::default());
+
+        let events = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                let events = store
+                    .events_between(chrono::DateTime::<Utc>::MIN_UTC, Utc::now())
+                    .await
+                    .unwrap();
+                if !events.is_empty() {
+                    break events;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("config change should be audited");
+        assert_eq!(events[0].event.event_type, AuditEventType::ConfigChange);
+    }
+
     #[test]
     fn test_circuit_breaker_manager() {
         let manager = CircuitBreakerManager::new();
@@ -0,0 +1,163 @@
+//! Per-tenant limits on concurrent streaming responses.
+//!
+//! Streams are long-lived, so request-rate limits don't bound how many a
+//! single tenant holds open. [`StreamLimiter`] counts open streams per
+//! caller key and hands out a [`StreamPermit`] that frees its slot when
+//! dropped: when the stream completes, fails, or the client disconnects.
+
+use dashmap::DashMap;
+use std::sync::Arc;
+
+use crate::auth::AuthenticatedEntity;
+
+/// Claim (or API key metadata entry) overriding the stream cap for a caller
+pub const MAX_CONCURRENT_STREAMS_CLAIM: &str = "max_concurrent_streams";
+
+/// Tracks open streaming responses per caller key
+#[derive(Debug, Clone, Default)]
+pub struct StreamLimiter {
+    active: Arc<DashMap<String, u32>>,
+}
+
+impl StreamLimiter {
+    /// Create an empty limiter
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Claim a stream slot for `key` if fewer than `limit` are open
+    #[must_use]
+    pub fn try_acquire(&self, key: &str, limit: u32) -> Option<StreamPermit> {
+        let acquired = {
+            let mut count = self.active.entry(key.to_string()).or_insert(0);
+            if *count < limit {
+                *count += 1;
+                true
+            } else {
+                false
+            }
+        };
+
+        if !acquired {
+            self.active.remove_if(key, |_, count| *count == 0);
+            return None;
+        }
+
+        Some(StreamPermit {
+            active: Arc::clone(&self.active),
+            key: key.to_string(),
+        })
+    }
+
+    /// Number of open streams for `key`
+    #[must_use]
+    pub fn active(&self, key: &str) -> u32 {
+        self.active.get(key).map_or(0, |count| *count)
+    }
+}
+
+/// An open stream slot, released on drop
+#[derive(Debug)]
+pub struct StreamPermit {
+    active: Arc<DashMap<String, u32>>,
+    key: String,
+}
+
+impl Drop for StreamPermit {
+    fn drop(&mut self) {
+        if let Some(mut count) = self.active.get_mut(&self.key) {
+            *count = count.saturating_sub(1);
+        }
+        self.active.remove_if(&self.key, |_, count| *count == 0);
+    }
+}
+
+/// Stream cap for a caller
+///
+/// A `max_concurrent_streams` claim on the authenticated entity (populated
+/// from API key metadata) takes precedence over the configured default.
+#[must_use]
+pub fn stream_limit(entity: Option<&AuthenticatedEntity>, default: Option<u32>) -> Option<u32> {
+    entity
+        .and_then(|entity| entity.claims.get(MAX_CONCURRENT_STREAMS_CLAIM))
+        .and_then(|value| match value {
+            serde_json::Value::Number(n) => n.as_u64().and_then(|n| u32::try_from(n).ok()),
+            serde_json::Value::String(s) => s.parse().ok(),
+            _ => None,
+        })
+        .or(default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::AuthMethod;
+    use std::collections::HashMap;
+
+    fn entity_with_claims(claims: HashMap<String, serde_json::Value>) -> AuthenticatedEntity {
+        AuthenticatedEntity {
+            id: "user".to_string(),
+            tenant_id: Some("acme".to_string()),
+            email: None,
+            name: None,
+            auth_method: AuthMethod::ApiKey,
+            credential_id: None,
+            scopes: vec![],
+            expires_at: None,
+            claims,
+        }
+    }
+
+    #[test]
+    fn test_acquire_up_to_limit() {
+        let limiter = StreamLimiter::new();
+
+        let first = limiter.try_acquire("tenant:acme", 2);
+        let second = limiter.try_acquire("tenant:acme", 2);
+        assert!(first.is_some());
+        assert!(second.is_some());
+        assert!(limiter.try_acquire("tenant:acme", 2).is_none());
+        assert_eq!(limiter.active("tenant:acme"), 2);
+
+        // Other tenants are counted separately
+        assert!(limiter.try_acquire("tenant:other", 2).is_some());
+    }
+
+    #[test]
+    fn test_dropping_permit_frees_slot() {
+        let limiter = StreamLimiter::new();
+
+        let permit = limiter.try_acquire("tenant:acme", 1);
+        assert!(limiter.try_acquire("tenant:acme", 1).is_none());
+
+        drop(permit);
+        assert_eq!(limiter.active("tenant:acme"), 0);
+        assert!(limiter.try_acquire("tenant:acme", 1).is_some());
+    }
+
+    #[test]
+    fn test_rejected_acquire_leaves_no_entry() {
+        let limiter = StreamLimiter::new();
+        assert!(limiter.try_acquire("tenant:acme", 0).is_none());
+        assert!(limiter.active.is_empty());
+    }
+
+    #[test]
+    fn test_stream_limit_prefers_key_metadata() {
+        let from_metadata = entity_with_claims(HashMap::from([(
+            MAX_CONCURRENT_STREAMS_CLAIM.to_string(),
+            serde_json::Value::String("5".to_string()),
+        )]));
+        let from_jwt = entity_with_claims(HashMap::from([(
+            MAX_CONCURRENT_STREAMS_CLAIM.to_string(),
+            serde_json::json!(3),
+        )]));
+        let without = entity_with_claims(HashMap::new());
+
+        assert_eq!(stream_limit(Some(&from_metadata), Some(10)), Some(5));
+        assert_eq!(stream_limit(Some(&from_jwt), None), Some(3));
+        assert_eq!(stream_limit(Some(&without), Some(10)), Some(10));
+        assert_eq!(stream_limit(None, None), None);
+    }
+}
@@ -0,0 +1,493 @@
+//! Request lifecycle traces served by `/admin/requests/{id}/trace`.
+//!
+//! The chat completion handler records each stage of a request here as it
+//! happens: the auth decision, the policy check, the routing decision, every
+//! provider attempt with its retries, and the response cache. The admin
+//! endpoint joins these with the request tracker's outcome and the cost
+//! tracker's usage events into one document.
+//!
+//! Traces are kept for `server.request_trace.retention` from the start of
+//! the request, up to `server.request_trace.max_traces`. Nothing secret is
+//! recorded: the auth stage holds the entity id, tenant, and scopes only,
+//! and error messages are redacted before they are exported.
+
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use gateway_config::RequestTraceConfig;
+use gateway_routing::RouteDecision;
+use gateway_telemetry::OutcomeKind;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::auth::{AuthMethod, AuthenticatedEntity};
+use crate::error::redact_secrets;
+use crate::state::AppState;
+
+/// Recent request traces by request id
+pub struct RequestTraceStore {
+    traces: DashMap<String, TraceEntry>,
+    retention: Duration,
+    max_traces: usize,
+}
+
+struct TraceEntry {
+    recorded: Instant,
+    trace: RequestTrace,
+}
+
+/// Stages of one request, as recorded by the handler
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RequestTrace {
+    /// Who made the request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auth: Option<AuthTrace>,
+
+    /// Policy engine decision, when a policy engine is configured
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub policy: Option<PolicyTrace>,
+
+    /// Provider selection
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub routing: Option<RoutingTrace>,
+
+    /// Dispatches to providers, one per provider tried
+    pub attempts: Vec<AttemptTrace>,
+
+    /// Response cache interaction
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache: Option<CacheTrace>,
+}
+
+/// Auth decision for a request
+#[derive(Debug, Clone, Serialize)]
+pub struct AuthTrace {
+    /// Whether the caller was authenticated
+    pub authenticated: bool,
+    /// How the caller authenticated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub method: Option<AuthMethod>,
+    /// Authenticated entity id
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    /// Tenant the request is scoped to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tenant_id: Option<String>,
+    /// Scopes held by the caller
+    pub scopes: Vec<String>,
+}
+
+/// Policy engine decision for a request
+#[derive(Debug, Clone, Serialize)]
+pub struct PolicyTrace {
+    /// Whether the request was allowed
+    pub allowed: bool,
+    /// Why it was denied
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Routing decision for a request
+#[derive(Debug, Clone, Serialize)]
+pub struct RoutingTrace {
+    /// Selected provider, absent if routing failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    /// Strategy that made the selection
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strategy: Option<String>,
+    /// Routing rules that matched
+    pub matched_rules: Vec<String>,
+    /// Per-provider scores from score-based strategies (lower is better)
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub scores: HashMap<String, f64>,
+    /// Region of the selected provider instance
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+    /// Provider the caller pinned the request to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider_override: Option<String>,
+    /// Why routing failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl RoutingTrace {
+    /// Trace of a successful routing decision
+    #[must_use]
+    pub fn selected(decision: &RouteDecision) -> Self {
+        Self {
+            provider: Some(decision.provider_id.clone()),
+            strategy: Some(decision.strategy.clone()),
+            matched_rules: decision.matched_rules.clone(),
+            scores: decision.scores.clone(),
+            region: decision.region.clone(),
+            provider_override: decision.provider_override.clone(),
+            error: None,
+        }
+    }
+
+    /// Trace of a request no provider could be selected for
+    #[must_use]
+    pub fn failed(error: impl Into<String>) -> Self {
+        Self {
+            provider: None,
+            strategy: None,
+            matched_rules: Vec::new(),
+            scores: HashMap::new(),
+            region: None,
+            provider_override: None,
+            error: Some(error.into()),
+        }
+    }
+}
+
+/// Dispatch of a request to one provider, including its retries
+#[derive(Debug, Clone, Serialize)]
+pub struct AttemptTrace {
+    /// Provider dispatched to
+    pub provider: String,
+    /// Region of the provider instance
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+    /// Calls made to the provider, the first plus any retries
+    pub calls: u32,
+    /// Retries after the first call
+    pub retries: u32,
+    /// Time spent on all calls to this provider
+    pub latency_ms: u64,
+    /// Whether the provider eventually answered
+    pub success: bool,
+    /// Error of the last call, if it failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl AttemptTrace {
+    /// Trace of `calls` calls to `provider` taking `latency` in total
+    #[must_use]
+    pub fn new(
+        provider: impl Into<String>,
+        region: Option<String>,
+        calls: u32,
+        latency: Duration,
+    ) -> Self {
+        Self {
+            provider: provider.into(),
+            region,
+            calls,
+            retries: calls.saturating_sub(1),
+            latency_ms: u64::try_from(latency.as_millis()).unwrap_or(u64::MAX),
+            success: true,
+            error: None,
+        }
+    }
+
+    /// Mark the attempt as failed with `error`
+    #[must_use]
+    pub fn with_error(mut self, error: impl Into<String>) -> Self {
+        self.success = false;
+        self.error = Some(error.into());
+        self
+    }
+}
+
+/// Response cache interaction for a request
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct CacheTrace {
+    /// Outcome of the fresh-response lookup before dispatch
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lookup: Option<CacheLookup>,
+    /// Outcome of the stale-response lookup after an upstream failure
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stale_lookup: Option<CacheLookup>,
+    /// Whether the response was stored in the cache
+    pub stored: bool,
+}
+
+/// Outcome of a cache lookup
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CacheLookup {
+    /// A cached response was served
+    Hit,
+    /// Nothing usable was cached
+    Miss,
+}
+
+impl RequestTraceStore {
+    /// Create a store keeping traces as configured
+    #[must_use]
+    pub fn new(config: &RequestTraceConfig) -> Self {
+        Self {
+            traces: DashMap::new(),
+            retention: config.retention,
+            max_traces: config.max_traces,
+        }
+    }
+
+    /// Update the trace of `request_id`, starting it if needed
+    pub fn record(&self, request_id: &str, update: impl FnOnce(&mut RequestTrace)) {
+        if self.max_traces == 0 {
+            return;
+        }
+        if let Some(mut entry) = self.traces.get_mut(request_id) {
+            update(&mut entry.trace);
+            return;
+        }
+
+        if self.traces.len() >= self.max_traces {
+            self.evict();
+        }
+        let mut trace = RequestTrace::default();
+        update(&mut trace);
+        self.traces.insert(
+            request_id.to_string(),
+            TraceEntry {
+                recorded: Instant::now(),
+                trace,
+            },
+        );
+    }
+
+    /// Trace of `request_id`, if still retained
+    #[must_use]
+    pub fn get(&self, request_id: &str) -> Option<RequestTrace> {
+        self.traces
+            .get(request_id)
+            .filter(|entry| entry.recorded.elapsed() < self.retention)
+            .map(|entry| entry.trace.clone())
+    }
+
+    /// Drop expired traces, then the oldest if the store is still full
+    fn evict(&self) {
+        self.traces
+            .retain(|_, entry| entry.recorded.elapsed() < self.retention);
+        while self.traces.len() >= self.max_traces {
+            let oldest = self
+                .traces
+                .iter()
+                .min_by_key(|entry| entry.recorded)
+                .map(|entry| entry.key().clone());
+            let Some(oldest) = oldest else { break };
+            self.traces.remove(&oldest);
+        }
+    }
+}
+
+impl AuthTrace {
+    /// Auth decision for `entity`, without any credential material
+    #[must_use]
+    pub fn new(entity: Option<&AuthenticatedEntity>, tenant_id: Option<&str>) -> Self {
+        let authenticated = entity.is_some_and(|e| e.auth_method != AuthMethod::Anonymous);
+        Self {
+            authenticated,
+            method: entity.map(|e| e.auth_method),
+            subject: entity.map(|e| e.id.clone()),
+            tenant_id: tenant_id.map(str::to_string),
+            scopes: entity.map(|e| e.scopes.clone()).unwrap_or_default(),
+        }
+    }
+}
+
+/// Exported trace of one request
+#[derive(Debug, Clone, Serialize)]
+pub struct RequestTraceDocument {
+    /// Request id
+    pub id: String,
+    /// Object type, always `request.trace`
+    pub object: &'static str,
+    /// `in_progress`, `completed`, `failed`, or `client_disconnected`
+    pub status: &'static str,
+    /// Model requested
+    pub model: String,
+    /// Provider that served the request last
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    /// Whether the response was streamed
+    pub streaming: bool,
+    /// When the request started
+    pub started_at: DateTime<Utc>,
+    /// When the request finished
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<DateTime<Utc>>,
+    /// HTTP status the request finished with
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_code: Option<u16>,
+    /// Total time in the gateway
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    /// Time to the first streamed token
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_to_first_token_ms: Option<u64>,
+    /// Error the request failed with
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Retries across all provider attempts
+    pub retries: u32,
+    /// Recorded stages
+    #[serde(flatten)]
+    pub trace: RequestTrace,
+    /// Billed usage, when cost tracking is enabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost: Option<CostTrace>,
+}
+
+/// Billed usage of a request
+#[derive(Debug, Clone, Serialize)]
+pub struct CostTrace {
+    /// Input tokens billed
+    pub input_tokens: u32,
+    /// Output tokens billed
+    pub output_tokens: u32,
+    /// Input tokens read from the provider's prompt cache
+    pub cache_read_tokens: u32,
+    /// Cost in USD
+    pub cost_usd: f64,
+    /// Savings from prompt cache reads in USD
+    pub cache_savings_usd: f64,
+}
+
+/// Assemble the trace of `request_id` from the trace store, the request
+/// tracker, and the cost tracker
+///
+/// Returns `None` once the request has aged out of the retention window.
+pub async fn export(state: &AppState, request_id: &str) -> Option<RequestTraceDocument> {
+    let mut trace = state.traces.get(request_id)?;
+    for attempt in &mut trace.attempts {
+        attempt.error = attempt.error.as_deref().map(redact_secrets);
+    }
+    if let Some(routing) = &mut trace.routing {
+        routing.error = routing.error.as_deref().map(redact_secrets);
+    }
+    if let Some(policy) = &mut trace.policy {
+        policy.reason = policy.reason.as_deref().map(redact_secrets);
+    }
+    let retries = trace.attempts.iter().map(|attempt| attempt.retries).sum();
+
+    let outcome = state.tracker.get_completed(request_id);
+    let info = match &outcome {
+        Some(outcome) => outcome.info.clone(),
+        None => state.tracker.get_active(request_id)?,
+    };
+
+    let events = state.cost_tracker.request_events(request_id).await;
+    let cost = (!events.is_empty()).then(|| {
+        events.iter().fold(
+            CostTrace {
+                input_tokens: 0,
+                output_tokens: 0,
+                cache_read_tokens: 0,
+                cost_usd: 0.0,
+                cache_savings_usd: 0.0,
+            },
+            |mut total, event| {
+                total.input_tokens = total.input_tokens.saturating_add(event.input_tokens);
+                total.output_tokens = total.output_tokens.saturating_add(event.output_tokens);
+                total.cache_read_tokens =
+                    total.cache_read_tokens.saturating_add(event.cache_read_tokens);
+                total.cost_usd += event.cost;
+                total.cache_savings_usd += event.cache_savings;
+                total
+            },
+        )
+    });
+
+    let millis = |d: Duration| u64::try_from(d.as_millis()).unwrap_or(u64::MAX);
+    Some(RequestTraceDocument {
+        id: request_id.to_string(),
+        object: "request.trace",
+        status: match outcome.as_ref().map(|o| o.kind) {
+            None => "in_progress",
+            Some(OutcomeKind::Completed) => "completed",
+            Some(OutcomeKind::Failed) => "failed",
+            Some(OutcomeKind::ClientDisconnect) => "client_disconnected",
+        },
+        model: info.model,
+        provider: info.provider,
+        streaming: info.streaming,
+        started_at: info.started_at,
+        completed_at: outcome.as_ref().map(|o| o.completed_at),
+        status_code: outcome.as_ref().map(|o| o.status_code),
+        duration_ms: outcome.as_ref().map(|o| millis(o.duration)),
+        time_to_first_token_ms: outcome
+            .as_ref()
+            .and_then(|o| o.time_to_first_token)
+            .map(millis),
+        error: outcome
+            .as_ref()
+            .and_then(|o| o.error.as_deref())
+            .map(redact_secrets),
+        retries,
+        trace,
+        cost,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store(retention: Duration, max_traces: usize) -> RequestTraceStore {
+        RequestTraceStore::new(&RequestTraceConfig {
+            retention,
+            max_traces,
+            ..RequestTraceConfig::default()
+        })
+    }
+
+    #[test]
+    fn test_records_accumulate() {
+        let store = store(Duration::from_secs(60), 10);
+        store.record("req-1", |trace| {
+            trace.routing = Some(RoutingTrace::failed("no providers"));
+        });
+        store.record("req-1", |trace| {
+            trace
+                .attempts
+                .push(AttemptTrace::new("openai", None, 3, Duration::from_millis(5)));
+        });
+
+        let trace = store.get("req-1").unwrap();
+        assert!(trace.routing.is_some());
+        assert_eq!(trace.attempts[0].retries, 2);
+        assert!(store.get("req-2").is_none());
+    }
+
+    #[test]
+    fn test_bounded_retention() {
+        let expired = store(Duration::ZERO, 10);
+        expired.record("req-1", |_| {});
+        assert!(expired.get("req-1").is_none());
+
+        let full = store(Duration::from_secs(60), 2);
+        for id in ["req-1", "req-2", "req-3"] {
+            full.record(id, |_| {});
+        }
+        assert!(full.get("req-1").is_none());
+        assert!(full.get("req-2").is_some());
+        assert!(full.get("req-3").is_some());
+    }
+
+    #[test]
+    fn test_auth_trace_has_no_credentials() {
+        let entity = AuthenticatedEntity {
+            id: "user-1".to_string(),
+            tenant_id: Some("acme".to_string()),
+            email: None,
+            name: None,
+            auth_method: AuthMethod::ApiKey,
+            credential_id: None,
+            scopes: vec!["chat".to_string()],
+            expires_at: None,
+            claims: HashMap::from([(
+                "api_key".to_string(),
+                serde_json::Value::String("sk-secret".to_string()),
+            )]),
+        };
+
+        let trace = serde_json::to_string(&AuthTrace::new(Some(&entity), Some("acme"))).unwrap();
+        assert!(trace.contains("user-1"));
+        assert!(!trace.contains("sk-secret"));
+    }
+}
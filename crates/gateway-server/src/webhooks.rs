@@ -0,0 +1,77 @@
+//! Lifecycle events raised from the request path.
+//!
+//! These helpers translate gateway state changes into webhook events when
+//! an emitter is configured on the [`AppState`]. Each event is built only if
+//! some endpoint subscribes to its type, so an unconfigured gateway pays
+//! nothing beyond an `Option` check.
+
+use gateway_core::GatewayError;
+use gateway_integrations::{WebhookEvent, WebhookEventType};
+use gateway_resilience::{CircuitBreaker, CircuitState};
+use serde_json::Value;
+
+use crate::state::AppState;
+
+/// Queue an event if any endpoint subscribes to its type
+pub fn emit(state: &AppState, event_type: WebhookEventType, data: impl FnOnce() -> Value) {
+    if let Some(webhooks) = state
+        .webhooks
+        .as_ref()
+        .filter(|webhooks| webhooks.is_subscribed(event_type))
+    {
+        webhooks.emit(&WebhookEvent::new(event_type, data()));
+    }
+}
+
+/// Record a provider error, raising `circuit.opened` if it trips the breaker
+pub fn record_breaker_error(state: &AppState, breaker: &CircuitBreaker, error: &GatewayError) {
+    let before = breaker.state();
+    breaker.record_error(error);
+    if before != CircuitState::Open && breaker.state() == CircuitState::Open {
+        emit(state, WebhookEventType::CircuitOpened, || {
+            serde_json::json!({
+                "provider": breaker.provider_id(),
+                "error": error.to_string(),
+            })
+        });
+    }
+}
+
+/// Whether the tenant is over budget, if `budget.exceeded` is subscribed
+///
+/// Taken before recording usage, so [`notify_budget_exceeded`] can tell
+/// whether that usage crossed the limit.
+pub async fn over_budget(state: &AppState, tenant_id: Option<&str>) -> Option<bool> {
+    let subscribed = state
+        .webhooks
+        .as_ref()
+        .is_some_and(|webhooks| webhooks.is_subscribed(WebhookEventType::BudgetExceeded));
+    if !subscribed {
+        return None;
+    }
+    let status = state.cost_tracker.check_budget(tenant_id?).await?;
+    Some(status.limit_exceeded)
+}
+
+/// Raise `budget.exceeded` if recorded usage pushed the tenant over budget
+pub async fn notify_budget_exceeded(
+    state: &AppState,
+    tenant_id: Option<&str>,
+    was_over: Option<bool>,
+) {
+    let (Some(tenant_id), Some(false)) = (tenant_id, was_over) else {
+        return;
+    };
+    let Some(status) = state.cost_tracker.check_budget(tenant_id).await else {
+        return;
+    };
+    if status.limit_exceeded {
+        emit(state, WebhookEventType::BudgetExceeded, || {
+            serde_json::json!({
+                "tenant_id": status.tenant_id,
+                "current_spend": status.current_spend,
+                "monthly_limit": status.monthly_limit,
+            })
+        });
+    }
+}
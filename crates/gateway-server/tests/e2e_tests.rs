@@ -10,16 +10,23 @@
 
 use axum::body::Body;
 use axum::http::{header, Method, Request, StatusCode};
+use futures::stream::BoxStream;
 use gateway_config::GatewayConfig;
-use gateway_core::{ChatMessage, GatewayRequest, GatewayResponse};
+use gateway_core::{
+    ChatChunk, ChatMessage, Choice, ChunkChoice, EmbeddingProvider, FinishReason, GatewayError,
+    GatewayRequest, GatewayResponse, HealthStatus, ImageProvider, LLMProvider, ModelInfo,
+    ProviderCapabilities, ProviderRateLimits, ProviderType,
+};
 use gateway_providers::openai::OpenAIConfig;
 use gateway_providers::{OpenAIProvider, ProviderRegistry};
 use gateway_resilience::{DistributedCache, DistributedCacheConfig, ResponseCache};
 use gateway_routing::{Router, RouterConfig};
+use gateway_server::state::AppStateBuilder;
 use gateway_server::AppState;
 use gateway_server::routes::create_router;
 use http_body_util::BodyExt;
 use serde_json::{json, Value};
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 use tower::ServiceExt;
@@ -47,6 +54,321 @@ fn create_test_state() -> AppState {
         .build()
 }
 
+// ============================================================================
+// Mock Provider Fixture
+// ============================================================================
+
+type ChunkStream = BoxStream<'static, Result<ChatChunk, GatewayError>>;
+type ChatScript =
+    Box<dyn Fn(&GatewayRequest, u32) -> Result<GatewayResponse, GatewayError> + Send + Sync>;
+type StreamScript =
+    Box<dyn Fn(&GatewayRequest, u32) -> Result<ChunkStream, GatewayError> + Send + Sync>;
+type RawStreamScript = Box<
+    dyn Fn(
+            &GatewayRequest,
+        ) -> Result<BoxStream<'static, Result<bytes::Bytes, GatewayError>>, GatewayError>
+        + Send
+        + Sync,
+>;
+
+/// Provider whose replies are scripted by each test
+///
+/// Chat and streaming calls share one counter, and each script receives
+/// the request and the zero-based call number. Every dispatched request is
+/// recorded. Unscripted calls fail with an internal error.
+struct MockProvider {
+    id: String,
+    provider_type: ProviderType,
+    models: Vec<ModelInfo>,
+    capabilities: ProviderCapabilities,
+    chat: Option<ChatScript>,
+    stream: Option<StreamScript>,
+    raw_stream: Option<RawStreamScript>,
+    /// How long chat completions take
+    delay: Duration,
+    health: parking_lot::Mutex<HealthStatus>,
+    health_checks: AtomicUsize,
+    rate_limits: Option<ProviderRateLimits>,
+    images: Option<Arc<dyn ImageProvider>>,
+    embeddings: Option<Arc<dyn EmbeddingProvider>>,
+    calls: AtomicU32,
+    /// Chat completions dropped before they finished
+    abandoned: AtomicU32,
+    requests: parking_lot::Mutex<Vec<GatewayRequest>>,
+}
+
+impl MockProvider {
+    fn new(id: &str, model: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            provider_type: ProviderType::Custom,
+            models: vec![ModelInfo::new(model)],
+            capabilities: ProviderCapabilities::basic_chat(),
+            chat: None,
+            stream: None,
+            raw_stream: None,
+            delay: Duration::ZERO,
+            health: parking_lot::Mutex::new(HealthStatus::Healthy),
+            health_checks: AtomicUsize::new(0),
+            rate_limits: None,
+            images: None,
+            embeddings: None,
+            calls: AtomicU32::new(0),
+            abandoned: AtomicU32::new(0),
+            requests: parking_lot::Mutex::new(Vec::new()),
+        }
+    }
+
+    fn with_models(mut self, models: Vec<ModelInfo>) -> Self {
+        self.models = models;
+        self
+    }
+
+    fn with_provider_type(mut self, provider_type: ProviderType) -> Self {
+        self.provider_type = provider_type;
+        self
+    }
+
+    fn with_chat(
+        mut self,
+        script: impl Fn(&GatewayRequest, u32) -> Result<GatewayResponse, GatewayError>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.chat = Some(Box::new(script));
+        self
+    }
+
+    /// Answer every chat completion with `content`
+    fn with_reply(self, content: &str) -> Self {
+        let id = format!("{}-response", self.id);
+        let content = content.to_string();
+        self.with_chat(move |request, _| Ok(text_response(&id, &request.model, &content)))
+    }
+
+    fn with_stream(
+        mut self,
+        script: impl Fn(&GatewayRequest, u32) -> Result<ChunkStream, GatewayError>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.stream = Some(Box::new(script));
+        self
+    }
+
+    fn with_raw_stream(
+        mut self,
+        script: impl Fn(
+                &GatewayRequest,
+            )
+                -> Result<BoxStream<'static, Result<bytes::Bytes, GatewayError>>, GatewayError>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.raw_stream = Some(Box::new(script));
+        self
+    }
+
+    fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    fn with_health(self, status: HealthStatus) -> Self {
+        self.set_health(status);
+        self
+    }
+
+    fn with_rate_limits(mut self, limits: ProviderRateLimits) -> Self {
+        self.rate_limits = Some(limits);
+        self
+    }
+
+    fn with_images(mut self, images: Arc<dyn ImageProvider>) -> Self {
+        self.images = Some(images);
+        self
+    }
+
+    fn with_embeddings(mut self, embeddings: Arc<dyn EmbeddingProvider>) -> Self {
+        self.embeddings = Some(embeddings);
+        self
+    }
+
+    fn set_health(&self, status: HealthStatus) {
+        *self.health.lock() = status;
+    }
+
+    fn record(&self, request: &GatewayRequest) -> u32 {
+        self.requests.lock().push(request.clone());
+        self.calls.fetch_add(1, Ordering::SeqCst)
+    }
+}
+
+/// Counts a chat completion as abandoned if dropped before it finishes
+struct Unfinished<'a>(Option<&'a AtomicU32>);
+
+impl Drop for Unfinished<'_> {
+    fn drop(&mut self) {
+        if let Some(abandoned) = self.0 {
+            abandoned.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl LLMProvider for MockProvider {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn provider_type(&self) -> ProviderType {
+        self.provider_type
+    }
+
+    async fn chat_completion(
+        &self,
+        request: &GatewayRequest,
+    ) -> Result<GatewayResponse, GatewayError> {
+        let call = self.record(request);
+        let mut unfinished = Unfinished(Some(&self.abandoned));
+        if !self.delay.is_zero() {
+            tokio::time::sleep(self.delay).await;
+        }
+        unfinished.0 = None;
+        match &self.chat {
+            Some(script) => script(request, call),
+            None => Err(GatewayError::internal("no chat reply scripted")),
+        }
+    }
+
+    async fn chat_completion_stream(
+        &self,
+        request: &GatewayRequest,
+    ) -> Result<ChunkStream, GatewayError> {
+        let call = self.record(request);
+        match &self.stream {
+            Some(script) => script(request, call),
+            None => Err(GatewayError::internal("not streaming")),
+        }
+    }
+
+    fn supports_raw_stream(&self) -> bool {
+        self.raw_stream.is_some()
+    }
+
+    async fn chat_completion_stream_raw(
+        &self,
+        request: &GatewayRequest,
+    ) -> Result<BoxStream<'static, Result<bytes::Bytes, GatewayError>>, GatewayError> {
+        self.record(request);
+        match &self.raw_stream {
+            Some(script) => script(request),
+            None => Err(GatewayError::internal("no raw stream scripted")),
+        }
+    }
+
+    async fn health_check(&self) -> HealthStatus {
+        self.health_checks.fetch_add(1, Ordering::SeqCst);
+        *self.health.lock()
+    }
+
+    fn capabilities(&self) -> &ProviderCapabilities {
+        &self.capabilities
+    }
+
+    fn models(&self) -> &[ModelInfo] {
+        &self.models
+    }
+
+    fn base_url(&self) -> &'static str {
+        "http://localhost"
+    }
+
+    fn rate_limits(&self, _model: &str) -> Option<ProviderRateLimits> {
+        self.rate_limits
+    }
+
+    fn as_image_provider(&self) -> Option<&dyn ImageProvider> {
+        self.images.as_deref()
+    }
+
+    fn as_embedding_provider(&self) -> Option<&dyn EmbeddingProvider> {
+        self.embeddings.as_deref()
+    }
+}
+
+/// A finished completion answering `content`
+fn text_response(id: &str, model: &str, content: &str) -> GatewayResponse {
+    GatewayResponse::builder()
+        .id(id)
+        .model(model)
+        .choice(Choice::new(0, content, FinishReason::Stop))
+        .build()
+}
+
+/// Chunks streaming `parts` in order, followed by a stop chunk
+fn text_chunks(id: &str, model: &str, parts: &[&str]) -> Vec<ChatChunk> {
+    parts
+        .iter()
+        .map(|part| ChunkChoice::with_content(0, *part))
+        .chain([ChunkChoice::with_finish(0, FinishReason::Stop)])
+        .map(|choice| {
+            ChatChunk::builder()
+                .id(id)
+                .model(model)
+                .choice(choice)
+                .build()
+        })
+        .collect()
+}
+
+/// Router serving `providers`, each registered alike and marked healthy
+fn mock_router<P: LLMProvider>(config: RouterConfig, providers: &[Arc<P>]) -> Router {
+    let router = Router::new(config);
+    for provider in providers {
+        router.register_provider(provider.clone(), 100, 1);
+        router.update_health(provider.id(), HealthStatus::Healthy);
+    }
+    router
+}
+
+/// Registry holding `providers`, for endpoints that look providers up by model
+fn mock_registry(providers: &[Arc<MockProvider>]) -> ProviderRegistry {
+    let registry = ProviderRegistry::new();
+    for provider in providers {
+        registry
+            .register(provider.clone(), 1, 100)
+            .expect("register mock provider");
+    }
+    registry
+}
+
+/// State routing to `providers` under the default config with `configure`
+/// applied, for tests that set more of the state themselves
+fn mock_state_builder(
+    providers: &[Arc<MockProvider>],
+    configure: impl FnOnce(&mut GatewayConfig),
+) -> AppStateBuilder {
+    let mut config = GatewayConfig::default();
+    configure(&mut config);
+    AppState::builder()
+        .config(config)
+        .providers(ProviderRegistry::new())
+        .router(mock_router(RouterConfig::default(), providers))
+}
+
+/// State routing to `providers` under the default config with `configure`
+/// applied
+fn mock_state(
+    providers: &[Arc<MockProvider>],
+    configure: impl FnOnce(&mut GatewayConfig),
+) -> AppState {
+    mock_state_builder(providers, configure).build()
+}
+
 #[cfg(test)]
 mod health_endpoint_tests {
     use super::*;
@@ -85,9 +407,20 @@ mod health_endpoint_tests {
         assert_eq!(response.status(), StatusCode::OK);
     }
 
+    fn probe(id: &str, status: HealthStatus) -> Arc<MockProvider> {
+        Arc::new(MockProvider::new(id, "gpt-4o").with_health(status))
+    }
+
+    fn probe_state(config: GatewayConfig, providers: &[Arc<MockProvider>]) -> AppState {
+        mock_state_builder(&[], move |c| *c = config)
+            .providers(mock_registry(providers))
+            .build()
+    }
+
     #[tokio::test]
     async fn test_readiness_endpoint() {
-        let app = create_router(create_test_state());
+        let provider = probe("probe", HealthStatus::Healthy);
+        let app = create_router(probe_state(GatewayConfig::default(), &[provider]));
 
         let request = Request::builder()
             .method(Method::GET)
@@ -99,6 +432,104 @@ mod health_endpoint_tests {
         assert_eq!(response.status(), StatusCode::OK);
     }
 
+    async fn readiness(state: &AppState) -> (StatusCode, Value) {
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri("/health/ready")
+            .body(Body::empty())
+            .unwrap();
+        let response = create_router(state.clone()).oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_readiness_waits_for_min_healthy_providers() {
+        let first = probe("first", HealthStatus::Unhealthy);
+        let second = probe("second", HealthStatus::Unhealthy);
+
+        let mut config = GatewayConfig::default();
+        config.server.readiness.min_healthy_providers = 2;
+        let state = probe_state(config, &[first.clone(), second.clone()]);
+
+        let (status, body) = readiness(&state).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["ready"], false);
+        assert_eq!(body["providers"], 2);
+        assert_eq!(body["healthy_providers"], 0);
+
+        first.set_health(HealthStatus::Healthy);
+        state.health.clear_cache().await;
+        let (status, body) = readiness(&state).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["reason"], "insufficient healthy providers: 1 < 2");
+
+        second.set_health(HealthStatus::Degraded);
+        state.health.clear_cache().await;
+        let (status, body) = readiness(&state).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["ready"], true);
+        assert_eq!(body["healthy_providers"], 2);
+
+        // Only providers with the required capabilities count
+        let mut config = (*state.config()).clone();
+        config.server.readiness.required_capabilities = vec!["embeddings".to_string()];
+        state.update_config(config);
+        let (status, body) = readiness(&state).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["providers"], 0);
+    }
+
+    #[tokio::test]
+    async fn test_readiness_fails_once_shutting_down() {
+        let provider = probe("probe", HealthStatus::Healthy);
+        let state = probe_state(GatewayConfig::default(), &[provider]);
+        assert_eq!(readiness(&state).await.0, StatusCode::OK);
+
+        state.health.mark_shutting_down();
+        let (status, body) = readiness(&state).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["reason"], "shutting down");
+    }
+
+    #[tokio::test]
+    async fn test_readiness_gate_disabled_with_zero_minimum() {
+        let provider = probe("probe", HealthStatus::Unhealthy);
+        let mut config = GatewayConfig::default();
+        config.server.readiness.min_healthy_providers = 0;
+        let state = probe_state(config, &[provider]);
+
+        let (status, body) = readiness(&state).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["healthy_providers"], 0);
+    }
+
+    #[tokio::test]
+    async fn test_readiness_probes_share_cached_provider_health() {
+        let provider = probe("probe", HealthStatus::Healthy);
+        let mut config = GatewayConfig::default();
+        config.server.readiness.provider_cache_ttl = Duration::from_millis(100);
+        let state = probe_state(config, std::slice::from_ref(&provider));
+
+        assert_eq!(readiness(&state).await.0, StatusCode::OK);
+        assert_eq!(readiness(&state).await.0, StatusCode::OK);
+        assert_eq!(provider.health_checks.load(Ordering::SeqCst), 1);
+
+        // Past the TTL the cached result is served while a refresh runs
+        provider.set_health(HealthStatus::Unhealthy);
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        assert_eq!(readiness(&state).await.0, StatusCode::OK);
+        tokio::time::timeout(Duration::from_secs(2), async {
+            while readiness(&state).await.0 == StatusCode::OK {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("refreshed result should be served");
+        assert_eq!(provider.health_checks.load(Ordering::SeqCst), 2);
+    }
+
     #[tokio::test]
     async fn test_liveness_endpoint() {
         let app = create_router(create_test_state());
@@ -237,6 +668,88 @@ mod chat_completions_validation_tests {
             response.status()
         );
     }
+
+    #[tokio::test]
+    async fn test_chat_completions_rejects_conflicting_token_limits() {
+        let app = create_router(create_test_state());
+
+        let body = json!({
+            "model": "o3-mini",
+            "messages": [{"role": "user", "content": "Hello"}],
+            "max_tokens": 100,
+            "max_completion_tokens": 500
+        });
+
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/chat/completions")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header("x-parent-span-id", uuid::Uuid::new_v4().to_string())
+            .body(Body::from(serde_json::to_string(&body).unwrap()))
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let json: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["error"]["code"], "conflicting_max_tokens");
+        assert_eq!(json["error"]["param"], "max_completion_tokens");
+    }
+
+    async fn send_image(state: AppState, url: &str) -> (StatusCode, Value) {
+        let body = json!({
+            "model": "gpt-4o",
+            "messages": [{
+                "role": "user",
+                "content": [
+                    {"type": "text", "text": "What is this?"},
+                    {"type": "image_url", "image_url": {"url": url}}
+                ]
+            }]
+        });
+
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/chat/completions")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header("x-parent-span-id", uuid::Uuid::new_v4().to_string())
+            .body(Body::from(body.to_string()))
+            .unwrap();
+
+        let response = create_router(state).oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+    }
+
+    #[tokio::test]
+    async fn test_chat_completions_rejects_oversized_image() {
+        let mut config = GatewayConfig::default();
+        config.server.images.max_bytes = 8;
+        let state = AppState::builder()
+            .config(config)
+            .providers(create_mock_registry())
+            .router(Router::new(RouterConfig::default()))
+            .build();
+
+        // A 1x1 PNG, well over 8 bytes
+        let png = "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mNkYPhfDwAChwGA60e6kgAAAABJRU5ErkJggg==";
+        let (status, json) = send_image(state, &format!("data:image/png;base64,{png}")).await;
+
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(json["error"]["code"], "image_too_large");
+        assert_eq!(json["error"]["param"], "messages[0].content[1].image_url");
+    }
+
+    #[tokio::test]
+    async fn test_chat_completions_rejects_malformed_image() {
+        let (status, json) =
+            send_image(create_test_state(), "data:image/png;base64,@@not-base64@@").await;
+
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(json["error"]["code"], "invalid_image");
+    }
 }
 
 #[cfg(test)]
@@ -337,6 +850,148 @@ mod admin_endpoint_tests {
     }
 }
 
+#[cfg(test)]
+mod rate_limit_endpoint_tests {
+    use super::*;
+    use gateway_server::{auth_middleware, ApiKeyConfig, ApiKeyMetadata, AuthConfig, AuthState};
+
+    async fn create_rate_limited_app() -> axum::Router {
+        create_token_limited_app(None).await
+    }
+
+    async fn create_token_limited_app(tpm: Option<u32>) -> axum::Router {
+        let mut config = GatewayConfig::default();
+        config.security.rate_limiting.enabled = true;
+        config.security.rate_limiting.default_rpm = 10;
+        config.security.rate_limiting.default_tpm = tpm;
+
+        let state = AppState::builder()
+            .config(config)
+            .providers(create_mock_registry())
+            .router(Router::new(RouterConfig::default()))
+            .build();
+
+        let auth_state = AuthState::new(
+            AuthConfig::builder()
+                .api_keys(
+                    ApiKeyConfig::new()
+                        .with_key("key-acme", ApiKeyMetadata::new().with_tenant("acme"))
+                        .with_key("key-globex", ApiKeyMetadata::new().with_tenant("globex")),
+                )
+                .required(true)
+                .build(),
+        )
+        .await
+        .unwrap();
+
+        create_router(state).layer(axum::middleware::from_fn_with_state(
+            auth_state,
+            auth_middleware,
+        ))
+    }
+
+    async fn get(app: &axum::Router, uri: &str, api_key: &str) -> axum::response::Response {
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri(uri)
+            .header("x-api-key", api_key)
+            .body(Body::empty())
+            .unwrap();
+
+        app.clone().oneshot(request).await.unwrap()
+    }
+
+    async fn rate_limit_status(app: &axum::Router, api_key: &str) -> Value {
+        let response = get(app, "/v1/rate_limit", api_key).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_endpoint_does_not_consume() {
+        let app = create_rate_limited_app().await;
+
+        let first = rate_limit_status(&app, "key-acme").await;
+        assert_eq!(first["enabled"], true);
+        assert_eq!(first["limit"], 10);
+
+        for _ in 0..5 {
+            let status = rate_limit_status(&app, "key-acme").await;
+            assert_eq!(status["remaining"], first["remaining"]);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_endpoint_reflects_consumption() {
+        let app = create_rate_limited_app().await;
+
+        let before = rate_limit_status(&app, "key-acme").await;
+        let remaining = before["remaining"].as_u64().unwrap();
+
+        let response = get(&app, "/v1/models", "key-acme").await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let after = rate_limit_status(&app, "key-acme").await;
+        assert_eq!(after["remaining"].as_u64().unwrap(), remaining - 1);
+        assert!(after["reset_seconds"].as_u64().unwrap() > 0);
+
+        // Another tenant's bucket is untouched
+        let other = rate_limit_status(&app, "key-globex").await;
+        assert_eq!(other["remaining"].as_u64().unwrap(), remaining);
+    }
+
+    #[tokio::test]
+    async fn test_chat_completion_charges_estimated_tokens() {
+        // 1200 tokens with the 1.2 burst allowance
+        let app = create_token_limited_app(Some(1000)).await;
+        let before = rate_limit_status(&app, "key-acme").await;
+        assert_eq!(before["tokens_remaining"], 1200);
+
+        let post = || {
+            let body = json!({
+                "model": "unrouted-model",
+                "messages": [{"role": "user", "content": "Hello"}],
+                "max_tokens": 700
+            });
+            let request = Request::builder()
+                .method(Method::POST)
+                .uri("/v1/chat/completions")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header("x-api-key", "key-acme")
+                .header("x-parent-span-id", uuid::Uuid::new_v4().to_string())
+                .body(Body::from(body.to_string()))
+                .unwrap();
+            app.clone().oneshot(request)
+        };
+
+        // The prompt and the completion limit are charged up front
+        let first = post().await.unwrap();
+        assert_ne!(first.status(), StatusCode::TOO_MANY_REQUESTS);
+        let after = rate_limit_status(&app, "key-acme").await;
+        let remaining = after["tokens_remaining"].as_u64().unwrap();
+        assert!(remaining < 500, "{remaining} tokens left");
+
+        let second = post().await.unwrap();
+        assert_eq!(second.status(), StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_endpoint_requires_authentication() {
+        let app = create_router(create_test_state());
+
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri("/v1/rate_limit")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+}
+
 #[cfg(test)]
 mod cache_tests {
     use super::*;
@@ -362,10 +1017,13 @@ mod cache_tests {
                 prompt_tokens: 5,
                 completion_tokens: 10,
                 total_tokens: 15,
+                prompt_tokens_cached: None,
+                completion_tokens_reasoning: None,
             },
             created: 1234567890,
             provider: Some("mock-openai".to_string()),
             system_fingerprint: None,
+            service_tier: None,
         };
 
         cache.put(&request, response.clone()).await;
@@ -403,10 +1061,13 @@ mod cache_tests {
                 prompt_tokens: 5,
                 completion_tokens: 10,
                 total_tokens: 15,
+                prompt_tokens_cached: None,
+                completion_tokens_reasoning: None,
             },
             created: 1234567890,
             provider: Some("mock".to_string()),
             system_fingerprint: None,
+            service_tier: None,
         };
 
         cache.put(&request, response.clone()).await;
@@ -421,53 +1082,161 @@ mod cache_tests {
 }
 
 #[cfg(test)]
-mod request_builder_tests {
+mod stale_cache_tests {
     use super::*;
+    use gateway_resilience::CacheConfig;
 
-    #[test]
-    fn test_request_builder_creates_valid_request() {
-        let request = GatewayRequest::builder()
-            .model("gpt-4o")
-            .message(ChatMessage::user("Hello"))
-            .message(ChatMessage::assistant("Hi there!"))
-            .message(ChatMessage::user("How are you?"))
-            .temperature(0.8)
-            .max_tokens(200u32)
-            .top_p(0.95)
-            .build();
-
-        assert!(request.is_ok());
-
-        let req = request.unwrap();
-        assert_eq!(req.model, "gpt-4o");
-        assert_eq!(req.messages.len(), 3);
-        assert_eq!(req.temperature, Some(0.8));
-        assert_eq!(req.max_tokens, Some(200));
-        assert_eq!(req.top_p, Some(0.95));
+    fn chat_body() -> Value {
+        json!({
+            "model": "gpt-4o-mini",
+            "messages": [{"role": "user", "content": "Hello"}]
+        })
     }
 
-    #[test]
-    fn test_request_builder_validates_temperature() {
-        let result = GatewayRequest::builder()
-            .model("gpt-4o")
-            .message(ChatMessage::user("Hello"))
-            .temperature(2.5)
-            .build();
-
-        assert!(result.is_err());
+    fn cached_response() -> GatewayResponse {
+        GatewayResponse::builder()
+            .id("stale-response-id")
+            .model("gpt-4o-mini")
+            .build()
     }
 
-    #[test]
-    fn test_request_builder_requires_model() {
-        let result = GatewayRequest::builder()
-            .message(ChatMessage::user("Hello"))
-            .build();
+    /// State whose router has no providers, so every dispatch fails
+    async fn create_state_with_stale_entry(max_staleness: Duration) -> AppState {
+        let cache = Arc::new(ResponseCache::new(CacheConfig {
+            serve_stale_on_error: true,
+            max_staleness,
+            ..CacheConfig::default()
+        }));
+        let request: GatewayRequest = serde_json::from_value(chat_body()).unwrap();
+        cache
+            .put_with_ttl(&request, cached_response(), Duration::from_millis(10))
+            .await;
+        tokio::time::sleep(Duration::from_millis(50)).await;
 
-        assert!(result.is_err());
+        mock_state_builder(&[], |_| {}).response_cache(cache).build()
     }
 
-    #[test]
-    fn test_request_builder_requires_messages() {
+    async fn post_chat(state: AppState) -> axum::response::Response {
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/chat/completions")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header("x-parent-span-id", uuid::Uuid::new_v4().to_string())
+            .body(Body::from(chat_body().to_string()))
+            .unwrap();
+
+        create_router(state).oneshot(request).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_upstream_failure_serves_stale_entry() {
+        let state = create_state_with_stale_entry(Duration::from_secs(60)).await;
+        let response = post_chat(state).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers().get("x-cache").unwrap(), "stale");
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let json: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["success"], true);
+        assert_eq!(json["result"]["id"], "stale-response-id");
+    }
+
+    #[tokio::test]
+    async fn test_upstream_failure_errors_beyond_max_staleness() {
+        let state = create_state_with_stale_entry(Duration::from_millis(10)).await;
+        let response = post_chat(state).await;
+
+        assert!(response.headers().get("x-cache").is_none());
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let json: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["success"], false);
+        assert!(json["result"].is_null());
+    }
+
+    #[tokio::test]
+    async fn test_fresh_entry_served_without_dispatch() {
+        let cache = Arc::new(ResponseCache::new(CacheConfig::default()));
+        let request: GatewayRequest = serde_json::from_value(chat_body()).unwrap();
+        cache.put(&request, cached_response()).await;
+        let provider = Arc::new(MockProvider::new("openai", "gpt-4o-mini").with_reply("Hello"));
+        let state = mock_state_builder(std::slice::from_ref(&provider), |_| {})
+            .response_cache(Arc::clone(&cache))
+            .build();
+
+        let response = post_chat(state).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers().get("x-cache").unwrap(), "HIT");
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let json: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["result"]["id"], "stale-response-id");
+        assert_eq!(cache.stats().await.hits, 1);
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_lookup_past_deadline_is_request_timeout() {
+        let cache = Arc::new(ResponseCache::new(CacheConfig::default()));
+        let state = mock_state_builder(&[], |config| config.server.request_timeout = Duration::ZERO)
+            .response_cache(cache)
+            .build();
+
+        let response = post_chat(state).await;
+
+        assert_eq!(response.status(), StatusCode::REQUEST_TIMEOUT);
+    }
+}
+
+#[cfg(test)]
+mod request_builder_tests {
+    use super::*;
+
+    #[test]
+    fn test_request_builder_creates_valid_request() {
+        let request = GatewayRequest::builder()
+            .model("gpt-4o")
+            .message(ChatMessage::user("Hello"))
+            .message(ChatMessage::assistant("Hi there!"))
+            .message(ChatMessage::user("How are you?"))
+            .temperature(0.8)
+            .max_tokens(200u32)
+            .top_p(0.95)
+            .build();
+
+        assert!(request.is_ok());
+
+        let req = request.unwrap();
+        assert_eq!(req.model, "gpt-4o");
+        assert_eq!(req.messages.len(), 3);
+        assert_eq!(req.temperature, Some(0.8));
+        assert_eq!(req.max_tokens, Some(200));
+        assert_eq!(req.top_p, Some(0.95));
+    }
+
+    #[test]
+    fn test_request_builder_validates_temperature() {
+        let result = GatewayRequest::builder()
+            .model("gpt-4o")
+            .message(ChatMessage::user("Hello"))
+            .temperature(2.5)
+            .build();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_request_builder_requires_model() {
+        let result = GatewayRequest::builder()
+            .message(ChatMessage::user("Hello"))
+            .build();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_request_builder_requires_messages() {
         let result = GatewayRequest::builder().model("gpt-4o").build();
 
         assert!(result.is_err());
@@ -490,10 +1259,13 @@ mod response_format_tests {
                 prompt_tokens: 10,
                 completion_tokens: 5,
                 total_tokens: 15,
+                prompt_tokens_cached: None,
+                completion_tokens_reasoning: None,
             },
             created: 1234567890,
             provider: Some("openai".to_string()),
             system_fingerprint: Some("fp_abc123".to_string()),
+            service_tier: None,
         };
 
         let json = serde_json::to_value(&response).unwrap();
@@ -504,3 +1276,3396 @@ mod response_format_tests {
         assert_eq!(json["usage"]["total_tokens"], 15);
     }
 }
+
+#[cfg(test)]
+mod stream_duration_tests {
+    use super::*;
+    use futures::stream::StreamExt;
+    use std::time::Instant;
+
+    #[tokio::test]
+    async fn test_trickling_stream_terminates_at_max_duration() {
+        // Yields a tiny chunk every 20ms, forever
+        let provider = MockProvider::new("trickle", "trickle-model").with_stream(|_, _| {
+            Ok(futures::stream::unfold((), |()| async {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                let chunk = ChatChunk::builder()
+                    .model("trickle-model")
+                    .choice(ChunkChoice::with_content(0, "."))
+                    .build();
+                Some((Ok(chunk), ()))
+            })
+            .boxed())
+        });
+        let state = mock_state(&[Arc::new(provider)], |config| {
+            config.server.max_stream_duration = Duration::from_millis(300);
+        });
+
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/chat/completions")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header("x-parent-span-id", uuid::Uuid::new_v4().to_string())
+            .body(Body::from(
+                json!({
+                    "model": "trickle-model",
+                    "messages": [{"role": "user", "content": "Hello"}],
+                    "stream": true
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        let start = Instant::now();
+        let response = create_router(state).oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = tokio::time::timeout(Duration::from_secs(5), response.into_body().collect())
+            .await
+            .expect("stream should terminate at the max duration")
+            .unwrap()
+            .to_bytes();
+        let elapsed = start.elapsed();
+        let body = String::from_utf8_lossy(&body);
+
+        assert!(elapsed >= Duration::from_millis(300));
+        assert!(elapsed < Duration::from_secs(2));
+        assert!(body.contains("\"code\":\"timeout\""));
+        assert!(body.contains("[DONE]"));
+    }
+}
+
+#[cfg(test)]
+mod request_deadline_tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[tokio::test]
+    async fn test_request_deadline_cancels_in_flight_provider_call() {
+        // Completions never finish in time
+        let provider = Arc::new(
+            MockProvider::new("hanging", "slow-model").with_delay(Duration::from_secs(30)),
+        );
+        let state = mock_state(std::slice::from_ref(&provider), |config| {
+            config.server.request_timeout = Duration::from_millis(200);
+        });
+
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/chat/completions")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header("x-parent-span-id", uuid::Uuid::new_v4().to_string())
+            .body(Body::from(
+                json!({
+                    "model": "slow-model",
+                    "messages": [{"role": "user", "content": "Hello"}]
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        let start = Instant::now();
+        let response = tokio::time::timeout(
+            Duration::from_secs(5),
+            create_router(state).oneshot(request),
+        )
+        .await
+        .expect("request should finish at the deadline")
+        .unwrap();
+        let elapsed = start.elapsed();
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let json: Value = serde_json::from_slice(&body).unwrap();
+
+        assert!(elapsed >= Duration::from_millis(200));
+        assert!(elapsed < Duration::from_secs(2));
+        assert_eq!(json["success"], false);
+        assert_eq!(provider.abandoned.load(Ordering::SeqCst), 1);
+        // The timeout is not retried once the deadline has passed
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 1);
+    }
+}
+
+#[cfg(test)]
+mod concurrent_stream_tests {
+    use super::*;
+    use futures::stream::StreamExt;
+
+    fn create_state(max_streams: u32) -> AppState {
+        // Streams stay open until the client goes away
+        let provider = MockProvider::new("open-ended", "stream-model")
+            .with_stream(|_, _| Ok(futures::stream::pending().boxed()));
+        mock_state(&[Arc::new(provider)], |config| {
+            config.server.max_concurrent_streams_per_tenant = Some(max_streams);
+        })
+    }
+
+    async fn open_stream(state: &AppState, tenant: &str) -> axum::response::Response {
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/chat/completions")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header("x-parent-span-id", uuid::Uuid::new_v4().to_string())
+            .header("x-tenant-id", tenant)
+            .body(Body::from(
+                json!({
+                    "model": "stream-model",
+                    "messages": [{"role": "user", "content": "Hello"}],
+                    "stream": true
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        create_router(state.clone()).oneshot(request).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_stream_beyond_tenant_cap_is_rejected() {
+        let state = create_state(2);
+
+        let first = open_stream(&state, "acme").await;
+        let second = open_stream(&state, "acme").await;
+        assert_eq!(first.status(), StatusCode::OK);
+        assert_eq!(second.status(), StatusCode::OK);
+
+        let third = open_stream(&state, "acme").await;
+        assert_eq!(third.status(), StatusCode::TOO_MANY_REQUESTS);
+        let body = third.into_body().collect().await.unwrap().to_bytes();
+        let json: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["error"]["code"], "concurrent_stream_limit_exceeded");
+
+        // Other tenants have their own slots
+        let other = open_stream(&state, "globex").await;
+        assert_eq!(other.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_finished_stream_frees_slot() {
+        let state = create_state(1);
+
+        let first = open_stream(&state, "acme").await;
+        assert_eq!(first.status(), StatusCode::OK);
+        assert_eq!(
+            open_stream(&state, "acme").await.status(),
+            StatusCode::TOO_MANY_REQUESTS
+        );
+
+        // Client goes away: dropping the body drops the stream and its slot
+        drop(first);
+        assert_eq!(state.stream_limiter.active("tenant:acme"), 0);
+
+        let next = open_stream(&state, "acme").await;
+        assert_eq!(next.status(), StatusCode::OK);
+    }
+}
+
+#[cfg(test)]
+mod provider_rate_limit_tests {
+    use super::*;
+    use gateway_core::RateLimitWindow;
+
+    fn create_state(backoff_enabled: bool) -> AppState {
+        // Reports a nearly exhausted request quota
+        let provider = MockProvider::new("low-quota", "quota-model")
+            .with_reply("Hello")
+            .with_rate_limits(ProviderRateLimits {
+                requests: RateLimitWindow {
+                    limit: Some(100),
+                    remaining: Some(1),
+                    reset: Some(Duration::from_secs(2)),
+                },
+                tokens: RateLimitWindow::default(),
+            });
+        mock_state(&[Arc::new(provider)], |config| {
+            config.resilience.proactive_backoff.enabled = backoff_enabled;
+        })
+    }
+
+    async fn send_completion(state: &AppState) -> axum::response::Response {
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/chat/completions")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header("x-parent-span-id", uuid::Uuid::new_v4().to_string())
+            .body(Body::from(
+                json!({
+                    "model": "quota-model",
+                    "messages": [{"role": "user", "content": "Hello"}]
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        create_router(state.clone()).oneshot(request).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_reported_rate_limits_exported_as_gauges() {
+        let state = create_state(false);
+
+        let response = send_completion(&state).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let metrics = state.metrics.gather();
+        assert!(metrics.contains(
+            "llm_gateway_provider_rate_limit_remaining{limit_type=\"requests\",model=\"quota-model\",provider=\"low-quota\"} 1"
+        ));
+        assert!(metrics.contains(
+            "llm_gateway_provider_rate_limit_reset_seconds{limit_type=\"requests\",model=\"quota-model\",provider=\"low-quota\"} 2"
+        ));
+
+        // Backoff is opt-in
+        assert!(state.provider_backoff.backoff("low-quota/quota-model").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_low_quota_arms_proactive_backoff() {
+        let state = create_state(true);
+
+        let response = send_completion(&state).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let backoff = state
+            .provider_backoff
+            .backoff("low-quota/quota-model")
+            .expect("low remaining quota should arm backoff");
+        assert!(backoff <= Duration::from_secs(2));
+    }
+}
+
+#[cfg(test)]
+mod payload_size_tests {
+    use super::*;
+    use futures::stream::StreamExt;
+
+    /// State whose provider answers with a ~10 KB body, or five ~1 KB
+    /// chunks when streaming
+    fn create_state() -> AppState {
+        let provider = MockProvider::new("sized", "sized-model")
+            .with_chat(|_, _| Ok(text_response("sized-response", "sized-model", &"a".repeat(10_000))))
+            .with_stream(|_, _| {
+                let chunks = (0..5).map(|_| {
+                    Ok(ChatChunk::builder()
+                        .model("sized-model")
+                        .choice(ChunkChoice::with_content(0, "b".repeat(1_000)))
+                        .build())
+                });
+                Ok(futures::stream::iter(chunks).boxed())
+            });
+        mock_state(&[Arc::new(provider)], |_| {})
+    }
+
+    /// Send a ~2 KB completion request and drain the response body
+    async fn send_completion(state: &AppState, stream: bool) {
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/chat/completions")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header("x-parent-span-id", uuid::Uuid::new_v4().to_string())
+            .body(Body::from(
+                json!({
+                    "model": "sized-model",
+                    "messages": [{"role": "user", "content": "x".repeat(2_000)}],
+                    "stream": stream
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        let response = create_router(state.clone()).oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        response.into_body().collect().await.unwrap();
+    }
+
+    fn bucket(metric: &str, le: &str, count: u64) -> String {
+        format!("{metric}_bucket{{model=\"sized-model\",provider=\"sized\",le=\"{le}\"}} {count}")
+    }
+
+    #[tokio::test]
+    async fn test_non_streaming_payload_sizes_observed() {
+        let state = create_state();
+        send_completion(&state, false).await;
+
+        let metrics = state.metrics.gather();
+        for line in [
+            bucket("llm_gateway_provider_request_bytes", "1024", 0),
+            bucket("llm_gateway_provider_request_bytes", "4096", 1),
+            bucket("llm_gateway_provider_response_bytes", "4096", 0),
+            bucket("llm_gateway_provider_response_bytes", "16384", 1),
+        ] {
+            assert!(metrics.contains(&line), "missing `{line}`");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_streaming_response_bytes_recorded_at_stream_end() {
+        let state = create_state();
+        send_completion(&state, true).await;
+
+        // Five ~1 KB chunks add up to a single ~5 KB observation
+        let metrics = state.metrics.gather();
+        for line in [
+            bucket("llm_gateway_provider_request_bytes", "4096", 1),
+            bucket("llm_gateway_provider_response_bytes", "4096", 0),
+            bucket("llm_gateway_provider_response_bytes", "16384", 1),
+        ] {
+            assert!(metrics.contains(&line), "missing `{line}`");
+        }
+        assert!(metrics.contains(
+            "llm_gateway_provider_response_bytes_count{model=\"sized-model\",provider=\"sized\"} 1"
+        ));
+    }
+}
+
+#[cfg(test)]
+mod json_repair_tests {
+    use super::*;
+    use gateway_core::Usage;
+    use gateway_telemetry::CostTracker;
+
+    /// Provider returning broken JSON for its first `broken_replies` calls
+    fn flaky_json(id: &str, model: &str, broken_replies: u32) -> Arc<MockProvider> {
+        Arc::new(MockProvider::new(id, model).with_chat(move |request, call| {
+            let content = if call < broken_replies {
+                r#"Here you go: {"ok": tru"#
+            } else {
+                r#"{"ok": true}"#
+            };
+            Ok(GatewayResponse::builder()
+                .id(format!("json-response-{call}"))
+                .model(&request.model)
+                .choice(Choice::new(0, content, FinishReason::Stop))
+                .usage(Usage::new(100, 10))
+                .build())
+        }))
+    }
+
+    fn create_state(broken_replies: u32) -> (AppState, Arc<MockProvider>) {
+        let provider = flaky_json("flaky-json", "json-model", broken_replies);
+        (mock_state(std::slice::from_ref(&provider), |_| {}), provider)
+    }
+
+    async fn send_completion(
+        state: &AppState,
+        response_format: Value,
+    ) -> (axum::http::HeaderMap, Value) {
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/chat/completions")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header("x-parent-span-id", uuid::Uuid::new_v4().to_string())
+            .body(Body::from(
+                json!({
+                    "model": "json-model",
+                    "messages": [{"role": "user", "content": "Are you ok? Answer in JSON"}],
+                    "response_format": response_format
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        let response = create_router(state.clone()).oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let headers = response.headers().clone();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (headers, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_invalid_then_valid_json_is_repaired() {
+        let (state, provider) = create_state(2);
+
+        let (headers, json) = send_completion(
+            &state,
+            json!({"type": "json_object", "max_repair_attempts": 3}),
+        )
+        .await;
+
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 3);
+        assert_eq!(headers["x-json-repair-attempts"], "2");
+        assert!(headers.get("x-json-repair").is_none());
+        assert_eq!(
+            json["result"]["choices"][0]["message"]["content"],
+            r#"{"ok": true}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn test_exhausted_repair_attempts_flag_failure() {
+        let (state, provider) = create_state(u32::MAX);
+
+        let (headers, json) = send_completion(
+            &state,
+            json!({"type": "json_object", "max_repair_attempts": 2}),
+        )
+        .await;
+
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 3);
+        assert_eq!(headers["x-json-repair-attempts"], "2");
+        assert_eq!(headers["x-json-repair"], "failed");
+        assert_eq!(json["result"]["id"], "json-response-2");
+    }
+
+    #[tokio::test]
+    async fn test_repair_is_off_by_default() {
+        let (state, provider) = create_state(1);
+
+        let (headers, _) = send_completion(&state, json!({"type": "json_object"})).await;
+
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 1);
+        assert!(headers.get("x-json-repair-attempts").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_repair_is_dispatched_to_repair_model() {
+        let primary = flaky_json("flaky-json", "json-model", u32::MAX);
+        let cheap = flaky_json("cheap-json", "cheap-model", 0);
+        let cost_tracker = Arc::new(CostTracker::with_defaults());
+        let state = mock_state_builder(&[primary.clone(), cheap.clone()], |config| {
+            config.server.json_repair_model = Some("cheap-model".to_string());
+        })
+        .cost_tracker(cost_tracker.clone())
+        .build();
+
+        let (headers, json) = send_completion(
+            &state,
+            json!({"type": "json_object", "max_repair_attempts": 2}),
+        )
+        .await;
+
+        // The expensive model answered once, the repair model fixed it
+        assert_eq!(primary.calls.load(Ordering::SeqCst), 1);
+        assert_eq!(cheap.calls.load(Ordering::SeqCst), 1);
+        assert_eq!(headers["x-json-repair-attempts"], "1");
+        assert!(headers.get("x-json-repair").is_none());
+        let result = &json["result"];
+        assert_eq!(result["model"], "cheap-model");
+        assert_eq!(result["choices"][0]["message"]["content"], r#"{"ok": true}"#);
+
+        // Usage covers both calls, each billed to its own model
+        assert_eq!(result["usage"]["prompt_tokens"], 200);
+        assert_eq!(result["usage"]["completion_tokens"], 20);
+        let mut billed: Vec<_> = cost_tracker
+            .recent_events(10)
+            .await
+            .into_iter()
+            .map(|e| (e.model, e.provider, e.input_tokens, e.output_tokens))
+            .collect();
+        billed.sort();
+        assert_eq!(
+            billed,
+            vec![
+                ("cheap-model".to_string(), "cheap-json".to_string(), 100, 10),
+                ("json-model".to_string(), "flaky-json".to_string(), 100, 10),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_repair_attempts_capped_by_config() {
+        let (state, provider) = create_state(u32::MAX);
+
+        let (headers, _) = send_completion(
+            &state,
+            json!({"type": "json_object", "max_repair_attempts": 50}),
+        )
+        .await;
+
+        let cap = GatewayConfig::default().server.max_json_repair_attempts;
+        assert_eq!(provider.calls.load(Ordering::SeqCst), cap + 1);
+        assert_eq!(headers["x-json-repair-attempts"], cap.to_string().as_str());
+    }
+}
+
+#[cfg(test)]
+mod policy_gate_tests {
+    use super::*;
+    use gateway_integrations::config::PolicyEngineConfig;
+    use gateway_integrations::traits::{
+        EnforcementReport, Policy, PolicyDecision, PolicyViolation,
+    };
+    use gateway_integrations::{IntegrationResult, PolicyConsumer};
+    use gateway_server::PolicyGate;
+    use std::collections::HashMap;
+
+    /// Policy engine denying one model
+    struct DenyModel(&'static str);
+
+    #[async_trait::async_trait]
+    impl PolicyConsumer for DenyModel {
+        async fn evaluate_request(
+            &self,
+            request: &GatewayRequest,
+        ) -> IntegrationResult<PolicyDecision> {
+            let denied = request.model == self.0;
+            Ok(PolicyDecision {
+                allowed: !denied,
+                matched_policies: Vec::new(),
+                violations: if denied {
+                    vec![PolicyViolation {
+                        policy_id: "restricted-models".to_string(),
+                        policy_name: "Restricted models".to_string(),
+                        description: format!("model {} is not approved", self.0),
+                        severity: 90,
+                    }]
+                } else {
+                    Vec::new()
+                },
+                required_actions: Vec::new(),
+                metadata: HashMap::new(),
+            })
+        }
+
+        async fn evaluate_response(
+            &self,
+            request: &GatewayRequest,
+            _: &GatewayResponse,
+        ) -> IntegrationResult<PolicyDecision> {
+            self.evaluate_request(request).await
+        }
+
+        async fn consume_policy_updates(&self) -> IntegrationResult<Vec<Policy>> {
+            Ok(Vec::new())
+        }
+
+        async fn report_enforcement(&self, _: EnforcementReport) -> IntegrationResult<()> {
+            Ok(())
+        }
+    }
+
+    async fn send_completion(model: &str) -> (StatusCode, Value) {
+        let state = mock_state_builder(&[], |_| {})
+            .policy_gate(PolicyGate::new(
+                Arc::new(DenyModel("gpt-4-restricted")),
+                &PolicyEngineConfig::default(),
+            ))
+            .build();
+
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/chat/completions")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header("x-parent-span-id", uuid::Uuid::new_v4().to_string())
+            .body(Body::from(
+                json!({
+                    "model": model,
+                    "messages": [{"role": "user", "content": "Hello"}]
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        let response = create_router(state).oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_denied_request_is_rejected_with_reason() {
+        let (status, json) = send_completion("gpt-4-restricted").await;
+
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(json["error"]["type"], "permission_error");
+        assert!(json["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("model gpt-4-restricted is not approved"));
+    }
+
+    #[tokio::test]
+    async fn test_allowed_request_reaches_routing() {
+        let (status, _) = send_completion("gpt-4o").await;
+
+        // No providers are registered, so routing reports the failure
+        assert_ne!(status, StatusCode::FORBIDDEN);
+    }
+}
+
+#[cfg(test)]
+mod shadow_mirroring_tests {
+    use super::*;
+    use std::time::Instant;
+
+    const PRIMARY_REPLY: &str = "The capital of France is Paris.";
+
+    /// Provider answering every request with a fixed reply
+    fn scripted(id: &str, reply: &'static str, finish_reason: FinishReason) -> MockProvider {
+        let response_id = format!("{id}-response");
+        MockProvider::new(id, "mirror-model").with_chat(move |_, _| {
+            Ok(GatewayResponse::builder()
+                .id(&response_id)
+                .model("mirror-model")
+                .choice(Choice::new(0, reply, finish_reason))
+                .build())
+        })
+    }
+
+    fn create_state(shadow: MockProvider) -> AppState {
+        let primary = scripted("primary", PRIMARY_REPLY, FinishReason::Stop);
+        mock_state_builder(&[Arc::new(primary)], |config| {
+            config.routing.mirroring.enabled = true;
+            config.routing.mirroring.shadow_provider = Some("shadow".to_string());
+            config.routing.mirroring.sample_rate = 1.0;
+        })
+        .providers(mock_registry(&[Arc::new(shadow)]))
+        .build()
+    }
+
+    async fn send_completion(state: &AppState) -> Value {
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/chat/completions")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header("x-parent-span-id", uuid::Uuid::new_v4().to_string())
+            .body(Body::from(
+                json!({
+                    "model": "mirror-model",
+                    "messages": [{"role": "user", "content": "What is the capital of France?"}]
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        let response = create_router(state.clone()).oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    fn series(metric: &str, suffix: &str) -> String {
+        format!("llm_gateway_{metric}{{primary=\"primary\",shadow=\"shadow\"{suffix}}}")
+    }
+
+    /// Wait for the background comparison to land in the metrics
+    async fn recorded_metrics(state: &AppState) -> String {
+        let count = format!("{} 1", series("shadow_token_overlap_count", ""));
+        for _ in 0..100 {
+            let metrics = state.metrics.gather();
+            if metrics.contains(&count) {
+                return metrics;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("shadow diff was not recorded");
+    }
+
+    #[tokio::test]
+    async fn test_identical_shadow_records_full_overlap() {
+        let state = create_state(scripted("shadow", PRIMARY_REPLY, FinishReason::Stop));
+
+        let json = send_completion(&state).await;
+        assert_eq!(json["result"]["id"], "primary-response");
+
+        let metrics = recorded_metrics(&state).await;
+        for line in [
+            format!("{} 0", series("shadow_token_overlap_bucket", ",le=\"0.9\"")),
+            format!("{} 1", series("shadow_token_overlap_sum", "")),
+            format!("{} 0", series("shadow_length_delta_sum", "")),
+            format!("{} 0", series("shadow_finish_reason_mismatches_total", "")),
+        ] {
+            assert!(metrics.contains(&line), "missing `{line}`");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_divergent_shadow_records_low_overlap() {
+        let state = create_state(scripted(
+            "shadow",
+            "I am unable to help with geography questions today, sorry about that",
+            FinishReason::Length,
+        ));
+
+        send_completion(&state).await;
+
+        let metrics = recorded_metrics(&state).await;
+        for line in [
+            format!("{} 1", series("shadow_token_overlap_bucket", ",le=\"0.1\"")),
+            format!("{} 6", series("shadow_length_delta_sum", "")),
+            format!("{} 1", series("shadow_finish_reason_mismatches_total", "")),
+        ] {
+            assert!(metrics.contains(&line), "missing `{line}`");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_slow_shadow_does_not_delay_client() {
+        let shadow = scripted("shadow", PRIMARY_REPLY, FinishReason::Stop)
+            .with_delay(Duration::from_millis(500));
+        let state = create_state(shadow);
+
+        let started = Instant::now();
+        send_completion(&state).await;
+        assert!(started.elapsed() < Duration::from_millis(400));
+        assert!(!state
+            .metrics
+            .gather()
+            .contains(&series("shadow_token_overlap_count", "")));
+
+        recorded_metrics(&state).await;
+    }
+}
+
+#[cfg(test)]
+mod stream_usage_tests {
+    use super::*;
+    use futures::stream::StreamExt;
+    use gateway_core::Usage;
+
+    /// State whose provider streams "Hello world", followed by a usage
+    /// chunk when asked for one and `reports_usage` is set
+    fn create_state(reports_usage: bool, estimate: bool) -> (AppState, Arc<MockProvider>) {
+        let provider = MockProvider::new("usage", "usage-model").with_stream(move |request, _| {
+            let mut chunks = text_chunks("chatcmpl-usage", "usage-model", &["Hello", " world"]);
+            if request.includes_stream_usage() && reports_usage {
+                chunks.push(
+                    ChatChunk::builder()
+                        .id("chatcmpl-usage")
+                        .model("usage-model")
+                        .usage(Usage::new(10, 2))
+                        .build(),
+                );
+            }
+            Ok(futures::stream::iter(chunks.into_iter().map(Ok)).boxed())
+        });
+        let provider = Arc::new(provider);
+        let state = mock_state(std::slice::from_ref(&provider), |config| {
+            config.server.estimate_stream_usage = estimate;
+        });
+        (state, provider)
+    }
+
+    fn requested_usage(provider: &MockProvider) -> bool {
+        provider.requests.lock().last().unwrap().includes_stream_usage()
+    }
+
+    /// Stream a completion and return the JSON chunks sent before `[DONE]`
+    async fn stream_chunks(state: &AppState, stream_options: Option<Value>) -> Vec<Value> {
+        let mut body = json!({
+            "model": "usage-model",
+            "messages": [{"role": "user", "content": "Say hello to the whole world"}],
+            "stream": true
+        });
+        if let Some(options) = stream_options {
+            body["stream_options"] = options;
+        }
+
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/chat/completions")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header("x-parent-span-id", uuid::Uuid::new_v4().to_string())
+            .body(Body::from(body.to_string()))
+            .unwrap();
+
+        let response = create_router(state.clone()).oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        let text = String::from_utf8(bytes.to_vec()).unwrap();
+
+        text.lines()
+            .filter_map(|line| line.strip_prefix("data: "))
+            .take_while(|data| *data != "[DONE]")
+            .map(|data| serde_json::from_str(data).unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_include_usage_emits_terminal_usage_chunk() {
+        let (state, provider) = create_state(true, false);
+        let chunks = stream_chunks(&state, Some(json!({"include_usage": true}))).await;
+
+        assert!(requested_usage(&provider));
+
+        let last = chunks.last().unwrap();
+        assert_eq!(last["choices"], json!([]));
+        assert_eq!(last["id"], "chatcmpl-usage");
+        assert_eq!(last["usage"]["prompt_tokens"], 10);
+        assert_eq!(last["usage"]["completion_tokens"], 2);
+        assert_eq!(last["usage"]["total_tokens"], 12);
+
+        // Only the terminal chunk carries usage
+        assert_eq!(chunks.iter().filter(|c| c.get("usage").is_some()).count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_no_usage_chunk_without_include_usage() {
+        let (state, provider) = create_state(true, true);
+        let chunks = stream_chunks(&state, None).await;
+
+        assert!(!requested_usage(&provider));
+        assert_eq!(chunks.len(), 3);
+        assert!(chunks.iter().all(|c| c.get("usage").is_none()));
+
+        let chunks = stream_chunks(&state, Some(json!({"include_usage": false}))).await;
+        assert!(chunks.iter().all(|c| c.get("usage").is_none()));
+    }
+
+    #[tokio::test]
+    async fn test_usage_estimated_when_provider_reports_none() {
+        let (state, _) = create_state(false, true);
+        let chunks = stream_chunks(&state, Some(json!({"include_usage": true}))).await;
+
+        let last = chunks.last().unwrap();
+        assert_eq!(last["choices"], json!([]));
+        // "Say hello to the whole world" is 28 chars, 7 tokens at ~4 chars
+        // per token, plus role, framing and reply priming; "Hello" and
+        // " world" count two tokens each
+        assert_eq!(last["usage"]["prompt_tokens"], 14);
+        assert_eq!(last["usage"]["completion_tokens"], 4);
+        assert_eq!(last["usage"]["total_tokens"], 18);
+    }
+
+    #[tokio::test]
+    async fn test_no_usage_chunk_when_unreported_and_not_estimated() {
+        let (state, _) = create_state(false, false);
+        let chunks = stream_chunks(&state, Some(json!({"include_usage": true}))).await;
+
+        assert_eq!(chunks.len(), 3);
+        assert!(chunks.iter().all(|c| c.get("usage").is_none()));
+    }
+}
+
+#[cfg(test)]
+mod image_generation_tests {
+    use super::*;
+    use gateway_core::{ImageData, ImageRequest, ImageResponse, ImageResponseFormat};
+
+    /// Returns `n` fake images, as URLs or base64 depending on the request
+    struct MockImages(&'static str);
+
+    #[async_trait::async_trait]
+    impl ImageProvider for MockImages {
+        async fn generate_images(
+            &self,
+            request: &ImageRequest,
+        ) -> Result<ImageResponse, GatewayError> {
+            let data = (0..request.n.unwrap_or(1))
+                .map(|i| match request.response_format {
+                    Some(ImageResponseFormat::B64Json) => ImageData {
+                        b64_json: Some(format!("aW1hZ2Ut{i}")),
+                        ..ImageData::default()
+                    },
+                    _ => ImageData {
+                        url: Some(format!("https://images.example.com/{}/{i}.png", self.0)),
+                        revised_prompt: Some(format!("{} (revised)", request.prompt)),
+                        ..ImageData::default()
+                    },
+                })
+                .collect();
+            Ok(ImageResponse {
+                created: 1_700_000_000,
+                data,
+            })
+        }
+    }
+
+    fn create_state() -> AppState {
+        let images = MockProvider::new("images", "mock-image").with_images(Arc::new(MockImages("images")));
+        let chat_only = MockProvider::new("chat-only", "mock-chat");
+        mock_state_builder(&[], |_| {})
+            .providers(mock_registry(&[Arc::new(images), Arc::new(chat_only)]))
+            .build()
+    }
+
+    async fn generate(body: Value) -> (StatusCode, Value) {
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/images/generations")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+
+        let response = create_router(create_state()).oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_image_urls_returned() {
+        let (status, body) = generate(json!({
+            "model": "mock-image",
+            "prompt": "a red fox",
+            "n": 2,
+            "size": "1024x1024",
+            "quality": "hd"
+        }))
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["created"], 1_700_000_000);
+        let data = body["data"].as_array().unwrap();
+        assert_eq!(data.len(), 2);
+        assert_eq!(data[1]["url"], "https://images.example.com/images/1.png");
+        assert_eq!(data[0]["revised_prompt"], "a red fox (revised)");
+        assert!(data[0].get("b64_json").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_image_b64_returned() {
+        let (status, body) = generate(json!({
+            "model": "mock-image",
+            "prompt": "a red fox",
+            "response_format": "b64_json"
+        }))
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        let data = body["data"].as_array().unwrap();
+        assert_eq!(data.len(), 1);
+        assert_eq!(data[0]["b64_json"], "aW1hZ2Ut0");
+        assert!(data[0].get("url").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_provider_without_image_support_rejected() {
+        let (status, body) = generate(json!({
+            "model": "mock-chat",
+            "prompt": "a red fox"
+        }))
+        .await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"]["code"], "unsupported_capability");
+    }
+
+    #[tokio::test]
+    async fn test_unknown_image_model_not_found() {
+        let (status, _) = generate(json!({
+            "model": "no-such-model",
+            "prompt": "a red fox"
+        }))
+        .await;
+
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_image_request_rejected() {
+        let (status, body) = generate(json!({
+            "model": "mock-image",
+            "prompt": "a red fox",
+            "n": 0
+        }))
+        .await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"]["param"], "n");
+    }
+}
+
+#[cfg(test)]
+mod connection_reset_tests {
+    use super::*;
+    use futures::stream::StreamExt;
+
+    fn create_state(chunks_before_reset: usize) -> (AppState, Arc<MockProvider>) {
+        create_state_with(chunks_before_reset, |_| {})
+    }
+
+    /// State whose provider's first response is cut off by a connection reset
+    ///
+    /// The first stream delivers `chunks_before_reset` chunks and then
+    /// resets; later streams and non-streaming calls succeed.
+    fn create_state_with(
+        chunks_before_reset: usize,
+        configure: impl FnOnce(&mut GatewayConfig),
+    ) -> (AppState, Arc<MockProvider>) {
+        let provider = MockProvider::new("resetting", "reset-model")
+            .with_chat(|_, call| {
+                if call == 0 {
+                    return Err(GatewayError::connection_reset("connection closed mid-body"));
+                }
+                Ok(text_response("chatcmpl-reset", "reset-model", "Hello world"))
+            })
+            .with_stream(move |_, call| {
+                let chunks = text_chunks("chatcmpl-reset", "reset-model", &["Hello", " world"]);
+                if call > 0 {
+                    return Ok(futures::stream::iter(chunks.into_iter().map(Ok)).boxed());
+                }
+                let mut partial: Vec<_> = chunks.into_iter().take(chunks_before_reset).map(Ok).collect();
+                partial.push(Err(GatewayError::connection_reset("connection closed mid-stream")));
+                Ok(futures::stream::iter(partial).boxed())
+            });
+        let provider = Arc::new(provider);
+        (mock_state(std::slice::from_ref(&provider), configure), provider)
+    }
+
+    async fn send(state: &AppState, stream: bool) -> (StatusCode, String) {
+        let body = json!({
+            "model": "reset-model",
+            "messages": [{"role": "user", "content": "Say hello"}],
+            "stream": stream
+        });
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/chat/completions")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header("x-parent-span-id", uuid::Uuid::new_v4().to_string())
+            .body(Body::from(body.to_string()))
+            .unwrap();
+
+        let response = create_router(state.clone()).oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (status, String::from_utf8(bytes.to_vec()).unwrap())
+    }
+
+    fn stream_events(text: &str) -> Vec<Value> {
+        text.lines()
+            .filter_map(|line| line.strip_prefix("data: "))
+            .take_while(|data| *data != "[DONE]")
+            .filter_map(|data| serde_json::from_str(data).ok())
+            .collect()
+    }
+
+    fn streamed_content(events: &[Value]) -> String {
+        events
+            .iter()
+            .filter_map(|e| e["choices"][0]["delta"]["content"].as_str())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_reset_before_first_chunk_restarts_stream() {
+        let (state, provider) = create_state(0);
+        let (status, text) = send(&state, true).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 2);
+
+        let events = stream_events(&text);
+        assert_eq!(streamed_content(&events), "Hello world");
+        assert!(events.iter().all(|e| e.get("error").is_none()));
+    }
+
+    #[tokio::test]
+    async fn test_reset_after_partial_content_is_surfaced() {
+        let (state, provider) = create_state(1);
+        let (status, text) = send(&state, true).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 1);
+
+        let events = stream_events(&text);
+        assert_eq!(streamed_content(&events), "Hello");
+        let error = events.iter().find_map(|e| e.get("error")).unwrap();
+        assert_eq!(error["code"], "connection_reset");
+    }
+
+    #[tokio::test]
+    async fn test_time_to_first_token_excludes_streams_without_tokens() {
+        let (state, _) = create_state(1);
+        send(&state, true).await;
+
+        let metrics = state.metrics.gather();
+        assert!(metrics.contains(
+            "llm_gateway_ttft_seconds_count{model=\"reset-model\",provider=\"resetting\"} 1"
+        ));
+        assert!(!metrics.contains("llm_gateway_stream_no_token_total{"));
+
+        // Without a restart, the reset fails the stream before any token
+        let (state, _) = create_state_with(0, |config| config.server.stream_reset_restarts = 0);
+        send(&state, true).await;
+
+        let metrics = state.metrics.gather();
+        assert!(!metrics.contains("llm_gateway_ttft_seconds_count{"));
+        assert!(metrics.contains(
+            "llm_gateway_stream_no_token_total{model=\"reset-model\",provider=\"resetting\"} 1"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_non_streaming_reset_is_retried() {
+        let (state, provider) = create_state(0);
+        let (status, text) = send(&state, false).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 2);
+        assert!(text.contains("Hello world"));
+    }
+}
+
+#[cfg(test)]
+mod tenant_allowlist_tests {
+    use super::*;
+
+    fn create_state(
+        allowlists: Vec<(&str, Vec<&str>)>,
+    ) -> (AppState, Arc<MockProvider>, Arc<MockProvider>) {
+        let openai = Arc::new(MockProvider::new("openai", "gpt-4o").with_reply("Hello"));
+        let azure = Arc::new(MockProvider::new("azure-eu", "gpt-4o").with_reply("Hello"));
+
+        let state = mock_state(&[openai.clone(), azure.clone()], |config| {
+            config.routing.tenant_provider_allowlists = allowlists
+                .into_iter()
+                .map(|(tenant, ids)| {
+                    (tenant.to_string(), ids.into_iter().map(str::to_string).collect())
+                })
+                .collect();
+        });
+        (state, openai, azure)
+    }
+
+    async fn send(state: &AppState, tenant: &str) -> (StatusCode, Value) {
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/chat/completions")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header("x-parent-span-id", uuid::Uuid::new_v4().to_string())
+            .header("x-tenant-id", tenant)
+            .body(Body::from(
+                json!({
+                    "model": "gpt-4o",
+                    "messages": [{"role": "user", "content": "Hello"}]
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        let response = create_router(state.clone()).oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_tenant_restricted_to_azure_never_routes_to_openai() {
+        let (state, openai, azure) = create_state(vec![("acme-eu", vec!["azure-eu"])]);
+
+        for _ in 0..10 {
+            let (status, _) = send(&state, "acme-eu").await;
+            assert_eq!(status, StatusCode::OK);
+        }
+        assert_eq!(openai.calls.load(Ordering::SeqCst), 0);
+        assert_eq!(azure.calls.load(Ordering::SeqCst), 10);
+
+        // Unrestricted tenants still use both providers
+        for _ in 0..10 {
+            send(&state, "globex").await;
+        }
+        assert!(openai.calls.load(Ordering::SeqCst) > 0);
+    }
+
+    #[tokio::test]
+    async fn test_no_allowed_provider_is_descriptive_error() {
+        let (state, openai, azure) = create_state(vec![("acme-eu", vec!["bedrock-eu"])]);
+
+        let (status, json) = send(&state, "acme-eu").await;
+
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(json["error"]["type"], "permission_error");
+        let message = json["error"]["message"].as_str().unwrap();
+        assert!(message.contains("acme-eu"));
+        assert!(message.contains("gpt-4o"));
+        assert!(message.contains("bedrock-eu"));
+        assert_eq!(openai.calls.load(Ordering::SeqCst), 0);
+        assert_eq!(azure.calls.load(Ordering::SeqCst), 0);
+    }
+}
+
+#[cfg(test)]
+mod deterministic_tests {
+    use super::*;
+    use gateway_config::DeterministicConfig;
+    use gateway_server::deterministic::{OVERRIDES_TAG, SEED_TAG, SESSION_HEADER};
+
+    fn create_state(deterministic: DeterministicConfig) -> (AppState, Arc<MockProvider>) {
+        let provider = Arc::new(MockProvider::new("recording", "gpt-4o").with_reply("Hello"));
+        let state = mock_state(std::slice::from_ref(&provider), |config| {
+            config.routing.deterministic = deterministic;
+        });
+        (state, provider)
+    }
+
+    async fn send(state: &AppState, tenant: &str, session: Option<&str>) -> StatusCode {
+        let mut request = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/chat/completions")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header("x-parent-span-id", uuid::Uuid::new_v4().to_string())
+            .header("x-tenant-id", tenant);
+        if let Some(session) = session {
+            request = request.header(SESSION_HEADER, session);
+        }
+        let request = request
+            .body(Body::from(
+                json!({
+                    "model": "gpt-4o",
+                    "messages": [{"role": "user", "content": "Hello"}],
+                    "temperature": 0.9,
+                    "top_p": 0.5
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        create_router(state.clone())
+            .oneshot(request)
+            .await
+            .unwrap()
+            .status()
+    }
+
+    fn eval_config() -> DeterministicConfig {
+        DeterministicConfig {
+            enabled: true,
+            tenants: vec!["eval".to_string()],
+            allow_session_header: true,
+            seed: 42,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dispatched_request_has_forced_params_and_seed() {
+        let (state, provider) = create_state(eval_config());
+
+        assert_eq!(send(&state, "eval", None).await, StatusCode::OK);
+        assert_eq!(send(&state, "eval", None).await, StatusCode::OK);
+
+        let requests = provider.requests.lock();
+        assert_eq!(requests.len(), 2);
+        for request in requests.iter() {
+            assert_eq!(request.temperature, Some(0.0));
+            assert_eq!(request.top_p, Some(1.0));
+            assert!(request.seed.is_some());
+            let tags = &request.metadata.as_ref().unwrap().tags;
+            assert_eq!(tags[SEED_TAG], request.seed.unwrap().to_string());
+            assert_eq!(tags[OVERRIDES_TAG], "temperature=0.9,top_p=0.5,seed=unset");
+        }
+        // Same tenant, same seed
+        assert_eq!(requests[0].seed, requests[1].seed);
+    }
+
+    #[tokio::test]
+    async fn test_session_header_opts_in_with_own_seed() {
+        let (state, provider) = create_state(eval_config());
+
+        send(&state, "globex", Some("run-1")).await;
+        send(&state, "globex", Some("run-2")).await;
+
+        let requests = provider.requests.lock();
+        assert_eq!(requests[0].temperature, Some(0.0));
+        assert_eq!(requests[1].temperature, Some(0.0));
+        assert_ne!(requests[0].seed, requests[1].seed);
+    }
+
+    #[tokio::test]
+    async fn test_request_unchanged_without_transform() {
+        let (state, provider) = create_state(DeterministicConfig::default());
+
+        assert_eq!(send(&state, "eval", Some("run-1")).await, StatusCode::OK);
+
+        let requests = provider.requests.lock();
+        assert_eq!(requests[0].temperature, Some(0.9));
+        assert_eq!(requests[0].top_p, Some(0.5));
+        assert_eq!(requests[0].seed, None);
+        assert!(requests[0].metadata.is_none());
+    }
+}
+
+#[cfg(test)]
+mod request_cancellation_tests {
+    use super::*;
+    use gateway_server::{auth_middleware, ApiKeyConfig, ApiKeyMetadata, AuthConfig, AuthState};
+
+    async fn create_app() -> (axum::Router, AppState, Arc<MockProvider>) {
+        // Completions run until aborted
+        let provider = Arc::new(
+            MockProvider::new("slow", "reasoning-model").with_delay(Duration::from_secs(30)),
+        );
+        let state = mock_state(std::slice::from_ref(&provider), |_| {});
+
+        let auth_state = AuthState::new(
+            AuthConfig::builder()
+                .api_keys(
+                    ApiKeyConfig::new()
+                        .with_key("key-acme", ApiKeyMetadata::new().with_tenant("acme"))
+                        .with_key("key-globex", ApiKeyMetadata::new().with_tenant("globex"))
+                        .with_key("key-solo", ApiKeyMetadata::new())
+                        .with_key("key-other", ApiKeyMetadata::new()),
+                )
+                .required(true)
+                .build(),
+        )
+        .await
+        .unwrap();
+
+        let app = create_router(state.clone()).layer(axum::middleware::from_fn_with_state(
+            auth_state,
+            auth_middleware,
+        ));
+        (app, state, provider)
+    }
+
+    /// Start a chat request in the background and wait until it is tracked
+    async fn start_request(
+        app: &axum::Router,
+        state: &AppState,
+        request_id: &str,
+        api_key: &str,
+    ) -> tokio::task::JoinHandle<Result<axum::response::Response, std::convert::Infallible>> {
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/chat/completions")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header("x-parent-span-id", uuid::Uuid::new_v4().to_string())
+            .header("x-api-key", api_key)
+            .header("x-request-id", request_id)
+            .body(Body::from(
+                json!({
+                    "model": "reasoning-model",
+                    "messages": [{"role": "user", "content": "Think hard"}]
+                })
+                .to_string(),
+            ))
+            .unwrap();
+        let handle = tokio::spawn(app.clone().oneshot(request));
+
+        for _ in 0..100 {
+            if state.tracker.get_active(request_id).is_some() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert!(state.tracker.get_active(request_id).is_some());
+        handle
+    }
+
+    async fn cancel(app: &axum::Router, request_id: &str, api_key: &str) -> (StatusCode, Value) {
+        cancel_as(app, request_id, api_key, None).await
+    }
+
+    async fn cancel_as(
+        app: &axum::Router,
+        request_id: &str,
+        api_key: &str,
+        tenant: Option<&str>,
+    ) -> (StatusCode, Value) {
+        let mut request = Request::builder()
+            .method(Method::POST)
+            .uri(format!("/v1/requests/{request_id}/cancel"))
+            .header("x-api-key", api_key);
+        if let Some(tenant) = tenant {
+            request = request.header("x-tenant-id", tenant);
+        }
+        let request = request
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_cancel_aborts_in_flight_request() {
+        let (app, state, provider) = create_app().await;
+        let handle = start_request(&app, &state, "req-cancel", "key-acme").await;
+
+        let (status, json) = cancel(&app, "req-cancel", "key-acme").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["id"], "req-cancel");
+        assert_eq!(json["cancelled"], true);
+
+        let response = tokio::time::timeout(Duration::from_secs(5), handle)
+            .await
+            .expect("cancelled request should finish")
+            .unwrap()
+            .unwrap();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let json: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["success"], false);
+        assert_eq!(provider.abandoned.load(Ordering::SeqCst), 1);
+        assert!(state.tracker.get_active("req-cancel").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_unknown_or_foreign_request_is_rejected() {
+        let (app, state, provider) = create_app().await;
+        let handle = start_request(&app, &state, "req-acme", "key-acme").await;
+
+        let (status, json) = cancel(&app, "req-unknown", "key-acme").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(json["error"]["type"], "not_found_error");
+
+        let (status, _) = cancel(&app, "req-acme", "key-globex").await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(provider.abandoned.load(Ordering::SeqCst), 0);
+        assert!(state.tracker.get_active("req-acme").is_some());
+
+        handle.abort();
+    }
+
+    #[tokio::test]
+    async fn test_cancel_ignores_tenant_header() {
+        let (app, state, provider) = create_app().await;
+        let acme = start_request(&app, &state, "req-acme", "key-acme").await;
+        let solo = start_request(&app, &state, "req-solo", "key-solo").await;
+
+        // A credential without a tenant cannot claim one through the header
+        let (status, _) = cancel_as(&app, "req-acme", "key-solo", Some("acme")).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        // Two credentials without a tenant cannot cancel each other's requests
+        let (status, _) = cancel(&app, "req-solo", "key-other").await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(provider.abandoned.load(Ordering::SeqCst), 0);
+
+        let (status, json) = cancel(&app, "req-solo", "key-solo").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["cancelled"], true);
+
+        acme.abort();
+        solo.abort();
+    }
+}
+
+#[cfg(test)]
+mod region_failover_tests {
+    use super::*;
+
+    /// Regional instance that either answers or fails with a 503
+    fn regional(id: &'static str, failing: bool) -> Arc<MockProvider> {
+        Arc::new(MockProvider::new(id, "gpt-4o").with_chat(move |_, _| {
+            if failing {
+                return Err(GatewayError::provider(id, "region unavailable", Some(503), false));
+            }
+            Ok(text_response(&format!("{id}-response"), "gpt-4o", "Hello"))
+        }))
+    }
+
+    /// State with a fast US instance and a slower EU instance
+    fn create_state(us_failing: bool) -> (AppState, Arc<MockProvider>, Arc<MockProvider>) {
+        let us = regional("openai-us", us_failing);
+        let eu = regional("openai-eu", false);
+
+        let router = Router::new(RouterConfig::default().with_region_selection(true));
+        router.register_regional_provider(us.clone(), "us-east-1", 100, 1);
+        router.register_regional_provider(eu.clone(), "eu-west-1", 100, 1);
+        router.update_health("openai-us", HealthStatus::Healthy);
+        router.update_health("openai-eu", HealthStatus::Healthy);
+        router.record_completion("openai-us", Duration::from_millis(60), true);
+        router.record_completion("openai-eu", Duration::from_millis(240), true);
+
+        let state = mock_state_builder(&[], |_| {}).router(router).build();
+        (state, us, eu)
+    }
+
+    async fn send(state: &AppState) -> Value {
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/chat/completions")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header("x-parent-span-id", uuid::Uuid::new_v4().to_string())
+            .body(Body::from(
+                json!({
+                    "model": "gpt-4o",
+                    "messages": [{"role": "user", "content": "Hello"}]
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        let response = create_router(state.clone()).oneshot(request).await.unwrap();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_fastest_region_serves_request() {
+        let (state, us, eu) = create_state(false);
+
+        let json = send(&state).await;
+        assert_eq!(json["success"], true);
+        assert_eq!(us.calls.load(Ordering::SeqCst), 1);
+        assert_eq!(eu.calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_failing_region_fails_over_to_next() {
+        let (state, us, eu) = create_state(true);
+
+        let json = send(&state).await;
+        assert_eq!(json["success"], true);
+        // Retries stay in the failing region before failing over
+        assert!(us.calls.load(Ordering::SeqCst) >= 1);
+        assert_eq!(eu.calls.load(Ordering::SeqCst), 1);
+    }
+}
+
+#[cfg(test)]
+mod overloaded_failover_tests {
+    use super::*;
+    use gateway_resilience::{RetryConfig, RetryPolicy};
+    use gateway_routing::{CooldownConfig, LoadBalancerConfig};
+
+    /// Provider that answers 529 for its first `overloads` calls
+    fn overloaded_for(id: &'static str, overloads: u32) -> Arc<MockProvider> {
+        Arc::new(MockProvider::new(id, "claude-3-5-sonnet").with_chat(move |_, call| {
+            if call < overloads {
+                return Err(GatewayError::provider(id, "Overloaded", Some(529), true));
+            }
+            Ok(text_response(&format!("{id}-response"), "claude-3-5-sonnet", "Hello"))
+        }))
+    }
+
+    /// State routing to `providers` in order, with cooldown enabled
+    fn create_state(providers: &[Arc<MockProvider>]) -> AppState {
+        let cooldown = CooldownConfig::new(Duration::from_secs(60), Duration::from_secs(600));
+        let router = mock_router(
+            RouterConfig::default()
+                .with_default_providers(providers.iter().map(|p| p.id.clone()).collect())
+                .with_load_balancer(LoadBalancerConfig::new().with_cooldown(cooldown)),
+            providers,
+        );
+        mock_state_builder(&[], |_| {})
+            .router(router)
+            .retry_policy(RetryPolicy::new(RetryConfig {
+                base_delay: Duration::from_millis(1),
+                overloaded_base_delay: Duration::from_millis(50),
+                jitter: 0.0,
+                ..RetryConfig::default()
+            }))
+            .build()
+    }
+
+    async fn send(state: &AppState) -> Value {
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/chat/completions")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header("x-parent-span-id", uuid::Uuid::new_v4().to_string())
+            .body(Body::from(
+                json!({
+                    "model": "claude-3-5-sonnet",
+                    "messages": [{"role": "user", "content": "Hello"}]
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        let response = create_router(state.clone()).oneshot(request).await.unwrap();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_overloaded_provider_fails_over_without_retrying() {
+        let overloaded = overloaded_for("anthropic", u32::MAX);
+        let fallback = overloaded_for("bedrock", 0);
+        let state = create_state(&[overloaded.clone(), fallback.clone()]);
+
+        // The overloaded provider is left for the alternate instead of
+        // being retried
+        let json = send(&state).await;
+        assert_eq!(json["success"], true);
+        assert_eq!(overloaded.calls.load(Ordering::SeqCst), 1);
+        assert_eq!(fallback.calls.load(Ordering::SeqCst), 1);
+        let cooldown = state.router.load_balancer().cooldown("anthropic").unwrap();
+        assert!(cooldown.level > 1);
+
+        // Round robin would come back to it, but it is cooling down
+        let json = send(&state).await;
+        assert_eq!(json["success"], true);
+        assert_eq!(overloaded.calls.load(Ordering::SeqCst), 1);
+        assert_eq!(fallback.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_lone_overloaded_provider_is_retried_after_backoff() {
+        let provider = overloaded_for("anthropic", 1);
+        let state = create_state(std::slice::from_ref(&provider));
+
+        let start = std::time::Instant::now();
+        let json = send(&state).await;
+        assert_eq!(json["success"], true);
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 2);
+        assert!(start.elapsed() >= Duration::from_millis(50));
+    }
+}
+
+#[cfg(test)]
+mod token_histogram_tests {
+    use super::*;
+    use futures::stream::StreamExt;
+    use gateway_core::Usage;
+
+    fn usage_for(model: &str) -> Usage {
+        if model == "small-model" {
+            Usage::new(20, 5)
+        } else {
+            Usage::new(3_000, 1_500)
+        }
+    }
+
+    /// State whose provider reports a small usage for `small-model` and a
+    /// large one otherwise
+    fn create_state() -> AppState {
+        let provider = MockProvider::new("usage", "small-model")
+            .with_models(vec![ModelInfo::new("small-model"), ModelInfo::new("large-model")])
+            .with_chat(|request, _| {
+                Ok(GatewayResponse::builder()
+                    .id("usage-response")
+                    .model(&request.model)
+                    .choice(Choice::new(0, "Hello", FinishReason::Stop))
+                    .usage(usage_for(&request.model))
+                    .build())
+            })
+            .with_stream(|request, _| {
+                let chunks = vec![
+                    ChatChunk::builder()
+                        .model(&request.model)
+                        .choice(ChunkChoice::with_content(0, "Hello"))
+                        .build(),
+                    ChatChunk::builder()
+                        .model(&request.model)
+                        .usage(Usage::new(20, 300))
+                        .build(),
+                ];
+                Ok(futures::stream::iter(chunks.into_iter().map(Ok)).boxed())
+            });
+        mock_state(&[Arc::new(provider)], |_| {})
+    }
+
+    async fn send(state: &AppState, model: &str, stream: bool) {
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/chat/completions")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header("x-parent-span-id", uuid::Uuid::new_v4().to_string())
+            .body(Body::from(
+                json!({
+                    "model": model,
+                    "messages": [{"role": "user", "content": "Hello"}],
+                    "stream": stream
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        let response = create_router(state.clone()).oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        // Drain the body so streams run to completion
+        response.into_body().collect().await.unwrap();
+    }
+
+    fn assert_bucket(output: &str, histogram: &str, labels: &str, le: &str, count: u64) {
+        let line = format!("{histogram}_bucket{{{labels},le=\"{le}\"}} {count}");
+        assert!(output.contains(&line), "missing `{line}` in:\n{output}");
+    }
+
+    #[tokio::test]
+    async fn test_token_histograms_per_model() {
+        let state = create_state();
+
+        for _ in 0..3 {
+            send(&state, "small-model", false).await;
+        }
+        send(&state, "large-model", false).await;
+        send(&state, "small-model", true).await;
+
+        let output = state.metrics.gather();
+        let prompt = "llm_gateway_prompt_tokens";
+        let completion = "llm_gateway_completion_tokens";
+
+        // Pre-flight estimates for every request
+        assert_bucket(&output, prompt, "model=\"small-model\",source=\"estimate\"", "16", 4);
+        assert_bucket(&output, prompt, "model=\"large-model\",source=\"estimate\"", "16", 1);
+
+        // Reported usage, including the stream's
+        assert_bucket(&output, prompt, "model=\"small-model\",source=\"usage\"", "16", 0);
+        assert_bucket(&output, prompt, "model=\"small-model\",source=\"usage\"", "32", 4);
+        assert_bucket(&output, prompt, "model=\"large-model\",source=\"usage\"", "2048", 0);
+        assert_bucket(&output, prompt, "model=\"large-model\",source=\"usage\"", "4096", 1);
+
+        // Completion tokens; the streamed 300 are recorded at stream end
+        assert_bucket(&output, completion, "model=\"small-model\"", "16", 3);
+        assert_bucket(&output, completion, "model=\"small-model\"", "256", 3);
+        assert_bucket(&output, completion, "model=\"small-model\"", "512", 4);
+        assert_bucket(&output, completion, "model=\"large-model\"", "1024", 0);
+        assert_bucket(&output, completion, "model=\"large-model\"", "2048", 1);
+    }
+}
+
+#[cfg(test)]
+mod post_processing_tests {
+    use super::*;
+    use gateway_server::postprocess::HEADER;
+
+    const FENCED: &str = "```json\n{\"ok\": true}\n```";
+
+    /// State whose provider answers with JSON wrapped in a markdown fence
+    fn create_state() -> AppState {
+        let provider = MockProvider::new("fenced", "gpt-4o").with_reply(FENCED);
+        mock_state(&[Arc::new(provider)], |config| {
+            config
+                .server
+                .post_processing
+                .tenants
+                .insert("tools".to_string(), vec!["strip_code_fences".to_string()]);
+        })
+    }
+
+    async fn send(
+        state: &AppState,
+        tenant: &str,
+        post_process: Option<&str>,
+    ) -> (StatusCode, serde_json::Value) {
+        let mut request = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/chat/completions")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header("x-parent-span-id", uuid::Uuid::new_v4().to_string())
+            .header("x-tenant-id", tenant);
+        if let Some(post_process) = post_process {
+            request = request.header(HEADER, post_process);
+        }
+        let request = request
+            .body(Body::from(
+                json!({
+                    "model": "gpt-4o",
+                    "messages": [{"role": "user", "content": "Reply in JSON"}]
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        let response = create_router(state.clone()).oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    fn content(json: &serde_json::Value) -> &str {
+        json["result"]["choices"][0]["message"]["content"]
+            .as_str()
+            .unwrap_or_default()
+    }
+
+    #[tokio::test]
+    async fn test_tenant_processor_strips_fences() {
+        let state = create_state();
+
+        let (status, json) = send(&state, "tools", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(content(&json), r#"{"ok": true}"#);
+    }
+
+    #[tokio::test]
+    async fn test_content_untouched_when_disabled() {
+        let state = create_state();
+
+        let (status, json) = send(&state, "other", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(content(&json), FENCED);
+
+        // The header replaces the tenant's processors
+        let (status, json) = send(&state, "tools", Some("none")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(content(&json), FENCED);
+    }
+
+    #[tokio::test]
+    async fn test_header_selects_processor() {
+        let state = create_state();
+
+        let (status, json) = send(&state, "other", Some("strip_code_fences")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(content(&json), r#"{"ok": true}"#);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_processor_is_rejected() {
+        let state = create_state();
+
+        let (status, json) = send(&state, "other", Some("shout")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(json["error"]["code"], "unknown_post_processor");
+    }
+}
+
+#[cfg(test)]
+mod embeddings_tests {
+    use super::*;
+    use gateway_core::{Embedding, EmbeddingRequest, EmbeddingResponse, EmbeddingUsage};
+
+    /// Embeds each text as `[len]`, two inputs per request at most
+    ///
+    /// Sub-batches containing the text `"bad"` fail; ones containing
+    /// `"slow"` take five seconds.
+    #[derive(Default)]
+    struct MockEmbeddings {
+        batch_sizes: parking_lot::Mutex<Vec<usize>>,
+    }
+
+    #[async_trait::async_trait]
+    impl EmbeddingProvider for MockEmbeddings {
+        async fn embed(
+            &self,
+            request: &EmbeddingRequest,
+        ) -> Result<EmbeddingResponse, GatewayError> {
+            let texts = request.input.as_slice();
+            self.batch_sizes.lock().push(texts.len());
+            if texts.iter().any(|text| text == "slow") {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+            }
+            if texts.iter().any(|text| text == "bad") {
+                return Err(GatewayError::provider("embeddings", "rejected input", Some(400), false));
+            }
+
+            let tokens = u32::try_from(texts.len()).unwrap();
+            Ok(EmbeddingResponse {
+                object: "list".to_string(),
+                data: texts
+                    .iter()
+                    .enumerate()
+                    .map(|(i, text)| Embedding::new(i, vec![f32::from(u8::try_from(text.len()).unwrap())]))
+                    .collect(),
+                model: request.model.clone(),
+                usage: EmbeddingUsage {
+                    prompt_tokens: tokens,
+                    total_tokens: tokens,
+                },
+                errors: Vec::new(),
+            })
+        }
+
+        fn max_inputs_per_request(&self) -> usize {
+            2
+        }
+    }
+
+    fn create_state() -> (AppState, Arc<MockEmbeddings>) {
+        create_state_with(|_| {})
+    }
+
+    fn create_state_with(
+        configure: impl FnOnce(&mut GatewayConfig),
+    ) -> (AppState, Arc<MockEmbeddings>) {
+        let embeddings = Arc::new(MockEmbeddings::default());
+        let provider = MockProvider::new("embeddings", "mock-embedding").with_embeddings(embeddings.clone());
+        let state = mock_state_builder(&[], configure)
+            .providers(mock_registry(&[Arc::new(provider)]))
+            .build();
+        (state, embeddings)
+    }
+
+    async fn embed(state: &AppState, input: Value) -> (StatusCode, Value) {
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/embeddings")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(
+                json!({"model": "mock-embedding", "input": input}).to_string(),
+            ))
+            .unwrap();
+
+        let response = create_router(state.clone()).oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_oversized_batch_is_split_and_reassembled() {
+        let (state, provider) = create_state();
+
+        let (status, body) = embed(&state, json!(["a", "bb", "ccc", "dddd", "eeeee"])).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(*provider.batch_sizes.lock(), [2, 2, 1]);
+        let data = body["data"].as_array().unwrap();
+        assert_eq!(data.len(), 5);
+        for (i, item) in data.iter().enumerate() {
+            assert_eq!(item["index"], i);
+            assert_eq!(item["embedding"], json!([(i + 1) as f64]));
+        }
+        assert_eq!(body["usage"], json!({"prompt_tokens": 5, "total_tokens": 5}));
+        assert!(body.get("errors").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_sub_batch_error_reported_per_index() {
+        let (state, _) = create_state();
+
+        let (status, body) = embed(&state, json!(["a", "bb", "bad", "dddd", "eeeee"])).await;
+
+        assert_eq!(status, StatusCode::OK);
+        let indices: Vec<&Value> = body["data"].as_array().unwrap().iter().map(|d| &d["index"]).collect();
+        assert_eq!(indices, [&json!(0), &json!(1), &json!(4)]);
+        let errors = body["errors"].as_array().unwrap();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0]["index"], 2);
+        assert_eq!(errors[1]["index"], 3);
+        assert_eq!(errors[0]["code"], "provider_error");
+        assert_eq!(body["usage"]["prompt_tokens"], 3);
+    }
+
+    #[tokio::test]
+    async fn test_single_input_and_empty_batch() {
+        let (state, provider) = create_state();
+
+        let (status, body) = embed(&state, json!("hello")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"][0]["embedding"], json!([5.0]));
+        assert_eq!(*provider.batch_sizes.lock(), [1]);
+
+        let (status, body) = embed(&state, json!([])).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"]["code"], "invalid_input");
+    }
+
+    #[tokio::test]
+    async fn test_streamed_batch_reports_progress_then_total_usage() {
+        let (state, _) = create_state();
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/embeddings")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(
+                json!({
+                    "model": "mock-embedding",
+                    "input": ["a", "bb", "bad", "dddd", "eeeee"],
+                    "stream": true,
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        let response = create_router(state).oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "text/event-stream");
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        let events: Vec<Value> = String::from_utf8(bytes.to_vec())
+            .unwrap()
+            .lines()
+            .filter_map(|line| line.strip_prefix("data: "))
+            .map(|data| serde_json::from_str(data).unwrap())
+            .collect();
+
+        assert_eq!(events.len(), 4);
+        let progress: Vec<(&Value, &Value, &Value)> = events[..3]
+            .iter()
+            .map(|event| {
+                assert_eq!(event["type"], "chunk");
+                assert_eq!(event["total"], 5);
+                (&event["indices"], &event["completed"], &event["usage"]["prompt_tokens"])
+            })
+            .collect();
+        assert_eq!(
+            progress,
+            [
+                (&json!({"start": 0, "end": 2}), &json!(2), &json!(2)),
+                (&json!({"start": 2, "end": 4}), &json!(4), &json!(2)),
+                (&json!({"start": 4, "end": 5}), &json!(5), &json!(3)),
+            ]
+        );
+        assert_eq!(events[0]["data"][1]["embedding"], json!([2.0]));
+        assert_eq!(events[1]["errors"][0]["index"], 2);
+
+        assert_eq!(events[3]["type"], "done");
+        assert_eq!(events[3]["usage"], json!({"prompt_tokens": 3, "total_tokens": 3}));
+        assert_eq!(events[3]["embedded"], 3);
+        assert_eq!(events[3]["failed"], 2);
+    }
+
+    fn stream_request(input: &Value) -> Request<Body> {
+        Request::builder()
+            .method(Method::POST)
+            .uri("/v1/embeddings")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(
+                json!({"model": "mock-embedding", "input": input, "stream": true}).to_string(),
+            ))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_streamed_batch_bounded_by_duration_and_stream_limit() {
+        let (state, _) = create_state_with(|config| {
+            config.server.max_stream_duration = Duration::from_millis(200);
+            config.server.max_concurrent_streams_per_tenant = Some(1);
+        });
+        let app = create_router(state);
+
+        let slow = app.clone().oneshot(stream_request(&json!(["slow"]))).await.unwrap();
+        assert_eq!(slow.status(), StatusCode::OK);
+
+        // The open stream holds the only slot
+        let refused = app.clone().oneshot(stream_request(&json!(["a"]))).await.unwrap();
+        assert_eq!(refused.status(), StatusCode::TOO_MANY_REQUESTS);
+
+        let bytes = tokio::time::timeout(Duration::from_secs(2), slow.into_body().collect())
+            .await
+            .expect("stream should end at the max duration")
+            .unwrap()
+            .to_bytes();
+        let events: Vec<Value> = String::from_utf8(bytes.to_vec())
+            .unwrap()
+            .lines()
+            .filter_map(|line| line.strip_prefix("data: "))
+            .map(|data| serde_json::from_str(data).unwrap())
+            .collect();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0]["error"]["type"], "stream_error");
+
+        // The slot is released once the stream ends
+        let next = app.oneshot(stream_request(&json!(["a"]))).await.unwrap();
+        assert_eq!(next.status(), StatusCode::OK);
+    }
+}
+
+// ============================================================================
+// Response Content Hash Tests
+// ============================================================================
+
+mod response_hash_tests {
+    use super::*;
+    use futures::stream::StreamExt;
+    use gateway_server::response_hash::HEADER;
+
+    /// State whose provider answers "Hello world", streamed in pieces, under
+    /// a new response ID every time
+    fn create_state(enabled: bool) -> AppState {
+        let provider = MockProvider::new("hello", "gpt-4o")
+            .with_chat(|_, call| Ok(text_response(&format!("hello-{call}"), "gpt-4o", "Hello world")))
+            .with_stream(|_, call| {
+                let chunks = text_chunks(&format!("hello-{call}"), "gpt-4o", &["Hello", " world"]);
+                Ok(futures::stream::iter(chunks.into_iter().map(Ok)).boxed())
+            });
+        mock_state(&[Arc::new(provider)], |config| config.server.response_hash = enabled)
+    }
+
+    async fn send(state: &AppState, stream: bool) -> axum::response::Response {
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/chat/completions")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header("x-parent-span-id", uuid::Uuid::new_v4().to_string())
+            .body(Body::from(
+                json!({
+                    "model": "gpt-4o",
+                    "messages": [{"role": "user", "content": "Say hello"}],
+                    "stream": stream
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        let response = create_router(state.clone()).oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        response
+    }
+
+    async fn header_hash(state: &AppState) -> Option<String> {
+        let response = send(state, false).await;
+        response
+            .headers()
+            .get(HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    }
+
+    /// The `execution_output` event's data from a streamed completion
+    async fn streamed_execution_output(state: &AppState) -> Value {
+        let response = send(state, true).await;
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        let text = String::from_utf8(bytes.to_vec()).unwrap();
+
+        text.split("\n\n")
+            .find(|event| event.contains("event: execution_output"))
+            .and_then(|event| event.lines().find_map(|line| line.strip_prefix("data: ")))
+            .map(|data| serde_json::from_str(data).unwrap())
+            .unwrap_or_default()
+    }
+
+    #[tokio::test]
+    async fn test_identical_content_yields_identical_hash() {
+        let state = create_state(true);
+
+        let first = header_hash(&state).await.unwrap();
+        let second = header_hash(&state).await.unwrap();
+
+        assert_eq!(first.len(), 64);
+        assert_eq!(first, second);
+    }
+
+    #[tokio::test]
+    async fn test_streaming_hash_matches_non_streaming() {
+        let state = create_state(true);
+
+        let hash = header_hash(&state).await.unwrap();
+        let output = streamed_execution_output(&state).await;
+
+        assert_eq!(output["success"], true);
+        assert_eq!(output["result"]["response_hash"], hash.as_str());
+    }
+
+    #[tokio::test]
+    async fn test_no_hash_when_disabled() {
+        let state = create_state(false);
+
+        assert!(header_hash(&state).await.is_none());
+        let output = streamed_execution_output(&state).await;
+        assert_eq!(output["success"], true);
+        assert!(output["result"].is_null());
+    }
+}
+
+// ============================================================================
+// API Key Expiry Warning Tests
+// ============================================================================
+
+mod key_expiry_warning_tests {
+    use super::*;
+    use gateway_server::auth::EXPIRES_IN_HEADER;
+    use gateway_server::{auth_middleware, ApiKeyConfig, ApiKeyMetadata, AuthConfig, AuthState};
+
+    async fn create_app() -> axum::Router {
+        let now = chrono::Utc::now();
+        let auth_state = AuthState::new(
+            AuthConfig::builder()
+                .api_keys(
+                    ApiKeyConfig::new()
+                        .with_expiry_warning(Duration::from_secs(7 * 86_400))
+                        .with_key(
+                            "key-expiring",
+                            ApiKeyMetadata::new()
+                                .with_tenant("acme")
+                                .with_expiration(now + chrono::Duration::days(2)),
+                        )
+                        .with_key(
+                            "key-fresh",
+                            ApiKeyMetadata::new()
+                                .with_tenant("acme")
+                                .with_expiration(now + chrono::Duration::days(90)),
+                        )
+                        .with_key("key-permanent", ApiKeyMetadata::new().with_tenant("acme")),
+                )
+                .required(true)
+                .build(),
+        )
+        .await
+        .unwrap();
+
+        create_router(create_test_state()).layer(axum::middleware::from_fn_with_state(
+            auth_state,
+            auth_middleware,
+        ))
+    }
+
+    async fn expires_in(app: &axum::Router, api_key: &str) -> Option<u64> {
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri("/v1/models")
+            .header("x-api-key", api_key)
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        response
+            .headers()
+            .get(EXPIRES_IN_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse().ok())
+    }
+
+    #[tokio::test]
+    async fn test_key_expiring_within_window_gets_warning() {
+        let app = create_app().await;
+
+        let seconds = expires_in(&app, "key-expiring").await.unwrap();
+        assert!(seconds > 86_400 && seconds <= 2 * 86_400);
+    }
+
+    #[tokio::test]
+    async fn test_key_far_from_expiry_has_no_warning() {
+        let app = create_app().await;
+
+        assert_eq!(expires_in(&app, "key-fresh").await, None);
+        assert_eq!(expires_in(&app, "key-permanent").await, None);
+    }
+}
+
+#[cfg(test)]
+mod batch_tests {
+    use super::*;
+    use gateway_core::{MessageContent, Usage};
+    use gateway_telemetry::CostTracker;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    /// Chat-only provider without a batch endpoint
+    ///
+    /// Requests whose last message is `"fail"` are rejected.
+    fn chat_only() -> Arc<MockProvider> {
+        Arc::new(MockProvider::new("chat-only", "mock-chat").with_chat(|request, _| {
+            let prompt = match request.messages.last().map(|m| &m.content) {
+                Some(MessageContent::Text(text)) => text.clone(),
+                _ => String::new(),
+            };
+            if prompt == "fail" {
+                return Err(GatewayError::provider("chat-only", "rejected", Some(400), false));
+            }
+            Ok(GatewayResponse::builder()
+                .id(format!("resp-{prompt}"))
+                .model("mock-chat")
+                .choice(Choice::new(0, format!("echo {prompt}"), FinishReason::Stop))
+                .usage(Usage::new(3, 2))
+                .build())
+        }))
+    }
+
+    fn create_state<P: LLMProvider>(provider: Arc<P>, costs: Arc<CostTracker>) -> AppState {
+        mock_state_builder(&[], |_| {})
+            .router(mock_router(RouterConfig::default(), &[provider]))
+            .cost_tracker(costs)
+            .build()
+    }
+
+    fn batch(model: &str, prompts: &[&str]) -> Value {
+        let requests: Vec<Value> = prompts
+            .iter()
+            .enumerate()
+            .map(|(i, prompt)| {
+                json!({
+                    "custom_id": format!("req-{i}"),
+                    "body": {"model": model, "messages": [{"role": "user", "content": prompt}]}
+                })
+            })
+            .collect();
+        json!({ "requests": requests })
+    }
+
+    async fn send(
+        state: &AppState,
+        method: Method,
+        uri: &str,
+        tenant: &str,
+        body: Option<Value>,
+    ) -> (StatusCode, Value) {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("x-tenant-id", tenant)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(body.map_or_else(Body::empty, |body| Body::from(body.to_string())))
+            .unwrap();
+
+        let response = create_router(state.clone()).oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    /// Poll until the batch reaches a terminal state
+    async fn wait_for_batch(state: &AppState, id: &str, tenant: &str) -> Value {
+        for _ in 0..100 {
+            let (status, body) =
+                send(state, Method::GET, &format!("/v1/batches/{id}"), tenant, None).await;
+            assert_eq!(status, StatusCode::OK);
+            if body["status"] == "completed" {
+                return body;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("batch {id} did not complete");
+    }
+
+    async fn mount_openai_batch_api(server: &MockServer) {
+        Mock::given(method("POST"))
+            .and(path("/v1/files"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"id": "file-in"})))
+            .expect(1)
+            .mount(server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/batches"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({"id": "batch_upstream", "status": "validating"})),
+            )
+            .expect(1)
+            .mount(server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/batches/batch_upstream"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": "batch_upstream",
+                "status": "in_progress",
+                "request_counts": {"total": 2, "completed": 1, "failed": 0}
+            })))
+            .up_to_n_times(2)
+            .mount(server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/batches/batch_upstream"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": "batch_upstream",
+                "status": "completed",
+                "request_counts": {"total": 2, "completed": 2, "failed": 0},
+                "output_file_id": "file-out"
+            })))
+            .mount(server)
+            .await;
+
+        let output: String = ["req-0", "req-1"]
+            .iter()
+            .map(|custom_id| {
+                let line = json!({
+                    "custom_id": custom_id,
+                    "response": {"status_code": 200, "body": {
+                        "id": format!("chatcmpl-{custom_id}"),
+                        "object": "chat.completion",
+                        "created": 1,
+                        "model": "gpt-4o",
+                        "choices": [{"index": 0, "message": {"role": "assistant", "content": "ok"}, "finish_reason": "stop"}],
+                        "usage": {"prompt_tokens": 10, "completion_tokens": 5, "total_tokens": 15}
+                    }}
+                });
+                format!("{line}\n")
+            })
+            .collect();
+        Mock::given(method("GET"))
+            .and(path("/v1/files/file-out/content"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(output))
+            .expect(1)
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_native_batch_submit_poll_and_retrieve() {
+        let server = MockServer::start().await;
+        mount_openai_batch_api(&server).await;
+        let provider = OpenAIProvider::new(
+            OpenAIConfig::new("openai", "sk-test")
+                .with_base_url(server.uri())
+                .with_models(vec![ModelInfo::new("gpt-4o")]),
+        )
+        .expect("provider");
+        let costs = Arc::new(CostTracker::with_defaults());
+        let state = create_state(Arc::new(provider), costs.clone());
+
+        let (status, created) = send(
+            &state,
+            Method::POST,
+            "/v1/batches",
+            "acme",
+            Some(batch("gpt-4o", &["a", "b"])),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(created["mode"], "native");
+        assert_eq!(created["provider"], "openai");
+        assert_eq!(created["request_counts"]["total"], 2);
+        let id = created["id"].as_str().unwrap();
+        let results_uri = format!("/v1/batches/{id}/results");
+
+        let (status, body) =
+            send(&state, Method::GET, &format!("/v1/batches/{id}"), "acme", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["status"], "in_progress");
+        assert_eq!(body["request_counts"]["completed"], 1);
+
+        let (status, body) = send(&state, Method::GET, &results_uri, "acme", None).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(body["error"]["code"], "batch_not_completed");
+
+        let body = wait_for_batch(&state, id, "acme").await;
+        assert_eq!(body["request_counts"]["completed"], 2);
+        assert_eq!(body["usage"]["total_tokens"], 30);
+
+        let (status, body) = send(&state, Method::GET, &results_uri, "acme", None).await;
+        assert_eq!(status, StatusCode::OK);
+        let data = body["data"].as_array().unwrap();
+        assert_eq!(data.len(), 2);
+        assert_eq!(data[0]["custom_id"], "req-0");
+        assert_eq!(data[1]["response"]["choices"][0]["message"]["content"], "ok");
+
+        // Billed once, to the submitting tenant
+        let stats = costs.tenant_stats("acme").await.expect("tenant usage");
+        assert_eq!(stats.total_requests, 2);
+        assert_eq!(stats.total_input_tokens, 20);
+        assert_eq!(stats.total_output_tokens, 10);
+    }
+
+    #[tokio::test]
+    async fn test_emulated_batch_for_provider_without_batch_endpoint() {
+        let provider = chat_only();
+        let costs = Arc::new(CostTracker::with_defaults());
+        let state = create_state(provider.clone(), costs.clone());
+
+        let (status, created) = send(
+            &state,
+            Method::POST,
+            "/v1/batches",
+            "acme",
+            Some(batch("mock-chat", &["one", "fail", "three"])),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(created["mode"], "emulated");
+        let id = created["id"].as_str().unwrap();
+
+        let body = wait_for_batch(&state, id, "acme").await;
+        assert_eq!(
+            body["request_counts"],
+            json!({"total": 3, "completed": 2, "failed": 1})
+        );
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 3);
+
+        let (status, body) =
+            send(&state, Method::GET, &format!("/v1/batches/{id}/results"), "acme", None).await;
+        assert_eq!(status, StatusCode::OK);
+        let data = body["data"].as_array().unwrap();
+        let ids: Vec<&Value> = data.iter().map(|r| &r["custom_id"]).collect();
+        assert_eq!(ids, [&json!("req-0"), &json!("req-1"), &json!("req-2")]);
+        assert_eq!(data[0]["response"]["choices"][0]["message"]["content"], "echo one");
+        assert_eq!(data[1]["error"]["code"], "provider_error");
+
+        let stats = costs.tenant_stats("acme").await.expect("tenant usage");
+        assert_eq!(stats.successful_requests, 2);
+        assert_eq!(stats.failed_requests, 1);
+        assert_eq!(stats.total_input_tokens, 6);
+    }
+
+    #[tokio::test]
+    async fn test_batch_hidden_from_other_tenants() {
+        let provider = chat_only();
+        let state = create_state(provider, Arc::new(CostTracker::with_defaults()));
+
+        let (_, created) = send(
+            &state,
+            Method::POST,
+            "/v1/batches",
+            "acme",
+            Some(batch("mock-chat", &["one"])),
+        )
+        .await;
+        let id = created["id"].as_str().unwrap();
+
+        for uri in [format!("/v1/batches/{id}"), format!("/v1/batches/{id}/results")] {
+            let (status, _) = send(&state, Method::GET, &uri, "globex", None).await;
+            assert_eq!(status, StatusCode::NOT_FOUND);
+        }
+
+        let (status, body) = send(
+            &state,
+            Method::POST,
+            "/v1/batches",
+            "acme",
+            Some(json!({"requests": [
+                {"custom_id": "a", "body": {"model": "mock-chat", "messages": [{"role": "user", "content": "x"}]}},
+                {"custom_id": "b", "body": {"model": "gpt-4o", "messages": [{"role": "user", "content": "y"}]}}
+            ]})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"]["code"], "mixed_batch_models");
+    }
+}
+
+// ============================================================================
+// Request Trace Tests
+// ============================================================================
+
+mod request_trace_tests {
+    use super::*;
+    use gateway_core::Usage;
+    use gateway_resilience::{RetryConfig, RetryPolicy};
+    use gateway_server::{auth_middleware, ApiKeyConfig, ApiKeyMetadata, AuthConfig, AuthState};
+    use gateway_telemetry::{CostTracker, ModelPricing};
+
+    async fn create_app() -> axum::Router {
+        // Unavailable for the first two calls
+        let provider = MockProvider::new("flaky", "mock-model").with_chat(|_, call| {
+            if call < 2 {
+                return Err(GatewayError::provider("flaky", "bad gateway", Some(502), true));
+            }
+            Ok(GatewayResponse::builder()
+                .id("resp-1")
+                .model("mock-model")
+                .choice(Choice::new(0, "hello", FinishReason::Stop))
+                .usage(Usage::new(1000, 500))
+                .build())
+        });
+
+        let costs = Arc::new(CostTracker::with_defaults());
+        costs
+            .register_pricing(ModelPricing::new("mock-model", "flaky").with_pricing(0.01, 0.02))
+            .await;
+
+        let state = mock_state_builder(&[Arc::new(provider)], |_| {})
+            .retry_policy(RetryPolicy::new(RetryConfig {
+                max_retries: 3,
+                base_delay: Duration::from_millis(1),
+                jitter: 0.0,
+                ..RetryConfig::default()
+            }))
+            .cost_tracker(costs)
+            .build();
+
+        let auth_state = AuthState::new(
+            AuthConfig::builder()
+                .api_keys(
+                    ApiKeyConfig::new()
+                        .with_key(
+                            "key-admin",
+                            ApiKeyMetadata::new().with_scopes(vec!["gateway:admin".to_string()]),
+                        )
+                        .with_key("sk-user-secret", ApiKeyMetadata::new().with_tenant("acme")),
+                )
+                .required(true)
+                .build(),
+        )
+        .await
+        .unwrap();
+
+        create_router(state).layer(axum::middleware::from_fn_with_state(
+            auth_state,
+            auth_middleware,
+        ))
+    }
+
+    async fn get_trace(app: &axum::Router, request_id: &str, api_key: &str) -> (StatusCode, Value) {
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri(format!("/admin/requests/{request_id}/trace"))
+            .header("x-api-key", api_key)
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_completed_request_trace_has_routing_retries_and_cost() {
+        let app = create_app().await;
+
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/chat/completions")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header("x-api-key", "sk-user-secret")
+            .header("x-request-id", "trace-req-1")
+            .header("x-parent-span-id", uuid::Uuid::new_v4().to_string())
+            .body(Body::from(
+                json!({"model": "mock-model", "messages": [{"role": "user", "content": "hi"}]})
+                    .to_string(),
+            ))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let (status, trace) = get_trace(&app, "trace-req-1", "key-admin").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(trace["object"], "request.trace");
+        assert_eq!(trace["status"], "completed");
+        assert_eq!(trace["auth"]["method"], "api_key");
+        assert_eq!(trace["auth"]["tenant_id"], "acme");
+        assert_eq!(trace["routing"]["provider"], "flaky");
+        assert_eq!(trace["attempts"][0]["calls"], 3);
+        assert_eq!(trace["retries"], 2);
+        assert_eq!(trace["cost"]["input_tokens"], 1000);
+        // 1k input at $0.01 plus 0.5k output at $0.02
+        let cost = trace["cost"]["cost_usd"].as_f64().unwrap();
+        assert!((cost - 0.02).abs() < 1e-9);
+        assert!(!trace.to_string().contains("sk-user-secret"));
+    }
+
+    #[tokio::test]
+    async fn test_trace_requires_admin_scope() {
+        let app = create_app().await;
+
+        let (status, _) = get_trace(&app, "unknown", "sk-user-secret").await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        let (status, body) = get_trace(&app, "unknown", "key-admin").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["error"]["code"], "trace_not_found");
+    }
+}
+
+#[cfg(test)]
+mod model_defaults_tests {
+    use super::*;
+    use gateway_config::ModelDefaults;
+    use gateway_server::model_defaults::DEFAULTS_TAG;
+    use std::collections::HashMap;
+
+    fn create_state() -> (AppState, Arc<MockProvider>) {
+        let provider = Arc::new(MockProvider::new("recording", "gpt-4o").with_reply("Hello"));
+        let state = mock_state(std::slice::from_ref(&provider), |config| {
+            config.routing.model_defaults = HashMap::from([(
+                "gpt-4*".to_string(),
+                ModelDefaults {
+                    temperature: Some(0.2),
+                    ..ModelDefaults::default()
+                },
+            )]);
+        });
+        (state, provider)
+    }
+
+    async fn send(state: &AppState, body: Value) -> StatusCode {
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/chat/completions")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header("x-parent-span-id", uuid::Uuid::new_v4().to_string())
+            .body(Body::from(body.to_string()))
+            .unwrap();
+
+        create_router(state.clone())
+            .oneshot(request)
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn test_omitted_temperature_filled_from_model_default() {
+        let (state, provider) = create_state();
+
+        let status = send(
+            &state,
+            json!({
+                "model": "gpt-4o",
+                "messages": [{"role": "user", "content": "Hello"}]
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+
+        let requests = provider.requests.lock();
+        assert_eq!(requests[0].temperature, Some(0.2));
+        let tags = &requests[0].metadata.as_ref().unwrap().tags;
+        assert_eq!(tags[DEFAULTS_TAG], "temperature=0.2");
+    }
+
+    #[tokio::test]
+    async fn test_explicit_temperature_preserved() {
+        let (state, provider) = create_state();
+
+        let status = send(
+            &state,
+            json!({
+                "model": "gpt-4o",
+                "messages": [{"role": "user", "content": "Hello"}],
+                "temperature": 0.9
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+
+        let requests = provider.requests.lock();
+        assert_eq!(requests[0].temperature, Some(0.9));
+        assert!(requests[0].metadata.is_none());
+    }
+}
+
+#[cfg(test)]
+mod passthrough_tests {
+    use super::*;
+    use gateway_routing::rules::{ModelTransform, RuleAction, RuleMatcher, RoutingRule};
+    use gateway_telemetry::CostTracker;
+
+    const CONTENT: &str = "data: {\"id\":\"chatcmpl-1\",\"object\":\"chat.completion.chunk\",\"created\":1,\"model\":\"gpt-4o-2024-08-06\",\"system_fingerprint\":\"fp_1\",\"choices\":[{\"index\":0,\"delta\":{\"role\":\"assistant\",\"content\":\"Hello\"},\"logprobs\":null,\"finish_reason\":null}]}\n\n";
+    const FINISH: &str = "data: {\"id\":\"chatcmpl-1\",\"object\":\"chat.completion.chunk\",\"created\":1,\"model\":\"gpt-4o-2024-08-06\",\"system_fingerprint\":\"fp_1\",\"choices\":[{\"index\":0,\"delta\":{},\"logprobs\":null,\"finish_reason\":\"stop\"}]}\n\n";
+    const USAGE: &str = "data: {\"id\":\"chatcmpl-1\",\"object\":\"chat.completion.chunk\",\"created\":1,\"model\":\"gpt-4o-2024-08-06\",\"system_fingerprint\":\"fp_1\",\"choices\":[],\"usage\":{\"prompt_tokens\":12,\"completion_tokens\":2,\"total_tokens\":14}}\n\n";
+    const DONE: &str = "data: [DONE]\n\n";
+
+    fn create_state() -> (AppState, Arc<MockProvider>) {
+        create_state_with(false)
+    }
+
+    /// State whose provider replays a fixed SSE body, split mid-event, or
+    /// fails the body before its first event
+    fn create_state_with(fail: bool) -> (AppState, Arc<MockProvider>) {
+        let provider = MockProvider::new("sse", "gpt-4o")
+            .with_provider_type(ProviderType::OpenAI)
+            .with_models(vec![ModelInfo::new("gpt-4o").with_alias("fast")])
+            .with_raw_stream(move |_| {
+                if fail {
+                    let reset = GatewayError::provider("sse", "connection reset", None, true);
+                    return Ok(Box::pin(futures::stream::iter(vec![Err(reset)])));
+                }
+                let body = format!("{CONTENT}{FINISH}{USAGE}{DONE}");
+                let pieces: Vec<Result<bytes::Bytes, GatewayError>> = body
+                    .as_bytes()
+                    .chunks(37)
+                    .map(|piece| Ok(bytes::Bytes::copy_from_slice(piece)))
+                    .collect();
+                Ok(Box::pin(futures::stream::iter(pieces)))
+            });
+        let provider = Arc::new(provider);
+
+        let state = mock_state_builder(std::slice::from_ref(&provider), |_| {})
+            .cost_tracker(Arc::new(CostTracker::with_defaults()))
+            .build();
+        state.router.add_rule(
+            RoutingRule::new("passthrough", "Passthrough")
+                .with_matcher(RuleMatcher::new().with_model("*"))
+                .with_action(
+                    RuleAction::new()
+                        .with_model_transform(ModelTransform::Replace {
+                            value: "gpt-4o".to_string(),
+                        })
+                        .with_stream_passthrough(true),
+                ),
+        );
+        (state, provider)
+    }
+
+    async fn stream(state: &AppState, model: &str, include_usage: bool) -> (StatusCode, String) {
+        let mut body = json!({
+            "model": model,
+            "messages": [{"role": "user", "content": "Hello"}],
+            "stream": true
+        });
+        if include_usage {
+            body["stream_options"] = json!({"include_usage": true});
+        }
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/chat/completions")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header("x-parent-span-id", uuid::Uuid::new_v4().to_string())
+            .header("x-request-id", format!("req-{model}"))
+            .body(Body::from(body.to_string()))
+            .unwrap();
+
+        let response = create_router(state.clone()).oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_passthrough_delivers_upstream_body_verbatim() {
+        let (state, provider) = create_state();
+
+        let (status, body) = stream(&state, "gpt-4o", true).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, format!("{CONTENT}{FINISH}{USAGE}{DONE}"));
+        // Usage is always requested upstream
+        let requests = provider.requests.lock();
+        assert!(requests[0].includes_stream_usage());
+    }
+
+    #[tokio::test]
+    async fn test_passthrough_rewrites_aliased_model_only() {
+        let (state, provider) = create_state();
+
+        let (status, body) = stream(&state, "fast", false).await;
+
+        assert_eq!(status, StatusCode::OK);
+        let expected = format!("{CONTENT}{FINISH}{DONE}").replace("gpt-4o-2024-08-06", "fast");
+        assert_eq!(body, expected);
+        assert_eq!(provider.requests.lock()[0].model, "gpt-4o");
+    }
+
+    #[tokio::test]
+    async fn test_passthrough_usage_is_billed() {
+        let (state, _) = create_state();
+
+        // Usage is billed even though the client did not ask to see it
+        let (status, _) = stream(&state, "fast", false).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let events = state.cost_tracker.request_events("req-fast").await;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].input_tokens, 12);
+        assert_eq!(events[0].output_tokens, 2);
+    }
+
+    #[tokio::test]
+    async fn test_passthrough_records_time_to_first_token() {
+        let (state, _) = create_state();
+        stream(&state, "gpt-4o", false).await;
+
+        let metrics = state.metrics.gather();
+        assert!(metrics.contains("llm_gateway_ttft_seconds_count{model=\"gpt-4o\",provider=\"sse\"} 1"));
+        assert!(!metrics.contains("llm_gateway_stream_no_token_total{"));
+
+        let (state, _) = create_state_with(true);
+        let (status, body) = stream(&state, "gpt-4o", false).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.contains("stream_error"));
+
+        let metrics = state.metrics.gather();
+        assert!(!metrics.contains("llm_gateway_ttft_seconds_count{"));
+        assert!(metrics.contains(
+            "llm_gateway_stream_no_token_total{model=\"gpt-4o\",provider=\"sse\"} 1"
+        ));
+    }
+}
+
+// ============================================================================
+// Lifecycle Webhook Tests
+// ============================================================================
+
+mod webhook_tests {
+    use super::*;
+    use gateway_core::Usage;
+    use gateway_integrations::webhooks::{sign, SIGNATURE_HEADER};
+    use gateway_integrations::{
+        BackgroundConfig, BackgroundRunner, WebhookEmitter, WebhookEndpointConfig,
+        WebhookEventType, WebhooksConfig,
+    };
+    use wiremock::matchers::method;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const SECRET: &str = "whsec_e2e";
+
+    fn create_state(receiver: &MockServer, events: Vec<WebhookEventType>) -> AppState {
+        let provider = MockProvider::new("completing", "gpt-4o").with_chat(|_, _| {
+            Ok(GatewayResponse::builder()
+                .id("completing-response")
+                .model("gpt-4o")
+                .choice(Choice::new(0, "Hello", FinishReason::Stop))
+                .usage(Usage::new(7, 3))
+                .build())
+        });
+
+        let runner = Arc::new(BackgroundRunner::new(BackgroundConfig {
+            retry_backoff: Duration::from_millis(10),
+            ..BackgroundConfig::default()
+        }));
+        let emitter = WebhookEmitter::new(
+            WebhooksConfig {
+                enabled: true,
+                endpoints: vec![WebhookEndpointConfig {
+                    url: receiver.uri(),
+                    secret: SECRET.to_string(),
+                    events,
+                }],
+                ..WebhooksConfig::default()
+            },
+            runner,
+        )
+        .unwrap();
+
+        mock_state_builder(&[Arc::new(provider)], |_| {})
+            .webhooks(Arc::new(emitter))
+            .build()
+    }
+
+    async fn complete(state: &AppState) {
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/chat/completions")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header("x-parent-span-id", uuid::Uuid::new_v4().to_string())
+            .header("x-request-id", "req-webhook")
+            .body(Body::from(
+                json!({
+                    "model": "gpt-4o",
+                    "messages": [{"role": "user", "content": "Hello"}]
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        let response = create_router(state.clone()).oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_completed_request_delivers_signed_event() {
+        let receiver = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&receiver)
+            .await;
+        let state = create_state(&receiver, vec![WebhookEventType::RequestCompleted]);
+
+        complete(&state).await;
+
+        let mut delivered = Vec::new();
+        for _ in 0..100 {
+            delivered = receiver.received_requests().await.unwrap();
+            if !delivered.is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(delivered.len(), 1);
+
+        let body: Value = serde_json::from_slice(&delivered[0].body).unwrap();
+        assert_eq!(body["type"], "request.completed");
+        assert_eq!(body["data"]["request_id"], "req-webhook");
+        assert_eq!(body["data"]["provider"], "completing");
+        assert_eq!(body["data"]["prompt_tokens"], 7);
+
+        let signature = delivered[0].headers[SIGNATURE_HEADER].to_str().unwrap();
+        let (timestamp, hex) = signature
+            .strip_prefix("t=")
+            .and_then(|rest| rest.split_once(",v1="))
+            .unwrap();
+        assert_eq!(hex, sign(SECRET, timestamp.parse().unwrap(), &delivered[0].body));
+    }
+
+    #[tokio::test]
+    async fn test_unsubscribed_event_is_not_delivered() {
+        let receiver = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&receiver)
+            .await;
+        let state = create_state(&receiver, vec![WebhookEventType::CircuitOpened]);
+
+        complete(&state).await;
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(receiver.received_requests().await.unwrap().is_empty());
+    }
+}
+
+#[cfg(test)]
+mod stream_retry_tests {
+    use super::*;
+    use futures::stream::StreamExt;
+    use gateway_resilience::{RetryConfig, RetryPolicy};
+
+    /// Where the first streaming call fails
+    #[derive(Clone, Copy)]
+    enum Failure {
+        /// The stream cannot be opened
+        Connect,
+        /// The stream yields an error before any chunk
+        BeforeFirstChunk,
+        /// The stream delivers one chunk and then errors
+        AfterFirstChunk,
+    }
+
+    fn overloaded() -> GatewayError {
+        GatewayError::provider("flaky-stream", "overloaded", Some(503), true)
+    }
+
+    /// State whose provider's first stream fails with a retryable 503
+    fn create_state(failure: Failure) -> (AppState, Arc<MockProvider>) {
+        let provider = MockProvider::new("flaky-stream", "flaky-model").with_stream(move |_, call| {
+            let chunks: Vec<_> = text_chunks("chatcmpl-flaky", "flaky-model", &["Hello", " world"])
+                .into_iter()
+                .map(Ok)
+                .collect();
+            if call > 0 {
+                return Ok(futures::stream::iter(chunks).boxed());
+            }
+            let items = match failure {
+                Failure::Connect => return Err(overloaded()),
+                Failure::BeforeFirstChunk => vec![Err(overloaded())],
+                Failure::AfterFirstChunk => {
+                    let mut items: Vec<_> = chunks.into_iter().take(1).collect();
+                    items.push(Err(overloaded()));
+                    items
+                }
+            };
+            Ok(futures::stream::iter(items).boxed())
+        });
+        let provider = Arc::new(provider);
+
+        let state = mock_state_builder(std::slice::from_ref(&provider), |config| {
+            config.server.stream_retry_before_first_chunk = true;
+        })
+        .retry_policy(RetryPolicy::new(RetryConfig {
+            max_retries: 2,
+            base_delay: Duration::from_millis(1),
+            overloaded_base_delay: Duration::from_millis(1),
+            jitter: 0.0,
+            ..RetryConfig::default()
+        }))
+        .build();
+        (state, provider)
+    }
+
+    async fn stream(state: &AppState) -> Vec<Value> {
+        let body = json!({
+            "model": "flaky-model",
+            "messages": [{"role": "user", "content": "Say hello"}],
+            "stream": true
+        });
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/chat/completions")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header("x-parent-span-id", uuid::Uuid::new_v4().to_string())
+            .body(Body::from(body.to_string()))
+            .unwrap();
+
+        let response = create_router(state.clone()).oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        String::from_utf8(bytes.to_vec())
+            .unwrap()
+            .lines()
+            .filter_map(|line| line.strip_prefix("data: "))
+            .take_while(|data| *data != "[DONE]")
+            .filter_map(|data| serde_json::from_str(data).ok())
+            .collect()
+    }
+
+    fn streamed_content(events: &[Value]) -> String {
+        events
+            .iter()
+            .filter_map(|e| e["choices"][0]["delta"]["content"].as_str())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_connect_failure_is_retried() {
+        let (state, provider) = create_state(Failure::Connect);
+        let events = stream(&state).await;
+
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 2);
+        assert_eq!(streamed_content(&events), "Hello world");
+        assert!(events.iter().all(|e| e.get("error").is_none()));
+    }
+
+    #[tokio::test]
+    async fn test_failure_before_first_chunk_is_retried() {
+        let (state, provider) = create_state(Failure::BeforeFirstChunk);
+        let events = stream(&state).await;
+
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 2);
+        assert_eq!(streamed_content(&events), "Hello world");
+        assert!(events.iter().all(|e| e.get("error").is_none()));
+    }
+
+    #[tokio::test]
+    async fn test_failure_after_first_chunk_is_surfaced() {
+        let (state, provider) = create_state(Failure::AfterFirstChunk);
+        let events = stream(&state).await;
+
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 1);
+        assert_eq!(streamed_content(&events), "Hello");
+        assert!(events.iter().any(|e| e.get("error").is_some()));
+    }
+}
+
+#[cfg(test)]
+mod provider_override_tests {
+    use super::*;
+    use gateway_config::UnauthorizedOverride;
+    use gateway_server::{auth_middleware, ApiKeyConfig, ApiKeyMetadata, AuthConfig, AuthState};
+
+    struct App {
+        app: axum::Router,
+        openai: Arc<MockProvider>,
+        azure: Arc<MockProvider>,
+    }
+
+    async fn create_app(unauthorized: UnauthorizedOverride) -> App {
+        let openai = Arc::new(MockProvider::new("openai", "gpt-4o").with_reply("Hello"));
+        let azure = Arc::new(MockProvider::new("azure-eu", "gpt-4o").with_reply("Hello"));
+
+        let state = mock_state(&[openai.clone(), azure.clone()], |config| {
+            config.routing.provider_override.enabled = true;
+            config.routing.provider_override.unauthorized = unauthorized;
+        });
+
+        let auth_state = AuthState::new(
+            AuthConfig::builder()
+                .api_keys(
+                    ApiKeyConfig::new()
+                        .with_key(
+                            "key-operator",
+                            ApiKeyMetadata::new()
+                                .with_scopes(vec!["gateway:provider_override".to_string()]),
+                        )
+                        .with_key("key-user", ApiKeyMetadata::new()),
+                )
+                .required(true)
+                .build(),
+        )
+        .await
+        .unwrap();
+
+        App {
+            app: create_router(state).layer(axum::middleware::from_fn_with_state(
+                auth_state,
+                auth_middleware,
+            )),
+            openai,
+            azure,
+        }
+    }
+
+    async fn send(app: &axum::Router, api_key: &str, provider: &str) -> (StatusCode, Value) {
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/chat/completions")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header("x-parent-span-id", uuid::Uuid::new_v4().to_string())
+            .header("x-api-key", api_key)
+            .header("x-gateway-provider", provider)
+            .body(Body::from(
+                json!({
+                    "model": "gpt-4o",
+                    "messages": [{"role": "user", "content": "Hello"}]
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        let response = app.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_authorized_override_pins_provider() {
+        let App { app, openai, azure } = create_app(UnauthorizedOverride::Reject).await;
+
+        for _ in 0..10 {
+            let (status, _) = send(&app, "key-operator", "azure-eu").await;
+            assert_eq!(status, StatusCode::OK);
+        }
+        assert_eq!(openai.calls.load(Ordering::SeqCst), 0);
+        assert_eq!(azure.calls.load(Ordering::SeqCst), 10);
+
+        let (status, json) = send(&app, "key-operator", "bedrock").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(json["error"]["code"], "unknown_provider");
+    }
+
+    #[tokio::test]
+    async fn test_unauthorized_override_is_rejected() {
+        let App { app, openai, azure } = create_app(UnauthorizedOverride::Reject).await;
+
+        let (status, json) = send(&app, "key-user", "azure-eu").await;
+
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(json["error"]["code"], "provider_override_forbidden");
+        assert_eq!(openai.calls.load(Ordering::SeqCst), 0);
+        assert_eq!(azure.calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_unauthorized_override_is_ignored() {
+        let App { app, openai, azure } = create_app(UnauthorizedOverride::Ignore).await;
+
+        for _ in 0..10 {
+            let (status, _) = send(&app, "key-user", "azure-eu").await;
+            assert_eq!(status, StatusCode::OK);
+        }
+        assert!(openai.calls.load(Ordering::SeqCst) > 0);
+        assert_eq!(
+            openai.calls.load(Ordering::SeqCst) + azure.calls.load(Ordering::SeqCst),
+            10
+        );
+    }
+}
+
+#[cfg(test)]
+mod client_disconnect_tests {
+    use super::*;
+    use futures::stream::StreamExt;
+    use gateway_telemetry::{CostTracker, OutcomeKind};
+    use std::sync::atomic::AtomicBool;
+
+    /// Sets its flag when the upstream stream holding it is dropped
+    struct DropFlag(Arc<AtomicBool>);
+
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    /// State whose provider streams two chunks, then either ends or hangs
+    fn create_state(hang: bool, dropped: Arc<AtomicBool>) -> AppState {
+        let provider = MockProvider::new("chatty", "chatty-model").with_stream(move |_, _| {
+            let chunk = |text: &str| {
+                Ok(ChatChunk::builder()
+                    .id("chunk-1")
+                    .model("chatty-model")
+                    .choice(ChunkChoice::with_content(0, text))
+                    .build())
+            };
+            let flag = DropFlag(dropped.clone());
+            let head = futures::stream::iter(vec![chunk("Hello there, "), chunk("how are you?")]);
+            let tail = futures::stream::once(async move {
+                let _flag = flag;
+                if hang {
+                    futures::future::pending::<()>().await;
+                }
+            })
+            .filter_map(|()| futures::future::ready(None));
+            Ok(head.chain(tail).boxed())
+        });
+        mock_state_builder(&[Arc::new(provider)], |_| {})
+            .cost_tracker(Arc::new(CostTracker::with_defaults()))
+            .build()
+    }
+
+    async fn open_stream(state: &AppState) -> axum::response::Response {
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/chat/completions")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header("x-parent-span-id", uuid::Uuid::new_v4().to_string())
+            .body(Body::from(
+                json!({
+                    "model": "chatty-model",
+                    "messages": [{"role": "user", "content": "Hello"}],
+                    "stream": true
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        create_router(state.clone()).oneshot(request).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_hangup_mid_stream_is_a_client_disconnect() {
+        let dropped = Arc::new(AtomicBool::new(false));
+        let state = create_state(true, dropped.clone());
+
+        let response = open_stream(&state).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // Read the first event, then hang up
+        let mut body = response.into_body();
+        let frame = body.frame().await.unwrap().unwrap();
+        assert!(String::from_utf8_lossy(frame.data_ref().unwrap()).contains("Hello there"));
+        assert_eq!(state.tracker.active_count(), 1);
+        drop(body);
+
+        assert!(dropped.load(Ordering::SeqCst), "upstream stream was not aborted");
+        assert_eq!(state.tracker.active_count(), 0);
+        let outcome = state.tracker.get_recent_completed(1).remove(0);
+        assert_eq!(outcome.kind, OutcomeKind::ClientDisconnect);
+        assert_eq!(outcome.status_code, 499);
+        assert!(!outcome.success);
+        assert!(outcome.error.is_none());
+        let partial = outcome.output_tokens.unwrap();
+        assert!(partial > 0);
+        assert_eq!(state.tracker.stats().client_disconnects, 1);
+    }
+
+    #[tokio::test]
+    async fn test_hangup_mid_stream_bills_generated_tokens() {
+        // Bill for the full generation
+        let state = create_state(false, Arc::new(AtomicBool::new(false)));
+        open_stream(&state).await.into_body().collect().await.unwrap();
+        let full = state.cost_tracker.recent_events(1).await.remove(0);
+        assert!(full.success);
+
+        // Hang up after the first of the two chunks
+        let state = create_state(true, Arc::new(AtomicBool::new(false)));
+        let mut body = open_stream(&state).await.into_body();
+        body.frame().await.unwrap().unwrap();
+        drop(body);
+        tokio::task::yield_now().await;
+
+        let partial = state.cost_tracker.recent_events(1).await.remove(0);
+        assert!(!partial.success);
+        assert_eq!(partial.input_tokens, full.input_tokens);
+        assert!(partial.output_tokens > 0);
+        assert!(partial.output_tokens < full.output_tokens);
+        assert_eq!(
+            Some(partial.output_tokens),
+            state.tracker.get_recent_completed(1).remove(0).output_tokens
+        );
+        assert!(partial.cost > 0.0 && partial.cost < full.cost);
+    }
+
+    #[tokio::test]
+    async fn test_finished_stream_is_completed() {
+        let dropped = Arc::new(AtomicBool::new(false));
+        let state = create_state(false, dropped);
+
+        let response = open_stream(&state).await;
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert!(String::from_utf8_lossy(&body).contains("[DONE]"));
+
+        let outcome = state.tracker.get_recent_completed(1).remove(0);
+        assert_eq!(outcome.kind, OutcomeKind::Completed);
+        assert_eq!(outcome.status_code, 200);
+        assert!(outcome.success);
+        assert_eq!(state.tracker.stats().client_disconnects, 0);
+    }
+}
+
+#[cfg(feature = "persistence")]
+mod stored_content_limit_tests {
+    use super::*;
+    use gateway_config::PersistenceConfig;
+    use gateway_server::persistence::ExchangeStore;
+
+    fn generation() -> String {
+        "All work and no play. ".repeat(200)
+    }
+
+    #[tokio::test]
+    async fn test_long_response_truncated_in_storage_only() {
+        let config = PersistenceConfig {
+            enabled: true,
+            database_url: Some("sqlite::memory:".to_string()),
+            max_connections: 1,
+            redact_pii: false,
+            max_stored_content_length: Some(20),
+            ..PersistenceConfig::default()
+        };
+        let store = Arc::new(ExchangeStore::connect(&config).await.unwrap().unwrap());
+
+        // Answers with a long generation
+        let provider = MockProvider::new("verbose", "gpt-4o").with_reply(&generation());
+        let state = mock_state_builder(&[Arc::new(provider)], |_| {})
+            .exchange_store(Arc::clone(&store))
+            .build();
+
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/chat/completions")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header("x-request-id", "req-long")
+            .header("x-parent-span-id", uuid::Uuid::new_v4().to_string())
+            .body(Body::from(
+                json!({
+                    "model": "gpt-4o",
+                    "messages": [{"role": "user", "content": "Hi"}]
+                })
+                .to_string(),
+            ))
+            .unwrap();
+        let response = create_router(state).oneshot(request).await.unwrap();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let json: Value = serde_json::from_slice(&body).unwrap();
+
+        // The client gets the whole generation
+        let content = &json["result"]["choices"][0]["message"]["content"];
+        assert_eq!(content.as_str().map(str::len), Some(generation().len()));
+
+        // Persistence happens in the background
+        let stored = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                if let Some(record) = store.get("req-long").await.unwrap() {
+                    break record;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("exchange should be persisted");
+        let stored: Value = serde_json::from_str(&stored.response).unwrap();
+        assert_eq!(
+            stored["choices"][0]["message"]["content"],
+            format!("All work and no play...[truncated from {} bytes]", generation().len())
+        );
+    }
+}
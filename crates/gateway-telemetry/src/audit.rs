@@ -7,8 +7,10 @@
 //! - Security events
 //! - Administrative actions
 
+use crate::truncate::ContentLimit;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -220,6 +222,23 @@ pub struct AuditEvent {
     pub tenant_id: Option<String>,
 }
 
+impl AuditEvent {
+    /// Cut a long description and metadata strings to `limit`
+    ///
+    /// Returns whether anything was cut.
+    pub fn truncate_content(&mut self, limit: &ContentLimit) -> bool {
+        let mut cut = false;
+        if let Cow::Owned(description) = limit.truncate(&self.description) {
+            self.description = description;
+            cut = true;
+        }
+        for value in self.metadata.values_mut() {
+            cut |= limit.truncate_json(value);
+        }
+        cut
+    }
+}
+
 /// Audit event outcome
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -370,6 +389,8 @@ pub struct AuditLogConfig {
     pub redact_sensitive: bool,
     /// Maximum events to keep in memory buffer
     pub buffer_size: usize,
+    /// Cap on the description and metadata strings kept per event
+    pub content_limit: Option<ContentLimit>,
 }
 
 impl Default for AuditLogConfig {
@@ -382,6 +403,7 @@ impl Default for AuditLogConfig {
             include_bodies: false,
             redact_sensitive: true,
             buffer_size: 1000,
+            content_limit: None,
         }
     }
 }
@@ -431,11 +453,14 @@ impl AuditLogger {
         }
 
         // Redact sensitive data if configured
-        let event = if self.config.redact_sensitive {
+        let mut event = if self.config.redact_sensitive {
             self.redact_event(event)
         } else {
             event
         };
+        if let Some(limit) = &self.config.content_limit {
+            event.truncate_content(limit);
+        }
 
         // Log to stdout
         if self.config.log_to_stdout {
@@ -891,6 +916,30 @@ mod tests {
         assert_eq!(api_key, "[REDACTED]");
     }
 
+    #[tokio::test]
+    async fn test_audit_logger_truncates_long_content() {
+        let logger = AuditLogger::new(AuditLogConfig {
+            log_to_stdout: false,
+            content_limit: Some(ContentLimit::new(8)),
+            ..Default::default()
+        });
+
+        let event = AuditEventBuilder::new(AuditEventType::ResponseSent)
+            .description("short")
+            .metadata("response", serde_json::json!({"content": "x".repeat(100)}))
+            .metadata("status_code", 200)
+            .build();
+        logger.log(event).await;
+
+        let events = logger.get_recent_events(1).await;
+        assert_eq!(events[0].description, "short");
+        assert_eq!(
+            events[0].metadata["response"]["content"],
+            "xxxxxxxx...[truncated from 100 bytes]"
+        );
+        assert_eq!(events[0].metadata["status_code"], 200);
+    }
+
     #[tokio::test]
     async fn test_audit_actor_builders() {
         let system = AuditActor::system();
@@ -6,10 +6,12 @@
 //! - Budget management and alerts
 //! - Usage reports and aggregation
 
+use gateway_core::Usage;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::time::Duration;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 use tracing::{debug, info, warn};
 
@@ -22,6 +24,9 @@ pub struct CostConfig {
     pub default_input_cost_per_1k: f64,
     /// Default cost per 1K output tokens (USD)
     pub default_output_cost_per_1k: f64,
+    /// Fraction of the input price charged for prompt-cache reads when a
+    /// model has no explicit cache-read pricing
+    pub default_cache_read_discount: f64,
     /// Maximum events to keep in memory
     pub max_events: usize,
     /// Aggregation interval
@@ -34,6 +39,7 @@ impl Default for CostConfig {
             enabled: true,
             default_input_cost_per_1k: 0.01,
             default_output_cost_per_1k: 0.03,
+            default_cache_read_discount: 0.1,
             max_events: 10_000,
             aggregation_interval: Duration::from_secs(60),
         }
@@ -74,6 +80,11 @@ pub struct ModelPricing {
     pub input_cost_per_1k: f64,
     /// Cost per 1K output tokens (USD)
     pub output_cost_per_1k: f64,
+    /// Cost per 1K prompt-cache read tokens (USD)
+    ///
+    /// Falls back to the tracker's default discount off `input_cost_per_1k`.
+    #[serde(default)]
+    pub cache_read_cost_per_1k: Option<f64>,
     /// Currency (default: USD)
     pub currency: String,
 }
@@ -87,6 +98,7 @@ impl ModelPricing {
             provider: provider.into(),
             input_cost_per_1k: 0.01,
             output_cost_per_1k: 0.03,
+            cache_read_cost_per_1k: None,
             currency: "USD".to_string(),
         }
     }
@@ -99,6 +111,13 @@ impl ModelPricing {
         self
     }
 
+    /// Set the prompt-cache read rate
+    #[must_use]
+    pub fn with_cache_read_pricing(mut self, cache_read_per_1k: f64) -> Self {
+        self.cache_read_cost_per_1k = Some(cache_read_per_1k);
+        self
+    }
+
     /// Calculate cost for given token counts
     #[must_use]
     pub fn calculate_cost(&self, input_tokens: u32, output_tokens: u32) -> f64 {
@@ -127,6 +146,18 @@ pub struct UsageEvent {
     pub output_tokens: u32,
     /// Total tokens
     pub total_tokens: u32,
+    /// Input tokens read from the provider's prompt cache (included in `input_tokens`)
+    #[serde(default)]
+    pub cache_read_tokens: u32,
+    /// Cost of the cache-read tokens at the discounted rate (USD)
+    #[serde(default)]
+    pub cache_read_cost: f64,
+    /// Savings versus billing cache reads at the full input price (USD)
+    #[serde(default)]
+    pub cache_savings: f64,
+    /// Reasoning tokens reported by the provider (included in `output_tokens`)
+    #[serde(default)]
+    pub reasoning_tokens: u32,
     /// Calculated cost (USD)
     pub cost: f64,
     /// Whether the request was successful
@@ -155,6 +186,10 @@ impl UsageEvent {
             input_tokens,
             output_tokens,
             total_tokens: input_tokens + output_tokens,
+            cache_read_tokens: 0,
+            cache_read_cost: 0.0,
+            cache_savings: 0.0,
+            reasoning_tokens: 0,
             cost,
             success: true,
             latency_ms: 0,
@@ -168,6 +203,22 @@ impl UsageEvent {
         self
     }
 
+    /// Set prompt-cache read usage
+    #[must_use]
+    pub fn with_cache_read(mut self, tokens: u32, cost: f64, savings: f64) -> Self {
+        self.cache_read_tokens = tokens;
+        self.cache_read_cost = cost;
+        self.cache_savings = savings;
+        self
+    }
+
+    /// Set reasoning token usage
+    #[must_use]
+    pub fn with_reasoning_tokens(mut self, tokens: u32) -> Self {
+        self.reasoning_tokens = tokens;
+        self
+    }
+
     /// Set latency
     #[must_use]
     pub fn with_latency(mut self, latency: Duration) -> Self {
@@ -196,6 +247,18 @@ pub struct UsageStats {
     pub total_input_tokens: u64,
     /// Total output tokens
     pub total_output_tokens: u64,
+    /// Total input tokens read from the prompt cache
+    #[serde(default)]
+    pub total_cache_read_tokens: u64,
+    /// Total cost of cache-read tokens (USD)
+    #[serde(default)]
+    pub total_cache_read_cost: f64,
+    /// Total prompt-cache savings (USD)
+    #[serde(default)]
+    pub total_cache_savings: f64,
+    /// Total reasoning tokens (included in output tokens)
+    #[serde(default)]
+    pub total_reasoning_tokens: u64,
     /// Total cost (USD)
     pub total_cost: f64,
     /// Average latency (ms)
@@ -217,6 +280,10 @@ impl UsageStats {
         }
         self.total_input_tokens += u64::from(event.input_tokens);
         self.total_output_tokens += u64::from(event.output_tokens);
+        self.total_cache_read_tokens += u64::from(event.cache_read_tokens);
+        self.total_cache_read_cost += event.cache_read_cost;
+        self.total_cache_savings += event.cache_savings;
+        self.total_reasoning_tokens += u64::from(event.reasoning_tokens);
         self.total_cost += event.cost;
 
         // Update average latency
@@ -233,6 +300,13 @@ impl UsageStats {
         }
     }
 
+    /// Input tokens billed at the full input price
+    #[must_use]
+    pub fn full_price_input_tokens(&self) -> u64 {
+        self.total_input_tokens
+            .saturating_sub(self.total_cache_read_tokens)
+    }
+
     /// Merge two stats
     #[must_use]
     pub fn merge(mut self, other: &UsageStats) -> Self {
@@ -241,6 +315,10 @@ impl UsageStats {
         self.failed_requests += other.failed_requests;
         self.total_input_tokens += other.total_input_tokens;
         self.total_output_tokens += other.total_output_tokens;
+        self.total_cache_read_tokens += other.total_cache_read_tokens;
+        self.total_cache_read_cost += other.total_cache_read_cost;
+        self.total_cache_savings += other.total_cache_savings;
+        self.total_reasoning_tokens += other.total_reasoning_tokens;
         self.total_cost += other.total_cost;
 
         if other.total_requests > 0 {
@@ -436,6 +514,51 @@ impl CostTracker {
         }
     }
 
+    /// Calculate cost for a request with prompt-cache reads
+    ///
+    /// `cache_read_tokens` are a subset of `input_tokens` billed at the
+    /// model's cache-read rate. Returns the total cost, the cost of the
+    /// cache-read portion, and the savings versus full-price input.
+    pub async fn calculate_cost_with_cache(
+        &self,
+        model: &str,
+        provider: &str,
+        input_tokens: u32,
+        cache_read_tokens: u32,
+        output_tokens: u32,
+    ) -> (f64, f64, f64) {
+        let pricing = self.get_pricing(model, provider).await;
+
+        let (input_per_1k, output_per_1k, cache_read_per_1k) = match pricing {
+            Some(p) => (
+                p.input_cost_per_1k,
+                p.output_cost_per_1k,
+                p.cache_read_cost_per_1k,
+            ),
+            None => (
+                self.config.default_input_cost_per_1k,
+                self.config.default_output_cost_per_1k,
+                None,
+            ),
+        };
+        let cache_read_per_1k =
+            cache_read_per_1k.unwrap_or(input_per_1k * self.config.default_cache_read_discount);
+
+        let cache_read_tokens = cache_read_tokens.min(input_tokens);
+        let full_price_tokens = input_tokens - cache_read_tokens;
+
+        let full_price_cost = (f64::from(full_price_tokens) / 1000.0) * input_per_1k;
+        let cache_read_cost = (f64::from(cache_read_tokens) / 1000.0) * cache_read_per_1k;
+        let output_cost = (f64::from(output_tokens) / 1000.0) * output_per_1k;
+        let savings = (f64::from(cache_read_tokens) / 1000.0) * input_per_1k - cache_read_cost;
+
+        (
+            full_price_cost + cache_read_cost + output_cost,
+            cache_read_cost,
+            savings,
+        )
+    }
+
     /// Record a usage event
     pub async fn record_usage(&self, event: UsageEvent) {
         if !self.config.enabled {
@@ -539,6 +662,61 @@ impl CostTracker {
         self.record_usage(event).await;
     }
 
+    /// Record provider-reported usage, pricing prompt-cache reads at the
+    /// discounted rate
+    #[allow(clippy::too_many_arguments)]
+    pub async fn record_response_usage(
+        &self,
+        request_id: impl Into<String>,
+        tenant_id: Option<String>,
+        model: impl Into<String>,
+        provider: impl Into<String>,
+        usage: &gateway_core::Usage,
+        latency: Duration,
+        success: bool,
+    ) {
+        let model = model.into();
+        let provider = provider.into();
+        let cache_read_tokens = usage.prompt_tokens_cached.unwrap_or(0);
+        let (cost, cache_read_cost, savings) = self
+            .calculate_cost_with_cache(
+                &model,
+                &provider,
+                usage.prompt_tokens,
+                cache_read_tokens,
+                usage.completion_tokens,
+            )
+            .await;
+
+        let mut event = UsageEvent::new(
+            request_id,
+            &model,
+            &provider,
+            usage.prompt_tokens,
+            usage.completion_tokens,
+            cost,
+        )
+        .with_cache_read(
+            cache_read_tokens.min(usage.prompt_tokens),
+            cache_read_cost,
+            savings,
+        )
+        .with_reasoning_tokens(
+            usage
+                .completion_tokens_reasoning
+                .unwrap_or(0)
+                .min(usage.completion_tokens),
+        )
+        .with_latency(latency)
+        .with_success(success);
+
+        if let Some(tenant) = tenant_id {
+            event = event.with_tenant(tenant);
+        }
+
+        self.record_usage(event).await;
+    }
+
     /// Set a budget for a tenant
     pub async fn set_budget(&self, budget: Budget) {
         let tenant_id = budget.tenant_id.clone();
@@ -656,6 +834,16 @@ impl CostTracker {
         events.iter().rev().take(limit).cloned().collect()
     }
 
+    /// Usage events recorded for one request, oldest first
+    pub async fn request_events(&self, request_id: &str) -> Vec<UsageEvent> {
+        let events = self.events.read().await;
+        events
+            .iter()
+            .filter(|event| event.request_id == request_id)
+            .cloned()
+            .collect()
+    }
+
     /// Clear all tracked data
     pub async fn clear(&self) {
         {
@@ -696,6 +884,144 @@ impl CostTracker {
     }
 }
 
+/// Bills a streamed response for what was generated, however it ends
+///
+/// Completion tokens are counted as chunks arrive. Usage reported by the
+/// provider, which may arrive incrementally, takes precedence over the
+/// count; without it the prompt estimate and the counted completion tokens
+/// are billed. The meter bills once: on [`Self::finish`], or when dropped
+/// unfinished after a client disconnect, in which case the partial usage is
+/// recorded as unsuccessful.
+pub struct StreamCostMeter {
+    tracker: Arc<CostTracker>,
+    request_id: String,
+    tenant_id: Option<String>,
+    model: String,
+    provider: String,
+    prompt_tokens: u32,
+    started: Instant,
+    state: parking_lot::Mutex<StreamMeterState>,
+}
+
+#[derive(Default)]
+struct StreamMeterState {
+    completion_tokens: u32,
+    reported: Option<Usage>,
+    billed: bool,
+}
+
+impl StreamCostMeter {
+    /// Start metering a stream whose prompt is estimated at `prompt_tokens`
+    #[must_use]
+    pub fn new(
+        tracker: Arc<CostTracker>,
+        request_id: impl Into<String>,
+        model: impl Into<String>,
+        provider: impl Into<String>,
+        prompt_tokens: u32,
+    ) -> Self {
+        Self {
+            tracker,
+            request_id: request_id.into(),
+            tenant_id: None,
+            model: model.into(),
+            provider: provider.into(),
+            prompt_tokens,
+            started: Instant::now(),
+            state: parking_lot::Mutex::default(),
+        }
+    }
+
+    /// Bill the stream to a tenant
+    #[must_use]
+    pub fn with_tenant(mut self, tenant_id: Option<String>) -> Self {
+        self.tenant_id = tenant_id;
+        self
+    }
+
+    /// Count completion tokens from a chunk that just arrived
+    pub fn add_completion_tokens(&self, tokens: u32) {
+        let mut state = self.state.lock();
+        state.completion_tokens = state.completion_tokens.saturating_add(tokens);
+    }
+
+    /// Take the provider's latest cumulative usage
+    pub fn observe_usage(&self, usage: &Usage) {
+        self.state.lock().reported = Some(usage.clone());
+    }
+
+    /// Usage to bill for what has been generated so far
+    #[must_use]
+    pub fn usage(&self) -> Usage {
+        let state = self.state.lock();
+        state
+            .reported
+            .clone()
+            .unwrap_or_else(|| Usage::new(self.prompt_tokens, state.completion_tokens))
+    }
+
+    /// Bill the stream once it has ended
+    ///
+    /// Does nothing if the stream was already billed.
+    pub async fn finish(&self, success: bool) {
+        let Some(usage) = self.take_unbilled() else {
+            return;
+        };
+        self.tracker
+            .record_response_usage(
+                &self.request_id,
+                self.tenant_id.clone(),
+                &self.model,
+                &self.provider,
+                &usage,
+                self.started.elapsed(),
+                success,
+            )
+            .await;
+    }
+
+    fn take_unbilled(&self) -> Option<Usage> {
+        let usage = self.usage();
+        let mut state = self.state.lock();
+        if state.billed {
+            return None;
+        }
+        state.billed = true;
+        Some(usage)
+    }
+}
+
+impl Drop for StreamCostMeter {
+    fn drop(&mut self) {
+        let Some(usage) = self.take_unbilled() else {
+            return;
+        };
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            warn!(request_id = %self.request_id, "No runtime to bill an abandoned stream");
+            return;
+        };
+
+        debug!(
+            request_id = %self.request_id,
+            completion_tokens = usage.completion_tokens,
+            "Billing abandoned stream"
+        );
+        let tracker = Arc::clone(&self.tracker);
+        let request_id = std::mem::take(&mut self.request_id);
+        let tenant_id = self.tenant_id.take();
+        let model = std::mem::take(&mut self.model);
+        let provider = std::mem::take(&mut self.provider);
+        let latency = self.started.elapsed();
+        runtime.spawn(async move {
+            tracker
+                .record_response_usage(
+                    request_id, tenant_id, model, provider, &usage, latency, false,
+                )
+                .await;
+        });
+    }
+}
+
 /// Cost report summary
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CostReport {
@@ -711,6 +1037,8 @@ pub struct CostReport {
     pub by_model: HashMap<String, UsageStats>,
     /// Per-provider breakdown
     pub by_provider: HashMap<String, UsageStats>,
+    /// Prompt-cache savings summary
+    pub cache_savings: CacheSavings,
     /// Generated timestamp
     pub generated_at: chrono::DateTime<chrono::Utc>,
 }
@@ -718,10 +1046,12 @@ pub struct CostReport {
 impl CostReport {
     /// Generate a cost report from the tracker
     pub async fn generate(tracker: &CostTracker, title: impl Into<String>, period: impl Into<String>) -> Self {
+        let global = tracker.global_stats().await;
         Self {
             title: title.into(),
             period: period.into(),
-            global: tracker.global_stats().await,
+            cache_savings: CacheSavings::from_stats(&global),
+            global,
             by_tenant: tracker.all_tenant_stats().await,
             by_model: tracker.all_model_stats().await,
             by_provider: tracker.provider_stats.read().await.clone(),
@@ -730,6 +1060,32 @@ impl CostReport {
     }
 }
 
+/// Prompt-cache savings summary
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CacheSavings {
+    /// Input tokens billed at the full input price
+    pub full_price_input_tokens: u64,
+    /// Input tokens read from the prompt cache at the discounted rate
+    pub cache_read_tokens: u64,
+    /// Cost of cache-read tokens (USD)
+    pub cache_read_cost: f64,
+    /// Savings versus billing cache reads at the full input price (USD)
+    pub savings: f64,
+}
+
+impl CacheSavings {
+    /// Build a summary from aggregated stats
+    #[must_use]
+    pub fn from_stats(stats: &UsageStats) -> Self {
+        Self {
+            full_price_input_tokens: stats.full_price_input_tokens(),
+            cache_read_tokens: stats.total_cache_read_tokens,
+            cache_read_cost: stats.total_cache_read_cost,
+            savings: stats.total_cache_savings,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -742,6 +1098,48 @@ mod tests {
         assert!((cost - 0.06).abs() < 0.001); // 0.03 + 0.03 = 0.06
     }
 
+    #[tokio::test]
+    async fn test_stream_meter_bills_partial_stream_on_drop() {
+        let tracker = Arc::new(CostTracker::with_defaults());
+        let meter = StreamCostMeter::new(Arc::clone(&tracker), "req-1", "gpt-4", "openai", 100)
+            .with_tenant(Some("tenant-1".to_string()));
+
+        // Aborted after 40 of a possible 1000 completion tokens
+        for _ in 0..10 {
+            meter.add_completion_tokens(4);
+        }
+        drop(meter);
+        tokio::task::yield_now().await;
+
+        let events = tracker.request_events("req-1").await;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].input_tokens, 100);
+        assert_eq!(events[0].output_tokens, 40);
+        assert!(!events[0].success);
+        assert_eq!(events[0].tenant_id.as_deref(), Some("tenant-1"));
+        // 100 * 0.01/1k + 40 * 0.03/1k
+        assert!((events[0].cost - 0.0022).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_stream_meter_prefers_reported_usage_and_bills_once() {
+        let tracker = Arc::new(CostTracker::with_defaults());
+        let meter = StreamCostMeter::new(Arc::clone(&tracker), "req-1", "gpt-4", "openai", 100);
+
+        meter.add_completion_tokens(7);
+        meter.observe_usage(&Usage::new(120, 10));
+        meter.finish(true).await;
+        meter.finish(true).await;
+        drop(meter);
+        tokio::task::yield_now().await;
+
+        let events = tracker.request_events("req-1").await;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].input_tokens, 120);
+        assert_eq!(events[0].output_tokens, 10);
+        assert!(events[0].success);
+    }
+
     #[test]
     fn test_usage_event() {
         let event = UsageEvent::new("req-1", "gpt-4", "openai", 100, 50, 0.005)
@@ -928,6 +1326,77 @@ mod tests {
         assert!(report.by_model.contains_key("gpt-4"));
     }
 
+    #[tokio::test]
+    async fn test_cost_report_cache_savings() {
+        let tracker = CostTracker::with_defaults();
+        tracker
+            .register_pricing(
+                ModelPricing::new("claude-3-5-sonnet", "anthropic")
+                    .with_pricing(0.003, 0.015)
+                    .with_cache_read_pricing(0.0003),
+            )
+            .await;
+
+        // 10K prompt tokens, 8K of them served from the prompt cache
+        let usage = gateway_core::Usage::new(10_000, 1_000).with_prompt_tokens_cached(8_000);
+        tracker
+            .record_response_usage(
+                "req-1",
+                Some("tenant-1".to_string()),
+                "claude-3-5-sonnet",
+                "anthropic",
+                &usage,
+                Duration::from_millis(100),
+                true,
+            )
+            .await;
+
+        let report = CostReport::generate(&tracker, "Cache Report", "daily").await;
+        let savings = &report.cache_savings;
+
+        assert_eq!(savings.full_price_input_tokens, 2_000);
+        assert_eq!(savings.cache_read_tokens, 8_000);
+        assert!((savings.cache_read_cost - 0.0024).abs() < 1e-9);
+        assert!((savings.savings - 0.0216).abs() < 1e-9);
+        // 2K full-price input + 8K discounted input + 1K output
+        assert!((report.global.total_cost - (0.006 + 0.0024 + 0.015)).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_record_response_usage_reasoning_tokens() {
+        let tracker = CostTracker::with_defaults();
+        let usage = gateway_core::Usage::new(100, 500).with_completion_tokens_reasoning(320);
+
+        tracker
+            .record_response_usage(
+                "req-1",
+                None,
+                "o1-mini",
+                "openai",
+                &usage,
+                Duration::from_millis(100),
+                true,
+            )
+            .await;
+
+        let stats = tracker.global_stats().await;
+        assert_eq!(stats.total_output_tokens, 500);
+        assert_eq!(stats.total_reasoning_tokens, 320);
+    }
+
+    #[tokio::test]
+    async fn test_cache_read_uses_default_discount() {
+        let tracker = CostTracker::new(CostConfig::default().with_default_pricing(0.01, 0.03));
+
+        let (cost, cache_read_cost, savings) = tracker
+            .calculate_cost_with_cache("unknown", "unknown", 1_000, 1_000, 0)
+            .await;
+
+        assert!((cache_read_cost - 0.001).abs() < 1e-9);
+        assert!((cost - 0.001).abs() < 1e-9);
+        assert!((savings - 0.009).abs() < 1e-9);
+    }
+
     #[test]
     fn test_cost_config() {
         let config = CostConfig::new()
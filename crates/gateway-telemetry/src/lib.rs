@@ -10,6 +10,8 @@
 //! - Audit logging for compliance
 //! - Cost tracking and billing
 //! - PII redaction for logs
+//! - Length caps for stored and logged content
+//! - SLO error-budget burn-rate alerts
 
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
@@ -20,7 +22,9 @@ pub mod logging;
 pub mod metrics;
 pub mod pii;
 pub mod request_tracker;
+pub mod slo;
 pub mod tracing_setup;
+pub mod truncate;
 
 // Re-export main types
 pub use audit::{
@@ -28,14 +32,18 @@ pub use audit::{
     AuditOutcome, AuditResource, AuditSeverity, AuditStats,
 };
 pub use cost::{
-    Budget, BudgetStatus, CostConfig, CostReport, CostTracker, ModelPricing, UsageEvent,
-    UsageStats,
+    Budget, BudgetStatus, CacheSavings, CostConfig, CostReport, CostTracker, ModelPricing,
+    StreamCostMeter, UsageEvent, UsageStats,
+};
+pub use logging::{init_logging, DisallowedFieldAction, FieldAllowlist, LoggingConfig};
+pub use metrics::{Metrics, MetricsConfig, RequestMetrics, TokenSource};
+pub use request_tracker::{
+    OutcomeKind, RequestInfo, RequestOutcome, RequestOwner, RequestTracker,
 };
-pub use logging::{init_logging, LoggingConfig};
-pub use metrics::{Metrics, MetricsConfig, RequestMetrics};
-pub use request_tracker::{RequestInfo, RequestOutcome, RequestTracker};
 pub use pii::{
     CustomPattern, PiiAnalysis, PiiConfig, PiiPattern, PiiPatternConfig, PiiRedactor,
     RedactPii, RedactionStyle,
 };
+pub use slo::{BurnRate, BurnSeverity, BurnWindow, Sli, SloConfig, SloEvent, SloMonitor};
 pub use tracing_setup::{init_tracing, shutdown_tracing, TracingConfig};
+pub use truncate::{ContentLimit, TRUNCATION_MARKER};
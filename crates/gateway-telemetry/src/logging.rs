@@ -4,12 +4,22 @@
 //! - JSON or pretty format
 //! - Log level filtering
 //! - Request context enrichment
+//! - Structured field allowlisting
 
 use serde::{Deserialize, Serialize};
-use tracing::Level;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
 use tracing_subscriber::{
-    fmt::{self, format::FmtSpan},
+    field::RecordFields,
+    fmt::{
+        self,
+        format::{FmtSpan, Writer},
+        FmtContext, FormatEvent, FormatFields, FormattedFields, MakeWriter,
+    },
     layer::SubscriberExt,
+    registry::LookupSpan,
     util::SubscriberInitExt,
     EnvFilter, Layer,
 };
@@ -31,6 +41,9 @@ pub struct LoggingConfig {
     pub span_events: SpanEvents,
     /// Filter directives (e.g., "hyper=warn,tower=info")
     pub filter: Option<String>,
+    /// Only emit these structured fields; `None` emits every field
+    #[serde(default)]
+    pub field_allowlist: Option<FieldAllowlist>,
 }
 
 impl Default for LoggingConfig {
@@ -43,6 +56,7 @@ impl Default for LoggingConfig {
             include_location: true,
             span_events: SpanEvents::None,
             filter: None,
+            field_allowlist: None,
         }
     }
 }
@@ -96,6 +110,13 @@ impl LoggingConfig {
         self
     }
 
+    /// Restrict logged structured fields to an allowlist
+    #[must_use]
+    pub fn with_field_allowlist(mut self, allowlist: FieldAllowlist) -> Self {
+        self.field_allowlist = Some(allowlist);
+        self
+    }
+
     /// Get the tracing Level
     #[must_use]
     pub fn tracing_level(&self) -> Level {
@@ -164,6 +185,13 @@ pub fn init_logging(config: &LoggingConfig) -> Result<(), LoggingError> {
     // Build the env filter
     let filter = build_filter(config)?;
 
+    if let Some(ref allowlist) = config.field_allowlist {
+        return tracing_subscriber::registry()
+            .with(allowlist_layer(config, allowlist, std::io::stdout).with_filter(filter))
+            .try_init()
+            .map_err(|e| LoggingError::Init(e.to_string()));
+    }
+
     // Initialize based on format
     match config.format {
         LogFormat::Json => init_json_logging(config, filter),
@@ -245,6 +273,296 @@ fn init_compact_logging(config: &LoggingConfig, filter: EnvFilter) -> Result<(),
         .map_err(|e| LoggingError::Init(e.to_string()))
 }
 
+/// Restricts which structured fields are written to logs
+///
+/// Fields not on the list are dropped or redacted before formatting, so a
+/// field such as `content = %msg` added to a log statement cannot leak
+/// request data. The event `message` is always kept. Span fields are
+/// filtered the same way.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldAllowlist {
+    /// Field names that are logged as-is
+    pub fields: HashSet<String>,
+    /// What happens to fields that are not allowlisted
+    #[serde(default)]
+    pub action: DisallowedFieldAction,
+}
+
+impl FieldAllowlist {
+    /// Create an allowlist permitting the given fields
+    #[must_use]
+    pub fn new<I, F>(fields: I) -> Self
+    where
+        I: IntoIterator<Item = F>,
+        F: Into<String>,
+    {
+        Self {
+            fields: fields.into_iter().map(Into::into).collect(),
+            action: DisallowedFieldAction::default(),
+        }
+    }
+
+    /// Set what happens to fields that are not allowlisted
+    #[must_use]
+    pub fn with_action(mut self, action: DisallowedFieldAction) -> Self {
+        self.action = action;
+        self
+    }
+
+    /// Whether `field` may be logged verbatim
+    #[must_use]
+    pub fn permits(&self, field: &str) -> bool {
+        field == "message" || self.fields.contains(field)
+    }
+}
+
+/// Handling of structured fields missing from the [`FieldAllowlist`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum DisallowedFieldAction {
+    /// Omit the field entirely
+    Drop,
+    /// Keep the field name but replace its value with `[REDACTED]`
+    #[default]
+    Redact,
+}
+
+/// Placeholder for redacted field values
+const REDACTED: &str = "[REDACTED]";
+
+/// Build a formatting layer that applies `allowlist` to every event
+///
+/// The pretty format has no hook for filtering event fields, so it is
+/// replaced by the default single-line format when an allowlist is set.
+fn allowlist_layer<S, W>(
+    config: &LoggingConfig,
+    allowlist: &FieldAllowlist,
+    writer: W,
+) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    let allowlist = Arc::new(allowlist.clone());
+    let base = fmt::layer()
+        .with_writer(writer)
+        .with_span_events(config.span_events.to_fmt_span())
+        .with_file(config.include_location)
+        .with_line_number(config.include_location)
+        .with_target(true);
+
+    match config.format {
+        LogFormat::Json => base
+            .fmt_fields(AllowlistFields { allowlist: Arc::clone(&allowlist), json: true })
+            .event_format(AllowlistJsonFormat {
+                allowlist,
+                include_location: config.include_location,
+            })
+            .boxed(),
+        LogFormat::Compact => {
+            let layer = base
+                .compact()
+                .fmt_fields(AllowlistFields { allowlist, json: false });
+            if config.timestamps {
+                layer.boxed()
+            } else {
+                layer.without_time().boxed()
+            }
+        }
+        LogFormat::Pretty => {
+            let layer = base.fmt_fields(AllowlistFields { allowlist, json: false });
+            if config.timestamps {
+                layer.boxed()
+            } else {
+                layer.without_time().boxed()
+            }
+        }
+    }
+}
+
+/// Collects the fields of an event or span that pass the allowlist
+struct AllowlistVisitor<'a> {
+    allowlist: &'a FieldAllowlist,
+    fields: serde_json::Map<String, serde_json::Value>,
+}
+
+impl<'a> AllowlistVisitor<'a> {
+    fn new(allowlist: &'a FieldAllowlist) -> Self {
+        Self {
+            allowlist,
+            fields: serde_json::Map::new(),
+        }
+    }
+
+    fn insert(&mut self, field: &Field, value: serde_json::Value) {
+        let name = field.name();
+        if self.allowlist.permits(name) {
+            self.fields.insert(name.to_string(), value);
+        } else if self.allowlist.action == DisallowedFieldAction::Redact {
+            self.fields.insert(name.to_string(), REDACTED.into());
+        }
+    }
+}
+
+impl Visit for AllowlistVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.insert(field, value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.insert(field, value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.insert(field, format!("{value:?}").into());
+    }
+}
+
+/// Field formatter that only writes allowlisted fields
+///
+/// Writes a JSON object in JSON mode (so span fields can be embedded by
+/// [`AllowlistJsonFormat`]) and `name=value` pairs otherwise.
+#[derive(Debug, Clone)]
+struct AllowlistFields {
+    allowlist: Arc<FieldAllowlist>,
+    json: bool,
+}
+
+impl AllowlistFields {
+    fn write(&self, writer: &mut Writer<'_>, fields: &serde_json::Map<String, serde_json::Value>) -> std::fmt::Result {
+        if self.json {
+            return write!(writer, "{}", serde_json::Value::Object(fields.clone()));
+        }
+
+        let message = fields.get("message").and_then(serde_json::Value::as_str);
+        let mut separator = if let Some(message) = message {
+            write!(writer, "{message}")?;
+            " "
+        } else {
+            ""
+        };
+        for (name, value) in fields.iter().filter(|(name, _)| *name != "message") {
+            match value.as_str() {
+                Some(value) => write!(writer, "{separator}{name}={value}")?,
+                None => write!(writer, "{separator}{name}={value}")?,
+            }
+            separator = " ";
+        }
+        Ok(())
+    }
+}
+
+impl<'writer> FormatFields<'writer> for AllowlistFields {
+    fn format_fields<R: RecordFields>(&self, mut writer: Writer<'writer>, fields: R) -> std::fmt::Result {
+        let mut visitor = AllowlistVisitor::new(&self.allowlist);
+        fields.record(&mut visitor);
+        self.write(&mut writer, &visitor.fields)
+    }
+
+    fn add_fields(
+        &self,
+        current: &'writer mut FormattedFields<Self>,
+        fields: &tracing::span::Record<'_>,
+    ) -> std::fmt::Result {
+        let mut visitor = AllowlistVisitor::new(&self.allowlist);
+        fields.record(&mut visitor);
+
+        if self.json {
+            let mut merged: serde_json::Map<String, serde_json::Value> =
+                serde_json::from_str(&current.fields).unwrap_or_default();
+            merged.extend(visitor.fields);
+            current.fields.clear();
+            return self.write(&mut current.as_writer(), &merged);
+        }
+
+        if !current.fields.is_empty() && !visitor.fields.is_empty() {
+            current.fields.push(' ');
+        }
+        self.write(&mut current.as_writer(), &visitor.fields)
+    }
+}
+
+/// JSON event formatter that only writes allowlisted fields
+///
+/// Produces the same shape as the built-in JSON format: `timestamp`,
+/// `level`, `fields`, `target`, the current `span` and all `spans`.
+#[derive(Debug, Clone)]
+struct AllowlistJsonFormat {
+    allowlist: Arc<FieldAllowlist>,
+    include_location: bool,
+}
+
+impl<S, N> FormatEvent<S, N> for AllowlistJsonFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'w> FormatFields<'w> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> std::fmt::Result {
+        let metadata = event.metadata();
+        let mut visitor = AllowlistVisitor::new(&self.allowlist);
+        event.record(&mut visitor);
+
+        let mut line = serde_json::json!({
+            "timestamp": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Micros, true),
+            "level": metadata.level().to_string(),
+            "fields": visitor.fields,
+            "target": metadata.target(),
+            "threadId": format!("{:?}", std::thread::current().id()),
+        });
+
+        if self.include_location {
+            if let Some(file) = metadata.file() {
+                line["filename"] = file.into();
+            }
+            if let Some(number) = metadata.line() {
+                line["line_number"] = number.into();
+            }
+        }
+
+        if let Some(scope) = ctx.event_scope() {
+            let spans: Vec<serde_json::Value> = scope
+                .from_root()
+                .map(|span| {
+                    let mut object = serde_json::Map::new();
+                    object.insert("name".to_string(), span.name().into());
+                    if let Some(fields) = span.extensions().get::<FormattedFields<N>>() {
+                        if let Ok(serde_json::Value::Object(fields)) =
+                            serde_json::from_str(&fields.fields)
+                        {
+                            object.extend(fields);
+                        }
+                    }
+                    serde_json::Value::Object(object)
+                })
+                .collect();
+
+            if let Some(current) = spans.last() {
+                line["span"] = current.clone();
+            }
+            line["spans"] = spans.into();
+        }
+
+        writeln!(writer, "{line}")
+    }
+}
+
 /// Logging initialization error
 #[derive(Debug, thiserror::Error)]
 pub enum LoggingError {
@@ -356,6 +674,135 @@ mod tests {
         assert_eq!(ctx.fields.get("custom"), Some(&"value".to_string()));
     }
 
+    /// Captures formatted log output for assertions
+    #[derive(Clone, Default)]
+    struct Captured(Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl std::io::Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Captured {
+        fn output(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    fn capture(config: &LoggingConfig, log: impl FnOnce()) -> String {
+        let captured = Captured::default();
+        let writer = captured.clone();
+        let allowlist = config.field_allowlist.clone().unwrap();
+        let subscriber = tracing_subscriber::registry()
+            .with(allowlist_layer(config, &allowlist, move || writer.clone()));
+
+        tracing::subscriber::with_default(subscriber, log);
+        captured.output()
+    }
+
+    #[test]
+    fn test_field_allowlist_redacts_json_fields() {
+        let config = LoggingConfig::new()
+            .json()
+            .with_field_allowlist(FieldAllowlist::new(["request_id", "latency_ms"]));
+
+        let output = capture(&config, || {
+            tracing::info!(
+                request_id = "req-1",
+                latency_ms = 42,
+                content = "my card is 4111 1111 1111 1111",
+                "request handled"
+            );
+        });
+
+        let line: serde_json::Value = serde_json::from_str(output.trim()).unwrap();
+        assert_eq!(line["level"], "INFO");
+        assert_eq!(line["fields"]["message"], "request handled");
+        assert_eq!(line["fields"]["request_id"], "req-1");
+        assert_eq!(line["fields"]["latency_ms"], 42);
+        assert_eq!(line["fields"]["content"], "[REDACTED]");
+        assert!(!output.contains("4111"));
+    }
+
+    #[test]
+    fn test_field_allowlist_drops_json_fields() {
+        let config = LoggingConfig::new().json().with_field_allowlist(
+            FieldAllowlist::new(["request_id"]).with_action(DisallowedFieldAction::Drop),
+        );
+
+        let output = capture(&config, || {
+            tracing::warn!(request_id = "req-2", prompt = %"tell me a secret", "slow request");
+        });
+
+        let line: serde_json::Value = serde_json::from_str(output.trim()).unwrap();
+        assert_eq!(line["fields"]["request_id"], "req-2");
+        assert!(line["fields"].get("prompt").is_none());
+        assert!(!output.contains("secret"));
+    }
+
+    #[test]
+    fn test_field_allowlist_filters_span_fields() {
+        let config = LoggingConfig::new()
+            .json()
+            .with_field_allowlist(FieldAllowlist::new(["tenant_id"]));
+
+        let output = capture(&config, || {
+            let span = tracing::info_span!("request", tenant_id = "acme", user_email = "a@b.com");
+            let _guard = span.enter();
+            tracing::info!("inside span");
+        });
+
+        let line: serde_json::Value = serde_json::from_str(output.trim()).unwrap();
+        assert_eq!(line["span"]["name"], "request");
+        assert_eq!(line["span"]["tenant_id"], "acme");
+        assert_eq!(line["span"]["user_email"], "[REDACTED]");
+        assert!(!output.contains("a@b.com"));
+    }
+
+    #[test]
+    fn test_field_allowlist_text_format() {
+        let mut config = LoggingConfig::new()
+            .with_format(LogFormat::Compact)
+            .with_field_allowlist(FieldAllowlist::new(["model"]));
+        config.timestamps = false;
+
+        let output = capture(&config, || {
+            tracing::info!(model = "gpt-4o", content = "hello there", "completed");
+        });
+
+        assert!(output.contains("completed"));
+        assert!(output.contains("model=gpt-4o"));
+        assert!(output.contains("content=[REDACTED]"));
+        assert!(!output.contains("hello there"));
+    }
+
+    #[test]
+    fn test_field_allowlist_deserialize() {
+        let config: LoggingConfig = serde_json::from_value(serde_json::json!({
+            "enabled": true,
+            "level": "info",
+            "format": "json",
+            "timestamps": true,
+            "include_location": false,
+            "span_events": "none",
+            "filter": null,
+            "field_allowlist": { "fields": ["request_id"], "action": "drop" }
+        }))
+        .unwrap();
+
+        let allowlist = config.field_allowlist.unwrap();
+        assert!(allowlist.permits("request_id"));
+        assert!(allowlist.permits("message"));
+        assert!(!allowlist.permits("content"));
+        assert_eq!(allowlist.action, DisallowedFieldAction::Drop);
+    }
+
     #[test]
     fn test_span_events() {
         assert_eq!(SpanEvents::None.to_fmt_span(), FmtSpan::NONE);
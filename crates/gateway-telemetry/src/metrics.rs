@@ -5,7 +5,11 @@
 //! - Token usage
 //! - Provider health and availability
 //! - Error rates
+//! - Shadow response drift
+//! - SLO error-budget burn rates
 
+use crate::slo::BurnRate;
+use gateway_core::{ProviderRateLimits, RateLimitWindow, ResponseDiff};
 use parking_lot::RwLock;
 use prometheus::{
     CounterVec, Encoder, GaugeVec, HistogramOpts, HistogramVec, Opts,
@@ -24,6 +28,8 @@ pub struct MetricsConfig {
     pub path: String,
     /// Histogram buckets for latency
     pub latency_buckets: Vec<f64>,
+    /// Histogram buckets for prompt and completion token counts
+    pub token_buckets: Vec<f64>,
     /// Custom labels to add to all metrics
     pub labels: HashMap<String, String>,
 }
@@ -36,6 +42,7 @@ impl Default for MetricsConfig {
             latency_buckets: vec![
                 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0,
             ],
+            token_buckets: DEFAULT_TOKEN_BUCKETS.to_vec(),
             labels: HashMap::new(),
         }
     }
@@ -64,6 +71,60 @@ pub struct RequestMetrics {
     pub tenant_id: Option<String>,
 }
 
+/// Default histogram buckets for token counts, from 16 to 128k tokens
+pub const DEFAULT_TOKEN_BUCKETS: [f64; 14] = [
+    16.0, 32.0, 64.0, 128.0, 256.0, 512.0, 1_024.0, 2_048.0, 4_096.0, 8_192.0, 16_384.0,
+    32_768.0, 65_536.0, 131_072.0,
+];
+
+/// Where a prompt token count comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenSource {
+    /// The gateway's pre-flight estimate from the request
+    Estimate,
+    /// The provider's reported usage
+    Usage,
+}
+
+impl TokenSource {
+    /// Label value for this source
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Estimate => "estimate",
+            Self::Usage => "usage",
+        }
+    }
+}
+
+/// Histogram buckets for payload sizes, from 256 B to 4 MiB
+const PAYLOAD_SIZE_BUCKETS: [f64; 9] = [
+    256.0, 1_024.0, 4_096.0, 16_384.0, 65_536.0, 262_144.0, 1_048_576.0, 2_097_152.0, 4_194_304.0,
+];
+
+/// Histogram buckets for generation throughput, from 1 to 1000 tokens/s
+const THROUGHPUT_BUCKETS: [f64; 11] = [
+    1.0, 5.0, 10.0, 20.0, 30.0, 50.0, 75.0, 100.0, 200.0, 500.0, 1_000.0,
+];
+
+/// Generation throughput in tokens per second
+///
+/// `None` when there is nothing to measure: no tokens, or a zero duration
+/// (as for a stream delivered in a single chunk).
+#[must_use]
+pub fn tokens_per_second(completion_tokens: u32, duration: Duration) -> Option<f64> {
+    let secs = duration.as_secs_f64();
+    (completion_tokens > 0 && secs > 0.0).then(|| f64::from(completion_tokens) / secs)
+}
+
+/// Histogram buckets for 0.0 - 1.0 similarity scores
+const SIMILARITY_BUCKETS: [f64; 10] = [0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9, 1.0];
+
+/// Histogram buckets for signed word count differences
+const LENGTH_DELTA_BUCKETS: [f64; 11] = [
+    -500.0, -100.0, -25.0, -5.0, -1.0, 0.0, 1.0, 5.0, 25.0, 100.0, 500.0,
+];
+
 /// Main metrics registry and collectors
 pub struct Metrics {
     /// Prometheus registry
@@ -88,8 +149,38 @@ pub struct Metrics {
     cache_operations: CounterVec,
     /// Time to first token histogram (streaming)
     ttft: HistogramVec,
+    /// Streams that failed before their first token
+    stream_no_token: CounterVec,
     /// Tokens per second gauge
     tokens_per_second: GaugeVec,
+    /// Generation throughput histogram
+    generation_throughput: HistogramVec,
+    /// Provider-reported rate limit quota
+    provider_rate_limit: GaugeVec,
+    /// Provider-reported remaining quota
+    provider_rate_limit_remaining: GaugeVec,
+    /// Provider-reported time until quota reset
+    provider_rate_limit_reset: GaugeVec,
+    /// Provider request body size histogram
+    provider_request_bytes: HistogramVec,
+    /// Provider response body size histogram
+    provider_response_bytes: HistogramVec,
+    /// Word overlap between primary and shadow responses
+    shadow_token_overlap: HistogramVec,
+    /// Word count difference of shadow vs primary responses
+    shadow_length_delta: HistogramVec,
+    /// Shadow responses with a different finish reason
+    shadow_finish_reason_mismatches: CounterVec,
+    /// Embedding similarity between primary and shadow responses
+    shadow_embedding_similarity: HistogramVec,
+    /// Prompt tokens per request, estimated and reported
+    prompt_tokens: HistogramVec,
+    /// Completion tokens per request
+    completion_tokens: HistogramVec,
+    /// SLO error-budget burn rate per window
+    slo_burn_rate: GaugeVec,
+    /// Whether an SLO burn-rate alert is firing
+    slo_burn_alert: GaugeVec,
     /// Internal state
     state: RwLock<MetricsState>,
 }
@@ -201,6 +292,16 @@ impl Metrics {
         )?;
         registry.register(Box::new(ttft.clone()))?;
 
+        let stream_no_token = CounterVec::new(
+            Opts::new(
+                "llm_gateway_stream_no_token_total",
+                "Streaming requests that failed before their first token",
+            )
+            .namespace("llm_gateway"),
+            &["model", "provider"],
+        )?;
+        registry.register(Box::new(stream_no_token.clone()))?;
+
         // Tokens per second
         let tokens_per_second = GaugeVec::new(
             Opts::new(
@@ -212,6 +313,159 @@ impl Metrics {
         )?;
         registry.register(Box::new(tokens_per_second.clone()))?;
 
+        let generation_throughput = HistogramVec::new(
+            HistogramOpts::new(
+                "llm_gateway_generation_tokens_per_second",
+                "Completion tokens generated per second",
+            )
+            .namespace("llm_gateway")
+            .buckets(THROUGHPUT_BUCKETS.to_vec()),
+            &["provider", "model"],
+        )?;
+        registry.register(Box::new(generation_throughput.clone()))?;
+
+        // Provider-reported rate limits
+        let provider_rate_limit = GaugeVec::new(
+            Opts::new(
+                "llm_gateway_provider_rate_limit",
+                "Rate limit quota reported by the provider",
+            )
+            .namespace("llm_gateway"),
+            &["provider", "model", "limit_type"],
+        )?;
+        registry.register(Box::new(provider_rate_limit.clone()))?;
+
+        let provider_rate_limit_remaining = GaugeVec::new(
+            Opts::new(
+                "llm_gateway_provider_rate_limit_remaining",
+                "Remaining rate limit quota reported by the provider",
+            )
+            .namespace("llm_gateway"),
+            &["provider", "model", "limit_type"],
+        )?;
+        registry.register(Box::new(provider_rate_limit_remaining.clone()))?;
+
+        let provider_rate_limit_reset = GaugeVec::new(
+            Opts::new(
+                "llm_gateway_provider_rate_limit_reset_seconds",
+                "Seconds until the provider rate limit quota resets",
+            )
+            .namespace("llm_gateway"),
+            &["provider", "model", "limit_type"],
+        )?;
+        registry.register(Box::new(provider_rate_limit_reset.clone()))?;
+
+        // Provider payload sizes
+        let provider_request_bytes = HistogramVec::new(
+            HistogramOpts::new(
+                "llm_gateway_provider_request_bytes",
+                "Size of request bodies sent to providers in bytes",
+            )
+            .namespace("llm_gateway")
+            .buckets(PAYLOAD_SIZE_BUCKETS.to_vec()),
+            &["provider", "model"],
+        )?;
+        registry.register(Box::new(provider_request_bytes.clone()))?;
+
+        let provider_response_bytes = HistogramVec::new(
+            HistogramOpts::new(
+                "llm_gateway_provider_response_bytes",
+                "Size of response bodies received from providers in bytes",
+            )
+            .namespace("llm_gateway")
+            .buckets(PAYLOAD_SIZE_BUCKETS.to_vec()),
+            &["provider", "model"],
+        )?;
+        registry.register(Box::new(provider_response_bytes.clone()))?;
+
+        // Shadow response drift
+        let shadow_token_overlap = HistogramVec::new(
+            HistogramOpts::new(
+                "llm_gateway_shadow_token_overlap",
+                "Word overlap between primary and shadow responses (0-1)",
+            )
+            .namespace("llm_gateway")
+            .buckets(SIMILARITY_BUCKETS.to_vec()),
+            &["primary", "shadow"],
+        )?;
+        registry.register(Box::new(shadow_token_overlap.clone()))?;
+
+        let shadow_length_delta = HistogramVec::new(
+            HistogramOpts::new(
+                "llm_gateway_shadow_length_delta",
+                "Shadow minus primary response length in words",
+            )
+            .namespace("llm_gateway")
+            .buckets(LENGTH_DELTA_BUCKETS.to_vec()),
+            &["primary", "shadow"],
+        )?;
+        registry.register(Box::new(shadow_length_delta.clone()))?;
+
+        let shadow_finish_reason_mismatches = CounterVec::new(
+            Opts::new(
+                "llm_gateway_shadow_finish_reason_mismatches_total",
+                "Shadow responses that stopped for a different reason than the primary",
+            )
+            .namespace("llm_gateway"),
+            &["primary", "shadow"],
+        )?;
+        registry.register(Box::new(shadow_finish_reason_mismatches.clone()))?;
+
+        let shadow_embedding_similarity = HistogramVec::new(
+            HistogramOpts::new(
+                "llm_gateway_shadow_embedding_similarity",
+                "Embedding similarity between primary and shadow responses (0-1)",
+            )
+            .namespace("llm_gateway")
+            .buckets(SIMILARITY_BUCKETS.to_vec()),
+            &["primary", "shadow"],
+        )?;
+        registry.register(Box::new(shadow_embedding_similarity.clone()))?;
+
+        // Token count distributions
+        let prompt_tokens = HistogramVec::new(
+            HistogramOpts::new(
+                "llm_gateway_prompt_tokens",
+                "Prompt tokens per request (estimated pre-flight or reported by the provider)",
+            )
+            .namespace("llm_gateway")
+            .buckets(config.token_buckets.clone()),
+            &["model", "source"],
+        )?;
+        registry.register(Box::new(prompt_tokens.clone()))?;
+
+        let completion_tokens = HistogramVec::new(
+            HistogramOpts::new(
+                "llm_gateway_completion_tokens",
+                "Completion tokens per request",
+            )
+            .namespace("llm_gateway")
+            .buckets(config.token_buckets.clone()),
+            &["model"],
+        )?;
+        registry.register(Box::new(completion_tokens.clone()))?;
+
+        // SLO burn rates
+        let slo_burn_rate = GaugeVec::new(
+            Opts::new(
+                "llm_gateway_slo_burn_rate",
+                "Error-budget burn rate (1 = budget spent exactly over the SLO period)",
+            )
+            .namespace("llm_gateway"),
+            &["sli", "severity", "window"],
+        )?;
+        registry.register(Box::new(slo_burn_rate.clone()))?;
+
+        let slo_burn_alert = GaugeVec::new(
+            Opts::new(
+                "llm_gateway_slo_burn_alert",
+                "Whether the SLO burn-rate alert is firing (0 or 1)",
+            )
+            .namespace("llm_gateway"),
+            &["sli", "severity"],
+        )?;
+        registry.register(Box::new(slo_burn_alert.clone()))?;
+
         info!("Metrics initialized");
 
         Ok(Self {
@@ -226,7 +480,22 @@ impl Metrics {
             rate_limit_hits,
             cache_operations,
             ttft,
+            stream_no_token,
             tokens_per_second,
+            generation_throughput,
+            provider_rate_limit,
+            provider_rate_limit_remaining,
+            provider_rate_limit_reset,
+            provider_request_bytes,
+            provider_response_bytes,
+            shadow_token_overlap,
+            shadow_length_delta,
+            shadow_finish_reason_mismatches,
+            shadow_embedding_similarity,
+            prompt_tokens,
+            completion_tokens,
+            slo_burn_rate,
+            slo_burn_alert,
             state: RwLock::new(MetricsState::default()),
         })
     }
@@ -329,11 +598,24 @@ impl Metrics {
             .inc();
     }
 
-    /// Record time to first token
-    pub fn record_ttft(&self, model: &str, provider: &str, ttft: Duration) {
+    /// Record time to first token of a streaming request
+    ///
+    /// `elapsed` runs from the start of the request to the first chunk with
+    /// content.
+    pub fn record_first_token(&self, provider: &str, model: &str, elapsed: Duration) {
         self.ttft
             .with_label_values(&[model, provider])
-            .observe(ttft.as_secs_f64());
+            .observe(elapsed.as_secs_f64());
+    }
+
+    /// Record a streaming request that failed before its first token
+    ///
+    /// Kept out of the time-to-first-token histogram so failures don't
+    /// skew it.
+    pub fn record_stream_no_token(&self, provider: &str, model: &str) {
+        self.stream_no_token
+            .with_label_values(&[model, provider])
+            .inc();
     }
 
     /// Update tokens per second
@@ -343,6 +625,130 @@ impl Metrics {
             .set(rate);
     }
 
+    /// Record generation throughput for a response
+    ///
+    /// `duration` is the total generation time for non-streaming responses
+    /// and the time from first to last token for streams. Also updates the
+    /// `tokens_per_second` gauge with the latest rate.
+    pub fn record_generation_throughput(
+        &self,
+        provider: &str,
+        model: &str,
+        completion_tokens: u32,
+        duration: Duration,
+    ) {
+        let Some(rate) = tokens_per_second(completion_tokens, duration) else {
+            return;
+        };
+        self.generation_throughput
+            .with_label_values(&[provider, model])
+            .observe(rate);
+        self.update_tokens_per_second(model, provider, rate);
+    }
+
+    /// Update gauges from rate limits reported by a provider
+    pub fn update_provider_rate_limits(
+        &self,
+        provider: &str,
+        model: &str,
+        limits: &ProviderRateLimits,
+    ) {
+        self.update_rate_limit_window(provider, model, "requests", &limits.requests);
+        self.update_rate_limit_window(provider, model, "tokens", &limits.tokens);
+    }
+
+    fn update_rate_limit_window(
+        &self,
+        provider: &str,
+        model: &str,
+        limit_type: &str,
+        window: &RateLimitWindow,
+    ) {
+        let labels = [provider, model, limit_type];
+        if let Some(limit) = window.limit {
+            self.provider_rate_limit
+                .with_label_values(&labels)
+                .set(limit as f64);
+        }
+        if let Some(remaining) = window.remaining {
+            self.provider_rate_limit_remaining
+                .with_label_values(&labels)
+                .set(remaining as f64);
+        }
+        if let Some(reset) = window.reset {
+            self.provider_rate_limit_reset
+                .with_label_values(&labels)
+                .set(reset.as_secs_f64());
+        }
+    }
+
+    /// Record the size of a request body sent to a provider
+    pub fn record_provider_request_bytes(&self, provider: &str, model: &str, bytes: usize) {
+        self.provider_request_bytes
+            .with_label_values(&[provider, model])
+            .observe(bytes as f64);
+    }
+
+    /// Record the size of a provider response body
+    ///
+    /// For streaming responses this is the cumulative size of all chunks.
+    pub fn record_provider_response_bytes(&self, provider: &str, model: &str, bytes: usize) {
+        self.provider_response_bytes
+            .with_label_values(&[provider, model])
+            .observe(bytes as f64);
+    }
+
+    /// Record how a shadow provider's response differed from the primary's
+    pub fn record_shadow_diff(&self, primary: &str, shadow: &str, diff: &ResponseDiff) {
+        let labels = [primary, shadow];
+        self.shadow_token_overlap
+            .with_label_values(&labels)
+            .observe(diff.token_overlap);
+        self.shadow_length_delta
+            .with_label_values(&labels)
+            .observe(diff.length_delta as f64);
+        let mismatches = self.shadow_finish_reason_mismatches.with_label_values(&labels);
+        if !diff.finish_reason_match {
+            mismatches.inc();
+        }
+        if let Some(similarity) = diff.embedding_similarity {
+            self.shadow_embedding_similarity
+                .with_label_values(&labels)
+                .observe(similarity);
+        }
+    }
+
+    /// Record a request's prompt token count
+    pub fn record_prompt_tokens(&self, model: &str, source: TokenSource, tokens: u32) {
+        self.prompt_tokens
+            .with_label_values(&[model, source.as_str()])
+            .observe(f64::from(tokens));
+    }
+
+    /// Record a response's completion token count
+    ///
+    /// For streaming responses this is recorded once the stream ends.
+    pub fn record_completion_tokens(&self, model: &str, tokens: u32) {
+        self.completion_tokens
+            .with_label_values(&[model])
+            .observe(f64::from(tokens));
+    }
+
+    /// Update SLO burn-rate gauges
+    pub fn update_slo_burn_rate(&self, rate: &BurnRate) {
+        let sli = rate.sli.as_str();
+        let severity = rate.severity.as_str();
+        self.slo_burn_rate
+            .with_label_values(&[sli, severity, "long"])
+            .set(rate.long_rate);
+        self.slo_burn_rate
+            .with_label_values(&[sli, severity, "short"])
+            .set(rate.short_rate);
+        self.slo_burn_alert
+            .with_label_values(&[sli, severity])
+            .set(if rate.firing { 1.0 } else { 0.0 });
+    }
+
     /// Get metrics as Prometheus text format
     #[must_use]
     pub fn gather(&self) -> String {
@@ -450,6 +856,187 @@ mod tests {
         assert!(output.contains("llm_gateway_circuit_breaker_state"));
     }
 
+    #[test]
+    fn test_provider_rate_limits() {
+        let config = MetricsConfig::default();
+        let metrics = Metrics::new(&config).unwrap();
+
+        let mut headers = http::HeaderMap::new();
+        headers.insert("x-ratelimit-limit-requests", "500".parse().unwrap());
+        headers.insert("x-ratelimit-remaining-requests", "12".parse().unwrap());
+        headers.insert("x-ratelimit-reset-requests", "1m30s".parse().unwrap());
+        headers.insert("x-ratelimit-remaining-tokens", "40000".parse().unwrap());
+        let limits = ProviderRateLimits::from_headers(&headers).unwrap();
+
+        metrics.update_provider_rate_limits("openai", "gpt-4", &limits);
+
+        let labels = ["openai", "gpt-4", "requests"];
+        let limit = metrics.provider_rate_limit.with_label_values(&labels).get();
+        let remaining = metrics.provider_rate_limit_remaining.with_label_values(&labels).get();
+        let reset = metrics.provider_rate_limit_reset.with_label_values(&labels).get();
+        let remaining_tokens = metrics
+            .provider_rate_limit_remaining
+            .with_label_values(&["openai", "gpt-4", "tokens"])
+            .get();
+        assert!((limit - 500.0).abs() < f64::EPSILON);
+        assert!((remaining - 12.0).abs() < f64::EPSILON);
+        assert!((reset - 90.0).abs() < f64::EPSILON);
+        assert!((remaining_tokens - 40_000.0).abs() < f64::EPSILON);
+
+        let output = metrics.gather();
+        assert!(output.contains("llm_gateway_provider_rate_limit_remaining"));
+        assert!(output.contains("limit_type=\"tokens\""));
+    }
+
+    #[test]
+    fn test_token_histograms() {
+        let config = MetricsConfig {
+            token_buckets: vec![100.0, 1_000.0, 10_000.0],
+            ..MetricsConfig::default()
+        };
+        let metrics = Metrics::new(&config).unwrap();
+
+        metrics.record_prompt_tokens("gpt-4", TokenSource::Estimate, 50);
+        metrics.record_prompt_tokens("gpt-4", TokenSource::Estimate, 5_000);
+        metrics.record_prompt_tokens("gpt-4", TokenSource::Usage, 60);
+        metrics.record_completion_tokens("gpt-4", 700);
+        metrics.record_completion_tokens("claude-3", 20);
+
+        let estimates = metrics
+            .prompt_tokens
+            .with_label_values(&["gpt-4", "estimate"])
+            .get_sample_count();
+        assert_eq!(estimates, 2);
+
+        let output = metrics.gather();
+        assert!(output.contains(
+            "llm_gateway_prompt_tokens_bucket{model=\"gpt-4\",source=\"estimate\",le=\"100\"} 1"
+        ));
+        assert!(output.contains(
+            "llm_gateway_prompt_tokens_bucket{model=\"gpt-4\",source=\"estimate\",le=\"10000\"} 2"
+        ));
+        assert!(output.contains(
+            "llm_gateway_completion_tokens_bucket{model=\"gpt-4\",le=\"100\"} 0"
+        ));
+        assert!(output.contains(
+            "llm_gateway_completion_tokens_bucket{model=\"gpt-4\",le=\"1000\"} 1"
+        ));
+        assert!(output.contains(
+            "llm_gateway_completion_tokens_bucket{model=\"claude-3\",le=\"100\"} 1"
+        ));
+    }
+
+    #[test]
+    fn test_generation_throughput() {
+        let config = MetricsConfig::default();
+        let metrics = Metrics::new(&config).unwrap();
+
+        metrics.record_generation_throughput("openai", "gpt-4", 200, Duration::from_secs(4));
+        metrics.record_generation_throughput("openai", "gpt-4", 0, Duration::from_secs(1));
+        metrics.record_generation_throughput("openai", "gpt-4", 10, Duration::ZERO);
+
+        let histogram = metrics
+            .generation_throughput
+            .with_label_values(&["openai", "gpt-4"]);
+        assert_eq!(histogram.get_sample_count(), 1);
+        assert!((histogram.get_sample_sum() - 50.0).abs() < f64::EPSILON);
+        let gauge = metrics
+            .tokens_per_second
+            .with_label_values(&["gpt-4", "openai"])
+            .get();
+        assert!((gauge - 50.0).abs() < f64::EPSILON);
+
+        let output = metrics.gather();
+        assert!(output.contains(
+            "llm_gateway_generation_tokens_per_second_bucket{model=\"gpt-4\",provider=\"openai\",le=\"50\"} 1"
+        ));
+    }
+
+    #[test]
+    fn test_first_token_and_no_token_streams() {
+        let config = MetricsConfig::default();
+        let metrics = Metrics::new(&config).unwrap();
+
+        metrics.record_first_token("openai", "gpt-4", Duration::from_millis(300));
+        metrics.record_stream_no_token("openai", "gpt-4");
+
+        let histogram = metrics.ttft.with_label_values(&["gpt-4", "openai"]);
+        assert_eq!(histogram.get_sample_count(), 1);
+        assert!((histogram.get_sample_sum() - 0.3).abs() < f64::EPSILON);
+        let no_token = metrics
+            .stream_no_token
+            .with_label_values(&["gpt-4", "openai"])
+            .get();
+        assert!((no_token - 1.0).abs() < f64::EPSILON);
+
+        let output = metrics.gather();
+        assert!(output.contains(
+            "llm_gateway_ttft_seconds_bucket{model=\"gpt-4\",provider=\"openai\",le=\"0.5\"} 1"
+        ));
+        assert!(output.contains("llm_gateway_stream_no_token_total"));
+    }
+
+    #[test]
+    fn test_provider_payload_sizes() {
+        let config = MetricsConfig::default();
+        let metrics = Metrics::new(&config).unwrap();
+
+        metrics.record_provider_request_bytes("openai", "gpt-4", 800);
+        metrics.record_provider_response_bytes("openai", "gpt-4", 20_000);
+        metrics.record_provider_response_bytes("openai", "gpt-4", 100);
+
+        let requests = metrics
+            .provider_request_bytes
+            .with_label_values(&["openai", "gpt-4"]);
+        assert_eq!(requests.get_sample_count(), 1);
+        assert!((requests.get_sample_sum() - 800.0).abs() < f64::EPSILON);
+
+        let responses = metrics
+            .provider_response_bytes
+            .with_label_values(&["openai", "gpt-4"]);
+        assert_eq!(responses.get_sample_count(), 2);
+
+        let output = metrics.gather();
+        let expected = [
+            "llm_gateway_provider_request_bytes_bucket{model=\"gpt-4\",provider=\"openai\",le=\"256\"} 0",
+            "llm_gateway_provider_request_bytes_bucket{model=\"gpt-4\",provider=\"openai\",le=\"1024\"} 1",
+            "llm_gateway_provider_response_bytes_bucket{model=\"gpt-4\",provider=\"openai\",le=\"256\"} 1",
+            "llm_gateway_provider_response_bytes_bucket{model=\"gpt-4\",provider=\"openai\",le=\"16384\"} 1",
+            "llm_gateway_provider_response_bytes_bucket{model=\"gpt-4\",provider=\"openai\",le=\"65536\"} 2",
+        ];
+        for line in expected {
+            assert!(output.contains(line), "missing `{line}` in:\n{output}");
+        }
+    }
+
+    #[test]
+    fn test_shadow_diff() {
+        let metrics = Metrics::new(&MetricsConfig::default()).unwrap();
+
+        metrics.record_shadow_diff(
+            "openai",
+            "anthropic",
+            &ResponseDiff {
+                token_overlap: 0.25,
+                length_delta: -12,
+                finish_reason_match: false,
+                embedding_similarity: Some(0.85),
+            },
+        );
+
+        let output = metrics.gather();
+        let expected = [
+            "llm_gateway_shadow_token_overlap_bucket{primary=\"openai\",shadow=\"anthropic\",le=\"0.2\"} 0",
+            "llm_gateway_shadow_token_overlap_bucket{primary=\"openai\",shadow=\"anthropic\",le=\"0.3\"} 1",
+            "llm_gateway_shadow_length_delta_bucket{primary=\"openai\",shadow=\"anthropic\",le=\"-5\"} 1",
+            "llm_gateway_shadow_finish_reason_mismatches_total{primary=\"openai\",shadow=\"anthropic\"} 1",
+            "llm_gateway_shadow_embedding_similarity_bucket{primary=\"openai\",shadow=\"anthropic\",le=\"0.9\"} 1",
+        ];
+        for line in expected {
+            assert!(output.contains(line), "missing `{line}` in:\n{output}");
+        }
+    }
+
     #[test]
     fn test_gather_output() {
         let config = MetricsConfig::default();
@@ -6,12 +6,20 @@
 //! - Provider selection tracking
 //! - Error categorization
 
+use crate::slo::SloMonitor;
+use crate::truncate::ContentLimit;
 use chrono::{DateTime, Utc};
+use gateway_core::RequestContext;
 use parking_lot::RwLock;
+use std::borrow::Cow;
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tracing::{debug, info, warn};
 
+/// Status recorded for requests the client abandoned, as used by nginx
+const CLIENT_CLOSED_REQUEST: u16 = 499;
+
 /// Request tracker for monitoring request lifecycle
 pub struct RequestTracker {
     /// Active requests
@@ -20,6 +28,10 @@ pub struct RequestTracker {
     completed: RwLock<Vec<RequestOutcome>>,
     /// Maximum completed requests to keep
     max_completed: usize,
+    /// SLO monitor fed with every completed request
+    slo: Option<Arc<SloMonitor>>,
+    /// Cap on recorded error messages
+    content_limit: Option<ContentLimit>,
 }
 
 /// Information about an active request
@@ -41,6 +53,20 @@ pub struct RequestInfo {
     pub client_ip: Option<String>,
     /// User agent
     pub user_agent: Option<String>,
+    /// Credential that made the request
+    pub owner: Option<RequestOwner>,
+}
+
+/// Credential a request was made with, as established by authentication
+///
+/// Unlike [`RequestInfo::tenant_id`], this never comes from client headers,
+/// so it can be used to authorize actions on the request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestOwner {
+    /// Credential identity
+    pub principal: String,
+    /// Tenant bound to the credential
+    pub tenant_id: Option<String>,
 }
 
 impl RequestInfo {
@@ -56,6 +82,7 @@ impl RequestInfo {
             started_at: Utc::now(),
             client_ip: None,
             user_agent: None,
+            owner: None,
         }
     }
 
@@ -73,6 +100,16 @@ impl RequestInfo {
         self
     }
 
+    /// Set the credential that made the request
+    #[must_use]
+    pub fn with_owner(mut self, principal: impl Into<String>, tenant_id: Option<String>) -> Self {
+        self.owner = Some(RequestOwner {
+            principal: principal.into(),
+            tenant_id,
+        });
+        self
+    }
+
     /// Set streaming
     #[must_use]
     pub fn with_streaming(mut self, streaming: bool) -> Self {
@@ -94,6 +131,19 @@ struct TrackedRequest {
     start_instant: Instant,
     first_token_at: Option<Instant>,
     tokens_received: u32,
+    /// Context cancelled by [`RequestTracker::cancel`]
+    ctx: Option<RequestContext>,
+}
+
+/// How a request ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutcomeKind {
+    /// The response was delivered in full
+    Completed,
+    /// The gateway or an upstream failed the request
+    Failed,
+    /// The client went away before a streamed response finished
+    ClientDisconnect,
 }
 
 /// Outcome of a completed request
@@ -113,6 +163,8 @@ pub struct RequestOutcome {
     pub status_code: u16,
     /// Whether successful
     pub success: bool,
+    /// How the request ended
+    pub kind: OutcomeKind,
     /// Error message if failed
     pub error: Option<String>,
     /// Completion time
@@ -127,11 +179,38 @@ impl RequestTracker {
             active: RwLock::new(HashMap::new()),
             completed: RwLock::new(Vec::with_capacity(max_completed)),
             max_completed,
+            slo: None,
+            content_limit: None,
         }
     }
 
+    /// Truncate recorded error messages longer than `limit`
+    #[must_use]
+    pub fn with_content_limit(mut self, limit: ContentLimit) -> Self {
+        self.content_limit = Some(limit);
+        self
+    }
+
+    /// Feed completed requests into an SLO monitor
+    ///
+    /// Only server-side failures (status 5xx) count against availability.
+    #[must_use]
+    pub fn with_slo(mut self, slo: Arc<SloMonitor>) -> Self {
+        self.slo = Some(slo);
+        self
+    }
+
     /// Start tracking a request
     pub fn start(&self, info: RequestInfo) {
+        self.track(info, None);
+    }
+
+    /// Start tracking a request that can be cancelled through `ctx`
+    pub fn start_with_context(&self, info: RequestInfo, ctx: RequestContext) {
+        self.track(info, Some(ctx));
+    }
+
+    fn track(&self, info: RequestInfo, ctx: Option<RequestContext>) {
         let request_id = info.request_id.clone();
 
         debug!(
@@ -147,6 +226,7 @@ impl RequestTracker {
             start_instant: Instant::now(),
             first_token_at: None,
             tokens_received: 0,
+            ctx,
         };
 
         self.active.write().insert(request_id, tracked);
@@ -180,6 +260,23 @@ impl RequestTracker {
         }
     }
 
+    /// Cancel an active request
+    ///
+    /// Returns whether a cancellation occurred: `false` if the request is not
+    /// active, was started without a context, or was already cancelled.
+    pub fn cancel(&self, request_id: &str) -> bool {
+        let active = self.active.read();
+        let Some(ctx) = active.get(request_id).and_then(|t| t.ctx.as_ref()) else {
+            return false;
+        };
+        if ctx.is_cancelled() {
+            return false;
+        }
+        ctx.cancel();
+        info!(request_id = %request_id, "Request cancelled");
+        true
+    }
+
     /// Complete a request successfully
     pub fn complete_success(
         &self,
@@ -188,12 +285,44 @@ impl RequestTracker {
         input_tokens: Option<u32>,
         output_tokens: Option<u32>,
     ) {
-        self.complete(request_id, status_code, true, None, input_tokens, output_tokens);
+        self.complete(
+            request_id,
+            status_code,
+            OutcomeKind::Completed,
+            None,
+            input_tokens,
+            output_tokens,
+        );
     }
 
     /// Complete a request with an error
     pub fn complete_error(&self, request_id: &str, status_code: u16, error: impl Into<String>) {
-        self.complete(request_id, status_code, false, Some(error.into()), None, None);
+        let mut error = error.into();
+        if let Some(limit) = &self.content_limit {
+            if let Cow::Owned(cut) = limit.truncate(&error) {
+                error = cut;
+            }
+        }
+        self.complete(request_id, status_code, OutcomeKind::Failed, Some(error), None, None);
+    }
+
+    /// Complete a streaming request the client abandoned
+    ///
+    /// The request's context is cancelled, aborting the upstream call, and
+    /// the outcome records the tokens streamed up to the disconnect with
+    /// status `499`. Disconnects do not count against the SLO.
+    pub fn complete_client_disconnect(&self, request_id: &str, input_tokens: Option<u32>) {
+        if let Some(ctx) = self.active.read().get(request_id).and_then(|t| t.ctx.as_ref()) {
+            ctx.cancel();
+        }
+        self.complete(
+            request_id,
+            CLIENT_CLOSED_REQUEST,
+            OutcomeKind::ClientDisconnect,
+            None,
+            input_tokens,
+            None,
+        );
     }
 
     /// Complete a request
@@ -201,12 +330,13 @@ impl RequestTracker {
         &self,
         request_id: &str,
         status_code: u16,
-        success: bool,
+        kind: OutcomeKind,
         error: Option<String>,
         input_tokens: Option<u32>,
         output_tokens: Option<u32>,
     ) {
         let tracked = self.active.write().remove(request_id);
+        let success = kind == OutcomeKind::Completed;
 
         if let Some(tracked) = tracked {
             let duration = tracked.start_instant.elapsed();
@@ -222,12 +352,13 @@ impl RequestTracker {
                 output_tokens: output_tokens.or(Some(tracked.tokens_received)),
                 status_code,
                 success,
+                kind,
                 error: error.clone(),
                 completed_at: Utc::now(),
             };
 
-            if success {
-                info!(
+            match kind {
+                OutcomeKind::Completed => info!(
                     request_id = %request_id,
                     model = %tracked.info.model,
                     provider = ?tracked.info.provider,
@@ -236,9 +367,16 @@ impl RequestTracker {
                     output_tokens = ?output_tokens,
                     status = status_code,
                     "Request completed successfully"
-                );
-            } else {
-                warn!(
+                ),
+                OutcomeKind::ClientDisconnect => info!(
+                    request_id = %request_id,
+                    model = %tracked.info.model,
+                    provider = ?tracked.info.provider,
+                    duration_ms = duration.as_millis(),
+                    output_tokens = tracked.tokens_received,
+                    "Client disconnected mid-stream"
+                ),
+                OutcomeKind::Failed => warn!(
                     request_id = %request_id,
                     model = %tracked.info.model,
                     provider = ?tracked.info.provider,
@@ -246,7 +384,11 @@ impl RequestTracker {
                     status = status_code,
                     error = ?error,
                     "Request failed"
-                );
+                ),
+            }
+
+            if let Some(slo) = &self.slo {
+                slo.record(success || status_code < 500, duration);
             }
 
             // Store in completed ring buffer
@@ -283,6 +425,17 @@ impl RequestTracker {
         completed.iter().rev().take(limit).cloned().collect()
     }
 
+    /// Get a completed request still in the buffer
+    #[must_use]
+    pub fn get_completed(&self, request_id: &str) -> Option<RequestOutcome> {
+        let completed = self.completed.read();
+        completed
+            .iter()
+            .rev()
+            .find(|r| r.info.request_id == request_id)
+            .cloned()
+    }
+
     /// Get statistics
     #[must_use]
     pub fn stats(&self) -> TrackerStats {
@@ -290,7 +443,11 @@ impl RequestTracker {
 
         let total = completed.len();
         let successful = completed.iter().filter(|r| r.success).count();
-        let failed = total - successful;
+        let client_disconnects = completed
+            .iter()
+            .filter(|r| r.kind == OutcomeKind::ClientDisconnect)
+            .count();
+        let failed = total - successful - client_disconnects;
 
         let avg_duration = if total > 0 {
             let sum: Duration = completed.iter().map(|r| r.duration).sum();
@@ -329,8 +486,10 @@ impl RequestTracker {
             total_completed: total,
             successful,
             failed,
-            success_rate: if total > 0 {
-                successful as f64 / total as f64
+            client_disconnects,
+            // Client disconnects are neither successes nor failures
+            success_rate: if successful + failed > 0 {
+                successful as f64 / (successful + failed) as f64
             } else {
                 1.0
             },
@@ -358,6 +517,8 @@ pub struct TrackerStats {
     pub successful: usize,
     /// Failed requests
     pub failed: usize,
+    /// Streaming requests abandoned by the client
+    pub client_disconnects: usize,
     /// Success rate
     pub success_rate: f64,
     /// Average request duration
@@ -397,6 +558,27 @@ mod tests {
         let completed = tracker.get_recent_completed(10);
         assert_eq!(completed.len(), 1);
         assert!(completed[0].success);
+
+        let outcome = tracker.get_completed("req-1").unwrap();
+        assert_eq!(outcome.input_tokens, Some(100));
+        assert!(tracker.get_completed("req-2").is_none());
+    }
+
+    #[test]
+    fn test_cancel() {
+        let tracker = RequestTracker::new(100);
+        let ctx = RequestContext::new();
+
+        tracker.start_with_context(RequestInfo::new("req-1", "gpt-4"), ctx.clone());
+        tracker.start(RequestInfo::new("req-2", "gpt-4"));
+
+        assert!(tracker.cancel("req-1"));
+        assert!(ctx.is_cancelled());
+        // Already cancelled
+        assert!(!tracker.cancel("req-1"));
+        // Not cancellable, or unknown
+        assert!(!tracker.cancel("req-2"));
+        assert!(!tracker.cancel("req-3"));
     }
 
     #[test]
@@ -422,6 +604,37 @@ mod tests {
         assert_eq!(completed[0].output_tokens, Some(30));
     }
 
+    #[test]
+    fn test_client_disconnect() {
+        let tracker = RequestTracker::new(100);
+        let ctx = RequestContext::new();
+
+        tracker.start_with_context(
+            RequestInfo::new("req-1", "gpt-4").with_streaming(true),
+            ctx.clone(),
+        );
+        tracker.record_first_token("req-1");
+        tracker.record_tokens("req-1", 12);
+        tracker.complete_client_disconnect("req-1", Some(40));
+
+        assert!(ctx.is_cancelled());
+        let outcome = tracker.get_completed("req-1").unwrap();
+        assert_eq!(outcome.kind, OutcomeKind::ClientDisconnect);
+        assert!(!outcome.success);
+        assert_eq!(outcome.status_code, 499);
+        assert_eq!(outcome.input_tokens, Some(40));
+        assert_eq!(outcome.output_tokens, Some(12));
+        assert!(outcome.error.is_none());
+
+        tracker.start(RequestInfo::new("req-2", "gpt-4"));
+        tracker.complete_success("req-2", 200, None, None);
+
+        let stats = tracker.stats();
+        assert_eq!(stats.client_disconnects, 1);
+        assert_eq!(stats.failed, 0);
+        assert!((stats.success_rate - 1.0).abs() < f64::EPSILON);
+    }
+
     #[test]
     fn test_error_tracking() {
         let tracker = RequestTracker::new(100);
@@ -433,9 +646,21 @@ mod tests {
 
         let completed = tracker.get_recent_completed(1);
         assert!(!completed[0].success);
+        assert_eq!(completed[0].kind, OutcomeKind::Failed);
         assert_eq!(completed[0].error, Some("Internal error".to_string()));
     }
 
+    #[test]
+    fn test_long_errors_are_truncated() {
+        let tracker = RequestTracker::new(100).with_content_limit(ContentLimit::new(9));
+        tracker.start(RequestInfo::new("req-1", "gpt-4"));
+
+        tracker.complete_error("req-1", 502, format!("upstream: {}", "x".repeat(500)));
+
+        let outcome = tracker.get_completed("req-1").unwrap();
+        assert_eq!(outcome.error.as_deref(), Some("upstream:...[truncated from 510 bytes]"));
+    }
+
     #[test]
     fn test_ring_buffer() {
         let tracker = RequestTracker::new(3);
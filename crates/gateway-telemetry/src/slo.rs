@@ -0,0 +1,517 @@
+//! SLO error-budget burn-rate tracking.
+//!
+//! Request outcomes are counted in fixed-width time buckets. For each SLI
+//! (availability and latency) the burn rate over a window is the observed bad
+//! ratio divided by the error budget (`1 - target`): a burn rate of 1 spends
+//! the budget exactly over the SLO period, 14.4 spends a 30-day budget in two
+//! days.
+//!
+//! Alerts use the multi-window scheme: a fast or slow burn fires only while
+//! both its long and its short window exceed the threshold, so it triggers
+//! on a sustained spike and clears soon after recovery. Burn rates are
+//! exported as gauges, and transitions are broadcast as [`SloEvent`]s for
+//! mechanisms such as spend limits or provider quarantine to act on.
+
+use crate::metrics::Metrics;
+use parking_lot::Mutex;
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+use tracing::{info, warn};
+
+/// Service level indicator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Sli {
+    /// Fraction of requests that did not fail server-side
+    Availability,
+    /// Fraction of successful requests faster than the latency threshold
+    Latency,
+}
+
+impl Sli {
+    /// Label value for metrics
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Availability => "availability",
+            Self::Latency => "latency",
+        }
+    }
+}
+
+impl fmt::Display for Sli {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Burn-rate alert severity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BurnSeverity {
+    /// Budget is being spent quickly (page-worthy)
+    Fast,
+    /// Budget is being spent steadily faster than sustainable
+    Slow,
+}
+
+impl BurnSeverity {
+    /// Label value for metrics
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Fast => "fast",
+            Self::Slow => "slow",
+        }
+    }
+}
+
+impl fmt::Display for BurnSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Window pair and threshold for one burn-rate alert
+#[derive(Debug, Clone, Copy)]
+pub struct BurnWindow {
+    /// Long window, which must show sustained burn
+    pub long: Duration,
+    /// Short window, which must still be burning
+    pub short: Duration,
+    /// Burn rate both windows must exceed
+    pub threshold: f64,
+}
+
+/// SLO burn-rate configuration
+#[derive(Debug, Clone)]
+pub struct SloConfig {
+    /// Target fraction of requests that succeed (e.g. 0.999)
+    pub availability_target: f64,
+    /// Requests slower than this count against the latency SLO
+    pub latency_threshold: Duration,
+    /// Target fraction of successful requests under the latency threshold
+    pub latency_target: f64,
+    /// Fast-burn alert
+    pub fast_burn: BurnWindow,
+    /// Slow-burn alert
+    pub slow_burn: BurnWindow,
+    /// Width of the counting buckets, and the minimum time between
+    /// evaluations triggered by new outcomes
+    pub bucket_width: Duration,
+}
+
+impl Default for SloConfig {
+    fn default() -> Self {
+        Self {
+            availability_target: 0.999,
+            latency_threshold: Duration::from_secs(10),
+            latency_target: 0.99,
+            fast_burn: BurnWindow {
+                long: Duration::from_secs(60 * 60),
+                short: Duration::from_secs(5 * 60),
+                threshold: 14.4,
+            },
+            slow_burn: BurnWindow {
+                long: Duration::from_secs(6 * 60 * 60),
+                short: Duration::from_secs(30 * 60),
+                threshold: 6.0,
+            },
+            bucket_width: Duration::from_secs(10),
+        }
+    }
+}
+
+/// Burn rate for one SLI and severity at the last evaluation
+#[derive(Debug, Clone, PartialEq)]
+pub struct BurnRate {
+    /// Indicator
+    pub sli: Sli,
+    /// Alert severity
+    pub severity: BurnSeverity,
+    /// Burn rate over the long window
+    pub long_rate: f64,
+    /// Burn rate over the short window
+    pub short_rate: f64,
+    /// Threshold both windows are compared against
+    pub threshold: f64,
+    /// Whether the alert is firing
+    pub firing: bool,
+}
+
+/// Burn-rate alert transition
+#[derive(Debug, Clone, PartialEq)]
+pub enum SloEvent {
+    /// A burn-rate alert started firing
+    BurnStarted(BurnRate),
+    /// A firing burn-rate alert cleared
+    BurnCleared(BurnRate),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    start: Instant,
+    total: u64,
+    errors: u64,
+    succeeded: u64,
+    slow: u64,
+}
+
+struct SloState {
+    buckets: VecDeque<Bucket>,
+    firing: HashSet<(Sli, BurnSeverity)>,
+    last_evaluated: Option<Instant>,
+}
+
+/// Rolling SLO burn-rate monitor
+pub struct SloMonitor {
+    config: SloConfig,
+    state: Mutex<SloState>,
+    events: broadcast::Sender<SloEvent>,
+    metrics: Option<Arc<Metrics>>,
+}
+
+impl SloMonitor {
+    /// Create a new monitor
+    #[must_use]
+    pub fn new(config: SloConfig) -> Self {
+        let (events, _) = broadcast::channel(64);
+        Self {
+            config,
+            state: Mutex::new(SloState {
+                buckets: VecDeque::new(),
+                firing: HashSet::new(),
+                last_evaluated: None,
+            }),
+            events,
+            metrics: None,
+        }
+    }
+
+    /// Export burn rates as gauges on `metrics`
+    #[must_use]
+    pub fn with_metrics(mut self, metrics: Arc<Metrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Get the configuration
+    #[must_use]
+    pub fn config(&self) -> &SloConfig {
+        &self.config
+    }
+
+    /// Subscribe to burn-rate alert transitions
+    #[must_use]
+    pub fn subscribe(&self) -> broadcast::Receiver<SloEvent> {
+        self.events.subscribe()
+    }
+
+    /// Record a request outcome
+    ///
+    /// `success` is false only for server-side failures; client errors
+    /// should not spend the error budget.
+    pub fn record(&self, success: bool, latency: Duration) {
+        self.record_at(Instant::now(), success, latency);
+    }
+
+    /// Record a request outcome observed at `now`
+    pub fn record_at(&self, now: Instant, success: bool, latency: Duration) {
+        let due = {
+            let mut state = self.state.lock();
+            let needs_bucket = state
+                .buckets
+                .back()
+                .map_or(true, |bucket| now.duration_since(bucket.start) >= self.config.bucket_width);
+            if needs_bucket {
+                state.buckets.push_back(Bucket {
+                    start: now,
+                    total: 0,
+                    errors: 0,
+                    succeeded: 0,
+                    slow: 0,
+                });
+            }
+
+            if let Some(bucket) = state.buckets.back_mut() {
+                bucket.total += 1;
+                if success {
+                    bucket.succeeded += 1;
+                    if latency > self.config.latency_threshold {
+                        bucket.slow += 1;
+                    }
+                } else {
+                    bucket.errors += 1;
+                }
+            }
+
+            self.prune(&mut state, now);
+            state
+                .last_evaluated
+                .map_or(true, |at| now.duration_since(at) >= self.config.bucket_width)
+        };
+
+        if due {
+            self.evaluate_at(now);
+        }
+    }
+
+    fn prune(&self, state: &mut SloState, now: Instant) {
+        let retain = self.config.fast_burn.long.max(self.config.slow_burn.long);
+        while state
+            .buckets
+            .front()
+            .is_some_and(|bucket| now.duration_since(bucket.start) > retain)
+        {
+            state.buckets.pop_front();
+        }
+    }
+
+    /// Evaluate burn rates now
+    pub fn evaluate(&self) -> Vec<BurnRate> {
+        self.evaluate_at(Instant::now())
+    }
+
+    /// Evaluate burn rates as of `now`, updating gauges and emitting events
+    /// for alerts that started or cleared
+    pub fn evaluate_at(&self, now: Instant) -> Vec<BurnRate> {
+        let mut transitions = Vec::new();
+        let rates = {
+            let mut state = self.state.lock();
+            self.prune(&mut state, now);
+            state.last_evaluated = Some(now);
+
+            let mut rates = Vec::with_capacity(4);
+            for sli in [Sli::Availability, Sli::Latency] {
+                for (severity, window) in [
+                    (BurnSeverity::Fast, self.config.fast_burn),
+                    (BurnSeverity::Slow, self.config.slow_burn),
+                ] {
+                    let long_rate = self.burn_rate(&state.buckets, sli, window.long, now);
+                    let short_rate = self.burn_rate(&state.buckets, sli, window.short, now);
+                    let firing = long_rate > window.threshold && short_rate > window.threshold;
+                    let rate = BurnRate {
+                        sli,
+                        severity,
+                        long_rate,
+                        short_rate,
+                        threshold: window.threshold,
+                        firing,
+                    };
+
+                    let was_firing = if firing {
+                        !state.firing.insert((sli, severity))
+                    } else {
+                        state.firing.remove(&(sli, severity))
+                    };
+                    if firing && !was_firing {
+                        transitions.push(SloEvent::BurnStarted(rate.clone()));
+                    } else if !firing && was_firing {
+                        transitions.push(SloEvent::BurnCleared(rate.clone()));
+                    }
+                    rates.push(rate);
+                }
+            }
+            rates
+        };
+
+        if let Some(metrics) = &self.metrics {
+            for rate in &rates {
+                metrics.update_slo_burn_rate(rate);
+            }
+        }
+
+        for event in transitions {
+            match &event {
+                SloEvent::BurnStarted(rate) => warn!(
+                    sli = %rate.sli,
+                    severity = %rate.severity,
+                    long_rate = rate.long_rate,
+                    short_rate = rate.short_rate,
+                    threshold = rate.threshold,
+                    "SLO burn rate alert firing"
+                ),
+                SloEvent::BurnCleared(rate) => info!(
+                    sli = %rate.sli,
+                    severity = %rate.severity,
+                    "SLO burn rate alert cleared"
+                ),
+            }
+            // No subscribers is fine; the gauges still carry the signal
+            let _ = self.events.send(event);
+        }
+
+        rates
+    }
+
+    /// Whether the alert for `sli` and `severity` is firing
+    #[must_use]
+    pub fn is_firing(&self, sli: Sli, severity: BurnSeverity) -> bool {
+        self.state.lock().firing.contains(&(sli, severity))
+    }
+
+    fn burn_rate(
+        &self,
+        buckets: &VecDeque<Bucket>,
+        sli: Sli,
+        window: Duration,
+        now: Instant,
+    ) -> f64 {
+        let (bad, total, target) = buckets
+            .iter()
+            .rev()
+            .take_while(|bucket| now.duration_since(bucket.start) < window)
+            .fold((0u64, 0u64, 0.0), |(bad, total, _), bucket| match sli {
+                Sli::Availability => (
+                    bad + bucket.errors,
+                    total + bucket.total,
+                    self.config.availability_target,
+                ),
+                Sli::Latency => (
+                    bad + bucket.slow,
+                    total + bucket.succeeded,
+                    self.config.latency_target,
+                ),
+            });
+
+        let budget = 1.0 - target;
+        if total == 0 || budget <= 0.0 {
+            return 0.0;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let bad_ratio = bad as f64 / total as f64;
+        bad_ratio / budget
+    }
+}
+
+impl Default for SloMonitor {
+    fn default() -> Self {
+        Self::new(SloConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor() -> SloMonitor {
+        SloMonitor::new(SloConfig {
+            availability_target: 0.99,
+            latency_threshold: Duration::from_secs(2),
+            latency_target: 0.99,
+            ..SloConfig::default()
+        })
+    }
+
+    /// Record `count` outcomes spread one second apart from `start`
+    fn record_many(
+        monitor: &SloMonitor,
+        start: Instant,
+        count: u64,
+        success: bool,
+        latency: Duration,
+    ) -> Instant {
+        let mut now = start;
+        for _ in 0..count {
+            monitor.record_at(now, success, latency);
+            now += Duration::from_secs(1);
+        }
+        now
+    }
+
+    #[test]
+    fn test_healthy_traffic_does_not_fire() {
+        let monitor = monitor();
+        let now = record_many(&monitor, Instant::now(), 600, true, Duration::from_millis(100));
+
+        let rates = monitor.evaluate_at(now);
+        assert!(rates.iter().all(|rate| !rate.firing));
+        assert!(rates.iter().all(|rate| rate.long_rate == 0.0));
+    }
+
+    #[test]
+    fn test_error_spike_fires_fast_burn_and_recovery_clears_it() {
+        let monitor = monitor();
+        let mut events = monitor.subscribe();
+        let start = Instant::now();
+
+        // Steady traffic, then a burst where every other request fails
+        let mut now = record_many(&monitor, start, 300, true, Duration::from_millis(100));
+        for _ in 0..120 {
+            monitor.record_at(now, false, Duration::from_millis(100));
+            monitor.record_at(now, true, Duration::from_millis(100));
+            now += Duration::from_secs(1);
+        }
+        monitor.evaluate_at(now);
+
+        assert!(monitor.is_firing(Sli::Availability, BurnSeverity::Fast));
+        assert!(!monitor.is_firing(Sli::Latency, BurnSeverity::Fast));
+        let started = std::iter::from_fn(|| events.try_recv().ok())
+            .find_map(|event| match event {
+                SloEvent::BurnStarted(rate) if rate.severity == BurnSeverity::Fast => Some(rate),
+                _ => None,
+            })
+            .expect("fast burn started event");
+        assert_eq!(started.sli, Sli::Availability);
+        assert!(started.short_rate > started.threshold);
+
+        // Errors stop; once the short window is clean the alert clears even
+        // though the long window still remembers the spike
+        let now = record_many(&monitor, now, 400, true, Duration::from_millis(100));
+        let rates = monitor.evaluate_at(now);
+
+        assert!(!monitor.is_firing(Sli::Availability, BurnSeverity::Fast));
+        let fast = rates
+            .iter()
+            .find(|r| r.sli == Sli::Availability && r.severity == BurnSeverity::Fast)
+            .expect("fast availability rate");
+        assert!(fast.long_rate > 0.0);
+        assert!(fast.short_rate < fast.threshold);
+
+        let cleared = std::iter::from_fn(|| events.try_recv().ok())
+            .find_map(|event| match event {
+                SloEvent::BurnCleared(rate) => Some(rate),
+                SloEvent::BurnStarted(_) => None,
+            })
+            .expect("burn cleared event");
+        assert_eq!(cleared.sli, Sli::Availability);
+        assert_eq!(cleared.severity, BurnSeverity::Fast);
+    }
+
+    #[test]
+    fn test_slow_requests_burn_latency_budget() {
+        let monitor = monitor();
+        let mut now = Instant::now();
+        for _ in 0..300 {
+            monitor.record_at(now, true, Duration::from_secs(5));
+            monitor.record_at(now, true, Duration::from_millis(100));
+            now += Duration::from_secs(1);
+        }
+        monitor.evaluate_at(now);
+
+        assert!(monitor.is_firing(Sli::Latency, BurnSeverity::Fast));
+        assert!(!monitor.is_firing(Sli::Availability, BurnSeverity::Fast));
+    }
+
+    #[test]
+    fn test_burn_rates_exported_as_gauges() {
+        let metrics = Arc::new(Metrics::new(&crate::metrics::MetricsConfig::default()).unwrap());
+        let monitor = monitor().with_metrics(Arc::clone(&metrics));
+
+        let mut now = Instant::now();
+        for _ in 0..60 {
+            monitor.record_at(now, false, Duration::from_millis(100));
+            now += Duration::from_secs(1);
+        }
+        monitor.evaluate_at(now);
+
+        let output = metrics.gather();
+        assert!(output.contains(
+            "llm_gateway_slo_burn_alert{severity=\"fast\",sli=\"availability\"} 1"
+        ));
+        assert!(output.contains(
+            "llm_gateway_slo_burn_rate{severity=\"fast\",sli=\"availability\",window=\"short\"} 99.9"
+        ));
+    }
+}
@@ -0,0 +1,141 @@
+//! Length caps for stored and logged content.
+//!
+//! Long generations make audit logs, request records and persisted
+//! exchanges expensive to keep. A [`ContentLimit`] cuts any string longer
+//! than its cap and appends [`TRUNCATION_MARKER`] followed by the original
+//! length in bytes, so a reader can tell content was shortened and by how
+//! much. Only the stored or logged copy is cut; what the client receives is
+//! never touched.
+
+use serde_json::Value;
+use std::borrow::Cow;
+
+/// Marker appended to truncated content, followed by `<bytes> bytes]`
+pub const TRUNCATION_MARKER: &str = "...[truncated from ";
+
+/// Maximum length, in bytes, of content that is stored or logged
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContentLimit {
+    max_len: usize,
+}
+
+impl ContentLimit {
+    /// Cap content at `max_len` bytes
+    #[must_use]
+    pub fn new(max_len: usize) -> Self {
+        Self { max_len }
+    }
+
+    /// The cap in bytes
+    #[must_use]
+    pub fn max_len(&self) -> usize {
+        self.max_len
+    }
+
+    /// Cut `text` to the cap, marked with its original length
+    ///
+    /// The cut falls on a character boundary at or below the cap, so the
+    /// kept prefix may be a few bytes shorter.
+    #[must_use]
+    pub fn truncate<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        if text.len() <= self.max_len {
+            return Cow::Borrowed(text);
+        }
+        let mut end = self.max_len;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        Cow::Owned(format!(
+            "{}{TRUNCATION_MARKER}{} bytes]",
+            &text[..end],
+            text.len()
+        ))
+    }
+
+    /// Cut every string inside a JSON value, returning whether any was cut
+    ///
+    /// Truncating strings rather than the serialized document keeps the
+    /// result valid JSON.
+    pub fn truncate_json(&self, value: &mut Value) -> bool {
+        match value {
+            Value::String(s) => match self.truncate(s) {
+                Cow::Owned(cut) => {
+                    *s = cut;
+                    true
+                }
+                Cow::Borrowed(_) => false,
+            },
+            Value::Array(items) => items
+                .iter_mut()
+                .fold(false, |cut, item| self.truncate_json(item) | cut),
+            Value::Object(fields) => fields
+                .values_mut()
+                .fold(false, |cut, field| self.truncate_json(field) | cut),
+            _ => false,
+        }
+    }
+
+    /// Cut the strings inside serialized JSON, or the text itself if it is
+    /// not JSON
+    #[must_use]
+    pub fn truncate_serialized<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        if text.len() <= self.max_len {
+            return Cow::Borrowed(text);
+        }
+        match serde_json::from_str::<Value>(text) {
+            Ok(mut value) => {
+                if self.truncate_json(&mut value) {
+                    Cow::Owned(value.to_string())
+                } else {
+                    Cow::Borrowed(text)
+                }
+            }
+            Err(_) => self.truncate(text),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_short_content_is_untouched() {
+        let limit = ContentLimit::new(10);
+        assert!(matches!(limit.truncate("hello"), Cow::Borrowed("hello")));
+        assert!(matches!(limit.truncate("0123456789"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_long_content_is_marked_with_original_length() {
+        let limit = ContentLimit::new(5);
+        assert_eq!(limit.truncate("hello world"), "hello...[truncated from 11 bytes]");
+
+        // Never splits a character
+        assert_eq!(limit.truncate("héllo wörld"), "héll...[truncated from 13 bytes]");
+    }
+
+    #[test]
+    fn test_json_strings_are_cut_and_document_stays_valid() {
+        let limit = ContentLimit::new(4);
+        let mut value = json!({
+            "id": "abc",
+            "choices": [{"message": {"content": "a long generation"}}],
+            "usage": {"total_tokens": 12},
+        });
+        assert!(limit.truncate_json(&mut value));
+        assert_eq!(value["id"], "abc");
+        assert_eq!(
+            value["choices"][0]["message"]["content"],
+            "a lo...[truncated from 17 bytes]"
+        );
+        assert_eq!(value["usage"]["total_tokens"], 12);
+
+        let serialized = json!({"content": "a long generation"}).to_string();
+        let cut = limit.truncate_serialized(&serialized);
+        let parsed: Value = serde_json::from_str(&cut).unwrap();
+        assert_eq!(parsed["content"], "a lo...[truncated from 17 bytes]");
+        assert_eq!(limit.truncate_serialized("not json at all"), "not ...[truncated from 15 bytes]");
+    }
+}
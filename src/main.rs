@@ -25,11 +25,18 @@
 
 use gateway_config::{load_config, GatewayConfig};
 use gateway_core::ProviderType;
-use gateway_providers::{AnthropicProvider, OpenAIProvider, ProviderRegistry};
+use gateway_providers::{
+    AnthropicProvider, EgressPolicy, OpenAIProvider, ProviderDefinition, ProviderRegistry,
+    RegistryBuilder,
+};
 use gateway_resilience::RetryPolicy;
-use gateway_routing::{Router, RouterConfig};
+use gateway_routing::{
+    ContextRoutingConfig, CooldownConfig, LoadBalancerConfig, Router, RouterConfig,
+};
 use gateway_server::{AppState, Server, ServerConfig};
 use gateway_telemetry::{init_logging, LoggingConfig, Metrics, MetricsConfig};
+#[cfg(feature = "persistence")]
+use gateway_telemetry::{AuditEventBuilder, AuditEventType, AuditOutcome};
 use std::env;
 use std::sync::Arc;
 use tracing::{error, info, warn};
@@ -76,8 +83,32 @@ async fn run() -> Result<(), Box<dyn std::error::Error>> {
         "Provider registry initialized"
     );
 
+    // Keep connection pools warm for providers with `pool.min_idle` set
+    let pool_maintenance = registry.start_pool_maintenance();
+    if !pool_maintenance.is_empty() {
+        info!(
+            providers = pool_maintenance.len(),
+            "Connection pool warmup started"
+        );
+    }
+
     // Create router
-    let router_config = RouterConfig::new().with_default_providers(registry.provider_ids());
+    let mut load_balancer = LoadBalancerConfig::new();
+    let cooldown = &config.routing.cooldown;
+    if cooldown.enabled {
+        load_balancer = load_balancer.with_cooldown(CooldownConfig::new(cooldown.base, cooldown.max));
+    }
+    let context = &config.routing.context_routing;
+    let mut context_routing = ContextRoutingConfig::new()
+        .with_enabled(context.enabled)
+        .with_headroom(context.headroom);
+    if let Some(tokens) = context.large_prompt_threshold {
+        context_routing = context_routing.with_large_prompt_threshold(tokens);
+    }
+    let router_config = RouterConfig::new()
+        .with_default_providers(registry.provider_ids())
+        .with_load_balancer(load_balancer)
+        .with_context_routing(context_routing);
     let router = Router::new(router_config);
 
     // Register providers with router
@@ -92,13 +123,51 @@ async fn run() -> Result<(), Box<dyn std::error::Error>> {
     let retry_policy = RetryPolicy::with_defaults();
 
     // Build application state
-    let state = AppState::builder()
+    let builder = AppState::builder()
         .config(config.clone())
         .providers(registry)
         .router(router)
         .retry_policy(retry_policy)
-        .metrics(metrics)
-        .build();
+        .metrics(metrics);
+
+    // Attach the request/response store if persistence is enabled
+    #[cfg(feature = "persistence")]
+    let builder = match gateway_server::persistence::ExchangeStore::connect(&config.persistence)
+        .await?
+    {
+        Some(store) => {
+            let store = Arc::new(store);
+            let _sweeper = Arc::clone(&store).spawn_sweeper(config.persistence.sweep_interval);
+            info!(
+                retention_secs = config.persistence.retention.as_secs(),
+                "Request persistence enabled"
+            );
+            builder.exchange_store(store)
+        }
+        None => builder,
+    };
+
+    // Attach the audit store and its retention sweep
+    #[cfg(feature = "persistence")]
+    let (builder, audit_store) =
+        match gateway_server::audit_store::AuditStore::connect(&config.persistence).await? {
+            Some(store) => {
+                let store = Arc::new(store);
+                let _retention =
+                    Arc::clone(&store).spawn_retention(config.persistence.sweep_interval);
+                info!(
+                    retention_secs = config.persistence.audit_retention.as_secs(),
+                    "Audit persistence enabled"
+                );
+                (builder.audit_store(Arc::clone(&store)), Some(store))
+            }
+            None => (builder, None),
+        };
+
+    let state = builder.build();
+
+    #[cfg(feature = "persistence")]
+    record_lifecycle(audit_store.as_deref(), AuditEventType::SystemStartup).await;
 
     // Create server
     let server_config = ServerConfig::new()
@@ -110,21 +179,55 @@ async fn run() -> Result<(), Box<dyn std::error::Error>> {
     // Run server
     server.run().await?;
 
+    #[cfg(feature = "persistence")]
+    record_lifecycle(audit_store.as_deref(), AuditEventType::SystemShutdown).await;
+
     Ok(())
 }
 
+/// Persist a startup or shutdown event, waiting for the write to finish
+#[cfg(feature = "persistence")]
+async fn record_lifecycle(
+    store: Option<&gateway_server::audit_store::AuditStore>,
+    event_type: AuditEventType,
+) {
+    let Some(store) = store else {
+        return;
+    };
+    let event = AuditEventBuilder::new(event_type)
+        .outcome(AuditOutcome::Success)
+        .description(format!("LLM Inference Gateway v{}", env!("CARGO_PKG_VERSION")))
+        .build();
+    if let Err(e) = store.append(&event).await {
+        warn!(error = %e, "Failed to persist audit event");
+    }
+}
+
 /// Create provider registry from configuration
 fn create_provider_registry(
     config: &GatewayConfig,
 ) -> Result<ProviderRegistry, Box<dyn std::error::Error>> {
     let registry = ProviderRegistry::new();
+    let egress = EgressPolicy {
+        enabled: config.egress.enabled,
+        allowed_hosts: config.egress.allowed_hosts.clone(),
+        allow_private_networks: config.egress.allow_private_networks,
+    };
+    if egress.enabled {
+        info!(
+            allowed_hosts = ?egress.allowed_hosts,
+            allow_private_networks = egress.allow_private_networks,
+            "Outbound provider hosts restricted"
+        );
+    }
 
     // Register OpenAI provider if API key is available
     if let Ok(api_key) = env::var("OPENAI_API_KEY") {
         info!("Registering OpenAI provider from environment");
 
         let openai_config =
-            gateway_providers::openai::OpenAIConfig::new("openai", api_key);
+            gateway_providers::openai::OpenAIConfig::new("openai", api_key)
+                .with_egress(egress.clone());
         let provider = OpenAIProvider::new(openai_config)?;
         registry.register(Arc::new(provider), 100, 100)?;
     } else {
@@ -136,7 +239,8 @@ fn create_provider_registry(
         info!("Registering Anthropic provider from environment");
 
         let anthropic_config =
-            gateway_providers::anthropic::AnthropicConfig::new(api_key);
+            gateway_providers::anthropic::AnthropicConfig::new(api_key)
+                .with_egress(egress.clone());
         let provider = AnthropicProvider::new(anthropic_config)?;
         registry.register(Arc::new(provider), 100, 100)?;
     } else {
@@ -145,16 +249,10 @@ fn create_provider_registry(
 
     // Register providers from config file
     for provider_config in &config.providers {
-        if !provider_config.enabled {
+        if !provider_config.enabled || registry.get(&provider_config.id).is_some() {
             continue;
         }
-
-        let api_key = provider_config
-            .api_key
-            .clone()
-            .or_else(|| provider_config.api_key_env.as_ref().and_then(|var| env::var(var).ok()));
-
-        if api_key.is_none() {
+        if missing_api_key(provider_config) {
             warn!(
                 provider = %provider_config.id,
                 "Provider has no API key configured, skipping"
@@ -162,56 +260,30 @@ fn create_provider_registry(
             continue;
         }
 
-        let api_key = api_key.expect("api key exists");
-
-        match provider_config.provider_type {
-            ProviderType::OpenAI => {
-                if registry.get(&provider_config.id).is_none() {
-                    let mut openai_config = gateway_providers::openai::OpenAIConfig::new(
-                        &provider_config.id,
-                        &api_key,
-                    );
-                    if !provider_config.endpoint.is_empty() {
-                        openai_config = openai_config.with_base_url(&provider_config.endpoint);
-                    }
-                    let provider = OpenAIProvider::new(openai_config)?;
-                    registry.register(
-                        Arc::new(provider),
-                        provider_config.priority,
-                        provider_config.weight,
-                    )?;
-                }
-            }
-            ProviderType::Anthropic => {
-                if registry.get(&provider_config.id).is_none() {
-                    let mut anthropic_config =
-                        gateway_providers::anthropic::AnthropicConfig::new(&api_key);
-                    if !provider_config.endpoint.is_empty() {
-                        anthropic_config =
-                            anthropic_config.with_base_url(&provider_config.endpoint);
-                    }
-                    let provider =
-                        AnthropicProvider::with_id(&provider_config.id, anthropic_config)?;
-                    registry.register(
-                        Arc::new(provider),
-                        provider_config.priority,
-                        provider_config.weight,
-                    )?;
-                }
-            }
-            _ => {
-                warn!(
-                    provider = %provider_config.id,
-                    provider_type = ?provider_config.provider_type,
-                    "Unknown or unsupported provider type"
-                );
-            }
-        }
+        let definition = ProviderDefinition::from_gateway_config(provider_config)?;
+        RegistryBuilder::new()
+            .provider(definition)
+            .egress(egress.clone())
+            .build_into(&registry)?;
+        info!(
+            provider = %provider_config.id,
+            provider_type = %provider_config.provider_type,
+            "Registered provider from configuration"
+        );
     }
 
     Ok(registry)
 }
 
+/// Whether a provider of a type that needs an API key has none configured
+fn missing_api_key(provider_config: &gateway_config::ProviderConfig) -> bool {
+    let needs_key = matches!(
+        provider_config.provider_type,
+        ProviderType::OpenAI | ProviderType::Anthropic | ProviderType::Azure | ProviderType::Google
+    );
+    needs_key && provider_config.resolve_api_key().is_none()
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -127,6 +127,8 @@ pub fn sample_response(model: &str) -> GatewayResponse {
             prompt_tokens: 15,
             completion_tokens: 12,
             total_tokens: 27,
+            prompt_tokens_cached: None,
+            completion_tokens_reasoning: None,
         })
         .build()
 }